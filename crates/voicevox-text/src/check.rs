@@ -0,0 +1,64 @@
+use core::fmt;
+
+pub const MAX_TEXT_LENGTH: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextError {
+    Empty,
+    TooLong { length: usize },
+}
+
+impl fmt::Display for TextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str(
+                "No text provided. Use command line argument, -f file, or pipe text to stdin.",
+            ),
+            Self::TooLong { length } => write!(
+                f,
+                "Text too long: {length} characters (max: {MAX_TEXT_LENGTH})"
+            ),
+        }
+    }
+}
+
+#[must_use]
+pub fn text_char_count(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// Checks that `text` is speakable: not blank and within [`MAX_TEXT_LENGTH`] characters.
+///
+/// # Errors
+///
+/// Returns [`TextError`] describing the first violated rule.
+pub fn check_text(text: &str) -> Result<(), TextError> {
+    if text.trim().is_empty() {
+        return Err(TextError::Empty);
+    }
+    let length = text_char_count(text);
+    if length > MAX_TEXT_LENGTH {
+        return Err(TextError::TooLong { length });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+
+    #[test]
+    fn rejects_blank_and_overlong_text() {
+        assert_eq!(check_text(" \n\t "), Err(TextError::Empty));
+        assert_eq!(check_text("こんにちは"), Ok(()));
+
+        let long = String::from("あ").repeat(MAX_TEXT_LENGTH + 1);
+        assert_eq!(
+            check_text(&long),
+            Err(TextError::TooLong {
+                length: MAX_TEXT_LENGTH + 1
+            })
+        );
+    }
+}
@@ -0,0 +1,17 @@
+//! Text preprocessing shared by the CLI, the daemon and web frontends.
+//!
+//! The crate is `no_std` (it only needs `alloc`) so it builds for
+//! `wasm32-unknown-unknown`, letting a browser pre-chunk text exactly the way the
+//! CLI does before sending it to the daemon's HTTP API.
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod check;
+pub mod lint;
+pub mod segment;
+
+pub use check::{MAX_TEXT_LENGTH, TextError, check_text, text_char_count};
+pub use lint::{IssueKind, TextIssue, lint_text};
+pub use segment::{TextSegmenter, TextSplitter};
@@ -0,0 +1,198 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Runs of ASCII symbols at least this long are treated as ASCII art.
+pub const MIN_SYMBOL_RUN: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    /// A control character other than newline or tab.
+    ControlCharacter(char),
+    /// An ideograph outside the unified CJK block, usually missing from the dictionary.
+    RareKanji(char),
+    /// A private-use code point whose meaning depends on the font.
+    PrivateUse(char),
+    /// A pictograph or emoji, which the synthesizer skips or spells out.
+    Pictograph(char),
+    /// A long run of ASCII symbols or box drawing, typically ASCII art or a divider.
+    SymbolRun { length: usize },
+}
+
+impl IssueKind {
+    /// A short hint on how to rewrite the text.
+    #[must_use]
+    pub const fn suggestion(&self) -> &'static str {
+        match self {
+            Self::ControlCharacter(_) => "remove it",
+            Self::RareKanji(_) => "write the reading in kana",
+            Self::PrivateUse(_) => "replace it with standard text",
+            Self::Pictograph(_) => "describe it in words or remove it",
+            Self::SymbolRun { .. } => "remove decorative lines and ASCII art",
+        }
+    }
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ControlCharacter(ch) => write!(f, "control character U+{:04X}", u32::from(*ch)),
+            Self::RareKanji(ch) => write!(f, "rare kanji '{ch}' (U+{:04X})", u32::from(*ch)),
+            Self::PrivateUse(ch) => {
+                write!(f, "private-use character U+{:04X}", u32::from(*ch))
+            }
+            Self::Pictograph(ch) => write!(f, "pictograph '{ch}'"),
+            Self::SymbolRun { length } => write!(f, "run of {length} symbols"),
+        }
+    }
+}
+
+/// A character that is likely to synthesize poorly, located by 1-based line and column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextIssue {
+    pub line: usize,
+    pub column: usize,
+    pub kind: IssueKind,
+}
+
+impl fmt::Display for TextIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {} ({})",
+            self.line,
+            self.column,
+            self.kind,
+            self.kind.suggestion()
+        )
+    }
+}
+
+const fn is_rare_kanji(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{3400}'..='\u{4DBF}'      // CJK Extension A
+            | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+            | '\u{20000}'..='\u{3134F}' // CJK Extensions B-G
+    )
+}
+
+const fn is_private_use(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{E000}'..='\u{F8FF}' | '\u{F0000}'..='\u{FFFFD}' | '\u{100000}'..='\u{10FFFD}'
+    )
+}
+
+const fn is_pictograph(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{1F300}'..='\u{1FAFF}' | '\u{2600}'..='\u{27BF}' | '\u{1F000}'..='\u{1F2FF}'
+    )
+}
+
+const fn is_art_symbol(ch: char) -> bool {
+    ch.is_ascii_punctuation() || matches!(ch, '\u{2500}'..='\u{259F}')
+}
+
+fn single_char_issue(ch: char) -> Option<IssueKind> {
+    if ch.is_control() && ch != '\n' && ch != '\t' && ch != '\r' {
+        Some(IssueKind::ControlCharacter(ch))
+    } else if is_rare_kanji(ch) {
+        Some(IssueKind::RareKanji(ch))
+    } else if is_private_use(ch) {
+        Some(IssueKind::PrivateUse(ch))
+    } else if is_pictograph(ch) {
+        Some(IssueKind::Pictograph(ch))
+    } else {
+        None
+    }
+}
+
+/// Pending symbol run as (line, column, length).
+type SymbolRun = Option<(usize, usize, usize)>;
+
+fn flush_run(run: &mut SymbolRun, issues: &mut Vec<TextIssue>) {
+    if let Some((line, column, length)) = run.take()
+        && length >= MIN_SYMBOL_RUN
+    {
+        issues.push(TextIssue {
+            line,
+            column,
+            kind: IssueKind::SymbolRun { length },
+        });
+    }
+}
+
+/// Flags characters that are likely to synthesize poorly, in text order.
+///
+/// Symbol runs are reported once at their first character. Spaces inside a run
+/// do not break it, so spaced-out ASCII art is still caught.
+#[must_use]
+pub fn lint_text(text: &str) -> Vec<TextIssue> {
+    let mut issues = Vec::new();
+    let mut run: SymbolRun = None;
+    let (mut line, mut column) = (1, 0);
+
+    for ch in text.chars() {
+        column += 1;
+        if is_art_symbol(ch) {
+            match run.as_mut() {
+                Some((_, _, length)) => *length += 1,
+                None => run = Some((line, column, 1)),
+            }
+        } else if ch != ' ' {
+            flush_run(&mut run, &mut issues);
+        }
+        if let Some(kind) = single_char_issue(ch) {
+            issues.push(TextIssue { line, column, kind });
+        }
+        if ch == '\n' {
+            line += 1;
+            column = 0;
+        }
+    }
+    flush_run(&mut run, &mut issues);
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_problem_characters() {
+        let issues = lint_text("こんにちは\u{7}\n𠮷野家で🍣\n===== ===== ==");
+
+        assert_eq!(
+            issues,
+            [
+                TextIssue {
+                    line: 1,
+                    column: 6,
+                    kind: IssueKind::ControlCharacter('\u{7}'),
+                },
+                TextIssue {
+                    line: 2,
+                    column: 1,
+                    kind: IssueKind::RareKanji('𠮷'),
+                },
+                TextIssue {
+                    line: 2,
+                    column: 5,
+                    kind: IssueKind::Pictograph('🍣'),
+                },
+                TextIssue {
+                    line: 3,
+                    column: 1,
+                    kind: IssueKind::SymbolRun { length: 12 },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ordinary_japanese_and_short_punctuation_pass() {
+        assert!(lint_text("今日は良い天気ですね！……本当に？ (笑) 1,000円").is_empty());
+    }
+}
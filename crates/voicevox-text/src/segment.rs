@@ -1,3 +1,7 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
 #[derive(Debug, Clone)]
 pub struct TextSplitter {
     delimiters: Vec<char>,
@@ -54,7 +58,7 @@ impl TextSplitter {
 
             if self.is_delimiter(ch) {
                 self.consume_consecutive_delimiters(&mut chars, &mut current_segment);
-                segments.push(std::mem::take(&mut current_segment));
+                segments.push(core::mem::take(&mut current_segment));
                 current_len = 0;
             } else if current_len >= self.max_length {
                 current_len =
@@ -71,7 +75,7 @@ impl TextSplitter {
 
     fn consume_consecutive_delimiters(
         &self,
-        chars: &mut std::iter::Peekable<std::str::Chars>,
+        chars: &mut core::iter::Peekable<core::str::Chars>,
         current_segment: &mut String,
     ) {
         while let Some(&next_ch) = chars.peek() {
@@ -92,10 +96,10 @@ impl TextSplitter {
     ) -> usize {
         if let Some((break_pos, head_len)) = self.find_break_position(current_segment) {
             let rest = current_segment.split_off(break_pos);
-            segments.push(std::mem::replace(current_segment, rest));
+            segments.push(core::mem::replace(current_segment, rest));
             current_len.saturating_sub(head_len)
         } else {
-            segments.push(std::mem::take(current_segment));
+            segments.push(core::mem::take(current_segment));
             0
         }
     }
@@ -120,6 +124,8 @@ impl TextSegmenter for TextSplitter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::boxed::Box;
+    use alloc::string::ToString;
 
     struct FixedSegmenter;
 
@@ -0,0 +1,36 @@
+/* C API for daemon-backed VOICEVOX synthesis (build with `--features ffi`). */
+#ifndef VOICEVOX_CLI_H
+#define VOICEVOX_CLI_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define VOICEVOX_CLI_OK 0
+#define VOICEVOX_CLI_INVALID_ARGUMENT 1
+#define VOICEVOX_CLI_SYNTHESIS_FAILED 2
+
+/*
+ * Synthesizes UTF-8 `text` through the user daemon, starting it if needed.
+ * `socket_path` may be NULL to use the default socket. On success, `*out_wav`
+ * and `*out_len` receive WAV bytes that must be released with
+ * voicevox_cli_free_wav().
+ */
+int32_t voicevox_cli_synthesize(const char *text, uint32_t style_id, float rate,
+                                const char *socket_path, uint8_t **out_wav,
+                                size_t *out_len);
+
+/* Releases bytes returned by voicevox_cli_synthesize(). NULL is ignored. */
+void voicevox_cli_free_wav(uint8_t *wav, size_t len);
+
+/* Last error on the calling thread, or NULL. Valid until the next failure. */
+const char *voicevox_cli_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* VOICEVOX_CLI_H */
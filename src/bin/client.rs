@@ -1,22 +1,78 @@
-use anyhow::Result;
-use clap::{ArgGroup, Parser};
-use std::path::PathBuf;
+use anyhow::{Context, Result, anyhow};
+use clap::{ArgGroup, Parser, Subcommand};
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
+use std::time::Duration;
 
-use voicevox_cli::infrastructure::daemon::client::find_daemon_client_error;
-use voicevox_cli::infrastructure::ipc::DEFAULT_SYNTHESIS_RATE;
-use voicevox_cli::infrastructure::paths::get_socket_path;
+use voicevox_cli::config::{init_user_config, user_config};
+use voicevox_cli::domain::synthesis::breath::BreathPauses;
+use voicevox_cli::domain::synthesis::chime::{ChimeSpec, parse_chime_spec};
+use voicevox_cli::domain::synthesis::length_limit::{LengthLimit, OverlongPolicy};
+use voicevox_cli::domain::synthesis::options::{
+    DEFAULT_INTONATION, DEFAULT_PHONEME_PAUSE, DEFAULT_VOLUME,
+};
+use voicevox_cli::domain::synthesis::postprocess::{AudioEffects, Fade, parse_transpose};
+use voicevox_cli::domain::synthesis::timing::parse_duration_spec;
+use voicevox_cli::domain::template::{collect_template_vars, render_template};
+use voicevox_cli::infrastructure::daemon::client::{find_daemon_client_error, set_remote_daemon};
+use voicevox_cli::infrastructure::ipc::{
+    DEFAULT_SYNTHESIS_PITCH, DEFAULT_SYNTHESIS_RATE, SynthesisOptions,
+};
+use voicevox_cli::infrastructure::paths::{
+    get_socket_path, hooks_config_path, user_dict_path, voice_aliases_path,
+};
 use voicevox_cli::interface::StdAppOutput;
+use voicevox_cli::interface::audio::{
+    AudioBackend, OutputFormat, init_audio_backend, init_audio_output, parse_volume,
+};
+use voicevox_cli::interface::cli::alias::{run_alias_add, run_alias_list, run_alias_remove};
+use voicevox_cli::interface::cli::align::{AlignRequest, run_align_srt};
+use voicevox_cli::interface::cli::backup::{
+    BackupCreateRequest, BackupRestoreRequest, run_backup_create, run_backup_restore,
+};
+use voicevox_cli::interface::cli::batch::{BatchRequest, run_batch};
+use voicevox_cli::interface::cli::capabilities::run_capabilities;
 use voicevox_cli::interface::cli::daemon_error::{
     daemon_client_exit_code, format_daemon_client_error_for_cli,
 };
+use voicevox_cli::interface::cli::dialogue::{DialogueRequest, run_dialogue};
+use voicevox_cli::interface::cli::dict::{
+    DictAddRequest, run_dict_add, run_dict_list, run_dict_remove,
+};
+use voicevox_cli::interface::cli::events::run_watch_events;
+use voicevox_cli::interface::cli::greeting::{greet_if_first_today, speak_greeting};
+use voicevox_cli::interface::cli::import_editor::{
+    ImportEditorSettingsRequest, run_import_editor_settings,
+};
 use voicevox_cli::interface::cli::input::get_input_text_from_sources;
 use voicevox_cli::interface::cli::inspect::{
-    run_list_models_command, run_list_speakers_command, run_status_command,
+    run_list_audio_devices_command, run_list_models_command, run_list_speakers_command,
+    run_stats_command, run_status_command, run_suggest_similar_command,
+};
+use voicevox_cli::interface::cli::inspect_wav::{InspectWavRequest, run_inspect_wav};
+use voicevox_cli::interface::cli::listen::{ListenRequest, run_listen_mode};
+use voicevox_cli::interface::cli::pipeline::{PipelineRequest, run_pipeline};
+use voicevox_cli::interface::cli::progress::{
+    ProgressFormat, ProgressReadingRequest, run_progress_reading,
+};
+use voicevox_cli::interface::cli::queue::{
+    QueueAddRequest, QueueRunRequest, run_queue_add, run_queue_clear, run_queue_list, run_queue_run,
+};
+use voicevox_cli::interface::cli::regression::{
+    RegressionAction, RegressionRequest, run_regression,
 };
-use voicevox_cli::interface::cli::say::{SaySynthesisRequest, run_say_synthesis};
+use voicevox_cli::interface::cli::say::{SaySynthesisRequest, load_chimes, run_say_synthesis};
+use voicevox_cli::interface::cli::serve_stdio::{ServeStdioRequest, run_serve_stdio};
 use voicevox_cli::interface::cli::voice_help::print_voice_help;
-use voicevox_cli::interface::cli::voice_selector::resolve_voice_input;
+use voicevox_cli::interface::cli::voice_pack::{InstallPackRequest, run_install_pack};
+use voicevox_cli::interface::cli::voice_selector::{resolve_speaker_uuid, resolve_voice_input};
+use voicevox_cli::interface::cli::vvproj::{VvprojExportRequest, run_export_vvproj};
+use voicevox_cli::interface::hooks::load_hooks_config;
+use voicevox_cli::interface::interrupt::{
+    EXIT_CODE_INTERRUPTED, install_interrupt_handler, is_interrupted, restore_terminal,
+};
+use voicevox_cli::interface::latency::{self, Stage};
+use voicevox_cli::interface::tee::TeeTarget;
 
 // Clap option flags are intentionally represented as booleans.
 #[allow(clippy::struct_excessive_bools)]
@@ -27,7 +83,7 @@ use voicevox_cli::interface::cli::voice_selector::resolve_voice_input;
     about = "VOICEVOX Say - Convert text to audible speech using VOICEVOX",
     group(
         ArgGroup::new("meta_command")
-            .args(["list_speakers", "list_models", "status"])
+            .args(["list_speakers", "list_models", "status", "suggest_similar", "capabilities", "stats", "list_audio_devices", "greet"])
             .multiple(false)
     )
 )]
@@ -40,7 +96,7 @@ struct CliArgs {
         short = 'v',
         value_name = "VOICE",
         help = "Specify the voice to be used. Use '?' to list all available voices",
-        conflicts_with_all = ["speaker_id", "model"]
+        conflicts_with_all = ["speaker_id", "model", "speaker_uuid"]
     )]
     voice: Option<String>,
 
@@ -48,40 +104,286 @@ struct CliArgs {
         long,
         short = 'r',
         value_name = "RATE",
-        default_value_t = DEFAULT_SYNTHESIS_RATE,
-        help = "Speech rate multiplier (0.5-2.0, default: 1.0)"
+        help = "Speech rate multiplier (0.5-2.0, default: 1.0 or rate in config.toml)"
+    )]
+    rate: Option<f32>,
+
+    #[arg(
+        long,
+        value_name = "PITCH",
+        default_value_t = DEFAULT_SYNTHESIS_PITCH,
+        allow_negative_numbers = true,
+        help = "VOICEVOX pitch offset (-0.15-0.15, default: 0.0)"
     )]
-    rate: f32,
+    pitch: f32,
+
+    #[arg(
+        long,
+        value_name = "SCALE",
+        default_value_t = DEFAULT_INTONATION,
+        help = "Intonation scale; 0.0 is monotone (0.0-2.0, default: 1.0)"
+    )]
+    intonation: f32,
+
+    #[arg(
+        long,
+        value_name = "SCALE",
+        default_value_t = DEFAULT_VOLUME,
+        help = "Volume scale (0.0-2.0, default: 1.0)"
+    )]
+    volume: f32,
+
+    #[arg(
+        long = "pre-phoneme-length",
+        value_name = "SECONDS",
+        default_value_t = DEFAULT_PHONEME_PAUSE,
+        help = "Silence before the speech (0.0-1.5, default: 0.1)"
+    )]
+    pre_phoneme_length: f32,
+
+    #[arg(
+        long = "post-phoneme-length",
+        value_name = "SECONDS",
+        default_value_t = DEFAULT_PHONEME_PAUSE,
+        help = "Silence after the speech (0.0-1.5, default: 0.1)"
+    )]
+    post_phoneme_length: f32,
 
     #[arg(long = "output-file", short = 'o', value_name = "FILE")]
     output_file: Option<PathBuf>,
 
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        value_parser = OutputFormat::parse,
+        requires = "output_file",
+        help = "Encoding of -o: wav, flac, opus, or mp3 (default: from the file extension, else wav)"
+    )]
+    format: Option<OutputFormat>,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Write when each mora starts and ends in the audio to FILE as JSON, for lip-sync or subtitles",
+        conflicts_with_all = ["listen_fifo", "dialogue", "batch", "align_srt", "serve_stdio", "meta_command"]
+    )]
+    timings: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        default_missing_value = "text",
+        value_parser = ProgressFormat::parse,
+        help = "Play long text sentence by sentence, showing the sentence playing, percent done, and time left (text, or json for event lines)",
+        conflicts_with_all = ["output_file", "quiet", "timings", "tee", "chime", "transpose", "fade_in_ms", "fade_out_ms", "fit_duration", "max_duration", "markup", "breath_pauses", "auto_emotion", "listen_fifo", "dialogue", "batch", "align_srt", "export_vvproj", "serve_stdio", "pipeline", "meta_command"]
+    )]
+    progress: Option<ProgressFormat>,
+
+    #[arg(
+        long,
+        value_name = "BACKEND",
+        value_parser = AudioBackend::parse,
+        help = "Audio output: device (default) or null to never open a device"
+    )]
+    audio: Option<AudioBackend>,
+
+    #[arg(
+        long = "audio-device",
+        value_name = "NAME",
+        help = "Play on this output device instead of the system default (see --list-audio-devices)"
+    )]
+    audio_device: Option<String>,
+
+    #[arg(
+        long = "playback-volume",
+        value_name = "LEVEL",
+        value_parser = parse_volume,
+        help = "Playback volume from 0.0 to 1.0; unlike --volume, files written with -o are unchanged"
+    )]
+    playback_volume: Option<f32>,
+
+    #[arg(
+        long = "list-audio-devices",
+        help = "List the audio output devices --audio-device accepts and exit"
+    )]
+    list_audio_devices: bool,
+
+    #[arg(
+        long,
+        help = "Say the [greeting] from config.toml (which voice and how many models are active) and exit"
+    )]
+    greet: bool,
+
+    #[arg(
+        long = "fade-in-ms",
+        value_name = "MS",
+        default_value_t = 0,
+        help = "Fade the start of the audio in over MS milliseconds"
+    )]
+    fade_in_ms: u32,
+
+    #[arg(
+        long = "fade-out-ms",
+        value_name = "MS",
+        default_value_t = 0,
+        help = "Fade the end of the audio out over MS milliseconds"
+    )]
+    fade_out_ms: u32,
+
+    #[arg(
+        long,
+        value_name = "START,END",
+        value_parser = parse_chime_spec,
+        help = "Play earcons before and after the speech, e.g. start.wav,end.wav (either may be empty)"
+    )]
+    chime: Option<ChimeSpec>,
+
+    #[arg(
+        long,
+        value_name = "SEMITONES",
+        allow_hyphen_values = true,
+        value_parser = parse_transpose,
+        help = "Shift pitch by semitones while keeping formants (e.g. +2st, -3st)"
+    )]
+    transpose: Option<f32>,
+
+    #[arg(
+        long = "fit-duration",
+        value_name = "DURATION",
+        value_parser = parse_duration_spec,
+        help = "Adjust the speech rate (0.5-2.0) so the audio lasts DURATION (e.g. 30s, 1500ms)"
+    )]
+    fit_duration: Option<Duration>,
+
+    #[arg(
+        long = "max-duration",
+        value_name = "DURATION",
+        value_parser = parse_duration_spec,
+        help = "Stop runaway input: cap the spoken audio at DURATION (e.g. 60s, 2m)"
+    )]
+    max_duration: Option<Duration>,
+
+    #[arg(
+        long = "on-overlong",
+        value_name = "POLICY",
+        requires = "max_duration",
+        default_value = "error",
+        value_parser = OverlongPolicy::parse,
+        help = "When text exceeds --max-duration: error (default) or truncate"
+    )]
+    on_overlong: OverlongPolicy,
+
+    #[arg(
+        long,
+        visible_alias = "ssml",
+        help = "Read the text as markup: <break time=\"500ms\"/>, <prosody rate=\"1.2\" pitch=\"+2st\">, <voice id=\"3\">, <emphasis>",
+        conflicts_with_all = ["max_duration", "listen_fifo", "dialogue", "batch", "align_srt", "serve_stdio", "meta_command"]
+    )]
+    markup: bool,
+
+    #[arg(
+        long = "breath-pauses",
+        value_name = "RANGES",
+        num_args = 0..=1,
+        default_missing_value = "",
+        value_parser = BreathPauses::parse,
+        help = "Pause briefly at commas and clause ends (default comma=80ms-200ms,clause=250ms-500ms)"
+    )]
+    breath_pauses: Option<BreathPauses>,
+
+    #[arg(
+        long = "auto-emotion",
+        help = "Read each sentence in the voice's happy or sad style when its wording, emoji, or punctuation suggest one"
+    )]
+    auto_emotion: bool,
+
+    #[arg(
+        long,
+        value_name = "URL",
+        value_parser = TeeTarget::parse,
+        help = "Also send the audio to udp://HOST:PORT (raw PCM, real time) or http://HOST/PATH (PUT)",
+        conflicts_with_all = ["listen_fifo", "dialogue", "batch", "align_srt", "serve_stdio", "meta_command"]
+    )]
+    tee: Option<TeeTarget>,
+
     #[arg(long = "input-file", short = 'f', value_name = "FILE")]
     input_file: Option<String>,
 
+    #[arg(
+        long,
+        value_name = "TEMPLATE",
+        help = "Speak a template with {{name}} placeholders filled from --var",
+        conflicts_with_all = ["text", "input_file", "listen_fifo", "dialogue", "batch", "align_srt", "serve_stdio", "meta_command"]
+    )]
+    template: Option<String>,
+
+    #[arg(
+        long = "var",
+        value_name = "NAME=VALUE",
+        requires = "template",
+        help = "Set a template variable (repeatable)"
+    )]
+    vars: Vec<String>,
+
+    #[arg(
+        long = "reading",
+        value_name = "NAME=READING",
+        requires = "template",
+        help = "Speak READING instead of a variable's value, e.g. --reading name=たなか (repeatable)"
+    )]
+    readings: Vec<String>,
+
     #[arg(long, short = 'q', help = "Don't play audio, only save to file")]
     quiet: bool,
 
+    #[arg(
+        long,
+        help = "Print how long each stage of the request took, from voice resolution to playback start"
+    )]
+    verbose: bool,
+
+    #[arg(
+        long,
+        help = "Fail instead of warning when the text has characters likely to synthesize poorly"
+    )]
+    strict: bool,
+
     #[arg(
         long = "list-speakers",
         help = "List all available speakers and styles"
     )]
     list_speakers: bool,
 
+    #[arg(
+        long = "suggest-similar",
+        value_name = "N",
+        help = "Suggest N installed styles similar to the selected voice, then exit"
+    )]
+    suggest_similar: Option<usize>,
+
     #[arg(
         long = "speaker-id",
         value_name = "ID",
         help = "Directly specify speaker style ID (advanced users)",
-        conflicts_with_all = ["voice", "model"]
+        conflicts_with_all = ["voice", "model", "speaker_uuid"]
     )]
     speaker_id: Option<u32>,
 
+    #[arg(
+        long = "speaker-uuid",
+        value_name = "UUID[:STYLE]",
+        help = "Select a voice by speaker UUID and optional style name; stable across model renumbering",
+        conflicts_with_all = ["voice", "speaker_id", "model"]
+    )]
+    speaker_uuid: Option<String>,
+
     #[arg(
         long,
         short = 'm',
         value_name = "MODEL_ID",
         help = "Specify voice model by file number (e.g., --model 3 for 3.vvm)",
-        conflicts_with_all = ["voice", "speaker_id"]
+        conflicts_with_all = ["voice", "speaker_id", "speaker_uuid"]
     )]
     model: Option<u32>,
 
@@ -94,8 +396,344 @@ struct CliArgs {
     #[arg(long, help = "Show installation status of voice models and dictionary")]
     status: bool,
 
+    #[arg(
+        long,
+        help = "Show the formats, protocols, and features this build supports, then exit"
+    )]
+    capabilities: bool,
+
+    #[arg(
+        long,
+        help = "Print --list-speakers, --list-models, --list-audio-devices, --status, and --capabilities as JSON"
+    )]
+    json: bool,
+
+    #[arg(
+        long,
+        help = "Show local usage statistics recorded by the daemon, then exit"
+    )]
+    stats: bool,
+
     #[arg(long = "socket-path", short = 'S', value_name = "PATH")]
     socket_path: Option<PathBuf>,
+
+    #[arg(
+        long = "connect",
+        value_name = "HOST:PORT",
+        conflicts_with = "socket_path",
+        help = "Use the daemon started with --listen on another machine (token from VOICEVOX_DAEMON_TOKEN or ~/.config/voicevox/daemon-token)"
+    )]
+    connect: Option<String>,
+
+    #[arg(
+        long = "listen-fifo",
+        value_name = "PATH",
+        help = "Speak each line written to a FIFO, interrupting the previous utterance",
+        conflicts_with_all = ["text", "input_file", "output_file", "quiet", "meta_command"]
+    )]
+    listen_fifo: Option<PathBuf>,
+
+    #[arg(
+        long = "dialogue",
+        value_name = "SCRIPT",
+        num_args = 1..,
+        help = "Speak dialogue scripts ('Name: text' lines with optional casting front-matter)",
+        conflicts_with_all = ["text", "input_file", "listen_fifo", "meta_command"]
+    )]
+    dialogue: Vec<PathBuf>,
+
+    #[arg(
+        long = "batch",
+        value_name = "FILE",
+        help = "Render a CSV/TSV batch (columns: text, output, optional voice and rate); - reads stdin",
+        conflicts_with_all = ["text", "input_file", "output_file", "listen_fifo", "dialogue", "meta_command"]
+    )]
+    batch: Option<PathBuf>,
+
+    #[arg(
+        long = "batch-dir",
+        value_name = "DIR",
+        requires = "batch",
+        help = "Read the batch as one utterance per line (optionally VOICE<TAB>TEXT) and write numbered WAVs into DIR"
+    )]
+    batch_dir: Option<PathBuf>,
+
+    #[arg(
+        long = "jobs",
+        short = 'j',
+        value_name = "N",
+        default_value_t = 1,
+        requires = "batch",
+        help = "Render up to N batch rows at once over separate daemon connections (max 8)"
+    )]
+    jobs: usize,
+
+    #[arg(
+        long = "batch-report",
+        value_name = "FILE",
+        requires = "batch",
+        help = "Where to write the JSON batch report (default: <batch>.report.json)"
+    )]
+    batch_report: Option<PathBuf>,
+
+    #[arg(
+        long = "anki-export",
+        value_name = "DIR",
+        requires = "batch",
+        help = "Also write an Anki import (notes.txt plus media/) of the rendered batch into DIR"
+    )]
+    anki_export: Option<PathBuf>,
+
+    #[arg(
+        long = "m4b",
+        value_name = "FILE",
+        requires = "batch",
+        help = "Also join the rendered batch into one M4B audiobook with a chapter per row (or per 'chapter' column title)"
+    )]
+    m4b: Option<PathBuf>,
+
+    #[arg(
+        long = "m4b-title",
+        value_name = "TITLE",
+        requires = "m4b",
+        help = "Audiobook title for --m4b (default: the batch file's name)"
+    )]
+    m4b_title: Option<String>,
+
+    #[arg(
+        long = "m4b-author",
+        value_name = "NAME",
+        requires = "m4b",
+        help = "Audiobook author for --m4b"
+    )]
+    m4b_author: Option<String>,
+
+    #[arg(
+        long = "verify",
+        requires = "batch",
+        conflicts_with_all = ["anki_export", "m4b"],
+        help = "Check batch outputs against <batch>.sha256 instead of rendering; lists files to regenerate"
+    )]
+    verify: bool,
+
+    #[arg(
+        long = "align-srt",
+        value_name = "SRT",
+        requires = "output_file",
+        help = "Render SRT cues into one track aligned to their timecodes (for dubbing)",
+        conflicts_with_all = ["text", "input_file", "listen_fifo", "dialogue", "batch", "meta_command"]
+    )]
+    align_srt: Option<PathBuf>,
+
+    #[arg(
+        long = "export-vvproj",
+        value_name = "FILE",
+        help = "Write the text as a VOICEVOX editor project (.vvproj), one editor line per input line, instead of speaking it",
+        conflicts_with_all = ["output_file", "listen_fifo", "dialogue", "batch", "align_srt", "serve_stdio", "meta_command"]
+    )]
+    export_vvproj: Option<PathBuf>,
+
+    #[arg(
+        long = "regression",
+        num_args = 2,
+        value_names = ["ACTION", "DIR"],
+        help = "Record a voice regression baseline into DIR, or check fresh synthesis against it (ACTION: record or check)",
+        conflicts_with_all = ["text", "input_file", "output_file", "listen_fifo", "dialogue", "batch", "align_srt", "export_vvproj", "serve_stdio", "meta_command"]
+    )]
+    regression: Vec<String>,
+
+    #[arg(
+        long = "serve-stdio",
+        help = "Serve JSON-lines synthesis requests on stdin, answering with base64 WAV on stdout",
+        conflicts_with_all = ["text", "input_file", "output_file", "listen_fifo", "dialogue", "batch", "align_srt", "meta_command"]
+    )]
+    serve_stdio: bool,
+
+    #[arg(
+        long = "pipeline",
+        help = "Run JSON jobs ({text, voice, rate, output}) from stdin lines in order, printing a JSON result line for each",
+        conflicts_with_all = ["text", "input_file", "output_file", "listen_fifo", "dialogue", "batch", "align_srt", "serve_stdio", "meta_command"]
+    )]
+    pipeline: bool,
+
+    #[command(subcommand)]
+    command: Option<ClientCommand>,
+}
+
+#[derive(Debug, Subcommand)]
+enum ClientCommand {
+    /// Manage the persistent render queue
+    Queue {
+        #[command(subcommand)]
+        action: QueueAction,
+    },
+    /// Stream daemon activity (model loads, synthesis start/finish) until interrupted
+    Events,
+    /// Import presets and the user dictionary from the official VOICEVOX editor
+    ImportEditorSettings {
+        #[arg(
+            long = "editor-config",
+            value_name = "FILE",
+            help = "Editor config.json to read instead of the default location"
+        )]
+        editor_config: Option<PathBuf>,
+
+        #[arg(
+            long = "user-dict",
+            value_name = "FILE",
+            help = "Engine user_dict.json to read instead of the default location"
+        )]
+        user_dict: Option<PathBuf>,
+    },
+    /// Manage the user dictionary of pronunciation overrides
+    Dict {
+        #[command(subcommand)]
+        action: DictAction,
+    },
+    /// Manage your own names for voices, usable anywhere a voice is accepted
+    Alias {
+        #[command(subcommand)]
+        action: AliasAction,
+    },
+    /// Install a voice pack (zip of VVM models, manifest.toml, and license)
+    InstallPack {
+        #[arg(value_name = "PACK", help = "Voice pack zip to install")]
+        pack: PathBuf,
+
+        #[arg(long, help = "Replace models that another pack, or no pack, installed")]
+        force: bool,
+    },
+    /// Back up or restore configuration, dictionary, and state (optionally models)
+    Backup {
+        #[command(subcommand)]
+        action: BackupAction,
+    },
+    /// Print a produced WAV's format, duration, and levels, and check it against a batch report
+    Inspect {
+        #[arg(value_name = "WAV", help = "WAV file to inspect")]
+        wav: PathBuf,
+
+        #[arg(
+            long,
+            value_name = "REPORT",
+            help = "Batch report (<batch>.report.json) to validate the WAV against"
+        )]
+        manifest: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum AliasAction {
+    /// Name a voice, replacing any alias with the same name
+    Add {
+        #[arg(value_name = "ALIAS", help = "New name, such as zunda")]
+        alias: String,
+
+        #[arg(
+            value_name = "VOICE",
+            help = "Style ID, voice name, or <speaker-uuid>/<style> it stands for"
+        )]
+        voice: String,
+    },
+    /// List defined aliases
+    List,
+    /// Remove an alias
+    Remove {
+        #[arg(value_name = "ALIAS")]
+        alias: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum DictAction {
+    /// Register a word, replacing any word with the same surface
+    Add {
+        #[arg(value_name = "SURFACE", help = "Word as written")]
+        surface: String,
+
+        #[arg(value_name = "PRONUNCIATION", help = "Reading in katakana")]
+        pronunciation: String,
+
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = 0,
+            help = "Mora after which the pitch drops (0 for a flat accent)"
+        )]
+        accent: usize,
+    },
+    /// List registered words
+    List,
+    /// Remove a registered word
+    Remove {
+        #[arg(value_name = "SURFACE")]
+        surface: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum BackupAction {
+    /// Write a snapshot archive, compressed by its suffix (e.g. snapshot.tar.zst)
+    Create {
+        #[arg(value_name = "SNAPSHOT")]
+        snapshot: PathBuf,
+
+        #[arg(long = "include-models", help = "Also store installed VVM models")]
+        include_models: bool,
+    },
+    /// Restore the files in a snapshot over the current ones
+    Restore {
+        #[arg(value_name = "SNAPSHOT")]
+        snapshot: PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum QueueAction {
+    /// Add a render job to the queue
+    Add {
+        #[arg(help = "Text to render (reads -f or stdin when omitted)")]
+        text: Option<String>,
+
+        #[arg(long = "output-file", short = 'o', value_name = "FILE")]
+        output_file: PathBuf,
+
+        #[arg(long = "input-file", short = 'f', value_name = "FILE")]
+        input_file: Option<String>,
+
+        #[arg(long, short = 'v', value_name = "VOICE", conflicts_with = "speaker_id")]
+        voice: Option<String>,
+
+        #[arg(long = "speaker-id", value_name = "ID")]
+        speaker_id: Option<u32>,
+
+        #[arg(long, short = 'r', value_name = "RATE")]
+        rate: Option<f32>,
+    },
+    /// Render pending jobs
+    Run {
+        #[arg(
+            long,
+            short = 'j',
+            value_name = "N",
+            default_value_t = 1,
+            help = "Number of concurrent daemon connections"
+        )]
+        jobs: usize,
+
+        #[arg(long = "retry-failed", help = "Requeue failed jobs before running")]
+        retry_failed: bool,
+
+        #[arg(long, short = 'q')]
+        quiet: bool,
+    },
+    /// List queued jobs
+    List,
+    /// Remove finished jobs from the queue
+    Clear {
+        #[arg(long, help = "Remove every job, including pending and failed ones")]
+        all: bool,
+    },
 }
 
 impl CliArgs {
@@ -103,6 +741,10 @@ impl CliArgs {
         self.socket_path.clone().unwrap_or_else(get_socket_path)
     }
 
+    fn rate(&self) -> f32 {
+        self.rate.unwrap_or_else(default_rate)
+    }
+
     fn wants_voice_help(&self) -> bool {
         self.voice.as_deref() == Some("?")
     }
@@ -114,8 +756,16 @@ impl CliArgs {
             Some(MetaCommand::Status)
         } else if self.list_speakers {
             Some(MetaCommand::ListSpeakers)
+        } else if self.capabilities {
+            Some(MetaCommand::Capabilities)
+        } else if self.stats {
+            Some(MetaCommand::Stats)
+        } else if self.list_audio_devices {
+            Some(MetaCommand::ListAudioDevices)
+        } else if self.greet {
+            Some(MetaCommand::Greet)
         } else {
-            None
+            self.suggest_similar.map(MetaCommand::SuggestSimilar)
         }
     }
 }
@@ -130,22 +780,34 @@ fn handle_voice_help_request(args: &CliArgs) -> bool {
 
 const DEFAULT_STYLE_ID: u32 = 3;
 
-const fn default_voice_selection() -> u32 {
-    DEFAULT_STYLE_ID
+/// The voice from `config.toml` (or `VOICEVOX_VOICE`), else style 3.
+fn default_voice_selection() -> Result<u32> {
+    user_config()
+        .voice
+        .as_deref()
+        .map_or(Ok(DEFAULT_STYLE_ID), |voice| {
+            resolve_voice_input(voice)
+                .map(|(style_id, _description)| style_id)
+                .context("Invalid voice in config.toml")
+        })
+}
+
+fn default_rate() -> f32 {
+    user_config().rate.unwrap_or(DEFAULT_SYNTHESIS_RATE)
 }
 
 async fn handle_list_models_command(args: &CliArgs) -> Result<bool> {
-    run_list_models_command(&args.socket_path()).await?;
+    run_list_models_command(&args.socket_path(), args.json).await?;
     Ok(true)
 }
 
-fn handle_status_command() -> bool {
-    run_status_command();
-    true
+async fn handle_status_command(args: &CliArgs) -> Result<bool> {
+    run_status_command(&args.socket_path(), args.json).await?;
+    Ok(true)
 }
 
 async fn handle_list_speakers_command(args: &CliArgs) -> Result<bool> {
-    run_list_speakers_command(&args.socket_path()).await?;
+    run_list_speakers_command(&args.socket_path(), args.json).await?;
     Ok(true)
 }
 
@@ -153,12 +815,18 @@ enum MetaCommand {
     ListModels,
     Status,
     ListSpeakers,
+    SuggestSimilar(usize),
+    Capabilities,
+    Stats,
+    ListAudioDevices,
+    Greet,
 }
 
 enum VoiceSelection<'a> {
     SpeakerId(u32),
     ModelId(u32),
     VoiceName(&'a str),
+    SpeakerUuid(&'a str),
     Default,
 }
 
@@ -168,6 +836,8 @@ impl<'a> VoiceSelection<'a> {
             Self::SpeakerId(id)
         } else if let Some(id) = args.model {
             Self::ModelId(id)
+        } else if let Some(reference) = args.speaker_uuid.as_deref() {
+            Self::SpeakerUuid(reference)
         } else if let Some(voice_name) = args.voice.as_deref() {
             Self::VoiceName(voice_name)
         } else {
@@ -179,20 +849,115 @@ impl<'a> VoiceSelection<'a> {
 async fn maybe_handle_meta_commands(args: &CliArgs) -> Result<bool> {
     match args.selected_meta_command() {
         Some(MetaCommand::ListModels) => handle_list_models_command(args).await,
-        Some(MetaCommand::Status) => Ok(handle_status_command()),
+        Some(MetaCommand::Status) => handle_status_command(args).await,
         Some(MetaCommand::ListSpeakers) => handle_list_speakers_command(args).await,
+        Some(MetaCommand::Capabilities) => {
+            run_capabilities(args.json, &StdAppOutput)?;
+            Ok(true)
+        }
+        Some(MetaCommand::Stats) => {
+            run_stats_command()?;
+            Ok(true)
+        }
+        Some(MetaCommand::ListAudioDevices) => {
+            run_list_audio_devices_command(args.json, &StdAppOutput)?;
+            Ok(true)
+        }
+        Some(MetaCommand::Greet) => {
+            let style_id = resolve_voice_from_args(args)?;
+            let greeting = user_config().greeting.clone().unwrap_or_default();
+            speak_greeting(&args.socket_path(), style_id, &greeting, &StdAppOutput).await?;
+            Ok(true)
+        }
+        Some(MetaCommand::SuggestSimilar(count)) => {
+            let style_id = resolve_voice_from_args(args)?;
+            run_suggest_similar_command(&args.socket_path(), style_id, count).await?;
+            Ok(true)
+        }
         None => Ok(false),
     }
 }
 
+fn synthesis_text_from_args(args: &CliArgs) -> Result<String> {
+    match args.template.as_deref() {
+        Some(template) => {
+            let vars = collect_template_vars(&args.vars, &args.readings)?;
+            render_template(template, &vars)
+        }
+        None => get_input_text_from_sources(args.text.as_deref(), args.input_file.as_deref()),
+    }
+}
+
 async fn run_synthesis_command(args: &CliArgs) -> Result<()> {
-    let text = get_input_text_from_sources(args.text.as_deref(), args.input_file.as_deref())?;
+    if args.verbose {
+        latency::enable();
+    }
+    let result = synthesize_from_args(args).await;
+    latency::print_report(&StdAppOutput);
+    result
+}
+
+async fn synthesize_from_args(args: &CliArgs) -> Result<()> {
+    let text = synthesis_text_from_args(args)?;
     let style_id = resolve_voice_from_args(args)?;
+    if !args.quiet && args.output_file.is_none() {
+        greet_if_first_today(&args.socket_path(), style_id, &StdAppOutput).await;
+    }
+    let options = SynthesisOptions {
+        rate: args.rate(),
+        pitch: args.pitch,
+        intonation: args.intonation,
+        volume: args.volume,
+        pre_phoneme_length: args.pre_phoneme_length,
+        post_phoneme_length: args.post_phoneme_length,
+        ..SynthesisOptions::default()
+    };
+    if let Some(format) = args.progress {
+        return run_progress_reading(
+            ProgressReadingRequest {
+                text: &text,
+                style_id,
+                options,
+                format,
+                socket_path: &args.socket_path(),
+            },
+            &StdAppOutput,
+        )
+        .await;
+    }
+    let chimes = args
+        .chime
+        .as_ref()
+        .map(load_chimes)
+        .transpose()?
+        .unwrap_or_default();
+    let hooks = load_hooks_config(&hooks_config_path())?;
     run_say_synthesis(SaySynthesisRequest {
         text: &text,
         style_id,
-        rate: args.rate,
+        options,
+        effects: AudioEffects {
+            transpose_semitones: args.transpose.unwrap_or_default(),
+            fade: Fade {
+                fade_in_ms: args.fade_in_ms,
+                fade_out_ms: args.fade_out_ms,
+            },
+        },
+        chimes: &chimes,
+        hooks: &hooks,
+        fit_duration: args.fit_duration,
+        length_limit: args.max_duration.map(|max| LengthLimit {
+            max,
+            policy: args.on_overlong,
+        }),
         output_file: args.output_file.as_deref(),
+        output_format: OutputFormat::resolve(args.format, args.output_file.as_deref()),
+        timings_file: args.timings.as_deref(),
+        tee: args.tee.as_ref(),
+        markup: args.markup,
+        breath_pauses: args.breath_pauses,
+        auto_emotion: args.auto_emotion,
+        strict: args.strict,
         quiet: args.quiet,
         socket_path: args.socket_path(),
     })
@@ -200,25 +965,291 @@ async fn run_synthesis_command(args: &CliArgs) -> Result<()> {
 }
 
 fn resolve_voice_from_args(args: &CliArgs) -> Result<u32> {
+    let _span = latency::span(Stage::VoiceResolution);
     match VoiceSelection::from_args(args) {
         VoiceSelection::SpeakerId(id) | VoiceSelection::ModelId(id) => Ok(id),
         VoiceSelection::VoiceName(voice_name) => {
             resolve_voice_input(voice_name).map(|(style_id, _description)| style_id)
         }
-        VoiceSelection::Default => Ok(default_voice_selection()),
+        VoiceSelection::SpeakerUuid(reference) => {
+            resolve_speaker_uuid(reference).map(|(style_id, _description)| style_id)
+        }
+        VoiceSelection::Default => default_voice_selection(),
+    }
+}
+
+fn run_backup_action(action: &BackupAction) -> Result<()> {
+    match action {
+        BackupAction::Create {
+            snapshot,
+            include_models,
+        } => run_backup_create(&BackupCreateRequest {
+            snapshot,
+            include_models: *include_models,
+        }),
+        BackupAction::Restore { snapshot } => {
+            run_backup_restore(&BackupRestoreRequest { snapshot })
+        }
+    }
+}
+
+fn run_alias_action(action: &AliasAction) -> Result<()> {
+    let aliases_path = voice_aliases_path();
+    match action {
+        AliasAction::Add { alias, voice } => {
+            run_alias_add(alias, voice, &aliases_path, &StdAppOutput)
+        }
+        AliasAction::List => run_alias_list(&aliases_path, &StdAppOutput),
+        AliasAction::Remove { alias } => run_alias_remove(alias, &aliases_path, &StdAppOutput),
+    }
+}
+
+async fn run_dict_action(args: &CliArgs, action: &DictAction) -> Result<()> {
+    let dict_path = user_dict_path();
+    match action {
+        DictAction::Add {
+            surface,
+            pronunciation,
+            accent,
+        } => {
+            run_dict_add(
+                &DictAddRequest {
+                    surface,
+                    pronunciation,
+                    accent_type: *accent,
+                },
+                &dict_path,
+                &args.socket_path(),
+                &StdAppOutput,
+            )
+            .await
+        }
+        DictAction::List => run_dict_list(&dict_path, &StdAppOutput),
+        DictAction::Remove { surface } => {
+            run_dict_remove(surface, &dict_path, &args.socket_path(), &StdAppOutput).await
+        }
+    }
+}
+
+async fn run_queue_command(args: &CliArgs, action: &QueueAction) -> Result<()> {
+    match action {
+        QueueAction::Add {
+            text,
+            output_file,
+            input_file,
+            voice,
+            speaker_id,
+            rate,
+        } => {
+            let text = get_input_text_from_sources(text.as_deref(), input_file.as_deref())?;
+            let style_id = match (speaker_id, voice.as_deref()) {
+                (Some(id), _) => *id,
+                (None, Some(voice_name)) => resolve_voice_input(voice_name)?.0,
+                (None, None) => default_voice_selection()?,
+            };
+            run_queue_add(
+                QueueAddRequest {
+                    text,
+                    style_id,
+                    rate: rate.unwrap_or_else(default_rate),
+                    output: output_file.clone(),
+                },
+                &StdAppOutput,
+            )
+        }
+        QueueAction::Run {
+            jobs,
+            retry_failed,
+            quiet,
+        } => {
+            run_queue_run(QueueRunRequest {
+                workers: *jobs,
+                retry_failed: *retry_failed,
+                quiet: *quiet,
+                socket_path: args.socket_path(),
+            })
+            .await
+        }
+        QueueAction::List => run_queue_list(&StdAppOutput),
+        QueueAction::Clear { all } => run_queue_clear(*all, &StdAppOutput),
     }
 }
 
 async fn run_client_command(args: &CliArgs) -> Result<()> {
+    init_user_config()?;
+    init_audio_backend(args.audio)?;
+    init_audio_output(args.audio_device.clone(), args.playback_volume);
+    if let Some(address) = &args.connect {
+        set_remote_daemon(Some(address.clone()));
+    }
+    match &args.command {
+        Some(ClientCommand::Queue { action }) => return run_queue_command(args, action).await,
+        Some(ClientCommand::Events) => return run_watch_events(&args.socket_path()).await,
+        Some(ClientCommand::ImportEditorSettings {
+            editor_config,
+            user_dict,
+        }) => {
+            return run_import_editor_settings(&ImportEditorSettingsRequest {
+                editor_config: editor_config.as_deref(),
+                user_dict: user_dict.as_deref(),
+            });
+        }
+        Some(ClientCommand::Dict { action }) => return run_dict_action(args, action).await,
+        Some(ClientCommand::Alias { action }) => return run_alias_action(action),
+        Some(ClientCommand::InstallPack { pack, force }) => {
+            return run_install_pack(&InstallPackRequest {
+                pack,
+                force: *force,
+            });
+        }
+        Some(ClientCommand::Backup { action }) => return run_backup_action(action),
+        Some(ClientCommand::Inspect { wav, manifest }) => {
+            return run_inspect_wav(&InspectWavRequest {
+                wav,
+                manifest: manifest.as_deref(),
+            });
+        }
+        None => {}
+    }
     if handle_voice_help_request(args) {
         return Ok(());
     }
     if maybe_handle_meta_commands(args).await? {
         return Ok(());
     }
+    if let Some(fifo_path) = args.listen_fifo.as_deref() {
+        return run_listen_command(args, fifo_path).await;
+    }
+    if !args.dialogue.is_empty() {
+        return run_dialogue_command(args).await;
+    }
+    if let Some(batch_file) = args.batch.as_deref() {
+        return run_batch_command(args, batch_file).await;
+    }
+    if let Some(srt_file) = args.align_srt.as_deref() {
+        return run_align_command(args, srt_file).await;
+    }
+    if args.serve_stdio {
+        return run_serve_stdio_command(args).await;
+    }
+    if args.pipeline {
+        return run_pipeline_command(args).await;
+    }
+    if let Some(project_file) = args.export_vvproj.as_deref() {
+        return run_export_vvproj_command(args, project_file).await;
+    }
+    if let [action, dir] = args.regression.as_slice() {
+        return run_regression_command(args, action, Path::new(dir)).await;
+    }
     run_synthesis_command(args).await
 }
 
+async fn run_regression_command(args: &CliArgs, action: &str, dir: &Path) -> Result<()> {
+    run_regression(RegressionRequest {
+        action: RegressionAction::parse(action)?,
+        dir,
+        style_id: resolve_voice_from_args(args)?,
+        rate: args.rate(),
+        quiet: args.quiet,
+        socket_path: args.socket_path(),
+    })
+    .await
+}
+
+async fn run_align_command(args: &CliArgs, srt_file: &Path) -> Result<()> {
+    let output_file = args
+        .output_file
+        .as_deref()
+        .ok_or_else(|| anyhow!("--align-srt requires --output-file"))?;
+    run_align_srt(AlignRequest {
+        srt_file,
+        output_file,
+        style_id: resolve_voice_from_args(args)?,
+        rate: args.rate(),
+        quiet: args.quiet,
+        socket_path: args.socket_path(),
+    })
+    .await
+}
+
+async fn run_export_vvproj_command(args: &CliArgs, project_file: &Path) -> Result<()> {
+    let text = synthesis_text_from_args(args)?;
+    run_export_vvproj(VvprojExportRequest {
+        text: &text,
+        style_id: resolve_voice_from_args(args)?,
+        rate: args.rate(),
+        output_file: project_file,
+        quiet: args.quiet,
+        socket_path: args.socket_path(),
+    })
+    .await
+}
+
+async fn run_serve_stdio_command(args: &CliArgs) -> Result<()> {
+    let default_style_id = resolve_voice_from_args(args)?;
+    run_serve_stdio(ServeStdioRequest {
+        default_style_id,
+        default_rate: args.rate(),
+        socket_path: args.socket_path(),
+    })
+    .await
+}
+
+async fn run_pipeline_command(args: &CliArgs) -> Result<()> {
+    let default_style_id = resolve_voice_from_args(args)?;
+    run_pipeline(PipelineRequest {
+        default_style_id,
+        default_rate: args.rate(),
+        socket_path: args.socket_path(),
+    })
+    .await
+}
+
+async fn run_batch_command(args: &CliArgs, batch_file: &Path) -> Result<()> {
+    let default_style_id = resolve_voice_from_args(args)?;
+    run_batch(BatchRequest {
+        batch_file,
+        report_file: args.batch_report.as_deref(),
+        anki_export: args.anki_export.as_deref(),
+        m4b_export: args.m4b.as_deref(),
+        m4b_title: args.m4b_title.as_deref(),
+        m4b_author: args.m4b_author.as_deref(),
+        verify: args.verify,
+        default_style_id,
+        default_rate: args.rate(),
+        quiet: args.quiet,
+        socket_path: args.socket_path(),
+        lines_output_dir: args.batch_dir.as_deref(),
+        jobs: args.jobs,
+    })
+    .await
+}
+
+async fn run_dialogue_command(args: &CliArgs) -> Result<()> {
+    let default_style_id = resolve_voice_from_args(args)?;
+    run_dialogue(DialogueRequest {
+        script_paths: &args.dialogue,
+        default_style_id,
+        default_rate: args.rate(),
+        output_file: args.output_file.as_deref(),
+        output_format: OutputFormat::resolve(args.format, args.output_file.as_deref()),
+        quiet: args.quiet,
+        socket_path: args.socket_path(),
+    })
+    .await
+}
+
+async fn run_listen_command(args: &CliArgs, fifo_path: &Path) -> Result<()> {
+    let style_id = resolve_voice_from_args(args)?;
+    run_listen_mode(ListenRequest {
+        fifo_path,
+        style_id,
+        rate: args.rate(),
+        socket_path: args.socket_path(),
+    })
+    .await
+}
+
 fn should_print_error_in_main(args: &CliArgs, error: &anyhow::Error) -> bool {
     if find_daemon_client_error(error).is_none() {
         return true;
@@ -246,8 +1277,15 @@ fn exit_code_for_error(error: &anyhow::Error) -> ExitCode {
 #[tokio::main]
 async fn main() -> ExitCode {
     let args = CliArgs::parse();
+    if let Err(error) = install_interrupt_handler() {
+        eprintln!("Warning: {error:#}");
+    }
     match run_client_command(&args).await {
         Ok(()) => ExitCode::SUCCESS,
+        Err(error) if is_interrupted(&error) => {
+            restore_terminal();
+            ExitCode::from(EXIT_CODE_INTERRUPTED)
+        }
         Err(error) => {
             print_cli_error(&args, &error);
             exit_code_for_error(&error)
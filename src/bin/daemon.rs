@@ -1,8 +1,14 @@
 use clap::Parser;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::process::ExitCode;
 
-use voicevox_cli::infrastructure::paths::get_socket_path;
+use voicevox_cli::config::init_user_config;
+use voicevox_cli::infrastructure::daemon::config::{
+    TransportConfig, daemon_config_path, load_daemon_config,
+};
+use voicevox_cli::infrastructure::daemon::shared_socket::SharedSocket;
+use voicevox_cli::infrastructure::paths::{get_shared_socket_path, get_socket_path};
 use voicevox_cli::interface::cli::daemon_cli::run_daemon_cli;
 use voicevox_cli::interface::cli::daemon_invocation::{
     DaemonCliFlags, DaemonControlCommand, DaemonStartMode,
@@ -29,28 +35,154 @@ struct CliArgs {
     #[arg(long, help = "Start the daemon (default behavior)")]
     start: bool,
 
-    #[arg(long, conflicts_with_all = ["status", "restart"])]
+    #[arg(long, conflicts_with_all = ["status", "restart", "reload", "self_test"])]
     stop: bool,
 
-    #[arg(long, conflicts_with_all = ["stop", "restart"])]
+    #[arg(long, conflicts_with_all = ["stop", "restart", "reload", "self_test"])]
     status: bool,
 
-    #[arg(long, conflicts_with_all = ["stop", "status"])]
+    #[arg(long, conflicts_with_all = ["stop", "status", "reload", "self_test"])]
     restart: bool,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["stop", "status", "restart", "self_test", "start", "foreground", "detach"],
+        help = "Make the running daemon re-read daemon.toml (same as sending SIGHUP)"
+    )]
+    reload: bool,
+
+    #[arg(
+        long = "reload-models",
+        conflicts_with_all = ["stop", "status", "restart", "reload", "self_test", "start", "foreground", "detach"],
+        help = "Make the running daemon re-read daemon.toml and rescan models, and print the model count"
+    )]
+    reload_models: bool,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["stop", "status", "restart", "reload", "self_test", "start", "foreground", "detach"],
+        help = "Print the running daemon's synthesis, latency, and model cache counters"
+    )]
+    stats: bool,
+
+    #[arg(
+        long = "slow-log",
+        conflicts_with_all = ["stop", "status", "restart", "reload", "stats", "self_test", "start", "foreground", "detach"],
+        help = "Print the running daemon's recent requests slower than [slow_log] in daemon.toml"
+    )]
+    slow_log: bool,
+
+    #[arg(
+        long = "check-config",
+        conflicts_with_all = ["stop", "status", "restart", "reload", "stats", "slow_log", "self_test", "start", "foreground", "detach"],
+        help = "Validate daemon.toml and exit with a status code"
+    )]
+    check_config: bool,
+
+    #[arg(
+        long = "self-test",
+        conflicts_with_all = ["stop", "status", "restart", "start", "foreground", "detach"],
+        help = "Synthesize a test phrase with the smallest model and exit with a status code"
+    )]
+    self_test: bool,
+
+    #[arg(
+        long,
+        value_name = "MODEL_IDS",
+        value_delimiter = ',',
+        conflicts_with_all = ["stop", "status", "reload", "self_test"],
+        help = "Load these models (e.g. 3,2,8) into the running daemon, or at startup with --start/-f/-d/--restart"
+    )]
+    preload: Vec<u32>,
+
+    #[arg(
+        long = "shared-socket",
+        requires = "group",
+        help = "Let members of --group use this daemon (socket in a shared directory, mode 0660)"
+    )]
+    shared_socket: bool,
+
+    #[arg(
+        long = "copy-models-local",
+        help = "Copy models to the local cache before loading them (for models on a network share)"
+    )]
+    copy_models_local: bool,
+
+    #[arg(
+        long = "low-power",
+        help = "Use fewer CPU threads, unload models after each request, and hold batch jobs while on battery"
+    )]
+    low_power: bool,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        requires = "shared_socket",
+        help = "Group allowed to connect in --shared-socket mode"
+    )]
+    group: Option<String>,
+
+    #[arg(
+        long = "listen",
+        value_name = "ADDR",
+        help = "Also accept clients over TCP (e.g. 0.0.0.0:50021); they must send the token in daemon-token"
+    )]
+    listen_address: Option<SocketAddr>,
+
+    #[cfg(feature = "http")]
+    #[arg(
+        long = "http",
+        value_name = "ADDR",
+        help = "Also serve the HTTP API on a loopback address (e.g. 127.0.0.1:50080)"
+    )]
+    http_address: Option<SocketAddr>,
 }
 
 impl CliArgs {
-    fn socket_path(&self) -> PathBuf {
-        self.socket_path.clone().unwrap_or_else(get_socket_path)
+    /// `--socket-path`, else `transport.socket_path`, else the default for the mode.
+    fn socket_path(&self, transport: &TransportConfig) -> PathBuf {
+        self.socket_path
+            .clone()
+            .or_else(|| transport.socket_path.clone())
+            .unwrap_or_else(|| {
+                if self.group(transport).is_some() {
+                    get_shared_socket_path()
+                } else {
+                    get_socket_path()
+                }
+            })
+    }
+
+    /// `--group`, else `transport.shared_group`, which turns on shared mode by itself.
+    fn group<'a>(&'a self, transport: &'a TransportConfig) -> Option<&'a str> {
+        self.group.as_deref().or(transport.shared_group.as_deref())
     }
 
-    fn to_daemon_flags(&self) -> DaemonCliFlags {
-        DaemonCliFlags {
+    fn to_daemon_flags(&self, transport: &TransportConfig) -> anyhow::Result<DaemonCliFlags> {
+        Ok(DaemonCliFlags {
             start_mode: DaemonStartMode::from_flags(self.foreground, self.detach),
             mode_flag_explicit: self.foreground || self.detach,
             start: self.start,
             control: self.control_command(),
-        }
+            http_address: self.http_address().or(transport.http),
+            listen_address: self.listen_address.or(transport.listen),
+            shared_socket: self
+                .group(transport)
+                .map(SharedSocket::for_group)
+                .transpose()?,
+            copy_models_local: self.copy_models_local,
+            low_power: self.low_power,
+        })
+    }
+
+    #[cfg(feature = "http")]
+    const fn http_address(&self) -> Option<SocketAddr> {
+        self.http_address
+    }
+
+    #[cfg(not(feature = "http"))]
+    const fn http_address(&self) -> Option<SocketAddr> {
+        None
     }
 
     fn control_command(&self) -> DaemonControlCommand {
@@ -58,6 +190,22 @@ impl CliArgs {
             .then_some(DaemonControlCommand::Stop)
             .or_else(|| self.status.then_some(DaemonControlCommand::Status))
             .or_else(|| self.restart.then_some(DaemonControlCommand::Restart))
+            .or_else(|| self.reload.then_some(DaemonControlCommand::Reload))
+            .or_else(|| {
+                self.reload_models
+                    .then_some(DaemonControlCommand::ReloadModels)
+            })
+            .or_else(|| self.self_test.then_some(DaemonControlCommand::SelfTest))
+            .or_else(|| self.stats.then_some(DaemonControlCommand::Stats))
+            .or_else(|| self.slow_log.then_some(DaemonControlCommand::SlowLog))
+            .or_else(|| {
+                self.check_config
+                    .then_some(DaemonControlCommand::CheckConfig)
+            })
+            .or_else(|| {
+                (!self.preload.is_empty() && !self.start && !self.foreground && !self.detach)
+                    .then_some(DaemonControlCommand::Preload)
+            })
             .unwrap_or(DaemonControlCommand::None)
     }
 }
@@ -65,7 +213,22 @@ impl CliArgs {
 #[tokio::main]
 async fn main() -> ExitCode {
     let args = CliArgs::parse();
-    match run_daemon_cli(args.socket_path(), args.to_daemon_flags()).await {
+    if let Err(error) = init_user_config() {
+        eprintln!("Error: {error:#}");
+        return ExitCode::from(1);
+    }
+    // An invalid file is reported by --check-config or when the daemon starts.
+    let transport = load_daemon_config(&daemon_config_path())
+        .map(|config| config.transport)
+        .unwrap_or_default();
+    let flags = match args.to_daemon_flags(&transport) {
+        Ok(flags) => flags,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            return ExitCode::from(1);
+        }
+    };
+    match run_daemon_cli(args.socket_path(&transport), flags, args.preload).await {
         Ok(code) => ExitCode::from(code as u8),
         Err(error) => {
             eprintln!("Error: {error}");
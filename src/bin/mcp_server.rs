@@ -1,7 +1,11 @@
 use anyhow::Result;
 use clap::Parser;
+use std::path::PathBuf;
+use std::time::Duration;
 
-use voicevox_cli::interface::mcp_server::run_mcp_server_app;
+use voicevox_cli::config::init_user_config;
+use voicevox_cli::domain::synthesis::timing::parse_duration_spec;
+use voicevox_cli::interface::mcp_server::{McpDaemonOptions, run_mcp_server_app};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -9,10 +13,69 @@ use voicevox_cli::interface::mcp_server::run_mcp_server_app;
     about = "VOICEVOX MCP Server for AI assistants",
     version
 )]
-struct Args;
+struct Args {
+    #[arg(
+        long,
+        help = "Never start voicevox-daemon; expect the host to run it (env: VOICEVOX_MCP_NO_AUTO_START_DAEMON)"
+    )]
+    no_auto_start_daemon: bool,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Daemon socket to connect to, and to start the daemon on (env: VOICEVOX_SOCKET_PATH)"
+    )]
+    daemon_socket: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = parse_duration_spec,
+        help = "Give up connecting to the daemon after DURATION (default 20s; env: VOICEVOX_MCP_CONNECT_TIMEOUT)"
+    )]
+    connect_timeout: Option<Duration>,
+
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = parse_duration_spec,
+        help = "Give up on a text_to_speech chunk after DURATION (default 120s; env: VOICEVOX_MCP_SYNTHESIS_TIMEOUT)"
+    )]
+    synthesis_timeout: Option<Duration>,
+
+    #[arg(
+        long,
+        value_name = "ID",
+        conflicts_with = "no_prewarm",
+        help = "Style whose model is warmed when a session starts (default 3)"
+    )]
+    prewarm_style: Option<u32>,
+
+    #[arg(long, help = "Do not warm a model when a session starts")]
+    no_prewarm: bool,
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let _ = Args::parse();
-    run_mcp_server_app().await
+    let args = Args::parse();
+    init_user_config()?;
+    let mut options = McpDaemonOptions::from_env()?;
+    if args.no_auto_start_daemon {
+        options.auto_start = false;
+    }
+    if let Some(socket_path) = args.daemon_socket {
+        options.socket_path = socket_path;
+    }
+    if let Some(timeout) = args.connect_timeout {
+        options.connect_timeout = timeout;
+    }
+    if let Some(timeout) = args.synthesis_timeout {
+        options.synthesis_timeout = timeout;
+    }
+    if args.no_prewarm {
+        options.prewarm_style = None;
+    } else if let Some(style_id) = args.prewarm_style {
+        options.prewarm_style = Some(style_id);
+    }
+    run_mcp_server_app(options).await
 }
@@ -1,11 +1,29 @@
+use anyhow::{Context, Result, ensure};
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use voicevox_text::TextSplitter;
+
+use crate::infrastructure::daemon::config::ModelCacheConfig;
+use crate::infrastructure::ipc::is_valid_synthesis_rate;
+use crate::infrastructure::paths::get_config_dir;
+use crate::interface::audio::{AudioBackend, is_valid_volume};
 
 pub const APP_NAME: &str = "voicevox";
 pub const SOCKET_FILENAME: &str = "voicevox-daemon.sock";
 pub const MCP_INSTRUCTIONS_FILE: &str = "VOICEVOX.md";
+pub const DAEMON_CONFIG_FILE: &str = "daemon.toml";
+pub const USER_CONFIG_FILE: &str = "config.toml";
+pub const USER_DICT_FILE: &str = "user_dict.json";
+pub const VOICE_PRESETS_FILE: &str = "presets.json";
+pub const VOICE_ALIASES_FILE: &str = "aliases.json";
+pub const HOOKS_CONFIG_FILE: &str = "hooks.toml";
+pub const SPEAKER_CACHE_FILE: &str = "speakers.json";
+pub const USAGE_STATS_FILE: &str = "usage_stats.json";
 
 pub const ENV_HOME: &str = "HOME";
 pub const ENV_PATH: &str = "PATH";
+pub const ENV_XDG_CACHE_HOME: &str = "XDG_CACHE_HOME";
 pub const ENV_XDG_CONFIG_HOME: &str = "XDG_CONFIG_HOME";
 pub const ENV_XDG_DATA_HOME: &str = "XDG_DATA_HOME";
 pub const ENV_XDG_RUNTIME_DIR: &str = "XDG_RUNTIME_DIR";
@@ -16,12 +34,28 @@ pub const ENV_VOICEVOX_SOCKET_PATH: &str = "VOICEVOX_SOCKET_PATH";
 pub const ENV_VOICEVOX_MODELS_DIR: &str = "VOICEVOX_MODELS_DIR";
 pub const ENV_VOICEVOX_OPENJTALK_DICT: &str = "VOICEVOX_OPENJTALK_DICT";
 pub const ENV_VOICEVOX_MCP_INSTRUCTIONS: &str = "VOICEVOX_MCP_INSTRUCTIONS";
-pub const ENV_VOICEVOX_LOW_LATENCY: &str = "VOICEVOX_LOW_LATENCY";
+pub const ENV_VOICEVOX_DAEMON_CONFIG: &str = "VOICEVOX_DAEMON_CONFIG";
+pub const ENV_VOICEVOX_CONFIG: &str = "VOICEVOX_CONFIG";
+pub const ENV_VOICEVOX_VOICE: &str = "VOICEVOX_VOICE";
+pub const ENV_VOICEVOX_RATE: &str = "VOICEVOX_RATE";
+pub const ENV_VOICEVOX_APP_ID: &str = "VOICEVOX_APP_ID";
+pub const ENV_VOICEVOX_AUDIO: &str = "VOICEVOX_AUDIO";
+pub const ENV_VOICEVOX_MCP_NO_AUTO_START_DAEMON: &str = "VOICEVOX_MCP_NO_AUTO_START_DAEMON";
+pub const ENV_VOICEVOX_MCP_CONNECT_TIMEOUT: &str = "VOICEVOX_MCP_CONNECT_TIMEOUT";
+pub const ENV_VOICEVOX_MCP_SYNTHESIS_TIMEOUT: &str = "VOICEVOX_MCP_SYNTHESIS_TIMEOUT";
+pub const ENV_VOICEVOX_MCP_OUTPUT_DIR: &str = "VOICEVOX_MCP_OUTPUT_DIR";
+pub const ENV_VOICEVOX_TMPDIR: &str = "VOICEVOX_TMPDIR";
+pub const ENV_VOICEVOX_SHARED_SOCKET: &str = "VOICEVOX_SHARED_SOCKET";
+pub const ENV_VOICEVOX_DAEMON_ADDR: &str = "VOICEVOX_DAEMON_ADDR";
+pub const ENV_VOICEVOX_DAEMON_TOKEN: &str = "VOICEVOX_DAEMON_TOKEN";
 pub const ENV_VOICEVOX_DETACH_PARENT_PID: &str = "VOICEVOX_DETACH_PARENT_PID";
 pub const ENV_VOICEVOX_ALLOW_UNSAFE_PATH_COMMANDS: &str = "VOICEVOX_ALLOW_UNSAFE_PATH_COMMANDS";
 pub const ENV_VOICEVOX_ALLOW_UNSAFE_DAEMON_LOOKUP: &str = "VOICEVOX_ALLOW_UNSAFE_DAEMON_LOOKUP";
 
 pub const DEFAULT_TMP_DIR: &str = "/tmp";
+/// Directory under [`DEFAULT_TMP_DIR`] holding the socket of a daemon shared by a group.
+pub const SHARED_SOCKET_DIR_NAME: &str = "voicevox-shared";
+pub const USER_CACHE_DIR: &str = ".cache";
 pub const USER_CONFIG_DIR: &str = ".config";
 pub const USER_LOCAL_SHARE_DIR: &str = ".local/share";
 pub const USER_LOCAL_STATE_DIR: &str = ".local/state";
@@ -29,13 +63,10 @@ pub const USER_LOCAL_STATE_DIR: &str = ".local/state";
 pub const SYSTEM_PGREP_PATH: &str = "/usr/bin/pgrep";
 pub const SYSTEM_PS_PATH: &str = "/bin/ps";
 pub const SYSTEM_KILL_PATH: &str = "/bin/kill";
-
-pub const SYSTEM_AUDIO_PLAYER_PATHS: [&str; 3] = [
-    "/usr/bin/afplay",
-    "/opt/homebrew/bin/play",
-    "/usr/local/bin/play",
-];
-pub const FALLBACK_AUDIO_PLAYERS: [&str; 2] = ["afplay", "play"];
+pub const SYSTEM_SH_PATH: &str = "/bin/sh";
+pub const SYSTEM_UNZIP_PATH: &str = "/usr/bin/unzip";
+pub const SYSTEM_TAR_PATH: &str = "/usr/bin/tar";
+pub const SYSTEM_PMSET_PATH: &str = "/usr/bin/pmset";
 
 pub const SYSTEM_VOICEVOX_LIB_DIRS: [&str; 2] =
     ["/usr/local/share/voicevox/lib", "/opt/voicevox/lib"];
@@ -45,6 +76,26 @@ pub fn allow_unsafe_path_commands() -> bool {
     std::env::var_os(ENV_VOICEVOX_ALLOW_UNSAFE_PATH_COMMANDS).is_some()
 }
 
+/// Whether clients should use the group-shared daemon instead of a per-user one.
+#[must_use]
+pub fn use_shared_socket() -> bool {
+    std::env::var_os(ENV_VOICEVOX_SHARED_SOCKET).is_some_and(|value| !value.is_empty())
+}
+
+/// Whether the MCP server should leave starting the daemon to its host.
+#[must_use]
+pub fn mcp_auto_start_daemon_disabled() -> bool {
+    std::env::var_os(ENV_VOICEVOX_MCP_NO_AUTO_START_DAEMON).is_some_and(|value| !value.is_empty())
+}
+
+/// Directory the MCP `synthesize_to_file` tool must write inside, if one is set.
+#[must_use]
+pub fn mcp_output_dir() -> Option<std::path::PathBuf> {
+    std::env::var_os(ENV_VOICEVOX_MCP_OUTPUT_DIR)
+        .filter(|value| !value.is_empty())
+        .map(std::path::PathBuf::from)
+}
+
 #[must_use]
 pub fn command_path_or_fallback(
     preferred: &'static str,
@@ -59,10 +110,165 @@ pub fn command_path_or_fallback(
     }
 }
 
+/// User settings read from `config.toml` by voicevox-say, the daemon, and the MCP
+/// server.
+///
+/// Every key is optional. Environment variables override the file and command-line
+/// flags override both; a malformed file stops the program rather than being ignored.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
+    /// Voice used when none is given: a style ID, voice name, or speaker UUID.
+    pub voice: Option<String>,
+    /// Speech rate used when none is given (0.5-2.0).
+    pub rate: Option<f32>,
+    /// Models directory, checked after `VOICEVOX_MODELS_DIR`.
+    pub models_dir: Option<PathBuf>,
+    /// Daemon socket, checked after `VOICEVOX_SOCKET_PATH`.
+    pub socket_path: Option<PathBuf>,
+    /// Audio output: `device` or `null`, checked after `VOICEVOX_AUDIO`.
+    pub audio: Option<String>,
+    /// Output device to play on, by the name `--list-audio-devices` prints.
+    pub audio_device: Option<String>,
+    /// Playback volume (0.0-1.0); synthesized and saved audio is unchanged.
+    pub playback_volume: Option<f32>,
+    /// Daemon model cache policy, used when `daemon.toml` has no `[model_cache]`.
+    pub model_cache: Option<ModelCacheConfig>,
     #[serde(default)]
     pub text_splitter: TextSplitterConfig,
+    /// What `voicevox-say --greet` says, and whether to say it once a day unasked.
+    pub greeting: Option<GreetingConfig>,
+}
+
+/// `[greeting]`: a short announcement of the active voice and models.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GreetingConfig {
+    /// What to say; `{voice}` and `{models}` are replaced with the voice's name and
+    /// the number of models the daemon can use.
+    pub text: Option<String>,
+    /// Speak the greeting before the first `voicevox-say` of each day that plays audio.
+    #[serde(default)]
+    pub daily: bool,
+}
+
+impl Config {
+    /// Parses and validates `config.toml` contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for unknown keys, values of the wrong type, a rate outside
+    /// 0.5-2.0, an unknown audio backend, a playback volume outside 0.0-1.0, an invalid
+    /// `[model_cache]`, or an empty greeting.
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut config: Self = toml::from_str(source)?;
+        if let Some(rate) = config.rate {
+            ensure!(
+                is_valid_synthesis_rate(rate),
+                "rate must be between 0.5 and 2.0: {rate}"
+            );
+        }
+        if let Some(audio) = &config.audio {
+            AudioBackend::parse(audio)?;
+        }
+        if let Some(volume) = config.playback_volume {
+            ensure!(
+                is_valid_volume(volume),
+                "playback_volume must be between 0.0 and 1.0: {volume}"
+            );
+        }
+        if let Some(model_cache) = &config.model_cache {
+            model_cache.validate()?;
+        }
+        if let Some(text) = config
+            .greeting
+            .as_ref()
+            .and_then(|greeting| greeting.text.as_deref())
+        {
+            ensure!(!text.trim().is_empty(), "greeting text is empty");
+        }
+        config.models_dir = config.models_dir.as_deref().map(expand_home);
+        config.socket_path = config.socket_path.as_deref().map(expand_home);
+        Ok(config)
+    }
+
+    /// Loads `path`, falling back to defaults when the file does not exist, then
+    /// applies `VOICEVOX_VOICE` and `VOICEVOX_RATE`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or is invalid, or if
+    /// `VOICEVOX_RATE` is not a valid rate.
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut config = match std::fs::read_to_string(path) {
+            Ok(source) => Self::parse(&source)
+                .with_context(|| format!("Invalid config {}", path.display()))?,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(error) => {
+                return Err(error)
+                    .with_context(|| format!("Failed to read config {}", path.display()));
+            }
+        };
+        if let Some(voice) = std::env::var(ENV_VOICEVOX_VOICE)
+            .ok()
+            .filter(|voice| !voice.trim().is_empty())
+        {
+            config.voice = Some(voice);
+        }
+        if let Some(rate) = std::env::var(ENV_VOICEVOX_RATE)
+            .ok()
+            .filter(|rate| !rate.trim().is_empty())
+        {
+            let rate = rate
+                .trim()
+                .parse::<f32>()
+                .ok()
+                .filter(|&rate| is_valid_synthesis_rate(rate))
+                .with_context(|| {
+                    format!("Invalid {ENV_VOICEVOX_RATE}: expected 0.5-2.0, got '{rate}'")
+                })?;
+            config.rate = Some(rate);
+        }
+        Ok(config)
+    }
+}
+
+/// Resolves a leading `~` so paths in `config.toml` can be written as in a shell.
+pub(crate) fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Location of `config.toml`: `$VOICEVOX_CONFIG`, else the user config directory.
+#[must_use]
+pub fn user_config_path() -> PathBuf {
+    std::env::var_os(ENV_VOICEVOX_CONFIG)
+        .map_or_else(|| get_config_dir().join(USER_CONFIG_FILE), PathBuf::from)
+}
+
+static USER_CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Loads `config.toml` for this process; binaries call this once at startup.
+///
+/// The first call wins; later calls return the settings already in use.
+///
+/// # Errors
+///
+/// Returns an error if the file or `VOICEVOX_RATE` is invalid.
+pub fn init_user_config() -> Result<&'static Config> {
+    if let Some(config) = USER_CONFIG.get() {
+        return Ok(config);
+    }
+    let config = Config::load(&user_config_path())?;
+    Ok(USER_CONFIG.get_or_init(|| config))
+}
+
+/// The settings loaded by [`init_user_config`], or defaults if it was never called.
+#[must_use]
+pub fn user_config() -> &'static Config {
+    USER_CONFIG.get_or_init(Config::default)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -73,6 +279,19 @@ pub struct TextSplitterConfig {
     pub max_length: usize,
 }
 
+impl TextSplitterConfig {
+    /// Builds the sentence splitter these settings describe.
+    #[must_use]
+    pub fn splitter(&self) -> TextSplitter {
+        let delimiters = self
+            .delimiters
+            .iter()
+            .filter_map(|s| s.chars().next())
+            .collect();
+        TextSplitter::new(delimiters, self.max_length)
+    }
+}
+
 impl Default for TextSplitterConfig {
     fn default() -> Self {
         Self {
@@ -95,3 +314,44 @@ fn default_delimiters() -> Vec<String> {
 const fn default_max_length() -> usize {
     100
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_user_settings() {
+        let config = Config::parse(
+            "voice = \"zundamon-whisper\"\nrate = 1.2\nmodels_dir = \"~/nas/models\"\n\
+             socket_path = \"/run/voicevox.sock\"\naudio = \"null\"\n\n\
+             [model_cache]\nmax_loaded_models = 2\n\n[greeting]\ndaily = true\n",
+        )
+        .expect("valid config");
+
+        assert_eq!(config.voice.as_deref(), Some("zundamon-whisper"));
+        assert_eq!(config.rate, Some(1.2));
+        assert_eq!(
+            config.models_dir,
+            dirs::home_dir().map(|home| home.join("nas/models"))
+        );
+        assert_eq!(
+            config.socket_path.as_deref(),
+            Some(Path::new("/run/voicevox.sock"))
+        );
+        assert_eq!(
+            config.model_cache.map(|cache| cache.max_loaded_models),
+            Some(2)
+        );
+        assert!(config.greeting.is_some_and(|greeting| greeting.daily));
+        assert!(Config::parse("").expect("empty").voice.is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        assert!(Config::parse("rate = 3.0\n").is_err());
+        assert!(Config::parse("audio = \"speakers\"\n").is_err());
+        assert!(Config::parse("default_voice = \"3\"\n").is_err());
+        assert!(Config::parse("[model_cache]\nmax_loaded_models = 100\n").is_err());
+        assert!(Config::parse("[greeting]\ntext = \" \"\n").is_err());
+    }
+}
@@ -0,0 +1,68 @@
+/// Header lines understood by Anki's "Import File" dialog for plain-text notes.
+const NOTES_HEADER: &str = "#separator:tab\n#html:true\n#columns:Text\tAudio\n";
+
+/// One sentence card: the spoken text and the media file name holding its audio.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnkiNote {
+    pub text: String,
+    pub audio_file: String,
+}
+
+fn escape_field(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '\n' => escaped.push_str("<br>"),
+            '\t' => escaped.push(' '),
+            '\r' => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Renders notes as an Anki tab-separated import file.
+///
+/// The audio column uses `[sound:…]` tags, so the referenced files must be copied
+/// into Anki's `collection.media` folder (or imported alongside the notes).
+#[must_use]
+pub fn render_anki_notes(notes: &[AnkiNote]) -> String {
+    let mut rendered = String::from(NOTES_HEADER);
+    for note in notes {
+        rendered.push_str(&escape_field(&note.text));
+        rendered.push('\t');
+        rendered.push_str("[sound:");
+        rendered.push_str(&escape_field(&note.audio_file));
+        rendered.push_str("]\n");
+    }
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_header_and_escaped_sound_rows() {
+        let notes = [
+            AnkiNote {
+                text: "こんにちは".to_owned(),
+                audio_file: "hello.wav".to_owned(),
+            },
+            AnkiNote {
+                text: "A<B\tC\n次".to_owned(),
+                audio_file: "two.wav".to_owned(),
+            },
+        ];
+
+        assert_eq!(
+            render_anki_notes(&notes),
+            "#separator:tab\n#html:true\n#columns:Text\tAudio\n\
+             こんにちは\t[sound:hello.wav]\n\
+             A&lt;B C<br>次\t[sound:two.wav]\n"
+        );
+    }
+}
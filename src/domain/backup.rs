@@ -0,0 +1,108 @@
+use anyhow::{Result, ensure};
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path};
+
+/// Manifest file at the root of every backup snapshot.
+pub const BACKUP_MANIFEST_FILE: &str = "backup.toml";
+
+/// Snapshot layout version written by this build.
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+/// One per-user directory captured in a snapshot, stored under [`Self::dir_name`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackupSection {
+    /// `daemon.toml`, presets, hooks, and MCP instructions.
+    Config,
+    /// The user dictionary from the data directory.
+    Data,
+    /// Render queue, dialogue casting, and other records.
+    State,
+    /// VVM models and voice pack records, only when requested.
+    Models,
+}
+
+impl BackupSection {
+    pub const ALL: [Self; 4] = [Self::Config, Self::Data, Self::State, Self::Models];
+
+    #[must_use]
+    pub const fn dir_name(self) -> &'static str {
+        match self {
+            Self::Config => "config",
+            Self::Data => "data",
+            Self::State => "state",
+            Self::Models => "models",
+        }
+    }
+}
+
+/// The `backup.toml` of a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    /// `voicevox-cli` version that created the snapshot.
+    pub created_by: String,
+    pub includes_models: bool,
+}
+
+impl BackupManifest {
+    #[must_use]
+    pub fn current(includes_models: bool) -> Self {
+        Self {
+            format_version: BACKUP_FORMAT_VERSION,
+            created_by: env!("CARGO_PKG_VERSION").to_string(),
+            includes_models,
+        }
+    }
+
+    /// Parses a snapshot manifest this build can restore.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on TOML syntax errors or a newer snapshot format.
+    pub fn parse(source: &str) -> Result<Self> {
+        let manifest: Self = toml::from_str(source)?;
+        ensure!(
+            manifest.format_version <= BACKUP_FORMAT_VERSION,
+            "Snapshot format {} was written by voicevox-cli {}; this build reads up to format {BACKUP_FORMAT_VERSION}",
+            manifest.format_version,
+            manifest.created_by
+        );
+        Ok(manifest)
+    }
+}
+
+/// Whether a path inside a section stays inside it when restored.
+#[must_use]
+pub fn is_contained_path(path: &Path) -> bool {
+    path.components().next().is_some()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_roundtrips_and_rejects_newer_formats() {
+        let manifest = BackupManifest::current(true);
+        let source = toml::to_string(&manifest).expect("serialize");
+        assert_eq!(BackupManifest::parse(&source).expect("parse"), manifest);
+
+        let newer = format!(
+            "format_version = {}\ncreated_by = \"99.0.0\"\nincludes_models = false\n",
+            BACKUP_FORMAT_VERSION + 1
+        );
+        assert!(BackupManifest::parse(&newer).is_err());
+    }
+
+    #[test]
+    fn only_relative_descending_paths_are_contained() {
+        assert!(is_contained_path(Path::new("presets.json")));
+        assert!(is_contained_path(Path::new("packs/alpha/LICENSE")));
+        assert!(!is_contained_path(Path::new("../presets.json")));
+        assert!(!is_contained_path(Path::new("/etc/passwd")));
+        assert!(!is_contained_path(Path::new("")));
+    }
+}
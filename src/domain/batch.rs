@@ -0,0 +1,342 @@
+use anyhow::{Result, anyhow};
+use std::path::Path;
+
+const TEXT_COLUMN: &str = "text";
+const OUTPUT_COLUMN: &str = "output";
+const VOICE_COLUMNS: [&str; 3] = ["voice", "style", "style_id"];
+const RATE_COLUMN: &str = "rate";
+const CHAPTER_COLUMN: &str = "chapter";
+const ERROR_COLUMN: &str = "error";
+/// Fewest digits in numbered output names, so a directory listing sorts in order.
+const MIN_NUMBER_WIDTH: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableDelimiter {
+    Comma,
+    Tab,
+}
+
+impl TableDelimiter {
+    const fn as_char(self) -> char {
+        match self {
+            Self::Comma => ',',
+            Self::Tab => '\t',
+        }
+    }
+
+    /// Conventional file extension for tables using this delimiter.
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Comma => "csv",
+            Self::Tab => "tsv",
+        }
+    }
+
+    /// Picks the delimiter from the header line: tabs win when present.
+    #[must_use]
+    pub fn detect(header: &str) -> Self {
+        if header.contains('\t') {
+            Self::Tab
+        } else {
+            Self::Comma
+        }
+    }
+}
+
+/// One structured batch row. `line` is the 1-based source line for reporting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchRow {
+    pub line: usize,
+    pub text: String,
+    pub voice: Option<String>,
+    pub rate: Option<String>,
+    pub output: String,
+    /// Title of the audiobook chapter this row starts, if any.
+    pub chapter: Option<String>,
+}
+
+struct ColumnMap {
+    text: usize,
+    output: usize,
+    voice: Option<usize>,
+    rate: Option<usize>,
+    chapter: Option<usize>,
+}
+
+impl ColumnMap {
+    fn from_header(header: &[String]) -> Result<Self> {
+        let find = |name: &str| {
+            header
+                .iter()
+                .position(|column| column.trim().eq_ignore_ascii_case(name))
+        };
+        Ok(Self {
+            text: find(TEXT_COLUMN)
+                .ok_or_else(|| anyhow!("Batch header is missing the '{TEXT_COLUMN}' column"))?,
+            output: find(OUTPUT_COLUMN)
+                .ok_or_else(|| anyhow!("Batch header is missing the '{OUTPUT_COLUMN}' column"))?,
+            voice: VOICE_COLUMNS.into_iter().find_map(find),
+            rate: find(RATE_COLUMN),
+            chapter: find(CHAPTER_COLUMN),
+        })
+    }
+}
+
+/// Parses a CSV or TSV batch table with a header row.
+///
+/// Required columns are `text` and `output`; `voice` (or `style`/`style_id`) and `rate`
+/// are optional and empty cells fall back to CLI defaults. An optional `chapter`
+/// column titles the audiobook chapter a row starts. CSV fields may be quoted
+/// with `"` and contain delimiters, doubled quotes, or newlines.
+///
+/// # Errors
+///
+/// Returns an error if the header lacks required columns or quoting is unbalanced.
+pub fn parse_batch_table(source: &str) -> Result<Vec<BatchRow>> {
+    let header_line = source.lines().next().unwrap_or_default();
+    let delimiter = TableDelimiter::detect(header_line);
+    let records = split_records(source, delimiter)?;
+    let mut records = records.into_iter();
+
+    let Some((_, header)) = records.next() else {
+        return Err(anyhow!("Batch file is empty"));
+    };
+    let columns = ColumnMap::from_header(&header)?;
+
+    Ok(records
+        .filter(|(_, fields)| fields.iter().any(|field| !field.trim().is_empty()))
+        .map(|(line, fields)| {
+            let cell = |index: usize| fields.get(index).map_or("", |field| field.trim());
+            let optional = |index: Option<usize>| {
+                index
+                    .map(cell)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_owned)
+            };
+            BatchRow {
+                line,
+                text: cell(columns.text).to_owned(),
+                voice: optional(columns.voice),
+                rate: optional(columns.rate),
+                output: cell(columns.output).to_owned(),
+                chapter: optional(columns.chapter),
+            }
+        })
+        .collect())
+}
+
+/// Parses one utterance per line, optionally prefixed by `VOICE<TAB>`, into rows
+/// writing numbered WAVs (`0001.wav`, `0002.wav`, ...) into `output_dir`.
+///
+/// Blank lines are skipped and do not take a number.
+#[must_use]
+pub fn parse_batch_lines(source: &str, output_dir: &Path) -> Vec<BatchRow> {
+    let utterances = source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .collect::<Vec<_>>();
+    let width = utterances.len().to_string().len().max(MIN_NUMBER_WIDTH);
+    utterances
+        .into_iter()
+        .enumerate()
+        .map(|(index, (line_index, line))| {
+            let (voice, text) = match line.split_once('\t') {
+                Some((voice, text)) => (Some(voice.trim()).filter(|v| !v.is_empty()), text),
+                None => (None, line),
+            };
+            BatchRow {
+                line: line_index + 1,
+                text: text.trim().to_owned(),
+                voice: voice.map(str::to_owned),
+                rate: None,
+                output: output_dir
+                    .join(format!("{:0width$}.wav", index + 1))
+                    .display()
+                    .to_string(),
+                chapter: None,
+            }
+        })
+        .collect()
+}
+
+/// Renders rows back into a batch table, with each row's error in a trailing column.
+///
+/// The `error` column is ignored by [`parse_batch_table`], so the output can be fed
+/// straight back in as a batch once the problems are fixed.
+#[must_use]
+pub fn render_retry_table(rows: &[(&BatchRow, &str)], delimiter: TableDelimiter) -> String {
+    let separator = delimiter.as_char().to_string();
+    let mut table = [
+        TEXT_COLUMN,
+        VOICE_COLUMNS[0],
+        RATE_COLUMN,
+        OUTPUT_COLUMN,
+        ERROR_COLUMN,
+    ]
+    .join(&separator);
+    table.push('\n');
+    for (row, error) in rows {
+        let fields = [
+            row.text.as_str(),
+            row.voice.as_deref().unwrap_or_default(),
+            row.rate.as_deref().unwrap_or_default(),
+            row.output.as_str(),
+            error,
+        ];
+        let fields = fields
+            .iter()
+            .map(|field| quote_field(field, delimiter))
+            .collect::<Vec<_>>();
+        table.push_str(&fields.join(&separator));
+        table.push('\n');
+    }
+    table
+}
+
+fn quote_field(field: &str, delimiter: TableDelimiter) -> String {
+    if field.contains([delimiter.as_char(), '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+fn split_records(source: &str, delimiter: TableDelimiter) -> Result<Vec<(usize, Vec<String>)>> {
+    let delimiter = delimiter.as_char();
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = source.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if field.is_empty() => in_quotes = true,
+            '\n' if in_quotes => {
+                line += 1;
+                field.push('\n');
+            }
+            '\r' if !in_quotes => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                records.push((record_line, std::mem::take(&mut fields)));
+                line += 1;
+                record_line = line;
+            }
+            ch if ch == delimiter && !in_quotes => fields.push(std::mem::take(&mut field)),
+            ch => field.push(ch),
+        }
+    }
+
+    if in_quotes {
+        return Err(anyhow!(
+            "Unterminated quoted field starting on line {record_line}"
+        ));
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        records.push((record_line, fields));
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_quoted_csv_fields() {
+        let rows = parse_batch_table(
+            "text,voice,rate,output\n\"こんにちは, \"\"世界\"\"\",3,1.2,a.wav\nさようなら,,,b.wav\n",
+        )
+        .expect("csv should parse");
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].text, "こんにちは, \"世界\"");
+        assert_eq!(rows[0].voice.as_deref(), Some("3"));
+        assert_eq!(rows[0].rate.as_deref(), Some("1.2"));
+        assert_eq!(rows[1].line, 3);
+        assert_eq!(rows[1].voice, None);
+        assert_eq!(rows[1].output, "b.wav");
+    }
+
+    #[test]
+    fn numbers_plain_lines_and_reads_voice_prefixes() {
+        let rows = parse_batch_lines(
+            "おはよう\n\n3\tこんにちは\n\tこんばんは\n",
+            Path::new("out"),
+        );
+
+        assert_eq!(
+            rows.iter()
+                .map(|row| (row.line, row.text.as_str(), row.voice.as_deref()))
+                .collect::<Vec<_>>(),
+            [
+                (1, "おはよう", None),
+                (3, "こんにちは", Some("3")),
+                (4, "こんばんは", None)
+            ]
+        );
+        assert_eq!(
+            rows[2].output,
+            Path::new("out").join("0003.wav").display().to_string()
+        );
+    }
+
+    #[test]
+    fn parses_tsv_with_reordered_columns() {
+        let rows =
+            parse_batch_table("output\tstyle\ttext\tchapter\nout.wav\t2\tテスト\t第一章\n\n")
+                .expect("tsv should parse");
+
+        assert_eq!(
+            rows,
+            vec![BatchRow {
+                line: 2,
+                text: "テスト".to_owned(),
+                voice: Some("2".to_owned()),
+                rate: None,
+                output: "out.wav".to_owned(),
+                chapter: Some("第一章".to_owned()),
+            }]
+        );
+    }
+
+    #[test]
+    fn retry_tables_parse_back_to_the_failed_rows() {
+        let rows = parse_batch_table(
+            "text,voice,rate,output\n\"一行目,\n二行目\",3,,a.wav\n\"言った\"\"はい\"\"\",,1.2,b.wav\n",
+        )
+        .expect("csv should parse");
+        let failures = rows
+            .iter()
+            .map(|row| (row, "Synthesis error: \"boom\", retry"))
+            .collect::<Vec<_>>();
+
+        let retry = render_retry_table(&failures, TableDelimiter::Comma);
+        let reparsed = parse_batch_table(&retry).expect("retry table should parse");
+
+        assert_eq!(reparsed.len(), 2);
+        for (original, retried) in rows.iter().zip(&reparsed) {
+            assert_eq!(retried.text, original.text);
+            assert_eq!(retried.voice, original.voice);
+            assert_eq!(retried.rate, original.rate);
+            assert_eq!(retried.output, original.output);
+        }
+    }
+
+    #[test]
+    fn rejects_missing_columns_and_open_quotes() {
+        assert!(parse_batch_table("text,voice\nこんにちは,3\n").is_err());
+        assert!(parse_batch_table("text,output\n\"open,a.wav\n").is_err());
+    }
+}
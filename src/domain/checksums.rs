@@ -0,0 +1,71 @@
+use anyhow::{Result, anyhow};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+/// Hex-encoded SHA-256 of `data`.
+#[must_use]
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// One line of a checksums file in `sha256sum` format (`<digest>  <path>`).
+#[must_use]
+pub fn checksum_line(digest: &str, path: &str) -> String {
+    format!("{digest}  {path}\n")
+}
+
+/// Parses a `sha256sum`-style checksums file into a path-to-digest map.
+///
+/// Later lines for the same path win, so a file can be appended to across reruns.
+///
+/// # Errors
+///
+/// Returns an error if a non-empty line is not `<64 hex digits>  <path>`.
+pub fn parse_checksums(source: &str) -> Result<BTreeMap<String, String>> {
+    let mut digests = BTreeMap::new();
+    for (index, line) in source.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (digest, path) = line
+            .split_once("  ")
+            .filter(|(digest, path)| {
+                digest.len() == 64
+                    && digest.bytes().all(|byte| byte.is_ascii_hexdigit())
+                    && !path.is_empty()
+            })
+            .ok_or_else(|| anyhow!("Malformed checksum on line {}", index + 1))?;
+        digests.insert(path.to_owned(), digest.to_ascii_lowercase());
+    }
+    Ok(digests)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksum_lines_roundtrip_in_sha256sum_format() {
+        let digest = sha256_hex(b"abc");
+        assert_eq!(
+            digest,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        let source = checksum_line(&sha256_hex(b"old"), "out/a.wav")
+            + &checksum_line(&digest, "out/b c.wav")
+            + &checksum_line(&digest, "out/a.wav");
+        let digests = parse_checksums(&source).expect("valid checksums");
+        assert_eq!(digests.len(), 2);
+        assert_eq!(digests["out/a.wav"], digest);
+        assert_eq!(digests["out/b c.wav"], digest);
+
+        assert!(parse_checksums("deadbeef  a.wav\n").is_err());
+    }
+}
@@ -0,0 +1,295 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::domain::voice::VoiceId;
+
+const FRONT_MATTER_FENCE: &str = "---";
+const COMMENT_PREFIX: char = '#';
+
+/// A cast voice, either a numeric style ID or a canonical [`VoiceId`].
+///
+/// Numeric IDs are accepted for convenience; stored casting is rewritten to the
+/// canonical form whenever the installed models can resolve it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CastVoice {
+    StyleId(u32),
+    Voice(VoiceId),
+}
+
+impl CastVoice {
+    /// Parses a style ID or a `<speaker-uuid>/<style-name>` voice ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is neither.
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.parse() {
+            Ok(style_id) => Ok(Self::StyleId(style_id)),
+            Err(_) => VoiceId::parse(value).map(Self::Voice),
+        }
+    }
+}
+
+impl fmt::Display for CastVoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StyleId(style_id) => write!(f, "{style_id}"),
+            Self::Voice(voice) => write!(f, "{voice}"),
+        }
+    }
+}
+
+/// Per-character voice settings. Unset fields fall back to earlier casting or CLI defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CastSettings {
+    #[serde(default, alias = "style_id", skip_serializing_if = "Option::is_none")]
+    pub voice: Option<CastVoice>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pitch: Option<f32>,
+}
+
+impl CastSettings {
+    /// Returns `self` with every field set in `overrides` replaced.
+    #[must_use]
+    pub fn merged_with(self, overrides: Self) -> Self {
+        Self {
+            voice: overrides.voice.or(self.voice),
+            rate: overrides.rate.or(self.rate),
+            pitch: overrides.pitch.or(self.pitch),
+        }
+    }
+}
+
+pub type Casting = BTreeMap<String, CastSettings>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DialogueLine {
+    pub speaker: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogueScript {
+    pub cast: Casting,
+    pub lines: Vec<DialogueLine>,
+}
+
+/// Merges script front-matter into previously stored casting, script entries winning.
+#[must_use]
+pub fn merge_casting(stored: &Casting, script: &Casting) -> Casting {
+    let mut merged = stored.clone();
+    for (name, settings) in script {
+        let entry = merged.entry(name.clone()).or_default();
+        *entry = std::mem::take(entry).merged_with(settings.clone());
+    }
+    merged
+}
+
+/// Parses a dialogue script.
+///
+/// An optional front-matter block fenced by `---` lines declares casting as
+/// `Name: voice=3 rate=1.1 pitch=0.05`, where `voice` is a style ID or a
+/// `<speaker-uuid>/<style-name>` voice ID. The body uses `Name: text` lines; a line
+/// without a speaker prefix continues the previous speaker. Blank lines and lines
+/// starting with `#` are ignored.
+///
+/// # Errors
+///
+/// Returns an error for malformed front-matter or body text before the first speaker.
+pub fn parse_dialogue_script(source: &str) -> Result<DialogueScript> {
+    let mut lines = source.lines().enumerate().peekable();
+    let mut cast = Casting::new();
+
+    if lines
+        .peek()
+        .is_some_and(|(_, line)| line.trim() == FRONT_MATTER_FENCE)
+    {
+        lines.next();
+        let mut closed = false;
+        for (index, line) in lines.by_ref() {
+            let line = line.trim();
+            if line == FRONT_MATTER_FENCE {
+                closed = true;
+                break;
+            }
+            if line.is_empty() || line.starts_with(COMMENT_PREFIX) {
+                continue;
+            }
+            let (name, settings) = parse_cast_entry(line)
+                .map_err(|error| anyhow!("Front-matter line {}: {error}", index + 1))?;
+            cast.insert(name, settings);
+        }
+        if !closed {
+            return Err(anyhow!("Front-matter block is missing its closing '---'"));
+        }
+    }
+
+    let mut dialogue = Vec::<DialogueLine>::new();
+    for (index, line) in lines {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(COMMENT_PREFIX) {
+            continue;
+        }
+        match split_speaker_prefix(line) {
+            Some((speaker, text)) => dialogue.push(DialogueLine {
+                speaker: speaker.to_owned(),
+                text: text.to_owned(),
+            }),
+            None => match dialogue.last_mut() {
+                Some(previous) => {
+                    previous.text.push('\n');
+                    previous.text.push_str(line);
+                }
+                None => {
+                    return Err(anyhow!(
+                        "Line {}: expected 'Name: text' before any continuation line",
+                        index + 1
+                    ));
+                }
+            },
+        }
+    }
+
+    Ok(DialogueScript {
+        cast,
+        lines: dialogue,
+    })
+}
+
+fn split_speaker_prefix(line: &str) -> Option<(&str, &str)> {
+    let (speaker, text) = line.split_once([':', '：'])?;
+    let speaker = speaker.trim();
+    let text = text.trim();
+    let plausible_name = !speaker.is_empty() && !speaker.contains(char::is_whitespace);
+    (plausible_name && !text.is_empty()).then_some((speaker, text))
+}
+
+fn parse_cast_entry(line: &str) -> Result<(String, CastSettings)> {
+    let (name, settings) = line
+        .split_once([':', '：'])
+        .ok_or_else(|| anyhow!("expected 'Name: key=value ...'"))?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(anyhow!("character name is empty"));
+    }
+
+    let mut cast = CastSettings::default();
+    for pair in settings.split([' ', ',']).filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| anyhow!("expected key=value, got '{pair}'"))?;
+        match key {
+            "voice" | "style_id" => {
+                cast.voice =
+                    Some(CastVoice::parse(value).map_err(|_| anyhow!("invalid voice '{value}'"))?);
+            }
+            "rate" => {
+                cast.rate = Some(
+                    value
+                        .parse()
+                        .map_err(|_| anyhow!("invalid rate '{value}'"))?,
+                );
+            }
+            "pitch" => {
+                cast.pitch = Some(
+                    value
+                        .parse()
+                        .map_err(|_| anyhow!("invalid pitch '{value}'"))?,
+                );
+            }
+            other => return Err(anyhow!("unknown setting '{other}'")),
+        }
+    }
+
+    Ok((name.to_owned(), cast))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_front_matter_and_body() {
+        let script = parse_dialogue_script(
+            "---\nずんだもん: voice=3 rate=1.1\nめたん: voice=2, pitch=0.05\nナレーター: voice=388f246b-8c41-4ac1-8e2d-5d79f3ff56d9/ノーマル\n---\nずんだもん: こんにちは\nめたん：やあ\n続きの行\n",
+        )
+        .expect("script should parse");
+
+        assert_eq!(
+            script.cast.get("ずんだもん"),
+            Some(&CastSettings {
+                voice: Some(CastVoice::StyleId(3)),
+                rate: Some(1.1),
+                pitch: None,
+            })
+        );
+        assert_eq!(
+            script.cast.get("めたん").and_then(|cast| cast.pitch),
+            Some(0.05)
+        );
+        assert_eq!(
+            script
+                .cast
+                .get("ナレーター")
+                .and_then(|cast| cast.voice.clone()),
+            Some(CastVoice::Voice(VoiceId {
+                speaker_uuid: "388f246b-8c41-4ac1-8e2d-5d79f3ff56d9".to_owned(),
+                style_name: "ノーマル".to_owned(),
+            }))
+        );
+        assert_eq!(script.lines.len(), 2);
+        assert_eq!(script.lines[1].speaker, "めたん");
+        assert_eq!(script.lines[1].text, "やあ\n続きの行");
+    }
+
+    #[test]
+    fn script_casting_overrides_only_declared_fields() {
+        let stored = Casting::from([(
+            "ずんだもん".to_owned(),
+            CastSettings {
+                voice: Some(CastVoice::StyleId(3)),
+                rate: Some(1.2),
+                pitch: None,
+            },
+        )]);
+        let script = Casting::from([(
+            "ずんだもん".to_owned(),
+            CastSettings {
+                rate: Some(0.9),
+                ..CastSettings::default()
+            },
+        )]);
+
+        let merged = merge_casting(&stored, &script);
+
+        assert_eq!(
+            merged.get("ずんだもん"),
+            Some(&CastSettings {
+                voice: Some(CastVoice::StyleId(3)),
+                rate: Some(0.9),
+                pitch: None,
+            })
+        );
+    }
+
+    #[test]
+    fn stored_numeric_style_ids_still_load() {
+        let cast: CastSettings = serde_json::from_str(r#"{"style_id":3}"#).expect("legacy casting");
+        assert_eq!(cast.voice, Some(CastVoice::StyleId(3)));
+        assert_eq!(
+            serde_json::to_string(&cast).expect("serialize"),
+            r#"{"voice":3}"#
+        );
+    }
+
+    #[test]
+    fn rejects_unclosed_front_matter_and_orphan_text() {
+        assert!(parse_dialogue_script("---\nA: voice=1\n").is_err());
+        assert!(parse_dialogue_script("orphan line").is_err());
+    }
+}
@@ -0,0 +1,126 @@
+use anyhow::{Result, anyhow};
+
+const MARKER_OPEN: &str = "((";
+const MARKER_CLOSE: &str = "))";
+const MARKER_KEY: &str = "voice";
+
+/// Text spoken in one voice; `voice: None` is the voice chosen for the whole utterance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VoiceRun<'a> {
+    pub voice: Option<&'a str>,
+    pub text: &'a str,
+}
+
+/// Splits plain text at inline `((voice: NAME))` markers.
+///
+/// Each marker switches the voice for the text after it, up to the next marker.
+/// Text before the first marker keeps the utterance's own voice. Other
+/// double-parenthesized text, such as `((笑))`, is spoken as written. Blank runs
+/// are dropped, so text with no markers is a single run of the whole text.
+///
+/// # Errors
+///
+/// Returns an error if a marker is not closed or names no voice.
+pub fn split_voice_markers(text: &str) -> Result<Vec<VoiceRun<'_>>> {
+    let mut runs = Vec::new();
+    let mut voice = None;
+    let mut run_start = 0;
+    let mut search = 0;
+
+    while let Some(found) = text[search..].find(MARKER_OPEN) {
+        let open = search + found;
+        let body_start = open + MARKER_OPEN.len();
+        let Some(marker) = parse_marker(&text[body_start..]) else {
+            search = body_start;
+            continue;
+        };
+        let (name, consumed) = marker?;
+        push_run(&mut runs, voice, &text[run_start..open]);
+        voice = Some(name);
+        run_start = body_start + consumed;
+        search = run_start;
+    }
+    push_run(&mut runs, voice, &text[run_start..]);
+    Ok(runs)
+}
+
+fn push_run<'a>(runs: &mut Vec<VoiceRun<'a>>, voice: Option<&'a str>, text: &'a str) {
+    if !text.trim().is_empty() {
+        runs.push(VoiceRun { voice, text });
+    }
+}
+
+/// Reads `voice: NAME))` after a `((`, returning the name and bytes consumed.
+///
+/// Returns `None` when the parentheses are not a voice marker at all.
+fn parse_marker(body: &str) -> Option<Result<(&str, usize)>> {
+    let rest = body
+        .trim_start()
+        .strip_prefix(MARKER_KEY)?
+        .trim_start()
+        .strip_prefix([':', '：'])?;
+    let unclosed = || {
+        anyhow!(
+            "Voice marker '{MARKER_OPEN}{}' is missing its closing '{MARKER_CLOSE}'",
+            body.chars().take(20).collect::<String>()
+        )
+    };
+    let Some(close) = rest.find(MARKER_CLOSE) else {
+        return Some(Err(unclosed()));
+    };
+    let name = rest[..close].trim();
+    if name.contains(MARKER_OPEN) {
+        return Some(Err(unclosed()));
+    }
+    if name.is_empty() {
+        return Some(Err(anyhow!("Voice marker names no voice")));
+    }
+    Some(Ok((
+        name,
+        body.len() - rest.len() + close + MARKER_CLOSE.len(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn switches_voices_at_markers_and_keeps_other_parentheses() {
+        let runs = split_voice_markers(
+            "こんにちは。((voice: tsumugi))やっほー((笑))。 ((voice：3)) ((voice: zundamon))なのだ",
+        )
+        .expect("valid markers");
+        assert_eq!(
+            runs,
+            vec![
+                VoiceRun {
+                    voice: None,
+                    text: "こんにちは。",
+                },
+                VoiceRun {
+                    voice: Some("tsumugi"),
+                    text: "やっほー((笑))。 ",
+                },
+                VoiceRun {
+                    voice: Some("zundamon"),
+                    text: "なのだ",
+                },
+            ]
+        );
+        assert_eq!(
+            split_voice_markers("ただのテキスト").expect("plain text"),
+            vec![VoiceRun {
+                voice: None,
+                text: "ただのテキスト",
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_unclosed_and_empty_markers() {
+        assert!(split_voice_markers("((voice: tsumugi こんにちは").is_err());
+        assert!(split_voice_markers("((voice: tsumugi ((voice: 3))").is_err());
+        assert!(split_voice_markers("((voice: ))こんにちは").is_err());
+    }
+}
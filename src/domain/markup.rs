@@ -0,0 +1,294 @@
+use anyhow::{Context, Result, anyhow, bail, ensure};
+use std::time::Duration;
+
+use crate::domain::synthesis::postprocess::parse_transpose;
+use crate::domain::synthesis::timing::parse_duration_spec;
+
+/// VOICEVOX pitch units per semitone: pitch shifts log F0, and a semitone is ln 2 / 12.
+pub const PITCH_PER_SEMITONE: f32 = std::f32::consts::LN_2 / 12.0;
+
+/// Rate factor inside `<emphasis>`.
+const EMPHASIS_RATE_SCALE: f32 = 0.9;
+/// Intonation factor inside `<emphasis>`.
+const EMPHASIS_INTONATION_SCALE: f32 = 1.3;
+
+/// Prosody changes in effect for a stretch of marked-up text, relative to the
+/// utterance's own settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Prosody {
+    pub rate_scale: f32,
+    /// Added to the utterance's pitch, in VOICEVOX pitch units.
+    pub pitch_shift: f32,
+    pub intonation_scale: f32,
+}
+
+impl Default for Prosody {
+    fn default() -> Self {
+        Self {
+            rate_scale: 1.0,
+            pitch_shift: 0.0,
+            intonation_scale: 1.0,
+        }
+    }
+}
+
+/// One piece of a marked-up utterance, in speaking order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarkupPiece<'a> {
+    /// Text spoken in one voice and prosody; `voice: None` is the utterance's own voice.
+    Speech {
+        text: String,
+        voice: Option<&'a str>,
+        prosody: Prosody,
+    },
+    Break(Duration),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Scope<'a> {
+    tag: &'a str,
+    voice: Option<&'a str>,
+    prosody: Prosody,
+}
+
+/// Parses the small SSML-like tag set `voicevox-say --markup` understands.
+///
+/// `<break time="500ms"/>` inserts silence, `<prosody rate="1.2" pitch="+2st">`
+/// scales the rate and shifts the pitch by semitones, `<voice id="3">` (or a voice
+/// name) switches voices, and `<emphasis>` speaks a little slower with livelier
+/// intonation. Tags nest; a `<speak>` root is accepted and ignored. `&lt;`, `&gt;`,
+/// `&amp;`, `&quot;` and `&apos;` stand for their characters. Blank text is dropped.
+///
+/// # Errors
+///
+/// Returns an error for unknown tags or attributes, invalid values, or tags that
+/// are not closed in order.
+pub fn parse_markup(text: &str) -> Result<Vec<MarkupPiece<'_>>> {
+    let root = Scope {
+        tag: "",
+        voice: None,
+        prosody: Prosody::default(),
+    };
+    let mut pieces = Vec::new();
+    let mut scopes: Vec<Scope<'_>> = Vec::new();
+    let mut rest = text;
+
+    while let Some(open) = rest.find('<') {
+        let current = scopes.last().copied().unwrap_or(root);
+        push_speech(&mut pieces, current, &rest[..open])?;
+        let close = rest[open..]
+            .find('>')
+            .with_context(|| format!("Unclosed tag starting at '{}'", preview(&rest[open..])))?;
+        let tag = &rest[open + 1..open + close];
+        rest = &rest[open + close + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim();
+            ensure!(
+                scopes.last().is_some_and(|scope| scope.tag == name),
+                "Unexpected closing tag </{name}>"
+            );
+            scopes.pop();
+            continue;
+        }
+
+        let (body, self_closing) = match tag.strip_suffix('/') {
+            Some(body) => (body, true),
+            None => (tag, false),
+        };
+        let (name, attributes) = parse_tag(body)?;
+        if name == "break" {
+            ensure!(self_closing, "<break> must be written as <break .../>");
+            pieces.push(MarkupPiece::Break(break_time(&attributes)?));
+            continue;
+        }
+        ensure!(!self_closing, "<{name}/> has no content to apply to");
+        let scope = open_scope(current, name, &attributes)?;
+        scopes.push(scope);
+    }
+
+    push_speech(&mut pieces, root, rest)?;
+    if let Some(unclosed) = scopes.last() {
+        bail!("<{}> is never closed", unclosed.tag);
+    }
+    Ok(pieces)
+}
+
+fn push_speech<'a>(pieces: &mut Vec<MarkupPiece<'a>>, scope: Scope<'a>, raw: &str) -> Result<()> {
+    if raw.trim().is_empty() {
+        return Ok(());
+    }
+    pieces.push(MarkupPiece::Speech {
+        text: decode_entities(raw)?,
+        voice: scope.voice,
+        prosody: scope.prosody,
+    });
+    Ok(())
+}
+
+fn open_scope<'a>(
+    parent: Scope<'a>,
+    name: &'a str,
+    attributes: &[(&'a str, &'a str)],
+) -> Result<Scope<'a>> {
+    let mut scope = Scope {
+        tag: name,
+        ..parent
+    };
+    match name {
+        "speak" => ensure!(attributes.is_empty(), "<speak> takes no attributes"),
+        "emphasis" => {
+            ensure!(attributes.is_empty(), "<emphasis> takes no attributes");
+            scope.prosody.rate_scale *= EMPHASIS_RATE_SCALE;
+            scope.prosody.intonation_scale *= EMPHASIS_INTONATION_SCALE;
+        }
+        "voice" => match attributes {
+            [("id" | "name", voice)] if !voice.trim().is_empty() => {
+                scope.voice = Some(voice.trim());
+            }
+            _ => bail!("<voice> needs exactly one id or name attribute"),
+        },
+        "prosody" => {
+            ensure!(!attributes.is_empty(), "<prosody> needs rate or pitch");
+            for &(key, value) in attributes {
+                match key {
+                    "rate" => scope.prosody.rate_scale *= parse_rate_scale(value)?,
+                    "pitch" => {
+                        scope.prosody.pitch_shift += parse_transpose(value)? * PITCH_PER_SEMITONE;
+                    }
+                    _ => bail!("Unknown <prosody> attribute '{key}' (expected rate or pitch)"),
+                }
+            }
+        }
+        _ => bail!("Unknown tag <{name}> (expected break, prosody, voice, emphasis, or speak)"),
+    }
+    Ok(scope)
+}
+
+fn break_time(attributes: &[(&str, &str)]) -> Result<Duration> {
+    match attributes {
+        [("time", time)] => parse_duration_spec(time),
+        _ => bail!("<break/> needs exactly one time attribute, e.g. time=\"500ms\""),
+    }
+}
+
+/// Reads `1.2` or `120%` as a rate factor.
+fn parse_rate_scale(value: &str) -> Result<f32> {
+    let trimmed = value.trim();
+    let (number, divisor) = match trimmed.strip_suffix('%') {
+        Some(number) => (number, 100.0),
+        None => (trimmed, 1.0),
+    };
+    number
+        .trim()
+        .parse::<f32>()
+        .ok()
+        .map(|scale| scale / divisor)
+        .filter(|scale| scale.is_finite() && *scale > 0.0)
+        .ok_or_else(|| anyhow!("Invalid prosody rate '{value}' (expected e.g. 1.2 or 120%)"))
+}
+
+/// Splits `name key="value" ...` into the tag name and its attributes.
+fn parse_tag(body: &str) -> Result<(&str, Vec<(&str, &str)>)> {
+    let body = body.trim();
+    let name_end = body.find(char::is_whitespace).unwrap_or(body.len());
+    let (name, mut rest) = body.split_at(name_end);
+    ensure!(!name.is_empty(), "Empty tag");
+
+    let mut attributes = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok((name, attributes));
+        }
+        let (key, after_key) = rest
+            .split_once('=')
+            .with_context(|| format!("Attribute without a value in <{name}>"))?;
+        let after_key = after_key.trim_start();
+        let quote = after_key
+            .chars()
+            .next()
+            .filter(|quote| matches!(quote, '"' | '\''))
+            .with_context(|| format!("Unquoted attribute value in <{name}>"))?;
+        let value_end = after_key[1..]
+            .find(quote)
+            .with_context(|| format!("Unterminated attribute value in <{name}>"))?;
+        attributes.push((key.trim(), &after_key[1..=value_end]));
+        rest = &after_key[value_end + 2..];
+    }
+}
+
+fn decode_entities(raw: &str) -> Result<String> {
+    let mut decoded = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find(';')
+            .with_context(|| format!("Unterminated entity at '{}'", preview(&rest[start..])))?;
+        decoded.push(match &rest[start + 1..start + end] {
+            "lt" => '<',
+            "gt" => '>',
+            "amp" => '&',
+            "quot" => '"',
+            "apos" => '\'',
+            other => bail!("Unknown entity '&{other};'"),
+        });
+        rest = &rest[start + end + 1..];
+    }
+    decoded.push_str(rest);
+    Ok(decoded)
+}
+
+fn preview(text: &str) -> String {
+    text.chars().take(20).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn speech<'a>(text: &str, voice: Option<&'a str>, prosody: Prosody) -> MarkupPiece<'a> {
+        MarkupPiece::Speech {
+            text: text.to_string(),
+            voice,
+            prosody,
+        }
+    }
+
+    #[test]
+    fn nests_voices_prosody_and_breaks() {
+        let pieces = parse_markup(
+            "<speak>こんにちは<break time=\"500ms\"/><voice id=\"3\">ずんだ<prosody rate=\"120%\" pitch=\"+2st\">もん&amp;</prosody></voice></speak>",
+        )
+        .expect("valid markup");
+        let faster = Prosody {
+            rate_scale: 1.2,
+            pitch_shift: 2.0 * PITCH_PER_SEMITONE,
+            ..Prosody::default()
+        };
+        assert_eq!(
+            pieces,
+            vec![
+                speech("こんにちは", None, Prosody::default()),
+                MarkupPiece::Break(Duration::from_millis(500)),
+                speech("ずんだ", Some("3"), Prosody::default()),
+                speech("もん&", Some("3"), faster),
+            ]
+        );
+        assert_eq!(
+            parse_markup("ただのテキスト").expect("plain text"),
+            vec![speech("ただのテキスト", None, Prosody::default())]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_and_mismatched_tags() {
+        assert!(parse_markup("<audio src=\"a.wav\"/>").is_err());
+        assert!(parse_markup("<voice id=\"3\">こんにちは").is_err());
+        assert!(parse_markup("<voice id=\"3\">a</prosody>").is_err());
+        assert!(parse_markup("<prosody speed=\"2\">a</prosody>").is_err());
+        assert!(parse_markup("<break/>").is_err());
+        assert!(parse_markup("a &nbsp; b").is_err());
+    }
+}
@@ -1,3 +1,21 @@
+pub mod anki;
+pub mod backup;
+pub mod batch;
+pub mod checksums;
+pub mod dialogue;
+pub mod inline_voice;
+pub mod markup;
+pub mod preset;
+pub mod regression;
+pub mod sentiment;
+pub mod subtitles;
 pub mod synthesis;
+pub mod template;
 pub mod text_to_speech;
+pub mod user_dict;
 pub mod voice;
+pub mod voice_alias;
+pub mod voice_name;
+pub mod voice_pack;
+pub mod voice_similarity;
+pub mod vvproj;
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::domain::voice::VoiceId;
+
+/// Named synthesis settings, optionally tied to the voice they were made for.
+///
+/// Scales use the VOICEVOX editor's meaning: `1.0` speed, intonation, and volume
+/// and `0.0` pitch are neutral, and phoneme lengths are silence in seconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VoicePreset {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voice: Option<VoiceId>,
+    pub rate: f32,
+    pub pitch: f32,
+    pub intonation: f32,
+    pub volume: f32,
+    pub pre_phoneme_length: f32,
+    pub post_phoneme_length: f32,
+}
+
+impl Default for VoicePreset {
+    fn default() -> Self {
+        Self {
+            voice: None,
+            rate: 1.0,
+            pitch: 0.0,
+            intonation: 1.0,
+            volume: 1.0,
+            pre_phoneme_length: 0.1,
+            post_phoneme_length: 0.1,
+        }
+    }
+}
+
+/// Presets keyed by their user-visible name.
+pub type VoicePresets = BTreeMap<String, VoicePreset>;
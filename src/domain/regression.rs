@@ -0,0 +1,305 @@
+use anyhow::{Result, ensure};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::f32::consts::PI;
+
+use crate::domain::synthesis::wav::parse_wav_header;
+
+/// Sentences synthesized by `--regression record`, keyed by the id used in reports.
+///
+/// They cover plain statements, question intonation, numerals, long vowels, and
+/// Latin text, which are the areas most likely to shift after an upgrade.
+pub const REGRESSION_CORPUS: &[(&str, &str)] = &[
+    ("greeting", "こんにちは、今日もよろしくお願いします。"),
+    ("question", "明日の天気は晴れるでしょうか？"),
+    (
+        "numbers",
+        "会議は午後三時十五分から、第二会議室で始まります。",
+    ),
+    (
+        "long_vowels",
+        "ゆーっくり、のーんびり、すーっと進みましょう。",
+    ),
+    ("latin", "VOICEVOXのバージョン0.14で、APIが追加されました。"),
+];
+
+/// Baseline file layout version; `check` refuses baselines it does not understand.
+pub const REGRESSION_BASELINE_VERSION: u32 = 1;
+
+/// Number of log-spaced frequency bands in a fingerprint.
+pub const FINGERPRINT_BANDS: usize = 24;
+
+/// Mean per-band level change, in dB, above which a clip counts as drifted.
+pub const MAX_BAND_DRIFT_DB: f32 = 3.0;
+
+/// Relative length change above which a clip counts as drifted.
+pub const MAX_DURATION_DRIFT: f32 = 0.1;
+
+const FRAME_LEN: usize = 1024;
+const HOP_LEN: usize = FRAME_LEN / 2;
+const LOWEST_BAND_HZ: f32 = 80.0;
+/// Frames quieter than this mean square are silence and do not shape the spectrum.
+const SILENT_FRAME_POWER: f32 = 1e-6;
+const POWER_FLOOR: f32 = 1e-12;
+/// Bands this far below the loudest one are clamped, so near-empty bands do not
+/// turn tiny leakage differences into large dB swings.
+const DYNAMIC_RANGE_DB: f32 = 60.0;
+
+/// Compact description of how a clip sounds: its length and average spectrum.
+///
+/// Averaging over time makes the fingerprint insensitive to small timing shifts,
+/// while changes in timbre, pitch range, or loudness still move the band levels.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpectralFingerprint {
+    pub duration_ms: u64,
+    /// Mean energy per band in dB, lowest band first.
+    pub bands_db: Vec<f32>,
+}
+
+/// One recorded corpus sentence.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegressionClip {
+    pub text: String,
+    pub fingerprint: SpectralFingerprint,
+}
+
+/// Contents of `baseline.json` in a regression directory.
+///
+/// The voice and rate are stored so `check` re-synthesizes under the recorded
+/// conditions regardless of the options it is invoked with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegressionBaseline {
+    pub version: u32,
+    pub style_id: u32,
+    pub rate: f32,
+    pub clips: BTreeMap<String, RegressionClip>,
+}
+
+/// How far a clip moved from its baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FingerprintDrift {
+    pub band_drift_db: f32,
+    /// Relative change in length; `0.1` means 10% longer or shorter.
+    pub duration_drift: f32,
+}
+
+impl FingerprintDrift {
+    #[must_use]
+    pub fn is_significant(&self) -> bool {
+        self.band_drift_db > MAX_BAND_DRIFT_DB || self.duration_drift > MAX_DURATION_DRIFT
+    }
+}
+
+/// Computes the spectral fingerprint of a 16-bit PCM WAV.
+///
+/// # Errors
+///
+/// Returns an error if the WAV is malformed, not 16-bit, or contains no samples.
+pub fn fingerprint_wav(wav_data: &[u8]) -> Result<SpectralFingerprint> {
+    let header = parse_wav_header(wav_data)?;
+    ensure!(
+        header.bits_per_sample == 16,
+        "Fingerprints need 16-bit PCM, got {} bits",
+        header.bits_per_sample
+    );
+    let channels = usize::from(header.channels);
+    ensure!(channels > 0, "WAV declares no channels");
+    let samples: Vec<f32> = wav_data[header.data_offset..header.data_offset + header.data_size]
+        .chunks_exact(2 * channels)
+        .map(|frame| {
+            let sum: f32 = frame
+                .chunks_exact(2)
+                .map(|pair| f32::from(i16::from_le_bytes([pair[0], pair[1]])))
+                .sum();
+            sum / (channels as f32 * 32_768.0)
+        })
+        .collect();
+    ensure!(!samples.is_empty(), "WAV contains no samples");
+
+    let sample_rate = header.sample_rate as f32;
+    let band_of_bin = band_lookup(sample_rate);
+    let window: Vec<f32> = (0..FRAME_LEN)
+        .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / FRAME_LEN as f32).cos())
+        .collect();
+
+    let mut band_power = [0.0f32; FINGERPRINT_BANDS];
+    let mut voiced_frames = 0usize;
+    // A trailing partial frame is only analyzed when the clip is shorter than one frame.
+    let last_start = samples.len().saturating_sub(FRAME_LEN);
+    let mut start = 0;
+    loop {
+        let end = (start + FRAME_LEN).min(samples.len());
+        let frame = &samples[start..end];
+        let mean_square =
+            frame.iter().map(|sample| sample * sample).sum::<f32>() / FRAME_LEN as f32;
+        if mean_square > SILENT_FRAME_POWER {
+            let mut re = vec![0.0f32; FRAME_LEN];
+            let mut im = vec![0.0f32; FRAME_LEN];
+            for (slot, (sample, weight)) in re.iter_mut().zip(frame.iter().zip(&window)) {
+                *slot = sample * weight;
+            }
+            fft(&mut re, &mut im);
+            for (bin, band) in band_of_bin.iter().enumerate() {
+                if let Some(band) = band {
+                    band_power[*band] += re[bin] * re[bin] + im[bin] * im[bin];
+                }
+            }
+            voiced_frames += 1;
+        }
+        start += HOP_LEN;
+        if start > last_start {
+            break;
+        }
+    }
+
+    let frames = voiced_frames.max(1) as f32;
+    let levels: Vec<f32> = band_power
+        .iter()
+        .map(|power| 10.0 * (power / frames).max(POWER_FLOOR).log10())
+        .collect();
+    let floor = levels.iter().copied().fold(f32::MIN, f32::max) - DYNAMIC_RANGE_DB;
+    Ok(SpectralFingerprint {
+        duration_ms: (samples.len() as u64 * 1000) / u64::from(header.sample_rate.max(1)),
+        bands_db: levels.into_iter().map(|level| level.max(floor)).collect(),
+    })
+}
+
+/// Maps each FFT bin below Nyquist to its log-spaced band, or `None` below the lowest band.
+fn band_lookup(sample_rate: f32) -> Vec<Option<usize>> {
+    let nyquist = sample_rate / 2.0;
+    let span = (nyquist / LOWEST_BAND_HZ).ln();
+    (0..FRAME_LEN / 2)
+        .map(|bin| {
+            let frequency = bin as f32 * sample_rate / FRAME_LEN as f32;
+            if frequency < LOWEST_BAND_HZ {
+                return None;
+            }
+            let position = (frequency / LOWEST_BAND_HZ).ln() / span;
+            Some(((position * FINGERPRINT_BANDS as f32) as usize).min(FINGERPRINT_BANDS - 1))
+        })
+        .collect()
+}
+
+/// In-place iterative radix-2 FFT; `re` and `im` must share a power-of-two length.
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f32;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let twiddled_re = re[b] * cos - im[b] * sin;
+                let twiddled_im = re[b] * sin + im[b] * cos;
+                re[b] = re[a] - twiddled_re;
+                im[b] = im[a] - twiddled_im;
+                re[a] += twiddled_re;
+                im[a] += twiddled_im;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Measures how far `current` moved from `baseline`.
+///
+/// # Errors
+///
+/// Returns an error if the fingerprints have different band counts.
+pub fn compare_fingerprints(
+    baseline: &SpectralFingerprint,
+    current: &SpectralFingerprint,
+) -> Result<FingerprintDrift> {
+    ensure!(
+        baseline.bands_db.len() == current.bands_db.len() && !baseline.bands_db.is_empty(),
+        "Fingerprints have different band layouts ({} vs {})",
+        baseline.bands_db.len(),
+        current.bands_db.len()
+    );
+    let band_drift_db = baseline
+        .bands_db
+        .iter()
+        .zip(&current.bands_db)
+        .map(|(before, after)| (before - after).abs())
+        .sum::<f32>()
+        / baseline.bands_db.len() as f32;
+    let duration_drift = if baseline.duration_ms == 0 {
+        if current.duration_ms == 0 {
+            0.0
+        } else {
+            f32::INFINITY
+        }
+    } else {
+        baseline.duration_ms.abs_diff(current.duration_ms) as f32 / baseline.duration_ms as f32
+    };
+    Ok(FingerprintDrift {
+        band_drift_db,
+        duration_drift,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::synthesis::wav::make_wav;
+
+    fn tone(frequency: f32, amplitude: f32, seconds: f32) -> Vec<u8> {
+        let sample_rate = 24_000.0;
+        let pcm: Vec<u8> = (0..(sample_rate * seconds) as usize)
+            .flat_map(|i| {
+                let phase = 2.0 * PI * frequency * i as f32 / sample_rate;
+                ((phase.sin() * amplitude * 32_767.0) as i16).to_le_bytes()
+            })
+            .collect();
+        make_wav(&pcm, 1, 24_000, 16)
+    }
+
+    #[test]
+    fn tone_energy_lands_in_its_band() {
+        let fingerprint = fingerprint_wav(&tone(1_000.0, 0.5, 1.0)).expect("fingerprint");
+        assert_eq!(fingerprint.duration_ms, 1_000);
+        assert_eq!(fingerprint.bands_db.len(), FINGERPRINT_BANDS);
+
+        let loudest = fingerprint
+            .bands_db
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(band, _)| band);
+        assert_eq!(loudest, band_lookup(24_000.0)[1_000 * FRAME_LEN / 24_000]);
+    }
+
+    #[test]
+    fn drift_flags_timbre_and_length_changes_only() {
+        let baseline = fingerprint_wav(&tone(440.0, 0.5, 1.0)).expect("fingerprint");
+        let same = compare_fingerprints(&baseline, &baseline).expect("compare");
+        assert!(!same.is_significant());
+
+        let higher = fingerprint_wav(&tone(1_760.0, 0.5, 1.0)).expect("fingerprint");
+        assert!(
+            compare_fingerprints(&baseline, &higher)
+                .expect("compare")
+                .is_significant()
+        );
+
+        let longer = fingerprint_wav(&tone(440.0, 0.5, 1.5)).expect("fingerprint");
+        let drift = compare_fingerprints(&baseline, &longer).expect("compare");
+        assert!(drift.band_drift_db < MAX_BAND_DRIFT_DB);
+        assert!(drift.is_significant());
+    }
+}
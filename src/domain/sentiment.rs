@@ -0,0 +1,141 @@
+//! Sentence mood guessing for `--auto-emotion`.
+//!
+//! A deliberately small heuristic: a keyword lexicon, emoji, and punctuation
+//! score each sentence, and only clear scores move away from the neutral style.
+
+use crate::domain::voice::VoiceStyle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mood {
+    Neutral,
+    Happy,
+    Sad,
+}
+
+const HAPPY_WORDS: &[&str] = &[
+    "嬉しい",
+    "うれしい",
+    "楽しい",
+    "たのしい",
+    "やった",
+    "最高",
+    "ありがとう",
+    "おめでとう",
+    "大好き",
+    "よかった",
+    "良かった",
+    "わーい",
+    "素敵",
+    "すてき",
+];
+
+const SAD_WORDS: &[&str] = &[
+    "悲しい",
+    "かなしい",
+    "寂しい",
+    "さびしい",
+    "残念",
+    "つらい",
+    "辛い",
+    "泣",
+    "ごめん",
+    "しょんぼり",
+    "疲れた",
+    "最悪",
+    "失敗",
+];
+
+const HAPPY_EMOJI: &[char] = &[
+    '😀', '😃', '😄', '😁', '😆', '😊', '🥰', '😍', '🎉', '✨', '♪', '♫', '💕', '👍',
+];
+
+const SAD_EMOJI: &[char] = &['😢', '😭', '😞', '😔', '😥', '😓', '💔'];
+
+/// Style names tried for each mood, most fitting first.
+const HAPPY_STYLES: &[&str] = &["喜び", "楽々", "上機嫌", "あまあま"];
+const SAD_STYLES: &[&str] = &["悲しみ", "なみだめ", "しょんぼり", "かなしみ"];
+
+/// Score at or beyond which a sentence counts as happy (or, negated, sad).
+const MOOD_THRESHOLD: i32 = 2;
+
+/// Guesses the mood of one sentence.
+///
+/// Each lexicon word or emoji counts 2 toward its mood, so opposite words cancel.
+/// An exclamation mark adds 1 and a trailing ellipsis takes 1 away; punctuation
+/// alone never leaves neutral.
+#[must_use]
+pub fn classify_sentence(sentence: &str) -> Mood {
+    let count_words = |words: &[&str]| {
+        words
+            .iter()
+            .map(|word| sentence.matches(word).count())
+            .sum::<usize>()
+    };
+    let count_chars = |chars: &[char]| sentence.chars().filter(|ch| chars.contains(ch)).count();
+    let happy = count_words(HAPPY_WORDS) + count_chars(HAPPY_EMOJI);
+    let sad = count_words(SAD_WORDS) + count_chars(SAD_EMOJI);
+
+    let mut score = 2 * (happy as i32 - sad as i32);
+    if sentence.contains(['！', '!']) {
+        score += 1;
+    }
+    let trimmed = sentence.trim_end_matches(['。', '．', '\n', ' ', '　']);
+    if trimmed.ends_with('…') || trimmed.ends_with("...") {
+        score -= 1;
+    }
+
+    if score >= MOOD_THRESHOLD {
+        Mood::Happy
+    } else if score <= -MOOD_THRESHOLD {
+        Mood::Sad
+    } else {
+        Mood::Neutral
+    }
+}
+
+/// The style of `styles` (one speaker's) to read a `mood` sentence in.
+///
+/// Returns `None` for neutral sentences and when the speaker has no style for
+/// the mood, leaving the caller's own style in place.
+#[must_use]
+pub fn mood_style(mood: Mood, styles: &[VoiceStyle]) -> Option<u32> {
+    let names = match mood {
+        Mood::Neutral => return None,
+        Mood::Happy => HAPPY_STYLES,
+        Mood::Sad => SAD_STYLES,
+    };
+    names.iter().find_map(|name| {
+        styles
+            .iter()
+            .find(|style| style.name == *name)
+            .map(|style| style.id)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_by_lexicon_emoji_and_punctuation() {
+        assert_eq!(classify_sentence("やった！合格した。"), Mood::Happy);
+        assert_eq!(classify_sentence("また明日🎉"), Mood::Happy);
+        assert_eq!(classify_sentence("今日は疲れた…"), Mood::Sad);
+        assert_eq!(classify_sentence("嬉しいけど、寂しい。"), Mood::Neutral);
+        assert_eq!(classify_sentence("明日は雨です！"), Mood::Neutral);
+    }
+
+    #[test]
+    fn picks_the_speakers_style_for_the_mood() {
+        let styles =
+            [("ノーマル", 3), ("あまあま", 1), ("なみだめ", 76)].map(|(name, id)| VoiceStyle {
+                name: name.to_string(),
+                id,
+            });
+
+        assert_eq!(mood_style(Mood::Happy, &styles), Some(1));
+        assert_eq!(mood_style(Mood::Sad, &styles), Some(76));
+        assert_eq!(mood_style(Mood::Neutral, &styles), None);
+        assert_eq!(mood_style(Mood::Sad, &styles[..2]), None);
+    }
+}
@@ -0,0 +1,142 @@
+use anyhow::{Result, anyhow};
+use std::time::Duration;
+
+const TIMING_ARROW: &str = "-->";
+
+/// One timed subtitle cue. `number` is its 1-based position in the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cue {
+    pub number: usize,
+    pub start: Duration,
+    pub end: Duration,
+    pub text: String,
+}
+
+impl Cue {
+    #[must_use]
+    pub fn slot(&self) -> Duration {
+        self.end.saturating_sub(self.start)
+    }
+}
+
+fn parse_timestamp(value: &str) -> Result<Duration> {
+    let invalid = || anyhow!("Invalid SRT timestamp '{value}'");
+    let (clock, millis) = value.trim().split_once([',', '.']).ok_or_else(invalid)?;
+    let mut parts = clock.split(':').map(str::parse::<u64>);
+    let (Some(Ok(hours)), Some(Ok(minutes)), Some(Ok(seconds)), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+    let millis: u64 = millis.parse().map_err(|_| invalid())?;
+    if minutes >= 60 || seconds >= 60 || millis >= 1000 {
+        return Err(invalid());
+    }
+    Ok(Duration::from_millis(
+        ((hours * 60 + minutes) * 60 + seconds) * 1000 + millis,
+    ))
+}
+
+/// Removes `<i>`-style formatting tags that SRT files commonly carry.
+fn strip_tags(line: &str) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut in_tag = false;
+    for ch in line.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            ch if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Parses an SRT subtitle file into cues ordered as written.
+///
+/// Numeric index lines are optional; multi-line cue text is joined with spaces.
+/// Cues with empty text are dropped.
+///
+/// # Errors
+///
+/// Returns an error if a timing line is malformed or a cue ends before it starts.
+pub fn parse_srt(source: &str) -> Result<Vec<Cue>> {
+    let source = source.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let mut cues = Vec::new();
+
+    for block in source
+        .split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+    {
+        let mut lines = block.lines().skip_while(|line| line.trim().is_empty());
+        let mut timing = lines.next().unwrap_or_default();
+        if !timing.contains(TIMING_ARROW) {
+            timing = lines.next().unwrap_or_default();
+        }
+        let number = cues.len() + 1;
+        let (start, end) = timing
+            .split_once(TIMING_ARROW)
+            .ok_or_else(|| anyhow!("Cue {number} has no '{TIMING_ARROW}' timing line"))?;
+        let start = parse_timestamp(start)?;
+        // Position hints such as `X1:40` may follow the end time.
+        let end = parse_timestamp(end.split_whitespace().next().unwrap_or_default())?;
+        if end <= start {
+            return Err(anyhow!("Cue {number} ends before it starts"));
+        }
+
+        let text = lines
+            .map(|line| strip_tags(line).trim().to_owned())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !text.is_empty() {
+            cues.push(Cue {
+                number,
+                start,
+                end,
+                text,
+            });
+        }
+    }
+    Ok(cues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cues_with_tags_and_crlf() {
+        let cues = parse_srt(
+            "\u{feff}1\r\n00:00:01,000 --> 00:00:03,500\r\n<i>こんにちは</i>\r\n世界\r\n\r\n\
+             2\r\n00:01:02.250 --> 00:01:04,000 X1:40\r\nまたね\r\n",
+        )
+        .expect("srt should parse");
+
+        assert_eq!(
+            cues,
+            vec![
+                Cue {
+                    number: 1,
+                    start: Duration::from_secs(1),
+                    end: Duration::from_millis(3500),
+                    text: "こんにちは 世界".to_owned(),
+                },
+                Cue {
+                    number: 2,
+                    start: Duration::from_millis(62_250),
+                    end: Duration::from_secs(64),
+                    text: "またね".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(cues[0].slot(), Duration::from_millis(2500));
+    }
+
+    #[test]
+    fn rejects_bad_timing() {
+        assert!(parse_srt("1\n00:00:01,000 -> 00:00:02,000\ntext\n").is_err());
+        assert!(parse_srt("1\n00:00:02,000 --> 00:00:01,000\ntext\n").is_err());
+        assert!(parse_srt("1\n00:00:61,000 --> 00:01:02,000\ntext\n").is_err());
+    }
+}
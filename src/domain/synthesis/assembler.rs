@@ -0,0 +1,169 @@
+use anyhow::{Context, Result, ensure};
+use std::time::Duration;
+
+use crate::domain::synthesis::wav::{WavHeader, assemble_wav, parse_wav_header};
+
+/// Joins ordered audio segments and silences into one WAV without gaps or clicks.
+///
+/// PCM is copied back to back, so segments play exactly as synthesized. The first
+/// segment fixes the format and its header chunks; every later segment must match
+/// it. Silence requested before the first segment is inserted once the format is
+/// known. The output gets a fresh header whose RIFF and data sizes match the
+/// combined audio.
+#[derive(Debug, Default)]
+pub struct Assembler {
+    format: Option<Format>,
+    pcm: Vec<u8>,
+    leading_silence: Duration,
+    segments: usize,
+}
+
+#[derive(Debug)]
+struct Format {
+    /// Header bytes of the first segment, up to its PCM.
+    template: Vec<u8>,
+    channels: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+}
+
+impl Format {
+    fn frame_bytes(&self) -> usize {
+        usize::from(self.channels) * usize::from(self.bits_per_sample / 8)
+    }
+
+    fn silence_bytes(&self, length: Duration) -> usize {
+        let frames = (length.as_secs_f64() * f64::from(self.sample_rate)).round() as usize;
+        frames.saturating_mul(self.frame_bytes())
+    }
+
+    fn matches(&self, header: &WavHeader) -> bool {
+        header.channels == self.channels
+            && header.sample_rate == self.sample_rate
+            && header.bits_per_sample == self.bits_per_sample
+    }
+}
+
+impl Assembler {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a WAV segment's audio.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the segment is malformed or its format differs from the first.
+    pub fn push_audio(&mut self, wav: &[u8]) -> Result<&mut Self> {
+        let index = self.segments;
+        let header =
+            parse_wav_header(wav).with_context(|| format!("Failed to parse segment {index}"))?;
+        let format = match &self.format {
+            Some(format) => {
+                ensure!(
+                    format.matches(&header),
+                    "Segment {index} has incompatible audio format"
+                );
+                format
+            }
+            None => {
+                let format = Format {
+                    template: wav[..header.data_offset].to_vec(),
+                    channels: header.channels,
+                    sample_rate: header.sample_rate,
+                    bits_per_sample: header.bits_per_sample,
+                };
+                ensure!(
+                    format.frame_bytes() > 0,
+                    "Segment {index} declares an empty sample format"
+                );
+                let leading = format.silence_bytes(std::mem::take(&mut self.leading_silence));
+                self.pcm.resize(leading, 0);
+                self.format.insert(format)
+            }
+        };
+        let pcm = &wav[header.data_offset..header.data_offset + header.data_size];
+        // A stray byte would shift every later sample off its frame boundary.
+        let whole_frames = pcm.len() - pcm.len() % format.frame_bytes();
+        self.pcm.extend_from_slice(&pcm[..whole_frames]);
+        self.segments += 1;
+        Ok(self)
+    }
+
+    /// Appends `length` of digital silence in the segments' format.
+    pub fn push_silence(&mut self, length: Duration) -> &mut Self {
+        match &self.format {
+            Some(format) => {
+                let bytes = format.silence_bytes(length);
+                self.pcm.resize(self.pcm.len() + bytes, 0);
+            }
+            None => self.leading_silence += length,
+        }
+        self
+    }
+
+    /// Produces the combined WAV.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no audio segment was pushed or the result exceeds 4 GB.
+    pub fn finish(self) -> Result<Vec<u8>> {
+        let format = self.format.context("No WAV segments to concatenate")?;
+        assemble_wav(
+            &format.template,
+            format.template.len(),
+            &[&self.pcm],
+            self.pcm.len(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::synthesis::wav::make_wav;
+
+    fn riff_size(wav: &[u8]) -> usize {
+        u32::from_le_bytes([wav[4], wav[5], wav[6], wav[7]]) as usize
+    }
+
+    #[test]
+    fn header_sizes_cover_audio_and_silence() {
+        let mut assembler = Assembler::new();
+        assembler
+            .push_silence(Duration::from_millis(1))
+            .push_audio(&make_wav(&[1, 0, 2, 0], 1, 1000, 16))
+            .expect("first segment")
+            .push_silence(Duration::from_millis(2))
+            .push_audio(&make_wav(&[3, 0, 4, 0, 9], 1, 1000, 16))
+            .expect("second segment");
+        let wav = assembler.finish().expect("assembled");
+
+        let header = parse_wav_header(&wav).expect("valid header");
+        assert_eq!(riff_size(&wav), wav.len() - 8);
+        assert_eq!(header.data_offset + header.data_size, wav.len());
+        assert_eq!(
+            &wav[header.data_offset..],
+            &[0, 0, 1, 0, 2, 0, 0, 0, 0, 0, 3, 0, 4, 0]
+        );
+        assert_eq!((header.channels, header.sample_rate), (1, 1000));
+    }
+
+    #[test]
+    fn rejects_mismatched_formats_and_empty_input() {
+        let mut assembler = Assembler::new();
+        assembler
+            .push_audio(&make_wav(&[1, 0], 1, 24000, 16))
+            .expect("first segment");
+        assert!(
+            assembler
+                .push_audio(&make_wav(&[1, 0, 2, 0], 2, 24000, 16))
+                .is_err()
+        );
+
+        let mut silent = Assembler::new();
+        silent.push_silence(Duration::from_secs(1));
+        assert!(silent.finish().is_err());
+    }
+}
@@ -0,0 +1,252 @@
+use anyhow::{Context, Result, bail, ensure};
+use std::time::Duration;
+
+use crate::domain::synthesis::timing::parse_duration_spec;
+
+/// Longest breath pause accepted in a range.
+pub const MAX_BREATH_PAUSE: Duration = Duration::from_secs(2);
+
+/// Marks after which a reader takes a short breath.
+const COMMA_MARKS: [char; 4] = ['、', '，', ',', '\u{FF64}'];
+/// Marks that close a clause or sentence, followed by a longer breath.
+const CLAUSE_MARKS: [char; 10] = ['。', '．', '！', '？', '!', '?', '；', '：', ';', '\n'];
+
+/// Inclusive range a breath pause is drawn from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PauseRange {
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl PauseRange {
+    /// Parses `120ms-250ms`, or a single duration for a fixed pause.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a bound is not a positive duration, `min` exceeds `max`,
+    /// or `max` is longer than [`MAX_BREATH_PAUSE`].
+    pub fn parse(value: &str) -> Result<Self> {
+        let (min, max) = match value.split_once('-') {
+            Some((min, max)) => (parse_duration_spec(min)?, parse_duration_spec(max)?),
+            None => {
+                let fixed = parse_duration_spec(value)?;
+                (fixed, fixed)
+            }
+        };
+        ensure!(min <= max, "Pause range '{value}' starts after it ends");
+        ensure!(
+            max <= MAX_BREATH_PAUSE,
+            "Pause range '{value}' exceeds {}s",
+            MAX_BREATH_PAUSE.as_secs()
+        );
+        Ok(Self { min, max })
+    }
+
+    fn sample(self, rng: &mut SplitMix64) -> Duration {
+        let span = (self.max - self.min).as_micros() as u64;
+        let offset = if span == 0 {
+            0
+        } else {
+            rng.next() % (span + 1)
+        };
+        self.min + Duration::from_micros(offset)
+    }
+}
+
+/// Randomized pauses inserted at commas and clause boundaries so long narrations
+/// sound less mechanical.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BreathPauses {
+    pub comma: PauseRange,
+    pub clause: PauseRange,
+}
+
+impl Default for BreathPauses {
+    fn default() -> Self {
+        Self {
+            comma: PauseRange {
+                min: Duration::from_millis(80),
+                max: Duration::from_millis(200),
+            },
+            clause: PauseRange {
+                min: Duration::from_millis(250),
+                max: Duration::from_millis(500),
+            },
+        }
+    }
+}
+
+impl BreathPauses {
+    /// Parses `comma=80ms-200ms,clause=250ms-500ms`; omitted keys keep their
+    /// defaults and an empty value means all defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for unknown keys or invalid ranges.
+    pub fn parse(value: &str) -> Result<Self> {
+        let mut pauses = Self::default();
+        for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let Some((key, range)) = entry.split_once('=') else {
+                bail!("Expected KEY=RANGE in breath pauses, got '{entry}'");
+            };
+            let range = PauseRange::parse(range)
+                .with_context(|| format!("Invalid {} pause", key.trim()))?;
+            match key.trim() {
+                "comma" => pauses.comma = range,
+                "clause" => pauses.clause = range,
+                other => bail!("Unknown breath pause '{other}' (expected comma or clause)"),
+            }
+        }
+        Ok(pauses)
+    }
+}
+
+/// A stretch of text up to a breath, and the pause that follows it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BreathRun<'a> {
+    pub text: &'a str,
+    /// Zero after the last run, which the surrounding audio already ends.
+    pub pause_after: Duration,
+}
+
+/// Small deterministic generator, so the same text always gets the same pauses.
+#[derive(Debug, Clone)]
+pub struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// Seeds the generator from the text being read.
+    #[must_use]
+    pub fn for_text(text: &str) -> Self {
+        // FNV-1a, stable across builds unlike the std hasher.
+        Self(text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        }))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Splits `text` after commas and clause-ending marks, drawing a pause for each
+/// break from `pauses`.
+///
+/// Marks stay with the text before them so each run keeps its natural ending
+/// intonation; runs of consecutive marks count as one break, the clause pause
+/// winning over the comma pause. Runs without any words are dropped.
+pub fn plan_breaths<'a>(
+    text: &'a str,
+    pauses: &BreathPauses,
+    rng: &mut SplitMix64,
+) -> Vec<BreathRun<'a>> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    let mut last_start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, ch)) = chars.next() {
+        let is_comma = COMMA_MARKS.contains(&ch);
+        let mut is_clause = CLAUSE_MARKS.contains(&ch);
+        if !is_comma && !is_clause {
+            continue;
+        }
+        let mut end = index + ch.len_utf8();
+        while let Some(&(next_index, next)) = chars.peek() {
+            let next_clause = CLAUSE_MARKS.contains(&next);
+            if !next_clause && !COMMA_MARKS.contains(&next) && !next.is_whitespace() {
+                break;
+            }
+            is_clause |= next_clause;
+            end = next_index + next.len_utf8();
+            chars.next();
+        }
+        let run = &text[start..end];
+        start = end;
+        if run.trim().is_empty() || run.chars().all(|ch| !ch.is_alphanumeric()) {
+            continue;
+        }
+        last_start = start - run.len();
+        let range = if is_clause {
+            pauses.clause
+        } else {
+            pauses.comma
+        };
+        runs.push(BreathRun {
+            text: run,
+            pause_after: range.sample(rng),
+        });
+    }
+    let rest = &text[start..];
+    if rest.chars().any(char::is_alphanumeric) {
+        runs.push(BreathRun {
+            text: rest,
+            pause_after: Duration::ZERO,
+        });
+    } else if let Some(last) = runs.last_mut() {
+        // Trailing quotes and brackets belong to the last run.
+        last.text = &text[last_start..];
+        last.pause_after = Duration::ZERO;
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_at_commas_and_clauses_within_ranges() {
+        let pauses = BreathPauses::parse("comma=100ms-100ms,clause=300ms-400ms").expect("spec");
+        let runs = plan_breaths(
+            "今日は、晴れです。明日は雨！？",
+            &pauses,
+            &mut SplitMix64::for_text("x"),
+        );
+
+        let texts = runs.iter().map(|run| run.text).collect::<Vec<_>>();
+        assert_eq!(texts, vec!["今日は、", "晴れです。", "明日は雨！？"]);
+        assert_eq!(runs[0].pause_after, Duration::from_millis(100));
+        assert!(
+            (Duration::from_millis(300)..=Duration::from_millis(400))
+                .contains(&runs[1].pause_after)
+        );
+        assert_eq!(runs[2].pause_after, Duration::ZERO);
+
+        let again = plan_breaths(
+            "今日は、晴れです。明日は雨！？",
+            &pauses,
+            &mut SplitMix64::for_text("x"),
+        );
+        assert_eq!(runs, again);
+
+        let quoted = plan_breaths("「はい。」", &pauses, &mut SplitMix64::for_text("x"));
+        assert_eq!(
+            quoted,
+            vec![BreathRun {
+                text: "「はい。」",
+                pause_after: Duration::ZERO,
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_pause_specs() {
+        assert_eq!(
+            BreathPauses::parse("").expect("defaults"),
+            BreathPauses::default()
+        );
+        assert_eq!(
+            BreathPauses::parse("clause=300ms").expect("fixed").clause,
+            PauseRange {
+                min: Duration::from_millis(300),
+                max: Duration::from_millis(300),
+            }
+        );
+        assert!(BreathPauses::parse("comma=300ms-100ms").is_err());
+        assert!(BreathPauses::parse("comma=1s-5s").is_err());
+        assert!(BreathPauses::parse("period=100ms").is_err());
+    }
+}
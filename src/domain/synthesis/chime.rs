@@ -0,0 +1,201 @@
+use anyhow::{Context, Result, anyhow, ensure};
+use std::path::PathBuf;
+
+use crate::domain::synthesis::wav::{WavHeader, concatenate_wav_segments, parse_wav_header};
+
+/// Earcon files named by `--chime START,END`; either side may be left empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChimeSpec {
+    pub start: Option<PathBuf>,
+    pub end: Option<PathBuf>,
+}
+
+/// Parses `start.wav,end.wav`, `start.wav,`, or `,end.wav`.
+///
+/// # Errors
+///
+/// Returns an error if the value is not two comma-separated paths or names neither.
+pub fn parse_chime_spec(value: &str) -> Result<ChimeSpec> {
+    let (start, end) = value
+        .split_once(',')
+        .filter(|(_, end)| !end.contains(','))
+        .ok_or_else(|| anyhow!("Invalid chime '{value}' (expected START.wav,END.wav)"))?;
+    let path = |part: &str| {
+        let part = part.trim();
+        (!part.is_empty()).then(|| PathBuf::from(part))
+    };
+    let spec = ChimeSpec {
+        start: path(start),
+        end: path(end),
+    };
+    ensure!(
+        spec.start.is_some() || spec.end.is_some(),
+        "Chime needs at least one file (e.g. start.wav, or ,end.wav)"
+    );
+    Ok(spec)
+}
+
+/// Loaded earcon audio played before and after the utterance.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Chimes {
+    pub start: Option<Vec<u8>>,
+    pub end: Option<Vec<u8>>,
+}
+
+impl Chimes {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.start.is_none() && self.end.is_none()
+    }
+}
+
+/// Joins the chimes around `speech` into one WAV in the speech's format.
+///
+/// Chimes may be any 16-bit PCM WAV; they are remixed to the speech's channel
+/// count and linearly resampled to its sample rate, so stock 44.1 kHz stereo
+/// earcons work with the daemon's mono output.
+///
+/// # Errors
+///
+/// Returns an error if any buffer is malformed or a chime is not 16-bit PCM.
+pub fn surround_with_chimes(speech: Vec<u8>, chimes: &Chimes) -> Result<Vec<u8>> {
+    if chimes.is_empty() {
+        return Ok(speech);
+    }
+    let target = parse_wav_header(&speech).context("Failed to parse synthesized audio")?;
+    let start = chimes
+        .start
+        .as_deref()
+        .map(|chime| conform_chime(chime, &speech, &target).context("Invalid start chime"))
+        .transpose()?;
+    let end = chimes
+        .end
+        .as_deref()
+        .map(|chime| conform_chime(chime, &speech, &target).context("Invalid end chime"))
+        .transpose()?;
+    let segments = start
+        .into_iter()
+        .chain(Some(speech))
+        .chain(end)
+        .collect::<Vec<_>>();
+    concatenate_wav_segments(&segments)
+}
+
+/// Re-encodes `chime` to match `target`, reusing `template`'s header chunks.
+fn conform_chime(chime: &[u8], template: &[u8], target: &WavHeader) -> Result<Vec<u8>> {
+    let header = parse_wav_header(chime)?;
+    ensure!(
+        header.bits_per_sample == 16 && target.bits_per_sample == 16,
+        "Chimes must be 16-bit PCM (got {} bits)",
+        header.bits_per_sample
+    );
+    ensure!(
+        header.channels > 0 && header.sample_rate > 0,
+        "Chime has an empty format"
+    );
+
+    let pcm = &chime[header.data_offset..header.data_offset + header.data_size];
+    let frames = remix(
+        pcm,
+        usize::from(header.channels),
+        usize::from(target.channels),
+    );
+    let frames = resample(&frames, header.sample_rate, target.sample_rate);
+
+    let mut wav = template[..target.data_offset].to_vec();
+    let data_size = u32::try_from(frames.len() * 2).context("Chime exceeds the WAV 4 GB limit")?;
+    let data_size_at = target.data_offset - 4;
+    wav[data_size_at..target.data_offset].copy_from_slice(&data_size.to_le_bytes());
+    for sample in frames.into_iter().flatten() {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    Ok(wav)
+}
+
+/// Splits interleaved samples into frames of `to` channels.
+///
+/// Mono is copied to every output channel; anything else is averaged to mono first
+/// unless the channel counts already match.
+fn remix(pcm: &[u8], from: usize, to: usize) -> Vec<Vec<i16>> {
+    pcm.chunks_exact(from * 2)
+        .map(|frame| {
+            let samples = frame
+                .chunks_exact(2)
+                .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+                .collect::<Vec<_>>();
+            if from == to {
+                return samples;
+            }
+            let sum: i32 = samples.iter().copied().map(i32::from).sum();
+            let mono = (sum / from as i32) as i16;
+            vec![mono; to]
+        })
+        .collect()
+}
+
+/// Linearly interpolates `frames` from rate `from` to rate `to`.
+fn resample(frames: &[Vec<i16>], from: u32, to: u32) -> Vec<Vec<i16>> {
+    if from == to || frames.is_empty() {
+        return frames.to_vec();
+    }
+    let step = f64::from(from) / f64::from(to);
+    let output_len = (frames.len() as f64 / step).round() as usize;
+    (0..output_len)
+        .map(|index| {
+            let position = index as f64 * step;
+            let before = (position.floor() as usize).min(frames.len() - 1);
+            let after = (before + 1).min(frames.len() - 1);
+            let weight = position - before as f64;
+            frames[before]
+                .iter()
+                .zip(&frames[after])
+                .map(|(&a, &b)| {
+                    (f64::from(a) + (f64::from(b) - f64::from(a)) * weight).round() as i16
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::synthesis::wav::{make_wav, wav_duration};
+    use std::time::Duration;
+
+    fn pcm16(samples: &[i16]) -> Vec<u8> {
+        samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn parses_one_or_both_chime_paths() {
+        let spec = parse_chime_spec("start.wav,end.wav").expect("both");
+        assert_eq!(spec.start, Some(PathBuf::from("start.wav")));
+        assert_eq!(spec.end, Some(PathBuf::from("end.wav")));
+        assert_eq!(parse_chime_spec(",end.wav").expect("end").start, None);
+        assert!(parse_chime_spec("start.wav").is_err());
+        assert!(parse_chime_spec(",").is_err());
+        assert!(parse_chime_spec("a,b,c").is_err());
+    }
+
+    #[test]
+    fn converts_stereo_chimes_to_the_speech_format() {
+        let speech = make_wav(&pcm16(&[100; 24_000]), 1, 24_000, 16);
+        // 0.5 s of stereo at 48 kHz whose channels average to 300.
+        let stereo = [200, 400].repeat(24_000);
+        let chimes = Chimes {
+            start: Some(make_wav(&pcm16(&stereo), 2, 48_000, 16)),
+            end: None,
+        };
+
+        let combined = surround_with_chimes(speech, &chimes).expect("combined");
+        let header = parse_wav_header(&combined).expect("header");
+        assert_eq!((header.channels, header.sample_rate), (1, 24_000));
+        assert_eq!(
+            wav_duration(&combined).expect("duration"),
+            Duration::from_millis(1500)
+        );
+        let first = &combined[header.data_offset..header.data_offset + 2];
+        assert_eq!(i16::from_le_bytes([first[0], first[1]]), 300);
+    }
+}
@@ -0,0 +1,130 @@
+use anyhow::{Result, bail};
+use std::time::Duration;
+
+/// Spoken characters per second at rate 1.0, used to estimate length before synthesis.
+///
+/// Kana run close to one mora per character and kanji usually more, so this
+/// sits at the fast end of typical VOICEVOX output and errs toward longer estimates.
+pub const ESTIMATED_CHARS_PER_SECOND: f64 = 7.0;
+
+/// What to do when text would run past `--max-duration`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverlongPolicy {
+    /// Refuse to synthesize.
+    #[default]
+    Error,
+    /// Speak only the leading text that fits, and cut the audio at the limit.
+    Truncate,
+}
+
+impl OverlongPolicy {
+    /// Parses `error` or `truncate`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for any other name.
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "error" => Ok(Self::Error),
+            "truncate" => Ok(Self::Truncate),
+            _ => bail!("Unknown overlong policy '{value}' (expected error or truncate)"),
+        }
+    }
+}
+
+/// Upper bound on spoken audio length and what happens beyond it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthLimit {
+    pub max: Duration,
+    pub policy: OverlongPolicy,
+}
+
+/// Estimates how long `text` takes to speak at `rate`, ignoring whitespace.
+#[must_use]
+pub fn estimate_speech_duration(text: &str, rate: f32) -> Duration {
+    let chars = text.chars().filter(|ch| !ch.is_whitespace()).count();
+    Duration::from_secs_f64(chars as f64 / (ESTIMATED_CHARS_PER_SECOND * f64::from(rate)))
+}
+
+/// The leading part of a text estimated to fit a length limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextCut {
+    pub text: String,
+    pub kept_segments: usize,
+    pub total_segments: usize,
+}
+
+/// Keeps the leading `segments` whose combined estimate stays within `max`.
+///
+/// When even the first segment is too long, it is cut by character count so
+/// something is still spoken.
+#[must_use]
+pub fn cut_segments_to_estimate(segments: &[String], rate: f32, max: Duration) -> TextCut {
+    let mut text = String::new();
+    let mut kept_segments = 0;
+    for segment in segments {
+        let candidate = format!("{text}{segment}");
+        if estimate_speech_duration(&candidate, rate) > max {
+            break;
+        }
+        text = candidate;
+        kept_segments += 1;
+    }
+
+    if kept_segments == 0
+        && let Some(first) = segments.first()
+    {
+        let budget = (max.as_secs_f64() * ESTIMATED_CHARS_PER_SECOND * f64::from(rate)) as usize;
+        let mut spoken = 0;
+        text = first
+            .chars()
+            .take_while(|ch| {
+                if !ch.is_whitespace() {
+                    spoken += 1;
+                }
+                spoken <= budget
+            })
+            .collect();
+    }
+
+    TextCut {
+        text,
+        kept_segments,
+        total_segments: segments.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_scale_with_length_and_rate() {
+        assert_eq!(
+            estimate_speech_duration("あいうえお かき", 1.0),
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            estimate_speech_duration("あいうえおかきくけこさしすせ", 2.0),
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            OverlongPolicy::parse(" Truncate ").expect("valid"),
+            OverlongPolicy::Truncate
+        );
+        assert!(OverlongPolicy::parse("clip").is_err());
+    }
+
+    #[test]
+    fn cuts_at_segment_boundaries_then_by_characters() {
+        let segments = ["あいうえお。", "かきくけこ。", "さしすせそ。"].map(String::from);
+
+        let cut = cut_segments_to_estimate(&segments, 1.0, Duration::from_secs(2));
+        assert_eq!(cut.text, "あいうえお。かきくけこ。");
+        assert_eq!((cut.kept_segments, cut.total_segments), (2, 3));
+
+        let cut = cut_segments_to_estimate(&segments, 1.0, Duration::from_millis(500));
+        assert_eq!(cut.text, "あいう");
+        assert_eq!(cut.kept_segments, 0);
+    }
+}
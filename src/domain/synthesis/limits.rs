@@ -1,13 +1,21 @@
 pub const DEFAULT_SYNTHESIS_RATE: f32 = 1.0;
 pub const MIN_SYNTHESIS_RATE: f32 = 0.5;
 pub const MAX_SYNTHESIS_RATE: f32 = 2.0;
-pub const MAX_SYNTHESIS_TEXT_LENGTH: usize = 10_000;
+pub const DEFAULT_SYNTHESIS_PITCH: f32 = 0.0;
+pub const MIN_SYNTHESIS_PITCH: f32 = -0.15;
+pub const MAX_SYNTHESIS_PITCH: f32 = 0.15;
+pub const MAX_SYNTHESIS_TEXT_LENGTH: usize = voicevox_text::MAX_TEXT_LENGTH;
 
 #[must_use]
 pub const fn is_valid_synthesis_rate(rate: f32) -> bool {
     rate >= MIN_SYNTHESIS_RATE && rate <= MAX_SYNTHESIS_RATE
 }
 
+#[must_use]
+pub const fn is_valid_synthesis_pitch(pitch: f32) -> bool {
+    pitch >= MIN_SYNTHESIS_PITCH && pitch <= MAX_SYNTHESIS_PITCH
+}
+
 #[cfg(kani)]
 mod kani_proofs {
     use super::*;
@@ -17,6 +25,11 @@ mod kani_proofs {
         assert!(is_valid_synthesis_rate(DEFAULT_SYNTHESIS_RATE));
     }
 
+    #[kani::proof]
+    fn default_pitch_is_valid() {
+        assert!(is_valid_synthesis_pitch(DEFAULT_SYNTHESIS_PITCH));
+    }
+
     #[kani::proof]
     fn boundary_rates_are_valid() {
         assert!(is_valid_synthesis_rate(MIN_SYNTHESIS_RATE));
@@ -0,0 +1,136 @@
+use anyhow::{Context, Result, ensure};
+use std::time::Duration;
+
+use crate::domain::synthesis::wav::parse_wav_header;
+
+/// Level below which a frame counts as silence at the edges of a clip, in dBFS.
+pub const EDGE_SILENCE_DBFS: f64 = -50.0;
+
+/// Format and level summary of a 16-bit PCM WAV, for debugging rendered assets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WavSummary {
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub bits_per_sample: u16,
+    pub duration: Duration,
+    /// Loudest sample; negative infinity for digital silence.
+    pub peak_dbfs: f64,
+    /// Average power over the whole clip; negative infinity for digital silence.
+    pub rms_dbfs: f64,
+    /// Samples at full scale, a sign the audio was clipped.
+    pub clipped_samples: usize,
+    /// Quiet audio before the first and after the last frame above [`EDGE_SILENCE_DBFS`].
+    pub leading_silence: Duration,
+    pub trailing_silence: Duration,
+}
+
+fn dbfs(level: f64) -> f64 {
+    20.0 * (level / f64::from(i16::MAX)).log10()
+}
+
+/// Measures the format and levels of a 16-bit PCM WAV.
+///
+/// # Errors
+///
+/// Returns an error if the WAV is malformed or not 16-bit PCM.
+pub fn summarize_wav(wav: &[u8]) -> Result<WavSummary> {
+    let header = parse_wav_header(wav).context("Failed to parse WAV")?;
+    ensure!(
+        header.bits_per_sample == 16,
+        "Only 16-bit PCM can be measured (got {} bits)",
+        header.bits_per_sample
+    );
+    ensure!(
+        header.channels > 0 && header.sample_rate > 0,
+        "WAV declares an empty sample format"
+    );
+
+    let channels = usize::from(header.channels);
+    let samples = wav[header.data_offset..header.data_offset + header.data_size]
+        .chunks_exact(2)
+        .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+        .collect::<Vec<_>>();
+    let frames = samples.len() / channels;
+    let frame_duration =
+        |count: usize| Duration::from_secs_f64(count as f64 / f64::from(header.sample_rate));
+
+    let peak = samples
+        .iter()
+        .map(|&sample| i32::from(sample).unsigned_abs())
+        .max()
+        .unwrap_or(0);
+    let power = samples
+        .iter()
+        .map(|&sample| f64::from(sample).powi(2))
+        .sum::<f64>()
+        / samples.len().max(1) as f64;
+    let clipped_samples = samples
+        .iter()
+        .filter(|&&sample| sample == i16::MAX || sample == i16::MIN)
+        .count();
+
+    let threshold = f64::from(i16::MAX) * 10_f64.powf(EDGE_SILENCE_DBFS / 20.0);
+    let audible = |frame: &[i16]| {
+        frame
+            .iter()
+            .any(|&sample| f64::from(sample).abs() > threshold)
+    };
+    let leading = samples
+        .chunks_exact(channels)
+        .position(audible)
+        .unwrap_or(frames);
+    let trailing = samples
+        .chunks_exact(channels)
+        .rev()
+        .position(audible)
+        .unwrap_or(frames);
+
+    Ok(WavSummary {
+        channels: header.channels,
+        sample_rate: header.sample_rate,
+        bits_per_sample: header.bits_per_sample,
+        duration: frame_duration(frames),
+        peak_dbfs: dbfs(f64::from(peak)),
+        rms_dbfs: dbfs(power.sqrt()),
+        clipped_samples,
+        leading_silence: frame_duration(leading),
+        // Silent clips are all leading silence; counting them twice would overstate it.
+        trailing_silence: frame_duration(if leading == frames { 0 } else { trailing }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::synthesis::wav::make_wav;
+
+    fn pcm(samples: &[i16]) -> Vec<u8> {
+        samples
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn measures_levels_and_edge_silence() {
+        let wav = make_wav(&pcm(&[0, 0, i16::MAX, -16384, 0]), 1, 10, 16);
+        let summary = summarize_wav(&wav).expect("valid wav");
+
+        assert_eq!(summary.duration, Duration::from_millis(500));
+        assert!(summary.peak_dbfs.abs() < 1e-9);
+        assert_eq!(summary.clipped_samples, 1);
+        assert_eq!(summary.leading_silence, Duration::from_millis(200));
+        assert_eq!(summary.trailing_silence, Duration::from_millis(100));
+        assert!(summary.rms_dbfs < 0.0 && summary.rms_dbfs > -10.0);
+    }
+
+    #[test]
+    fn silent_and_non_16_bit_audio() {
+        let silent = summarize_wav(&make_wav(&pcm(&[0, 0]), 1, 10, 16)).expect("silent");
+        assert_eq!(silent.peak_dbfs, f64::NEG_INFINITY);
+        assert_eq!(silent.leading_silence, Duration::from_millis(200));
+        assert_eq!(silent.trailing_silence, Duration::ZERO);
+
+        assert!(summarize_wav(&make_wav(&[0; 4], 1, 10, 8)).is_err());
+    }
+}
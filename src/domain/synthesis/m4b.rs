@@ -0,0 +1,625 @@
+//! Chaptered audiobook (M4B) writer.
+//!
+//! Audio is stored as Apple Lossless in uncompressed frames, since there is no
+//! pure-Rust AAC encoder; every audiobook player that reads M4B decodes ALAC.
+//! Chapters are written twice: as a QuickTime text track, which Apple players
+//! read, and as a Nero `chpl` list, which most others read.
+
+use anyhow::{Context, Result, ensure};
+
+use super::flac::BitWriter;
+use crate::domain::synthesis::wav::parse_wav_header;
+
+/// Samples per channel in each ALAC frame; Apple's default.
+const FRAME_LENGTH: usize = 4096;
+
+/// Movie timescale, so durations in `mvhd` and `tkhd` are milliseconds.
+const MOVIE_TIMESCALE: u32 = 1000;
+
+const AUDIO_TRACK_ID: u32 = 1;
+const CHAPTER_TRACK_ID: u32 = 2;
+
+/// ALAC element types.
+const SINGLE_CHANNEL_ELEMENT: u64 = 0;
+const CHANNEL_PAIR_ELEMENT: u64 = 1;
+const END_ELEMENT: u64 = 7;
+
+/// Nero chapter lists count chapters in one byte.
+const MAX_NERO_CHAPTERS: usize = 255;
+
+/// `und`, packed as three 5-bit letters.
+const UNDETERMINED_LANGUAGE: u16 = 0x55C4;
+
+/// `stik` value iTunes uses for audiobooks.
+const MEDIA_KIND_AUDIOBOOK: u8 = 2;
+
+const IDENTITY_MATRIX: [u32; 9] = [0x0001_0000, 0, 0, 0, 0x0001_0000, 0, 0, 0, 0x4000_0000];
+
+/// One chapter: its title and its audio as a 16-bit PCM WAV.
+#[derive(Debug, Clone, Copy)]
+pub struct M4bChapter<'a> {
+    pub title: &'a str,
+    pub wav: &'a [u8],
+}
+
+/// Book-level tags shown by audiobook players.
+#[derive(Debug, Clone, Copy)]
+pub struct M4bMetadata<'a> {
+    pub title: &'a str,
+    pub author: Option<&'a str>,
+}
+
+/// Sample format shared by every chapter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AudioFormat {
+    sample_rate: u32,
+    channels: u16,
+}
+
+fn boxed(kind: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(payload.len() + 8);
+    data.extend_from_slice(&(payload.len() as u32 + 8).to_be_bytes());
+    data.extend_from_slice(kind);
+    data.extend_from_slice(payload);
+    data
+}
+
+fn full_box(kind: &[u8; 4], flags: u32, payload: &[u8]) -> Vec<u8> {
+    let mut data = flags.to_be_bytes().to_vec();
+    data.extend_from_slice(payload);
+    boxed(kind, &data)
+}
+
+fn container(kind: &[u8; 4], children: &[Vec<u8>]) -> Vec<u8> {
+    boxed(kind, &children.concat())
+}
+
+fn be32s(values: &[u32]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|value| value.to_be_bytes())
+        .collect()
+}
+
+/// Encodes `samples` (interleaved) as one uncompressed ALAC frame.
+fn alac_frame(samples: &[i16], channels: usize) -> Vec<u8> {
+    let frame_samples = samples.len() / channels;
+    let partial = frame_samples < FRAME_LENGTH;
+    let mut writer = BitWriter::default();
+    let element = if channels == 1 {
+        SINGLE_CHANNEL_ELEMENT
+    } else {
+        CHANNEL_PAIR_ELEMENT
+    };
+    writer.write(element, 3);
+    writer.write(0, 4); // element instance
+    writer.write(0, 12); // unused
+    writer.write(u64::from(partial), 1); // sample count follows
+    writer.write(0, 2); // no extra low bytes
+    writer.write(1, 1); // verbatim
+    if partial {
+        writer.write(frame_samples as u64, 32);
+    }
+    for &sample in samples {
+        writer.write_signed(i64::from(sample), 16);
+    }
+    writer.write(END_ELEMENT, 3);
+    writer.finish()
+}
+
+/// The `ALACSpecificConfig` decoders need, wrapped in its `alac` box.
+fn alac_cookie(format: AudioFormat) -> Vec<u8> {
+    let mut config = Vec::with_capacity(24);
+    config.extend_from_slice(&(FRAME_LENGTH as u32).to_be_bytes());
+    config.extend_from_slice(&[0, 16, 40, 10, 14, format.channels as u8]);
+    config.extend_from_slice(&255u16.to_be_bytes()); // max run
+    config.extend_from_slice(&0u32.to_be_bytes()); // max frame bytes, unknown
+    config.extend_from_slice(&(format.sample_rate * u32::from(format.channels) * 16).to_be_bytes());
+    config.extend_from_slice(&format.sample_rate.to_be_bytes());
+    full_box(b"alac", 0, &config)
+}
+
+/// A chapter title as a QuickTime text sample, marked as UTF-8.
+fn chapter_sample(title: &str) -> Vec<u8> {
+    let mut sample = (title.len() as u16).to_be_bytes().to_vec();
+    sample.extend_from_slice(title.as_bytes());
+    sample.extend(full_box(b"encd", 0x100, &[]));
+    sample
+}
+
+/// Cuts `title` to at most `max` bytes without splitting a character.
+fn truncate_title(title: &str, max: usize) -> &str {
+    let mut end = title.len().min(max);
+    while !title.is_char_boundary(end) {
+        end -= 1;
+    }
+    &title[..end]
+}
+
+fn handler(kind: &[u8; 4], name: &str) -> Vec<u8> {
+    let mut payload = be32s(&[0]);
+    payload.extend_from_slice(kind);
+    payload.extend(be32s(&[0, 0, 0]));
+    payload.extend_from_slice(name.as_bytes());
+    payload.push(0);
+    full_box(b"hdlr", 0, &payload)
+}
+
+fn track_header(track_id: u32, flags: u32, duration_ms: u32, volume: u16) -> Vec<u8> {
+    let mut payload = be32s(&[0, 0, track_id, 0, duration_ms, 0, 0]);
+    payload.extend_from_slice(&[0, 0, 0, 0]); // layer, alternate group
+    payload.extend_from_slice(&volume.to_be_bytes());
+    payload.extend_from_slice(&[0, 0]);
+    payload.extend(be32s(&IDENTITY_MATRIX));
+    payload.extend(be32s(&[0, 0])); // width, height
+    full_box(b"tkhd", flags, &payload)
+}
+
+fn media_header(timescale: u32, duration: u32) -> Vec<u8> {
+    let mut payload = be32s(&[0, 0, timescale, duration]);
+    payload.extend_from_slice(&UNDETERMINED_LANGUAGE.to_be_bytes());
+    payload.extend_from_slice(&[0, 0]);
+    full_box(b"mdhd", 0, &payload)
+}
+
+fn data_information() -> Vec<u8> {
+    let url = full_box(b"url ", 1, &[]);
+    let mut references = be32s(&[1]);
+    references.extend(url);
+    container(b"dinf", &[full_box(b"dref", 0, &references)])
+}
+
+/// Sample tables for samples of `durations` and `sizes`, stored at `offsets`
+/// in chunks of `samples_per_chunk`.
+fn sample_table(
+    description: Vec<u8>,
+    durations: &[u32],
+    sizes: &[u32],
+    offsets: &[u32],
+    samples_per_chunk: u32,
+) -> Vec<u8> {
+    let mut runs: Vec<(u32, u32)> = Vec::new();
+    for &duration in durations {
+        match runs.last_mut() {
+            Some((count, last)) if *last == duration => *count += 1,
+            _ => runs.push((1, duration)),
+        }
+    }
+    let mut stts = be32s(&[runs.len() as u32]);
+    stts.extend(
+        runs.iter()
+            .flat_map(|&(count, duration)| be32s(&[count, duration])),
+    );
+
+    let mut stsz = be32s(&[0, sizes.len() as u32]);
+    stsz.extend(be32s(sizes));
+    let mut stco = be32s(&[offsets.len() as u32]);
+    stco.extend(be32s(offsets));
+
+    let mut stsd = be32s(&[1]);
+    stsd.extend(description);
+    container(
+        b"stbl",
+        &[
+            full_box(b"stsd", 0, &stsd),
+            full_box(b"stts", 0, &stts),
+            full_box(b"stsc", 0, &be32s(&[1, 1, samples_per_chunk, 1])),
+            full_box(b"stsz", 0, &stsz),
+            full_box(b"stco", 0, &stco),
+        ],
+    )
+}
+
+fn audio_sample_entry(format: AudioFormat) -> Vec<u8> {
+    let mut payload = vec![0; 6];
+    payload.extend_from_slice(&1u16.to_be_bytes()); // data reference
+    payload.extend_from_slice(&[0; 8]); // version, revision, vendor
+    payload.extend_from_slice(&format.channels.to_be_bytes());
+    payload.extend_from_slice(&16u16.to_be_bytes());
+    payload.extend_from_slice(&[0; 4]); // compression ID, packet size
+    payload.extend_from_slice(&(format.sample_rate << 16).to_be_bytes());
+    payload.extend(alac_cookie(format));
+    boxed(b"alac", &payload)
+}
+
+fn text_sample_entry() -> Vec<u8> {
+    let mut payload = vec![0; 6];
+    payload.extend_from_slice(&1u16.to_be_bytes()); // data reference
+    // Display flags, then justification 1; colors, text box, and font left at zero.
+    let mut description = [0u8; 43];
+    description[7] = 1;
+    payload.extend_from_slice(&description);
+    boxed(b"text", &payload)
+}
+
+/// QuickTime's base media header for a text track.
+fn text_media_header() -> Vec<u8> {
+    let mut gmin = 0x40u16.to_be_bytes().to_vec(); // graphics mode: copy
+    gmin.extend([0x8000u16; 3].iter().flat_map(|c| c.to_be_bytes()));
+    gmin.extend_from_slice(&[0; 4]); // balance, reserved
+    let mut text = 1u16.to_be_bytes().to_vec();
+    text.extend(be32s(&[0, 0, 0, 1, 0, 0, 0, 0x4000]));
+    text.extend_from_slice(&[0, 0]);
+    container(
+        b"gmhd",
+        &[full_box(b"gmin", 0, &gmin), boxed(b"text", &text)],
+    )
+}
+
+fn nero_chapters(starts_100ns: &[u64], titles: &[&str]) -> Vec<u8> {
+    let mut payload = be32s(&[0]);
+    payload.push(titles.len() as u8);
+    for (&start, title) in starts_100ns.iter().zip(titles) {
+        let title = truncate_title(title, usize::from(u8::MAX));
+        payload.extend_from_slice(&start.to_be_bytes());
+        payload.push(title.len() as u8);
+        payload.extend_from_slice(title.as_bytes());
+    }
+    full_box(b"chpl", 0x0100_0000, &payload)
+}
+
+fn metadata_item(kind: &[u8; 4], data_type: u32, value: &[u8]) -> Vec<u8> {
+    let mut data = be32s(&[data_type, 0]);
+    data.extend_from_slice(value);
+    container(kind, &[boxed(b"data", &data)])
+}
+
+fn itunes_metadata(metadata: &M4bMetadata<'_>) -> Vec<u8> {
+    const UTF8: u32 = 1;
+    const INTEGER: u32 = 21;
+    let mut items = vec![
+        metadata_item(b"\xa9nam", UTF8, metadata.title.as_bytes()),
+        metadata_item(b"\xa9alb", UTF8, metadata.title.as_bytes()),
+        metadata_item(b"\xa9gen", UTF8, b"Audiobook"),
+        metadata_item(b"stik", INTEGER, &[MEDIA_KIND_AUDIOBOOK]),
+    ];
+    if let Some(author) = metadata.author {
+        items.push(metadata_item(b"\xa9ART", UTF8, author.as_bytes()));
+    }
+    let mut handler = be32s(&[0]);
+    handler.extend_from_slice(b"mdirappl");
+    handler.extend(be32s(&[0, 0]));
+    handler.push(0);
+    let mut meta = be32s(&[0]);
+    meta.extend(full_box(b"hdlr", 0, &handler));
+    meta.extend(container(b"ilst", &items));
+    boxed(b"meta", &meta)
+}
+
+/// Reads every chapter's PCM, checking that they share one format.
+fn collect_samples(chapters: &[M4bChapter<'_>]) -> Result<(AudioFormat, Vec<i16>, Vec<u32>)> {
+    let mut format = None;
+    let mut samples = Vec::new();
+    let mut lengths = Vec::with_capacity(chapters.len());
+    for chapter in chapters {
+        let header = parse_wav_header(chapter.wav)
+            .with_context(|| format!("Failed to parse the audio of '{}'", chapter.title))?;
+        ensure!(
+            header.bits_per_sample == 16,
+            "M4B output needs 16-bit PCM, got {} bits",
+            header.bits_per_sample
+        );
+        ensure!(
+            (1..=2).contains(&header.channels),
+            "M4B output supports mono or stereo, got {} channels",
+            header.channels
+        );
+        ensure!(
+            header.sample_rate > 0 && header.sample_rate <= u32::from(u16::MAX),
+            "M4B cannot store {} Hz audio",
+            header.sample_rate
+        );
+        let chapter_format = AudioFormat {
+            sample_rate: header.sample_rate,
+            channels: header.channels,
+        };
+        ensure!(
+            *format.get_or_insert(chapter_format) == chapter_format,
+            "Chapter '{}' has a different sample rate or channel count",
+            chapter.title
+        );
+        let pcm = &chapter.wav[header.data_offset..header.data_offset + header.data_size];
+        let before = samples.len();
+        samples.extend(
+            pcm.chunks_exact(2)
+                .map(|pair| i16::from_le_bytes([pair[0], pair[1]])),
+        );
+        let frames = (samples.len() - before) / usize::from(header.channels);
+        lengths.push(u32::try_from(frames).context("Chapter is too long for M4B")?);
+    }
+    let format = format.context("An audiobook needs at least one chapter")?;
+    Ok((format, samples, lengths))
+}
+
+/// Muxes `chapters` into one M4B audiobook with chapter markers and `metadata`.
+///
+/// # Errors
+///
+/// Returns an error if there are no chapters, a chapter is not 16-bit mono or
+/// stereo PCM, chapters differ in format, or the book exceeds 4 GiB.
+pub fn encode_m4b(chapters: &[M4bChapter<'_>], metadata: &M4bMetadata<'_>) -> Result<Vec<u8>> {
+    let (format, samples, chapter_lengths) = collect_samples(chapters)?;
+    let channels = usize::from(format.channels);
+
+    let frames = samples
+        .chunks(FRAME_LENGTH * channels)
+        .map(|frame| alac_frame(frame, channels))
+        .collect::<Vec<_>>();
+    let frame_durations = samples
+        .chunks(FRAME_LENGTH * channels)
+        .map(|frame| (frame.len() / channels) as u32)
+        .collect::<Vec<_>>();
+    let titles = chapters
+        .iter()
+        .map(|chapter| truncate_title(chapter.title, usize::from(u16::MAX)))
+        .collect::<Vec<_>>();
+    let text_samples = titles
+        .iter()
+        .map(|title| chapter_sample(title))
+        .collect::<Vec<_>>();
+
+    let total_samples = u32::try_from(samples.len() / channels).context("Audiobook is too long")?;
+    let duration_ms =
+        u32::try_from(u64::from(total_samples) * 1000 / u64::from(format.sample_rate))
+            .context("Audiobook is too long")?;
+    let mut starts_100ns = Vec::with_capacity(chapter_lengths.len());
+    let mut start = 0u64;
+    for &length in &chapter_lengths {
+        starts_100ns.push(start * 10_000_000 / u64::from(format.sample_rate));
+        start += u64::from(length);
+    }
+
+    let frame_sizes = frames
+        .iter()
+        .map(|frame| frame.len() as u32)
+        .collect::<Vec<_>>();
+    let text_sizes = text_samples
+        .iter()
+        .map(|sample| sample.len() as u32)
+        .collect::<Vec<_>>();
+    let mdat_payload = frames.concat().len() + text_samples.concat().len();
+    ensure!(
+        u32::try_from(mdat_payload + (1 << 20)).is_ok(),
+        "Audiobook is larger than 4 GiB, which M4B output does not support"
+    );
+
+    let ftyp = boxed(b"ftyp", b"M4B \0\0\0\0M4B M4A mp42isom");
+    let build_moov = |data_start: u32| {
+        let audio_offset = data_start;
+        let mut text_offsets = Vec::with_capacity(text_sizes.len());
+        let mut offset = audio_offset + frame_sizes.iter().sum::<u32>();
+        for &size in &text_sizes {
+            text_offsets.push(offset);
+            offset += size;
+        }
+
+        let mut mvhd = be32s(&[0, 0, MOVIE_TIMESCALE, duration_ms, 0x0001_0000]);
+        mvhd.extend_from_slice(&0x0100u16.to_be_bytes());
+        mvhd.extend_from_slice(&[0; 10]);
+        mvhd.extend(be32s(&IDENTITY_MATRIX));
+        mvhd.extend_from_slice(&[0; 24]);
+        mvhd.extend(be32s(&[CHAPTER_TRACK_ID + 1]));
+
+        let audio_track = container(
+            b"trak",
+            &[
+                track_header(AUDIO_TRACK_ID, 3, duration_ms, 0x0100),
+                container(b"tref", &[boxed(b"chap", &be32s(&[CHAPTER_TRACK_ID]))]),
+                container(
+                    b"mdia",
+                    &[
+                        media_header(format.sample_rate, total_samples),
+                        handler(b"soun", "SoundHandler"),
+                        container(
+                            b"minf",
+                            &[
+                                full_box(b"smhd", 0, &[0; 4]),
+                                data_information(),
+                                sample_table(
+                                    audio_sample_entry(format),
+                                    &frame_durations,
+                                    &frame_sizes,
+                                    &[audio_offset],
+                                    frame_sizes.len() as u32,
+                                ),
+                            ],
+                        ),
+                    ],
+                ),
+            ],
+        );
+        let chapter_track = container(
+            b"trak",
+            &[
+                track_header(CHAPTER_TRACK_ID, 2, duration_ms, 0),
+                container(
+                    b"mdia",
+                    &[
+                        media_header(format.sample_rate, total_samples),
+                        handler(b"text", "ChapterHandler"),
+                        container(
+                            b"minf",
+                            &[
+                                text_media_header(),
+                                data_information(),
+                                sample_table(
+                                    text_sample_entry(),
+                                    &chapter_lengths,
+                                    &text_sizes,
+                                    &text_offsets,
+                                    1,
+                                ),
+                            ],
+                        ),
+                    ],
+                ),
+            ],
+        );
+
+        let mut user_data = Vec::new();
+        if titles.len() <= MAX_NERO_CHAPTERS {
+            user_data.push(nero_chapters(&starts_100ns, &titles));
+        }
+        user_data.push(itunes_metadata(metadata));
+        container(
+            b"moov",
+            &[
+                full_box(b"mvhd", 0, &mvhd),
+                audio_track,
+                chapter_track,
+                container(b"udta", &user_data),
+            ],
+        )
+    };
+
+    // Offsets have a fixed width, so the movie box is the same size either way.
+    let moov_len = build_moov(0).len();
+    let data_start = u32::try_from(ftyp.len() + moov_len + 8).context("Movie box is too large")?;
+    let moov = build_moov(data_start);
+
+    let mut m4b = Vec::with_capacity(data_start as usize + mdat_payload);
+    m4b.extend(ftyp);
+    m4b.extend(moov);
+    m4b.extend_from_slice(&(mdat_payload as u32 + 8).to_be_bytes());
+    m4b.extend_from_slice(b"mdat");
+    for frame in &frames {
+        m4b.extend_from_slice(frame);
+    }
+    for sample in &text_samples {
+        m4b.extend_from_slice(sample);
+    }
+    Ok(m4b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::synthesis::wav::make_wav;
+
+    /// Top-level or child boxes of `data`, as `(kind, payload)`.
+    fn children(mut data: &[u8]) -> Vec<(&[u8], &[u8])> {
+        let mut boxes = Vec::new();
+        while data.len() >= 8 {
+            let size = u32::from_be_bytes(data[..4].try_into().unwrap()) as usize;
+            boxes.push((&data[4..8], &data[8..size]));
+            data = &data[size..];
+        }
+        assert!(data.is_empty(), "trailing bytes");
+        boxes
+    }
+
+    fn child<'a>(data: &'a [u8], kind: &[u8; 4]) -> &'a [u8] {
+        children(data)
+            .into_iter()
+            .find(|(found, _)| found == kind)
+            .map(|(_, payload)| payload)
+            .unwrap_or_else(|| panic!("missing {}", String::from_utf8_lossy(kind)))
+    }
+
+    fn wav_of(samples: &[i16]) -> Vec<u8> {
+        let pcm = samples
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect::<Vec<_>>();
+        make_wav(&pcm, 1, 24_000, 16)
+    }
+
+    #[test]
+    fn verbatim_frames_hold_the_samples_and_partial_lengths() {
+        let frame = alac_frame(&[1, -2], 1);
+        // SCE, instance 0, 12 unused bits, has-size, no shift, verbatim,
+        // then the 32-bit count, two samples, and the end element.
+        let mut expected = BitWriter::default();
+        for (value, bits) in [(0, 3), (0, 4), (0, 12), (1, 1), (0, 2), (1, 1), (2, 32)] {
+            expected.write(value, bits);
+        }
+        expected.write_signed(1, 16);
+        expected.write_signed(-2, 16);
+        expected.write(END_ELEMENT, 3);
+        assert_eq!(frame, expected.finish());
+        assert_eq!(
+            alac_frame(&[0; FRAME_LENGTH], 1).len(),
+            4 + FRAME_LENGTH * 2
+        );
+    }
+
+    #[test]
+    fn books_carry_audio_chapters_and_tags() {
+        let first = wav_of(&vec![100; FRAME_LENGTH + 10]);
+        let second = wav_of(&vec![-100; 24_000]);
+        let m4b = encode_m4b(
+            &[
+                M4bChapter {
+                    title: "第一章",
+                    wav: &first,
+                },
+                M4bChapter {
+                    title: "Two",
+                    wav: &second,
+                },
+            ],
+            &M4bMetadata {
+                title: "Book",
+                author: Some("Zundamon"),
+            },
+        )
+        .expect("encoded");
+
+        let top = children(&m4b);
+        assert_eq!(
+            top.iter().map(|(kind, _)| *kind).collect::<Vec<_>>(),
+            [b"ftyp", b"moov", b"mdat"]
+        );
+        let moov = top[1].1;
+        let traks = children(moov)
+            .into_iter()
+            .filter(|(kind, _)| kind == b"trak")
+            .collect::<Vec<_>>();
+        assert_eq!(traks.len(), 2);
+
+        // The chapter track's samples sit in mdat at the offsets it records.
+        let text_stbl = child(child(child(traks[1].1, b"mdia"), b"minf"), b"stbl");
+        let offsets = child(text_stbl, b"stco");
+        let second_offset = u32::from_be_bytes(offsets[12..16].try_into().unwrap()) as usize;
+        assert_eq!(&m4b[second_offset..second_offset + 5], b"\0\x03Two");
+        let stts = child(text_stbl, b"stts");
+        assert_eq!(
+            stts[4..],
+            be32s(&[2, 1, FRAME_LENGTH as u32 + 10, 1, 24_000])[..]
+        );
+
+        let chpl = child(child(moov, b"udta"), b"chpl");
+        assert_eq!(chpl[8], 2);
+        let second_start = 9 + 8 + 1 + "第一章".len();
+        assert_eq!(
+            u64::from_be_bytes(chpl[second_start..second_start + 8].try_into().unwrap()),
+            (FRAME_LENGTH as u64 + 10) * 10_000_000 / 24_000
+        );
+
+        let audio_stbl = child(child(child(traks[0].1, b"mdia"), b"minf"), b"stbl");
+        let audio_offset =
+            u32::from_be_bytes(child(audio_stbl, b"stco")[8..12].try_into().unwrap()) as usize;
+        // A full mono frame: element header, 4096 samples, end element.
+        assert_eq!(
+            &m4b[audio_offset..audio_offset + 5],
+            &[0x00, 0x00, 0x02, 0x00, 0xc8]
+        );
+        let ilst = &child(child(moov, b"udta"), b"meta")[4..];
+        assert!(
+            children(child(ilst, b"ilst"))
+                .iter()
+                .any(|(kind, _)| kind == b"\xa9ART")
+        );
+
+        assert!(
+            encode_m4b(
+                &[],
+                &M4bMetadata {
+                    title: "",
+                    author: None
+                }
+            )
+            .is_err()
+        );
+    }
+}
@@ -1,7 +1,19 @@
+pub mod assembler;
+pub mod breath;
+pub mod chime;
+pub mod flac;
+pub mod length_limit;
 pub mod limits;
+pub mod loudness;
+pub mod m4b;
+pub mod ogg;
+pub mod options;
+pub mod opus_stream;
+pub mod pitch_shift;
+pub mod postprocess;
 pub mod service;
-pub mod text_splitter;
+pub mod timing;
 pub mod wav;
 
+pub use assembler::Assembler;
 pub use service::{TextSynthesisRequest, validate_basic_request};
-pub use text_splitter::{TextSegmenter, TextSplitter};
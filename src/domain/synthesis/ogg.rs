@@ -0,0 +1,190 @@
+/// Largest number of lacing values, and so 255-byte segments, one page holds.
+const MAX_PAGE_SEGMENTS: usize = 255;
+
+const CONTINUED_PACKET: u8 = 0x01;
+const BEGINNING_OF_STREAM: u8 = 0x02;
+const END_OF_STREAM: u8 = 0x04;
+
+fn ogg_crc(data: &[u8]) -> u32 {
+    data.iter().fold(0u32, |crc, &byte| {
+        (0..8).fold(crc ^ (u32::from(byte) << 24), |crc, _| {
+            if crc & 0x8000_0000 == 0 {
+                crc << 1
+            } else {
+                (crc << 1) ^ 0x04C1_1DB7
+            }
+        })
+    })
+}
+
+/// Writes one logical Ogg bitstream, packing packets into pages.
+///
+/// Packets share a page until [`OggWriter::flush_page`] is called or the page fills
+/// up; a packet too large for the remaining segment table continues on the next
+/// page. [`OggWriter::finish`] marks the last page as the end of the stream.
+#[derive(Debug)]
+pub struct OggWriter {
+    serial: u32,
+    sequence: u32,
+    output: Vec<u8>,
+    segments: Vec<u8>,
+    body: Vec<u8>,
+    /// Granule position of the last packet that ends on the pending page.
+    granule_position: Option<u64>,
+    continued: bool,
+    /// Set by `flush_page`; the page is written once it is known not to be the last.
+    page_break: bool,
+}
+
+impl OggWriter {
+    #[must_use]
+    pub const fn new(serial: u32) -> Self {
+        Self {
+            serial,
+            sequence: 0,
+            output: Vec::new(),
+            segments: Vec::new(),
+            body: Vec::new(),
+            granule_position: None,
+            continued: false,
+            page_break: false,
+        }
+    }
+
+    /// Adds a packet that completes at `granule_position`.
+    pub fn push_packet(&mut self, packet: &[u8], granule_position: u64) {
+        if std::mem::take(&mut self.page_break) {
+            self.write_page(false);
+        }
+        let mut rest = packet;
+        loop {
+            if self.segments.len() == MAX_PAGE_SEGMENTS {
+                self.write_page(false);
+                self.continued = true;
+            }
+            let length = rest.len().min(255);
+            self.segments.push(length as u8);
+            self.body.extend_from_slice(&rest[..length]);
+            rest = &rest[length..];
+            // A segment shorter than 255 bytes, possibly empty, ends the packet.
+            if length < 255 {
+                break;
+            }
+        }
+        self.granule_position = Some(granule_position);
+    }
+
+    /// Ends the current page, so the next packet starts a new one.
+    pub fn flush_page(&mut self) {
+        self.page_break = !self.segments.is_empty();
+    }
+
+    /// Writes the final page and returns the whole stream.
+    #[must_use]
+    pub fn finish(mut self) -> Vec<u8> {
+        self.write_page(true);
+        self.output
+    }
+
+    fn write_page(&mut self, last: bool) {
+        let mut flags = 0;
+        if self.continued {
+            flags |= CONTINUED_PACKET;
+        }
+        if self.sequence == 0 {
+            flags |= BEGINNING_OF_STREAM;
+        }
+        if last {
+            flags |= END_OF_STREAM;
+        }
+        // Pages where no packet ends carry a granule position of -1.
+        let granule = self.granule_position.take().unwrap_or(u64::MAX);
+
+        let start = self.output.len();
+        self.output.extend_from_slice(b"OggS");
+        self.output.push(0);
+        self.output.push(flags);
+        self.output.extend_from_slice(&granule.to_le_bytes());
+        self.output.extend_from_slice(&self.serial.to_le_bytes());
+        self.output.extend_from_slice(&self.sequence.to_le_bytes());
+        let crc_offset = self.output.len();
+        self.output.extend_from_slice(&[0; 4]);
+        self.output.push(self.segments.len() as u8);
+        self.output.append(&mut self.segments);
+        self.output.append(&mut self.body);
+        let crc = ogg_crc(&self.output[start..]);
+        self.output[crc_offset..crc_offset + 4].copy_from_slice(&crc.to_le_bytes());
+
+        self.sequence += 1;
+        self.continued = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pages(stream: &[u8]) -> Vec<(u8, u64, Vec<u8>, Vec<u8>)> {
+        let mut pages = Vec::new();
+        let mut rest = stream;
+        while !rest.is_empty() {
+            assert_eq!(&rest[..4], b"OggS");
+            let count = usize::from(rest[26]);
+            let segments = rest[27..27 + count].to_vec();
+            let body_length = segments
+                .iter()
+                .map(|&length| usize::from(length))
+                .sum::<usize>();
+            let length = 27 + count + body_length;
+            let mut unchecked = rest[..length].to_vec();
+            unchecked[22..26].fill(0);
+            assert_eq!(
+                u32::from_le_bytes([rest[22], rest[23], rest[24], rest[25]]),
+                ogg_crc(&unchecked)
+            );
+            let granule = u64::from_le_bytes(rest[6..14].try_into().expect("granule"));
+            pages.push((
+                rest[5],
+                granule,
+                segments,
+                rest[27 + count..length].to_vec(),
+            ));
+            rest = &rest[length..];
+        }
+        pages
+    }
+
+    #[test]
+    fn splits_packets_into_checksummed_pages() {
+        let mut writer = OggWriter::new(7);
+        writer.push_packet(b"head", 0);
+        writer.flush_page();
+        writer.push_packet(&[1; 300], 960);
+        writer.push_packet(&[2; 255], 1920);
+        let pages = pages(&writer.finish());
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].0, BEGINNING_OF_STREAM);
+        assert_eq!((pages[0].1, pages[0].3.as_slice()), (0, b"head".as_slice()));
+        assert_eq!(pages[1].0, END_OF_STREAM);
+        assert_eq!(pages[1].1, 1920);
+        assert_eq!(pages[1].2, vec![255, 45, 255, 0]);
+        assert_eq!(pages[1].3.len(), 555);
+    }
+
+    #[test]
+    fn long_packets_continue_on_the_next_page() {
+        let mut writer = OggWriter::new(7);
+        writer.push_packet(&vec![3; 255 * 300], 48_000);
+        let pages = pages(&writer.finish());
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!((pages[0].0, pages[0].1), (BEGINNING_OF_STREAM, u64::MAX));
+        assert_eq!(pages[0].2.len(), MAX_PAGE_SEGMENTS);
+        assert_eq!(
+            (pages[1].0, pages[1].1),
+            (CONTINUED_PACKET | END_OF_STREAM, 48_000)
+        );
+        assert_eq!(pages[1].2.len(), 46);
+    }
+}
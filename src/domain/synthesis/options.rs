@@ -0,0 +1,150 @@
+use anyhow::{Result, ensure};
+use serde::{Deserialize, Serialize};
+
+use super::limits::{
+    DEFAULT_SYNTHESIS_PITCH, DEFAULT_SYNTHESIS_RATE, MAX_SYNTHESIS_PITCH, MAX_SYNTHESIS_RATE,
+    MIN_SYNTHESIS_PITCH, MIN_SYNTHESIS_RATE, is_valid_synthesis_pitch, is_valid_synthesis_rate,
+};
+
+/// Layout version of [`SynthesisOptions`]; bumped whenever a field is added.
+pub const SYNTHESIS_OPTIONS_VERSION: u16 = 1;
+
+pub const DEFAULT_INTONATION: f32 = 1.0;
+pub const MAX_INTONATION: f32 = 2.0;
+pub const DEFAULT_VOLUME: f32 = 1.0;
+pub const MAX_VOLUME: f32 = 2.0;
+/// Silence before and after an utterance, in seconds.
+pub const DEFAULT_PHONEME_PAUSE: f32 = 0.1;
+pub const MAX_PHONEME_PAUSE: f32 = 1.5;
+pub const MIN_OUTPUT_SAMPLE_RATE: u32 = 8_000;
+pub const MAX_OUTPUT_SAMPLE_RATE: u32 = 96_000;
+
+/// Sample rate and channels of the PCM audio returned by synthesis; the file
+/// encoding it is written in is chosen separately.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PcmFormat {
+    /// Resample to this rate in Hz; `None` keeps the model's native rate.
+    pub sample_rate: Option<u32>,
+    pub stereo: bool,
+}
+
+/// Synthesis parameters shared by the CLI, the daemon protocol, and the MCP server.
+///
+/// Every field has a neutral default and missing fields deserialize to it, so
+/// JSON callers written against an older version keep working.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SynthesisOptions {
+    pub version: u16,
+    /// Speech rate multiplier.
+    pub rate: f32,
+    /// Pitch offset; `0.0` keeps the voice's own pitch.
+    pub pitch: f32,
+    /// Intonation scale; `0.0` is monotone.
+    pub intonation: f32,
+    pub volume: f32,
+    pub pre_phoneme_length: f32,
+    pub post_phoneme_length: f32,
+    pub output_format: PcmFormat,
+}
+
+impl Default for SynthesisOptions {
+    fn default() -> Self {
+        Self {
+            version: SYNTHESIS_OPTIONS_VERSION,
+            rate: DEFAULT_SYNTHESIS_RATE,
+            pitch: DEFAULT_SYNTHESIS_PITCH,
+            intonation: DEFAULT_INTONATION,
+            volume: DEFAULT_VOLUME,
+            pre_phoneme_length: DEFAULT_PHONEME_PAUSE,
+            post_phoneme_length: DEFAULT_PHONEME_PAUSE,
+            output_format: PcmFormat::default(),
+        }
+    }
+}
+
+impl SynthesisOptions {
+    /// Default options with the given rate.
+    #[must_use]
+    pub fn with_rate(rate: f32) -> Self {
+        Self {
+            rate,
+            ..Self::default()
+        }
+    }
+
+    /// Checks every field against its supported range.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first field that is out of range, or if the
+    /// options come from a newer, unknown version.
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            self.version <= SYNTHESIS_OPTIONS_VERSION,
+            "Synthesis options version {} is newer than supported version {SYNTHESIS_OPTIONS_VERSION}",
+            self.version
+        );
+        ensure!(
+            is_valid_synthesis_rate(self.rate),
+            "Rate must be between {MIN_SYNTHESIS_RATE:.1} and {MAX_SYNTHESIS_RATE:.1}, got: {}",
+            self.rate
+        );
+        ensure!(
+            is_valid_synthesis_pitch(self.pitch),
+            "Pitch must be between {MIN_SYNTHESIS_PITCH:.2} and {MAX_SYNTHESIS_PITCH:.2}, got: {}",
+            self.pitch
+        );
+        ensure!(
+            (0.0..=MAX_INTONATION).contains(&self.intonation),
+            "Intonation must be between 0.0 and {MAX_INTONATION:.1}, got: {}",
+            self.intonation
+        );
+        ensure!(
+            (0.0..=MAX_VOLUME).contains(&self.volume),
+            "Volume must be between 0.0 and {MAX_VOLUME:.1}, got: {}",
+            self.volume
+        );
+        for (name, length) in [
+            ("Leading pause", self.pre_phoneme_length),
+            ("Trailing pause", self.post_phoneme_length),
+        ] {
+            ensure!(
+                (0.0..=MAX_PHONEME_PAUSE).contains(&length),
+                "{name} must be between 0.0 and {MAX_PHONEME_PAUSE:.1} seconds, got: {length}"
+            );
+        }
+        if let Some(sample_rate) = self.output_format.sample_rate {
+            ensure!(
+                (MIN_OUTPUT_SAMPLE_RATE..=MAX_OUTPUT_SAMPLE_RATE).contains(&sample_rate),
+                "Sample rate must be between {MIN_OUTPUT_SAMPLE_RATE} and {MAX_OUTPUT_SAMPLE_RATE} Hz, got: {sample_rate}"
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_fields_take_defaults_and_newer_versions_are_rejected() {
+        let options: SynthesisOptions =
+            serde_json::from_str(r#"{"rate": 1.2}"#).expect("partial options");
+        assert_eq!(options, SynthesisOptions::with_rate(1.2));
+        assert!(options.validate().is_ok());
+
+        let newer = SynthesisOptions {
+            version: SYNTHESIS_OPTIONS_VERSION + 1,
+            ..SynthesisOptions::default()
+        };
+        assert!(newer.validate().is_err());
+        let loud = SynthesisOptions {
+            volume: 3.0,
+            ..SynthesisOptions::default()
+        };
+        assert!(loud.validate().is_err());
+    }
+}
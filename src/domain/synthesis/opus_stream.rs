@@ -0,0 +1,195 @@
+use anyhow::{Result, ensure};
+
+/// Leading bytes of every framed Opus stream.
+pub const OPUS_STREAM_MAGIC: &[u8; 4] = b"VVOP";
+
+/// Framing version written after the magic; readers reject newer streams.
+pub const OPUS_STREAM_VERSION: u8 = 1;
+
+/// Encoded size of [`OpusStreamHeader`], magic included.
+pub const OPUS_STREAM_HEADER_LEN: usize = 12;
+
+/// Largest Opus packet the framing accepts; libopus never emits more per frame.
+pub const MAX_OPUS_PACKET_LEN: usize = 1275;
+
+const PACKET_LENGTH_PREFIX: usize = 2;
+
+/// Audio format announced once at the start of a framed Opus stream.
+///
+/// The stream layout is the 12-byte header followed by packets, each prefixed
+/// with its length as a little-endian `u16`. Framing is kept this small so a
+/// client can decode packets as they arrive over a plain HTTP body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpusStreamHeader {
+    pub sample_rate: u32,
+    pub channels: u8,
+    /// Samples per channel in every packet.
+    pub frame_samples: u16,
+}
+
+impl OpusStreamHeader {
+    #[must_use]
+    pub fn encode(&self) -> [u8; OPUS_STREAM_HEADER_LEN] {
+        let mut bytes = [0; OPUS_STREAM_HEADER_LEN];
+        bytes[..4].copy_from_slice(OPUS_STREAM_MAGIC);
+        bytes[4] = OPUS_STREAM_VERSION;
+        bytes[5] = self.channels;
+        bytes[6..10].copy_from_slice(&self.sample_rate.to_le_bytes());
+        bytes[10..12].copy_from_slice(&self.frame_samples.to_le_bytes());
+        bytes
+    }
+
+    /// Parses a header from the first [`OPUS_STREAM_HEADER_LEN`] bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the magic or version does not match or a field is zero.
+    pub fn parse(bytes: &[u8; OPUS_STREAM_HEADER_LEN]) -> Result<Self> {
+        ensure!(
+            &bytes[..4] == OPUS_STREAM_MAGIC,
+            "Not a VOICEVOX Opus stream"
+        );
+        ensure!(
+            bytes[4] == OPUS_STREAM_VERSION,
+            "Unsupported Opus stream version {} (expected {OPUS_STREAM_VERSION})",
+            bytes[4]
+        );
+        let header = Self {
+            channels: bytes[5],
+            sample_rate: u32::from_le_bytes([bytes[6], bytes[7], bytes[8], bytes[9]]),
+            frame_samples: u16::from_le_bytes([bytes[10], bytes[11]]),
+        };
+        ensure!(
+            header.channels > 0 && header.sample_rate > 0 && header.frame_samples > 0,
+            "Opus stream header declares an empty format"
+        );
+        Ok(header)
+    }
+}
+
+/// Appends one length-prefixed packet to `output`.
+///
+/// # Errors
+///
+/// Returns an error if the packet is empty or longer than [`MAX_OPUS_PACKET_LEN`].
+pub fn write_opus_packet(packet: &[u8], output: &mut Vec<u8>) -> Result<()> {
+    ensure!(
+        (1..=MAX_OPUS_PACKET_LEN).contains(&packet.len()),
+        "Opus packet length {} is out of range",
+        packet.len()
+    );
+    output.extend_from_slice(&(packet.len() as u16).to_le_bytes());
+    output.extend_from_slice(packet);
+    Ok(())
+}
+
+/// One item read back from a framed Opus stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpusStreamItem {
+    Header(OpusStreamHeader),
+    Packet(Vec<u8>),
+}
+
+/// Incremental parser for framed Opus streams.
+///
+/// Bytes can be pushed in arbitrary chunks as they arrive from the network;
+/// [`OpusFrameReader::next_item`] yields each header or packet once it is complete.
+#[derive(Debug, Default)]
+pub struct OpusFrameReader {
+    buffer: Vec<u8>,
+    header: Option<OpusStreamHeader>,
+}
+
+impl OpusFrameReader {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    #[must_use]
+    pub const fn header(&self) -> Option<OpusStreamHeader> {
+        self.header
+    }
+
+    /// Returns the next complete item, or `None` until more bytes arrive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the header is invalid or a packet length is out of range.
+    pub fn next_item(&mut self) -> Result<Option<OpusStreamItem>> {
+        if self.header.is_none() {
+            let Some(bytes) = self.buffer.first_chunk::<OPUS_STREAM_HEADER_LEN>() else {
+                return Ok(None);
+            };
+            let header = OpusStreamHeader::parse(bytes)?;
+            self.buffer.drain(..OPUS_STREAM_HEADER_LEN);
+            self.header = Some(header);
+            return Ok(Some(OpusStreamItem::Header(header)));
+        }
+
+        let Some(&[low, high]) = self.buffer.first_chunk::<PACKET_LENGTH_PREFIX>() else {
+            return Ok(None);
+        };
+        let length = usize::from(u16::from_le_bytes([low, high]));
+        ensure!(
+            (1..=MAX_OPUS_PACKET_LEN).contains(&length),
+            "Opus packet length {length} is out of range"
+        );
+        if self.buffer.len() < PACKET_LENGTH_PREFIX + length {
+            return Ok(None);
+        }
+        let packet = self.buffer[PACKET_LENGTH_PREFIX..PACKET_LENGTH_PREFIX + length].to_vec();
+        self.buffer.drain(..PACKET_LENGTH_PREFIX + length);
+        Ok(Some(OpusStreamItem::Packet(packet)))
+    }
+
+    /// Bytes received but not yet returned as an item.
+    #[must_use]
+    pub fn pending_len(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reader_reassembles_items_split_across_chunks() {
+        let header = OpusStreamHeader {
+            sample_rate: 24_000,
+            channels: 1,
+            frame_samples: 480,
+        };
+        let mut stream = header.encode().to_vec();
+        write_opus_packet(&[1, 2, 3], &mut stream).expect("packet");
+        write_opus_packet(&[4], &mut stream).expect("packet");
+        assert!(write_opus_packet(&[], &mut stream).is_err());
+
+        let mut reader = OpusFrameReader::new();
+        let mut items = Vec::new();
+        for byte in stream {
+            reader.push(&[byte]);
+            while let Some(item) = reader.next_item().expect("valid stream") {
+                items.push(item);
+            }
+        }
+        assert_eq!(
+            items,
+            [
+                OpusStreamItem::Header(header),
+                OpusStreamItem::Packet(vec![1, 2, 3]),
+                OpusStreamItem::Packet(vec![4]),
+            ]
+        );
+        assert_eq!(reader.pending_len(), 0);
+
+        let mut bad = OpusFrameReader::new();
+        bad.push(b"RIFF\x01\x01\xc0\x5d\0\0\xe0\x01");
+        assert!(bad.next_item().is_err());
+    }
+}
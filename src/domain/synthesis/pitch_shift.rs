@@ -0,0 +1,194 @@
+//! Formant-preserving pitch shift using TD-PSOLA.
+//!
+//! The signal is cut into two-period grains centered on pitch marks and
+//! overlap-added at a new spacing. Each grain keeps its spectral envelope, so the
+//! formants stay in place while the fundamental moves. Unvoiced stretches are
+//! resynthesized at their original spacing.
+
+const MIN_F0_HZ: f32 = 60.0;
+const MAX_F0_HZ: f32 = 500.0;
+const ANALYSIS_HOP_MS: u32 = 10;
+const VOICING_THRESHOLD: f32 = 0.5;
+const OCTAVE_TOLERANCE: f32 = 0.9;
+const UNVOICED_PERIOD_MS: u32 = 10;
+
+/// Per-hop period estimates in samples; `None` marks unvoiced or silent hops.
+struct PitchTrack {
+    hop: usize,
+    periods: Vec<Option<usize>>,
+}
+
+impl PitchTrack {
+    fn period_at(&self, position: usize) -> Option<usize> {
+        self.periods.get(position / self.hop).copied().flatten()
+    }
+}
+
+fn ms_to_samples(ms: u32, sample_rate: u32) -> usize {
+    usize::try_from(u64::from(ms) * u64::from(sample_rate) / 1000).unwrap_or(usize::MAX)
+}
+
+/// Returns the fundamental period in samples from normalized autocorrelation, if voiced.
+fn estimate_period(frame: &[f32], min_lag: usize, max_lag: usize) -> Option<usize> {
+    let energy: f32 = frame.iter().map(|sample| sample * sample).sum();
+    if energy <= f32::EPSILON {
+        return None;
+    }
+    let scores = (min_lag..=max_lag.min(frame.len().saturating_sub(1)))
+        .filter_map(|lag| {
+            let (head, tail) = (&frame[..frame.len() - lag], &frame[lag..]);
+            let correlation: f32 = head.iter().zip(tail).map(|(a, b)| a * b).sum();
+            let normalizer = (head.iter().map(|s| s * s).sum::<f32>()
+                * tail.iter().map(|s| s * s).sum::<f32>())
+            .sqrt();
+            (normalizer > f32::EPSILON).then(|| (lag, correlation / normalizer))
+        })
+        .collect::<Vec<_>>();
+    let best = scores
+        .iter()
+        .map(|(_, score)| *score)
+        .fold(f32::MIN, f32::max);
+    if best < VOICING_THRESHOLD {
+        return None;
+    }
+    // Multiples of the true period score almost as well, so take the first peak that
+    // comes close to the best one rather than the global maximum.
+    let mut index = scores
+        .iter()
+        .position(|(_, score)| *score >= best * OCTAVE_TOLERANCE)?;
+    while index + 1 < scores.len() && scores[index + 1].1 > scores[index].1 {
+        index += 1;
+    }
+    Some(scores[index].0)
+}
+
+fn track_pitch(samples: &[f32], sample_rate: u32) -> PitchTrack {
+    let hop = ms_to_samples(ANALYSIS_HOP_MS, sample_rate).max(1);
+    let min_lag = (sample_rate as f32 / MAX_F0_HZ) as usize;
+    let max_lag = (sample_rate as f32 / MIN_F0_HZ) as usize;
+    let window = max_lag * 2;
+    let periods = (0..samples.len().div_ceil(hop))
+        .map(|index| {
+            let start = (index * hop).saturating_sub(window / 2);
+            let end = (start + window).min(samples.len());
+            estimate_period(&samples[start..end], min_lag.max(1), max_lag)
+        })
+        .collect();
+    PitchTrack { hop, periods }
+}
+
+/// Places analysis marks one local period apart, snapping voiced marks to peaks.
+fn analysis_marks(samples: &[f32], track: &PitchTrack, unvoiced_period: usize) -> Vec<usize> {
+    let mut marks = Vec::new();
+    let mut position = 0;
+    while position < samples.len() {
+        let mark = match track.period_at(position) {
+            Some(period) => {
+                let end = (position + period / 2).min(samples.len());
+                (position..end)
+                    .max_by(|&a, &b| samples[a].abs().total_cmp(&samples[b].abs()))
+                    .unwrap_or(position)
+            }
+            None => position,
+        };
+        marks.push(mark);
+        let period = track.period_at(mark).unwrap_or(unvoiced_period);
+        position = mark + period.max(1);
+    }
+    marks
+}
+
+fn hann(index: usize, length: usize) -> f32 {
+    let phase = index as f32 / length as f32;
+    0.5 - 0.5 * (std::f32::consts::TAU * phase).cos()
+}
+
+/// Shifts the pitch of mono `samples` by `ratio` while keeping duration and formants.
+#[must_use]
+pub fn shift_pitch(samples: &[f32], sample_rate: u32, ratio: f32) -> Vec<f32> {
+    if samples.is_empty() || (ratio - 1.0).abs() <= f32::EPSILON {
+        return samples.to_vec();
+    }
+    let track = track_pitch(samples, sample_rate);
+    let unvoiced_period = ms_to_samples(UNVOICED_PERIOD_MS, sample_rate).max(1);
+    let marks = analysis_marks(samples, &track, unvoiced_period);
+
+    let mut output = vec![0.0; samples.len()];
+    let mut nearest = 0;
+    let mut position = 0.0_f32;
+    while (position as usize) < samples.len() {
+        let target = position as usize;
+        while nearest + 1 < marks.len()
+            && marks[nearest + 1].abs_diff(target) <= marks[nearest].abs_diff(target)
+        {
+            nearest += 1;
+        }
+        let mark = marks[nearest];
+        let (period, spacing) = match track.period_at(mark) {
+            Some(period) => (period, period as f32 / ratio),
+            None => (unvoiced_period, unvoiced_period as f32),
+        };
+        let gain = spacing / period as f32;
+        let length = period * 2;
+        for offset in 0..length {
+            let source = (mark + offset).checked_sub(period);
+            let destination = (target + offset).checked_sub(period);
+            if let (Some(source), Some(destination)) = (source, destination)
+                && source < samples.len()
+                && destination < output.len()
+            {
+                output[destination] += samples[source] * hann(offset, length) * gain;
+            }
+        }
+        position += spacing.max(1.0);
+    }
+    output
+}
+
+/// Converts a semitone offset into a frequency ratio.
+#[must_use]
+pub fn semitones_to_ratio(semitones: f32) -> f32 {
+    (semitones / 12.0).exp2()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A harmonic-rich tone, like a voiced vowel, so the shifted harmonics have energy.
+    fn buzz(frequency: f32, sample_rate: u32, length: usize) -> Vec<f32> {
+        (0..length)
+            .map(|index| {
+                let time = index as f32 / sample_rate as f32;
+                (1..=12)
+                    .map(|harmonic| {
+                        let harmonic = harmonic as f32;
+                        (std::f32::consts::TAU * frequency * harmonic * time).sin() * 0.1 / harmonic
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn octave_up_doubles_detected_pitch_and_keeps_length() {
+        let sample_rate = 8000;
+        let input = buzz(150.0, sample_rate, 8000);
+        let output = shift_pitch(&input, sample_rate, semitones_to_ratio(12.0));
+        assert_eq!(output.len(), input.len());
+
+        let min_lag = (sample_rate as f32 / MAX_F0_HZ) as usize;
+        let max_lag = (sample_rate as f32 / MIN_F0_HZ) as usize;
+        let period = estimate_period(&output[2000..2800], min_lag, max_lag)
+            .expect("shifted signal stays voiced");
+        let frequency = sample_rate as f32 / period as f32;
+        assert!((frequency - 300.0).abs() < 15.0, "got {frequency} Hz");
+    }
+
+    #[test]
+    fn unity_ratio_and_silence_pass_through() {
+        let input = buzz(200.0, 8000, 400);
+        assert_eq!(shift_pitch(&input, 8000, 1.0), input);
+        assert_eq!(shift_pitch(&[0.0; 100], 8000, 2.0), vec![0.0; 100]);
+    }
+}
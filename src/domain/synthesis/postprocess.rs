@@ -0,0 +1,377 @@
+use anyhow::{Context, Result, anyhow, ensure};
+
+use crate::domain::synthesis::pitch_shift::{semitones_to_ratio, shift_pitch};
+use crate::domain::synthesis::wav::{WavHeader, parse_wav_header};
+
+pub const MAX_FADE_MS: u32 = 10_000;
+pub const MAX_TRANSPOSE_SEMITONES: f32 = 12.0;
+
+/// Post-processing applied to synthesized audio before it is played or written.
+///
+/// Effects run in a fixed order: transpose, then fades, so the fade envelope is
+/// never smeared by the pitch shifter.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AudioEffects {
+    pub transpose_semitones: f32,
+    pub fade: Fade,
+}
+
+impl AudioEffects {
+    /// # Errors
+    ///
+    /// Returns an error if any effect parameter is out of range.
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            self.transpose_semitones.abs() <= MAX_TRANSPOSE_SEMITONES,
+            "Transpose must be within ±{MAX_TRANSPOSE_SEMITONES} semitones"
+        );
+        self.fade.validate()
+    }
+}
+
+/// Runs the effects chain over a 16-bit PCM WAV buffer in place.
+///
+/// # Errors
+///
+/// Returns an error if an effect is requested and the WAV is malformed or not 16-bit PCM.
+pub fn apply_effects(wav: &mut [u8], effects: &AudioEffects) -> Result<()> {
+    apply_transpose(wav, effects.transpose_semitones)?;
+    apply_fade(wav, effects.fade)
+}
+
+/// Parses a transpose amount such as `+2st`, `-3st`, or `1.5`.
+///
+/// # Errors
+///
+/// Returns an error if the value is not a number of semitones.
+pub fn parse_transpose(value: &str) -> Result<f32> {
+    let trimmed = value.trim();
+    let number = trimmed.strip_suffix("st").unwrap_or(trimmed);
+    number
+        .trim_start_matches('+')
+        .parse::<f32>()
+        .ok()
+        .filter(|semitones| semitones.is_finite())
+        .ok_or_else(|| anyhow!("Invalid transpose '{value}' (expected e.g. +2st or -3st)"))
+}
+
+/// Linear fade envelope applied to the start and end of a synthesized clip.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Fade {
+    pub fade_in_ms: u32,
+    pub fade_out_ms: u32,
+}
+
+impl Fade {
+    #[must_use]
+    pub const fn is_none(&self) -> bool {
+        self.fade_in_ms == 0 && self.fade_out_ms == 0
+    }
+
+    /// Rejects fades longer than [`MAX_FADE_MS`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either fade length is out of range.
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            self.fade_in_ms <= MAX_FADE_MS && self.fade_out_ms <= MAX_FADE_MS,
+            "Fade length must be at most {MAX_FADE_MS} ms"
+        );
+        Ok(())
+    }
+}
+
+fn frames_for_ms(ms: u32, sample_rate: u32) -> usize {
+    usize::try_from(u64::from(ms) * u64::from(sample_rate) / 1000).unwrap_or(usize::MAX)
+}
+
+/// Applies `fade` in place to the PCM data of a 16-bit WAV buffer.
+///
+/// Fades longer than the clip are clamped to its length, so a short clip is faded
+/// across its whole duration rather than rejected.
+///
+/// # Errors
+///
+/// Returns an error if the WAV is malformed or not 16-bit PCM.
+pub fn apply_fade(wav: &mut [u8], fade: Fade) -> Result<()> {
+    if fade.is_none() {
+        return Ok(());
+    }
+    let header = parse_pcm16_header(wav)?;
+
+    let frame_bytes = usize::from(header.channels) * 2;
+    let pcm = &mut wav[header.data_offset..header.data_offset + header.data_size];
+    let total_frames = pcm.len() / frame_bytes;
+    let fade_in = frames_for_ms(fade.fade_in_ms, header.sample_rate).min(total_frames);
+    let fade_out = frames_for_ms(fade.fade_out_ms, header.sample_rate).min(total_frames);
+
+    for (index, frame) in pcm.chunks_exact_mut(frame_bytes).enumerate() {
+        let mut gain = 1.0_f32;
+        if index < fade_in {
+            gain *= index as f32 / fade_in as f32;
+        }
+        let remaining = total_frames - index;
+        if remaining <= fade_out {
+            gain *= (remaining - 1) as f32 / fade_out as f32;
+        }
+        if gain >= 1.0 {
+            continue;
+        }
+        for sample in frame.chunks_exact_mut(2) {
+            let value = f32::from(i16::from_le_bytes([sample[0], sample[1]]));
+            let scaled = (value * gain).round() as i16;
+            sample.copy_from_slice(&scaled.to_le_bytes());
+        }
+    }
+    Ok(())
+}
+
+/// Scales every sample by `gain` (1.0 leaves the audio untouched).
+///
+/// # Errors
+///
+/// Returns an error if scaling is needed and the WAV is malformed or not 16-bit PCM.
+pub fn apply_gain(wav: &mut [u8], gain: f32) -> Result<()> {
+    if gain == 1.0 {
+        return Ok(());
+    }
+    let header = parse_pcm16_header(wav)?;
+    let pcm = &mut wav[header.data_offset..header.data_offset + header.data_size];
+    for sample in pcm.chunks_exact_mut(2) {
+        let value = f32::from(i16::from_le_bytes([sample[0], sample[1]]));
+        let scaled = (value * gain).round() as i16;
+        sample.copy_from_slice(&scaled.to_le_bytes());
+    }
+    Ok(())
+}
+
+/// Compressor attack: how fast gain reduction engages on a rising level.
+const COMPRESSOR_ATTACK_MS: f32 = 5.0;
+/// Compressor release: how fast gain recovers once the level falls.
+const COMPRESSOR_RELEASE_MS: f32 = 100.0;
+
+/// Makeup gain followed by a peak compressor, for lifting quiet voices.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VolumeBoost {
+    pub gain_db: f32,
+    /// Level in dBFS above which the compressor reduces gain.
+    pub threshold_db: f32,
+    /// Input dB above the threshold per output dB; 1.0 disables compression.
+    pub ratio: f32,
+}
+
+/// Raises the level by `boost.gain_db`, then compresses peaks above the threshold.
+///
+/// The compressor follows the loudest channel of each frame, so all channels share
+/// one gain and the stereo image is kept. Anything still over full scale is clipped.
+///
+/// # Errors
+///
+/// Returns an error if the WAV is malformed or not 16-bit PCM.
+pub fn apply_volume_boost(wav: &mut [u8], boost: VolumeBoost) -> Result<()> {
+    let header = parse_pcm16_header(wav)?;
+    let frame_bytes = usize::from(header.channels) * 2;
+    let pcm = &mut wav[header.data_offset..header.data_offset + header.data_size];
+    let makeup = 10_f32.powf(boost.gain_db / 20.0);
+    let smoothing = |ms: f32| (-1.0 / (ms * 0.001 * header.sample_rate as f32)).exp();
+    let (attack, release) = (
+        smoothing(COMPRESSOR_ATTACK_MS),
+        smoothing(COMPRESSOR_RELEASE_MS),
+    );
+    let full_scale = -f32::from(i16::MIN);
+
+    let mut envelope = 0.0_f32;
+    for frame in pcm.chunks_exact_mut(frame_bytes) {
+        let peak = frame
+            .chunks_exact(2)
+            .map(|sample| f32::from(i16::from_le_bytes([sample[0], sample[1]])).abs())
+            .fold(0.0_f32, f32::max)
+            * makeup
+            / full_scale;
+        let coefficient = if peak > envelope { attack } else { release };
+        envelope = coefficient * envelope + (1.0 - coefficient) * peak;
+
+        let over_db = 20.0 * envelope.max(f32::MIN_POSITIVE).log10() - boost.threshold_db;
+        let reduction = if over_db > 0.0 {
+            10_f32.powf(-over_db * (1.0 - 1.0 / boost.ratio) / 20.0)
+        } else {
+            1.0
+        };
+        let gain = makeup * reduction;
+        for sample in frame.chunks_exact_mut(2) {
+            let value = f32::from(i16::from_le_bytes([sample[0], sample[1]]));
+            let scaled = (value * gain)
+                .round()
+                .clamp(f32::from(i16::MIN), f32::from(i16::MAX));
+            sample.copy_from_slice(&(scaled as i16).to_le_bytes());
+        }
+    }
+    Ok(())
+}
+
+fn parse_pcm16_header(wav: &[u8]) -> Result<WavHeader> {
+    let header = parse_wav_header(wav).context("Failed to parse WAV for post-processing")?;
+    ensure!(
+        header.bits_per_sample == 16,
+        "Audio effects require 16-bit PCM (got {} bits)",
+        header.bits_per_sample
+    );
+    ensure!(header.channels > 0, "WAV has no channels");
+    Ok(header)
+}
+
+/// Shifts pitch by `semitones` in place, preserving duration and formants.
+///
+/// Each channel is processed independently.
+///
+/// # Errors
+///
+/// Returns an error if the WAV is malformed or not 16-bit PCM.
+pub fn apply_transpose(wav: &mut [u8], semitones: f32) -> Result<()> {
+    if semitones == 0.0 {
+        return Ok(());
+    }
+    let header = parse_pcm16_header(wav)?;
+    let channels = usize::from(header.channels);
+    let pcm = &mut wav[header.data_offset..header.data_offset + header.data_size];
+    let ratio = semitones_to_ratio(semitones);
+
+    for channel in 0..channels {
+        let samples = pcm
+            .chunks_exact(2)
+            .skip(channel)
+            .step_by(channels)
+            .map(|pair| f32::from(i16::from_le_bytes([pair[0], pair[1]])))
+            .collect::<Vec<_>>();
+        let shifted = shift_pitch(&samples, header.sample_rate, ratio);
+        for (pair, value) in pcm
+            .chunks_exact_mut(2)
+            .skip(channel)
+            .step_by(channels)
+            .zip(shifted)
+        {
+            let clamped = value
+                .round()
+                .clamp(f32::from(i16::MIN), f32::from(i16::MAX));
+            pair.copy_from_slice(&(clamped as i16).to_le_bytes());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::synthesis::wav::make_wav;
+
+    fn samples(wav: &[u8]) -> Vec<i16> {
+        let header = parse_wav_header(wav).expect("valid wav");
+        wav[header.data_offset..header.data_offset + header.data_size]
+            .chunks_exact(2)
+            .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+            .collect()
+    }
+
+    #[test]
+    fn fades_ramp_edges_and_keep_middle() {
+        let pcm: Vec<u8> = std::iter::repeat_n(1000_i16.to_le_bytes(), 10)
+            .flatten()
+            .collect();
+        // 1 kHz sample rate: 1 ms == 1 frame.
+        let mut wav = make_wav(&pcm, 1, 1000, 16);
+        apply_fade(
+            &mut wav,
+            Fade {
+                fade_in_ms: 4,
+                fade_out_ms: 2,
+            },
+        )
+        .expect("fade applies");
+
+        assert_eq!(
+            samples(&wav),
+            vec![0, 250, 500, 750, 1000, 1000, 1000, 1000, 500, 0]
+        );
+    }
+
+    #[test]
+    fn gain_scales_samples() {
+        let pcm: Vec<u8> = [1000_i16, -2000, 3]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        let mut wav = make_wav(&pcm, 1, 1000, 16);
+        apply_gain(&mut wav, 0.5).expect("gain applies");
+        assert_eq!(samples(&wav), vec![500, -1000, 2]);
+    }
+
+    #[test]
+    fn volume_boost_lifts_quiet_audio_and_compresses_loud_audio() {
+        let tone = |level: i16| -> Vec<u8> {
+            (0..2000)
+                .map(|index| if index % 2 == 0 { level } else { -level })
+                .flat_map(i16::to_le_bytes)
+                .collect()
+        };
+        let boost = VolumeBoost {
+            gain_db: 12.0,
+            threshold_db: -6.0,
+            ratio: 4.0,
+        };
+
+        // -36 dBFS stays below the threshold after +12 dB, so it is only amplified.
+        let mut quiet = make_wav(&tone(512), 1, 1000, 16);
+        apply_volume_boost(&mut quiet, boost).expect("boost applies");
+        assert!(samples(&quiet).iter().all(|value| value.abs() == 2038));
+
+        // Full scale plus 12 dB settles 18 dB over the threshold, compressed to 4.5 dB over.
+        let mut loud = make_wav(&tone(i16::MAX), 1, 1000, 16);
+        apply_volume_boost(&mut loud, boost).expect("boost applies");
+        let settled = f32::from(samples(&loud).last().expect("samples").unsigned_abs());
+        let settled_db = 20.0 * (settled / 32768.0).log10();
+        assert!(
+            (settled_db - -1.5).abs() < 0.2,
+            "settled at {settled_db} dB"
+        );
+    }
+
+    #[test]
+    fn parses_transpose_amounts() {
+        assert_eq!(parse_transpose("+2st").expect("valid"), 2.0);
+        assert_eq!(parse_transpose("-3st").expect("valid"), -3.0);
+        assert_eq!(parse_transpose("1.5").expect("valid"), 1.5);
+        assert!(parse_transpose("up").is_err());
+        assert!(
+            AudioEffects {
+                transpose_semitones: 13.0,
+                ..Default::default()
+            }
+            .validate()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_non_16_bit_and_overlong_fades() {
+        let mut wav = make_wav(&[0; 4], 1, 1000, 8);
+        assert!(
+            apply_fade(
+                &mut wav,
+                Fade {
+                    fade_in_ms: 1,
+                    fade_out_ms: 0
+                }
+            )
+            .is_err()
+        );
+        assert!(
+            Fade {
+                fade_in_ms: MAX_FADE_MS + 1,
+                fade_out_ms: 0
+            }
+            .validate()
+            .is_err()
+        );
+    }
+}
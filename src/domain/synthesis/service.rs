@@ -1,33 +1,33 @@
 use anyhow::{Result, anyhow};
 
+use voicevox_text::check_text;
+
 use super::limits::{
-    MAX_SYNTHESIS_RATE, MAX_SYNTHESIS_TEXT_LENGTH, MIN_SYNTHESIS_RATE, is_valid_synthesis_rate,
+    MAX_SYNTHESIS_PITCH, MAX_SYNTHESIS_RATE, MIN_SYNTHESIS_PITCH, MIN_SYNTHESIS_RATE,
+    is_valid_synthesis_pitch, is_valid_synthesis_rate,
 };
 
 pub struct TextSynthesisRequest<'a> {
     pub text: &'a str,
     pub style_id: u32,
     pub rate: f32,
+    pub pitch: f32,
 }
 
 pub fn validate_basic_request(request: &TextSynthesisRequest<'_>) -> Result<()> {
-    if request.text.trim().is_empty() {
-        return Err(anyhow!(
-            "No text provided. Use command line argument, -f file, or pipe text to stdin."
-        ));
-    }
+    check_text(request.text).map_err(|error| anyhow!("{error}"))?;
 
-    let text_len = request.text.chars().count();
-    if text_len > MAX_SYNTHESIS_TEXT_LENGTH {
+    if !is_valid_synthesis_rate(request.rate) {
         return Err(anyhow!(
-            "Text too long: {text_len} characters (max: {MAX_SYNTHESIS_TEXT_LENGTH})"
+            "Rate must be between {MIN_SYNTHESIS_RATE:.1} and {MAX_SYNTHESIS_RATE:.1}, got: {}",
+            request.rate
         ));
     }
 
-    if !is_valid_synthesis_rate(request.rate) {
+    if !is_valid_synthesis_pitch(request.pitch) {
         return Err(anyhow!(
-            "Rate must be between {MIN_SYNTHESIS_RATE:.1} and {MAX_SYNTHESIS_RATE:.1}, got: {}",
-            request.rate
+            "Pitch must be between {MIN_SYNTHESIS_PITCH:.2} and {MAX_SYNTHESIS_PITCH:.2}, got: {}",
+            request.pitch
         ));
     }
 
@@ -45,6 +45,7 @@ mod kani_proofs {
             text: "hello",
             style_id: kani::any(),
             rate: kani::any(),
+            pitch: 0.0,
         };
 
         let result = validate_basic_request(&request);
@@ -62,6 +63,7 @@ mod kani_proofs {
             text: " \n\t ",
             style_id: kani::any(),
             rate: kani::any(),
+            pitch: 0.0,
         };
 
         assert!(validate_basic_request(&request).is_err());
@@ -0,0 +1,105 @@
+use anyhow::{Result, anyhow};
+use std::time::Duration;
+
+use super::limits::{MAX_SYNTHESIS_RATE, MIN_SYNTHESIS_RATE};
+
+/// Relative duration error below which a fitted render is accepted as-is.
+pub const FIT_TOLERANCE: f64 = 0.02;
+
+/// Parses a duration such as `30s`, `1.5s`, `750ms`, `2m`, or bare seconds (`12`).
+///
+/// # Errors
+///
+/// Returns an error if the value is not a positive duration.
+pub fn parse_duration_spec(value: &str) -> Result<Duration> {
+    let trimmed = value.trim();
+    let (number, scale) = if let Some(number) = trimmed.strip_suffix("ms") {
+        (number, 0.001)
+    } else if let Some(number) = trimmed.strip_suffix('s') {
+        (number, 1.0)
+    } else if let Some(number) = trimmed.strip_suffix('m') {
+        (number, 60.0)
+    } else {
+        (trimmed, 1.0)
+    };
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .map(|number| number * scale)
+        .filter(|seconds| seconds.is_finite() && *seconds > 0.0)
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .ok_or_else(|| anyhow!("Invalid duration '{value}' (expected e.g. 30s, 1.5s or 750ms)"))
+}
+
+/// Speech rate chosen to hit a target duration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateFit {
+    pub rate: f32,
+    /// The ideal rate fell outside the supported range and was clamped to it.
+    pub clamped: bool,
+}
+
+/// Estimates the rate that turns a render of `natural` length at `rate` into `target`.
+///
+/// Duration scales roughly inversely with the speed scale, so the estimate is
+/// `rate * natural / target`, clamped to the supported rate range.
+#[must_use]
+pub fn fit_rate(natural: Duration, rate: f32, target: Duration) -> RateFit {
+    let ideal = f64::from(rate) * natural.as_secs_f64() / target.as_secs_f64();
+    let clamped = ideal.clamp(f64::from(MIN_SYNTHESIS_RATE), f64::from(MAX_SYNTHESIS_RATE));
+    RateFit {
+        rate: clamped as f32,
+        clamped: (clamped - ideal).abs() > f64::EPSILON,
+    }
+}
+
+/// Whether `actual` is within [`FIT_TOLERANCE`] of `target`.
+#[must_use]
+pub fn is_within_fit_tolerance(actual: Duration, target: Duration) -> bool {
+    let target = target.as_secs_f64();
+    (actual.as_secs_f64() - target).abs() <= target * FIT_TOLERANCE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_duration_units() {
+        assert_eq!(
+            parse_duration_spec("30s").expect("valid"),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            parse_duration_spec("750ms").expect("valid"),
+            Duration::from_millis(750)
+        );
+        assert_eq!(
+            parse_duration_spec("2m").expect("valid"),
+            Duration::from_secs(120)
+        );
+        assert_eq!(
+            parse_duration_spec("1.5").expect("valid"),
+            Duration::from_millis(1500)
+        );
+        assert!(parse_duration_spec("0s").is_err());
+        assert!(parse_duration_spec("soon").is_err());
+    }
+
+    #[test]
+    fn fits_rate_inversely_and_clamps() {
+        let fit = fit_rate(Duration::from_secs(12), 1.0, Duration::from_secs(10));
+        assert!((fit.rate - 1.2).abs() < 1e-6);
+        assert!(!fit.clamped);
+
+        let fit = fit_rate(Duration::from_secs(40), 1.0, Duration::from_secs(10));
+        assert_eq!(fit.rate, MAX_SYNTHESIS_RATE);
+        assert!(fit.clamped);
+
+        assert!(is_within_fit_tolerance(
+            Duration::from_millis(10_100),
+            Duration::from_secs(10)
+        ));
+    }
+}
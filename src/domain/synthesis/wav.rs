@@ -1,12 +1,15 @@
 use anyhow::{Context, Result, bail, ensure};
+use std::time::Duration;
+
+use super::assembler::Assembler;
 
 const RIFF_HEADER_LEN: usize = 12; // "RIFF" + size + "WAVE"
 
 /// Concatenates multiple WAV segments into a single WAV file.
 ///
 /// All segments must share the same audio format (channels, sample rate,
-/// bits per sample). The function extracts PCM data from each segment,
-/// combines them, and produces a new WAV with a valid header.
+/// bits per sample). A single segment is returned as is; several are joined
+/// through an [`Assembler`].
 ///
 /// # Errors
 ///
@@ -18,27 +21,24 @@ pub fn concatenate_wav_segments(segments: &[Vec<u8>]) -> Result<Vec<u8>> {
         return Ok(segments[0].clone());
     }
 
-    let first_header = parse_wav_header(&segments[0]).context("Failed to parse first segment")?;
-
-    let mut total_data_size: usize = 0;
-    let mut pcm_chunks: Vec<&[u8]> = Vec::with_capacity(segments.len());
-
-    for (i, segment) in segments.iter().enumerate() {
-        let header =
-            parse_wav_header(segment).with_context(|| format!("Failed to parse segment {i}"))?;
-        ensure!(
-            header.channels == first_header.channels
-                && header.sample_rate == first_header.sample_rate
-                && header.bits_per_sample == first_header.bits_per_sample,
-            "Segment {i} has incompatible audio format"
-        );
-        let pcm = &segment[header.data_offset..header.data_offset + header.data_size];
-        total_data_size += pcm.len();
-        pcm_chunks.push(pcm);
+    let mut assembler = Assembler::new();
+    for segment in segments {
+        assembler.push_audio(segment)?;
     }
+    assembler.finish()
+}
 
-    // Copy everything before the data chunk from the first segment, then write new data chunk
-    let pre_data_len = first_header.data_offset - 8; // offset of "data" chunk header
+/// Builds a WAV that reuses `template`'s pre-data chunks (fmt, etc.) around new PCM.
+///
+/// `data_offset` is where the template's PCM starts, right after its data chunk header.
+pub(super) fn assemble_wav(
+    template: &[u8],
+    data_offset: usize,
+    pcm_chunks: &[&[u8]],
+    total_data_size: usize,
+) -> Result<Vec<u8>> {
+    // Copy everything before the data chunk from the template, then write new data chunk
+    let pre_data_len = data_offset - 8; // offset of "data" chunk header
     let output_size = pre_data_len + 8 + total_data_size; // pre-data + data header + PCM
 
     let data_size_u32 =
@@ -53,27 +53,109 @@ pub fn concatenate_wav_segments(segments: &[Vec<u8>]) -> Result<Vec<u8>> {
     output.extend_from_slice(&file_size.to_le_bytes());
     output.extend_from_slice(b"WAVE");
 
-    // Chunks before data (fmt, etc.) -- copy from first segment
-    output.extend_from_slice(&segments[0][RIFF_HEADER_LEN..pre_data_len]);
+    // Chunks before data (fmt, etc.) -- copy from template
+    output.extend_from_slice(&template[RIFF_HEADER_LEN..pre_data_len]);
 
     // Data chunk header with combined size
     output.extend_from_slice(b"data");
     output.extend_from_slice(&data_size_u32.to_le_bytes());
 
     // Combined PCM data
-    for pcm in &pcm_chunks {
+    for pcm in pcm_chunks {
         output.extend_from_slice(pcm);
     }
 
     Ok(output)
 }
 
-struct WavHeader {
-    channels: u16,
-    sample_rate: u32,
-    bits_per_sample: u16,
-    data_offset: usize,
-    data_size: usize,
+/// A rendered clip placed on an absolute timeline.
+pub struct TimelineClip<'a> {
+    pub start: Duration,
+    /// Audio past this length is cut so the clip cannot run into the next slot.
+    pub max_length: Duration,
+    pub wav: &'a [u8],
+}
+
+/// Lays clips out on a silent track of `total` length, producing one WAV.
+///
+/// Gaps are filled with silence. All clips must share the same audio format.
+///
+/// # Errors
+///
+/// Returns an error if there are no clips, any clip is malformed, or formats differ.
+pub fn render_timeline(clips: &[TimelineClip<'_>], total: Duration) -> Result<Vec<u8>> {
+    let first = clips.first().context("No clips to place on the timeline")?;
+    let first_header = parse_wav_header(first.wav).context("Failed to parse first clip")?;
+    let frame_bytes =
+        usize::from(first_header.channels) * usize::from(first_header.bits_per_sample / 8);
+    ensure!(frame_bytes > 0, "Clip declares an empty sample format");
+    let byte_offset = |time: Duration| {
+        let frames = time.as_secs_f64() * f64::from(first_header.sample_rate);
+        (frames.round() as usize).saturating_mul(frame_bytes)
+    };
+
+    let mut track = vec![0u8; byte_offset(total)];
+    for (i, clip) in clips.iter().enumerate() {
+        let header =
+            parse_wav_header(clip.wav).with_context(|| format!("Failed to parse clip {i}"))?;
+        ensure!(
+            header.channels == first_header.channels
+                && header.sample_rate == first_header.sample_rate
+                && header.bits_per_sample == first_header.bits_per_sample,
+            "Clip {i} has incompatible audio format"
+        );
+        let start = byte_offset(clip.start).min(track.len());
+        let length = header
+            .data_size
+            .min(byte_offset(clip.max_length))
+            .min(track.len() - start);
+        let length = length - length % frame_bytes;
+        track[start..start + length]
+            .copy_from_slice(&clip.wav[header.data_offset..header.data_offset + length]);
+    }
+
+    let data_size = track.len();
+    assemble_wav(first.wav, first_header.data_offset, &[&track], data_size)
+}
+
+/// Returns the playback duration of a PCM WAV buffer.
+///
+/// # Errors
+///
+/// Returns an error if the WAV is malformed or declares a zero-sized frame.
+pub fn wav_duration(wav: &[u8]) -> Result<Duration> {
+    let header = parse_wav_header(wav)?;
+    let bytes_per_second = u64::from(header.sample_rate)
+        * u64::from(header.channels)
+        * u64::from(header.bits_per_sample / 8);
+    ensure!(bytes_per_second > 0, "WAV declares an empty sample format");
+    Ok(Duration::from_secs_f64(
+        header.data_size as f64 / bytes_per_second as f64,
+    ))
+}
+
+/// Cuts a PCM WAV buffer down to at most `max` of audio, on a frame boundary.
+///
+/// # Errors
+///
+/// Returns an error if the WAV is malformed or declares a zero-sized frame.
+pub fn truncate_wav(wav: &[u8], max: Duration) -> Result<Vec<u8>> {
+    let header = parse_wav_header(wav)?;
+    let frame_bytes = usize::from(header.channels) * usize::from(header.bits_per_sample / 8);
+    ensure!(frame_bytes > 0, "WAV declares an empty sample format");
+    let frames = (max.as_secs_f64() * f64::from(header.sample_rate)).floor() as usize;
+    let length = header.data_size.min(frames.saturating_mul(frame_bytes));
+    let length = length - length % frame_bytes;
+    let pcm = &wav[header.data_offset..header.data_offset + length];
+    assemble_wav(wav, header.data_offset, &[pcm], length)
+}
+
+pub(crate) struct WavHeader {
+    pub(crate) channels: u16,
+    pub(crate) sample_rate: u32,
+    pub(crate) bits_per_sample: u16,
+    pub(crate) data_offset: usize,
+    pub(crate) data_size: usize,
 }
 
 /// Computes the next chunk position with RIFF even-byte alignment.
@@ -90,7 +172,7 @@ fn next_chunk_pos(pos: usize, chunk_size: usize, data_len: usize) -> Option<usiz
     }
 }
 
-fn parse_wav_header(data: &[u8]) -> Result<WavHeader> {
+pub(crate) fn parse_wav_header(data: &[u8]) -> Result<WavHeader> {
     ensure!(data.len() >= RIFF_HEADER_LEN, "WAV data too short");
     ensure!(&data[0..4] == b"RIFF", "Missing RIFF marker");
     ensure!(&data[8..12] == b"WAVE", "Missing WAVE marker");
@@ -152,34 +234,41 @@ fn parse_wav_header(data: &[u8]) -> Result<WavHeader> {
     bail!("No data chunk found in WAV")
 }
 
+/// Builds a minimal PCM WAV around `pcm`.
+#[cfg(any(test, feature = "opus"))]
+pub(crate) fn make_wav(
+    pcm: &[u8],
+    channels: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+) -> Vec<u8> {
+    let data_size = pcm.len() as u32;
+    let byte_rate = sample_rate * u32::from(channels) * u32::from(bits_per_sample) / 8;
+    let block_align = channels * bits_per_sample / 8;
+    let file_size = 36 + data_size;
+
+    let mut wav = Vec::new();
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&file_size.to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&channels.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&byte_rate.to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&bits_per_sample.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_size.to_le_bytes());
+    wav.extend_from_slice(pcm);
+    wav
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn make_wav(pcm: &[u8], channels: u16, sample_rate: u32, bits_per_sample: u16) -> Vec<u8> {
-        let data_size = pcm.len() as u32;
-        let byte_rate = sample_rate * u32::from(channels) * u32::from(bits_per_sample) / 8;
-        let block_align = channels * bits_per_sample / 8;
-        let file_size = 36 + data_size;
-
-        let mut wav = Vec::new();
-        wav.extend_from_slice(b"RIFF");
-        wav.extend_from_slice(&file_size.to_le_bytes());
-        wav.extend_from_slice(b"WAVE");
-        wav.extend_from_slice(b"fmt ");
-        wav.extend_from_slice(&16u32.to_le_bytes());
-        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
-        wav.extend_from_slice(&channels.to_le_bytes());
-        wav.extend_from_slice(&sample_rate.to_le_bytes());
-        wav.extend_from_slice(&byte_rate.to_le_bytes());
-        wav.extend_from_slice(&block_align.to_le_bytes());
-        wav.extend_from_slice(&bits_per_sample.to_le_bytes());
-        wav.extend_from_slice(b"data");
-        wav.extend_from_slice(&data_size.to_le_bytes());
-        wav.extend_from_slice(pcm);
-        wav
-    }
-
     #[test]
     fn single_segment_returns_clone() {
         let wav = make_wav(&[1, 2, 3, 4], 1, 24000, 16);
@@ -207,6 +296,51 @@ mod tests {
         assert!(concatenate_wav_segments(&[wav1, wav2]).is_err());
     }
 
+    #[test]
+    fn duration_follows_data_size_and_format() {
+        let wav = make_wav(&[0; 48000], 1, 24000, 16);
+        assert_eq!(wav_duration(&wav).unwrap(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn truncation_keeps_whole_frames_up_to_the_limit() {
+        // 1 kHz, 16-bit mono: one frame per millisecond.
+        let wav = make_wav(&[1, 1, 2, 2, 3, 3, 4, 4], 1, 1000, 16);
+        let clipped = truncate_wav(&wav, Duration::from_micros(2500)).unwrap();
+        assert_eq!(clipped, make_wav(&[1, 1, 2, 2], 1, 1000, 16));
+
+        let untouched = truncate_wav(&wav, Duration::from_secs(1)).unwrap();
+        assert_eq!(untouched, wav);
+    }
+
+    #[test]
+    fn timeline_pads_gaps_and_cuts_overruns() {
+        // 1 kHz, 16-bit mono: one frame per millisecond.
+        let first = make_wav(&[1, 1, 2, 2], 1, 1000, 16);
+        let second = make_wav(&[3, 3, 4, 4, 5, 5], 1, 1000, 16);
+        let track = render_timeline(
+            &[
+                TimelineClip {
+                    start: Duration::from_millis(1),
+                    max_length: Duration::from_millis(5),
+                    wav: &first,
+                },
+                TimelineClip {
+                    start: Duration::from_millis(4),
+                    max_length: Duration::from_millis(2),
+                    wav: &second,
+                },
+            ],
+            Duration::from_millis(7),
+        )
+        .unwrap();
+        let header = parse_wav_header(&track).unwrap();
+        assert_eq!(
+            &track[header.data_offset..],
+            &[0, 0, 1, 1, 2, 2, 0, 0, 3, 3, 4, 4, 0, 0]
+        );
+    }
+
     #[test]
     fn empty_segments_rejected() {
         let result = concatenate_wav_segments(&[]);
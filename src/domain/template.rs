@@ -0,0 +1,114 @@
+use anyhow::{Result, anyhow};
+use std::collections::BTreeMap;
+
+const OPEN: &str = "{{";
+const CLOSE: &str = "}}";
+
+/// A template variable value plus an optional reading that is spoken instead.
+///
+/// Readings let scripted announcements spell a name in kanji for logs while the
+/// synthesizer receives kana that it is guaranteed to pronounce correctly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateVar {
+    pub value: String,
+    pub reading: Option<String>,
+}
+
+impl TemplateVar {
+    fn spoken(&self) -> &str {
+        self.reading.as_deref().unwrap_or(&self.value)
+    }
+}
+
+pub type TemplateVars = BTreeMap<String, TemplateVar>;
+
+/// Splits a `name=value` assignment.
+///
+/// # Errors
+///
+/// Returns an error if there is no `=` or the name is empty.
+pub fn parse_assignment(assignment: &str) -> Result<(String, String)> {
+    let (name, value) = assignment
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Expected NAME=VALUE, got '{assignment}'"))?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(anyhow!("Variable name is empty in '{assignment}'"));
+    }
+    Ok((name.to_owned(), value.to_owned()))
+}
+
+/// Builds the variable table from `--var` values and `--reading` overrides.
+///
+/// # Errors
+///
+/// Returns an error if an assignment is malformed or a reading names an unknown variable.
+pub fn collect_template_vars(values: &[String], readings: &[String]) -> Result<TemplateVars> {
+    let mut vars = TemplateVars::new();
+    for assignment in values {
+        let (name, value) = parse_assignment(assignment)?;
+        vars.entry(name).or_default().value = value;
+    }
+    for assignment in readings {
+        let (name, reading) = parse_assignment(assignment)?;
+        let var = vars
+            .get_mut(&name)
+            .ok_or_else(|| anyhow!("Reading given for undefined variable '{name}'"))?;
+        var.reading = Some(reading);
+    }
+    Ok(vars)
+}
+
+/// Substitutes `{{name}}` placeholders with each variable's spoken form.
+///
+/// # Errors
+///
+/// Returns an error if a placeholder is unterminated or names an undefined variable.
+pub fn render_template(template: &str, vars: &TemplateVars) -> Result<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find(OPEN) {
+        rendered.push_str(&rest[..start]);
+        let after_open = &rest[start + OPEN.len()..];
+        let end = after_open
+            .find(CLOSE)
+            .ok_or_else(|| anyhow!("Unterminated '{OPEN}' in template"))?;
+        let name = after_open[..end].trim();
+        let var = vars.get(name).ok_or_else(|| {
+            anyhow!("Template variable '{name}' is not defined (use --var {name}=...)")
+        })?;
+        rendered.push_str(var.spoken());
+        rest = &after_open[end + CLOSE.len()..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_values_and_prefers_readings() {
+        let vars = collect_template_vars(
+            &["name=田中".to_owned(), "room= 3番".to_owned()],
+            &["name=たなか".to_owned()],
+        )
+        .expect("valid vars");
+
+        assert_eq!(
+            render_template("こんにちは、{{ name }}さん。{{room}}へどうぞ", &vars)
+                .expect("renders"),
+            "こんにちは、たなかさん。 3番へどうぞ"
+        );
+    }
+
+    #[test]
+    fn rejects_undefined_and_unterminated_placeholders() {
+        let vars = TemplateVars::new();
+        assert!(render_template("{{name}}", &vars).is_err());
+        assert!(render_template("{{name", &vars).is_err());
+        assert!(collect_template_vars(&[], &["name=たなか".to_owned()]).is_err());
+        assert!(parse_assignment("novalue").is_err());
+    }
+}
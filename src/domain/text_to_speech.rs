@@ -4,6 +4,9 @@ use crate::domain::synthesis::limits::DEFAULT_SYNTHESIS_RATE;
 
 pub const MAX_STYLE_ID: u32 = 1000;
 
+/// Longest text the MCP tool speaks as one piece; longer text is chunked server-side.
+pub const MAX_CHUNK_CHARS: usize = 200;
+
 #[derive(Debug, Clone)]
 pub struct SynthesizeParams {
     pub text: String,
@@ -27,17 +30,82 @@ pub const fn default_streaming() -> bool {
     true
 }
 
-#[must_use]
-pub fn text_char_count(text: &str) -> usize {
-    text.chars().count()
-}
-
 pub fn validate_style_id(style_id: u32) -> Result<()> {
     is_valid_style_id(style_id)
         .then_some(())
         .ok_or_else(|| anyhow!("Invalid style_id: {} (max: {})", style_id, MAX_STYLE_ID))
 }
 
+/// Packs consecutive sentences into chunks of at most `max_chars` characters.
+///
+/// A sentence longer than `max_chars` becomes a chunk of its own; the sentence
+/// splitter already caps sentence length, so this only happens with a small limit.
+#[must_use]
+pub fn chunk_sentences(sentences: Vec<String>, max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_chars = 0;
+    for sentence in sentences {
+        let sentence_chars = sentence.chars().count();
+        if current_chars > 0 && current_chars + sentence_chars > max_chars {
+            chunks.push(std::mem::take(&mut current));
+            current_chars = 0;
+        }
+        current.push_str(&sentence);
+        current_chars += sentence_chars;
+    }
+    if !current.trim().is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Re-packs a failed chunk's sentences into pieces of at most half its length.
+///
+/// Returns `None` when the sentences cannot be spread over more than one piece.
+#[must_use]
+pub fn rechunk_smaller(sentences: Vec<String>) -> Option<Vec<String>> {
+    let total_chars = sentences
+        .iter()
+        .map(|sentence| sentence.chars().count())
+        .sum::<usize>();
+    let pieces = chunk_sentences(sentences, (total_chars / 2).max(1));
+    (pieces.len() > 1).then_some(pieces)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packs_sentences_up_to_the_chunk_limit() {
+        let sentences = ["あいう。", "えお。", "かきくけこ。", "さ。"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(
+            chunk_sentences(sentences, 8),
+            vec!["あいう。えお。", "かきくけこ。さ。"]
+        );
+        assert!(chunk_sentences(Vec::new(), 7).is_empty());
+    }
+
+    #[test]
+    fn rechunks_into_halves_unless_a_single_sentence() {
+        let sentences = ["あいう。", "えお。", "かきくけこ。", "さ。"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(
+            rechunk_smaller(sentences),
+            Some(vec![
+                "あいう。えお。".to_owned(),
+                "かきくけこ。".to_owned(),
+                "さ。".to_owned()
+            ])
+        );
+        assert_eq!(rechunk_smaller(vec!["あいうえお。".to_owned()]), None);
+    }
+}
+
 #[cfg(kani)]
 mod kani_proofs {
     use super::*;
@@ -0,0 +1,174 @@
+use anyhow::{Result, bail, ensure};
+use serde::{Deserialize, Serialize};
+
+/// Priority used when a source does not say; matches the VOICEVOX default.
+pub const DEFAULT_WORD_PRIORITY: u32 = 5;
+
+/// Highest word priority; higher values win over the built-in dictionary more often.
+pub const MAX_WORD_PRIORITY: u32 = 10;
+
+/// Part of speech of a user dictionary word, as VOICEVOX Core classifies it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WordType {
+    #[default]
+    ProperNoun,
+    CommonNoun,
+    Verb,
+    Adjective,
+    Suffix,
+}
+
+/// A pronunciation override for one surface form.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DictionaryWord {
+    pub surface: String,
+    /// Reading in katakana.
+    pub pronunciation: String,
+    /// Mora position of the accent drop (0 for a flat accent).
+    pub accent_type: usize,
+    #[serde(default)]
+    pub word_type: WordType,
+    #[serde(default = "default_priority")]
+    pub priority: u32,
+}
+
+const fn default_priority() -> u32 {
+    DEFAULT_WORD_PRIORITY
+}
+
+/// Small kana that join the preceding kana into one mora.
+const GLIDE_KANA: [char; 9] = ['ァ', 'ィ', 'ゥ', 'ェ', 'ォ', 'ャ', 'ュ', 'ョ', 'ヮ'];
+
+impl DictionaryWord {
+    /// Number of morae in the pronunciation, which bounds the accent position.
+    #[must_use]
+    pub fn mora_count(&self) -> usize {
+        self.pronunciation
+            .chars()
+            .filter(|ch| !GLIDE_KANA.contains(ch))
+            .count()
+    }
+
+    /// Checks the word the way VOICEVOX Core will when the dictionary is loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the surface is empty, the pronunciation is not katakana,
+    /// the accent lies past the last mora, or the priority is out of range.
+    pub fn validate(&self) -> Result<()> {
+        ensure!(!self.surface.trim().is_empty(), "Word surface is empty");
+        if let Some(ch) = self
+            .pronunciation
+            .chars()
+            .find(|&ch| !matches!(ch, 'ァ'..='ヴ' | 'ー'))
+        {
+            bail!(
+                "Pronunciation '{}' must be katakana, found '{ch}'",
+                self.pronunciation
+            );
+        }
+        ensure!(
+            self.pronunciation
+                .chars()
+                .next()
+                .is_some_and(|ch| !GLIDE_KANA.contains(&ch)),
+            "Pronunciation '{}' must start with a full kana",
+            self.pronunciation
+        );
+        ensure!(
+            self.accent_type <= self.mora_count(),
+            "Accent {} is past the last of {} morae in '{}'",
+            self.accent_type,
+            self.mora_count(),
+            self.pronunciation
+        );
+        ensure!(
+            self.priority <= MAX_WORD_PRIORITY,
+            "Priority {} exceeds {MAX_WORD_PRIORITY}",
+            self.priority
+        );
+        Ok(())
+    }
+}
+
+/// Adds `imported` words to `words`, replacing existing entries with the same surface.
+///
+/// Returns how many words were added or replaced.
+pub fn merge_dictionary_words(
+    words: &mut Vec<DictionaryWord>,
+    imported: impl IntoIterator<Item = DictionaryWord>,
+) -> usize {
+    let mut changed = 0;
+    for word in imported {
+        match words
+            .iter_mut()
+            .find(|existing| existing.surface == word.surface)
+        {
+            Some(existing) if *existing == word => continue,
+            Some(existing) => *existing = word,
+            None => words.push(word),
+        }
+        changed += 1;
+    }
+    changed
+}
+
+/// Removes the word with `surface`; returns whether one was there.
+pub fn remove_dictionary_word(words: &mut Vec<DictionaryWord>, surface: &str) -> bool {
+    let before = words.len();
+    words.retain(|word| word.surface != surface);
+    words.len() != before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(surface: &str, pronunciation: &str) -> DictionaryWord {
+        DictionaryWord {
+            surface: surface.to_owned(),
+            pronunciation: pronunciation.to_owned(),
+            accent_type: 1,
+            word_type: WordType::ProperNoun,
+            priority: DEFAULT_WORD_PRIORITY,
+        }
+    }
+
+    #[test]
+    fn merging_replaces_by_surface_and_skips_identical_words() {
+        let mut words = vec![word("東方", "トーホー"), word("刀剣", "トーケン")];
+        let changed = merge_dictionary_words(
+            &mut words,
+            [
+                word("東方", "ヒガシカタ"),
+                word("刀剣", "トーケン"),
+                word("鬼滅", "キメツ"),
+            ],
+        );
+
+        assert_eq!(changed, 2);
+        assert_eq!(
+            words,
+            [
+                word("東方", "ヒガシカタ"),
+                word("刀剣", "トーケン"),
+                word("鬼滅", "キメツ")
+            ]
+        );
+    }
+
+    #[test]
+    fn validation_checks_kana_and_accent_position() {
+        let mut entry = word("ずんだ", "ズンダ");
+        entry.accent_type = 3;
+        assert!(entry.validate().is_ok());
+        assert_eq!(word("東京", "トーキョー").mora_count(), 4);
+
+        entry.accent_type = 4;
+        assert!(entry.validate().is_err());
+        assert!(word("ずんだ", "ずんだ").validate().is_err());
+        assert!(word("ずんだ", "ャン").validate().is_err());
+        assert!(word(" ", "ズンダ").validate().is_err());
+    }
+}
@@ -1,3 +1,80 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Returns whether `value` is a hyphenated UUID such as a speaker UUID.
+#[must_use]
+pub fn is_speaker_uuid(value: &str) -> bool {
+    value.len() == 36
+        && value.char_indices().all(|(index, ch)| match index {
+            8 | 13 | 18 | 23 => ch == '-',
+            _ => ch.is_ascii_hexdigit(),
+        })
+}
+
+/// Canonical voice identifier, written `<speaker_uuid>/<style_name>`.
+///
+/// Unlike numeric style IDs it does not change when model files are renumbered, so
+/// it is the form saved setups should store.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct VoiceId {
+    pub speaker_uuid: String,
+    pub style_name: String,
+}
+
+impl VoiceId {
+    pub const SEPARATOR: char = '/';
+
+    /// Parses `<speaker_uuid>/<style_name>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the UUID is malformed or the style name is empty.
+    pub fn parse(value: &str) -> Result<Self> {
+        let invalid =
+            || anyhow!("Invalid voice ID '{value}' (expected <speaker-uuid>/<style-name>)");
+        let (speaker_uuid, style_name) = value
+            .trim()
+            .split_once(Self::SEPARATOR)
+            .ok_or_else(invalid)?;
+        let style_name = style_name.trim();
+        if !is_speaker_uuid(speaker_uuid) || style_name.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            speaker_uuid: speaker_uuid.to_ascii_lowercase(),
+            style_name: style_name.to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for VoiceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            self.speaker_uuid,
+            Self::SEPARATOR,
+            self.style_name
+        )
+    }
+}
+
+impl TryFrom<String> for VoiceId {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        Self::parse(&value)
+    }
+}
+
+impl From<VoiceId> for String {
+    fn from(value: VoiceId) -> Self {
+        value.to_string()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ListVoiceStylesFilter {
     pub speaker_name: Option<String>,
@@ -61,6 +138,31 @@ pub fn filter_speakers(
         .collect()
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn voice_ids_roundtrip_through_their_canonical_form() {
+        let voice =
+            VoiceId::parse("388F246B-8C41-4AC1-8E2D-5D79F3FF56D9/ノーマル").expect("valid id");
+        assert_eq!(voice.speaker_uuid, "388f246b-8c41-4ac1-8e2d-5d79f3ff56d9");
+        assert_eq!(
+            voice.to_string(),
+            "388f246b-8c41-4ac1-8e2d-5d79f3ff56d9/ノーマル"
+        );
+        assert_eq!(
+            serde_json::from_str::<VoiceId>(&serde_json::to_string(&voice).expect("serialize"))
+                .expect("deserialize"),
+            voice
+        );
+
+        assert!(VoiceId::parse("388f246b-8c41-4ac1-8e2d-5d79f3ff56d9/").is_err());
+        assert!(VoiceId::parse("zundamon/ノーマル").is_err());
+        assert!(VoiceId::parse("3").is_err());
+    }
+}
+
 #[cfg(kani)]
 mod kani_proofs {
     use super::*;
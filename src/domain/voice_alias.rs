@@ -0,0 +1,63 @@
+use anyhow::{Result, bail, ensure};
+use std::collections::BTreeMap;
+
+/// User-defined voice names, keyed by lowercase alias, each naming any voice
+/// `--voice` accepts: a style ID, a voice name, or a `<speaker-uuid>/<style>` ID.
+pub type VoiceAliases = BTreeMap<String, String>;
+
+/// The key `alias` is stored and looked up under.
+#[must_use]
+pub fn alias_key(alias: &str) -> String {
+    alias.trim().to_lowercase()
+}
+
+/// Checks that `alias` can be told apart from other voice input and that it
+/// points at something other than an alias.
+///
+/// # Errors
+///
+/// Returns an error if the alias is empty, contains whitespace, is a number that
+/// would shadow a style ID, or if the target is empty or itself an alias.
+pub fn validate_alias(aliases: &VoiceAliases, alias: &str, target: &str) -> Result<()> {
+    let alias = alias.trim();
+    ensure!(!alias.is_empty(), "Alias name is empty");
+    ensure!(
+        !alias.contains(char::is_whitespace),
+        "Alias '{alias}' must not contain spaces"
+    );
+    if alias.parse::<u32>().is_ok() {
+        bail!("Alias '{alias}' is a number, which would hide style ID {alias}");
+    }
+    let target = target.trim();
+    ensure!(!target.is_empty(), "Alias '{alias}' points at no voice");
+    ensure!(
+        !aliases.contains_key(&alias_key(target)),
+        "'{target}' is itself an alias; point '{alias}' at a voice instead"
+    );
+    Ok(())
+}
+
+/// The voice `input` stands for: its alias target, or `input` itself.
+#[must_use]
+pub fn expand_alias<'a>(aliases: &'a VoiceAliases, input: &'a str) -> &'a str {
+    aliases.get(&alias_key(input)).map_or(input, String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aliases_expand_case_insensitively_and_reject_ambiguous_names() {
+        let aliases = VoiceAliases::from([("zunda".to_string(), "3".to_string())]);
+
+        assert_eq!(expand_alias(&aliases, " Zunda "), "3");
+        assert_eq!(expand_alias(&aliases, "metan"), "metan");
+
+        validate_alias(&aliases, "sad-zunda", "zundamon-tearful").expect("valid");
+        assert!(validate_alias(&aliases, "3", "zundamon").is_err());
+        assert!(validate_alias(&aliases, "my voice", "3").is_err());
+        assert!(validate_alias(&aliases, "zundamon2", "ZUNDA").is_err());
+        assert!(validate_alias(&aliases, "empty", " ").is_err());
+    }
+}
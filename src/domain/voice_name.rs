@@ -0,0 +1,278 @@
+use anyhow::{Result, anyhow, bail};
+
+use crate::domain::voice::SpeakerStyles;
+
+/// Romanized names for speakers whose names are mostly kanji, which the kana
+/// romanizer cannot spell.
+const SPEAKER_ALIASES: &[(&str, &str)] = &[
+    ("四国めたん", "shikoku-metan"),
+    ("春日部つむぎ", "kasukabe-tsumugi"),
+    ("雨晴はう", "amehare-hau"),
+    ("波音リツ", "namine-ritsu"),
+    ("玄野武宏", "kurono-takehiro"),
+    ("白上虎太郎", "shirakami-kotaro"),
+    ("青山龍星", "aoyama-ryusei"),
+    ("冥鳴ひまり", "meimei-himari"),
+    ("九州そら", "kyushu-sora"),
+    ("もち子さん", "mochiko-san"),
+    ("剣崎雌雄", "kenzaki-mesuo"),
+];
+
+/// English names for common styles.
+const STYLE_ALIASES: &[(&str, &str)] = &[
+    ("ノーマル", "normal"),
+    ("あまあま", "sweet"),
+    ("ツンツン", "tsundere"),
+    ("セクシー", "sexy"),
+    ("ささやき", "whisper"),
+    ("ヒソヒソ", "murmur"),
+    ("なみだめ", "tearful"),
+    ("ヘロヘロ", "exhausted"),
+    ("喜び", "happy"),
+    ("悲しみ", "sad"),
+    ("怒り", "angry"),
+];
+
+/// Resolves a human-friendly voice name such as `zundamon-whisper` to a style ID.
+///
+/// The name is a speaker, optionally followed by a style after `-`, `_`, `/`, `:`,
+/// or a space; without a style the speaker's first style is used. Speakers and
+/// styles match their Japanese names, romanized kana (`zundamon`, `sasayaki`), or
+/// the built-in English aliases (`shikoku-metan`, `whisper`), ignoring case.
+///
+/// # Errors
+///
+/// Returns an error if no installed speaker and style match, or the name is ambiguous.
+pub fn resolve_voice_name(name: &str, speakers: &[SpeakerStyles]) -> Result<u32> {
+    let tokens = name
+        .split(['-', '_', '/', ':', ' ', '\u{3000}'])
+        .map(romanize)
+        .filter(|token| !token.is_empty())
+        .collect::<Vec<_>>();
+    if tokens.is_empty() {
+        bail!("Voice name is empty");
+    }
+
+    let mut matches = Vec::new();
+    for split in 1..=tokens.len() {
+        let speaker_key = tokens[..split].concat();
+        let style_key = tokens[split..].concat();
+        for speaker in speakers {
+            if !speaker_keys(&speaker.speaker_name).contains(&speaker_key) {
+                continue;
+            }
+            let style = if style_key.is_empty() {
+                speaker.styles.first()
+            } else {
+                speaker
+                    .styles
+                    .iter()
+                    .find(|style| style_keys(&style.name).contains(&style_key))
+            };
+            if let Some(style) = style {
+                matches.push((speaker, style));
+            }
+        }
+    }
+
+    match matches.as_slice() {
+        [(_, style)] => Ok(style.id),
+        [] => Err(anyhow!(
+            "No installed voice matches '{name}'. Use list_voice_styles to see voices."
+        )),
+        [(first_speaker, first_style), ..] => {
+            if matches.iter().all(|(_, style)| style.id == first_style.id) {
+                return Ok(first_style.id);
+            }
+            Err(anyhow!(
+                "Voice '{name}' is ambiguous (e.g. {} {}); add the style or use style_id",
+                first_speaker.speaker_name,
+                first_style.name
+            ))
+        }
+    }
+}
+
+fn speaker_keys(speaker_name: &str) -> Vec<String> {
+    let mut keys = vec![romanize(speaker_name)];
+    keys.extend(
+        SPEAKER_ALIASES
+            .iter()
+            .filter(|(japanese, _)| *japanese == speaker_name)
+            .map(|(_, alias)| romanize(alias)),
+    );
+    keys.retain(|key| !key.is_empty());
+    keys
+}
+
+fn style_keys(style_name: &str) -> Vec<String> {
+    let mut keys = vec![romanize(style_name)];
+    keys.extend(
+        STYLE_ALIASES
+            .iter()
+            .filter(|(japanese, _)| *japanese == style_name)
+            .map(|(_, alias)| romanize(alias)),
+    );
+    keys.retain(|key| !key.is_empty());
+    keys
+}
+
+/// Spells kana in Hepburn romaji and keeps ASCII letters and digits in lowercase.
+///
+/// Anything else, such as kanji, punctuation, and the long vowel mark, is dropped,
+/// so `ノーマル` becomes `nomaru`.
+#[must_use]
+pub fn romanize(value: &str) -> String {
+    let mut output = String::new();
+    let mut double_next = false;
+    for ch in value.chars().map(katakana_to_hiragana) {
+        match ch {
+            'っ' => double_next = true,
+            'ゃ' | 'ゅ' | 'ょ' if output.ends_with('i') => {
+                output.pop();
+                let palatal =
+                    output.ends_with("sh") || output.ends_with("ch") || output.ends_with('j');
+                if !palatal {
+                    output.push('y');
+                }
+                output.push_str(small_vowel(ch));
+            }
+            'ぁ' | 'ぃ' | 'ぅ' | 'ぇ' | 'ぉ' | 'ゃ' | 'ゅ' | 'ょ' | 'ゎ' => {
+                // Extended kana such as ティ and ファ replace the preceding vowel.
+                if output.ends_with(['a', 'i', 'u', 'e', 'o']) {
+                    output.pop();
+                }
+                output.push_str(small_vowel(ch));
+            }
+            _ => {
+                let syllable = match kana_syllable(ch) {
+                    Some(syllable) => syllable.to_string(),
+                    None if ch.is_ascii_alphanumeric() => ch.to_ascii_lowercase().to_string(),
+                    None => continue,
+                };
+                if std::mem::take(&mut double_next)
+                    && let Some(first) = syllable.chars().next()
+                    && !matches!(first, 'a' | 'i' | 'u' | 'e' | 'o' | 'n')
+                {
+                    output.push(if first == 'c' { 't' } else { first });
+                }
+                output.push_str(&syllable);
+            }
+        }
+    }
+    output
+}
+
+fn katakana_to_hiragana(ch: char) -> char {
+    match ch {
+        'ァ'..='ヶ' => char::from_u32(ch as u32 - 0x60).unwrap_or(ch),
+        _ => ch,
+    }
+}
+
+const fn small_vowel(ch: char) -> &'static str {
+    match ch {
+        'ぁ' => "a",
+        'ぃ' => "i",
+        'ぅ' => "u",
+        'ぇ' => "e",
+        'ぉ' => "o",
+        'ゃ' => "a",
+        'ゅ' => "u",
+        'ょ' => "o",
+        _ => "wa",
+    }
+}
+
+fn kana_syllable(ch: char) -> Option<&'static str> {
+    const ROWS: &[(&str, [&str; 5])] = &[
+        ("あいうえお", ["a", "i", "u", "e", "o"]),
+        ("かきくけこ", ["ka", "ki", "ku", "ke", "ko"]),
+        ("がぎぐげご", ["ga", "gi", "gu", "ge", "go"]),
+        ("さしすせそ", ["sa", "shi", "su", "se", "so"]),
+        ("ざじずぜぞ", ["za", "ji", "zu", "ze", "zo"]),
+        ("たちつてと", ["ta", "chi", "tsu", "te", "to"]),
+        ("だぢづでど", ["da", "ji", "zu", "de", "do"]),
+        ("なにぬねの", ["na", "ni", "nu", "ne", "no"]),
+        ("はひふへほ", ["ha", "hi", "fu", "he", "ho"]),
+        ("ばびぶべぼ", ["ba", "bi", "bu", "be", "bo"]),
+        ("ぱぴぷぺぽ", ["pa", "pi", "pu", "pe", "po"]),
+        ("まみむめも", ["ma", "mi", "mu", "me", "mo"]),
+        ("らりるれろ", ["ra", "ri", "ru", "re", "ro"]),
+    ];
+    match ch {
+        'や' => return Some("ya"),
+        'ゆ' => return Some("yu"),
+        'よ' => return Some("yo"),
+        'わ' => return Some("wa"),
+        'ゐ' => return Some("i"),
+        'ゑ' => return Some("e"),
+        'を' => return Some("o"),
+        'ん' => return Some("n"),
+        'ゔ' => return Some("vu"),
+        _ => {}
+    }
+    ROWS.iter().find_map(|(kana, romaji)| {
+        kana.chars()
+            .position(|candidate| candidate == ch)
+            .map(|index| romaji[index])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::voice::VoiceStyle;
+
+    fn speaker(name: &str, styles: &[(&str, u32)]) -> SpeakerStyles {
+        SpeakerStyles {
+            speaker_name: name.to_string(),
+            styles: styles
+                .iter()
+                .map(|(name, id)| VoiceStyle {
+                    name: (*name).to_string(),
+                    id: *id,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn romanizes_kana_in_hepburn() {
+        assert_eq!(romanize("ずんだもん"), "zundamon");
+        assert_eq!(romanize("ささやき"), "sasayaki");
+        assert_eq!(romanize("ちびしきじい"), "chibishikijii");
+        assert_eq!(romanize("ツンツン"), "tsuntsun");
+        assert_eq!(romanize("きょうしゅう"), "kyoushuu");
+        assert_eq!(romanize("マッチ"), "matchi");
+        assert_eq!(romanize("No.7"), "no7");
+    }
+
+    #[test]
+    fn resolves_names_aliases_and_default_styles() {
+        let speakers = vec![
+            speaker("四国めたん", &[("ノーマル", 2), ("ささやき", 36)]),
+            speaker("ずんだもん", &[("ノーマル", 3), ("ささやき", 22)]),
+        ];
+
+        assert_eq!(
+            resolve_voice_name("zundamon-whisper", &speakers).expect("alias"),
+            22
+        );
+        assert_eq!(
+            resolve_voice_name("Zundamon Sasayaki", &speakers).expect("romaji"),
+            22
+        );
+        assert_eq!(
+            resolve_voice_name("ずんだもん/ささやき", &speakers).expect("japanese"),
+            22
+        );
+        assert_eq!(resolve_voice_name("zundamon", &speakers).expect("first"), 3);
+        assert_eq!(
+            resolve_voice_name("shikoku-metan-whisper", &speakers).expect("speaker alias"),
+            36
+        );
+        assert!(resolve_voice_name("zundamon-angry", &speakers).is_err());
+        assert!(resolve_voice_name("tsumugi", &speakers).is_err());
+    }
+}
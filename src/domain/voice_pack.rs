@@ -0,0 +1,154 @@
+use anyhow::{Result, bail, ensure};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Manifest file at the root of every voice pack archive.
+pub const PACK_MANIFEST_FILE: &str = "manifest.toml";
+
+/// Directory under the models directory recording which pack installed which model.
+pub const PACKS_SUBDIR: &str = "packs";
+
+/// Highest model ID the model scanner accepts in a `<id>.vvm` file name.
+const MAX_MODEL_ID: u32 = 9999;
+
+/// The `manifest.toml` of a voice pack: a zip holding VVM models, this manifest,
+/// and the license the models are distributed under, all at the archive root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PackManifest {
+    /// Identifies the pack across versions; letters, digits, `.`, `_`, and `-`.
+    pub name: String,
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// License file shipped in the pack.
+    pub license: String,
+    pub models: Vec<PackModel>,
+}
+
+/// One model in a pack, installed as `file` in the models directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PackModel {
+    /// `<model id>.vvm`, as the model scanner expects.
+    pub file: String,
+    /// Lowercase hex SHA-256 of the file.
+    pub sha256: String,
+}
+
+impl PackManifest {
+    /// Parses and validates a pack manifest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on TOML syntax errors, unknown keys, unsafe file names,
+    /// malformed digests, or duplicate models.
+    pub fn parse(source: &str) -> Result<Self> {
+        let manifest: Self = toml::from_str(source)?;
+        ensure!(
+            is_safe_name(&manifest.name),
+            "Pack name '{}' must use only letters, digits, '.', '_', and '-'",
+            manifest.name
+        );
+        ensure!(!manifest.version.trim().is_empty(), "Pack version is empty");
+        ensure!(
+            is_safe_name(&manifest.license),
+            "License '{}' must be a file at the pack root",
+            manifest.license
+        );
+        ensure!(!manifest.models.is_empty(), "Pack lists no models");
+
+        let mut files = BTreeSet::new();
+        for model in &manifest.models {
+            model_id_from_file(&model.file)?;
+            ensure!(
+                model.sha256.len() == 64
+                    && model
+                        .sha256
+                        .bytes()
+                        .all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f')),
+                "{}: sha256 must be 64 lowercase hex digits",
+                model.file
+            );
+            ensure!(
+                files.insert(model.file.as_str()),
+                "{} is listed more than once",
+                model.file
+            );
+        }
+        Ok(manifest)
+    }
+
+    /// Every file the pack must contain besides the manifest.
+    pub fn files(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.license.as_str())
+            .chain(self.models.iter().map(|model| model.file.as_str()))
+    }
+}
+
+/// Reads the model ID from a pack model file name such as `101.vvm`.
+///
+/// # Errors
+///
+/// Returns an error unless the name is `<id>.vvm` with an ID the scanner accepts.
+pub fn model_id_from_file(file: &str) -> Result<u32> {
+    let Some(id) = file
+        .strip_suffix(".vvm")
+        .filter(|stem| !stem.is_empty() && stem.bytes().all(|byte| byte.is_ascii_digit()))
+        .and_then(|stem| stem.parse::<u32>().ok())
+        .filter(|id| *id <= MAX_MODEL_ID)
+    else {
+        bail!("Model file '{file}' must be named <id>.vvm with an ID up to {MAX_MODEL_ID}");
+    };
+    Ok(id)
+}
+
+/// A root-level name with no path separators or archive wildcard characters.
+fn is_safe_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'_' | b'-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    fn manifest(name: &str, license: &str, files: &[&str]) -> String {
+        let models = files
+            .iter()
+            .map(|file| format!("[[models]]\nfile = \"{file}\"\nsha256 = \"{DIGEST}\"\n"))
+            .collect::<String>();
+        format!("name = \"{name}\"\nversion = \"1.0.0\"\nlicense = \"{license}\"\n{models}")
+    }
+
+    #[test]
+    fn accepts_well_formed_manifests() {
+        let parsed = PackManifest::parse(&manifest("my-voices", "LICENSE.txt", &["101.vvm"]))
+            .expect("valid manifest");
+        assert_eq!(
+            parsed.files().collect::<Vec<_>>(),
+            vec!["LICENSE.txt", "101.vvm"]
+        );
+        assert_eq!(model_id_from_file("101.vvm").expect("id"), 101);
+    }
+
+    #[test]
+    fn rejects_unsafe_names_and_duplicates() {
+        for source in [
+            manifest("../evil", "LICENSE", &["1.vvm"]),
+            manifest("ok", "docs/LICENSE", &["1.vvm"]),
+            manifest("ok", "LICENSE", &["../1.vvm"]),
+            manifest("ok", "LICENSE", &["voice.vvm"]),
+            manifest("ok", "LICENSE", &["10000.vvm"]),
+            manifest("ok", "LICENSE", &["1.vvm", "1.vvm"]),
+            manifest("ok", "LICENSE", &[]),
+        ] {
+            assert!(PackManifest::parse(&source).is_err(), "{source}");
+        }
+    }
+}
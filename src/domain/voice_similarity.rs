@@ -0,0 +1,181 @@
+use anyhow::{Result, anyhow};
+
+/// Broad delivery of a style, judged from its name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StyleCategory {
+    Normal,
+    Bright,
+    Whisper,
+    Sad,
+    Angry,
+    Tired,
+}
+
+impl StyleCategory {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Bright => "bright",
+            Self::Whisper => "whisper",
+            Self::Sad => "sad",
+            Self::Angry => "angry",
+            Self::Tired => "tired",
+        }
+    }
+
+    /// Classifies a style name by the keywords VOICEVOX styles commonly use.
+    #[must_use]
+    pub fn classify(style_name: &str) -> Option<Self> {
+        const KEYWORDS: &[(StyleCategory, &[&str])] = &[
+            (StyleCategory::Whisper, &["ささやき", "ヒソヒソ", "囁"]),
+            (StyleCategory::Tired, &["ヘロヘロ", "へろへろ", "疲"]),
+            (
+                StyleCategory::Sad,
+                &["悲", "なみだめ", "泣", "かなしみ", "しょんぼり"],
+            ),
+            (
+                StyleCategory::Angry,
+                &["怒", "ツンツン", "おこ", "ぷりぷり"],
+            ),
+            (
+                StyleCategory::Bright,
+                &[
+                    "喜",
+                    "あまあま",
+                    "楽",
+                    "うきうき",
+                    "わーい",
+                    "上機嫌",
+                    "セクシー",
+                ],
+            ),
+            (StyleCategory::Normal, &["ノーマル", "ふつう", "普通"]),
+        ];
+        KEYWORDS.iter().find_map(|(category, keywords)| {
+            keywords
+                .iter()
+                .any(|keyword| style_name.contains(keyword))
+                .then_some(*category)
+        })
+    }
+}
+
+/// One installed style, as the similarity ranking sees it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleEntry {
+    pub speaker_name: String,
+    pub style_name: String,
+    pub style_id: u32,
+    /// VOICEVOX style type such as `talk` or `sing`; `None` is treated as `talk`.
+    pub style_type: Option<String>,
+}
+
+impl StyleEntry {
+    fn kind(&self) -> &str {
+        self.style_type.as_deref().unwrap_or("talk")
+    }
+}
+
+/// Why a style was suggested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimilarityReason {
+    SameSpeaker,
+    SameCategory(StyleCategory),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion<'a> {
+    pub entry: &'a StyleEntry,
+    pub reason: SimilarityReason,
+}
+
+/// Ranks up to `count` alternatives to the style `style_id`.
+///
+/// Only styles of the same type (talk or sing) are considered. The same speaker's
+/// other styles come first, those sharing the style's category leading; then other
+/// speakers' styles in the same category. Ties keep catalog order.
+///
+/// # Errors
+///
+/// Returns an error if `style_id` is not an installed style.
+pub fn suggest_similar(
+    entries: &[StyleEntry],
+    style_id: u32,
+    count: usize,
+) -> Result<Vec<Suggestion<'_>>> {
+    let target = entries
+        .iter()
+        .find(|entry| entry.style_id == style_id)
+        .ok_or_else(|| anyhow!("Style ID {style_id} is not installed"))?;
+    let category = StyleCategory::classify(&target.style_name);
+
+    let mut ranked = entries
+        .iter()
+        .filter(|entry| entry.style_id != style_id && entry.kind() == target.kind())
+        .filter_map(|entry| {
+            let same_speaker = entry.speaker_name == target.speaker_name;
+            let same_category =
+                category.is_some() && StyleCategory::classify(&entry.style_name) == category;
+            let (rank, reason) = match (same_speaker, category) {
+                (true, _) => (u8::from(!same_category), SimilarityReason::SameSpeaker),
+                (false, Some(category)) if same_category => {
+                    (2, SimilarityReason::SameCategory(category))
+                }
+                (false, _) => return None,
+            };
+            Some((rank, Suggestion { entry, reason }))
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by_key(|(rank, _)| *rank);
+    Ok(ranked
+        .into_iter()
+        .take(count)
+        .map(|(_, suggestion)| suggestion)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(speaker: &str, style: &str, id: u32, style_type: Option<&str>) -> StyleEntry {
+        StyleEntry {
+            speaker_name: speaker.to_owned(),
+            style_name: style.to_owned(),
+            style_id: id,
+            style_type: style_type.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn prefers_same_speaker_then_same_category() {
+        let entries = vec![
+            entry("四国めたん", "ささやき", 36, None),
+            entry("ずんだもん", "ノーマル", 3, Some("talk")),
+            entry("ずんだもん", "あまあま", 1, None),
+            entry("ずんだもん", "ささやき", 22, None),
+            entry("ずんだもん", "ヒソヒソ", 38, None),
+            entry("ずんだもん", "ハミング", 3003, Some("sing")),
+            entry("九州そら", "ささやき", 19, None),
+        ];
+
+        let ids = |suggestions: Vec<Suggestion<'_>>| {
+            suggestions
+                .iter()
+                .map(|suggestion| suggestion.entry.style_id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ids(suggest_similar(&entries, 22, 10).expect("installed")),
+            vec![38, 3, 1, 36, 19]
+        );
+        let top = suggest_similar(&entries, 36, 1).expect("installed");
+        assert_eq!(
+            top[0].reason,
+            SimilarityReason::SameCategory(StyleCategory::Whisper)
+        );
+        assert_eq!(top[0].entry.style_id, 22);
+        assert!(suggest_similar(&entries, 99, 3).is_err());
+    }
+}
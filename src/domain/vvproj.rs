@@ -0,0 +1,128 @@
+use serde_json::{Map, Value, json};
+
+/// Engine ID the official editor assigns to the bundled VOICEVOX engine.
+pub const VOICEVOX_ENGINE_ID: &str = "074fc39e-678b-4c13-8916-ffca8d505d1d";
+
+/// Editor version whose project layout is written; newer editors migrate it on open.
+pub const VVPROJ_APP_VERSION: &str = "0.14.0";
+
+/// One talk line for an editor project.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VvprojLine {
+    pub text: String,
+    pub speaker_uuid: String,
+    pub style_id: u32,
+    /// The line's `AudioQuery` in VOICEVOX engine JSON.
+    pub query: Value,
+}
+
+/// Builds a `.vvproj` document with one audio item per line, in order.
+///
+/// Engine queries mix `snake_case` and `camelCase` keys; the editor stores them all
+/// in `camelCase`, so keys are converted.
+#[must_use]
+pub fn build_vvproj(lines: Vec<VvprojLine>) -> Value {
+    let mut audio_keys = Vec::with_capacity(lines.len());
+    let mut audio_items = Map::new();
+    for (index, line) in lines.into_iter().enumerate() {
+        let key = audio_key(index);
+        audio_items.insert(
+            key.clone(),
+            json!({
+                "text": line.text,
+                "voice": {
+                    "engineId": VOICEVOX_ENGINE_ID,
+                    "speakerId": line.speaker_uuid,
+                    "styleId": line.style_id,
+                },
+                "query": camel_case_keys(line.query),
+            }),
+        );
+        audio_keys.push(Value::String(key));
+    }
+    json!({
+        "appVersion": VVPROJ_APP_VERSION,
+        "audioKeys": audio_keys,
+        "audioItems": audio_items,
+    })
+}
+
+/// Deterministic UUID-shaped key, so exports of the same text diff cleanly.
+fn audio_key(index: usize) -> String {
+    format!("00000000-0000-4000-8000-{index:012x}")
+}
+
+fn camel_case_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (snake_to_camel(&key), camel_case_keys(value)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(camel_case_keys).collect()),
+        other => other,
+    }
+}
+
+fn snake_to_camel(key: &str) -> String {
+    let mut camel = String::with_capacity(key.len());
+    let mut upper_next = false;
+    for ch in key.chars() {
+        if ch == '_' {
+            upper_next = true;
+        } else if upper_next {
+            camel.push(ch.to_ascii_uppercase());
+            upper_next = false;
+        } else {
+            camel.push(ch);
+        }
+    }
+    camel
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_items_follow_line_order_with_editor_keys() {
+        let query = json!({
+            "accent_phrases": [{
+                "moras": [{"text": "コ", "consonant_length": 0.05, "vowel_length": 0.1, "pitch": 5.5}],
+                "accent": 1,
+                "pause_mora": null,
+                "is_interrogative": false
+            }],
+            "speedScale": 1.0,
+            "outputSamplingRate": 24000
+        });
+        let project = build_vvproj(vec![
+            VvprojLine {
+                text: "こんにちは".to_owned(),
+                speaker_uuid: "388f246b-8c41-4ac1-8e2d-5d79f3ff56d9".to_owned(),
+                style_id: 3,
+                query: query.clone(),
+            },
+            VvprojLine {
+                text: "さようなら".to_owned(),
+                speaker_uuid: "388f246b-8c41-4ac1-8e2d-5d79f3ff56d9".to_owned(),
+                style_id: 1,
+                query,
+            },
+        ]);
+
+        let keys = project["audioKeys"].as_array().expect("keys");
+        assert_eq!(keys.len(), 2);
+        let first = &project["audioItems"][keys[0].as_str().expect("key")];
+        assert_eq!(first["text"], "こんにちは");
+        assert_eq!(first["voice"]["styleId"], 3);
+        let phrase = &first["query"]["accentPhrases"][0];
+        assert_eq!(phrase["moras"][0]["vowelLength"], 0.1);
+        assert_eq!(phrase["isInterrogative"], false);
+        assert_eq!(first["query"]["speedScale"], 1.0);
+        assert_eq!(
+            project["audioItems"][keys[1].as_str().expect("key")]["text"],
+            "さようなら"
+        );
+    }
+}
@@ -1,15 +1,24 @@
 use anyhow::{Result, anyhow};
+use serde::Deserialize;
 use std::path::Path;
 use voicevox_core::{
     AccelerationMode, OnExistingVoiceModelId, StyleId,
     blocking::{OpenJtalk, Synthesizer},
 };
 
-use crate::infrastructure::ipc::{
-    DEFAULT_SYNTHESIS_RATE, MAX_SYNTHESIS_RATE, MIN_SYNTHESIS_RATE, is_valid_synthesis_rate,
-};
+/// The editable intermediate representation between text analysis and synthesis.
+///
+/// Accent phrases hold per-mora `pitch`, `vowel_length`, and `consonant_length`;
+/// the query's scales and phoneme padding apply to the whole utterance.
+pub use voicevox_core::{AccentPhrase, AudioQuery, Mora};
+
+use crate::infrastructure::ipc::{MoraTiming, SynthesisOptions};
 use crate::infrastructure::onnxruntime;
 use crate::infrastructure::openjtalk;
+use crate::infrastructure::paths::user_dict_path;
+use crate::infrastructure::user_dict::{
+    load_dictionary_words, use_dictionary_words, user_dict_generation,
+};
 use crate::infrastructure::voicevox::{
     Speaker, open_voice_model_file, open_voice_model_file_by_id,
 };
@@ -30,6 +39,22 @@ pub trait CoreSynthesis {
     /// Returns an implementation-specific error if synthesis fails.
     fn synthesize<'a>(&'a self, text: &str, style_id: u32)
     -> Result<Self::Output<'a>, Self::Error>;
+    /// Analyzes text into an editable query with predicted phoneme lengths and pitches.
+    ///
+    /// # Errors
+    ///
+    /// Returns an implementation-specific error if text analysis fails.
+    fn create_audio_query(&self, text: &str, style_id: u32) -> Result<AudioQuery, Self::Error>;
+    /// Synthesizes audio from a query, typically one edited after [`Self::create_audio_query`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an implementation-specific error if synthesis fails.
+    fn synthesize_from_query<'a>(
+        &'a self,
+        query: &AudioQuery,
+        style_id: u32,
+    ) -> Result<Self::Output<'a>, Self::Error>;
     /// Returns speaker metadata currently visible to the core instance.
     ///
     /// # Errors
@@ -38,8 +63,48 @@ pub trait CoreSynthesis {
     fn get_speakers(&self) -> Result<Self::SpeakerData<'_>, Self::Error>;
 }
 
+/// ONNX Runtime execution provider used for inference.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionProvider {
+    #[default]
+    Cpu,
+    /// CUDA or DirectML, whichever the loaded ONNX Runtime build provides.
+    Gpu,
+    /// GPU when one is usable, otherwise CPU.
+    Auto,
+}
+
+impl From<ExecutionProvider> for AccelerationMode {
+    fn from(provider: ExecutionProvider) -> Self {
+        match provider {
+            ExecutionProvider::Cpu => Self::Cpu,
+            ExecutionProvider::Gpu => Self::Gpu,
+            ExecutionProvider::Auto => Self::Auto,
+        }
+    }
+}
+
+/// Session tuning for the synthesizer's ONNX Runtime inference.
+///
+/// These are the knobs VOICEVOX Core passes through: inter-op threading and the
+/// memory arena always keep ONNX Runtime's defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InferenceOptions {
+    #[serde(default)]
+    pub execution_provider: ExecutionProvider,
+    /// Intra-op threads per session; 0 lets VOICEVOX Core pick from the CPU count.
+    #[serde(default)]
+    pub cpu_threads: u16,
+}
+
 pub struct VoicevoxCore {
     synthesizer: Synthesizer<OpenJtalk>,
+    /// Shares its dictionary with the synthesizer's text analyzer.
+    open_jtalk: OpenJtalk,
+    /// [`user_dict_generation`] when the saved user dictionary was last applied.
+    user_dict_generation: u64,
 }
 
 impl VoicevoxCore {
@@ -50,47 +115,120 @@ impl VoicevoxCore {
     /// Returns an error if runtime libraries, dictionary resources, or the synthesizer
     /// builder cannot be initialized.
     pub fn new() -> Result<Self> {
+        Self::with_inference(InferenceOptions::default())
+    }
+
+    /// Creates a `VoicevoxCore` instance with the given inference tuning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if runtime libraries, dictionary resources, or the synthesizer
+    /// builder cannot be initialized, including when a GPU provider is unavailable.
+    pub fn with_inference(inference: InferenceOptions) -> Result<Self> {
         let onnxruntime = onnxruntime::initialize()?;
         let open_jtalk = openjtalk::initialize()?;
+        let user_dict_generation = user_dict_generation();
+        apply_saved_user_dict(&open_jtalk, false);
 
         let synthesizer = Synthesizer::builder(onnxruntime)
-            .text_analyzer(open_jtalk)
-            .acceleration_mode(AccelerationMode::Cpu)
-            .cpu_num_threads(0)
+            .text_analyzer(open_jtalk.clone())
+            .acceleration_mode(inference.execution_provider.into())
+            .cpu_num_threads(inference.cpu_threads)
             .build()
             .map_err(|e| anyhow!("Failed to create synthesizer: {e}"))?;
 
-        Ok(Self { synthesizer })
+        Ok(Self {
+            synthesizer,
+            open_jtalk,
+            user_dict_generation,
+        })
+    }
+
+    /// Re-reads the saved user dictionary if it changed since this core applied it.
+    pub fn refresh_user_dict(&mut self) {
+        let generation = user_dict_generation();
+        if generation != self.user_dict_generation {
+            apply_saved_user_dict(&self.open_jtalk, true);
+            self.user_dict_generation = generation;
+        }
     }
 
-    /// Synthesizes speech while applying a speech-rate multiplier via `AudioQuery`.
+    /// Synthesizes speech with the given options applied via `AudioQuery`.
     ///
     /// # Errors
     ///
-    /// Returns an error if text is empty, rate is outside the supported range, or
+    /// Returns an error if text is empty, an option is outside the supported range, or
     /// query generation/synthesis fails.
-    pub fn synthesize_with_rate(&self, text: &str, style_id: u32, rate: f32) -> Result<Vec<u8>> {
+    pub fn synthesize_with_options(
+        &self,
+        text: &str,
+        style_id: u32,
+        options: SynthesisOptions,
+    ) -> Result<Vec<u8>> {
+        let query = self.create_audio_query_with_options(text, style_id, options)?;
+        self.synthesize_query(&query, style_id)
+    }
+
+    pub(crate) fn synthesize_query(&self, query: &AudioQuery, style_id: u32) -> Result<Vec<u8>> {
+        self.synthesizer
+            .synthesis(query, StyleId::new(style_id))
+            .perform()
+            .map_err(|e| anyhow!("Speech synthesis failed: {e}"))
+    }
+
+    /// Builds the `AudioQuery` for `text` with every synthesis option applied.
+    ///
+    /// The style's model must be loaded, since phoneme lengths and pitches are predicted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if text is empty, an option is outside the supported range, or
+    /// query generation fails.
+    pub fn create_audio_query_with_options(
+        &self,
+        text: &str,
+        style_id: u32,
+        options: SynthesisOptions,
+    ) -> Result<AudioQuery> {
         if text.trim().is_empty() {
             return Err(anyhow!("Empty text provided for synthesis"));
         }
 
-        if !is_valid_synthesis_rate(rate) {
-            return Err(anyhow!(
-                "Rate must be between {MIN_SYNTHESIS_RATE:.1} and {MAX_SYNTHESIS_RATE:.1}, got: {rate}"
-            ));
-        }
+        options.validate()?;
 
-        let style_id = StyleId::new(style_id);
         let mut query = self
             .synthesizer
-            .create_audio_query(text, style_id)
+            .create_audio_query(text, StyleId::new(style_id))
             .map_err(|e| anyhow!("Failed to create audio query: {e}"))?;
-        query.speed_scale = rate;
+        query.speed_scale = options.rate;
+        query.pitch_scale = options.pitch;
+        query.intonation_scale = options.intonation;
+        query.volume_scale = options.volume;
+        query.pre_phoneme_length = options.pre_phoneme_length;
+        query.post_phoneme_length = options.post_phoneme_length;
+        if let Some(sample_rate) = options.output_format.sample_rate {
+            query.output_sampling_rate = sample_rate;
+        }
+        query.output_stereo = options.output_format.stereo;
+        Ok(query)
+    }
+}
 
-        self.synthesizer
-            .synthesis(&query, style_id)
-            .perform()
-            .map_err(|e| anyhow!("Speech synthesis failed: {e}"))
+/// Applies the saved user dictionary, logging instead of failing so a bad word
+/// never stops synthesis; an empty dictionary is only applied to clear old words.
+fn apply_saved_user_dict(open_jtalk: &OpenJtalk, replace: bool) {
+    let path = user_dict_path();
+    let applied = load_dictionary_words(&path).and_then(|words| {
+        if words.is_empty() && !replace {
+            return Ok(());
+        }
+        use_dictionary_words(open_jtalk, &words)
+    });
+    if let Err(error) = applied {
+        crate::infrastructure::logging::warn(&format!(
+            "Ignoring user dictionary {}: {error:#}",
+            path.display()
+        ));
     }
 }
 
@@ -110,7 +248,21 @@ impl CoreSynthesis for VoicevoxCore {
         text: &str,
         style_id: u32,
     ) -> Result<Self::Output<'a>, Self::Error> {
-        self.synthesize_with_rate(text, style_id, DEFAULT_SYNTHESIS_RATE)
+        self.synthesize_with_options(text, style_id, SynthesisOptions::default())
+            .map_err(|e| anyhow!("Speech synthesis failed for style_id {style_id}: {e}"))
+    }
+
+    fn create_audio_query(&self, text: &str, style_id: u32) -> Result<AudioQuery, Self::Error> {
+        self.create_audio_query_with_options(text, style_id, SynthesisOptions::default())
+            .map_err(|e| anyhow!("Text analysis failed for style_id {style_id}: {e}"))
+    }
+
+    fn synthesize_from_query<'a>(
+        &'a self,
+        query: &AudioQuery,
+        style_id: u32,
+    ) -> Result<Self::Output<'a>, Self::Error> {
+        self.synthesize_query(query, style_id)
             .map_err(|e| anyhow!("Speech synthesis failed for style_id {style_id}: {e}"))
     }
 
@@ -152,3 +304,104 @@ impl VoicevoxCore {
         Ok(())
     }
 }
+
+/// Where each mora of `query` falls in the audio synthesized from it.
+///
+/// Core renders every phoneme for its predicted length divided by the speed scale,
+/// after `pre_phoneme_length` of silence.
+#[must_use]
+pub fn mora_timings(query: &AudioQuery) -> Vec<MoraTiming> {
+    let speed = if query.speed_scale > 0.0 {
+        query.speed_scale
+    } else {
+        1.0
+    };
+    let mut elapsed = query.pre_phoneme_length / speed;
+    let ms = |seconds: f32| (seconds * 1000.0).round() as u32;
+    let mut timings = Vec::new();
+    for phrase in &query.accent_phrases {
+        for mora in phrase.moras.iter().chain(&phrase.pause_mora) {
+            let start = elapsed;
+            elapsed += mora.consonant_length.unwrap_or(0.0).max(0.0) / speed;
+            let vowel_start = elapsed;
+            elapsed += mora.vowel_length.max(0.0) / speed;
+            timings.push(MoraTiming {
+                text: mora.text.clone(),
+                consonant: mora.consonant.clone(),
+                vowel: mora.vowel.clone(),
+                start_ms: ms(start),
+                vowel_start_ms: ms(vowel_start),
+                end_ms: ms(elapsed),
+            });
+        }
+    }
+    timings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mora(text: &str, consonant: Option<(&str, f32)>, vowel: &str, length: f32) -> Mora {
+        Mora {
+            text: text.to_string(),
+            consonant: consonant.map(|(phoneme, _)| phoneme.to_string()),
+            consonant_length: consonant.map(|(_, length)| length),
+            vowel: vowel.to_string(),
+            vowel_length: length,
+            pitch: 5.5,
+        }
+    }
+
+    #[test]
+    fn mora_timings_follow_phoneme_lengths_and_speed() {
+        let query = AudioQuery {
+            accent_phrases: vec![
+                AccentPhrase {
+                    moras: vec![mora("コ", Some(("k", 0.05)), "o", 0.1)],
+                    accent: 1,
+                    pause_mora: Some(mora("、", None, "pau", 0.3)),
+                    is_interrogative: false,
+                },
+                AccentPhrase {
+                    moras: vec![mora("ア", None, "a", 0.2)],
+                    accent: 1,
+                    pause_mora: None,
+                    is_interrogative: false,
+                },
+            ],
+            speed_scale: 2.0,
+            pitch_scale: 0.0,
+            intonation_scale: 1.0,
+            volume_scale: 1.0,
+            pre_phoneme_length: 0.2,
+            post_phoneme_length: 0.1,
+            output_sampling_rate: 24000,
+            output_stereo: false,
+            kana: None,
+        };
+
+        let timings = mora_timings(&query);
+
+        let spans: Vec<_> = timings
+            .iter()
+            .map(|timing| {
+                (
+                    timing.text.as_str(),
+                    timing.start_ms,
+                    timing.vowel_start_ms,
+                    timing.end_ms,
+                )
+            })
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                ("コ", 100, 125, 175),
+                ("、", 175, 175, 325),
+                ("ア", 325, 325, 425)
+            ]
+        );
+        assert_eq!(timings[1].vowel, "pau");
+    }
+}
@@ -0,0 +1,44 @@
+//! Pinning synthesis threads to CPUs for batch synthesis on many-core machines.
+
+use anyhow::Result;
+
+/// Restricts the calling thread, and threads it spawns afterwards, to `cpus`.
+///
+/// ONNX Runtime starts its intra-op thread pool when a session is created, so
+/// pinning the thread that creates the core keeps the whole synthesis on `cpus`.
+///
+/// # Errors
+///
+/// Returns an error if a CPU index is out of range, the kernel rejects the set,
+/// or the platform has no thread affinity API.
+#[cfg(target_os = "linux")]
+pub fn pin_current_thread(cpus: &[usize]) -> Result<()> {
+    use anyhow::{Context, ensure};
+
+    let max_cpus = usize::try_from(libc::CPU_SETSIZE).unwrap_or(usize::MAX);
+    // SAFETY: `cpu_set_t` is a plain bit mask for which all zeroes is the empty set.
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for &cpu in cpus {
+        ensure!(
+            cpu < max_cpus,
+            "CPU {cpu} is out of range (limit {max_cpus})"
+        );
+        // SAFETY: `cpu` was checked against the set's capacity above.
+        unsafe { libc::CPU_SET(cpu, &mut set) };
+    }
+
+    // SAFETY: pid 0 names the calling thread and `set` lives for the call.
+    let status =
+        unsafe { libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) };
+    if status == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+            .with_context(|| format!("Failed to pin thread to CPUs {cpus:?}"))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_current_thread(cpus: &[usize]) -> Result<()> {
+    anyhow::bail!("CPU pinning is only supported on Linux (requested CPUs {cpus:?})")
+}
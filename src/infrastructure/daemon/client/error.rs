@@ -29,6 +29,18 @@ impl DaemonClientError {
     }
 }
 
+/// The daemon declined to speak a request that repeated a recent one.
+#[derive(Debug, thiserror::Error)]
+#[error("Skipped: the same text was synthesized moments ago")]
+pub struct DuplicateSkipped;
+
+#[must_use]
+pub fn is_duplicate_skipped(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .any(|cause| cause.downcast_ref::<DuplicateSkipped>().is_some())
+}
+
 pub fn daemon_response_error(context: &str, code: DaemonErrorCode, message: &str) -> anyhow::Error {
     anyhow!(DaemonClientError::new(context, code, message))
 }
@@ -34,6 +34,16 @@ fn validate_startup_preconditions() -> Result<()> {
 }
 
 async fn start_daemon_automatically(socket_path: &Path) -> Result<()> {
+    // A shared daemon belongs to whoever started it with `--shared-socket`; starting
+    // a private one at its path would lock the rest of the group out.
+    if crate::config::use_shared_socket() {
+        return Err(anyhow!(
+            "Shared VOICEVOX daemon is not running at {}. Ask its owner to start \
+             'voicevox-daemon --shared-socket --group <group>', or unset {}.",
+            socket_path.display(),
+            crate::config::ENV_VOICEVOX_SHARED_SOCKET
+        ));
+    }
     crate::infrastructure::logging::info(
         "Starting VOICEVOX daemon (first startup may take a few seconds)...",
     );
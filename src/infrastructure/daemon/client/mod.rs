@@ -5,17 +5,23 @@ mod transport;
 
 use anyhow::{Result, anyhow};
 use std::collections::HashMap;
-use std::path::Path;
-use tokio::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::Duration;
 
+use crate::domain::voice_alias::VoiceAliases;
 use crate::infrastructure::ipc::{
-    IpcModel, IpcSpeaker, IpcStyle, OwnedRequest, OwnedResponse, OwnedSynthesizeOptions,
+    DaemonEvent, DaemonStats, DaemonWarning, IPC_PROTOCOL_VERSION, IpcModel, IpcSpeaker, IpcStyle,
+    MoraTiming, OwnedRequest, OwnedResponse, SlowRequest, SynthesisOptions, SynthesisTimings,
 };
 use crate::infrastructure::paths::get_socket_path;
 use crate::infrastructure::voicevox::{AvailableModel, Speaker, Style};
 
 pub use crate::infrastructure::daemon::find_daemon_binary;
-pub use error::{DaemonClientError, daemon_response_error, find_daemon_client_error};
+pub use error::{
+    DaemonClientError, DuplicateSkipped, daemon_response_error, find_daemon_client_error,
+    is_duplicate_skipped,
+};
 pub use policy::{DaemonAutoStartPolicy, DaemonConnectRetryPolicy};
 
 fn unexpected_daemon_response(operation: &str, expected: &str) -> anyhow::Error {
@@ -28,6 +34,7 @@ fn map_ipc_style(style: IpcStyle) -> Style {
         name: style.name.into(),
         id: style.id,
         style_type: style.style_type.map(Into::into),
+        loaded: style.loaded,
     }
 }
 
@@ -46,29 +53,132 @@ fn map_ipc_model(model: IpcModel) -> AvailableModel {
         model_id: model.model_id,
         file_path: model.file_path,
         speakers: model.speakers.into_iter().map(map_ipc_speaker).collect(),
+        loaded: model.loaded,
     }
 }
 
 pub struct DaemonClient {
-    stream: UnixStream,
+    stream: transport::DaemonStream,
+    endpoint: DaemonEndpoint,
+    /// Warnings attached to synthesis responses since the last `take_warnings`.
+    warnings: Vec<DaemonWarning>,
+}
+
+/// Where a [`DaemonClient`] is connected, so more connections can be opened there.
+#[derive(Debug, Clone)]
+enum DaemonEndpoint {
+    Socket(PathBuf),
+    Remote(String),
+}
+
+static REMOTE_DAEMON: RwLock<Option<String>> = RwLock::new(None);
+
+/// Makes every [`DaemonClient`] in this process connect to the daemon listening at
+/// `address` (`host:port`, see `voicevox-daemon --listen`) instead of a Unix socket;
+/// `None` restores the default.
+pub fn set_remote_daemon(address: Option<String>) {
+    *REMOTE_DAEMON
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = address;
+}
+
+/// The address set with [`set_remote_daemon`], else `$VOICEVOX_DAEMON_ADDR`.
+#[must_use]
+pub fn remote_daemon_address() -> Option<String> {
+    REMOTE_DAEMON
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone()
+        .or_else(|| {
+            std::env::var(crate::config::ENV_VOICEVOX_DAEMON_ADDR)
+                .ok()
+                .filter(|address| !address.is_empty())
+        })
+}
+
+/// Daemon activity snapshot returned by [`DaemonClient::status`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaemonStatus {
+    pub queue_length: u32,
+    pub current_style_id: Option<u32>,
+    pub recent_warnings: Vec<DaemonWarning>,
+}
+
+/// Push-mode connection returned by [`DaemonClient::subscribe`].
+pub struct DaemonEventStream {
+    frames: transport::ResponseFrames,
+}
+
+impl DaemonEventStream {
+    /// Waits for the next daemon event; `None` means the daemon went away.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream cannot be read or a frame is not an event.
+    pub async fn next_event(&mut self) -> Result<Option<DaemonEvent>> {
+        match transport::receive_pushed_response(&mut self.frames).await? {
+            Some(OwnedResponse::Event { event }) => Ok(Some(event)),
+            Some(_) => Err(unexpected_daemon_response("streaming events", "Event")),
+            None => Ok(None),
+        }
+    }
 }
 
 impl DaemonClient {
-    async fn from_stream(stream: UnixStream) -> Result<Self> {
-        Ok(Self { stream })
+    /// Wraps a connected stream after `Hello`, which checks that the daemon speaks this
+    /// build's protocol and identifies the caller when `VOICEVOX_APP_ID` is set.
+    async fn from_stream(
+        stream: transport::DaemonStream,
+        endpoint: DaemonEndpoint,
+    ) -> Result<Self> {
+        let mut client = Self {
+            stream,
+            endpoint,
+            warnings: Vec::new(),
+        };
+        let app_id = std::env::var(crate::config::ENV_VOICEVOX_APP_ID).unwrap_or_default();
+        client.hello(&app_id).await?;
+        Ok(client)
     }
 
     pub async fn new() -> Result<Self> {
         Self::new_at(&get_socket_path()).await
     }
 
+    /// Connects to the daemon at `socket_path`, or to the remote daemon when one is
+    /// set (see [`set_remote_daemon`]).
     pub async fn new_at(socket_path: &Path) -> Result<Self> {
+        if let Some(address) = remote_daemon_address() {
+            return Self::new_remote(&address).await;
+        }
         let stream = transport::connect_socket_with_timeout(
             socket_path,
             transport::DAEMON_CONNECTION_TIMEOUT,
         )
         .await?;
-        Self::from_stream(stream).await
+        Self::from_stream(
+            Box::new(stream),
+            DaemonEndpoint::Socket(socket_path.to_path_buf()),
+        )
+        .await
+    }
+
+    /// Connects to a daemon started with `--listen` at `address` (`host:port`),
+    /// authenticating with the daemon token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no token is available, the daemon cannot be reached, or
+    /// it rejects the token.
+    pub async fn new_remote(address: &str) -> Result<Self> {
+        let token = crate::infrastructure::daemon::token::client_daemon_token()?;
+        let stream = transport::connect_tcp_with_timeout(
+            address,
+            &token,
+            transport::DAEMON_CONNECTION_TIMEOUT,
+        )
+        .await?;
+        Self::from_stream(stream, DaemonEndpoint::Remote(address.to_owned())).await
     }
 
     pub async fn connect_with_retry() -> Result<Self> {
@@ -76,6 +186,9 @@ impl DaemonClient {
     }
 
     pub async fn connect_with_retry_at(socket_path: &Path) -> Result<Self> {
+        if let Some(address) = remote_daemon_address() {
+            return Self::new_remote(&address).await;
+        }
         let policy = DaemonConnectRetryPolicy::default();
         let stream = transport::connect_with_retry(
             socket_path,
@@ -83,16 +196,42 @@ impl DaemonClient {
             policy,
         )
         .await?;
-        Self::from_stream(stream).await
+        Self::from_stream(
+            Box::new(stream),
+            DaemonEndpoint::Socket(socket_path.to_path_buf()),
+        )
+        .await
+    }
+
+    /// Opens another connection to the daemon this client is connected to, so
+    /// requests can run alongside the ones on this connection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the daemon cannot be connected.
+    pub async fn connect_another(&self) -> Result<Self> {
+        match &self.endpoint {
+            DaemonEndpoint::Socket(socket_path) => Self::new_at(socket_path).await,
+            DaemonEndpoint::Remote(address) => Self::new_remote(address).await,
+        }
     }
 
     pub async fn new_with_auto_start() -> Result<Self> {
         Self::new_with_auto_start_at(&get_socket_path()).await
     }
 
+    /// Like [`DaemonClient::new_at`], starting the local daemon if it is not running.
+    /// A remote daemon is never started.
     pub async fn new_with_auto_start_at(socket_path: &Path) -> Result<Self> {
+        if let Some(address) = remote_daemon_address() {
+            return Self::new_remote(&address).await;
+        }
         let stream = launcher::connect_or_start(socket_path).await?;
-        Self::from_stream(stream).await
+        Self::from_stream(
+            Box::new(stream),
+            DaemonEndpoint::Socket(socket_path.to_path_buf()),
+        )
+        .await
     }
 
     async fn send_request_and_receive_response(
@@ -102,11 +241,23 @@ impl DaemonClient {
         transport::send_request_and_receive_response(&mut self.stream, &request).await
     }
 
+    /// Asks the daemon to abandon the request this connection is waiting on.
+    ///
+    /// The acknowledgement is not awaited, since the interrupted call may have left
+    /// part of a response unread; the connection is closed instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cancel frame cannot be written.
+    pub async fn cancel(mut self) -> Result<()> {
+        transport::send_request(&mut self.stream, &OwnedRequest::Cancel).await
+    }
+
     pub async fn synthesize(
         &mut self,
         text: &str,
         style_id: u32,
-        options: OwnedSynthesizeOptions,
+        options: SynthesisOptions,
     ) -> Result<Vec<u8>> {
         let request = OwnedRequest::Synthesize {
             text: text.to_string(),
@@ -115,17 +266,412 @@ impl DaemonClient {
         };
 
         match self.send_request_and_receive_response(request).await? {
-            OwnedResponse::SynthesizeResult { wav_data } => Ok(wav_data),
+            OwnedResponse::SynthesizeResult { wav_data, warnings } => {
+                self.warnings.extend(warnings);
+                Ok(wav_data)
+            }
+            OwnedResponse::Skipped => Err(DuplicateSkipped.into()),
             OwnedResponse::Error { code, message } => {
                 Err(daemon_response_error("Synthesis error", code, &message))
             }
             _ => Err(unexpected_daemon_response(
                 "handling synthesize request",
-                "SynthesizeResult or Error",
+                "SynthesizeResult, Skipped, or Error",
+            )),
+        }
+    }
+
+    /// Synthesizes like [`Self::synthesize`], also returning how long the daemon
+    /// spent on each stage.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the daemon skips it as a repeat, or
+    /// the daemon returns an unexpected response.
+    pub async fn synthesize_timed(
+        &mut self,
+        text: &str,
+        style_id: u32,
+        options: SynthesisOptions,
+    ) -> Result<(Vec<u8>, SynthesisTimings)> {
+        let request = OwnedRequest::SynthesizeTimed {
+            text: text.to_string(),
+            style_id,
+            options,
+        };
+
+        match self.send_request_and_receive_response(request).await? {
+            OwnedResponse::SynthesizeTimedResult {
+                wav_data,
+                warnings,
+                timings,
+            } => {
+                self.warnings.extend(warnings);
+                Ok((wav_data, timings))
+            }
+            OwnedResponse::Skipped => Err(DuplicateSkipped.into()),
+            OwnedResponse::Error { code, message } => {
+                Err(daemon_response_error("Synthesis error", code, &message))
+            }
+            _ => Err(unexpected_daemon_response(
+                "handling timed synthesize request",
+                "SynthesizeTimedResult, Skipped, or Error",
             )),
         }
     }
 
+    /// Synthesizes like [`Self::synthesize_timed`], also returning where each mora
+    /// falls in the audio.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the daemon skips it as a repeat, or
+    /// the daemon returns an unexpected response.
+    pub async fn synthesize_with_moras(
+        &mut self,
+        text: &str,
+        style_id: u32,
+        options: SynthesisOptions,
+    ) -> Result<(Vec<u8>, SynthesisTimings, Vec<MoraTiming>)> {
+        let request = OwnedRequest::SynthesizeWithMoras {
+            text: text.to_string(),
+            style_id,
+            options,
+        };
+
+        match self.send_request_and_receive_response(request).await? {
+            OwnedResponse::SynthesizeWithMorasResult {
+                wav_data,
+                warnings,
+                timings,
+                moras,
+            } => {
+                self.warnings.extend(warnings);
+                Ok((wav_data, timings, moras))
+            }
+            OwnedResponse::Skipped => Err(DuplicateSkipped.into()),
+            OwnedResponse::Error { code, message } => {
+                Err(daemon_response_error("Synthesis error", code, &message))
+            }
+            _ => Err(unexpected_daemon_response(
+                "handling mora synthesize request",
+                "SynthesizeWithMorasResult, Skipped, or Error",
+            )),
+        }
+    }
+
+    /// Fetches the `AudioQuery` the daemon would synthesize `text` from, as engine JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the daemon returns an unexpected response.
+    pub async fn audio_query(
+        &mut self,
+        text: &str,
+        style_id: u32,
+        options: SynthesisOptions,
+    ) -> Result<String> {
+        let request = OwnedRequest::AudioQuery {
+            text: text.to_string(),
+            style_id,
+            options,
+        };
+        match self.send_request_and_receive_response(request).await? {
+            OwnedResponse::AudioQuery { query_json } => Ok(query_json),
+            OwnedResponse::Error { code, message } => {
+                Err(daemon_response_error("Audio query error", code, &message))
+            }
+            _ => Err(unexpected_daemon_response(
+                "building audio query",
+                "AudioQuery or Error",
+            )),
+        }
+    }
+
+    /// Asks the daemon to warm up the style's model so the next request starts fast.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the style is unknown, the warmup synthesis fails, or the
+    /// daemon returns an unexpected response.
+    pub async fn prewarm(&mut self, style_id: u32) -> Result<Duration> {
+        match self
+            .send_request_and_receive_response(OwnedRequest::Prewarm { style_id })
+            .await?
+        {
+            OwnedResponse::Prewarmed { elapsed_ms } => Ok(Duration::from_millis(elapsed_ms)),
+            OwnedResponse::Error { code, message } => {
+                Err(daemon_response_error("Prewarm error", code, &message))
+            }
+            _ => Err(unexpected_daemon_response(
+                "prewarming model",
+                "Prewarmed or Error",
+            )),
+        }
+    }
+
+    /// Asks the daemon to load the models into its cache, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a model is unknown, more models are given than the cache
+    /// holds, a model fails to load, or the daemon returns an unexpected response.
+    pub async fn preload_models(&mut self, model_ids: &[u32]) -> Result<Duration> {
+        match self
+            .send_request_and_receive_response(OwnedRequest::PreloadModels {
+                model_ids: model_ids.to_vec(),
+            })
+            .await?
+        {
+            OwnedResponse::ModelsPreloaded { elapsed_ms, .. } => {
+                Ok(Duration::from_millis(elapsed_ms))
+            }
+            OwnedResponse::Error { code, message } => {
+                Err(daemon_response_error("Preload error", code, &message))
+            }
+            _ => Err(unexpected_daemon_response(
+                "preloading models",
+                "ModelsPreloaded or Error",
+            )),
+        }
+    }
+
+    /// Returns and clears the warnings the daemon attached to earlier synthesis responses.
+    pub fn take_warnings(&mut self) -> Vec<DaemonWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Fetches queue activity and the daemon's recent warnings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the daemon returns an unexpected response.
+    pub async fn status(&mut self) -> Result<DaemonStatus> {
+        match self
+            .send_request_and_receive_response(OwnedRequest::Status)
+            .await?
+        {
+            OwnedResponse::Status {
+                queue_length,
+                current_style_id,
+                recent_warnings,
+            } => Ok(DaemonStatus {
+                queue_length,
+                current_style_id,
+                recent_warnings,
+            }),
+            OwnedResponse::Error { code, message } => {
+                Err(daemon_response_error("Status error", code, &message))
+            }
+            _ => Err(unexpected_daemon_response(
+                "reading status",
+                "Status or Error",
+            )),
+        }
+    }
+
+    /// Fetches the user's voice aliases from the daemon.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the daemon returns an unexpected response.
+    pub async fn voice_mapping(&mut self) -> Result<VoiceAliases> {
+        match self
+            .send_request_and_receive_response(OwnedRequest::GetVoiceMapping)
+            .await?
+        {
+            OwnedResponse::VoiceMapping { aliases } => Ok(aliases),
+            OwnedResponse::Error { code, message } => {
+                Err(daemon_response_error("Voice mapping error", code, &message))
+            }
+            _ => Err(unexpected_daemon_response(
+                "reading voice aliases",
+                "VoiceMapping or Error",
+            )),
+        }
+    }
+
+    /// Fetches the counters the daemon has kept since it started.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the daemon returns an unexpected response.
+    pub async fn stats(&mut self) -> Result<DaemonStats> {
+        match self
+            .send_request_and_receive_response(OwnedRequest::GetStats)
+            .await?
+        {
+            OwnedResponse::Stats { stats } => Ok(stats),
+            OwnedResponse::Error { code, message } => {
+                Err(daemon_response_error("Stats error", code, &message))
+            }
+            _ => Err(unexpected_daemon_response(
+                "reading stats",
+                "Stats or Error",
+            )),
+        }
+    }
+
+    /// Returns the slow log threshold, `None` while the log is off, and the recent
+    /// requests that exceeded it, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the daemon cannot report its slow log.
+    pub async fn slow_log(&mut self) -> Result<(Option<u64>, Vec<SlowRequest>)> {
+        match self
+            .send_request_and_receive_response(OwnedRequest::GetSlowLog)
+            .await?
+        {
+            OwnedResponse::SlowLog {
+                threshold_ms,
+                entries,
+            } => Ok((threshold_ms, entries)),
+            OwnedResponse::Error { code, message } => {
+                Err(daemon_response_error("Slow log error", code, &message))
+            }
+            _ => Err(unexpected_daemon_response(
+                "reading the slow log",
+                "SlowLog or Error",
+            )),
+        }
+    }
+
+    /// Identifies this connection as `app_id` (none when empty) so the daemon applies
+    /// that app's profile. Returns whether the daemon has a profile configured for it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the daemon speaks another protocol
+    /// revision, or the daemon returns an unexpected response.
+    pub async fn hello(&mut self, app_id: &str) -> Result<bool> {
+        let request = OwnedRequest::Hello {
+            protocol_version: IPC_PROTOCOL_VERSION,
+            app_id: app_id.to_string(),
+        };
+        match self.send_request_and_receive_response(request).await? {
+            OwnedResponse::Hello { profile_applied } => Ok(profile_applied),
+            OwnedResponse::Error { code, message } => {
+                Err(daemon_response_error("Hello error", code, &message))
+            }
+            _ => Err(unexpected_daemon_response(
+                "sending hello",
+                "Hello or Error",
+            )),
+        }
+    }
+
+    /// Returns whether the daemon's time-of-day policy currently allows streaming playback.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the daemon returns an unexpected response.
+    pub async fn streaming_allowed(&mut self) -> Result<bool> {
+        match self
+            .send_request_and_receive_response(OwnedRequest::GetPlaybackPolicy)
+            .await?
+        {
+            OwnedResponse::PlaybackPolicy { streaming, .. } => Ok(streaming),
+            OwnedResponse::Error { code, message } => Err(daemon_response_error(
+                "Playback policy error",
+                code,
+                &message,
+            )),
+            _ => Err(unexpected_daemon_response(
+                "reading playback policy",
+                "PlaybackPolicy or Error",
+            )),
+        }
+    }
+
+    /// Returns whether the daemon asks background work to wait, as a `--low-power`
+    /// daemon does while on battery.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the daemon returns an unexpected response.
+    pub async fn background_deferred(&mut self) -> Result<bool> {
+        match self
+            .send_request_and_receive_response(OwnedRequest::GetPowerPolicy)
+            .await?
+        {
+            OwnedResponse::PowerPolicy { defer_background } => Ok(defer_background),
+            OwnedResponse::Error { code, message } => {
+                Err(daemon_response_error("Power policy error", code, &message))
+            }
+            _ => Err(unexpected_daemon_response(
+                "reading power policy",
+                "PowerPolicy or Error",
+            )),
+        }
+    }
+
+    /// Asks the daemon to re-read the saved user dictionary; returns its word count.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the dictionary is invalid, or the daemon
+    /// returns an unexpected response.
+    pub async fn reload_user_dict(&mut self) -> Result<u32> {
+        match self
+            .send_request_and_receive_response(OwnedRequest::ReloadUserDict)
+            .await?
+        {
+            OwnedResponse::UserDictReloaded { word_count } => Ok(word_count),
+            OwnedResponse::Error { code, message } => Err(daemon_response_error(
+                "User dictionary error",
+                code,
+                &message,
+            )),
+            _ => Err(unexpected_daemon_response(
+                "reloading the user dictionary",
+                "UserDictReloaded or Error",
+            )),
+        }
+    }
+
+    /// Asks the daemon to shut down once its running requests finish.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the daemon returns an unexpected
+    /// response, as daemons from before `Shutdown` do.
+    pub async fn shutdown(&mut self) -> Result<()> {
+        match self
+            .send_request_and_receive_response(OwnedRequest::Shutdown)
+            .await?
+        {
+            OwnedResponse::ShuttingDown => Ok(()),
+            OwnedResponse::Error { code, message } => {
+                Err(daemon_response_error("Shutdown error", code, &message))
+            }
+            _ => Err(unexpected_daemon_response(
+                "requesting shutdown",
+                "ShuttingDown or Error",
+            )),
+        }
+    }
+
+    /// Asks the daemon to re-read `daemon.toml` and rescan its models; returns how
+    /// many models it found.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, either reload step fails, or the daemon
+    /// returns an unexpected response.
+    pub async fn reload(&mut self) -> Result<u32> {
+        match self
+            .send_request_and_receive_response(OwnedRequest::Reload)
+            .await?
+        {
+            OwnedResponse::Reloaded { model_count } => Ok(model_count),
+            OwnedResponse::Error { code, message } => {
+                Err(daemon_response_error("Reload error", code, &message))
+            }
+            _ => Err(unexpected_daemon_response("reloading", "Reloaded or Error")),
+        }
+    }
+
     pub async fn list_speakers(&mut self) -> Result<Vec<Speaker>> {
         match self
             .send_request_and_receive_response(OwnedRequest::ListSpeakers)
@@ -166,6 +712,16 @@ impl DaemonClient {
         }
     }
 
+    /// Turns this connection into an event subscription.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request cannot be sent or the daemon does not acknowledge it.
+    pub async fn subscribe(self) -> Result<DaemonEventStream> {
+        let frames = transport::open_subscription(self.stream).await?;
+        Ok(DaemonEventStream { frames })
+    }
+
     pub async fn list_models(&mut self) -> Result<Vec<AvailableModel>> {
         match self
             .send_request_and_receive_response(OwnedRequest::ListModels)
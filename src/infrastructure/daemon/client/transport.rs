@@ -3,18 +3,28 @@ use futures_util::{SinkExt, StreamExt};
 use std::os::unix::fs::{FileTypeExt, MetadataExt, PermissionsExt};
 use std::path::Path;
 use std::time::Duration;
-use tokio::net::UnixStream;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpStream, UnixStream};
 use tokio::time::timeout;
 use tokio_util::codec::{Framed, LengthDelimitedCodec};
 
+use super::error::daemon_response_error;
 use super::policy::DaemonConnectRetryPolicy;
 use crate::infrastructure::ipc::{
-    MAX_DAEMON_REQUEST_FRAME_BYTES, MAX_DAEMON_RESPONSE_FRAME_BYTES, OwnedRequest, OwnedResponse,
+    IPC_PROTOCOL_VERSION, MAX_DAEMON_REQUEST_FRAME_BYTES, MAX_DAEMON_RESPONSE_FRAME_BYTES,
+    OwnedRequest, OwnedResponse,
 };
 
 pub(crate) const DAEMON_CONNECTION_TIMEOUT: Duration = Duration::from_secs(2);
 pub(crate) const DAEMON_RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// A connection to the daemon, over its Unix socket or over TCP.
+pub(crate) trait DaemonIo: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> DaemonIo for T {}
+
+pub(crate) type DaemonStream = Box<dyn DaemonIo>;
+
 fn encode_request_frame(request: &OwnedRequest) -> Result<Vec<u8>> {
     postcard::to_allocvec(request).map_err(|e| anyhow!("Failed to serialize request: {e}"))
 }
@@ -28,10 +38,16 @@ fn current_uid() -> u32 {
     unsafe { libc::getuid() }
 }
 
-fn validate_socket_path(socket_path: &Path) -> Result<()> {
+/// Checks the socket before connecting and returns the UID the daemon must run as.
+///
+/// Normally the socket must be ours and not writable by group or others. With
+/// `VOICEVOX_SHARED_SOCKET`, another user's socket is accepted as long as others
+/// cannot write to it; the daemon must then be that socket's owner.
+fn validate_socket_path(socket_path: &Path) -> Result<u32> {
+    let uid = current_uid();
     let metadata = match std::fs::symlink_metadata(socket_path) {
         Ok(metadata) => metadata,
-        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(uid),
         Err(error) => {
             return Err(anyhow!(
                 "Failed to inspect daemon socket {}: {error}",
@@ -47,8 +63,8 @@ fn validate_socket_path(socket_path: &Path) -> Result<()> {
         ));
     }
 
-    let uid = current_uid();
-    if metadata.uid() != uid {
+    let shared = crate::config::use_shared_socket();
+    if metadata.uid() != uid && !shared {
         return Err(anyhow!(
             "Refusing to connect to daemon socket owned by another user: {}",
             socket_path.display()
@@ -56,7 +72,8 @@ fn validate_socket_path(socket_path: &Path) -> Result<()> {
     }
 
     let mode = metadata.permissions().mode() & 0o777;
-    if mode & 0o022 != 0 {
+    let forbidden = if shared { 0o002 } else { 0o022 };
+    if mode & forbidden != 0 {
         return Err(anyhow!(
             "Daemon socket permissions are too permissive (mode {:o}): {}",
             mode,
@@ -64,18 +81,17 @@ fn validate_socket_path(socket_path: &Path) -> Result<()> {
         ));
     }
 
-    Ok(())
+    Ok(metadata.uid())
 }
 
-fn verify_peer_credentials(stream: &UnixStream) -> Result<()> {
+fn verify_peer_credentials(stream: &UnixStream, expected_uid: u32) -> Result<()> {
     let cred = stream
         .peer_cred()
         .map_err(|error| anyhow!("Failed to read daemon peer credentials: {error}"))?;
     let peer_uid = cred.uid();
-    let uid = current_uid();
-    if peer_uid != uid {
+    if peer_uid != expected_uid {
         return Err(anyhow!(
-            "Refusing daemon connection from different uid (expected {uid}, got {peer_uid})"
+            "Refusing daemon connection from different uid (expected {expected_uid}, got {peer_uid})"
         ));
     }
     Ok(())
@@ -91,7 +107,7 @@ pub(crate) async fn connect_socket_with_timeout(
     socket_path: &Path,
     timeout_duration: Duration,
 ) -> Result<UnixStream> {
-    validate_socket_path(socket_path)?;
+    let daemon_uid = validate_socket_path(socket_path)?;
     let stream = timeout(timeout_duration, UnixStream::connect(socket_path))
         .await
         .map_err(|_| anyhow!("Timeout connecting to daemon"))?
@@ -101,10 +117,40 @@ pub(crate) async fn connect_socket_with_timeout(
                 socket_path.display()
             )
         })?;
-    verify_peer_credentials(&stream)?;
+    verify_peer_credentials(&stream, daemon_uid)?;
     Ok(stream)
 }
 
+/// Connects to a daemon started with `--listen` at `address` (`host:port`) and
+/// authenticates with `token`.
+pub(crate) async fn connect_tcp_with_timeout(
+    address: &str,
+    token: &str,
+    timeout_duration: Duration,
+) -> Result<DaemonStream> {
+    let stream = timeout(timeout_duration, TcpStream::connect(address))
+        .await
+        .map_err(|_| anyhow!("Timeout connecting to daemon at {address}"))?
+        .map_err(|e| anyhow!("Failed to connect to daemon at {address}: {e}"))?;
+    stream.set_nodelay(true)?;
+    let mut stream: DaemonStream = Box::new(stream);
+    let request = OwnedRequest::Authenticate {
+        protocol_version: IPC_PROTOCOL_VERSION,
+        token: token.to_owned(),
+    };
+    match send_request_and_receive_response(&mut stream, &request).await? {
+        OwnedResponse::Authenticated => Ok(stream),
+        OwnedResponse::Error { code, message } => Err(daemon_response_error(
+            &format!("Daemon at {address} refused the connection"),
+            code,
+            &message,
+        )),
+        _ => Err(anyhow!(
+            "Daemon at {address} returned an unexpected response while authenticating"
+        )),
+    }
+}
+
 pub(crate) async fn connect_with_retry(
     socket_path: &Path,
     timeout_duration: Duration,
@@ -129,7 +175,7 @@ pub(crate) async fn connect_with_retry(
 }
 
 pub(crate) async fn send_request_and_receive_response(
-    stream: &mut UnixStream,
+    stream: &mut DaemonStream,
     request: &OwnedRequest,
 ) -> Result<OwnedResponse> {
     let request_data = encode_request_frame(request)?;
@@ -141,3 +187,41 @@ pub(crate) async fn send_request_and_receive_response(
         .ok_or_else(|| anyhow!("No response from daemon"))??;
     decode_response_frame(&response_data)
 }
+
+/// Writes one request frame without waiting for a response.
+pub(crate) async fn send_request(stream: &mut DaemonStream, request: &OwnedRequest) -> Result<()> {
+    let request_data = encode_request_frame(request)?;
+    let mut framed = Framed::new(stream, daemon_response_codec());
+    framed.send(request_data.into()).await?;
+    Ok(())
+}
+
+pub(crate) type ResponseFrames = Framed<DaemonStream, LengthDelimitedCodec>;
+
+pub(crate) async fn open_subscription(stream: DaemonStream) -> Result<ResponseFrames> {
+    let mut framed = Framed::new(stream, daemon_response_codec());
+    framed
+        .send(encode_request_frame(&OwnedRequest::Subscribe)?.into())
+        .await?;
+    let ack = timeout(
+        DAEMON_RESPONSE_TIMEOUT,
+        receive_pushed_response(&mut framed),
+    )
+    .await
+    .map_err(|_| anyhow!("Daemon response timeout"))??;
+    match ack {
+        Some(OwnedResponse::Subscribed) => Ok(framed),
+        _ => Err(anyhow!("Daemon did not acknowledge the event subscription")),
+    }
+}
+
+/// Waits without a timeout for the next pushed frame; `None` means the daemon closed
+/// the connection.
+pub(crate) async fn receive_pushed_response(
+    framed: &mut ResponseFrames,
+) -> Result<Option<OwnedResponse>> {
+    match framed.next().await {
+        Some(frame) => decode_response_frame(&frame?).map(Some),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,862 @@
+use anyhow::{Context, Result, anyhow, ensure};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use crate::config::expand_home;
+use crate::domain::synthesis::postprocess::VolumeBoost;
+use crate::infrastructure::core::InferenceOptions;
+use crate::infrastructure::ipc::{
+    DEFAULT_SYNTHESIS_PITCH, DEFAULT_SYNTHESIS_RATE, SynthesisOptions, is_valid_synthesis_pitch,
+    is_valid_synthesis_rate,
+};
+use crate::infrastructure::local_time::LocalTime;
+use crate::infrastructure::logging::LogLevel;
+use crate::infrastructure::paths::get_config_dir;
+
+/// Daemon settings read from `daemon.toml` at startup.
+///
+/// A missing file means built-in defaults; a malformed one stops the daemon so a
+/// typo never silently disables a policy.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DaemonConfig {
+    /// Per-integration defaults keyed by the `app_id` clients send in `Hello`.
+    #[serde(default)]
+    pub apps: BTreeMap<String, AppProfile>,
+    /// Time-of-day rules; the first rule covering the current local time wins.
+    #[serde(default)]
+    pub policies: Vec<TimePolicy>,
+    /// Suppression of identical requests repeated within a short window.
+    pub dedup: Option<DedupConfig>,
+    /// External command that rewrites request text before synthesis.
+    pub preprocess: Option<PreprocessConfig>,
+    /// Gain and compression for whisper styles, which are otherwise barely audible.
+    pub whisper_boost: Option<WhisperBoostConfig>,
+    /// ONNX Runtime tuning for profiling inference across machines.
+    #[serde(default)]
+    pub inference: InferenceOptions,
+    /// Concurrent synthesizers and their CPU pinning, read once at startup.
+    #[serde(default)]
+    pub workers: WorkerConfig,
+    /// Voice models kept loaded between requests.
+    #[serde(default)]
+    pub model_cache: ModelCacheConfig,
+    /// Records per-voice counts and cache hit rates to a local stats file.
+    #[serde(default)]
+    pub usage_stats: bool,
+    /// Saves energy on laptops (`--low-power`); see [`DaemonConfig::apply_low_power`].
+    #[serde(default)]
+    pub low_power: bool,
+    /// Records syntheses slower than a threshold for `voicevox-daemon --slow-log`.
+    pub slow_log: Option<SlowLogConfig>,
+    /// Models loaded at startup, like `--preload`.
+    #[serde(default)]
+    pub preload_models: Vec<u32>,
+    /// Where the daemon listens, read once at startup.
+    #[serde(default)]
+    pub transport: TransportConfig,
+    /// Where and how much the daemon logs, read once at startup.
+    #[serde(default)]
+    pub logging: LoggingConfig,
+}
+
+/// `[slow_log]`: which syntheses count as slow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SlowLogConfig {
+    /// Syntheses taking at least this long, queueing included, are recorded.
+    pub threshold_ms: u64,
+}
+
+/// Listening settings; each command-line flag overrides its setting.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransportConfig {
+    /// Unix socket to listen on (`--socket-path`).
+    pub socket_path: Option<PathBuf>,
+    /// Group allowed to use the daemon, as with `--shared-socket --group`.
+    pub shared_group: Option<String>,
+    /// Loopback address for the HTTP API (`--http`), in builds with the `http` feature.
+    pub http: Option<SocketAddr>,
+    /// Address for remote clients (`--listen`); they must present the daemon token.
+    pub listen: Option<SocketAddr>,
+}
+
+/// Daemon log output.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LoggingConfig {
+    /// Least severe messages written: `info`, `warn`, or `error`.
+    #[serde(default)]
+    pub level: LogLevel,
+    /// Appends log lines to this file instead of stdout and stderr, which a
+    /// detached daemon discards.
+    pub file: Option<PathBuf>,
+}
+
+/// Intra-op threads per session in low-power mode.
+pub const LOW_POWER_CPU_THREADS: u16 = 2;
+
+/// Most models accepted in `model_cache.max_loaded_models`.
+pub const MAX_CACHED_MODELS: usize = 32;
+
+/// Keeps recently used models resident instead of loading one per request.
+///
+/// With `max_loaded_models = 0` (the default) every request loads its model and
+/// unloads it afterwards, which keeps idle memory lowest. Otherwise each worker
+/// keeps up to that many models and evicts the least recently used one first, also
+/// when available memory falls below `min_available_mb` (Linux only).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelCacheConfig {
+    #[serde(default)]
+    pub max_loaded_models: usize,
+    /// Evict before loading another model while less memory than this is available.
+    #[serde(default)]
+    pub min_available_mb: u64,
+    /// Models loaded at startup, the most used first according to the usage statistics.
+    #[serde(default)]
+    pub preload: usize,
+    /// Load models from a copy in the local cache, refreshed at startup and on reload,
+    /// instead of from a slow network share (`--copy-models-local`).
+    #[serde(default)]
+    pub copy_local: bool,
+}
+
+impl ModelCacheConfig {
+    pub(crate) fn validate(&self) -> Result<()> {
+        ensure!(
+            self.max_loaded_models <= MAX_CACHED_MODELS,
+            "model_cache.max_loaded_models must be at most {MAX_CACHED_MODELS}: {}",
+            self.max_loaded_models
+        );
+        ensure!(
+            self.preload <= self.max_loaded_models,
+            "model_cache.preload ({}) cannot exceed max_loaded_models ({})",
+            self.preload,
+            self.max_loaded_models
+        );
+        Ok(())
+    }
+}
+
+/// Most intra-op threads accepted in `[inference]`.
+pub const MAX_INFERENCE_CPU_THREADS: u16 = 256;
+
+/// Most concurrent synthesizers accepted in `[workers]`.
+pub const MAX_SYNTHESIS_WORKERS: usize = 64;
+
+/// Synthesizers run side by side for batch synthesis on many-core machines.
+///
+/// Each worker creates its own core and loads its own model, so memory grows with
+/// `count`. `cpus` is shared out among the workers in consecutive, near-equal slices
+/// and each worker's synthesis thread is pinned to its slice.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkerConfig {
+    #[serde(default = "default_worker_count")]
+    pub count: usize,
+    /// CPU indices to pin workers to; empty leaves scheduling to the OS.
+    #[serde(default)]
+    pub cpus: Vec<usize>,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            count: default_worker_count(),
+            cpus: Vec::new(),
+        }
+    }
+}
+
+const fn default_worker_count() -> usize {
+    1
+}
+
+impl WorkerConfig {
+    /// CPUs assigned to worker `index`, or an empty slice when pinning is off.
+    #[must_use]
+    pub fn cpus_for(&self, index: usize) -> &[usize] {
+        let start = index * self.cpus.len() / self.count;
+        let end = (index + 1) * self.cpus.len() / self.count;
+        &self.cpus[start..end]
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            (1..=MAX_SYNTHESIS_WORKERS).contains(&self.count),
+            "workers.count must be between 1 and {MAX_SYNTHESIS_WORKERS}: {}",
+            self.count
+        );
+        ensure!(
+            self.cpus.is_empty() || self.cpus.len() >= self.count,
+            "workers.cpus must list at least one CPU per worker ({} for {} workers)",
+            self.cpus.len(),
+            self.count
+        );
+        Ok(())
+    }
+}
+
+/// Longest accepted dedup window.
+pub const MAX_DEDUP_WINDOW_SECS: u64 = 3600;
+
+/// Guards against agent retry loops speaking the same sentence over and over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DedupConfig {
+    pub window_secs: u64,
+    #[serde(default)]
+    pub action: DedupAction,
+}
+
+/// What the daemon answers when a request repeats within the dedup window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DedupAction {
+    /// Reply `Skipped` so nothing is spoken again.
+    #[default]
+    Skip,
+    /// Reply with the earlier audio without synthesizing it again.
+    Replay,
+}
+
+/// Longest accepted preprocessor timeout.
+pub const MAX_PREPROCESS_TIMEOUT_MS: u64 = 60_000;
+const DEFAULT_PREPROCESS_TIMEOUT_MS: u64 = 2_000;
+
+/// A language front-end plugin, such as a MeCab-based reading normalizer.
+///
+/// The command receives the request text as UTF-8 on stdin and prints the text (or
+/// kana reading) to synthesize on stdout. It is run directly, not through a shell.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PreprocessConfig {
+    /// Program followed by its arguments.
+    pub command: Vec<String>,
+    #[serde(default = "default_preprocess_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+const fn default_preprocess_timeout_ms() -> u64 {
+    DEFAULT_PREPROCESS_TIMEOUT_MS
+}
+
+/// Largest accepted whisper boost gain.
+pub const MAX_WHISPER_BOOST_GAIN_DB: f32 = 24.0;
+
+/// Lifts whisper styles to sit level with normal speech in mixed output.
+///
+/// Synthesized audio for matching styles gets `gain_db` of makeup gain, then a
+/// compressor above `threshold_db` keeps the boosted peaks from clipping.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WhisperBoostConfig {
+    #[serde(default = "default_whisper_gain_db")]
+    pub gain_db: f32,
+    /// Compressor threshold in dBFS.
+    #[serde(default = "default_whisper_threshold_db")]
+    pub threshold_db: f32,
+    /// Compression ratio above the threshold; 1.0 disables compression.
+    #[serde(default = "default_whisper_ratio")]
+    pub ratio: f32,
+    /// Style names treated as whispers, matched exactly.
+    #[serde(default = "default_whisper_styles")]
+    pub styles: Vec<String>,
+}
+
+impl WhisperBoostConfig {
+    #[must_use]
+    pub fn applies_to(&self, style_name: &str) -> bool {
+        self.styles.iter().any(|style| style == style_name)
+    }
+
+    #[must_use]
+    pub const fn volume_boost(&self) -> VolumeBoost {
+        VolumeBoost {
+            gain_db: self.gain_db,
+            threshold_db: self.threshold_db,
+            ratio: self.ratio,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            (0.0..=MAX_WHISPER_BOOST_GAIN_DB).contains(&self.gain_db),
+            "whisper_boost.gain_db must be between 0 and {MAX_WHISPER_BOOST_GAIN_DB}: {}",
+            self.gain_db
+        );
+        ensure!(
+            (-60.0..=0.0).contains(&self.threshold_db),
+            "whisper_boost.threshold_db must be between -60 and 0: {}",
+            self.threshold_db
+        );
+        ensure!(
+            (1.0..=20.0).contains(&self.ratio),
+            "whisper_boost.ratio must be between 1 and 20: {}",
+            self.ratio
+        );
+        ensure!(
+            !self.styles.is_empty(),
+            "whisper_boost.styles must name at least one style"
+        );
+        Ok(())
+    }
+}
+
+const fn default_whisper_gain_db() -> f32 {
+    9.0
+}
+
+const fn default_whisper_threshold_db() -> f32 {
+    -12.0
+}
+
+const fn default_whisper_ratio() -> f32 {
+    4.0
+}
+
+fn default_whisper_styles() -> Vec<String> {
+    vec!["ささやき".to_owned(), "ヒソヒソ".to_owned()]
+}
+
+const MINUTES_PER_DAY: u16 = 24 * 60;
+
+/// A local wall-clock time, stored as minutes since midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(try_from = "String")]
+pub struct ClockTime(u16);
+
+impl ClockTime {
+    /// Parses `HH:MM` in 24-hour form.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not a valid `HH:MM` time.
+    pub fn parse(value: &str) -> Result<Self> {
+        let invalid = || anyhow!("Invalid time '{value}' (expected HH:MM)");
+        let (hours, minutes) = value.trim().split_once(':').ok_or_else(invalid)?;
+        let hours: u16 = hours.parse().map_err(|_| invalid())?;
+        let minutes: u16 = minutes.parse().map_err(|_| invalid())?;
+        ensure!(hours < 24 && minutes < 60, invalid());
+        Ok(Self(hours * 60 + minutes))
+    }
+
+    #[must_use]
+    pub const fn from_minutes(minutes: u16) -> Self {
+        Self(minutes % MINUTES_PER_DAY)
+    }
+}
+
+impl TryFrom<String> for ClockTime {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        Self::parse(&value)
+    }
+}
+
+/// Playback restrictions in force between `from` (inclusive) and `to` (exclusive).
+///
+/// Ranges may wrap midnight (`22:00`–`07:00`); equal ends cover the whole day.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimePolicy {
+    pub from: ClockTime,
+    pub to: ClockTime,
+    /// Gain applied to synthesized audio, from 0.0 to 1.0.
+    pub volume: Option<f32>,
+    /// Set to `false` to make clients play whole utterances instead of streaming segments.
+    pub streaming: Option<bool>,
+}
+
+impl TimePolicy {
+    #[must_use]
+    pub fn covers(&self, time: ClockTime) -> bool {
+        if self.from < self.to {
+            self.from <= time && time < self.to
+        } else if self.from > self.to {
+            time >= self.from || time < self.to
+        } else {
+            true
+        }
+    }
+}
+
+/// Effective playback settings at a given moment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackPolicy {
+    pub volume: f32,
+    pub streaming: bool,
+}
+
+impl Default for PlaybackPolicy {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            streaming: true,
+        }
+    }
+}
+
+/// Synthesis defaults for one calling application.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AppProfile {
+    pub rate: Option<f32>,
+    pub pitch: Option<f32>,
+}
+
+impl AppProfile {
+    /// Fills options the client left at the built-in defaults with this profile's values.
+    ///
+    /// Explicit client choices win: only values equal to the global default are replaced.
+    #[must_use]
+    pub fn apply_defaults(&self, mut options: SynthesisOptions) -> SynthesisOptions {
+        if let Some(rate) = self.rate
+            && options.rate == DEFAULT_SYNTHESIS_RATE
+        {
+            options.rate = rate;
+        }
+        if let Some(pitch) = self.pitch
+            && options.pitch == DEFAULT_SYNTHESIS_PITCH
+        {
+            options.pitch = pitch;
+        }
+        options
+    }
+
+    fn validate(&self, app_id: &str) -> Result<()> {
+        if let Some(rate) = self.rate {
+            ensure!(
+                is_valid_synthesis_rate(rate),
+                "apps.{app_id}.rate is out of range: {rate}"
+            );
+        }
+        if let Some(pitch) = self.pitch {
+            ensure!(
+                is_valid_synthesis_pitch(pitch),
+                "apps.{app_id}.pitch is out of range: {pitch}"
+            );
+        }
+        Ok(())
+    }
+}
+
+impl TransportConfig {
+    fn validate(&mut self) -> Result<()> {
+        self.socket_path = self.socket_path.as_deref().map(expand_home);
+        if let Some(socket_path) = &self.socket_path {
+            ensure!(
+                socket_path.is_absolute(),
+                "transport.socket_path must be an absolute path: {}",
+                socket_path.display()
+            );
+        }
+        if let Some(group) = &self.shared_group {
+            ensure!(
+                !group.trim().is_empty(),
+                "transport.shared_group must name a group"
+            );
+        }
+        if let Some(address) = self.http {
+            ensure!(
+                address.ip().is_loopback(),
+                "transport.http must be a loopback address such as 127.0.0.1:50080: {address}"
+            );
+        }
+        Ok(())
+    }
+}
+
+impl DaemonConfig {
+    /// Parses and validates a `daemon.toml` document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on TOML syntax errors, unknown keys, out-of-range values,
+    /// a relative socket or log path, or a non-loopback HTTP address.
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut config: Self = toml::from_str(source)?;
+        for (app_id, profile) in &config.apps {
+            profile.validate(app_id)?;
+        }
+        if let Some(dedup) = config.dedup {
+            ensure!(
+                (1..=MAX_DEDUP_WINDOW_SECS).contains(&dedup.window_secs),
+                "dedup.window_secs must be between 1 and {MAX_DEDUP_WINDOW_SECS}: {}",
+                dedup.window_secs
+            );
+        }
+        if let Some(preprocess) = &config.preprocess {
+            ensure!(
+                preprocess
+                    .command
+                    .first()
+                    .is_some_and(|program| !program.is_empty()),
+                "preprocess.command must name a program"
+            );
+            ensure!(
+                (1..=MAX_PREPROCESS_TIMEOUT_MS).contains(&preprocess.timeout_ms),
+                "preprocess.timeout_ms must be between 1 and {MAX_PREPROCESS_TIMEOUT_MS}: {}",
+                preprocess.timeout_ms
+            );
+        }
+        if let Some(whisper_boost) = &config.whisper_boost {
+            whisper_boost.validate()?;
+        }
+        ensure!(
+            config.inference.cpu_threads <= MAX_INFERENCE_CPU_THREADS,
+            "inference.cpu_threads must be at most {MAX_INFERENCE_CPU_THREADS}: {}",
+            config.inference.cpu_threads
+        );
+        config.workers.validate()?;
+        config.model_cache.validate()?;
+        for (index, policy) in config.policies.iter().enumerate() {
+            if let Some(volume) = policy.volume {
+                ensure!(
+                    (0.0..=1.0).contains(&volume),
+                    "policies[{index}].volume must be between 0.0 and 1.0: {volume}"
+                );
+            }
+        }
+        if let Some(slow_log) = config.slow_log {
+            ensure!(
+                slow_log.threshold_ms > 0,
+                "slow_log.threshold_ms must be at least 1"
+            );
+        }
+        config.transport.validate()?;
+        config.logging.file = config.logging.file.as_deref().map(expand_home);
+        if let Some(file) = &config.logging.file {
+            ensure!(
+                file.is_absolute(),
+                "logging.file must be an absolute path: {}",
+                file.display()
+            );
+        }
+        Ok(config)
+    }
+
+    /// One line describing what the config sets up, for logs and `--check-config`.
+    #[must_use]
+    pub fn summary(&self, config_path: &Path) -> String {
+        format!(
+            "{} app profile(s), {} time policy rule(s), and {} from {}",
+            self.apps.len(),
+            self.policies.len(),
+            if self.preprocess.is_some() {
+                "a text preprocessor"
+            } else {
+                "no text preprocessor"
+            },
+            config_path.display()
+        )
+    }
+
+    /// Playback settings from the first policy covering `time`, or defaults.
+    #[must_use]
+    pub fn playback_policy_at(&self, time: ClockTime) -> PlaybackPolicy {
+        let defaults = PlaybackPolicy::default();
+        self.policies
+            .iter()
+            .find(|policy| policy.covers(time))
+            .map_or(defaults, |policy| PlaybackPolicy {
+                volume: policy.volume.unwrap_or(defaults.volume),
+                streaming: policy.streaming.unwrap_or(defaults.streaming),
+            })
+    }
+
+    #[must_use]
+    pub fn app_profile(&self, app_id: &str) -> Option<&AppProfile> {
+        self.apps.get(app_id)
+    }
+
+    /// In low-power mode, runs one worker on at most [`LOW_POWER_CPU_THREADS`]
+    /// threads and unloads each model after its request instead of caching it.
+    ///
+    /// Deferring background work on battery is decided per request, since the power
+    /// source changes while the daemon runs.
+    pub fn apply_low_power(&mut self) {
+        if !self.low_power {
+            return;
+        }
+        self.inference.cpu_threads = match self.inference.cpu_threads {
+            0 => LOW_POWER_CPU_THREADS,
+            threads => threads.min(LOW_POWER_CPU_THREADS),
+        };
+        self.workers.count = 1;
+        self.model_cache.max_loaded_models = 0;
+        self.model_cache.preload = 0;
+    }
+}
+
+/// Current local wall-clock time according to the system time zone.
+#[must_use]
+pub fn local_clock_time() -> ClockTime {
+    LocalTime::now().map_or(ClockTime::from_minutes(0), |now| {
+        ClockTime::from_minutes(now.minutes_since_midnight())
+    })
+}
+
+/// Location of `daemon.toml`: `$VOICEVOX_DAEMON_CONFIG`, else the user config directory.
+#[must_use]
+pub fn daemon_config_path() -> PathBuf {
+    std::env::var_os(crate::config::ENV_VOICEVOX_DAEMON_CONFIG).map_or_else(
+        || get_config_dir().join(crate::config::DAEMON_CONFIG_FILE),
+        PathBuf::from,
+    )
+}
+
+/// Loads the daemon config, falling back to defaults when the file does not exist.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read or is invalid.
+///
+/// Without a `[model_cache]` section, the one from the user's `config.toml` applies.
+pub fn load_daemon_config(path: &Path) -> Result<DaemonConfig> {
+    let (mut config, has_model_cache) = match std::fs::read_to_string(path) {
+        Ok(source) => (
+            DaemonConfig::parse(&source)
+                .with_context(|| format!("Invalid daemon config {}", path.display()))?,
+            source
+                .parse::<toml::Table>()
+                .is_ok_and(|table| table.contains_key("model_cache")),
+        ),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            (DaemonConfig::default(), false)
+        }
+        Err(error) => {
+            return Err(error)
+                .with_context(|| format!("Failed to read daemon config {}", path.display()));
+        }
+    };
+    if !has_model_cache && let Some(model_cache) = crate::config::user_config().model_cache {
+        config.model_cache = model_cache;
+    }
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::core::ExecutionProvider;
+
+    #[test]
+    fn app_profiles_fill_only_default_options() {
+        let config = DaemonConfig::parse(
+            r#"
+            [apps.claude-mcp]
+            rate = 1.3
+            pitch = -0.05
+            "#,
+        )
+        .expect("valid config");
+        let profile = config.app_profile("claude-mcp").expect("profile");
+
+        let defaults = profile.apply_defaults(SynthesisOptions::default());
+        assert_eq!((defaults.rate, defaults.pitch), (1.3, -0.05));
+
+        let explicit = profile.apply_defaults(SynthesisOptions {
+            rate: 0.8,
+            pitch: 0.1,
+            ..SynthesisOptions::default()
+        });
+        assert_eq!((explicit.rate, explicit.pitch), (0.8, 0.1));
+    }
+
+    #[test]
+    fn time_policies_wrap_midnight_and_first_match_wins() {
+        let config = DaemonConfig::parse(
+            r#"
+            [[policies]]
+            from = "22:00"
+            to = "07:00"
+            volume = 0.5
+            streaming = false
+
+            [[policies]]
+            from = "06:00"
+            to = "09:00"
+            volume = 0.8
+            "#,
+        )
+        .expect("valid config");
+        let at = |value| config.playback_policy_at(ClockTime::parse(value).expect("time"));
+
+        let night = PlaybackPolicy {
+            volume: 0.5,
+            streaming: false,
+        };
+        assert_eq!(at("23:30"), night);
+        assert_eq!(at("06:30"), night);
+        assert_eq!(
+            at("07:00"),
+            PlaybackPolicy {
+                volume: 0.8,
+                streaming: true,
+            }
+        );
+        assert_eq!(at("12:00"), PlaybackPolicy::default());
+        assert!(DaemonConfig::parse("[[policies]]\nfrom = \"25:00\"\nto = \"07:00\"\n").is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_out_of_range_values() {
+        assert!(DaemonConfig::parse("[apps.x]\nvolume = 1.0\n").is_err());
+        assert!(DaemonConfig::parse("[apps.x]\nrate = 5.0\n").is_err());
+        assert!(
+            DaemonConfig::parse("[[policies]]\nfrom = \"0:00\"\nto = \"0:00\"\nvolume = 1.5\n")
+                .is_err()
+        );
+        assert!(DaemonConfig::parse("[preprocess]\ncommand = []\n").is_err());
+        assert!(
+            DaemonConfig::parse("[preprocess]\ncommand = [\"norm\"]\ntimeout_ms = 0\n").is_err()
+        );
+        assert_eq!(
+            DaemonConfig::parse("[preprocess]\ncommand = [\"norm\", \"--kana\"]\n")
+                .expect("preprocess")
+                .preprocess
+                .map(|preprocess| preprocess.timeout_ms),
+            Some(DEFAULT_PREPROCESS_TIMEOUT_MS)
+        );
+        assert_eq!(
+            DaemonConfig::parse("").expect("empty"),
+            DaemonConfig::default()
+        );
+    }
+
+    #[test]
+    fn transport_and_logging_settings_are_checked() {
+        let config = DaemonConfig::parse(
+            r#"
+            preload_models = [3, 8]
+
+            [transport]
+            socket_path = "/run/voicevox/daemon.sock"
+            shared_group = "audio"
+            http = "127.0.0.1:50080"
+
+            [logging]
+            level = "warn"
+            file = "/var/log/voicevox-daemon.log"
+            "#,
+        )
+        .expect("valid config");
+        assert_eq!(config.preload_models, [3, 8]);
+        assert_eq!(config.transport.shared_group.as_deref(), Some("audio"));
+        assert_eq!(config.logging.level, LogLevel::Warn);
+
+        assert!(DaemonConfig::parse("[transport]\nsocket_path = \"daemon.sock\"\n").is_err());
+        assert!(DaemonConfig::parse("[transport]\nhttp = \"0.0.0.0:50080\"\n").is_err());
+        assert!(DaemonConfig::parse("[logging]\nlevel = \"debug\"\n").is_err());
+        assert!(DaemonConfig::parse("[logging]\nfile = \"daemon.log\"\n").is_err());
+        assert!(DaemonConfig::parse("[slow_log]\nthreshold_ms = 0\n").is_err());
+    }
+
+    #[test]
+    fn whisper_boost_defaults_to_common_whisper_styles() {
+        let boost = DaemonConfig::parse("[whisper_boost]\n")
+            .expect("whisper boost")
+            .whisper_boost
+            .expect("section present");
+        assert!(boost.applies_to("ささやき"));
+        assert!(boost.applies_to("ヒソヒソ"));
+        assert!(!boost.applies_to("ノーマル"));
+        assert_eq!(boost.volume_boost().gain_db, 9.0);
+
+        assert!(DaemonConfig::parse("[whisper_boost]\ngain_db = 30.0\n").is_err());
+        assert!(DaemonConfig::parse("[whisper_boost]\nratio = 0.5\n").is_err());
+        assert!(DaemonConfig::parse("[whisper_boost]\nstyles = []\n").is_err());
+    }
+
+    #[test]
+    fn inference_tuning_parses_and_rejects_unsupported_knobs() {
+        let inference =
+            DaemonConfig::parse("[inference]\nexecution_provider = \"gpu\"\ncpu_threads = 4\n")
+                .expect("inference")
+                .inference;
+        assert_eq!(
+            inference,
+            InferenceOptions {
+                execution_provider: ExecutionProvider::Gpu,
+                cpu_threads: 4,
+            }
+        );
+
+        assert!(DaemonConfig::parse("[inference]\nexecution_provider = \"tpu\"\n").is_err());
+        assert!(DaemonConfig::parse("[inference]\ncpu_threads = 1000\n").is_err());
+        assert!(DaemonConfig::parse("[inference]\ninter_op_threads = 2\n").is_err());
+    }
+
+    #[test]
+    fn model_cache_defaults_off_and_rejects_large_limits() {
+        assert_eq!(
+            DaemonConfig::parse("").expect("empty").model_cache,
+            ModelCacheConfig::default()
+        );
+        assert_eq!(
+            DaemonConfig::parse(
+                "[model_cache]\nmax_loaded_models = 3\nmin_available_mb = 512\ncopy_local = true\n"
+            )
+            .expect("model cache")
+            .model_cache,
+            ModelCacheConfig {
+                max_loaded_models: 3,
+                min_available_mb: 512,
+                preload: 0,
+                copy_local: true,
+            }
+        );
+        assert!(DaemonConfig::parse("[model_cache]\nmax_loaded_models = 100\n").is_err());
+        assert!(
+            DaemonConfig::parse("[model_cache]\nmax_loaded_models = 2\npreload = 3\n").is_err()
+        );
+    }
+
+    #[test]
+    fn low_power_caps_threads_and_disables_model_cache() {
+        let mut config = DaemonConfig::parse(
+            "low_power = true\n[inference]\ncpu_threads = 8\n[workers]\ncount = 4\n\
+             [model_cache]\nmax_loaded_models = 3\npreload = 2\n",
+        )
+        .expect("low power config");
+        config.apply_low_power();
+        assert_eq!(config.inference.cpu_threads, LOW_POWER_CPU_THREADS);
+        assert_eq!(config.workers.count, 1);
+        assert_eq!(
+            (
+                config.model_cache.max_loaded_models,
+                config.model_cache.preload
+            ),
+            (0, 0)
+        );
+
+        let mut unchanged = DaemonConfig::parse("[workers]\ncount = 4\n").expect("config");
+        unchanged.apply_low_power();
+        assert_eq!(unchanged.workers.count, 4);
+        assert_eq!(unchanged.inference.cpu_threads, 0);
+    }
+
+    #[test]
+    fn workers_share_out_cpus_in_slices() {
+        assert_eq!(
+            DaemonConfig::parse("").expect("empty").workers,
+            WorkerConfig::default()
+        );
+
+        let workers = DaemonConfig::parse("[workers]\ncount = 2\ncpus = [0, 1, 2, 3, 4]\n")
+            .expect("workers")
+            .workers;
+        assert_eq!(workers.cpus_for(0), &[0, 1]);
+        assert_eq!(workers.cpus_for(1), &[2, 3, 4]);
+        assert!(WorkerConfig::default().cpus_for(0).is_empty());
+
+        assert!(DaemonConfig::parse("[workers]\ncount = 0\n").is_err());
+        assert!(DaemonConfig::parse("[workers]\ncount = 3\ncpus = [0, 1]\n").is_err());
+    }
+}
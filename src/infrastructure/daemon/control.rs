@@ -38,3 +38,16 @@ pub fn terminate_process(pid: u32) -> bool {
     .status()
     .is_ok_and(|status| status.success())
 }
+
+/// Sends SIGHUP, which makes a running daemon reload `daemon.toml`.
+#[must_use]
+pub fn hangup_process(pid: u32) -> bool {
+    Command::new(crate::config::command_path_or_fallback(
+        crate::config::SYSTEM_KILL_PATH,
+        "kill",
+    ))
+    .arg("-HUP")
+    .arg(pid.to_string())
+    .status()
+    .is_ok_and(|status| status.success())
+}
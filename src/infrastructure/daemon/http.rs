@@ -0,0 +1,515 @@
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Semaphore;
+use tokio::time::timeout;
+
+use crate::infrastructure::daemon::state::DaemonState;
+use crate::infrastructure::ipc::{DaemonErrorCode, OwnedRequest, OwnedResponse, SynthesisOptions};
+
+const MAX_CONCURRENT_CONNECTIONS: usize = 16;
+const MAX_REQUEST_HEAD_BYTES: usize = 16 * 1024;
+const MAX_REQUEST_BODY_BYTES: usize = 256 * 1024;
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[cfg(feature = "menu-bar")]
+const EVENT_STREAM_HEAD: &str = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n";
+
+#[cfg(feature = "opus")]
+const OPUS_STREAM_HEAD: &str = "HTTP/1.1 200 OK\r\nContent-Type: audio/x-voicevox-opus\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n";
+
+#[cfg(feature = "web-ui")]
+const INDEX_HTML: &str = include_str!("http/index.html");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Method {
+    Get,
+    Post,
+    Other,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct RequestHead {
+    method: Method,
+    path: String,
+    host: Option<String>,
+    content_type: Option<String>,
+    content_length: usize,
+}
+
+struct HttpRequest {
+    head: RequestHead,
+    body: Vec<u8>,
+}
+
+enum Routed {
+    Response(HttpResponse),
+    #[cfg(feature = "menu-bar")]
+    EventStream,
+    #[cfg(feature = "opus")]
+    OpusStream(SynthesizeBody),
+}
+
+impl From<HttpResponse> for Routed {
+    fn from(response: HttpResponse) -> Self {
+        Self::Response(response)
+    }
+}
+
+struct HttpResponse {
+    status: u16,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl HttpResponse {
+    fn json(status: u16, value: &impl Serialize) -> Self {
+        let body = serde_json::to_vec(value).unwrap_or_else(|_| b"{}".to_vec());
+        Self {
+            status,
+            content_type: "application/json",
+            body,
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, &serde_json::json!({ "error": message }))
+    }
+
+    fn encode_head(&self) -> String {
+        format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nX-Content-Type-Options: nosniff\r\nConnection: close\r\n\r\n",
+            self.status,
+            reason_phrase(self.status),
+            self.content_type,
+            self.body.len()
+        )
+    }
+}
+
+const fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        _ => "Internal Server Error",
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SynthesizeBody {
+    text: String,
+    style_id: u32,
+    /// `rate`, `pitch`, and the other options sit beside `text`; omitted ones default.
+    #[serde(flatten)]
+    options: SynthesisOptions,
+}
+
+fn parse_request_head(head: &str) -> Result<RequestHead> {
+    let mut lines = head.lines();
+    let request_line = lines.next().ok_or_else(|| anyhow!("Empty request"))?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target), Some(version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(anyhow!("Malformed request line"));
+    };
+    if !version.starts_with("HTTP/1.") {
+        return Err(anyhow!("Unsupported HTTP version: {version}"));
+    }
+
+    let mut request = RequestHead {
+        method: match method {
+            "GET" => Method::Get,
+            "POST" => Method::Post,
+            _ => Method::Other,
+        },
+        path: target.split('?').next().unwrap_or(target).to_owned(),
+        host: None,
+        content_type: None,
+        content_length: 0,
+    };
+    for line in lines.take_while(|line| !line.is_empty()) {
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(anyhow!("Malformed header line"));
+        };
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "host" => request.host = Some(value.to_owned()),
+            "content-type" => request.content_type = Some(value.to_ascii_lowercase()),
+            "content-length" => {
+                request.content_length = value
+                    .parse()
+                    .map_err(|_| anyhow!("Invalid Content-Length"))?;
+            }
+            "transfer-encoding" => return Err(anyhow!("Chunked request bodies are not supported")),
+            _ => {}
+        }
+    }
+    Ok(request)
+}
+
+/// Accepts only loopback `Host` headers so a DNS-rebound page cannot reach the daemon.
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => host.rsplit_once(':').map_or(host, |(name, _)| name),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name
+            .parse::<IpAddr>()
+            .is_ok_and(|address| address.is_loopback())
+}
+
+async fn read_request(stream: &mut TcpStream) -> Result<HttpRequest> {
+    let mut reader = BufReader::new(stream);
+    let mut head = String::new();
+    loop {
+        let read = (&mut reader)
+            .take((MAX_REQUEST_HEAD_BYTES - head.len()) as u64)
+            .read_line(&mut head)
+            .await?;
+        if read == 0 || head.len() >= MAX_REQUEST_HEAD_BYTES {
+            return Err(anyhow!("Request head is incomplete or too large"));
+        }
+        if head.ends_with("\r\n\r\n") || head.ends_with("\n\n") {
+            break;
+        }
+    }
+
+    let head = parse_request_head(&head)?;
+    if head.content_length > MAX_REQUEST_BODY_BYTES {
+        return Ok(HttpRequest {
+            head,
+            body: Vec::new(),
+        });
+    }
+    let mut body = vec![0; head.content_length];
+    reader.read_exact(&mut body).await?;
+    Ok(HttpRequest { head, body })
+}
+
+async fn route(request: HttpRequest, state: &DaemonState) -> Routed {
+    if !request.head.host.as_deref().is_some_and(is_loopback_host) {
+        return HttpResponse::error(403, "Only loopback Host headers are accepted").into();
+    }
+    // Requiring a JSON content type on POST forces a CORS preflight, which this server
+    // never answers, so pages from other origins cannot trigger side effects.
+    if request.head.method == Method::Post
+        && !request
+            .head
+            .content_type
+            .as_deref()
+            .is_some_and(|content_type| content_type.starts_with("application/json"))
+    {
+        return HttpResponse::error(415, "Content-Type must be application/json").into();
+    }
+
+    match (request.head.method, request.head.path.as_str()) {
+        #[cfg(feature = "web-ui")]
+        (Method::Get, "/") => HttpResponse {
+            status: 200,
+            content_type: "text/html; charset=utf-8",
+            body: INDEX_HTML.as_bytes().to_vec(),
+        }
+        .into(),
+        #[cfg(feature = "menu-bar")]
+        (Method::Get, "/status") => menu_bar_status(state).into(),
+        #[cfg(feature = "menu-bar")]
+        (Method::Get, "/events") => Routed::EventStream,
+        #[cfg(feature = "menu-bar")]
+        (Method::Post, "/stop") => HttpResponse::json(
+            200,
+            &serde_json::json!({ "stopped": state.stop_pending_synthesis() }),
+        )
+        .into(),
+        (Method::Get, "/speakers") => list_speakers(state).await.into(),
+        (Method::Post, "/synthesize") => synthesize(&request, state).await.into(),
+        #[cfg(feature = "opus")]
+        (Method::Post, "/synthesize/opus") => match parse_synthesize_body(&request) {
+            Ok(body) => Routed::OpusStream(body),
+            Err(response) => response.into(),
+        },
+        (_, "/speakers" | "/synthesize" | "/status" | "/events" | "/stop") => {
+            HttpResponse::error(405, "Method not allowed").into()
+        }
+        _ => HttpResponse::error(404, "Not found").into(),
+    }
+}
+
+#[cfg(feature = "menu-bar")]
+fn menu_bar_status(state: &DaemonState) -> HttpResponse {
+    let activity = state.synthesis_activity();
+    HttpResponse::json(
+        200,
+        &serde_json::json!({
+            "current_style_id": activity.current_style_id,
+            "queue_length": activity.queue_length,
+        }),
+    )
+}
+
+/// Streams daemon events as server-sent events until the client disconnects.
+#[cfg(feature = "menu-bar")]
+async fn stream_events(stream: &mut TcpStream, state: &DaemonState) -> Result<()> {
+    use tokio::sync::broadcast::error::RecvError;
+
+    let mut events = state.subscribe_events();
+    stream.write_all(EVENT_STREAM_HEAD.as_bytes()).await?;
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(missed)) => {
+                crate::infrastructure::ipc::DaemonEvent::Lagged { missed }
+            }
+            Err(RecvError::Closed) => return Ok(()),
+        };
+        let data = serde_json::to_string(&event)?;
+        stream
+            .write_all(format!("data: {data}\n\n").as_bytes())
+            .await?;
+    }
+}
+
+async fn list_speakers(state: &DaemonState) -> HttpResponse {
+    match state.handle_request(OwnedRequest::ListSpeakers, None).await {
+        OwnedResponse::SpeakersListWithModels { speakers, .. } => {
+            HttpResponse::json(200, &speakers)
+        }
+        response => daemon_error_response(response),
+    }
+}
+
+fn parse_synthesize_body(request: &HttpRequest) -> Result<SynthesizeBody, HttpResponse> {
+    if request.head.content_length > MAX_REQUEST_BODY_BYTES {
+        return Err(HttpResponse::error(413, "Request body is too large"));
+    }
+    serde_json::from_slice(&request.body)
+        .map_err(|error| HttpResponse::error(400, &format!("Invalid request body: {error}")))
+}
+
+async fn synthesize(request: &HttpRequest, state: &DaemonState) -> HttpResponse {
+    let body = match parse_synthesize_body(request) {
+        Ok(body) => body,
+        Err(response) => return response,
+    };
+
+    let response = state
+        .handle_request(
+            OwnedRequest::Synthesize {
+                text: body.text,
+                style_id: body.style_id,
+                options: body.options,
+            },
+            None,
+        )
+        .await;
+    match response {
+        OwnedResponse::SynthesizeResult { wav_data, .. } => HttpResponse {
+            status: 200,
+            content_type: "audio/wav",
+            body: wav_data,
+        },
+        OwnedResponse::Skipped => skipped_response(),
+        response => daemon_error_response(response),
+    }
+}
+
+fn skipped_response() -> HttpResponse {
+    HttpResponse::error(409, "Skipped: the same text was synthesized moments ago")
+}
+
+/// Synthesizes sentence by sentence and sends each as Opus packets once it is ready.
+///
+/// The body is close-delimited: the framed stream from
+/// [`crate::domain::synthesis::opus_stream`] runs until the connection ends. Errors
+/// before the first segment still get a JSON error response; later ones end the
+/// stream early, which clients see as a truncated final packet or a short stream.
+#[cfg(feature = "opus")]
+async fn stream_opus(
+    stream: &mut TcpStream,
+    state: &DaemonState,
+    body: SynthesizeBody,
+) -> Result<()> {
+    use crate::infrastructure::opus::OpusStreamEncoder;
+
+    let segments = crate::config::Config::default()
+        .text_splitter
+        .splitter()
+        .split(&body.text);
+    let mut encoder: Option<OpusStreamEncoder> = None;
+    for segment in segments
+        .into_iter()
+        .filter(|segment| !segment.trim().is_empty())
+    {
+        let response = state
+            .handle_request(
+                OwnedRequest::Synthesize {
+                    text: segment,
+                    style_id: body.style_id,
+                    options: body.options,
+                },
+                None,
+            )
+            .await;
+        let wav_data = match response {
+            OwnedResponse::SynthesizeResult { wav_data, .. } => wav_data,
+            OwnedResponse::Skipped => continue,
+            response => {
+                let error = daemon_error_response(response);
+                if encoder.is_none() {
+                    return write_response(stream, &error).await;
+                }
+                return Err(anyhow!(
+                    "Opus stream aborted: {}",
+                    String::from_utf8_lossy(&error.body)
+                ));
+            }
+        };
+        let encoder = match &mut encoder {
+            Some(encoder) => encoder,
+            None => {
+                let created = match OpusStreamEncoder::for_wav(&wav_data) {
+                    Ok(created) => created,
+                    Err(error) => {
+                        return write_response(
+                            stream,
+                            &HttpResponse::error(500, &error.to_string()),
+                        )
+                        .await;
+                    }
+                };
+                stream.write_all(OPUS_STREAM_HEAD.as_bytes()).await?;
+                encoder.insert(created)
+            }
+        };
+        stream.write_all(&encoder.encode_wav(&wav_data)?).await?;
+    }
+
+    match encoder {
+        Some(encoder) => {
+            stream.write_all(&encoder.finish()?).await?;
+            stream.shutdown().await?;
+            Ok(())
+        }
+        None => write_response(stream, &skipped_response()).await,
+    }
+}
+
+fn daemon_error_response(response: OwnedResponse) -> HttpResponse {
+    match response {
+        OwnedResponse::Error {
+            code: DaemonErrorCode::InvalidTargetId,
+            message,
+        } => HttpResponse::error(400, &message),
+        OwnedResponse::Error { message, .. } => HttpResponse::error(500, &message),
+        _ => HttpResponse::error(500, "Unexpected daemon response"),
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, state: Arc<DaemonState>) -> Result<()> {
+    let response = match timeout(REQUEST_READ_TIMEOUT, read_request(&mut stream)).await {
+        Ok(Ok(request)) => match route(request, &state).await {
+            Routed::Response(response) => response,
+            #[cfg(feature = "menu-bar")]
+            Routed::EventStream => return stream_events(&mut stream, &state).await,
+            #[cfg(feature = "opus")]
+            Routed::OpusStream(body) => return stream_opus(&mut stream, &state, body).await,
+        },
+        Ok(Err(error)) => HttpResponse::error(400, &error.to_string()),
+        Err(_) => HttpResponse::error(400, "Request timed out"),
+    };
+    write_response(&mut stream, &response).await
+}
+
+async fn write_response(stream: &mut TcpStream, response: &HttpResponse) -> Result<()> {
+    stream.write_all(response.encode_head().as_bytes()).await?;
+    stream.write_all(&response.body).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Serves the daemon's HTTP API on a loopback address until the listener fails.
+///
+/// `web-ui` adds the single-page UI at `/`; `menu-bar` adds `/status`, `/events`
+/// (server-sent events) and `POST /stop` for a menu-bar companion; `opus` adds
+/// `POST /synthesize/opus`, which streams sentence-by-sentence Opus packets.
+///
+/// # Errors
+///
+/// Returns an error if `address` is not a loopback address, the listener cannot be
+/// bound, or accepting a connection fails.
+pub async fn serve_http(address: SocketAddr, state: Arc<DaemonState>) -> Result<()> {
+    if !address.ip().is_loopback() {
+        return Err(anyhow!(
+            "HTTP mode only binds loopback addresses, got {address}"
+        ));
+    }
+    let listener = TcpListener::bind(address)
+        .await
+        .with_context(|| format!("Failed to bind HTTP listener on {address}"))?;
+    crate::infrastructure::logging::info(&format!(
+        "HTTP listening on: http://{}",
+        listener.local_addr()?
+    ));
+
+    let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_CONNECTIONS));
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let Ok(permit) = Arc::clone(&permits).acquire_owned().await else {
+            return Err(anyhow!("HTTP connection limiter closed"));
+        };
+        let state = Arc::clone(&state);
+        tokio::spawn(async move {
+            if let Err(error) = handle_connection(stream, state).await {
+                crate::infrastructure::logging::error(&format!("HTTP client error: {error}"));
+            }
+            drop(permit);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_request_line_and_relevant_headers() {
+        let head = parse_request_head(
+            "POST /synthesize?x=1 HTTP/1.1\r\nHost: localhost:50080\r\nContent-Type: Application/JSON\r\nContent-Length: 42\r\n\r\n",
+        )
+        .expect("valid head");
+
+        assert_eq!(
+            head,
+            RequestHead {
+                method: Method::Post,
+                path: "/synthesize".to_owned(),
+                host: Some("localhost:50080".to_owned()),
+                content_type: Some("application/json".to_owned()),
+                content_length: 42,
+            }
+        );
+        assert!(parse_request_head("GET /\r\n\r\n").is_err());
+        assert!(
+            parse_request_head("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n").is_err()
+        );
+    }
+
+    #[test]
+    fn only_loopback_hosts_are_allowed() {
+        assert!(is_loopback_host("localhost:50080"));
+        assert!(is_loopback_host("127.0.0.1:50080"));
+        assert!(is_loopback_host("[::1]:50080"));
+        assert!(!is_loopback_host("evil.example:50080"));
+        assert!(!is_loopback_host("192.168.1.10"));
+    }
+}
@@ -1,11 +1,18 @@
 pub mod bootstrap;
 pub mod client;
+pub mod config;
 pub mod control;
+#[cfg(feature = "http")]
+pub mod http;
+pub mod preprocess;
 pub mod process;
+pub mod self_test;
 pub mod server;
+pub mod shared_socket;
 pub mod socket_probe;
 pub mod start_process;
 pub mod state;
+pub mod token;
 
 use std::io;
 use std::path::PathBuf;
@@ -15,11 +22,11 @@ pub use bootstrap::{
     EnsureDaemonRunningOptions, EnsureDaemonRunningOutcome, ensure_daemon_running,
     recover_stuck_daemon_and_retry,
 };
-pub use control::{is_socket_responsive, pid_memory_info_line, terminate_process};
+pub use control::{hangup_process, is_socket_responsive, pid_memory_info_line, terminate_process};
 pub use process::{
     check_and_prevent_duplicate, find_daemon_processes, remove_stale_socket_if_present,
 };
-pub use server::run_daemon;
+pub use server::{NetworkListeners, run_daemon};
 pub use start_process::{StartDaemonOutcome, find_daemon_binary, start_daemon_detached};
 pub use state::DaemonState;
 
@@ -76,6 +83,9 @@ pub mod exit_codes {
 
     /// Daemon binary not found
     pub const BINARY_NOT_FOUND: i32 = 5;
+
+    /// `--self-test` failed to synthesize or verify the test phrase
+    pub const SELF_TEST_FAILED: i32 = 6;
 }
 
 /// Daemon startup constants
@@ -0,0 +1,101 @@
+use anyhow::{Context, Result, anyhow, ensure};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use super::config::PreprocessConfig;
+
+/// Runs the configured front-end plugin over `text` and returns what it printed.
+///
+/// A trailing newline is stripped. The plugin is killed if it outlives its timeout.
+///
+/// # Errors
+///
+/// Returns an error if the command cannot be started, times out, exits non-zero,
+/// or prints nothing or invalid UTF-8.
+pub async fn run_preprocessor(config: &PreprocessConfig, text: &str) -> Result<String> {
+    let (program, args) = config
+        .command
+        .split_first()
+        .context("Preprocessor command is empty")?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to start preprocessor '{program}'"))?;
+
+    let mut stdin = child
+        .stdin
+        .take()
+        .context("Preprocessor stdin unavailable")?;
+    let input = text.as_bytes().to_vec();
+    // Write while reading so a plugin that streams its output cannot deadlock on a full pipe.
+    let writer = tokio::spawn(async move {
+        let _ = stdin.write_all(&input).await;
+    });
+    let output = tokio::time::timeout(
+        Duration::from_millis(config.timeout_ms),
+        child.wait_with_output(),
+    )
+    .await
+    .map_err(|_| {
+        anyhow!(
+            "Preprocessor '{program}' did not finish within {} ms",
+            config.timeout_ms
+        )
+    })?
+    .with_context(|| format!("Preprocessor '{program}' failed"))?;
+    writer.abort();
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().next().unwrap_or_default();
+        return Err(anyhow!(
+            "Preprocessor '{program}' exited with {}: {reason}",
+            output.status
+        ));
+    }
+    let processed = String::from_utf8(output.stdout)
+        .with_context(|| format!("Preprocessor '{program}' printed invalid UTF-8"))?;
+    let processed = processed
+        .strip_suffix('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .unwrap_or(&processed);
+    ensure!(
+        !processed.trim().is_empty(),
+        "Preprocessor '{program}' printed no text"
+    );
+    Ok(processed.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(command: &[&str]) -> PreprocessConfig {
+        PreprocessConfig {
+            command: command.iter().map(ToString::to_string).collect(),
+            timeout_ms: 2_000,
+        }
+    }
+
+    #[tokio::test]
+    async fn pipes_text_through_the_plugin() {
+        let upper = config(&["tr", "a-z", "A-Z"]);
+        assert_eq!(
+            run_preprocessor(&upper, "hello\n").await.expect("tr"),
+            "HELLO"
+        );
+
+        let failing = config(&["sh", "-c", "echo no dictionary >&2; exit 3"]);
+        let error = run_preprocessor(&failing, "text").await.expect_err("exit");
+        assert!(error.to_string().contains("no dictionary"));
+
+        let silent = config(&["true"]);
+        assert!(run_preprocessor(&silent, "text").await.is_err());
+    }
+}
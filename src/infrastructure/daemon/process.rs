@@ -4,7 +4,7 @@ use std::fs;
 use std::io;
 use std::os::unix::fs::FileTypeExt;
 use std::os::unix::fs::MetadataExt;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process;
 
 fn pgrep_command_path() -> &'static str {
@@ -84,6 +84,58 @@ pub async fn check_and_prevent_duplicate(socket_path: &Path) -> DaemonResult<()>
     Ok(())
 }
 
+/// Removes the socket file at `socket_path` if it is left over from a daemon that
+/// is gone, so a new daemon can bind it; a live daemon's socket is left alone.
+///
+/// # Errors
+///
+/// Returns an error if a daemon still answers or holds the socket, or if the file
+/// cannot be safely removed.
+pub async fn reclaim_stale_socket(socket_path: &Path) -> DaemonResult<()> {
+    if socket_path.exists() {
+        handle_existing_socket(socket_path).await?;
+    }
+    Ok(())
+}
+
+/// File next to the daemon socket that records the PID of the daemon serving it.
+#[must_use]
+pub fn daemon_pid_path(socket_path: &Path) -> PathBuf {
+    socket_path.with_extension("pid")
+}
+
+/// Records this process as the daemon serving `socket_path`.
+///
+/// # Errors
+///
+/// Returns an error if the PID file cannot be written.
+pub fn write_daemon_pid_file(socket_path: &Path) -> io::Result<()> {
+    fs::write(daemon_pid_path(socket_path), format!("{}\n", process::id()))
+}
+
+/// The PID the daemon serving `socket_path` recorded, if its PID file is readable.
+#[must_use]
+pub fn recorded_daemon_pid(socket_path: &Path) -> Option<u32> {
+    fs::read_to_string(daemon_pid_path(socket_path))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Whether `pid` is a running `voicevox-daemon`, falling back to whether any
+/// process has that PID when `pgrep` is unavailable.
+#[must_use]
+pub fn daemon_pid_is_running(pid: u32) -> bool {
+    find_daemon_processes().map_or_else(
+        |_| {
+            // SAFETY: signal 0 only checks that the process exists.
+            libc::pid_t::try_from(pid).is_ok_and(|pid| unsafe { libc::kill(pid, 0) } == 0)
+        },
+        |pids| pids.contains(&pid),
+    )
+}
+
 /// Removes a stale daemon socket file if it exists.
 ///
 /// Returns `Ok(true)` when a socket file was removed, `Ok(false)` when the path does not exist.
@@ -140,8 +192,19 @@ async fn handle_existing_socket(socket_path: &Path) -> DaemonResult<()> {
                             .to_string(),
                 });
             }
-            if let Some(pid) = detect_other_daemon_pid()? {
-                return Err(DaemonError::AlreadyRunning { pid });
+            // The PID file says which daemon owned the socket, so a crashed daemon's
+            // socket is reclaimed even while daemons serve other sockets; a daemon
+            // that is still alive but not answering keeps it.
+            match recorded_daemon_pid(socket_path) {
+                Some(pid) if daemon_pid_is_running(pid) => {
+                    return Err(DaemonError::AlreadyRunning { pid });
+                }
+                Some(_) => {}
+                None => {
+                    if let Some(pid) = detect_other_daemon_pid()? {
+                        return Err(DaemonError::AlreadyRunning { pid });
+                    }
+                }
             }
             remove_stale_socket(socket_path)
         }
@@ -193,6 +256,12 @@ fn remove_stale_socket(socket_path: &Path) -> DaemonResult<()> {
         }
     };
 
+    if socket_owner_uid != current_uid() {
+        return Err(DaemonError::SocketPermissionDenied {
+            path: socket_path.to_path_buf(),
+        });
+    }
+
     fs::remove_file(socket_path).map_err(|e| match e.kind() {
         std::io::ErrorKind::PermissionDenied if socket_owner_uid != current_uid() => {
             DaemonError::SocketPermissionDenied {
@@ -208,7 +277,9 @@ fn remove_stale_socket(socket_path: &Path) -> DaemonResult<()> {
         _ => DaemonError::StartupFailed {
             message: format!("Failed to remove stale socket: {e}"),
         },
-    })
+    })?;
+    let _ = fs::remove_file(daemon_pid_path(socket_path));
+    Ok(())
 }
 
 fn check_for_other_daemons() -> DaemonResult<()> {
@@ -240,3 +311,24 @@ pub fn find_daemon_processes() -> Result<Vec<u32>> {
         Ok(Vec::new())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn socket_left_by_a_dead_daemon_is_reclaimed() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let socket_path = dir.path().join("voicevox-daemon.sock");
+        drop(std::os::unix::net::UnixListener::bind(&socket_path).expect("bind"));
+        let mut exited = process::Command::new("true").spawn().expect("spawn");
+        let dead_pid = exited.id();
+        exited.wait().expect("wait");
+        fs::write(daemon_pid_path(&socket_path), format!("{dead_pid}\n")).expect("pid file");
+
+        reclaim_stale_socket(&socket_path).await.expect("reclaim");
+        assert!(!socket_path.exists());
+        assert!(!daemon_pid_path(&socket_path).exists());
+        std::os::unix::net::UnixListener::bind(&socket_path).expect("rebind");
+    }
+}
@@ -0,0 +1,177 @@
+use anyhow::{Context, Result, anyhow, ensure};
+use std::time::{Duration, Instant};
+
+use crate::domain::synthesis::wav::parse_wav_header;
+use crate::infrastructure::core::{CoreSynthesis, VoicevoxCore};
+use crate::infrastructure::ipc::SynthesisOptions;
+use crate::infrastructure::voicevox::{AvailableModel, scan_available_models};
+
+use super::DaemonError;
+
+/// Phrase synthesized by the self-test; short enough to finish quickly on any CPU.
+pub const SELF_TEST_TEXT: &str = "テストです。";
+
+/// Longest accepted synthesis time for the self-test phrase.
+pub const SELF_TEST_SYNTHESIS_BUDGET: Duration = Duration::from_secs(30);
+
+const MIN_SPOKEN_DURATION: Duration = Duration::from_millis(200);
+const MAX_SPOKEN_DURATION: Duration = Duration::from_secs(10);
+
+/// One completed self-test step and how long it took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelfTestStep {
+    pub name: &'static str,
+    pub detail: String,
+    pub elapsed: Duration,
+}
+
+/// Checks that synthesized audio is well-formed 16-bit PCM of a plausible length.
+///
+/// # Errors
+///
+/// Returns an error describing the first header or duration check that fails.
+pub fn verify_self_test_wav(wav_data: &[u8]) -> Result<Duration> {
+    let header = parse_wav_header(wav_data).context("Synthesized audio is not a valid WAV")?;
+    ensure!(
+        header.bits_per_sample == 16,
+        "Expected 16-bit PCM, got {} bits",
+        header.bits_per_sample
+    );
+    ensure!(
+        (1..=2).contains(&header.channels),
+        "Unexpected channel count: {}",
+        header.channels
+    );
+    ensure!(
+        (8_000..=96_000).contains(&header.sample_rate),
+        "Unexpected sample rate: {} Hz",
+        header.sample_rate
+    );
+    ensure!(header.data_size > 0, "Synthesized audio has no samples");
+
+    let frame_size = usize::from(header.channels) * 2;
+    let frames = header.data_size / frame_size;
+    let duration = Duration::from_secs_f64(frames as f64 / f64::from(header.sample_rate));
+    ensure!(
+        (MIN_SPOKEN_DURATION..=MAX_SPOKEN_DURATION).contains(&duration),
+        "Implausible audio length for the test phrase: {:.2}s",
+        duration.as_secs_f64()
+    );
+    Ok(duration)
+}
+
+fn smallest_model(models: Vec<AvailableModel>) -> Option<AvailableModel> {
+    models.into_iter().min_by_key(|model| {
+        std::fs::metadata(&model.file_path).map_or(u64::MAX, |metadata| metadata.len())
+    })
+}
+
+fn timed<T>(
+    name: &'static str,
+    on_step: &mut dyn FnMut(&SelfTestStep),
+    run: impl FnOnce() -> Result<(T, String), DaemonError>,
+) -> Result<T, DaemonError> {
+    let started = Instant::now();
+    let (value, detail) = run()?;
+    on_step(&SelfTestStep {
+        name,
+        detail,
+        elapsed: started.elapsed(),
+    });
+    Ok(value)
+}
+
+/// Exercises the full synthesis path once without starting the socket server.
+///
+/// Initializes Core, loads the smallest installed model, synthesizes
+/// [`SELF_TEST_TEXT`] into memory, and verifies the resulting WAV. `on_step` is
+/// called after each step succeeds so callers can report progress.
+///
+/// # Errors
+///
+/// Returns [`DaemonError::NoModelsAvailable`] when no models are installed, and
+/// [`DaemonError::Other`] when any other step fails.
+pub fn run_self_test(mut on_step: impl FnMut(&SelfTestStep)) -> Result<(), DaemonError> {
+    let on_step: &mut dyn FnMut(&SelfTestStep) = &mut on_step;
+
+    let core = timed("core", on_step, || {
+        let core = VoicevoxCore::new().context("Failed to initialize VOICEVOX Core")?;
+        Ok((core, "initialized".to_string()))
+    })?;
+
+    let model = timed("model", on_step, || {
+        let model =
+            smallest_model(scan_available_models()?).ok_or(DaemonError::NoModelsAvailable)?;
+        core.load_specific_model(model.model_id)?;
+        let detail = format!("loaded {}", model.file_path.display());
+        Ok((model, detail))
+    })?;
+
+    if let Err(error) = synthesize_and_verify(&core, on_step) {
+        let _ = core.unload_voice_model_by_path(&model.file_path);
+        return Err(error);
+    }
+    timed("unload", on_step, || {
+        core.unload_voice_model_by_path(&model.file_path)?;
+        Ok(((), format!("model {}", model.model_id)))
+    })
+}
+
+fn synthesize_and_verify(
+    core: &VoicevoxCore,
+    on_step: &mut dyn FnMut(&SelfTestStep),
+) -> Result<(), DaemonError> {
+    let style_id = core
+        .get_speakers()?
+        .iter()
+        .flat_map(|speaker| speaker.styles.iter())
+        .map(|style| style.id)
+        .next()
+        .ok_or_else(|| anyhow!("Loaded model exposes no styles"))?;
+
+    let wav_data = timed("synthesis", on_step, || {
+        let started = Instant::now();
+        let wav_data =
+            core.synthesize_with_options(SELF_TEST_TEXT, style_id, SynthesisOptions::default())?;
+        let elapsed = started.elapsed();
+        if elapsed > SELF_TEST_SYNTHESIS_BUDGET {
+            return Err(anyhow!(
+                "Synthesis took {:.1}s (budget {}s)",
+                elapsed.as_secs_f64(),
+                SELF_TEST_SYNTHESIS_BUDGET.as_secs()
+            )
+            .into());
+        }
+        Ok((wav_data, format!("style {style_id}")))
+    })?;
+
+    timed("wav", on_step, || {
+        let duration = verify_self_test_wav(&wav_data)?;
+        Ok((
+            (),
+            format!(
+                "{} bytes, {:.2}s of audio",
+                wav_data.len(),
+                duration.as_secs_f64()
+            ),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::synthesis::wav::make_wav;
+
+    #[test]
+    fn wav_check_accepts_speech_and_rejects_odd_formats() {
+        let one_second = vec![0u8; 24_000 * 2];
+        let duration =
+            verify_self_test_wav(&make_wav(&one_second, 1, 24_000, 16)).expect("valid speech");
+        assert_eq!(duration, Duration::from_secs(1));
+
+        assert!(verify_self_test_wav(&make_wav(&one_second, 1, 24_000, 8)).is_err());
+        assert!(verify_self_test_wav(&make_wav(&[0u8; 16], 1, 24_000, 16)).is_err());
+        assert!(verify_self_test_wav(b"not a wav").is_err());
+    }
+}
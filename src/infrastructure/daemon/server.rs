@@ -1,24 +1,44 @@
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 use futures_util::{SinkExt, StreamExt};
+use std::net::SocketAddr;
 use std::os::unix::fs::{DirBuilderExt, FileTypeExt, MetadataExt, PermissionsExt};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::net::{UnixListener, UnixStream};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpListener, UnixListener, UnixStream};
 use tokio::signal;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tokio::time::timeout;
 use tokio_util::codec::{FramedRead, FramedWrite, LengthDelimitedCodec};
 
-use crate::infrastructure::daemon::state::DaemonState;
+use crate::infrastructure::daemon::config::DaemonConfig;
+use crate::infrastructure::daemon::process::{
+    daemon_pid_path, reclaim_stale_socket, write_daemon_pid_file,
+};
+use crate::infrastructure::daemon::shared_socket::SharedSocket;
+use crate::infrastructure::daemon::state::{DaemonState, LifecycleRequest};
+use crate::infrastructure::daemon::token::{
+    daemon_token_path, load_or_create_daemon_token, tokens_match,
+};
 use crate::infrastructure::ipc::{
-    DaemonRequest, MAX_DAEMON_REQUEST_FRAME_BYTES, MAX_DAEMON_RESPONSE_FRAME_BYTES, OwnedResponse,
+    DaemonErrorCode, DaemonEvent, DaemonRequest, IPC_PROTOCOL_VERSION,
+    MAX_DAEMON_REQUEST_FRAME_BYTES, MAX_DAEMON_RESPONSE_FRAME_BYTES, OwnedResponse,
+    protocol_mismatch_message,
+};
+use crate::infrastructure::model_mirror::{local_models_dir, mirror_models};
+use crate::infrastructure::paths::{
+    find_configured_models_dir, models_dir_override, set_models_dir_override,
 };
 
 const SOCKET_DIR_MODE: u32 = 0o700;
 const SOCKET_FILE_MODE: u32 = 0o600;
 const MAX_CONCURRENT_CLIENTS: usize = 32;
 const CLIENT_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+/// How long shutdown waits for running requests before exiting anyway.
+const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
 
 struct SocketFileGuard {
     path: Option<PathBuf>,
@@ -55,6 +75,7 @@ fn remove_socket_if_exists(socket_path: &Path) -> Result<()> {
                 ));
             }
             std::fs::remove_file(socket_path)?;
+            let _ = std::fs::remove_file(daemon_pid_path(socket_path));
             Ok(())
         }
         Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
@@ -104,6 +125,7 @@ pub async fn handle_client(stream: UnixStream, state: Arc<DaemonState>) -> Resul
         stream,
         state,
         Arc::new(Semaphore::new(MAX_CONCURRENT_CLIENTS)),
+        false,
     )
     .await
 }
@@ -112,20 +134,98 @@ async fn acquire_request_permit(permits: Arc<Semaphore>) -> Option<OwnedSemaphor
     permits.acquire_owned().await.ok()
 }
 
+/// With `log_callers`, every request is logged with the connecting process's UID,
+/// so the owner of a shared daemon can see who used it.
 async fn handle_client_with_limit(
     stream: UnixStream,
     state: Arc<DaemonState>,
     permits: Arc<Semaphore>,
+    log_callers: bool,
 ) -> Result<()> {
+    let peer_uid = stream.peer_cred().ok().map(|cred| cred.uid());
+    let caller = log_callers.then(|| {
+        format!(
+            "uid {}",
+            peer_uid.map_or_else(|| "unknown".to_string(), |uid| uid.to_string())
+        )
+    });
+    let (reader, writer) = stream.into_split();
+    let (framed_read, framed_write) = frame_connection(reader, writer);
+    serve_connection(
+        framed_read,
+        framed_write,
+        state,
+        permits,
+        caller,
+        is_daemon_owner(peer_uid),
+    )
+    .await
+}
+
+/// Whether a Unix peer runs as the same user as the daemon.
+fn is_daemon_owner(peer_uid: Option<u32>) -> bool {
+    peer_uid == Some(current_uid())
+}
+
+/// Requests that stop the daemon or change what it has loaded, which only its owner
+/// may send: before they existed, only the owner could signal the daemon.
+fn is_owner_only(request: &DaemonRequest) -> bool {
+    matches!(
+        request,
+        DaemonRequest::Shutdown | DaemonRequest::Reload | DaemonRequest::PreloadModels { .. }
+    )
+}
+
+/// The error sent instead of running an owner-only request for anyone else.
+fn owner_only_refusal(request: &DaemonRequest) -> OwnedResponse {
+    OwnedResponse::Error {
+        code: DaemonErrorCode::Internal,
+        message: format!(
+            "{} is only accepted from the daemon's owner over its Unix socket",
+            request.name()
+        ),
+    }
+}
+
+fn frame_connection<R, W>(
+    reader: R,
+    writer: W,
+) -> (
+    FramedRead<R, LengthDelimitedCodec>,
+    FramedWrite<W, LengthDelimitedCodec>,
+)
+where
+    R: AsyncRead,
+    W: AsyncWrite,
+{
     let request_codec = LengthDelimitedCodec::builder()
         .max_frame_length(MAX_DAEMON_REQUEST_FRAME_BYTES)
         .new_codec();
     let response_codec = LengthDelimitedCodec::builder()
         .max_frame_length(MAX_DAEMON_RESPONSE_FRAME_BYTES)
         .new_codec();
-    let (reader, writer) = stream.into_split();
-    let mut framed_read = FramedRead::new(reader, request_codec);
-    let mut framed_write = FramedWrite::new(writer, response_codec);
+    (
+        FramedRead::new(reader, request_codec),
+        FramedWrite::new(writer, response_codec),
+    )
+}
+
+/// Serves requests on one framed connection until it closes or decoding fails.
+/// Requests are logged with `caller` when it is set. Unless the peer is the
+/// daemon's `owner`, owner-only requests are answered with an error.
+async fn serve_connection<R, W>(
+    mut framed_read: FramedRead<R, LengthDelimitedCodec>,
+    mut framed_write: FramedWrite<W, LengthDelimitedCodec>,
+    state: Arc<DaemonState>,
+    permits: Arc<Semaphore>,
+    caller: Option<String>,
+    owner: bool,
+) -> Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut app_id: Option<String> = None;
 
     while let Some(frame) = timeout(CLIENT_IDLE_TIMEOUT, framed_read.next())
         .await
@@ -142,15 +242,74 @@ async fn handle_client_with_limit(
         let Some(request) = decode_request_or_log(&data) else {
             break;
         };
+        if let Some(caller) = &caller {
+            crate::infrastructure::logging::info(&format!("{caller}: {}", request.name()));
+        }
+
+        if is_owner_only(&request) && !owner {
+            let Some(refusal) = encode_response_or_log(&owner_only_refusal(&request)) else {
+                break;
+            };
+            framed_write.send(refusal.into()).await?;
+            continue;
+        }
+
+        if request == DaemonRequest::Subscribe {
+            let events = state.subscribe_events();
+            let Some(ack) = encode_response_or_log(&OwnedResponse::Subscribed) else {
+                break;
+            };
+            framed_write.send(ack.into()).await?;
+            stream_events(framed_read, framed_write, events).await;
+            break;
+        }
+
+        // Answered before shutting down, since the daemon may exit right after.
+        if request == DaemonRequest::Shutdown {
+            if let Some(ack) = encode_response_or_log(&OwnedResponse::ShuttingDown) {
+                framed_write.send(ack.into()).await?;
+            }
+            state.request_shutdown();
+            break;
+        }
 
         // `DaemonRequestHandling.tla` models permit admission per request, not per
         // connection. Acquire/release around request handling to keep that contract.
-        let Some(_permit) = acquire_request_permit(Arc::clone(&permits)).await else {
+        let Some(permit) = acquire_request_permit(Arc::clone(&permits)).await else {
             log_client_error("Permit semaphore closed", &"request limiter unavailable");
             break;
         };
 
-        let response = state.handle_request(request).await;
+        if let DaemonRequest::Hello { app_id: id, .. } = &request {
+            app_id = Some(id.clone()).filter(|id| !id.is_empty());
+        }
+        // The request runs as its own task so the connection keeps reading while it
+        // works; the task owns the permit, which is released only once it really ends.
+        let mut request_task = tokio::spawn({
+            let state = Arc::clone(&state);
+            let app_id = app_id.clone();
+            async move {
+                let _permit = permit;
+                state.handle_request(request, app_id.as_deref()).await
+            }
+        });
+        let response = tokio::select! {
+            joined = &mut request_task => match joined {
+                Ok(response) => response,
+                Err(error) => {
+                    log_client_error("Request task failed", &error);
+                    break;
+                }
+            },
+            interruption = wait_for_interruption(&mut framed_read) => {
+                request_task.abort();
+                match interruption {
+                    Interruption::Cancelled => OwnedResponse::Cancelled,
+                    // Nobody is left to read the response.
+                    Interruption::Disconnected => break,
+                }
+            }
+        };
         let Some(response_data) = encode_response_or_log(&response) else {
             break;
         };
@@ -164,26 +323,350 @@ async fn handle_client_with_limit(
     Ok(())
 }
 
+/// Why a running request was abandoned before it produced a response.
+#[derive(Debug, PartialEq, Eq)]
+enum Interruption {
+    Cancelled,
+    Disconnected,
+}
+
+/// Resolves when the client sends `Cancel` or goes away while its request is running.
+///
+/// Clients wait for each response before sending the next request, so any other
+/// frame is a protocol violation and is dropped. Aborting the request task drops
+/// it from the synthesis queue; a synthesis already inside Core runs to the end,
+/// since Core calls are blocking, and its result is discarded.
+async fn wait_for_interruption<R: AsyncRead + Unpin>(
+    framed_read: &mut FramedRead<R, LengthDelimitedCodec>,
+) -> Interruption {
+    loop {
+        match framed_read.next().await {
+            Some(Ok(data)) => match decode_request_or_log(&data) {
+                Some(DaemonRequest::Cancel) => return Interruption::Cancelled,
+                Some(_) => log_client_error(
+                    "Ignoring request sent before the previous response",
+                    &"request pipelining is not supported",
+                ),
+                None => {}
+            },
+            Some(Err(_)) | None => return Interruption::Disconnected,
+        }
+    }
+}
+
+/// Pushes events to a subscribed client until it disconnects or a write fails.
+///
+/// Subscriptions are long-lived, so they bypass the idle timeout and do not hold a
+/// request permit.
+async fn stream_events<R, W>(
+    mut framed_read: FramedRead<R, LengthDelimitedCodec>,
+    mut framed_write: FramedWrite<W, LengthDelimitedCodec>,
+    mut events: broadcast::Receiver<DaemonEvent>,
+) where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    loop {
+        let event = tokio::select! {
+            received = events.recv() => match received {
+                Ok(event) => event,
+                Err(RecvError::Lagged(missed)) => DaemonEvent::Lagged { missed },
+                Err(RecvError::Closed) => return,
+            },
+            // Subscribers send nothing further; any frame or EOF ends the subscription.
+            _ = framed_read.next() => return,
+        };
+        let Some(frame) = encode_response_or_log(&OwnedResponse::Event { event }) else {
+            return;
+        };
+        if let Err(error) = framed_write.send(frame.into()).await {
+            log_client_error("Subscriber write error", &error);
+            return;
+        }
+    }
+}
+
 async fn wait_for_shutdown_signal() -> Result<()> {
     signal::ctrl_c().await?;
     crate::infrastructure::logging::info("\nShutting down daemon...");
     Ok(())
 }
 
-async fn accept_loop(listener: &UnixListener, state: Arc<DaemonState>) -> Result<()> {
+/// Loads `daemon.toml`, switching to low-power mode when `low_power` asks for it
+/// even if the file does not.
+fn load_config(config_path: &Path, low_power: bool) -> Result<DaemonConfig> {
+    let mut config = super::config::load_daemon_config(config_path)?;
+    config.low_power |= low_power;
+    config.apply_low_power();
+    Ok(config)
+}
+
+/// Brings the local copy of the models directory up to date and returns its path.
+fn refresh_local_models() -> Result<PathBuf> {
+    let source = find_configured_models_dir()?;
+    let target = local_models_dir();
+    let summary = mirror_models(&source, &target)?;
+    crate::infrastructure::logging::info(&format!(
+        "Copied {} model(s) from {} to {} ({} unchanged, {} removed)",
+        summary.copied,
+        source.display(),
+        target.display(),
+        summary.unchanged,
+        summary.removed
+    ));
+    Ok(target)
+}
+
+/// Re-reads `daemon.toml` and rescans the models directory, applying both to new
+/// requests, and returns the number of models found.
+///
+/// The listening socket, open connections, and running requests are untouched. An
+/// invalid file or failed rescan is reported and the previous state stays in force.
+/// When models are loaded from a local copy, the copy is refreshed first. The copy
+/// and rescan run on a blocking thread so the accept loops, which share this task's
+/// `select!`, keep taking clients meanwhile.
+async fn reload_daemon(
+    config_path: &Path,
+    state: &Arc<DaemonState>,
+    low_power: bool,
+) -> Result<usize> {
+    let applied = load_config(config_path, low_power).map(|config| {
+        let summary = config.summary(config_path);
+        state.reload_config(config);
+        crate::infrastructure::logging::info(&format!("Reloaded {summary}"));
+    });
+    if let Err(error) = &applied {
+        crate::infrastructure::logging::error(&format!(
+            "Reload failed, keeping previous settings: {error:#}"
+        ));
+    }
+    let rescan_state = Arc::clone(state);
+    let rescanned = tokio::task::spawn_blocking(move || {
+        if models_dir_override().is_some()
+            && let Err(error) = refresh_local_models()
+        {
+            crate::infrastructure::logging::error(&format!(
+                "Model copy failed, keeping the previous local copy: {error:#}"
+            ));
+        }
+        crate::infrastructure::user_dict::bump_user_dict_generation();
+        rescan_state.reload_models()
+    })
+    .await
+    .unwrap_or_else(|error| Err(error.into()));
+    match &rescanned {
+        Ok(model_count) => crate::infrastructure::logging::info(&format!(
+            "Rescanned models directory: {model_count} model(s) available"
+        )),
+        Err(error) => crate::infrastructure::logging::error(&format!(
+            "Model rescan failed, keeping previous models: {error:#}"
+        )),
+    }
+    applied.context("Kept the previous settings")?;
+    rescanned.context("Kept the previous models")
+}
+
+/// Reloads on every SIGHUP or `Reload` request until a `Shutdown` request arrives.
+async fn handle_lifecycle(
+    config_path: &Path,
+    state: &Arc<DaemonState>,
+    low_power: bool,
+    mut requests: UnboundedReceiver<LifecycleRequest>,
+) -> Result<()> {
+    let mut hangups = signal::unix::signal(signal::unix::SignalKind::hangup())?;
+    loop {
+        tokio::select! {
+            hangup = hangups.recv() => {
+                if hangup.is_none() {
+                    return Ok(());
+                }
+                let _ = reload_daemon(config_path, state, low_power).await;
+            }
+            request = requests.recv() => match request {
+                Some(LifecycleRequest::Reload(reply)) => {
+                    let reloaded = reload_daemon(config_path, state, low_power)
+                        .await
+                        .map_err(|error| format!("{error:#}"));
+                    let _ = reply.send(reloaded);
+                }
+                Some(LifecycleRequest::Shutdown) | None => {
+                    crate::infrastructure::logging::info("Shutdown requested");
+                    return Ok(());
+                }
+            },
+        }
+    }
+}
+
+/// Binds the daemon socket. A socket file left by a crashed daemon is removed and
+/// bound again; one still in use by a live daemon is an error.
+///
+/// The returned guard removes the socket and PID file, so it only exists once the
+/// bind has succeeded; a refused bind leaves the live daemon's files alone.
+async fn bind_socket(socket_path: &Path) -> Result<(UnixListener, SocketFileGuard)> {
+    let listener = match UnixListener::bind(socket_path) {
+        Err(error) if error.kind() == std::io::ErrorKind::AddrInUse => {
+            reclaim_stale_socket(socket_path)
+                .await
+                .with_context(|| format!("Socket already in use: {}", socket_path.display()))?;
+            UnixListener::bind(socket_path)?
+        }
+        bound => bound?,
+    };
+    let guard = SocketFileGuard::new(socket_path.to_path_buf());
+    write_daemon_pid_file(socket_path)
+        .with_context(|| format!("Failed to write {}", daemon_pid_path(socket_path).display()))?;
+    Ok((listener, guard))
+}
+
+async fn accept_loop(
+    listener: &UnixListener,
+    state: Arc<DaemonState>,
+    log_callers: bool,
+) -> Result<()> {
     let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_CLIENTS));
     loop {
         let (stream, _) = listener.accept().await?;
         let state_clone = Arc::clone(&state);
         let permits_clone = Arc::clone(&permits);
         tokio::spawn(async move {
-            if let Err(error) = handle_client_with_limit(stream, state_clone, permits_clone).await {
+            if let Err(error) =
+                handle_client_with_limit(stream, state_clone, permits_clone, log_callers).await
+            {
                 log_client_error("Client handler error", &error);
             }
         });
     }
 }
 
+/// Accepts LAN clients on `listener`. Each connection must open with an
+/// `Authenticate` frame carrying `token`; requests are logged with the peer address.
+async fn accept_tcp_loop(
+    listener: TcpListener,
+    state: Arc<DaemonState>,
+    token: String,
+) -> Result<()> {
+    let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_CLIENTS));
+    let token = Arc::new(token);
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let _ = stream.set_nodelay(true);
+        let state_clone = Arc::clone(&state);
+        let permits_clone = Arc::clone(&permits);
+        let token_clone = Arc::clone(&token);
+        tokio::spawn(async move {
+            let (reader, writer) = stream.into_split();
+            let (mut framed_read, mut framed_write) = frame_connection(reader, writer);
+            match authenticate(&mut framed_read, &mut framed_write, &token_clone).await {
+                Ok(()) => {
+                    if let Err(error) = serve_connection(
+                        framed_read,
+                        framed_write,
+                        state_clone,
+                        permits_clone,
+                        Some(peer.to_string()),
+                        false,
+                    )
+                    .await
+                    {
+                        log_client_error("Client handler error", &error);
+                    }
+                }
+                Err(error) => log_client_error(&format!("Rejected {peer}"), &error),
+            }
+        });
+    }
+}
+
+/// Reads the first frame of a TCP connection and answers `Authenticated` if it is
+/// `Authenticate` with the daemon token and protocol revision, or an error response
+/// otherwise.
+async fn authenticate<R, W>(
+    framed_read: &mut FramedRead<R, LengthDelimitedCodec>,
+    framed_write: &mut FramedWrite<W, LengthDelimitedCodec>,
+    token: &str,
+) -> Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let frame = timeout(CLIENT_IDLE_TIMEOUT, framed_read.next())
+        .await
+        .map_err(|_| anyhow!("no Authenticate frame before the idle timeout"))?
+        .ok_or_else(|| anyhow!("connection closed before authenticating"))??;
+    let response = match decode_request_frame(&frame) {
+        Ok(DaemonRequest::Authenticate {
+            protocol_version, ..
+        }) if protocol_version != IPC_PROTOCOL_VERSION => OwnedResponse::Error {
+            code: DaemonErrorCode::ProtocolMismatch,
+            message: protocol_mismatch_message(protocol_version),
+        },
+        Ok(DaemonRequest::Authenticate { token: given, .. }) if tokens_match(token, &given) => {
+            OwnedResponse::Authenticated
+        }
+        _ => OwnedResponse::Error {
+            code: DaemonErrorCode::Internal,
+            message: "Invalid daemon token".to_string(),
+        },
+    };
+    let accepted = response == OwnedResponse::Authenticated;
+    framed_write
+        .send(encode_response_frame(&response)?.into())
+        .await?;
+    if accepted {
+        Ok(())
+    } else {
+        Err(anyhow!("invalid or missing daemon token"))
+    }
+}
+
+/// Binds the TCP listener for `--listen`, loading (or creating) the daemon token.
+async fn bind_tcp_listener(address: SocketAddr) -> Result<(TcpListener, String)> {
+    let token_path = daemon_token_path();
+    let token = load_or_create_daemon_token(&token_path)?;
+    let listener = TcpListener::bind(address)
+        .await
+        .with_context(|| format!("Failed to listen on {address}"))?;
+    crate::infrastructure::logging::info(&format!(
+        "Listening on: {address} (clients need the token in {})",
+        token_path.display()
+    ));
+    if !address.ip().is_loopback() {
+        crate::infrastructure::logging::warn(
+            "TCP traffic is not encrypted; listen only on networks you trust",
+        );
+    }
+    Ok((listener, token))
+}
+
+async fn serve_tcp_if_requested(
+    listener: Option<(TcpListener, String)>,
+    state: Arc<DaemonState>,
+) -> Result<()> {
+    match listener {
+        Some((listener, token)) => accept_tcp_loop(listener, state, token).await,
+        None => std::future::pending().await,
+    }
+}
+
+async fn serve_http_if_requested(
+    http_address: Option<SocketAddr>,
+    state: Arc<DaemonState>,
+) -> Result<()> {
+    match http_address {
+        #[cfg(feature = "http")]
+        Some(address) => super::http::serve_http(address, state).await,
+        #[cfg(not(feature = "http"))]
+        Some(_) => {
+            drop(state);
+            Err(anyhow!(
+                "HTTP mode is unavailable: voicevox-daemon was built without the `http` feature"
+            ))
+        }
+        None => std::future::pending().await,
+    }
+}
+
 fn ensure_socket_parent_dir(socket_path: &Path) -> Result<()> {
     if let Some(parent_dir) = socket_path.parent() {
         if !parent_dir.exists() {
@@ -239,6 +722,41 @@ fn set_socket_permissions(socket_path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Warms the model cache once clients can connect: the most used models first, then
+/// the ones named with `--preload`, so those are the last to be evicted.
+async fn preload_models(state: Arc<DaemonState>, requested: Vec<u32>) {
+    let loaded = state.preload_frequent_models().await;
+    if !loaded.is_empty() {
+        crate::infrastructure::logging::info(&format!("Preloaded models {loaded:?}"));
+    }
+    if requested.is_empty() {
+        return;
+    }
+    let request = DaemonRequest::PreloadModels {
+        model_ids: requested.clone(),
+    };
+    match state.handle_request(request, None).await {
+        OwnedResponse::ModelsPreloaded { elapsed_ms, .. } => {
+            crate::infrastructure::logging::info(&format!(
+                "Preloaded models {requested:?} in {elapsed_ms}ms"
+            ));
+        }
+        OwnedResponse::Error { message, .. } => {
+            crate::infrastructure::logging::warn(&format!("Failed to preload models: {message}"));
+        }
+        _ => {}
+    }
+}
+
+/// Network listeners in addition to the Unix socket.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NetworkListeners {
+    /// Loopback address for the HTTP API (`--http`).
+    pub http: Option<SocketAddr>,
+    /// Address for token-authenticated daemon protocol clients (`--listen`).
+    pub tcp: Option<SocketAddr>,
+}
+
 /// Runs the daemon accept loop and serves requests over a Unix domain socket.
 ///
 /// # Errors
@@ -250,27 +768,83 @@ fn set_socket_permissions(socket_path: &Path) -> Result<()> {
 /// the socket, ensuring the daemon is fully ready before clients can connect.
 /// This matches the TLA+ `ConnectedImpliesReady` invariant.
 ///
-/// Stale socket removal is handled by `check_and_prevent_duplicate` before
-/// this function is called. The `bind` call is the atomic safety gate:
-/// if the socket already exists (another daemon bound it), bind fails
-/// with `EADDRINUSE`, matching the TLA+ model's atomic `BindSocket`.
-pub async fn run_daemon(socket_path: PathBuf, foreground: bool) -> Result<()> {
-    ensure_socket_parent_dir(&socket_path)?;
-
-    let state = Arc::new(DaemonState::new()?);
-
-    let socket_guard = SocketFileGuard::new(socket_path.clone());
-    let listener = UnixListener::bind(&socket_path).map_err(|e| {
-        if e.kind() == std::io::ErrorKind::AddrInUse {
-            anyhow!(
-                "Socket already in use: {}. Another daemon may be running.",
-                socket_path.display()
+/// The `bind` call is the atomic safety gate, matching the TLA+ model's
+/// atomic `BindSocket`: if it fails with `EADDRINUSE`, the socket is removed
+/// and bound again only when no daemon answers on it. The socket and PID file
+/// are cleaned up on exit only after this daemon has bound the socket itself.
+///
+/// When `listeners.http` is set, the same state is also served over loopback HTTP;
+/// with `listeners.tcp`, remote clients that present the daemon token can use it too.
+/// With `shared`, the socket directory and socket belong to that group instead of
+/// being private to the current user. `preload` models are loaded in the background
+/// once the socket accepts connections, along with `preload_models` from
+/// `daemon.toml`, whose `[logging]` section is applied first. With `copy_models_local` (or
+/// `model_cache.copy_local`), models are copied to the local cache and loaded from
+/// there; if the copy fails they are loaded from the configured directory. With
+/// `low_power` (or `low_power` in `daemon.toml`), see [`DaemonConfig::apply_low_power`].
+/// Ctrl+C or a `Shutdown` request removes the socket, then waits for running
+/// requests before returning.
+pub async fn run_daemon(
+    socket_path: PathBuf,
+    foreground: bool,
+    listeners: NetworkListeners,
+    shared: Option<SharedSocket>,
+    mut preload: Vec<u32>,
+    copy_models_local: bool,
+    low_power: bool,
+) -> Result<()> {
+    match (shared, socket_path.parent()) {
+        (Some(shared), Some(parent_dir)) => shared.prepare_dir(parent_dir)?,
+        _ => ensure_socket_parent_dir(&socket_path)?,
+    }
+
+    let config_path = super::config::daemon_config_path();
+    let config = load_config(&config_path, low_power)?;
+    crate::infrastructure::logging::configure(config.logging.level, config.logging.file.as_deref())
+        .with_context(|| {
+            format!(
+                "Failed to open the log file set in {}",
+                config_path.display()
             )
-        } else {
-            e.into()
+        })?;
+    for model in &config.preload_models {
+        if !preload.contains(model) {
+            preload.push(*model);
         }
-    })?;
-    set_socket_permissions(&socket_path)?;
+    }
+    if config.low_power {
+        crate::infrastructure::logging::info(&format!(
+            "Low-power mode: {} CPU thread(s), models unloaded after each request, \
+             background work deferred on battery",
+            config.inference.cpu_threads
+        ));
+    }
+    if !config.apps.is_empty() || !config.policies.is_empty() {
+        crate::infrastructure::logging::info(&format!("Loaded {}", config.summary(&config_path)));
+    }
+    if copy_models_local || config.model_cache.copy_local {
+        match refresh_local_models() {
+            Ok(local_dir) => set_models_dir_override(Some(local_dir)),
+            Err(error) => crate::infrastructure::logging::warn(&format!(
+                "Failed to copy models locally, loading them in place: {error:#}"
+            )),
+        }
+    }
+    let state = Arc::new(DaemonState::new(config)?);
+    let lifecycle_requests = state
+        .take_lifecycle_requests()
+        .context("Daemon lifecycle requests were already taken")?;
+
+    let tcp_listener = match listeners.tcp {
+        Some(address) => Some(bind_tcp_listener(address).await?),
+        None => None,
+    };
+
+    let (listener, socket_guard) = bind_socket(&socket_path).await?;
+    match shared {
+        Some(shared) => shared.apply_to_socket(&socket_path)?,
+        None => set_socket_permissions(&socket_path)?,
+    }
     crate::infrastructure::logging::info("VOICEVOX daemon started successfully");
     crate::infrastructure::logging::info(&format!("Listening on: {}", socket_path.display()));
 
@@ -280,13 +854,140 @@ pub async fn run_daemon(socket_path: PathBuf, foreground: bool) -> Result<()> {
         );
     }
 
+    tokio::spawn(preload_models(Arc::clone(&state), preload));
+
     tokio::select! {
-        result = accept_loop(&listener, Arc::clone(&state)) => result?,
+        result = accept_loop(&listener, Arc::clone(&state), shared.is_some()) => result?,
+        result = serve_http_if_requested(listeners.http, Arc::clone(&state)) => result?,
+        result = serve_tcp_if_requested(tcp_listener, Arc::clone(&state)) => result?,
         result = wait_for_shutdown_signal() => result?,
+        result = handle_lifecycle(&config_path, &state, low_power, lifecycle_requests) => result?,
     }
 
+    // New clients find no daemon (and may start another) while running requests finish.
+    drop(listener);
     socket_guard.cleanup_now()?;
+    if timeout(SHUTDOWN_DRAIN_TIMEOUT, state.wait_until_idle())
+        .await
+        .is_err()
+    {
+        crate::infrastructure::logging::warn(&format!(
+            "Stopping with requests still running after {}s",
+            SHUTDOWN_DRAIN_TIMEOUT.as_secs()
+        ));
+    }
 
     crate::infrastructure::logging::info("VOICEVOX daemon stopped");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::unix::OwnedReadHalf;
+
+    fn request_reader(stream: UnixStream) -> FramedRead<OwnedReadHalf, LengthDelimitedCodec> {
+        FramedRead::new(stream.into_split().0, LengthDelimitedCodec::new())
+    }
+
+    #[tokio::test]
+    async fn running_requests_are_interrupted_by_cancel_or_disconnect() {
+        let (client, server) = UnixStream::pair().expect("socket pair");
+        let mut framed_read = request_reader(server);
+        let mut client = FramedWrite::new(client, LengthDelimitedCodec::new());
+        let cancel = postcard::to_allocvec(&DaemonRequest::Cancel).expect("encode");
+        client.send(cancel.into()).await.expect("send cancel");
+        assert_eq!(
+            wait_for_interruption(&mut framed_read).await,
+            Interruption::Cancelled
+        );
+
+        drop(client);
+        assert_eq!(
+            wait_for_interruption(&mut framed_read).await,
+            Interruption::Disconnected
+        );
+    }
+
+    #[tokio::test]
+    async fn a_refused_bind_leaves_the_live_daemons_files_in_place() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let socket_path = dir.path().join("voicevox-daemon.sock");
+        let _live = UnixListener::bind(&socket_path).expect("bind live socket");
+        std::fs::write(daemon_pid_path(&socket_path), "4242\n").expect("write pid file");
+
+        assert!(bind_socket(&socket_path).await.is_err());
+
+        assert!(socket_path.exists());
+        assert_eq!(
+            std::fs::read_to_string(daemon_pid_path(&socket_path)).expect("pid file"),
+            "4242\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn tcp_clients_must_present_the_daemon_token() {
+        async fn attempt(protocol_version: u32, token: &str) -> (bool, OwnedResponse) {
+            let (client, server) = UnixStream::pair().expect("socket pair");
+            let (reader, writer) = server.into_split();
+            let (mut framed_read, mut framed_write) = frame_connection(reader, writer);
+            let mut client = tokio_util::codec::Framed::new(client, LengthDelimitedCodec::new());
+            let request = DaemonRequest::Authenticate {
+                protocol_version,
+                token: token.to_string(),
+            };
+            client
+                .send(postcard::to_allocvec(&request).expect("encode").into())
+                .await
+                .expect("send");
+            let accepted = authenticate(&mut framed_read, &mut framed_write, "secret")
+                .await
+                .is_ok();
+            let reply = client.next().await.expect("reply").expect("frame");
+            (accepted, postcard::from_bytes(&reply).expect("decode"))
+        }
+
+        assert_eq!(
+            attempt(IPC_PROTOCOL_VERSION, "secret").await,
+            (true, OwnedResponse::Authenticated)
+        );
+        let (accepted, response) = attempt(IPC_PROTOCOL_VERSION, "guess").await;
+        assert!(!accepted);
+        assert!(matches!(
+            response,
+            OwnedResponse::Error {
+                code: DaemonErrorCode::Internal,
+                ..
+            }
+        ));
+        let (accepted, response) = attempt(IPC_PROTOCOL_VERSION + 1, "secret").await;
+        assert!(!accepted);
+        assert!(matches!(
+            response,
+            OwnedResponse::Error {
+                code: DaemonErrorCode::ProtocolMismatch,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn only_the_owner_may_stop_reload_or_preload() {
+        assert!(is_daemon_owner(Some(current_uid())));
+        assert!(!is_daemon_owner(Some(current_uid().wrapping_add(1))));
+        assert!(!is_daemon_owner(None));
+
+        for request in [
+            DaemonRequest::Shutdown,
+            DaemonRequest::Reload,
+            DaemonRequest::PreloadModels { model_ids: vec![3] },
+        ] {
+            assert!(is_owner_only(&request));
+            assert!(matches!(
+                owner_only_refusal(&request),
+                OwnedResponse::Error { message, .. } if message.starts_with(request.name())
+            ));
+        }
+        assert!(!is_owner_only(&DaemonRequest::ListSpeakers));
+    }
+}
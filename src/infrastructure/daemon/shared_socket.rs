@@ -0,0 +1,149 @@
+use anyhow::{Context, Result, anyhow, ensure};
+use std::ffi::CString;
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+use std::path::Path;
+
+/// Owner full access, group may enter; others are shut out.
+pub const SHARED_SOCKET_DIR_MODE: u32 = 0o750;
+/// Owner and group may connect.
+pub const SHARED_SOCKET_FILE_MODE: u32 = 0o660;
+
+/// Size of the scratch buffer `getgrnam_r` fills with member names.
+const GROUP_BUFFER_LEN: usize = 16 * 1024;
+
+/// Group whose members may use a daemon started with `--shared-socket`.
+///
+/// The daemon still runs as the user who started it; the group only gets
+/// permission to connect, and every request is logged with the caller's UID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SharedSocket {
+    pub gid: u32,
+}
+
+impl SharedSocket {
+    /// Looks up a group by name, or accepts a numeric GID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no such group exists.
+    pub fn for_group(name: &str) -> Result<Self> {
+        if let Some(gid) = lookup_group(name)? {
+            return Ok(Self { gid });
+        }
+        name.parse()
+            .map(|gid| Self { gid })
+            .map_err(|_| anyhow!("Unknown group '{name}'"))
+    }
+
+    /// Creates the socket directory if needed and hands it to the group.
+    ///
+    /// The directory must be a real directory owned by the daemon's user, so a
+    /// directory or symlink planted in the shared temp dir by someone else is
+    /// refused rather than adopted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created, is not owned by the
+    /// current user, or its group and mode cannot be set.
+    pub fn prepare_dir(self, dir: &Path) -> Result<()> {
+        if std::fs::symlink_metadata(dir).is_err() {
+            std::fs::DirBuilder::new()
+                .recursive(true)
+                .mode(SHARED_SOCKET_DIR_MODE)
+                .create(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let metadata = std::fs::symlink_metadata(dir)
+            .with_context(|| format!("Failed to inspect {}", dir.display()))?;
+        ensure!(
+            metadata.is_dir(),
+            "Shared socket path is not a directory: {}",
+            dir.display()
+        );
+        // SAFETY: `getuid` has no preconditions.
+        let uid = unsafe { libc::getuid() };
+        ensure!(
+            metadata.uid() == uid,
+            "Shared socket directory must be owned by the daemon user: {}",
+            dir.display()
+        );
+        self.hand_over(dir, SHARED_SOCKET_DIR_MODE)
+    }
+
+    /// Gives the bound socket to the group with mode `0660`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the group or mode cannot be set.
+    pub fn apply_to_socket(self, socket_path: &Path) -> Result<()> {
+        self.hand_over(socket_path, SHARED_SOCKET_FILE_MODE)
+    }
+
+    fn hand_over(self, path: &Path, mode: u32) -> Result<()> {
+        std::os::unix::fs::chown(path, None, Some(self.gid)).with_context(|| {
+            format!(
+                "Failed to give {} to group {} (is the daemon user a member?)",
+                path.display(),
+                self.gid
+            )
+        })?;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+            .with_context(|| format!("Failed to set permissions on {}", path.display()))
+    }
+}
+
+fn lookup_group(name: &str) -> Result<Option<u32>> {
+    let name = CString::new(name).context("Group name contains a NUL byte")?;
+    // SAFETY: `group` is plain old data, so the all-zero pattern is a valid value.
+    let mut group: libc::group = unsafe { std::mem::zeroed() };
+    let mut buffer = vec![0 as libc::c_char; GROUP_BUFFER_LEN];
+    let mut found: *mut libc::group = std::ptr::null_mut();
+    // SAFETY: every pointer refers to a live local, and `buffer.len()` is its real size.
+    let code = unsafe {
+        libc::getgrnam_r(
+            name.as_ptr(),
+            &raw mut group,
+            buffer.as_mut_ptr(),
+            buffer.len(),
+            &raw mut found,
+        )
+    };
+    ensure!(
+        code == 0,
+        "Group lookup failed: {}",
+        std::io::Error::from_raw_os_error(code)
+    );
+    Ok((!found.is_null()).then_some(group.gr_gid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prepares_group_readable_dir_and_rejects_unknown_groups() {
+        assert!(SharedSocket::for_group("voicevox-no-such-group").is_err());
+        assert_eq!(
+            SharedSocket::for_group("4242").expect("numeric gid").gid,
+            4242
+        );
+
+        // SAFETY: `getgid` has no preconditions.
+        let shared = SharedSocket {
+            gid: unsafe { libc::getgid() },
+        };
+        let root = tempfile::tempdir().expect("temp dir");
+        let dir = root.path().join("voicevox-shared");
+        shared.prepare_dir(&dir).expect("prepare");
+        let metadata = std::fs::metadata(&dir).expect("metadata");
+        assert_eq!(
+            metadata.permissions().mode() & 0o777,
+            SHARED_SOCKET_DIR_MODE
+        );
+        assert_eq!(metadata.gid(), shared.gid);
+
+        let link = root.path().join("planted");
+        std::os::unix::fs::symlink(&dir, &link).expect("symlink");
+        assert!(shared.prepare_dir(&link).is_err());
+    }
+}
@@ -1,22 +1,67 @@
 use crate::infrastructure::ipc::{
-    DaemonErrorCode, IpcModel, IpcSpeaker, IpcStyle, OwnedRequest, OwnedResponse,
+    DaemonErrorCode, DaemonEvent, IPC_PROTOCOL_VERSION, IpcModel, IpcSpeaker, IpcStyle,
+    OwnedRequest, OwnedResponse, SlowRequest, SynthesisOptions, SynthesisTimings,
+    protocol_mismatch_message,
 };
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 mod catalog;
+mod dedup;
+mod events;
 mod executor;
+mod lifecycle;
+mod metrics;
 mod policy;
 mod result;
+mod slow_log;
+mod usage;
+mod warnings;
 
+use super::config::{DaemonConfig, local_clock_time};
+use super::preprocess::run_preprocessor;
+use crate::domain::synthesis::postprocess::{apply_gain, apply_volume_boost};
+use crate::domain::synthesis::wav::wav_duration;
 use crate::domain::synthesis::{TextSynthesisRequest, validate_basic_request};
+use crate::infrastructure::power::{PowerSource, power_source};
+use crate::infrastructure::usage_stats::{UsageStats, load_usage_stats, usage_stats_path};
 use anyhow::Result;
-use catalog::ModelCatalog;
-use executor::DaemonSynthesisExecutor;
-use policy::SerializedSynthesisPolicy;
+use catalog::{ModelCatalog, TargetResolution};
+use dedup::{DedupWindow, Repeat};
+use events::EventBus;
+use executor::{DaemonSynthesisExecutor, JobOutput, SynthesisJob};
+use lifecycle::Lifecycle;
+pub use lifecycle::LifecycleRequest;
+use metrics::DaemonMetrics;
+pub use policy::SynthesisActivity;
+use policy::{SerializedSynthesisPolicy, run_blocking};
 use result::{DaemonServiceError, DaemonServiceErrorKind, DaemonServiceResult};
+use slow_log::SlowLog;
+use usage::UsageRecorder;
+use warnings::WarningLog;
+
+/// Text synthesized by `Prewarm`: one mora is enough to run every stage of the model.
+const PREWARM_TEXT: &str = "あ";
 
 pub struct DaemonState {
-    catalog: ModelCatalog,
+    catalog: RwLock<Arc<ModelCatalog>>,
     synthesis_policy: SerializedSynthesisPolicy,
+    events: Arc<EventBus>,
+    settings: RwLock<Arc<DaemonSettings>>,
+    warnings: WarningLog,
+    slow_log: SlowLog,
+    usage: UsageRecorder,
+    metrics: DaemonMetrics,
+    lifecycle: Lifecycle,
+}
+
+/// Everything `daemon.toml` controls, swapped as a whole on reload.
+///
+/// Requests take one snapshot up front, so a reload never mixes old and new
+/// settings within a request.
+struct DaemonSettings {
+    config: DaemonConfig,
+    dedup: Option<Arc<DedupWindow>>,
 }
 
 impl DaemonState {
@@ -25,6 +70,7 @@ impl DaemonState {
             name: style.name.to_string(),
             id: style.id,
             style_type: style.style_type.as_ref().map(ToString::to_string),
+            loaded: style.loaded,
         }
     }
 
@@ -42,6 +88,7 @@ impl DaemonState {
             model_id: model.model_id,
             file_path: model.file_path.clone(),
             speakers: model.speakers.iter().map(Self::to_ipc_speaker).collect(),
+            loaded: model.loaded,
         }
     }
 
@@ -51,19 +98,126 @@ impl DaemonState {
     ///
     /// Returns an error if VOICEVOX core initialization fails, model discovery fails,
     /// or the style-to-model mapping cannot be constructed.
-    pub fn new() -> Result<Self> {
-        let catalog_core = crate::infrastructure::core::VoicevoxCore::new()?;
+    pub fn new(config: DaemonConfig) -> Result<Self> {
+        let catalog_core =
+            crate::infrastructure::core::VoicevoxCore::with_inference(config.inference)?;
         let catalog = ModelCatalog::new(&catalog_core)?;
         drop(catalog_core);
         crate::infrastructure::memory::release_unused_allocator_memory();
 
-        let synthesis_executor = DaemonSynthesisExecutor::new();
-        let synthesis_policy = SerializedSynthesisPolicy::new(synthesis_executor);
+        forget_client_speaker_cache();
+        Ok(Self::with_catalog(config, catalog))
+    }
+
+    /// Builds daemon state around an already scanned `catalog`.
+    fn with_catalog(config: DaemonConfig, catalog: ModelCatalog) -> Self {
+        let synthesis_executors = (0..config.workers.count)
+            .map(|index| DaemonSynthesisExecutor::new(config.workers.cpus_for(index).to_vec()))
+            .collect();
+        let synthesis_policy = SerializedSynthesisPolicy::new(synthesis_executors);
 
-        Ok(Self {
-            catalog,
+        Self {
+            catalog: RwLock::new(Arc::new(catalog)),
             synthesis_policy,
-        })
+            events: Arc::new(EventBus::new()),
+            settings: RwLock::new(Arc::new(DaemonSettings {
+                dedup: config.dedup.map(|dedup| Arc::new(DedupWindow::new(dedup))),
+                config,
+            })),
+            warnings: WarningLog::new(),
+            slow_log: SlowLog::new(),
+            usage: UsageRecorder::new(),
+            metrics: DaemonMetrics::new(),
+            lifecycle: Lifecycle::new(),
+        }
+    }
+
+    fn settings(&self) -> Arc<DaemonSettings> {
+        Arc::clone(&self.settings.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn catalog(&self) -> Arc<ModelCatalog> {
+        Arc::clone(&self.catalog.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// The `Shutdown` and `Reload` requests clients send, for the server loop to carry
+    /// out; `None` once taken. Until then, `Reload` requests wait.
+    pub fn take_lifecycle_requests(
+        &self,
+    ) -> Option<tokio::sync::mpsc::UnboundedReceiver<LifecycleRequest>> {
+        self.lifecycle.take_requests()
+    }
+
+    /// Asks the server loop to stop accepting connections and shut down.
+    pub fn request_shutdown(&self) {
+        self.lifecycle.request_shutdown();
+    }
+
+    /// Resolves once every request being handled has been answered.
+    pub async fn wait_until_idle(&self) {
+        self.lifecycle.idle().await;
+    }
+
+    /// Rescans the models directory so newly installed models can be requested.
+    ///
+    /// Requests already running finish against the catalog they started with.
+    /// Returns the number of models found.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if VOICEVOX core initialization or model discovery fails; the
+    /// previous catalog stays in use.
+    pub fn reload_models(&self) -> Result<usize> {
+        let catalog_core = crate::infrastructure::core::VoicevoxCore::with_inference(
+            self.settings().config.inference,
+        )?;
+        let catalog = ModelCatalog::new(&catalog_core)?;
+        drop(catalog_core);
+        crate::infrastructure::memory::release_unused_allocator_memory();
+
+        let model_count = catalog.model_count();
+        *self.catalog.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(catalog);
+        forget_client_speaker_cache();
+        self.events.emit(DaemonEvent::ModelsReloaded {
+            model_count: u32::try_from(model_count).unwrap_or(u32::MAX),
+        });
+        Ok(model_count)
+    }
+
+    /// Replaces the `daemon.toml` settings for requests that start from now on.
+    ///
+    /// Requests already running finish under the settings they started with. The
+    /// dedup history survives when its window and action are unchanged.
+    pub fn reload_config(&self, config: DaemonConfig) {
+        let mut settings = self
+            .settings
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        let dedup = match (config.dedup, &settings.dedup) {
+            (Some(new), Some(window)) if settings.config.dedup == Some(new) => {
+                Some(Arc::clone(window))
+            }
+            (new, _) => new.map(|dedup| Arc::new(DedupWindow::new(dedup))),
+        };
+        *settings = Arc::new(DaemonSettings { config, dedup });
+    }
+
+    /// Returns a receiver for daemon activity events pushed to `Subscribe` clients.
+    #[must_use]
+    pub fn subscribe_events(&self) -> tokio::sync::broadcast::Receiver<DaemonEvent> {
+        self.events.subscribe()
+    }
+
+    /// Returns the current voice and synthesis queue length.
+    #[must_use]
+    pub fn synthesis_activity(&self) -> SynthesisActivity {
+        self.synthesis_policy.activity()
+    }
+
+    /// Cancels queued synthesis requests that have not started; returns the queue length
+    /// at the time of the stop.
+    pub fn stop_pending_synthesis(&self) -> usize {
+        self.synthesis_policy.stop_pending()
     }
 
     fn to_ipc_error(error: DaemonServiceError) -> OwnedResponse {
@@ -71,6 +225,8 @@ impl DaemonState {
             DaemonServiceErrorKind::InvalidTargetId => DaemonErrorCode::InvalidTargetId,
             DaemonServiceErrorKind::ModelLoadFailed => DaemonErrorCode::ModelLoadFailed,
             DaemonServiceErrorKind::SynthesisFailed => DaemonErrorCode::SynthesisFailed,
+            DaemonServiceErrorKind::Internal => DaemonErrorCode::Internal,
+            DaemonServiceErrorKind::ProtocolMismatch => DaemonErrorCode::ProtocolMismatch,
         };
         OwnedResponse::Error {
             code,
@@ -80,9 +236,29 @@ impl DaemonState {
 
     fn to_ipc_response(result: DaemonServiceResult) -> OwnedResponse {
         match result {
-            DaemonServiceResult::SynthesizeResult { wav_data } => {
-                OwnedResponse::SynthesizeResult { wav_data }
-            }
+            DaemonServiceResult::SynthesizeResult {
+                wav_data, warnings, ..
+            } => OwnedResponse::SynthesizeResult { wav_data, warnings },
+            DaemonServiceResult::SynthesizeTimedResult {
+                wav_data,
+                warnings,
+                timings,
+            } => OwnedResponse::SynthesizeTimedResult {
+                wav_data,
+                warnings,
+                timings,
+            },
+            DaemonServiceResult::SynthesizeWithMorasResult {
+                wav_data,
+                warnings,
+                timings,
+                moras,
+            } => OwnedResponse::SynthesizeWithMorasResult {
+                wav_data,
+                warnings,
+                timings,
+                moras,
+            },
             DaemonServiceResult::SpeakersListWithModels {
                 speakers,
                 style_to_model,
@@ -93,49 +269,651 @@ impl DaemonState {
             DaemonServiceResult::ModelsList { models } => OwnedResponse::ModelsList {
                 models: models.iter().map(Self::to_ipc_model).collect(),
             },
+            DaemonServiceResult::Subscribed => OwnedResponse::Subscribed,
+            DaemonServiceResult::ShuttingDown => OwnedResponse::ShuttingDown,
+            DaemonServiceResult::Reloaded { model_count } => OwnedResponse::Reloaded {
+                model_count: u32::try_from(model_count).unwrap_or(u32::MAX),
+            },
+            DaemonServiceResult::Authenticated => OwnedResponse::Authenticated,
+            DaemonServiceResult::SlowLog {
+                threshold_ms,
+                entries,
+            } => OwnedResponse::SlowLog {
+                threshold_ms,
+                entries,
+            },
+            DaemonServiceResult::Hello { profile_applied } => {
+                OwnedResponse::Hello { profile_applied }
+            }
+            DaemonServiceResult::Skipped => OwnedResponse::Skipped,
+            DaemonServiceResult::AudioQuery { query_json } => {
+                OwnedResponse::AudioQuery { query_json }
+            }
+            DaemonServiceResult::Cancelled => OwnedResponse::Cancelled,
+            DaemonServiceResult::Prewarmed { elapsed_ms } => {
+                OwnedResponse::Prewarmed { elapsed_ms }
+            }
+            DaemonServiceResult::ModelsPreloaded {
+                model_ids,
+                elapsed_ms,
+            } => OwnedResponse::ModelsPreloaded {
+                model_ids,
+                elapsed_ms,
+            },
+            DaemonServiceResult::Status {
+                activity,
+                recent_warnings,
+            } => OwnedResponse::Status {
+                queue_length: u32::try_from(activity.queue_length).unwrap_or(u32::MAX),
+                current_style_id: activity.current_style_id,
+                recent_warnings,
+            },
+            DaemonServiceResult::PowerPolicy { defer_background } => {
+                OwnedResponse::PowerPolicy { defer_background }
+            }
+            DaemonServiceResult::UserDictReloaded { word_count } => {
+                OwnedResponse::UserDictReloaded {
+                    word_count: u32::try_from(word_count).unwrap_or(u32::MAX),
+                }
+            }
+            DaemonServiceResult::Stats(stats) => OwnedResponse::Stats { stats },
+            DaemonServiceResult::VoiceMapping(aliases) => OwnedResponse::VoiceMapping { aliases },
+            DaemonServiceResult::PlaybackPolicy(policy) => OwnedResponse::PlaybackPolicy {
+                volume: policy.volume,
+                streaming: policy.streaming,
+            },
+        }
+    }
+
+    /// Synthesizes through the serialized policy unless the dedup window suppresses
+    /// the request as a repeat. `app_id` is recorded with slow requests.
+    async fn synthesize(
+        &self,
+        settings: &DaemonSettings,
+        text: String,
+        style_id: u32,
+        options: SynthesisOptions,
+        app_id: Option<&str>,
+    ) -> Result<DaemonServiceResult, DaemonServiceError> {
+        let dedup_key = settings
+            .dedup
+            .as_ref()
+            .map(|_| DedupWindow::key(&text, style_id, options));
+        if let (Some(dedup), Some(key)) = (&settings.dedup, &dedup_key) {
+            match dedup.check(key, Instant::now()) {
+                Some(Repeat::Skip) => {
+                    self.record_usage(settings, |stats| stats.dedup.hits += 1);
+                    return Ok(DaemonServiceResult::Skipped);
+                }
+                Some(Repeat::Replay(wav_data)) => {
+                    self.record_usage(settings, |stats| {
+                        stats.dedup.hits += 1;
+                        stats.record_voice(style_id, wav_duration(&wav_data).unwrap_or_default());
+                    });
+                    return Ok(DaemonServiceResult::SynthesizeResult {
+                        wav_data,
+                        warnings: Vec::new(),
+                        timings: SynthesisTimings::default(),
+                        moras: Vec::new(),
+                    });
+                }
+                None => {}
+            }
+        }
+
+        let mut warnings = Vec::new();
+        let text_chars = u32::try_from(text.chars().count()).unwrap_or(u32::MAX);
+        let started = Instant::now();
+        let result = self
+            .synthesis_policy
+            .synthesize(
+                &self.catalog(),
+                &self.events,
+                SynthesisJob {
+                    text,
+                    style_id,
+                    options,
+                    output: JobOutput::Wav,
+                    inference: settings.config.inference,
+                    model_cache: settings.config.model_cache,
+                },
+                &mut warnings,
+            )
+            .await;
+        self.warnings.record(&warnings);
+        let result = result?;
+        let elapsed = started.elapsed();
+        self.metrics.record_synthesis(style_id, elapsed);
+        if let (Some(slow_log), DaemonServiceResult::SynthesizeResult { timings, .. }) =
+            (settings.config.slow_log, &result)
+        {
+            self.slow_log.record_if_slow(
+                slow_log.threshold_ms,
+                SlowRequest {
+                    finished_at: SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map_or(0, |since| since.as_secs()),
+                    app_id: app_id.map(str::to_owned),
+                    style_id,
+                    text_chars,
+                    elapsed_ms: u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+                    timings: *timings,
+                },
+            );
+        }
+        let result = self.apply_whisper_boost(&settings.config, style_id, result)?;
+        let mut result = Self::apply_time_policy(&settings.config, result)?;
+        if let DaemonServiceResult::SynthesizeResult {
+            wav_data,
+            warnings: result_warnings,
+            ..
+        } = &mut result
+        {
+            *result_warnings = warnings;
+            if let (Some(dedup), Some(key)) = (&settings.dedup, &dedup_key) {
+                dedup.record(key, wav_data, Instant::now());
+            }
+            self.record_usage(settings, |stats| {
+                if dedup_key.is_some() {
+                    stats.dedup.misses += 1;
+                }
+                stats.record_voice(style_id, wav_duration(wav_data).unwrap_or_default());
+            });
+        }
+        Ok(result)
+    }
+
+    /// Updates the usage statistics file when `daemon.toml` opts in, along with the
+    /// model cache lookups since the last update.
+    fn record_usage(&self, settings: &DaemonSettings, update: impl FnOnce(&mut UsageStats)) {
+        let model_cache = self.synthesis_policy.take_model_cache_lookups();
+        if !settings.config.usage_stats {
+            return;
+        }
+        self.usage.record(|stats| {
+            stats.model_cache.add(model_cache);
+            update(stats);
+        });
+    }
+
+    /// Boosts whisper styles when `daemon.toml` enables it, before any policy volume.
+    fn apply_whisper_boost(
+        &self,
+        config: &DaemonConfig,
+        style_id: u32,
+        mut result: DaemonServiceResult,
+    ) -> Result<DaemonServiceResult, DaemonServiceError> {
+        let Some(boost) = config.whisper_boost.as_ref().filter(|boost| {
+            self.catalog()
+                .style_name(style_id)
+                .is_some_and(|name| boost.applies_to(name))
+        }) else {
+            return Ok(result);
+        };
+        if let DaemonServiceResult::SynthesizeResult { wav_data, .. } = &mut result {
+            apply_volume_boost(wav_data, boost.volume_boost()).map_err(|error| {
+                DaemonServiceError::new(
+                    DaemonServiceErrorKind::SynthesisFailed,
+                    format!("Failed to apply whisper boost: {error}"),
+                )
+            })?;
+        }
+        Ok(result)
+    }
+
+    /// Enforces the time-of-day volume on synthesized audio.
+    fn apply_time_policy(
+        config: &DaemonConfig,
+        mut result: DaemonServiceResult,
+    ) -> Result<DaemonServiceResult, DaemonServiceError> {
+        if let DaemonServiceResult::SynthesizeResult { wav_data, .. } = &mut result {
+            let policy = config.playback_policy_at(local_clock_time());
+            apply_gain(wav_data, policy.volume).map_err(|error| {
+                DaemonServiceError::new(
+                    DaemonServiceErrorKind::SynthesisFailed,
+                    format!("Failed to apply volume policy: {error}"),
+                )
+            })?;
+        }
+        Ok(result)
+    }
+
+    /// Builds an audio query through the same serialized model load as synthesis.
+    async fn audio_query(
+        &self,
+        text: String,
+        style_id: u32,
+        options: SynthesisOptions,
+    ) -> Result<DaemonServiceResult, DaemonServiceError> {
+        let settings = self.settings();
+        let mut warnings = Vec::new();
+        let result = self
+            .synthesis_policy
+            .synthesize(
+                &self.catalog(),
+                &self.events,
+                SynthesisJob {
+                    text,
+                    style_id,
+                    options,
+                    output: JobOutput::AudioQuery,
+                    inference: settings.config.inference,
+                    model_cache: settings.config.model_cache,
+                },
+                &mut warnings,
+            )
+            .await;
+        self.warnings.record(&warnings);
+        result
+    }
+
+    /// Loads the `[model_cache]` `preload` most used models into the cache, ranked by
+    /// the local usage statistics; returns the models loaded.
+    ///
+    /// Styles that no longer resolve are skipped, and a model that fails to load is
+    /// logged without stopping the rest.
+    pub async fn preload_frequent_models(&self) -> Vec<u32> {
+        let preload = self.settings().config.model_cache.preload;
+        if preload == 0 {
+            return Vec::new();
         }
+        let stats = match load_usage_stats(&usage_stats_path()) {
+            Ok(stats) => stats,
+            Err(error) => {
+                crate::infrastructure::logging::warn(&format!("Skipping model preload: {error:#}"));
+                return Vec::new();
+            }
+        };
+
+        let catalog = self.catalog();
+        let mut targets: Vec<(u32, u32)> = Vec::new();
+        for requested_id in stats.most_used_styles() {
+            if targets.len() == preload {
+                break;
+            }
+            if let TargetResolution::Exists { style_id, model_id } =
+                catalog.resolve_synthesis_target(requested_id)
+                && !targets.iter().any(|&(_, loaded)| loaded == model_id)
+            {
+                targets.push((style_id, model_id));
+            }
+        }
+
+        let mut loaded = Vec::new();
+        for (style_id, model_id) in targets {
+            match self.prewarm(style_id).await {
+                Ok(_) => loaded.push(model_id),
+                Err(error) => crate::infrastructure::logging::warn(&format!(
+                    "Failed to preload model {model_id}: {}",
+                    error.message
+                )),
+            }
+        }
+        loaded
+    }
+
+    /// Loads the given models into the cache in order; they stay loaded until evicted.
+    ///
+    /// Every ID is checked before anything loads, and more models than the cache
+    /// holds are refused since the first would be evicted by the last.
+    async fn preload_models(
+        &self,
+        model_ids: Vec<u32>,
+    ) -> Result<DaemonServiceResult, DaemonServiceError> {
+        let max_loaded_models = self.settings().config.model_cache.max_loaded_models;
+        if model_ids.len() > max_loaded_models {
+            return Err(DaemonServiceError::new(
+                DaemonServiceErrorKind::InvalidTargetId,
+                format!(
+                    "Cannot preload {} models: the model cache holds {max_loaded_models} (raise [model_cache] max_loaded_models)",
+                    model_ids.len()
+                ),
+            ));
+        }
+        let catalog = self.catalog();
+        let styles = model_ids
+            .iter()
+            .map(|&model_id| {
+                catalog.default_style_for_model(model_id).ok_or_else(|| {
+                    DaemonServiceError::new(
+                        DaemonServiceErrorKind::InvalidTargetId,
+                        format!("Unknown model ID {model_id}. Use --list-models to inspect available IDs."),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let started = Instant::now();
+        for style_id in styles {
+            self.prewarm(style_id).await?;
+        }
+        Ok(DaemonServiceResult::ModelsPreloaded {
+            model_ids,
+            elapsed_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        })
+    }
+
+    /// Runs a throwaway synthesis of [`PREWARM_TEXT`] through the serialized model load,
+    /// bypassing dedup, preprocessing, and post-processing.
+    async fn prewarm(&self, style_id: u32) -> Result<DaemonServiceResult, DaemonServiceError> {
+        let settings = self.settings();
+        let mut warnings = Vec::new();
+        let result = self
+            .synthesis_policy
+            .synthesize(
+                &self.catalog(),
+                &self.events,
+                SynthesisJob {
+                    text: PREWARM_TEXT.to_string(),
+                    style_id,
+                    options: SynthesisOptions::default(),
+                    output: JobOutput::Prewarm,
+                    inference: settings.config.inference,
+                    model_cache: settings.config.model_cache,
+                },
+                &mut warnings,
+            )
+            .await;
+        self.warnings.record(&warnings);
+        result
+    }
+
+    /// Applies the caller's app profile and checks the request before it is queued.
+    fn prepare_options(
+        settings: &DaemonSettings,
+        text: &str,
+        style_id: u32,
+        options: SynthesisOptions,
+        app_id: Option<&str>,
+    ) -> Result<SynthesisOptions, DaemonServiceError> {
+        let options = app_id
+            .and_then(|app_id| settings.config.app_profile(app_id))
+            .map_or(options, |profile| profile.apply_defaults(options));
+        validate_basic_request(&TextSynthesisRequest {
+            text,
+            style_id,
+            rate: options.rate,
+            pitch: options.pitch,
+        })
+        .and_then(|()| options.validate())
+        .map_err(|error| {
+            DaemonServiceError::new(
+                DaemonServiceErrorKind::SynthesisFailed,
+                format!("Invalid synthesis request: {error}"),
+            )
+        })?;
+        Ok(options)
+    }
+
+    /// Passes request text through the configured front-end plugin, if any.
+    async fn preprocess_text(
+        settings: &DaemonSettings,
+        text: String,
+    ) -> Result<String, DaemonServiceError> {
+        let Some(preprocess) = &settings.config.preprocess else {
+            return Ok(text);
+        };
+        run_preprocessor(preprocess, &text).await.map_err(|error| {
+            DaemonServiceError::new(
+                DaemonServiceErrorKind::SynthesisFailed,
+                format!("Text preprocessing failed: {error:#}"),
+            )
+        })
     }
 
     async fn execute_request(
         &self,
         request: OwnedRequest,
+        app_id: Option<&str>,
     ) -> Result<DaemonServiceResult, DaemonServiceError> {
+        let settings = self.settings();
         match request {
             OwnedRequest::Synthesize {
                 text,
                 style_id,
                 options,
             } => {
-                validate_basic_request(&TextSynthesisRequest {
-                    text: &text,
-                    style_id,
-                    rate: options.rate,
-                })
-                .map_err(|error| {
-                    DaemonServiceError::new(
-                        DaemonServiceErrorKind::SynthesisFailed,
-                        format!("Invalid synthesis request: {error}"),
-                    )
-                })?;
-
-                self.synthesis_policy
-                    .synthesize(&self.catalog, text, style_id, options.rate)
+                let text = Self::preprocess_text(&settings, text).await?;
+                let options = Self::prepare_options(&settings, &text, style_id, options, app_id)?;
+                self.synthesize(&settings, text, style_id, options, app_id)
                     .await
             }
-            OwnedRequest::ListSpeakers => Ok(DaemonServiceResult::SpeakersListWithModels {
-                speakers: self.catalog.speakers().to_vec(),
-                style_to_model: self.catalog.style_to_model_map().clone(),
-            }),
+            OwnedRequest::SynthesizeTimed {
+                text,
+                style_id,
+                options,
+            } => {
+                let text = Self::preprocess_text(&settings, text).await?;
+                let options = Self::prepare_options(&settings, &text, style_id, options, app_id)?;
+                let result = self
+                    .synthesize(&settings, text, style_id, options, app_id)
+                    .await?;
+                Ok(match result {
+                    DaemonServiceResult::SynthesizeResult {
+                        wav_data,
+                        warnings,
+                        timings,
+                        ..
+                    } => DaemonServiceResult::SynthesizeTimedResult {
+                        wav_data,
+                        warnings,
+                        timings,
+                    },
+                    other => other,
+                })
+            }
+            OwnedRequest::SynthesizeWithMoras {
+                text,
+                style_id,
+                options,
+            } => {
+                let text = Self::preprocess_text(&settings, text).await?;
+                let options = Self::prepare_options(&settings, &text, style_id, options, app_id)?;
+                let result = self
+                    .synthesize(&settings, text, style_id, options, app_id)
+                    .await?;
+                Ok(match result {
+                    DaemonServiceResult::SynthesizeResult {
+                        wav_data,
+                        warnings,
+                        timings,
+                        moras,
+                    } => DaemonServiceResult::SynthesizeWithMorasResult {
+                        wav_data,
+                        warnings,
+                        timings,
+                        moras,
+                    },
+                    other => other,
+                })
+            }
+            OwnedRequest::AudioQuery {
+                text,
+                style_id,
+                options,
+            } => {
+                let text = Self::preprocess_text(&settings, text).await?;
+                let options = Self::prepare_options(&settings, &text, style_id, options, app_id)?;
+                self.audio_query(text, style_id, options).await
+            }
+            OwnedRequest::ListSpeakers => {
+                let catalog = self.catalog();
+                Ok(DaemonServiceResult::SpeakersListWithModels {
+                    speakers: catalog.speakers(&self.synthesis_policy.activity().loaded_model_ids),
+                    style_to_model: catalog.style_to_model_map().clone(),
+                })
+            }
             OwnedRequest::ListModels => Ok(DaemonServiceResult::ModelsList {
-                models: self.catalog.available_models().to_vec(),
+                models: self
+                    .catalog()
+                    .available_models(&self.synthesis_policy.activity().loaded_model_ids),
+            }),
+            // Acknowledges the switch to push mode; the connection handler streams events.
+            OwnedRequest::Subscribe => Ok(DaemonServiceResult::Subscribed),
+            // TCP connections are checked by the connection handler before any request
+            // gets here, and Unix socket peers were already checked by the OS.
+            OwnedRequest::Authenticate { .. } => Ok(DaemonServiceResult::Authenticated),
+            // The connection handler remembers the id; this only reports whether it matched.
+            OwnedRequest::Hello {
+                protocol_version,
+                app_id,
+            } => {
+                if protocol_version != IPC_PROTOCOL_VERSION {
+                    return Err(DaemonServiceError::new(
+                        DaemonServiceErrorKind::ProtocolMismatch,
+                        protocol_mismatch_message(protocol_version),
+                    ));
+                }
+                Ok(DaemonServiceResult::Hello {
+                    profile_applied: settings.config.app_profile(&app_id).is_some(),
+                })
+            }
+            OwnedRequest::Status => Ok(DaemonServiceResult::Status {
+                activity: self.synthesis_activity(),
+                recent_warnings: self.warnings.recent(),
             }),
+            OwnedRequest::GetPlaybackPolicy => Ok(DaemonServiceResult::PlaybackPolicy(
+                settings.config.playback_policy_at(local_clock_time()),
+            )),
+            // Running requests are cancelled by the connection handler; reaching here
+            // means the request already finished, so there is nothing left to stop.
+            OwnedRequest::Cancel => Ok(DaemonServiceResult::Cancelled),
+            OwnedRequest::Prewarm { style_id } => self.prewarm(style_id).await,
+            OwnedRequest::PreloadModels { model_ids } => self.preload_models(model_ids).await,
+            OwnedRequest::GetPowerPolicy => Ok(DaemonServiceResult::PowerPolicy {
+                defer_background: settings.config.low_power
+                    && run_blocking(power_source) == Some(PowerSource::Battery),
+            }),
+            OwnedRequest::ReloadUserDict => run_blocking(reload_user_dict),
+            OwnedRequest::GetStats => Ok(DaemonServiceResult::Stats(self.metrics.snapshot(
+                self.events.model_churn(),
+                self.synthesis_policy.model_cache_totals(),
+            ))),
+            OwnedRequest::GetVoiceMapping => run_blocking(voice_mapping),
+            OwnedRequest::GetSlowLog => Ok(DaemonServiceResult::SlowLog {
+                threshold_ms: settings
+                    .config
+                    .slow_log
+                    .map(|slow_log| slow_log.threshold_ms),
+                entries: self.slow_log.recent(),
+            }),
+            OwnedRequest::Shutdown => {
+                self.request_shutdown();
+                Ok(DaemonServiceResult::ShuttingDown)
+            }
+            OwnedRequest::Reload => self
+                .lifecycle
+                .reload()
+                .await
+                .map(|model_count| DaemonServiceResult::Reloaded { model_count })
+                .map_err(|message| {
+                    DaemonServiceError::new(DaemonServiceErrorKind::Internal, message)
+                }),
         }
     }
 
-    pub async fn handle_request(&self, request: OwnedRequest) -> OwnedResponse {
-        match self.execute_request(request).await {
+    /// Handles one request; `app_id` is the id the connection sent in `Hello`, if any.
+    pub async fn handle_request(
+        &self,
+        request: OwnedRequest,
+        app_id: Option<&str>,
+    ) -> OwnedResponse {
+        let _in_flight = self.lifecycle.begin_request();
+        match self.execute_request(request, app_id).await {
             Ok(result) => Self::to_ipc_response(result),
             Err(error) => Self::to_ipc_error(error),
         }
     }
 }
+
+/// Checks the saved user dictionary and tells cores to apply it before their next
+/// request; an invalid dictionary keeps the words they already use.
+fn reload_user_dict() -> Result<DaemonServiceResult, DaemonServiceError> {
+    let path = crate::infrastructure::paths::user_dict_path();
+    let words = crate::infrastructure::user_dict::load_dictionary_words(&path)
+        .and_then(|words| {
+            crate::infrastructure::user_dict::build_user_dict(&words)?;
+            Ok(words)
+        })
+        .map_err(|error| {
+            DaemonServiceError::new(
+                DaemonServiceErrorKind::Internal,
+                format!("Failed to reload the user dictionary: {error:#}"),
+            )
+        })?;
+    crate::infrastructure::user_dict::bump_user_dict_generation();
+    crate::infrastructure::logging::info(&format!(
+        "Reloaded user dictionary: {} word(s)",
+        words.len()
+    ));
+    Ok(DaemonServiceResult::UserDictReloaded {
+        word_count: words.len(),
+    })
+}
+
+/// Reads the aliases file on every request, so `alias add` needs no reload.
+fn voice_mapping() -> Result<DaemonServiceResult, DaemonServiceError> {
+    let path = crate::infrastructure::paths::voice_aliases_path();
+    crate::infrastructure::voice_aliases::load_voice_aliases(&path)
+        .map(DaemonServiceResult::VoiceMapping)
+        .map_err(|error| {
+            DaemonServiceError::new(
+                DaemonServiceErrorKind::Internal,
+                format!("Failed to read voice aliases: {error:#}"),
+            )
+        })
+}
+
+/// Drops the speaker catalog clients cached, since the daemon's models may have changed.
+fn forget_client_speaker_cache() {
+    let path = crate::infrastructure::speaker_cache::speaker_cache_path();
+    if let Err(error) = crate::infrastructure::speaker_cache::invalidate_speaker_cache(&path) {
+        crate::infrastructure::logging::warn(&format!("{error:#}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn voice_mapping_is_served_on_a_current_thread_runtime() {
+        let state = DaemonState::with_catalog(DaemonConfig::default(), ModelCatalog::default());
+
+        let response = state
+            .handle_request(OwnedRequest::GetVoiceMapping, None)
+            .await;
+
+        assert!(
+            matches!(response, OwnedResponse::VoiceMapping { .. }),
+            "unexpected response: {response:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn hello_from_another_protocol_revision_is_refused() {
+        let state = DaemonState::with_catalog(DaemonConfig::default(), ModelCatalog::default());
+        let hello = |protocol_version| OwnedRequest::Hello {
+            protocol_version,
+            app_id: String::new(),
+        };
+
+        assert_eq!(
+            state
+                .handle_request(hello(IPC_PROTOCOL_VERSION), None)
+                .await,
+            OwnedResponse::Hello {
+                profile_applied: false
+            }
+        );
+        assert!(matches!(
+            state
+                .handle_request(hello(IPC_PROTOCOL_VERSION + 1), None)
+                .await,
+            OwnedResponse::Error {
+                code: DaemonErrorCode::ProtocolMismatch,
+                ..
+            }
+        ));
+    }
+}
@@ -9,6 +9,7 @@ pub(super) enum TargetResolution {
     Missing { message: String },
 }
 
+#[cfg_attr(test, derive(Default))]
 pub(super) struct ModelCatalog {
     style_to_model_map: HashMap<u32, u32>,
     model_default_style_map: HashMap<u32, u32>,
@@ -17,8 +18,8 @@ pub(super) struct ModelCatalog {
 }
 
 impl ModelCatalog {
-    // Catalog is intentionally a startup-time snapshot. Runtime model add/remove is not
-    // observed until daemon restart under the current fixed-contract architecture.
+    // Catalog is a snapshot of the models directory. Models added or removed later are
+    // not observed until `DaemonState::reload_models` builds a new one (on SIGHUP).
     fn build_model_default_style_map(
         speakers: &[crate::infrastructure::voicevox::Speaker],
         style_to_model_map: &HashMap<u32, u32>,
@@ -57,6 +58,10 @@ impl ModelCatalog {
         })
     }
 
+    pub(super) fn model_count(&self) -> usize {
+        self.available_models.len()
+    }
+
     pub(super) fn resolve_synthesis_target(&self, requested_id: u32) -> TargetResolution {
         if let Some(model_id) = self.style_to_model_map.get(&requested_id).copied() {
             return TargetResolution::Exists {
@@ -88,6 +93,24 @@ impl ModelCatalog {
         }
     }
 
+    /// Lowest style ID served by `model_id`, used to load the model by itself.
+    pub(super) fn default_style_for_model(&self, model_id: u32) -> Option<u32> {
+        self.model_default_style_map.get(&model_id).copied()
+    }
+
+    /// Name of the style a request for `requested_id` synthesizes with.
+    pub(super) fn style_name(&self, requested_id: u32) -> Option<&str> {
+        let TargetResolution::Exists { style_id, .. } = self.resolve_synthesis_target(requested_id)
+        else {
+            return None;
+        };
+        self.all_speakers
+            .iter()
+            .flat_map(|speaker| speaker.styles.iter())
+            .find(|style| style.id == style_id)
+            .map(|style| style.name.as_str())
+    }
+
     pub(super) fn get_model_path(&self, model_id: u32) -> Option<&Path> {
         self.available_models
             .iter()
@@ -95,23 +118,54 @@ impl ModelCatalog {
             .map(|model| model.file_path.as_path())
     }
 
-    pub(super) fn speakers(&self) -> &[crate::infrastructure::voicevox::Speaker] {
-        &self.all_speakers
+    /// All speakers, with `loaded` set on styles served by `loaded_model_ids`.
+    pub(super) fn speakers(
+        &self,
+        loaded_model_ids: &[u32],
+    ) -> Vec<crate::infrastructure::voicevox::Speaker> {
+        let mut speakers = self.all_speakers.clone();
+        self.mark_loaded_styles(&mut speakers, loaded_model_ids);
+        speakers
     }
 
     pub(super) fn style_to_model_map(&self) -> &HashMap<u32, u32> {
         &self.style_to_model_map
     }
 
-    pub(super) fn available_models(&self) -> &[crate::infrastructure::voicevox::AvailableModel] {
-        &self.available_models
+    /// All models, with `loaded` set on `loaded_model_ids` and their styles.
+    pub(super) fn available_models(
+        &self,
+        loaded_model_ids: &[u32],
+    ) -> Vec<crate::infrastructure::voicevox::AvailableModel> {
+        let mut models = self.available_models.clone();
+        for model in &mut models {
+            model.loaded = loaded_model_ids.contains(&model.model_id);
+            self.mark_loaded_styles(&mut model.speakers, loaded_model_ids);
+        }
+        models
+    }
+
+    fn mark_loaded_styles(
+        &self,
+        speakers: &mut [crate::infrastructure::voicevox::Speaker],
+        loaded_model_ids: &[u32],
+    ) {
+        for style in speakers
+            .iter_mut()
+            .flat_map(|speaker| speaker.styles.iter_mut())
+        {
+            style.loaded = self
+                .style_to_model_map
+                .get(&style.id)
+                .is_some_and(|model_id| loaded_model_ids.contains(model_id));
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{ModelCatalog, TargetResolution};
-    use crate::infrastructure::voicevox::AvailableModel;
+    use crate::infrastructure::voicevox::{AvailableModel, Speaker, Style};
     use std::collections::HashMap;
     use std::path::PathBuf;
 
@@ -119,17 +173,32 @@ mod tests {
         ModelCatalog {
             style_to_model_map: HashMap::from([(11, 1)]),
             model_default_style_map: HashMap::from([(1, 11), (2, 21)]),
-            all_speakers: vec![],
+            all_speakers: vec![Speaker {
+                name: "speaker".into(),
+                speaker_uuid: "uuid".into(),
+                styles: [11, 12]
+                    .into_iter()
+                    .map(|id| Style {
+                        name: format!("style-{id}").into(),
+                        id,
+                        style_type: None,
+                        loaded: false,
+                    })
+                    .collect(),
+                version: "1".into(),
+            }],
             available_models: vec![
                 AvailableModel {
                     model_id: 1,
                     file_path: PathBuf::from("/tmp/1.vvm"),
                     speakers: Default::default(),
+                    loaded: false,
                 },
                 AvailableModel {
                     model_id: 2,
                     file_path: PathBuf::from("/tmp/2.vvm"),
                     speakers: Default::default(),
+                    loaded: false,
                 },
             ],
         }
@@ -157,6 +226,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn default_style_for_model_ignores_style_ids() {
+        let catalog = test_catalog();
+        assert_eq!(catalog.default_style_for_model(2), Some(21));
+        assert_eq!(catalog.default_style_for_model(11), None);
+    }
+
+    #[test]
+    fn listings_flag_the_loaded_model_and_its_styles() {
+        let catalog = test_catalog();
+
+        let loaded = |speakers: Vec<Speaker>| {
+            speakers
+                .iter()
+                .flat_map(|speaker| speaker.styles.iter())
+                .map(|style| (style.id, style.loaded))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            loaded(catalog.speakers(&[1])),
+            vec![(11, true), (12, false)]
+        );
+        assert_eq!(
+            loaded(catalog.speakers(&[])),
+            vec![(11, false), (12, false)]
+        );
+
+        let model_flags = |loaded_model_ids: &[u32]| {
+            catalog
+                .available_models(loaded_model_ids)
+                .iter()
+                .map(|model| (model.model_id, model.loaded))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(model_flags(&[2]), vec![(1, false), (2, true)]);
+        assert_eq!(model_flags(&[1, 2]), vec![(1, true), (2, true)]);
+    }
+
+    #[test]
+    fn style_name_follows_model_ids_to_their_default_style() {
+        let catalog = test_catalog();
+        assert_eq!(catalog.style_name(11), Some("style-11"));
+        assert_eq!(catalog.style_name(1), Some("style-11"));
+        assert_eq!(catalog.style_name(999), None);
+    }
+
     #[test]
     fn unknown_target_is_missing() {
         match test_catalog().resolve_synthesis_target(999) {
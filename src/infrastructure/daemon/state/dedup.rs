@@ -0,0 +1,152 @@
+use std::collections::VecDeque;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::infrastructure::daemon::config::{DedupAction, DedupConfig};
+use crate::infrastructure::ipc::SynthesisOptions;
+
+/// Upper bound on remembered requests, so replay audio cannot grow without limit.
+const MAX_ENTRIES: usize = 32;
+
+/// A request's text, style, and options, with a digest of them checked first.
+///
+/// The digest only narrows the search; the fields themselves must match, so two
+/// different requests that hash alike are never treated as repeats.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct RequestKey {
+    digest: u64,
+    style_id: u32,
+    options: SynthesisOptions,
+    text: String,
+}
+
+struct Entry {
+    key: RequestKey,
+    at: Instant,
+    wav_data: Vec<u8>,
+}
+
+/// Outcome for a request that repeats one seen within the window.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Repeat {
+    Skip,
+    Replay(Vec<u8>),
+}
+
+/// Recently synthesized requests, keyed by [`RequestKey`].
+pub(super) struct DedupWindow {
+    window: Duration,
+    action: DedupAction,
+    entries: Mutex<VecDeque<Entry>>,
+}
+
+impl DedupWindow {
+    pub(super) fn new(config: DedupConfig) -> Self {
+        Self {
+            window: Duration::from_secs(config.window_secs),
+            action: config.action,
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub(super) fn key(text: &str, style_id: u32, options: SynthesisOptions) -> RequestKey {
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        style_id.hash(&mut hasher);
+        options.version.hash(&mut hasher);
+        for value in [
+            options.rate,
+            options.pitch,
+            options.intonation,
+            options.volume,
+            options.pre_phoneme_length,
+            options.post_phoneme_length,
+        ] {
+            value.to_bits().hash(&mut hasher);
+        }
+        options.output_format.hash(&mut hasher);
+        RequestKey {
+            digest: hasher.finish(),
+            style_id,
+            options,
+            text: text.to_owned(),
+        }
+    }
+
+    /// Reports whether `key` was synthesized within the window at `now`.
+    pub(super) fn check(&self, key: &RequestKey, now: Instant) -> Option<Repeat> {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        while entries
+            .front()
+            .is_some_and(|entry| now.duration_since(entry.at) > self.window)
+        {
+            entries.pop_front();
+        }
+        let entry = entries.iter().find(|entry| entry.key == *key)?;
+        Some(match self.action {
+            DedupAction::Skip => Repeat::Skip,
+            DedupAction::Replay => Repeat::Replay(entry.wav_data.clone()),
+        })
+    }
+
+    /// Remembers a completed synthesis; the window restarts only on real synthesis,
+    /// so a steady stream of repeats cannot keep one sentence suppressed forever.
+    pub(super) fn record(&self, key: &RequestKey, wav_data: &[u8], now: Instant) {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        if entries.len() >= MAX_ENTRIES {
+            entries.pop_front();
+        }
+        let wav_data = match self.action {
+            DedupAction::Skip => Vec::new(),
+            DedupAction::Replay => wav_data.to_vec(),
+        };
+        entries.push_back(Entry {
+            key: key.clone(),
+            at: now,
+            wav_data,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeats_inside_the_window_are_reported_until_it_expires() {
+        let dedup = DedupWindow::new(DedupConfig {
+            window_secs: 10,
+            action: DedupAction::Replay,
+        });
+        let key = DedupWindow::key("同じ文", 3, SynthesisOptions::default());
+        let other = DedupWindow::key("同じ文", 4, SynthesisOptions::default());
+        let start = Instant::now();
+
+        assert_eq!(dedup.check(&key, start), None);
+        dedup.record(&key, b"wav", start);
+        assert_eq!(
+            dedup.check(&key, start + Duration::from_secs(5)),
+            Some(Repeat::Replay(b"wav".to_vec()))
+        );
+        assert_eq!(dedup.check(&other, start + Duration::from_secs(5)), None);
+        assert_eq!(dedup.check(&key, start + Duration::from_secs(11)), None);
+    }
+
+    #[test]
+    fn a_digest_collision_is_not_a_repeat() {
+        let dedup = DedupWindow::new(DedupConfig {
+            window_secs: 10,
+            action: DedupAction::Replay,
+        });
+        let key = DedupWindow::key("同じ文", 3, SynthesisOptions::default());
+        let colliding = RequestKey {
+            text: "別の文".to_owned(),
+            ..key.clone()
+        };
+        let start = Instant::now();
+
+        dedup.record(&key, b"wav", start);
+        assert_eq!(dedup.check(&colliding, start), None);
+    }
+}
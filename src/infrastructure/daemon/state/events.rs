@@ -0,0 +1,53 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::broadcast;
+
+use crate::infrastructure::ipc::DaemonEvent;
+
+const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+/// Fan-out of daemon activity to subscribed connections.
+///
+/// Emitting never blocks synthesis: with no subscribers events are dropped, and slow
+/// subscribers observe a `Lagged` gap instead of back-pressuring the daemon.
+/// Model loads and unloads are also counted here, since every one is emitted.
+pub(super) struct EventBus {
+    sender: broadcast::Sender<DaemonEvent>,
+    model_loads: AtomicU64,
+    model_unloads: AtomicU64,
+}
+
+impl EventBus {
+    pub(super) fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self {
+            sender,
+            model_loads: AtomicU64::new(0),
+            model_unloads: AtomicU64::new(0),
+        }
+    }
+
+    pub(super) fn emit(&self, event: DaemonEvent) {
+        match event {
+            DaemonEvent::ModelLoaded { .. } => {
+                self.model_loads.fetch_add(1, Ordering::SeqCst);
+            }
+            DaemonEvent::ModelUnloaded { .. } => {
+                self.model_unloads.fetch_add(1, Ordering::SeqCst);
+            }
+            _ => {}
+        }
+        let _ = self.sender.send(event);
+    }
+
+    /// Models loaded and unloaded since the daemon started.
+    pub(super) fn model_churn(&self) -> (u64, u64) {
+        (
+            self.model_loads.load(Ordering::SeqCst),
+            self.model_unloads.load(Ordering::SeqCst),
+        )
+    }
+
+    pub(super) fn subscribe(&self) -> broadcast::Receiver<DaemonEvent> {
+        self.sender.subscribe()
+    }
+}
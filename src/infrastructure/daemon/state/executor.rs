@@ -1,11 +1,219 @@
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, mpsc};
+use std::time::Instant;
 
-use crate::infrastructure::core::VoicevoxCore;
+use crate::infrastructure::core::{InferenceOptions, VoicevoxCore, mora_timings};
+use crate::infrastructure::daemon::config::ModelCacheConfig;
+use crate::infrastructure::ipc::{
+    DaemonEvent, DaemonWarning, DaemonWarningCode, MoraTiming, SynthesisOptions, SynthesisTimings,
+};
+use crate::infrastructure::memory::{available_memory_bytes, release_unused_allocator_memory};
+use crate::infrastructure::usage_stats::CacheCounts;
 
 use super::catalog::{ModelCatalog, TargetResolution};
+use super::events::EventBus;
 use super::result::{DaemonServiceError, DaemonServiceErrorKind, DaemonServiceResult};
 
-pub(super) struct DaemonSynthesisExecutor;
+/// What a job produces once its style's model is loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum JobOutput {
+    Wav,
+    AudioQuery,
+    /// Synthesizes and discards the audio, leaving the model's files and the runtime warm.
+    Prewarm,
+}
+
+/// One unit of work for the executor: text to run through a style's model.
+pub(super) struct SynthesisJob {
+    pub(super) text: String,
+    pub(super) style_id: u32,
+    pub(super) options: SynthesisOptions,
+    pub(super) output: JobOutput,
+    /// `[inference]` settings the request started under.
+    pub(super) inference: InferenceOptions,
+    /// `[model_cache]` settings the request started under.
+    pub(super) model_cache: ModelCacheConfig,
+}
+
+/// Models an executor holds in memory, shared so listings can read them without
+/// waiting for the executor lock.
+#[derive(Debug, Clone, Default)]
+pub(super) struct ResidentModels(Arc<std::sync::Mutex<Vec<u32>>>);
+
+impl ResidentModels {
+    pub(super) fn snapshot(&self) -> Vec<u32> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    fn set(&self, model_ids: Vec<u32>) {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner) = model_ids;
+    }
+}
+
+/// Model cache hits and misses not yet taken for usage statistics, and in total.
+#[derive(Debug, Default)]
+pub(super) struct ModelCacheLookups {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    total_hits: AtomicU64,
+    total_misses: AtomicU64,
+}
+
+impl ModelCacheLookups {
+    fn record(&self, hit: bool) {
+        let (counter, total) = if hit {
+            (&self.hits, &self.total_hits)
+        } else {
+            (&self.misses, &self.total_misses)
+        };
+        counter.fetch_add(1, Ordering::SeqCst);
+        total.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Every lookup since the daemon started, whether taken or not.
+    pub(super) fn totals(&self) -> CacheCounts {
+        CacheCounts {
+            hits: self.total_hits.load(Ordering::SeqCst),
+            misses: self.total_misses.load(Ordering::SeqCst),
+        }
+    }
+
+    pub(super) fn take(&self) -> CacheCounts {
+        CacheCounts {
+            hits: self.hits.swap(0, Ordering::SeqCst),
+            misses: self.misses.swap(0, Ordering::SeqCst),
+        }
+    }
+}
+
+/// A core kept between requests with the models it has loaded.
+struct ModelCache {
+    core: VoicevoxCore,
+    models: CachedModels,
+}
+
+/// What a cached core holds, kept apart from the core so it can be followed without one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CachedModels {
+    /// Settings the core was created with; a change means a fresh core.
+    inference: InferenceOptions,
+    /// Loaded models, least recently used first.
+    models: Vec<u32>,
+}
+
+impl CachedModels {
+    fn new(inference: InferenceOptions) -> Self {
+        Self {
+            inference,
+            models: Vec::new(),
+        }
+    }
+
+    /// Whether a core created for these models can serve a request made under `inference`.
+    fn serves(&self, inference: InferenceOptions) -> bool {
+        self.inference == inference
+    }
+
+    /// Makes `model_id` the most recently used model and returns whether it was
+    /// already loaded.
+    ///
+    /// Otherwise the least recently used models go to `unload` while there are
+    /// `max_loaded_models` or `memory_is_low`, then `model_id` goes to `load`.
+    /// `resident` follows every step, so a model that fails to load is not listed.
+    fn acquire<E>(
+        &mut self,
+        model_id: u32,
+        max_loaded_models: usize,
+        resident: &ResidentModels,
+        mut memory_is_low: impl FnMut() -> bool,
+        mut unload: impl FnMut(u32),
+        load: impl FnOnce(u32) -> Result<(), E>,
+    ) -> Result<bool, E> {
+        if let Some(position) = self.models.iter().position(|&id| id == model_id) {
+            let hit = self.models.remove(position);
+            self.models.push(hit);
+            resident.set(self.models.clone());
+            return Ok(true);
+        }
+
+        while !self.models.is_empty() && (self.models.len() >= max_loaded_models || memory_is_low())
+        {
+            unload(self.models.remove(0));
+            resident.set(self.models.clone());
+        }
+        load(model_id)?;
+        self.models.push(model_id);
+        resident.set(self.models.clone());
+        Ok(false)
+    }
+}
+
+pub(super) struct DaemonSynthesisExecutor {
+    resident: ResidentModels,
+    lookups: Arc<ModelCacheLookups>,
+    thread: ExecutorThread,
+}
+
+/// Where an executor's jobs run.
+enum ExecutorThread {
+    /// On the thread that submits them.
+    Caller(ExecutorCore),
+    /// On one long-lived thread, pinned to the executor's CPUs when it started, which
+    /// owns the executor's core.
+    Pinned(mpsc::Sender<PinnedJob>),
+}
+
+/// The cores and models of one executor, owned by the thread its jobs run on.
+struct ExecutorCore {
+    resident: ResidentModels,
+    lookups: Arc<ModelCacheLookups>,
+    /// Core kept between requests while `[model_cache]` is enabled.
+    cache: Option<ModelCache>,
+}
+
+/// A job sent to a pinned executor thread, answered with its result and warnings.
+struct PinnedJob {
+    catalog: Arc<ModelCatalog>,
+    events: Arc<EventBus>,
+    job: SynthesisJob,
+    reply: mpsc::SyncSender<(
+        Result<DaemonServiceResult, DaemonServiceError>,
+        Vec<DaemonWarning>,
+    )>,
+}
+
+/// Starts the thread a pinned executor's jobs run on, pinning it to `cpus` once.
+///
+/// A thread that cannot be pinned still synthesizes, unpinned, after logging why.
+fn spawn_pinned_thread(
+    cpus: Vec<usize>,
+    mut core: ExecutorCore,
+) -> std::io::Result<mpsc::Sender<PinnedJob>> {
+    let (jobs, received) = mpsc::channel::<PinnedJob>();
+    std::thread::Builder::new()
+        .name("voicevox-synth".into())
+        .spawn(move || {
+            if let Err(error) = crate::infrastructure::cpu_affinity::pin_current_thread(&cpus) {
+                crate::infrastructure::logging::warn(&format!("{error:#}"));
+            }
+            for PinnedJob {
+                catalog,
+                events,
+                job,
+                reply,
+            } in received
+            {
+                let mut warnings = Vec::new();
+                let result = core.synthesize(&catalog, &events, job, &mut warnings);
+                let _ = reply.send((result, warnings));
+            }
+        })?;
+    Ok(jobs)
+}
 
 /// RAII guard that unloads a voice model on drop.
 ///
@@ -16,48 +224,230 @@ struct ModelUnloadGuard<'a> {
     core: &'a VoicevoxCore,
     model_id: u32,
     model_path: Option<&'a Path>,
+    events: &'a EventBus,
+    resident: &'a ResidentModels,
+    warnings: &'a mut Vec<DaemonWarning>,
 }
 
 struct AllocatorReliefGuard;
 
 impl Drop for AllocatorReliefGuard {
     fn drop(&mut self) {
-        crate::infrastructure::memory::release_unused_allocator_memory();
+        release_unused_allocator_memory();
     }
 }
 
 impl Drop for ModelUnloadGuard<'_> {
     fn drop(&mut self) {
-        let Some(model_path) = self.model_path else {
-            crate::infrastructure::logging::warn(&format!(
-                "Model {} not found in available models",
-                self.model_id
-            ));
-            return;
-        };
+        // The per-request core is dropped right after, so the model is gone either way.
+        self.resident.set(Vec::new());
+        unload_model(
+            self.core,
+            self.model_id,
+            self.model_path,
+            self.events,
+            self.warnings,
+        );
+    }
+}
 
-        if let Err(error) = self.core.unload_voice_model_by_path(model_path) {
-            crate::infrastructure::logging::warn(&format!(
-                "Failed to unload model {}: {error}",
-                self.model_id
-            ));
-        }
+fn unload_model(
+    core: &VoicevoxCore,
+    model_id: u32,
+    model_path: Option<&Path>,
+    events: &EventBus,
+    warnings: &mut Vec<DaemonWarning>,
+) {
+    let Some(model_path) = model_path else {
+        warnings.push(DaemonWarning {
+            code: DaemonWarningCode::ModelPathMissing,
+            message: format!("Model {model_id} not found in available models"),
+        });
+        return;
+    };
+
+    match core.unload_voice_model_by_path(model_path) {
+        Ok(()) => events.emit(DaemonEvent::ModelUnloaded { model_id }),
+        Err(error) => warnings.push(DaemonWarning {
+            code: DaemonWarningCode::ModelUnloadFailed,
+            message: format!("Failed to unload model {model_id}: {error}"),
+        }),
     }
 }
 
+fn create_core(inference: InferenceOptions) -> Result<VoicevoxCore, DaemonServiceError> {
+    VoicevoxCore::with_inference(inference).map_err(|error| {
+        DaemonServiceError::new(
+            DaemonServiceErrorKind::ModelLoadFailed,
+            format!("Failed to initialize VOICEVOX core for synthesis: {error}"),
+        )
+    })
+}
+
+fn load_model(core: &VoicevoxCore, model_id: u32) -> Result<(), DaemonServiceError> {
+    core.load_specific_model(model_id).map_err(|error| {
+        crate::infrastructure::logging::error(&format!("Failed to load model {model_id}: {error}"));
+        DaemonServiceError::new(
+            DaemonServiceErrorKind::ModelLoadFailed,
+            format!("Failed to load model {model_id} for synthesis: {error}"),
+        )
+    })
+}
+
+/// Whether less than `min_available_mb` is available; `0` or an unknown amount is never low.
+fn memory_is_low(min_available_mb: u64) -> bool {
+    min_available_mb > 0
+        && available_memory_bytes()
+            .is_some_and(|bytes| bytes < min_available_mb.saturating_mul(1024 * 1024))
+}
+
+fn elapsed_ms(since: Instant) -> u64 {
+    u64::try_from(since.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Synthesizes `job`, timing the query and the synthesis apart; everything since
+/// `started` before that went into getting the model loaded. The moras are read
+/// off the same query the audio comes from.
+fn synthesize_timed(
+    core: &VoicevoxCore,
+    job: &SynthesisJob,
+    style_id: u32,
+    started: Instant,
+) -> anyhow::Result<(Vec<u8>, SynthesisTimings, Vec<MoraTiming>)> {
+    let model_load_ms = elapsed_ms(started);
+    let query_started = Instant::now();
+    let query = core.create_audio_query_with_options(&job.text, style_id, job.options)?;
+    let audio_query_ms = elapsed_ms(query_started);
+    let synthesis_started = Instant::now();
+    let wav_data = core.synthesize_query(&query, style_id)?;
+    Ok((
+        wav_data,
+        SynthesisTimings {
+            model_load_ms,
+            audio_query_ms,
+            synthesis_ms: elapsed_ms(synthesis_started),
+            ..SynthesisTimings::default()
+        },
+        mora_timings(&query),
+    ))
+}
+
+fn run_job(
+    core: &VoicevoxCore,
+    job: &SynthesisJob,
+    style_id: u32,
+    started: Instant,
+) -> Result<DaemonServiceResult, DaemonServiceError> {
+    let synthesis_result = match job.output {
+        JobOutput::Wav => {
+            synthesize_timed(core, job, style_id, started).map(|(wav_data, timings, moras)| {
+                DaemonServiceResult::SynthesizeResult {
+                    wav_data,
+                    warnings: Vec::new(),
+                    timings,
+                    moras,
+                }
+            })
+        }
+        JobOutput::AudioQuery => core
+            .create_audio_query_with_options(&job.text, style_id, job.options)
+            .and_then(|query| Ok(serde_json::to_string(&query)?))
+            .map(|query_json| DaemonServiceResult::AudioQuery { query_json }),
+        JobOutput::Prewarm => core
+            .synthesize_with_options(&job.text, style_id, job.options)
+            .map(|_| DaemonServiceResult::Prewarmed {
+                elapsed_ms: elapsed_ms(started),
+            }),
+    };
+
+    synthesis_result.map_err(|error| {
+        DaemonServiceError::new(
+            DaemonServiceErrorKind::SynthesisFailed,
+            format!("Synthesis failed: {error}"),
+        )
+    })
+}
+
 impl DaemonSynthesisExecutor {
-    pub(super) fn new() -> Self {
-        Self
+    /// Creates an executor whose jobs run on a thread pinned to `cpus`, or on the
+    /// submitting thread when `cpus` is empty or no thread can be started.
+    pub(super) fn new(cpus: Vec<usize>) -> Self {
+        let resident = ResidentModels::default();
+        let lookups = Arc::<ModelCacheLookups>::default();
+        let core = || ExecutorCore {
+            resident: resident.clone(),
+            lookups: Arc::clone(&lookups),
+            cache: None,
+        };
+        let thread = if cpus.is_empty() {
+            ExecutorThread::Caller(core())
+        } else {
+            match spawn_pinned_thread(cpus, core()) {
+                Ok(jobs) => ExecutorThread::Pinned(jobs),
+                Err(error) => {
+                    crate::infrastructure::logging::warn(&format!(
+                        "Failed to start a pinned synthesis thread, synthesizing unpinned: {error}"
+                    ));
+                    ExecutorThread::Caller(core())
+                }
+            }
+        };
+        Self {
+            resident,
+            lookups,
+            thread,
+        }
+    }
+
+    pub(super) fn resident_models(&self) -> ResidentModels {
+        self.resident.clone()
+    }
+
+    pub(super) fn model_cache_lookups(&self) -> Arc<ModelCacheLookups> {
+        Arc::clone(&self.lookups)
     }
 
+    /// Runs `job`, on this executor's pinned thread when it has one.
     pub(super) fn synthesize(
+        &mut self,
+        catalog: &Arc<ModelCatalog>,
+        events: &Arc<EventBus>,
+        job: SynthesisJob,
+        warnings: &mut Vec<DaemonWarning>,
+    ) -> Result<DaemonServiceResult, DaemonServiceError> {
+        let jobs = match &mut self.thread {
+            ExecutorThread::Caller(core) => return core.synthesize(catalog, events, job, warnings),
+            ExecutorThread::Pinned(jobs) => jobs,
+        };
+        let stopped = || {
+            DaemonServiceError::new(
+                DaemonServiceErrorKind::SynthesisFailed,
+                "The pinned synthesis thread has stopped",
+            )
+        };
+        let (reply, answer) = mpsc::sync_channel(1);
+        jobs.send(PinnedJob {
+            catalog: Arc::clone(catalog),
+            events: Arc::clone(events),
+            job,
+            reply,
+        })
+        .map_err(|_| stopped())?;
+        let (result, job_warnings) = answer.recv().map_err(|_| stopped())?;
+        warnings.extend(job_warnings);
+        result
+    }
+}
+
+impl ExecutorCore {
+    fn synthesize(
         &mut self,
         catalog: &ModelCatalog,
-        text: String,
-        requested_id: u32,
-        rate: f32,
+        events: &EventBus,
+        job: SynthesisJob,
+        warnings: &mut Vec<DaemonWarning>,
     ) -> Result<DaemonServiceResult, DaemonServiceError> {
-        let (style_id, model_id) = match catalog.resolve_synthesis_target(requested_id) {
+        let (style_id, model_id) = match catalog.resolve_synthesis_target(job.style_id) {
             TargetResolution::Exists { style_id, model_id } => (style_id, model_id),
             TargetResolution::Missing { message } => {
                 return Err(DaemonServiceError::new(
@@ -66,45 +456,260 @@ impl DaemonSynthesisExecutor {
                 ));
             }
         };
+
+        if job.model_cache.max_loaded_models == 0 {
+            self.drop_cache(events);
+            return self.synthesize_uncached(catalog, events, &job, (style_id, model_id), warnings);
+        }
+
+        let started = Instant::now();
+        let core = self.cached_core(catalog, events, &job, model_id, warnings)?;
+        run_job(core, &job, style_id, started)
+    }
+
+    /// Loads the model into a fresh core and unloads it once the job is done.
+    fn synthesize_uncached(
+        &self,
+        catalog: &ModelCatalog,
+        events: &EventBus,
+        job: &SynthesisJob,
+        (style_id, model_id): (u32, u32),
+        warnings: &mut Vec<DaemonWarning>,
+    ) -> Result<DaemonServiceResult, DaemonServiceError> {
         let model_path = catalog.get_model_path(model_id);
 
+        let started = Instant::now();
         let _allocator_relief = AllocatorReliefGuard;
-        let core = VoicevoxCore::new().map_err(|error| {
-            DaemonServiceError::new(
-                DaemonServiceErrorKind::ModelLoadFailed,
-                format!("Failed to initialize VOICEVOX core for synthesis: {error}"),
-            )
-        })?;
+        let core = create_core(job.inference)?;
+        load_model(&core, model_id)?;
 
-        if let Err(error) = core.load_specific_model(model_id) {
-            crate::infrastructure::logging::error(&format!(
-                "Failed to load model {model_id}: {error}"
-            ));
-            return Err(DaemonServiceError::new(
-                DaemonServiceErrorKind::ModelLoadFailed,
-                format!("Failed to load model {model_id} for synthesis: {error}"),
-            ));
+        self.resident.set(vec![model_id]);
+        events.emit(DaemonEvent::ModelLoaded { model_id });
+
+        // RAII guard ensures the model is always unloaded, even on panic or
+        // task cancellation. Matches DaemonRequestHandling.tla ClientDisconnect:
+        //   mutex_holder = c => model_loaded' = FALSE
+        let _model_guard = ModelUnloadGuard {
+            core: &core,
+            model_id,
+            model_path,
+            events,
+            resident: &self.resident,
+            warnings,
+        };
+        run_job(&core, job, style_id, started)
+    }
+
+    /// Returns the cached core with `model_id` loaded, evicting least recently used
+    /// models first when the cache is full or memory runs low.
+    fn cached_core(
+        &mut self,
+        catalog: &ModelCatalog,
+        events: &EventBus,
+        job: &SynthesisJob,
+        model_id: u32,
+        warnings: &mut Vec<DaemonWarning>,
+    ) -> Result<&VoicevoxCore, DaemonServiceError> {
+        if self
+            .cache
+            .as_ref()
+            .is_some_and(|cache| !cache.models.serves(job.inference))
+        {
+            self.drop_cache(events);
         }
+        let cache = match self.cache.take() {
+            Some(cache) => cache,
+            None => ModelCache {
+                core: create_core(job.inference)?,
+                models: CachedModels::new(job.inference),
+            },
+        };
+        let cache = self.cache.insert(cache);
+        cache.core.refresh_user_dict();
 
-        let synthesis_result = {
-            // RAII guard ensures the model is always unloaded, even on panic or
-            // task cancellation. Matches DaemonRequestHandling.tla ClientDisconnect:
-            //   mutex_holder = c => model_loaded' = FALSE
-            let _model_guard = ModelUnloadGuard {
-                core: &core,
-                model_id,
-                model_path,
-            };
+        let limit = job.model_cache;
+        let core = &cache.core;
+        let hit = cache.models.acquire(
+            model_id,
+            limit.max_loaded_models,
+            &self.resident,
+            || memory_is_low(limit.min_available_mb),
+            |lru| {
+                unload_model(core, lru, catalog.get_model_path(lru), events, warnings);
+                release_unused_allocator_memory();
+            },
+            |model_id| {
+                load_model(core, model_id)?;
+                events.emit(DaemonEvent::ModelLoaded { model_id });
+                Ok(())
+            },
+        );
+        self.lookups.record(matches!(hit, Ok(true)));
+        hit?;
+        Ok(core)
+    }
 
-            core.synthesize_with_rate(&text, style_id, rate)
+    /// Drops the cached core and every model it holds.
+    fn drop_cache(&mut self, events: &EventBus) {
+        let Some(cache) = self.cache.take() else {
+            return;
         };
+        drop(cache.core);
+        for model_id in cache.models.models {
+            events.emit(DaemonEvent::ModelUnloaded { model_id });
+        }
+        self.resident.set(Vec::new());
+        release_unused_allocator_memory();
+    }
+}
 
-        match synthesis_result {
-            Ok(wav_data) => Ok(DaemonServiceResult::SynthesizeResult { wav_data }),
-            Err(error) => Err(DaemonServiceError::new(
-                DaemonServiceErrorKind::SynthesisFailed,
-                format!("Synthesis failed: {error}"),
-            )),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached(models: &[u32]) -> CachedModels {
+        CachedModels {
+            inference: InferenceOptions::default(),
+            models: models.to_vec(),
+        }
+    }
+
+    /// Acquires `model_id` without memory pressure, returning the result and the
+    /// models unloaded on the way.
+    fn acquire(
+        cache: &mut CachedModels,
+        model_id: u32,
+        max_loaded_models: usize,
+        resident: &ResidentModels,
+    ) -> (Result<bool, ()>, Vec<u32>) {
+        let mut unloaded = Vec::new();
+        let result = cache.acquire(
+            model_id,
+            max_loaded_models,
+            resident,
+            || false,
+            |id| unloaded.push(id),
+            |_| Ok(()),
+        );
+        (result, unloaded)
+    }
+
+    #[test]
+    fn a_hit_becomes_the_most_recently_used_model() {
+        let mut cache = cached(&[1, 2, 3]);
+        let resident = ResidentModels::default();
+
+        assert_eq!(acquire(&mut cache, 2, 3, &resident), (Ok(true), vec![]));
+        assert_eq!(cache.models, [1, 3, 2]);
+        assert_eq!(resident.snapshot(), [1, 3, 2]);
+    }
+
+    #[test]
+    fn a_full_cache_evicts_the_least_recently_used_model() {
+        let mut cache = cached(&[1, 2]);
+        let resident = ResidentModels::default();
+
+        assert_eq!(acquire(&mut cache, 3, 2, &resident), (Ok(false), vec![1]));
+        assert_eq!(cache.models, [2, 3]);
+        assert_eq!(resident.snapshot(), [2, 3]);
+
+        let mut single = cached(&[1]);
+        assert_eq!(acquire(&mut single, 2, 1, &resident), (Ok(false), vec![1]));
+        assert_eq!(single.models, [2]);
+    }
+
+    #[test]
+    fn low_memory_evicts_until_enough_is_free() {
+        let mut cache = cached(&[1, 2, 3]);
+        let resident = ResidentModels::default();
+        let mut low_checks = 2;
+        let mut unloaded = Vec::new();
+
+        let hit = cache.acquire(
+            4,
+            8,
+            &resident,
+            || {
+                low_checks -= 1;
+                low_checks >= 0
+            },
+            |id| unloaded.push(id),
+            |_| Ok::<(), ()>(()),
+        );
+
+        assert_eq!(hit, Ok(false));
+        assert_eq!(unloaded, [1, 2]);
+        assert_eq!(cache.models, [3, 4]);
+        assert!(!memory_is_low(0), "0 turns the memory check off");
+    }
+
+    #[test]
+    fn a_failed_load_is_not_listed_as_resident() {
+        let mut cache = cached(&[1, 2]);
+        let resident = ResidentModels::default();
+        resident.set(vec![1, 2]);
+
+        let result = cache.acquire(3, 2, &resident, || false, |_| {}, |_| Err("no model"));
+
+        assert_eq!(result, Err("no model"));
+        assert_eq!(cache.models, [2]);
+        assert_eq!(resident.snapshot(), [2]);
+    }
+
+    #[test]
+    fn evicting_a_model_leaves_other_executors_copies_loaded() {
+        // Two executors served the same model, so each loaded its own copy.
+        let (mut first, mut second) = (cached(&[1]), cached(&[1]));
+        let (first_resident, second_resident) =
+            (ResidentModels::default(), ResidentModels::default());
+        second_resident.set(vec![1]);
+
+        assert_eq!(
+            acquire(&mut first, 2, 1, &first_resident),
+            (Ok(false), vec![1])
+        );
+        assert_eq!(second.models, [1]);
+        assert_eq!(second_resident.snapshot(), [1]);
+        assert_eq!(
+            acquire(&mut second, 1, 1, &second_resident),
+            (Ok(true), vec![])
+        );
+    }
+
+    #[test]
+    fn a_pinned_executor_answers_every_job_from_its_thread() {
+        let mut executor = DaemonSynthesisExecutor::new(vec![0]);
+        assert!(matches!(executor.thread, ExecutorThread::Pinned(_)));
+        let catalog = Arc::new(ModelCatalog::default());
+        let events = Arc::new(EventBus::new());
+
+        for _ in 0..2 {
+            let job = SynthesisJob {
+                text: "あ".to_string(),
+                style_id: 1,
+                options: SynthesisOptions::default(),
+                output: JobOutput::Wav,
+                inference: InferenceOptions::default(),
+                model_cache: ModelCacheConfig::default(),
+            };
+            let result = executor.synthesize(&catalog, &events, job, &mut Vec::new());
+            assert!(matches!(
+                result,
+                Err(DaemonServiceError {
+                    kind: DaemonServiceErrorKind::InvalidTargetId,
+                    ..
+                })
+            ));
         }
     }
+
+    #[test]
+    fn changed_inference_settings_need_a_new_core() {
+        let cache = cached(&[1]);
+        assert!(cache.serves(InferenceOptions::default()));
+        assert!(!cache.serves(InferenceOptions {
+            cpu_threads: 2,
+            ..InferenceOptions::default()
+        }));
+    }
 }
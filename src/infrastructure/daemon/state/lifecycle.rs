@@ -0,0 +1,117 @@
+use std::sync::{Mutex, PoisonError};
+use tokio::sync::{mpsc, oneshot, watch};
+
+/// A `Shutdown` or `Reload` that arrived over IPC, for the server loop to carry out.
+#[derive(Debug)]
+pub enum LifecycleRequest {
+    Shutdown,
+    /// Answered with the number of models found, or why the reload failed.
+    Reload(oneshot::Sender<Result<usize, String>>),
+}
+
+/// Hands lifecycle requests to the server loop, which owns the listeners and knows
+/// where `daemon.toml` lives, and counts requests so shutdown can wait for them.
+pub(super) struct Lifecycle {
+    sender: mpsc::UnboundedSender<LifecycleRequest>,
+    receiver: Mutex<Option<mpsc::UnboundedReceiver<LifecycleRequest>>>,
+    in_flight: watch::Sender<usize>,
+}
+
+/// Counts one request as in flight until dropped, also when its task is aborted.
+pub(super) struct InFlightRequest<'a>(&'a watch::Sender<usize>);
+
+impl Drop for InFlightRequest<'_> {
+    fn drop(&mut self) {
+        self.0.send_modify(|count| *count -= 1);
+    }
+}
+
+impl Lifecycle {
+    pub(super) fn new() -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        Self {
+            sender,
+            receiver: Mutex::new(Some(receiver)),
+            in_flight: watch::Sender::new(0),
+        }
+    }
+
+    /// The requests to carry out; only the first caller gets them.
+    pub(super) fn take_requests(&self) -> Option<mpsc::UnboundedReceiver<LifecycleRequest>> {
+        self.receiver
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+
+    pub(super) fn request_shutdown(&self) {
+        let _ = self.sender.send(LifecycleRequest::Shutdown);
+    }
+
+    /// Waits for the server loop to reload.
+    pub(super) async fn reload(&self) -> Result<usize, String> {
+        let (reply, answer) = oneshot::channel();
+        self.sender
+            .send(LifecycleRequest::Reload(reply))
+            .map_err(|_| "The daemon is not accepting reloads".to_string())?;
+        answer
+            .await
+            .map_err(|_| "The daemon stopped before reloading".to_string())?
+    }
+
+    pub(super) fn begin_request(&self) -> InFlightRequest<'_> {
+        self.in_flight.send_modify(|count| *count += 1);
+        InFlightRequest(&self.in_flight)
+    }
+
+    /// Resolves once no request is in flight.
+    pub(super) async fn idle(&self) {
+        let mut in_flight = self.in_flight.subscribe();
+        let _ = in_flight.wait_for(|count| *count == 0).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn idle_waits_for_requests_in_flight() {
+        let lifecycle = Lifecycle::new();
+        lifecycle.idle().await;
+
+        let request = lifecycle.begin_request();
+        assert!(
+            tokio::time::timeout(Duration::from_millis(20), lifecycle.idle())
+                .await
+                .is_err()
+        );
+        drop(request);
+        lifecycle.idle().await;
+    }
+
+    #[tokio::test]
+    async fn reloads_are_answered_by_the_taker() {
+        let lifecycle = Lifecycle::new();
+        let mut requests = lifecycle.take_requests().expect("requests");
+        assert!(lifecycle.take_requests().is_none());
+
+        let server = async {
+            match requests.recv().await {
+                Some(LifecycleRequest::Reload(reply)) => {
+                    let _ = reply.send(Ok(3));
+                }
+                other => panic!("unexpected {other:?}"),
+            }
+        };
+        let (reloaded, ()) = tokio::join!(lifecycle.reload(), server);
+        assert_eq!(reloaded, Ok(3));
+
+        lifecycle.request_shutdown();
+        assert!(matches!(
+            requests.recv().await,
+            Some(LifecycleRequest::Shutdown)
+        ));
+    }
+}
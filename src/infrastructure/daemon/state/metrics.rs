@@ -0,0 +1,134 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::infrastructure::ipc::{DaemonStats, LatencyStats};
+use crate::infrastructure::usage_stats::CacheCounts;
+
+/// Latencies kept for percentiles; older ones only count toward the average.
+const LATENCY_SAMPLES: usize = 1024;
+
+#[derive(Debug, Default)]
+struct SynthesisCounts {
+    total: u64,
+    per_style: BTreeMap<u32, u64>,
+    latency_sum: Duration,
+    recent_latencies: VecDeque<Duration>,
+}
+
+/// In-memory counters for `GetStats`, reset when the daemon restarts.
+///
+/// Unlike the opt-in usage statistics file, these are always kept and never
+/// written anywhere.
+#[derive(Debug)]
+pub(super) struct DaemonMetrics {
+    started: Instant,
+    syntheses: Mutex<SynthesisCounts>,
+}
+
+impl DaemonMetrics {
+    pub(super) fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            syntheses: Mutex::default(),
+        }
+    }
+
+    pub(super) fn record_synthesis(&self, style_id: u32, latency: Duration) {
+        let mut counts = self
+            .syntheses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        counts.total += 1;
+        *counts.per_style.entry(style_id).or_default() += 1;
+        counts.latency_sum += latency;
+        if counts.recent_latencies.len() == LATENCY_SAMPLES {
+            counts.recent_latencies.pop_front();
+        }
+        counts.recent_latencies.push_back(latency);
+    }
+
+    /// Combines the synthesis counters with the model counts kept elsewhere.
+    pub(super) fn snapshot(
+        &self,
+        (model_loads, model_unloads): (u64, u64),
+        model_cache: CacheCounts,
+    ) -> DaemonStats {
+        let counts = self
+            .syntheses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        DaemonStats {
+            uptime_secs: self.started.elapsed().as_secs(),
+            syntheses: counts.total,
+            style_syntheses: counts.per_style.clone(),
+            latency: latency_stats(&counts),
+            model_loads,
+            model_unloads,
+            model_cache_hits: model_cache.hits,
+            model_cache_misses: model_cache.misses,
+        }
+    }
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+fn latency_stats(counts: &SynthesisCounts) -> Option<LatencyStats> {
+    if counts.total == 0 {
+        return None;
+    }
+    let mut recent = counts.recent_latencies.iter().copied().collect::<Vec<_>>();
+    recent.sort_unstable();
+    // Nearest rank: the smallest sample at or above the percentile.
+    let percentile = |percent: usize| {
+        let rank = (recent.len() * percent).div_ceil(100).max(1);
+        millis(recent[rank - 1])
+    };
+    Some(LatencyStats {
+        average_ms: millis(counts.latency_sum / u32::try_from(counts.total).unwrap_or(u32::MAX)),
+        p50_ms: percentile(50),
+        p95_ms: percentile(95),
+        p99_ms: percentile(99),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_counts_styles_and_latency_percentiles() {
+        let metrics = DaemonMetrics::new();
+        assert_eq!(
+            metrics.snapshot((0, 0), CacheCounts::default()).latency,
+            None
+        );
+
+        for ms in 1..=100 {
+            metrics.record_synthesis(if ms % 10 == 0 { 1 } else { 3 }, Duration::from_millis(ms));
+        }
+        let stats = metrics.snapshot(
+            (2, 1),
+            CacheCounts {
+                hits: 98,
+                misses: 2,
+            },
+        );
+
+        assert_eq!(stats.syntheses, 100);
+        assert_eq!(stats.style_syntheses, BTreeMap::from([(1, 10), (3, 90)]));
+        assert_eq!(
+            stats.latency,
+            Some(LatencyStats {
+                average_ms: 50,
+                p50_ms: 50,
+                p95_ms: 95,
+                p99_ms: 99,
+            })
+        );
+        assert_eq!((stats.model_loads, stats.model_unloads), (2, 1));
+        assert_eq!(stats.model_cache_hits, 98);
+    }
+}
@@ -1,32 +1,309 @@
-use tokio::sync::Mutex;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Instant;
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tokio::sync::{Mutex, Semaphore};
+
+use crate::infrastructure::ipc::{DaemonEvent, DaemonWarning};
+use crate::infrastructure::usage_stats::CacheCounts;
 
 use super::catalog::ModelCatalog;
-use super::executor::DaemonSynthesisExecutor;
-use super::result::{DaemonServiceError, DaemonServiceResult};
+use super::events::EventBus;
+use super::executor::{DaemonSynthesisExecutor, ModelCacheLookups, ResidentModels, SynthesisJob};
+use super::result::{DaemonServiceError, DaemonServiceErrorKind, DaemonServiceResult};
+
+const NO_STYLE: u64 = u64::MAX;
+
+/// Snapshot of the synthesis queue for status displays.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SynthesisActivity {
+    /// Style of the most recent synthesis, if any has run.
+    pub current_style_id: Option<u32>,
+    /// Requests running or waiting for the executor.
+    pub queue_length: usize,
+    /// Models held in memory by any executor, in ascending order.
+    pub loaded_model_ids: Vec<u32>,
+}
+
+/// Counts a request as queued until it finishes or its future is dropped.
+struct QueueSlot<'a> {
+    queue_length: &'a AtomicUsize,
+    events: &'a EventBus,
+}
+
+impl<'a> QueueSlot<'a> {
+    fn enter(queue_length: &'a AtomicUsize, events: &'a EventBus) -> Self {
+        let length = queue_length.fetch_add(1, Ordering::SeqCst) + 1;
+        emit_queue_length(events, length);
+        Self {
+            queue_length,
+            events,
+        }
+    }
+}
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        let length = self.queue_length.fetch_sub(1, Ordering::SeqCst) - 1;
+        emit_queue_length(self.events, length);
+    }
+}
+
+fn emit_queue_length(events: &EventBus, length: usize) {
+    events.emit(DaemonEvent::QueueChanged {
+        length: u32::try_from(length).unwrap_or(u32::MAX),
+    });
+}
 
 /// Explicitly serialized synthesis policy.
 ///
-/// VOICEVOX core/model loading is executed under a single async mutex to keep memory usage
-/// predictable under the current no-model-cache design.
+/// VOICEVOX core/model loading is executed under one async mutex per executor to keep
+/// memory usage predictable. With the default single executor every request is
+/// serialized; `[workers]` adds executors that run side by side. Each executor holds
+/// one model per request, or up to `[model_cache]`'s limit between requests.
+///
+/// Models are never shared between requests: every core belongs to one executor, and
+/// a request holds its executor's mutex from model load through unload or eviction.
+/// Two requests for the same model on different executors each load their own copy,
+/// so no request can unload a model another is using, and no reference count is kept.
 pub(super) struct SerializedSynthesisPolicy {
-    executor: Mutex<DaemonSynthesisExecutor>,
+    executors: Vec<Mutex<DaemonSynthesisExecutor>>,
+    /// One permit per executor, so a request holding a permit always finds one free.
+    permits: Semaphore,
+    queue_length: AtomicUsize,
+    stop_epoch: AtomicU64,
+    current_style_id: AtomicU64,
+    resident_models: Vec<ResidentModels>,
+    model_cache_lookups: Vec<Arc<ModelCacheLookups>>,
 }
 
 impl SerializedSynthesisPolicy {
-    pub(super) fn new(executor: DaemonSynthesisExecutor) -> Self {
+    pub(super) fn new(executors: Vec<DaemonSynthesisExecutor>) -> Self {
         Self {
-            executor: Mutex::new(executor),
+            resident_models: executors
+                .iter()
+                .map(DaemonSynthesisExecutor::resident_models)
+                .collect(),
+            model_cache_lookups: executors
+                .iter()
+                .map(DaemonSynthesisExecutor::model_cache_lookups)
+                .collect(),
+            permits: Semaphore::new(executors.len()),
+            executors: executors.into_iter().map(Mutex::new).collect(),
+            queue_length: AtomicUsize::new(0),
+            stop_epoch: AtomicU64::new(0),
+            current_style_id: AtomicU64::new(NO_STYLE),
+        }
+    }
+
+    pub(super) fn activity(&self) -> SynthesisActivity {
+        let current_style_id = self.current_style_id.load(Ordering::SeqCst);
+        let mut loaded_model_ids = self
+            .resident_models
+            .iter()
+            .flat_map(ResidentModels::snapshot)
+            .collect::<Vec<_>>();
+        loaded_model_ids.sort_unstable();
+        loaded_model_ids.dedup();
+        SynthesisActivity {
+            current_style_id: u32::try_from(current_style_id).ok(),
+            queue_length: self.queue_length.load(Ordering::SeqCst),
+            loaded_model_ids,
+        }
+    }
+
+    /// Model cache hits and misses across executors since the last call.
+    pub(super) fn take_model_cache_lookups(&self) -> CacheCounts {
+        let mut counts = CacheCounts::default();
+        for lookups in &self.model_cache_lookups {
+            counts.add(lookups.take());
+        }
+        counts
+    }
+
+    pub(super) fn model_cache_totals(&self) -> CacheCounts {
+        let mut counts = CacheCounts::default();
+        for lookups in &self.model_cache_lookups {
+            counts.add(lookups.totals());
         }
+        counts
+    }
+
+    /// Cancels every request still waiting for the executor; returns how many were queued.
+    ///
+    /// The synthesis already running finishes normally because Core calls are blocking.
+    pub(super) fn stop_pending(&self) -> usize {
+        self.stop_epoch.fetch_add(1, Ordering::SeqCst);
+        self.queue_length.load(Ordering::SeqCst)
     }
 
     pub(super) async fn synthesize(
         &self,
-        catalog: &ModelCatalog,
-        text: String,
-        requested_id: u32,
-        rate: f32,
+        catalog: &Arc<ModelCatalog>,
+        events: &Arc<EventBus>,
+        job: SynthesisJob,
+        warnings: &mut Vec<DaemonWarning>,
     ) -> Result<DaemonServiceResult, DaemonServiceError> {
-        let mut executor = self.executor.lock().await;
-        executor.synthesize(catalog, text, requested_id, rate)
+        let epoch = self.stop_epoch.load(Ordering::SeqCst);
+        let queued = Instant::now();
+        let _queue_slot = QueueSlot::enter(&self.queue_length, events);
+        let _permit = self.permits.acquire().await.map_err(|_| {
+            DaemonServiceError::new(
+                DaemonServiceErrorKind::SynthesisFailed,
+                "Synthesis executors are shut down",
+            )
+        })?;
+        let mut executor = self
+            .executors
+            .iter()
+            .find_map(|executor| executor.try_lock().ok())
+            .ok_or_else(|| {
+                DaemonServiceError::new(
+                    DaemonServiceErrorKind::SynthesisFailed,
+                    "No synthesis executor is free",
+                )
+            })?;
+        if self.stop_epoch.load(Ordering::SeqCst) != epoch {
+            return Err(DaemonServiceError::new(
+                DaemonServiceErrorKind::SynthesisFailed,
+                "Synthesis canceled by a stop request",
+            ));
+        }
+
+        let requested_id = job.style_id;
+        self.current_style_id
+            .store(u64::from(requested_id), Ordering::SeqCst);
+        events.emit(DaemonEvent::SynthesisStarted {
+            style_id: requested_id,
+            text_chars: u32::try_from(job.text.chars().count()).unwrap_or(u32::MAX),
+        });
+        let queue_ms = u64::try_from(queued.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let mut result = run_blocking(|| executor.synthesize(catalog, events, job, warnings));
+        if let Ok(DaemonServiceResult::SynthesizeResult { timings, .. }) = &mut result {
+            timings.queue_ms = queue_ms;
+        }
+        events.emit(DaemonEvent::SynthesisFinished {
+            style_id: requested_id,
+            succeeded: result.is_ok(),
+        });
+        result
+    }
+}
+
+/// Runs blocking Core or filesystem work without starving the runtime.
+///
+/// `[workers] count` may exceed the runtime's worker threads, so on the
+/// multi-threaded runtime the worker first hands its other tasks to a new thread:
+/// accepting clients, `Status`, and noticing `Cancel` or a disconnect keep working
+/// while every executor is busy. Other runtimes, as in tests, just run `work`,
+/// where `block_in_place` would panic.
+pub(super) fn run_blocking<T>(work: impl FnOnce() -> T) -> T {
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(work)
+        }
+        _ => work(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::executor::JobOutput;
+    use super::*;
+    use crate::infrastructure::core::InferenceOptions;
+    use crate::infrastructure::daemon::config::ModelCacheConfig;
+    use crate::infrastructure::ipc::SynthesisOptions;
+    use std::sync::atomic::AtomicBool;
+    use std::time::Duration;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn blocking_synthesis_leaves_the_runtime_running() {
+        let ran = Arc::new(AtomicBool::new(false));
+        tokio::spawn({
+            let ran = Arc::clone(&ran);
+            async move { ran.store(true, Ordering::SeqCst) }
+        });
+
+        // The only worker blocks until the spawned task has run elsewhere.
+        let observed = run_blocking(|| {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while !ran.load(Ordering::SeqCst) && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            ran.load(Ordering::SeqCst)
+        });
+        assert!(observed);
+        assert_eq!(run_blocking(|| 2 + 2), 4);
+    }
+
+    #[test]
+    fn queue_slots_track_length_and_announce_changes() {
+        let events = EventBus::new();
+        let mut receiver = events.subscribe();
+        let queue_length = AtomicUsize::new(0);
+
+        let first = QueueSlot::enter(&queue_length, &events);
+        let second = QueueSlot::enter(&queue_length, &events);
+        assert_eq!(queue_length.load(Ordering::SeqCst), 2);
+        drop(first);
+        drop(second);
+
+        let lengths = std::iter::from_fn(|| receiver.try_recv().ok())
+            .map(|event| match event {
+                DaemonEvent::QueueChanged { length } => length,
+                other => panic!("unexpected event {other:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(lengths, vec![1, 2, 1, 0]);
+    }
+
+    #[tokio::test]
+    async fn a_request_never_enters_an_executor_another_request_holds() {
+        async fn synthesize(
+            policy: &SerializedSynthesisPolicy,
+        ) -> Result<DaemonServiceResult, DaemonServiceError> {
+            let job = SynthesisJob {
+                text: "あ".to_string(),
+                style_id: 1,
+                options: SynthesisOptions::default(),
+                output: JobOutput::Wav,
+                inference: InferenceOptions::default(),
+                model_cache: ModelCacheConfig::default(),
+            };
+            let catalog = Arc::new(ModelCatalog::default());
+            let events = Arc::new(EventBus::new());
+            policy
+                .synthesize(&catalog, &events, job, &mut Vec::new())
+                .await
+        }
+
+        let policy = SerializedSynthesisPolicy::new(vec![
+            DaemonSynthesisExecutor::new(Vec::new()),
+            DaemonSynthesisExecutor::new(Vec::new()),
+        ]);
+
+        // With the first executor busy, the request runs on the second one, which
+        // rejects the unknown style itself.
+        let first = policy.executors[0].lock().await;
+        let result = synthesize(&policy).await;
+        assert!(matches!(
+            result,
+            Err(DaemonServiceError {
+                kind: DaemonServiceErrorKind::InvalidTargetId,
+                ..
+            })
+        ));
+
+        // With both busy, it is refused rather than sharing either executor's core.
+        let second = policy.executors[1].lock().await;
+        let result = synthesize(&policy).await;
+        assert!(matches!(
+            result,
+            Err(DaemonServiceError {
+                kind: DaemonServiceErrorKind::SynthesisFailed,
+                ref message,
+            }) if message == "No synthesis executor is free"
+        ));
+        drop((first, second));
     }
 }
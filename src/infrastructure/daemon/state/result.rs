@@ -1,10 +1,33 @@
 use std::collections::HashMap;
 
+use super::policy::SynthesisActivity;
+
+use crate::domain::voice_alias::VoiceAliases;
+use crate::infrastructure::daemon::config::PlaybackPolicy;
+use crate::infrastructure::ipc::{
+    DaemonStats, DaemonWarning, MoraTiming, SlowRequest, SynthesisTimings,
+};
 use crate::infrastructure::voicevox::{AvailableModel, Speaker};
 
 pub(super) enum DaemonServiceResult {
     SynthesizeResult {
         wav_data: Vec<u8>,
+        warnings: Vec<DaemonWarning>,
+        timings: SynthesisTimings,
+        moras: Vec<MoraTiming>,
+    },
+    /// `SynthesizeResult` for a client that asked for the stage timings.
+    SynthesizeTimedResult {
+        wav_data: Vec<u8>,
+        warnings: Vec<DaemonWarning>,
+        timings: SynthesisTimings,
+    },
+    /// `SynthesizeResult` for a client that asked for the mora timings.
+    SynthesizeWithMorasResult {
+        wav_data: Vec<u8>,
+        warnings: Vec<DaemonWarning>,
+        timings: SynthesisTimings,
+        moras: Vec<MoraTiming>,
     },
     SpeakersListWithModels {
         speakers: Vec<Speaker>,
@@ -13,6 +36,44 @@ pub(super) enum DaemonServiceResult {
     ModelsList {
         models: Vec<AvailableModel>,
     },
+    Subscribed,
+    Authenticated,
+    Hello {
+        profile_applied: bool,
+    },
+    PlaybackPolicy(PlaybackPolicy),
+    Skipped,
+    Status {
+        activity: SynthesisActivity,
+        recent_warnings: Vec<DaemonWarning>,
+    },
+    AudioQuery {
+        query_json: String,
+    },
+    Cancelled,
+    Prewarmed {
+        elapsed_ms: u64,
+    },
+    ModelsPreloaded {
+        model_ids: Vec<u32>,
+        elapsed_ms: u64,
+    },
+    PowerPolicy {
+        defer_background: bool,
+    },
+    Stats(DaemonStats),
+    SlowLog {
+        threshold_ms: Option<u64>,
+        entries: Vec<SlowRequest>,
+    },
+    VoiceMapping(VoiceAliases),
+    UserDictReloaded {
+        word_count: usize,
+    },
+    ShuttingDown,
+    Reloaded {
+        model_count: usize,
+    },
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -20,6 +81,8 @@ pub(super) enum DaemonServiceErrorKind {
     InvalidTargetId,
     ModelLoadFailed,
     SynthesisFailed,
+    Internal,
+    ProtocolMismatch,
 }
 
 pub(super) struct DaemonServiceError {
@@ -0,0 +1,83 @@
+use std::collections::VecDeque;
+use std::sync::{Mutex, PoisonError};
+
+use crate::infrastructure::ipc::SlowRequest;
+
+/// Slow requests kept for `voicevox-daemon --slow-log`.
+const MAX_SLOW_REQUESTS: usize = 100;
+
+/// Recent syntheses over the `[slow_log]` threshold, also mirrored to the daemon log.
+pub(super) struct SlowLog {
+    recent: Mutex<VecDeque<SlowRequest>>,
+}
+
+impl SlowLog {
+    pub(super) fn new() -> Self {
+        Self {
+            recent: Mutex::new(VecDeque::with_capacity(MAX_SLOW_REQUESTS)),
+        }
+    }
+
+    /// Keeps `request` if it took at least `threshold_ms`.
+    pub(super) fn record_if_slow(&self, threshold_ms: u64, request: SlowRequest) {
+        if request.elapsed_ms < threshold_ms {
+            return;
+        }
+        let timings = request.timings;
+        crate::infrastructure::logging::warn(&format!(
+            "Slow request: style {}, {} chars, {}ms (queue {}ms, model load {}ms, query {}ms, synthesis {}ms){}",
+            request.style_id,
+            request.text_chars,
+            request.elapsed_ms,
+            timings.queue_ms,
+            timings.model_load_ms,
+            timings.audio_query_ms,
+            timings.synthesis_ms,
+            request
+                .app_id
+                .as_ref()
+                .map_or_else(String::new, |app_id| format!(" from {app_id}"))
+        ));
+        let mut recent = self.recent.lock().unwrap_or_else(PoisonError::into_inner);
+        if recent.len() == MAX_SLOW_REQUESTS {
+            recent.pop_front();
+        }
+        recent.push_back(request);
+    }
+
+    pub(super) fn recent(&self) -> Vec<SlowRequest> {
+        let recent = self.recent.lock().unwrap_or_else(PoisonError::into_inner);
+        recent.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::ipc::SynthesisTimings;
+
+    fn request(elapsed_ms: u64) -> SlowRequest {
+        SlowRequest {
+            finished_at: 0,
+            app_id: None,
+            style_id: 3,
+            text_chars: 10,
+            elapsed_ms,
+            timings: SynthesisTimings::default(),
+        }
+    }
+
+    #[test]
+    fn keeps_only_recent_requests_over_the_threshold() {
+        let log = SlowLog::new();
+        log.record_if_slow(1000, request(999));
+        assert!(log.recent().is_empty());
+
+        for elapsed_ms in 1000..1000 + MAX_SLOW_REQUESTS as u64 + 2 {
+            log.record_if_slow(1000, request(elapsed_ms));
+        }
+        let recent = log.recent();
+        assert_eq!(recent.len(), MAX_SLOW_REQUESTS);
+        assert_eq!(recent[0].elapsed_ms, 1002);
+    }
+}
@@ -0,0 +1,68 @@
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+
+use crate::infrastructure::usage_stats::{
+    UsageStats, load_usage_stats, save_usage_stats, usage_stats_path,
+};
+
+/// Writes usage statistics through to the stats file as requests finish.
+///
+/// The file is read on first use so counts from earlier runs carry on; an
+/// unreadable file is replaced rather than stopping synthesis.
+pub(super) struct UsageRecorder {
+    path: PathBuf,
+    stats: Mutex<Option<UsageStats>>,
+}
+
+impl UsageRecorder {
+    pub(super) fn new() -> Self {
+        Self::at(usage_stats_path())
+    }
+
+    fn at(path: PathBuf) -> Self {
+        Self {
+            path,
+            stats: Mutex::new(None),
+        }
+    }
+
+    pub(super) fn record(&self, update: impl FnOnce(&mut UsageStats)) {
+        let mut stats = self.stats.lock().unwrap_or_else(PoisonError::into_inner);
+        let stats = stats.get_or_insert_with(|| {
+            load_usage_stats(&self.path).unwrap_or_else(|error| {
+                crate::infrastructure::logging::warn(&format!(
+                    "Starting usage statistics afresh: {error:#}"
+                ));
+                UsageStats::default()
+            })
+        });
+        update(stats);
+        if let Err(error) = save_usage_stats(&self.path, stats) {
+            crate::infrastructure::logging::warn(&format!(
+                "Failed to save usage statistics: {error:#}"
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn continues_counts_from_an_existing_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("usage_stats.json");
+        let mut earlier = UsageStats::default();
+        earlier.record_voice(3, Duration::from_secs(1));
+        save_usage_stats(&path, &earlier).expect("save");
+
+        let recorder = UsageRecorder::at(path.clone());
+        recorder.record(|stats| stats.record_voice(3, Duration::from_secs(2)));
+
+        let stats = load_usage_stats(&path).expect("load");
+        assert_eq!(stats.voices[&3].requests, 2);
+        assert_eq!(stats.voices[&3].audio_seconds, 3.0);
+    }
+}
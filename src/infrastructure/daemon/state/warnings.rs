@@ -0,0 +1,61 @@
+use std::collections::VecDeque;
+use std::sync::{Mutex, PoisonError};
+
+use crate::infrastructure::ipc::DaemonWarning;
+
+/// Warnings kept for the `Status` RPC.
+const MAX_RECENT_WARNINGS: usize = 32;
+
+/// Recent non-fatal warnings, also mirrored to the daemon log.
+pub(super) struct WarningLog {
+    recent: Mutex<VecDeque<DaemonWarning>>,
+}
+
+impl WarningLog {
+    pub(super) fn new() -> Self {
+        Self {
+            recent: Mutex::new(VecDeque::with_capacity(MAX_RECENT_WARNINGS)),
+        }
+    }
+
+    pub(super) fn record(&self, warnings: &[DaemonWarning]) {
+        if warnings.is_empty() {
+            return;
+        }
+        let mut recent = self.recent.lock().unwrap_or_else(PoisonError::into_inner);
+        for warning in warnings {
+            crate::infrastructure::logging::warn(&warning.message);
+            if recent.len() == MAX_RECENT_WARNINGS {
+                recent.pop_front();
+            }
+            recent.push_back(warning.clone());
+        }
+    }
+
+    pub(super) fn recent(&self) -> Vec<DaemonWarning> {
+        let recent = self.recent.lock().unwrap_or_else(PoisonError::into_inner);
+        recent.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::ipc::DaemonWarningCode;
+
+    #[test]
+    fn keeps_only_the_most_recent_warnings() {
+        let log = WarningLog::new();
+        let warnings = (0..MAX_RECENT_WARNINGS + 2)
+            .map(|index| DaemonWarning {
+                code: DaemonWarningCode::ModelUnloadFailed,
+                message: format!("warning {index}"),
+            })
+            .collect::<Vec<_>>();
+        log.record(&warnings);
+
+        let recent = log.recent();
+        assert_eq!(recent.len(), MAX_RECENT_WARNINGS);
+        assert_eq!(recent[0].message, "warning 2");
+    }
+}
@@ -0,0 +1,136 @@
+//! Shared secret for daemon connections over TCP (`--listen`).
+//!
+//! Unix socket clients are checked by file permissions and peer credentials; a TCP
+//! client has to send this token in `Authenticate` before anything else.
+
+use anyhow::{Context, Result, ensure};
+use std::io::{Read, Write};
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+
+use crate::infrastructure::paths::get_config_dir;
+
+const TOKEN_FILE: &str = "daemon-token";
+const TOKEN_BYTES: usize = 32;
+
+/// Location of the daemon token: `daemon-token` in the user config directory.
+#[must_use]
+pub fn daemon_token_path() -> PathBuf {
+    get_config_dir().join(TOKEN_FILE)
+}
+
+/// Reads the token at `path`, creating a random one readable only by the user if
+/// the file does not exist yet.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or created, is empty, or can be read
+/// by group or others.
+pub fn load_or_create_daemon_token(path: &Path) -> Result<String> {
+    match read_daemon_token(path) {
+        Ok(token) => return Ok(token),
+        Err(error)
+            if error
+                .downcast_ref::<std::io::Error>()
+                .is_none_or(|error| error.kind() != std::io::ErrorKind::NotFound) =>
+        {
+            return Err(error);
+        }
+        Err(_) => {}
+    }
+
+    let mut bytes = [0u8; TOKEN_BYTES];
+    std::fs::File::open("/dev/urandom")
+        .and_then(|mut random| random.read_exact(&mut bytes))
+        .context("Failed to generate a daemon token")?;
+    let token: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+        .with_context(|| format!("Failed to create daemon token {}", path.display()))?;
+    writeln!(file, "{token}")
+        .with_context(|| format!("Failed to write daemon token {}", path.display()))?;
+    Ok(token)
+}
+
+/// Reads the token a client sends: `$VOICEVOX_DAEMON_TOKEN`, else the token file
+/// copied from the daemon's machine.
+///
+/// # Errors
+///
+/// Returns an error if neither is set, or the file is unreadable, empty, or can be
+/// read by group or others.
+pub fn client_daemon_token() -> Result<String> {
+    if let Some(token) = std::env::var(crate::config::ENV_VOICEVOX_DAEMON_TOKEN)
+        .ok()
+        .filter(|token| !token.trim().is_empty())
+    {
+        return Ok(token.trim().to_owned());
+    }
+    let path = daemon_token_path();
+    read_daemon_token(&path).with_context(|| {
+        format!(
+            "No daemon token: set {} or copy the daemon's {} to {}",
+            crate::config::ENV_VOICEVOX_DAEMON_TOKEN,
+            TOKEN_FILE,
+            path.display()
+        )
+    })
+}
+
+fn read_daemon_token(path: &Path) -> Result<String> {
+    let metadata = std::fs::metadata(path)?;
+    let mode = metadata.permissions().mode() & 0o777;
+    ensure!(
+        mode & 0o077 == 0,
+        "Daemon token {} must be readable only by its owner (mode {mode:o}); run chmod 600",
+        path.display()
+    );
+    let token = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read daemon token {}", path.display()))?;
+    let token = token.trim();
+    ensure!(
+        !token.is_empty(),
+        "Daemon token {} is empty",
+        path.display()
+    );
+    Ok(token.to_owned())
+}
+
+/// Compares tokens in time that depends only on their lengths.
+#[must_use]
+pub fn tokens_match(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0u8, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_is_created_once_and_kept_private() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("voicevox").join(TOKEN_FILE);
+
+        let token = load_or_create_daemon_token(&path).expect("create");
+        assert_eq!(token.len(), TOKEN_BYTES * 2);
+        assert_eq!(load_or_create_daemon_token(&path).expect("reload"), token);
+        assert!(tokens_match(&token, &token));
+        assert!(!tokens_match(&token, &token[1..]));
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).expect("chmod");
+        assert!(load_or_create_daemon_token(&path).is_err());
+    }
+}
@@ -0,0 +1,303 @@
+//! Readers for the official VOICEVOX editor's settings files.
+//!
+//! The editor keeps presets in its Electron `config.json`, while the user
+//! dictionary belongs to the bundled engine. Both are read-only here; nothing
+//! writes back to the editor's files.
+
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::domain::preset::VoicePreset;
+use crate::domain::user_dict::{
+    DEFAULT_WORD_PRIORITY, DictionaryWord, MAX_WORD_PRIORITY, WordType,
+};
+
+const EDITOR_APP_DIR: &str = "voicevox";
+const EDITOR_CONFIG_FILE: &str = "config.json";
+const ENGINE_APP_DIR: &str = "voicevox-engine";
+const ENGINE_USER_DICT_FILE: &str = "user_dict.json";
+
+/// The editor's `config.json` under the platform app-data directory.
+#[must_use]
+pub fn editor_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(EDITOR_APP_DIR).join(EDITOR_CONFIG_FILE))
+}
+
+/// The engine's `user_dict.json` under the platform local data directory.
+#[must_use]
+pub fn engine_user_dict_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join(ENGINE_APP_DIR).join(ENGINE_USER_DICT_FILE))
+}
+
+/// An editor preset plus the voice it is the default for, if any.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EditorPreset {
+    pub name: String,
+    pub preset: VoicePreset,
+    /// Speaker UUID and numeric style ID from the editor's `defaultPresetKeys`.
+    pub default_for: Option<(String, u32)>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EditorConfig {
+    #[serde(default)]
+    presets: EditorPresetTable,
+    #[serde(default)]
+    default_preset_keys: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct EditorPresetTable {
+    #[serde(default)]
+    items: BTreeMap<String, RawEditorPreset>,
+    #[serde(default)]
+    keys: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawEditorPreset {
+    name: String,
+    speed_scale: f32,
+    pitch_scale: f32,
+    intonation_scale: f32,
+    volume_scale: f32,
+    pre_phoneme_length: f32,
+    post_phoneme_length: f32,
+}
+
+/// Parses the editor's `config.json`, returning presets in the editor's display order.
+///
+/// Duplicate preset names get a ` (2)`, ` (3)`, ... suffix so none are lost.
+///
+/// # Errors
+///
+/// Returns an error if the document is not valid editor configuration JSON.
+pub fn parse_editor_presets(source: &str) -> Result<Vec<EditorPreset>> {
+    let config: EditorConfig =
+        serde_json::from_str(source).context("Malformed editor configuration")?;
+    let default_for: BTreeMap<&str, (String, u32)> = config
+        .default_preset_keys
+        .iter()
+        .filter_map(|(voice_key, preset_key)| {
+            parse_editor_voice_key(voice_key).map(|voice| (preset_key.as_str(), voice))
+        })
+        .collect();
+
+    let ordered = config
+        .presets
+        .keys
+        .iter()
+        .chain(
+            config
+                .presets
+                .items
+                .keys()
+                .filter(|key| !config.presets.keys.contains(key)),
+        )
+        .collect::<Vec<_>>();
+    let mut names = HashSet::new();
+    Ok(ordered
+        .into_iter()
+        .filter_map(|key| config.presets.items.get(key).map(|raw| (key, raw)))
+        .map(|(key, raw)| EditorPreset {
+            name: unique_name(&mut names, raw.name.trim()),
+            preset: VoicePreset {
+                voice: None,
+                rate: raw.speed_scale,
+                pitch: raw.pitch_scale,
+                intonation: raw.intonation_scale,
+                volume: raw.volume_scale,
+                pre_phoneme_length: raw.pre_phoneme_length,
+                post_phoneme_length: raw.post_phoneme_length,
+            },
+            default_for: default_for.get(key.as_str()).cloned(),
+        })
+        .collect())
+}
+
+fn unique_name(names: &mut HashSet<String>, name: &str) -> String {
+    let mut candidate = name.to_owned();
+    let mut suffix = 2;
+    while !names.insert(candidate.clone()) {
+        candidate = format!("{name} ({suffix})");
+        suffix += 1;
+    }
+    candidate
+}
+
+/// Splits an editor voice key, `<engine-id>:<speaker-uuid>:<style-id>`.
+fn parse_editor_voice_key(key: &str) -> Option<(String, u32)> {
+    let mut parts = key.rsplitn(3, ':');
+    let style_id = parts.next()?.parse().ok()?;
+    let speaker_uuid = parts.next()?;
+    Some((speaker_uuid.to_owned(), style_id))
+}
+
+#[derive(Debug, Deserialize)]
+struct EngineUserDictWord {
+    surface: String,
+    pronunciation: String,
+    accent_type: usize,
+    #[serde(default)]
+    part_of_speech: String,
+    #[serde(default)]
+    part_of_speech_detail_1: String,
+    priority: Option<u32>,
+    cost: Option<i32>,
+}
+
+impl EngineUserDictWord {
+    fn word_type(&self) -> WordType {
+        match (
+            self.part_of_speech.as_str(),
+            self.part_of_speech_detail_1.as_str(),
+        ) {
+            ("名詞", "一般") => WordType::CommonNoun,
+            ("名詞", "接尾") => WordType::Suffix,
+            ("動詞", _) => WordType::Verb,
+            ("形容詞", _) => WordType::Adjective,
+            _ => WordType::ProperNoun,
+        }
+    }
+
+    /// Engine files store a MeCab cost; this inverts the engine's priority table.
+    fn priority(&self, word_type: WordType) -> u32 {
+        if let Some(priority) = self.priority {
+            return priority.min(MAX_WORD_PRIORITY);
+        }
+        let Some(cost) = self.cost else {
+            return DEFAULT_WORD_PRIORITY;
+        };
+        let candidates = cost_candidates(word_type);
+        let closest = (0..candidates.len())
+            .min_by_key(|&index| (candidates[index] - cost).unsigned_abs())
+            .unwrap_or_default();
+        MAX_WORD_PRIORITY - closest as u32
+    }
+}
+
+/// MeCab costs the engine assigns to priorities 10 down to 0.
+const fn cost_candidates(word_type: WordType) -> [i32; 11] {
+    match word_type {
+        WordType::ProperNoun => [
+            -988, 3488, 4768, 6048, 7328, 8609, 8734, 8859, 8984, 9110, 14176,
+        ],
+        WordType::CommonNoun => [
+            -4445, 49, 1473, 2897, 4321, 5746, 6554, 7362, 8170, 8979, 15001,
+        ],
+        WordType::Verb => [
+            3100, 6160, 6360, 6561, 6761, 6962, 7414, 7866, 8318, 8771, 13433,
+        ],
+        WordType::Adjective => [
+            1527, 3266, 3561, 3857, 4153, 4449, 5149, 5849, 6549, 7250, 10001,
+        ],
+        WordType::Suffix => [
+            4399, 5373, 6041, 6710, 7378, 8047, 9440, 10834, 12228, 13622, 15847,
+        ],
+    }
+}
+
+/// Parses the engine's `user_dict.json` (a map of word UUIDs to entries).
+///
+/// # Errors
+///
+/// Returns an error if the document is not a valid engine user dictionary.
+pub fn parse_engine_user_dict(source: &str) -> Result<Vec<DictionaryWord>> {
+    let entries: BTreeMap<String, EngineUserDictWord> =
+        serde_json::from_str(source).context("Malformed engine user dictionary")?;
+    entries
+        .into_values()
+        .map(|entry| {
+            if entry.surface.is_empty() || entry.pronunciation.is_empty() {
+                return Err(anyhow!("Dictionary entry has an empty surface or reading"));
+            }
+            let word_type = entry.word_type();
+            let priority = entry.priority(word_type);
+            Ok(DictionaryWord {
+                surface: entry.surface,
+                pronunciation: entry.pronunciation,
+                accent_type: entry.accent_type,
+                word_type,
+                priority,
+            })
+        })
+        .collect()
+}
+
+/// Reads a settings file, returning `None` when it does not exist.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read.
+pub fn read_optional(path: &Path) -> Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(source) => Ok(Some(source)),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn editor_presets_keep_order_and_default_voice() {
+        let presets = parse_editor_presets(
+            r#"{
+                "presets": {
+                    "items": {
+                        "b": {"name": "ゆっくり", "speedScale": 0.8, "pitchScale": 0, "intonationScale": 1,
+                              "volumeScale": 1, "prePhonemeLength": 0.1, "postPhonemeLength": 0.1},
+                        "a": {"name": "ゆっくり", "speedScale": 0.9, "pitchScale": 0.05, "intonationScale": 1.2,
+                              "volumeScale": 0.8, "prePhonemeLength": 0.1, "postPhonemeLength": 0.3}
+                    },
+                    "keys": ["a", "b"]
+                },
+                "defaultPresetKeys": {
+                    "074fc39e-678b-4c13-8916-ffca8d505d1d:388f246b-8c41-4ac1-8e2d-5d79f3ff56d9:3": "a"
+                },
+                "hotkeySettings": []
+            }"#,
+        )
+        .expect("valid config");
+
+        assert_eq!(presets.len(), 2);
+        assert_eq!(presets[0].name, "ゆっくり");
+        assert_eq!(presets[0].preset.rate, 0.9);
+        assert_eq!(
+            presets[0].default_for,
+            Some(("388f246b-8c41-4ac1-8e2d-5d79f3ff56d9".to_owned(), 3))
+        );
+        assert_eq!(presets[1].name, "ゆっくり (2)");
+        assert_eq!(presets[1].default_for, None);
+    }
+
+    #[test]
+    fn engine_dictionary_maps_part_of_speech_and_cost() {
+        let words = parse_engine_user_dict(
+            r#"{
+                "2f2a2c5e-0000-0000-0000-000000000001": {
+                    "surface": "ｖｏｉｃｅｖｏｘ", "pronunciation": "ボイスボックス", "accent_type": 5,
+                    "part_of_speech": "名詞", "part_of_speech_detail_1": "固有名詞", "cost": 8609,
+                    "context_id": 1348, "mora_count": 7
+                },
+                "2f2a2c5e-0000-0000-0000-000000000002": {
+                    "surface": "ばえる", "pronunciation": "バエル", "accent_type": 2,
+                    "part_of_speech": "動詞", "part_of_speech_detail_1": "自立", "priority": 8
+                }
+            }"#,
+        )
+        .expect("valid dictionary");
+
+        assert_eq!(words[0].word_type, WordType::ProperNoun);
+        assert_eq!(words[0].priority, 5);
+        assert_eq!(words[1].word_type, WordType::Verb);
+        assert_eq!(words[1].priority, 8);
+        assert!(parse_engine_user_dict("[]").is_err());
+    }
+}
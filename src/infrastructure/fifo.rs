@@ -0,0 +1,38 @@
+use anyhow::{Context, Result, anyhow};
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::FileTypeExt;
+use std::path::Path;
+
+const FIFO_MODE: libc::mode_t = 0o600;
+
+/// Creates a named pipe at `path` unless one already exists there.
+///
+/// # Errors
+///
+/// Returns an error if a non-FIFO file occupies the path or `mkfifo` fails.
+pub fn ensure_fifo(path: &Path) -> Result<()> {
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_fifo() => return Ok(()),
+        Ok(_) => {
+            return Err(anyhow!(
+                "{} exists and is not a FIFO; remove it or choose another path",
+                path.display()
+            ));
+        }
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+        Err(error) => {
+            return Err(error).with_context(|| format!("Failed to inspect {}", path.display()));
+        }
+    }
+
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .with_context(|| format!("Invalid FIFO path: {}", path.display()))?;
+    // SAFETY: `c_path` is a valid NUL-terminated string for the duration of the call.
+    let status = unsafe { libc::mkfifo(c_path.as_ptr(), FIFO_MODE) };
+    if status != 0 {
+        return Err(std::io::Error::last_os_error())
+            .with_context(|| format!("Failed to create FIFO at {}", path.display()));
+    }
+    Ok(())
+}
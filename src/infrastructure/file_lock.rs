@@ -0,0 +1,56 @@
+use anyhow::{Context, Result};
+use std::fs::{File, OpenOptions};
+use std::os::fd::AsRawFd;
+use std::path::Path;
+
+/// Advisory `flock(2)` lock held for the lifetime of the value.
+pub struct FileLock {
+    _file: File,
+}
+
+impl FileLock {
+    /// Blocks until an exclusive lock on `path` is acquired, creating the file if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lock file cannot be opened or locked.
+    pub fn exclusive(path: &Path) -> Result<Self> {
+        Self::lock(path, libc::LOCK_EX)?
+            .with_context(|| format!("Failed to lock {}", path.display()))
+    }
+
+    /// Attempts an exclusive lock without blocking; returns `None` if another process holds it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lock file cannot be opened or locking fails for another reason.
+    pub fn try_exclusive(path: &Path) -> Result<Option<Self>> {
+        Self::lock(path, libc::LOCK_EX | libc::LOCK_NB)
+    }
+
+    fn lock(path: &Path, operation: libc::c_int) -> Result<Option<Self>> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)
+            .with_context(|| format!("Failed to open lock file {}", path.display()))?;
+
+        // SAFETY: `file` owns a valid descriptor for the duration of the call.
+        let status = unsafe { libc::flock(file.as_raw_fd(), operation) };
+        if status == 0 {
+            return Ok(Some(Self { _file: file }));
+        }
+
+        let error = std::io::Error::last_os_error();
+        if error.kind() == std::io::ErrorKind::WouldBlock {
+            Ok(None)
+        } else {
+            Err(error).with_context(|| format!("Failed to lock {}", path.display()))
+        }
+    }
+}
@@ -1,5 +1,6 @@
 pub use crate::domain::synthesis::limits::{
-    DEFAULT_SYNTHESIS_RATE, MAX_SYNTHESIS_RATE, MAX_SYNTHESIS_TEXT_LENGTH, MIN_SYNTHESIS_RATE,
+    DEFAULT_SYNTHESIS_PITCH, DEFAULT_SYNTHESIS_RATE, MAX_SYNTHESIS_PITCH, MAX_SYNTHESIS_RATE,
+    MAX_SYNTHESIS_TEXT_LENGTH, MIN_SYNTHESIS_PITCH, MIN_SYNTHESIS_RATE, is_valid_synthesis_pitch,
     is_valid_synthesis_rate,
 };
 pub const MAX_DAEMON_REQUEST_FRAME_BYTES: usize = 256 * 1024;
@@ -2,10 +2,14 @@ mod limits;
 mod protocol;
 
 pub use limits::{
-    DEFAULT_SYNTHESIS_RATE, MAX_DAEMON_REQUEST_FRAME_BYTES, MAX_DAEMON_RESPONSE_FRAME_BYTES,
-    MAX_SYNTHESIS_RATE, MAX_SYNTHESIS_TEXT_LENGTH, MIN_SYNTHESIS_RATE, is_valid_synthesis_rate,
+    DEFAULT_SYNTHESIS_PITCH, DEFAULT_SYNTHESIS_RATE, MAX_DAEMON_REQUEST_FRAME_BYTES,
+    MAX_DAEMON_RESPONSE_FRAME_BYTES, MAX_SYNTHESIS_PITCH, MAX_SYNTHESIS_RATE,
+    MAX_SYNTHESIS_TEXT_LENGTH, MIN_SYNTHESIS_PITCH, MIN_SYNTHESIS_RATE, is_valid_synthesis_pitch,
+    is_valid_synthesis_rate,
 };
 pub use protocol::{
-    DaemonErrorCode, DaemonRequest, DaemonResponse, IpcModel, IpcSpeaker, IpcStyle, OwnedRequest,
-    OwnedResponse, OwnedSynthesizeOptions, SynthesizeOptions,
+    DaemonErrorCode, DaemonEvent, DaemonRequest, DaemonResponse, DaemonStats, DaemonWarning,
+    DaemonWarningCode, IPC_PROTOCOL_VERSION, IpcModel, IpcSpeaker, IpcStyle, LatencyStats,
+    MoraTiming, OwnedRequest, OwnedResponse, SlowRequest, SynthesisOptions, SynthesisTimings,
+    protocol_mismatch_message,
 };
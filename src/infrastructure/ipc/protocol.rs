@@ -1,7 +1,27 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 
-use super::DEFAULT_SYNTHESIS_RATE;
+pub use crate::domain::synthesis::options::SynthesisOptions;
+
+/// Revision of the daemon socket protocol, checked by the daemon on `Hello` and
+/// `Authenticate`.
+///
+/// Frames are postcard, which is not self-describing: adding, removing, or reordering
+/// a field of any message, or a variant anywhere but the end of an enum, changes the
+/// encoding, so every such change raises the revision. `Hello` and `Authenticate` carry it as their first field and keep their
+/// place in [`DaemonRequest`], so a peer of any revision can read it and is answered
+/// [`DaemonErrorCode::ProtocolMismatch`].
+pub const IPC_PROTOCOL_VERSION: u32 = 1;
+
+/// Explains to a client speaking `client_version` why the daemon refused it.
+#[must_use]
+pub fn protocol_mismatch_message(client_version: u32) -> String {
+    format!(
+        "Client speaks daemon protocol {client_version}, but the daemon speaks \
+         {IPC_PROTOCOL_VERSION}; restart the daemon so both come from the same build"
+    )
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct IpcStyle {
@@ -9,6 +29,8 @@ pub struct IpcStyle {
     pub id: u32,
     #[serde(rename = "type")]
     pub style_type: Option<String>,
+    /// Whether the style's model is currently loaded in the daemon.
+    pub loaded: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -26,6 +48,7 @@ pub struct IpcModel {
     pub model_id: u32,
     pub file_path: std::path::PathBuf,
     pub speakers: Vec<IpcSpeaker>,
+    pub loaded: bool,
 }
 
 /// Request messages sent from client to daemon.
@@ -34,22 +57,109 @@ pub enum DaemonRequest {
     Synthesize {
         text: String,
         style_id: u32,
-        options: SynthesizeOptions,
+        options: SynthesisOptions,
     },
     ListSpeakers,
     ListModels,
+    /// Switches the connection to push mode: after `Subscribed`, the daemon sends
+    /// `Event` frames until the client disconnects.
+    Subscribe,
+    /// Opens a connection: checks the client's [`IPC_PROTOCOL_VERSION`], and
+    /// identifies the calling application, if `app_id` is not empty, so the daemon
+    /// can apply its configured profile to later requests on this connection.
+    Hello {
+        protocol_version: u32,
+        app_id: String,
+    },
+    /// Asks for the time-of-day playback policy currently in force.
+    GetPlaybackPolicy,
+    /// Asks for queue activity and recent non-fatal warnings.
+    Status,
+    /// Builds the editable query Core would synthesize from, without synthesizing it.
+    AudioQuery {
+        text: String,
+        style_id: u32,
+        options: SynthesisOptions,
+    },
+    /// Abandons the request currently running on this connection. The daemon answers
+    /// `Cancelled`, also when the request had already finished.
+    Cancel,
+    /// Loads the style's model and runs a short throwaway synthesis so the next real
+    /// request finds the model file, dictionary, and runtime already warm.
+    Prewarm {
+        style_id: u32,
+    },
+    /// Loads each model into the daemon's model cache, in order, with the same warmup
+    /// as `Prewarm`, so requests for any of their styles skip the load.
+    PreloadModels {
+        model_ids: Vec<u32>,
+    },
+    /// Asks whether background work such as batch renders should wait, which a
+    /// `--low-power` daemon asks for while the machine runs on battery.
+    GetPowerPolicy,
+    /// Re-reads the saved user dictionary so later requests use its words.
+    ReloadUserDict,
+    /// `Synthesize`, answered with `SynthesizeTimedResult` so the client can see
+    /// where the daemon spent its time.
+    SynthesizeTimed {
+        text: String,
+        style_id: u32,
+        options: SynthesisOptions,
+    },
+    /// Asks for the counters the daemon has kept since it started.
+    GetStats,
+    /// Asks for the user's voice aliases as the daemon sees them.
+    GetVoiceMapping,
+    /// Proves the client knows the daemon token; the first frame on every TCP
+    /// (`--listen`) connection, which is closed if the token or the
+    /// [`IPC_PROTOCOL_VERSION`] does not match.
+    Authenticate {
+        protocol_version: u32,
+        token: String,
+    },
+    /// Asks for the most recent requests slower than `[slow_log]` allows.
+    GetSlowLog,
+    /// `SynthesizeTimed`, answered with `SynthesizeWithMorasResult` so the client
+    /// can line the audio up with its moras.
+    SynthesizeWithMoras {
+        text: String,
+        style_id: u32,
+        options: SynthesisOptions,
+    },
+    /// Stops the daemon: it answers `ShuttingDown`, stops accepting connections,
+    /// lets running requests finish, and removes its socket.
+    Shutdown,
+    /// Re-reads `daemon.toml` and rescans the models directory, as SIGHUP does,
+    /// answering once both are applied.
+    Reload,
 }
 
-/// Synthesis options for voice synthesis requests.
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
-pub struct SynthesizeOptions {
-    pub rate: f32,
-}
-
-impl Default for SynthesizeOptions {
-    fn default() -> Self {
-        Self {
-            rate: DEFAULT_SYNTHESIS_RATE,
+impl DaemonRequest {
+    /// Variant name, for logs that must not include the request text.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Synthesize { .. } => "Synthesize",
+            Self::ListSpeakers => "ListSpeakers",
+            Self::ListModels => "ListModels",
+            Self::Subscribe => "Subscribe",
+            Self::Hello { .. } => "Hello",
+            Self::GetPlaybackPolicy => "GetPlaybackPolicy",
+            Self::Status => "Status",
+            Self::AudioQuery { .. } => "AudioQuery",
+            Self::Cancel => "Cancel",
+            Self::Prewarm { .. } => "Prewarm",
+            Self::PreloadModels { .. } => "PreloadModels",
+            Self::GetPowerPolicy => "GetPowerPolicy",
+            Self::ReloadUserDict => "ReloadUserDict",
+            Self::SynthesizeTimed { .. } => "SynthesizeTimed",
+            Self::GetStats => "GetStats",
+            Self::GetVoiceMapping => "GetVoiceMapping",
+            Self::Authenticate { .. } => "Authenticate",
+            Self::GetSlowLog => "GetSlowLog",
+            Self::SynthesizeWithMoras { .. } => "SynthesizeWithMoras",
+            Self::Shutdown => "Shutdown",
+            Self::Reload => "Reload",
         }
     }
 }
@@ -59,6 +169,8 @@ impl Default for SynthesizeOptions {
 pub enum DaemonResponse {
     SynthesizeResult {
         wav_data: Vec<u8>,
+        /// Non-fatal problems hit while serving this request.
+        warnings: Vec<DaemonWarning>,
     },
     SpeakersListWithModels {
         speakers: Vec<IpcSpeaker>,
@@ -71,6 +183,208 @@ pub enum DaemonResponse {
         code: DaemonErrorCode,
         message: String,
     },
+    Subscribed,
+    Event {
+        event: DaemonEvent,
+    },
+    Hello {
+        profile_applied: bool,
+    },
+    PlaybackPolicy {
+        volume: f32,
+        streaming: bool,
+    },
+    /// The request repeated one synthesized within the daemon's dedup window and was
+    /// not spoken again.
+    Skipped,
+    Status {
+        queue_length: u32,
+        current_style_id: Option<u32>,
+        /// Most recent warnings, oldest first.
+        recent_warnings: Vec<DaemonWarning>,
+    },
+    /// An `AudioQuery` as VOICEVOX engine JSON, kept as text because its schema is
+    /// owned by Core.
+    AudioQuery {
+        query_json: String,
+    },
+    /// Acknowledges `Cancel`; the cancelled request gets no other response.
+    Cancelled,
+    /// `Prewarm` finished; `elapsed_ms` is how long the warmup synthesis took.
+    Prewarmed {
+        elapsed_ms: u64,
+    },
+    /// `PreloadModels` finished; `elapsed_ms` covers loading every model.
+    ModelsPreloaded {
+        model_ids: Vec<u32>,
+        elapsed_ms: u64,
+    },
+    PowerPolicy {
+        defer_background: bool,
+    },
+    /// `ReloadUserDict` finished; `word_count` is how many words the dictionary holds.
+    UserDictReloaded {
+        word_count: u32,
+    },
+    /// `SynthesizeResult` for `SynthesizeTimed`, with the daemon's stage timings.
+    SynthesizeTimedResult {
+        wav_data: Vec<u8>,
+        warnings: Vec<DaemonWarning>,
+        timings: SynthesisTimings,
+    },
+    Stats {
+        stats: DaemonStats,
+    },
+    /// Alias name to the voice it stands for, answered to `GetVoiceMapping`.
+    VoiceMapping {
+        aliases: BTreeMap<String, String>,
+    },
+    /// The token sent with `Authenticate` matched.
+    Authenticated,
+    /// Answer to `GetSlowLog`, oldest entry first; `threshold_ms` is `None` while
+    /// the slow log is off.
+    SlowLog {
+        threshold_ms: Option<u64>,
+        entries: Vec<SlowRequest>,
+    },
+    /// `SynthesizeTimedResult` for `SynthesizeWithMoras`, with where each mora
+    /// falls in `wav_data`.
+    SynthesizeWithMorasResult {
+        wav_data: Vec<u8>,
+        warnings: Vec<DaemonWarning>,
+        timings: SynthesisTimings,
+        moras: Vec<MoraTiming>,
+    },
+    /// `Shutdown` was accepted; the connection closes after this response.
+    ShuttingDown,
+    /// `Reload` finished; `model_count` is how many models the rescan found.
+    Reloaded {
+        model_count: u32,
+    },
+}
+
+/// Milliseconds the daemon spent on each stage of one synthesis request.
+///
+/// Audio replayed from the dedup window reports all zeros.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SynthesisTimings {
+    /// Waiting for a free synthesis executor.
+    pub queue_ms: u64,
+    /// Creating the core and loading the style's model; near zero on a cache hit.
+    pub model_load_ms: u64,
+    pub audio_query_ms: u64,
+    pub synthesis_ms: u64,
+}
+
+/// A synthesis that took at least the `[slow_log]` threshold. The text itself is
+/// never kept, only its length.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SlowRequest {
+    /// When the synthesis finished, in seconds since the Unix epoch.
+    pub finished_at: u64,
+    /// The app the connection named in `Hello`, if any.
+    pub app_id: Option<String>,
+    pub style_id: u32,
+    pub text_chars: u32,
+    /// From the request reaching the queue to the audio being ready.
+    pub elapsed_ms: u64,
+    pub timings: SynthesisTimings,
+}
+
+/// Where one mora falls in synthesized audio, in milliseconds from its start.
+///
+/// Pauses between accent phrases appear as moras with the vowel `pau` and no
+/// consonant. Audio replayed from the dedup window comes with no moras.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct MoraTiming {
+    /// The mora in katakana, or `、` for a pause.
+    pub text: String,
+    pub consonant: Option<String>,
+    pub vowel: String,
+    pub start_ms: u32,
+    /// Where the consonant ends and the vowel begins; `start_ms` without a consonant.
+    pub vowel_start_ms: u32,
+    pub end_ms: u32,
+}
+
+/// What a daemon has done since it started, answered to `GetStats`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct DaemonStats {
+    pub uptime_secs: u64,
+    /// Syntheses that produced audio; dedup replays and skips are not counted.
+    pub syntheses: u64,
+    /// Syntheses per style ID.
+    pub style_syntheses: BTreeMap<u32, u64>,
+    /// `None` until the first synthesis.
+    pub latency: Option<LatencyStats>,
+    pub model_loads: u64,
+    pub model_unloads: u64,
+    /// Lookups in the `[model_cache]` of loaded models.
+    pub model_cache_hits: u64,
+    pub model_cache_misses: u64,
+}
+
+/// Synthesis latency, from the request reaching the queue to the audio being ready.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LatencyStats {
+    /// Over every synthesis since the daemon started.
+    pub average_ms: u64,
+    /// Percentiles over the most recent syntheses.
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub p99_ms: u64,
+}
+
+/// A non-fatal daemon problem, reported to clients instead of only to the daemon log.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DaemonWarning {
+    pub code: DaemonWarningCode,
+    pub message: String,
+}
+
+impl fmt::Display for DaemonWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DaemonWarningCode {
+    /// A style resolved to a model that has no file in the catalog.
+    ModelPathMissing,
+    /// A model could not be unloaded after synthesis.
+    ModelUnloadFailed,
+}
+
+/// Daemon activity pushed to `Subscribe` clients.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum DaemonEvent {
+    SynthesisStarted {
+        style_id: u32,
+        text_chars: u32,
+    },
+    ModelLoaded {
+        model_id: u32,
+    },
+    ModelUnloaded {
+        model_id: u32,
+    },
+    SynthesisFinished {
+        style_id: u32,
+        succeeded: bool,
+    },
+    /// Number of synthesis requests running or waiting changed.
+    QueueChanged {
+        length: u32,
+    },
+    /// The subscriber fell behind and `missed` events were dropped.
+    Lagged {
+        missed: u64,
+    },
+    /// The models directory was rescanned; cached speaker lists are out of date.
+    ModelsReloaded {
+        model_count: u32,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
@@ -79,6 +393,8 @@ pub enum DaemonErrorCode {
     ModelLoadFailed,
     SynthesisFailed,
     Internal,
+    /// The client speaks another [`IPC_PROTOCOL_VERSION`] than the daemon.
+    ProtocolMismatch,
 }
 
 /// Request type for owned data.
@@ -87,9 +403,6 @@ pub type OwnedRequest = DaemonRequest;
 /// Response type for owned data.
 pub type OwnedResponse = DaemonResponse;
 
-/// Synthesis options for owned data.
-pub type OwnedSynthesizeOptions = SynthesizeOptions;
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,7 +423,12 @@ mod tests {
         let request = DaemonRequest::Synthesize {
             text: "これはテストです".to_string(),
             style_id: 3,
-            options: SynthesizeOptions { rate: 1.2 },
+            options: SynthesisOptions {
+                rate: 1.2,
+                pitch: -0.05,
+                intonation: 1.3,
+                ..SynthesisOptions::default()
+            },
         };
         assert_eq!(roundtrip_request(&request), request);
     }
@@ -125,6 +443,223 @@ mod tests {
             roundtrip_request(&DaemonRequest::ListModels),
             DaemonRequest::ListModels
         );
+        assert_eq!(
+            roundtrip_request(&DaemonRequest::Subscribe),
+            DaemonRequest::Subscribe
+        );
+    }
+
+    #[test]
+    fn audio_query_roundtrip() {
+        let request = DaemonRequest::AudioQuery {
+            text: "こんにちは".to_string(),
+            style_id: 3,
+            options: SynthesisOptions::default(),
+        };
+        assert_eq!(roundtrip_request(&request), request);
+        let response = DaemonResponse::AudioQuery {
+            query_json: r#"{"accent_phrases":[],"speedScale":1.0}"#.to_string(),
+        };
+        assert_eq!(roundtrip_response(&response), response);
+    }
+
+    #[test]
+    fn cancel_roundtrip() {
+        assert_eq!(
+            roundtrip_request(&DaemonRequest::Cancel),
+            DaemonRequest::Cancel
+        );
+        assert_eq!(
+            roundtrip_response(&DaemonResponse::Cancelled),
+            DaemonResponse::Cancelled
+        );
+    }
+
+    #[test]
+    fn prewarm_roundtrip() {
+        let request = DaemonRequest::Prewarm { style_id: 3 };
+        assert_eq!(roundtrip_request(&request), request);
+        let response = DaemonResponse::Prewarmed { elapsed_ms: 412 };
+        assert_eq!(roundtrip_response(&response), response);
+    }
+
+    #[test]
+    fn preload_models_roundtrip() {
+        let request = DaemonRequest::PreloadModels {
+            model_ids: vec![3, 2, 8],
+        };
+        assert_eq!(roundtrip_request(&request), request);
+        let response = DaemonResponse::ModelsPreloaded {
+            model_ids: vec![3, 2, 8],
+            elapsed_ms: 2100,
+        };
+        assert_eq!(roundtrip_response(&response), response);
+    }
+
+    #[test]
+    fn power_policy_roundtrip() {
+        assert_eq!(
+            roundtrip_request(&DaemonRequest::GetPowerPolicy),
+            DaemonRequest::GetPowerPolicy
+        );
+        let response = DaemonResponse::PowerPolicy {
+            defer_background: true,
+        };
+        assert_eq!(roundtrip_response(&response), response);
+    }
+
+    #[test]
+    fn user_dict_reload_roundtrip() {
+        assert_eq!(
+            roundtrip_request(&DaemonRequest::ReloadUserDict),
+            DaemonRequest::ReloadUserDict
+        );
+        let response = DaemonResponse::UserDictReloaded { word_count: 12 };
+        assert_eq!(roundtrip_response(&response), response);
+    }
+
+    #[test]
+    fn slow_log_roundtrip() {
+        assert_eq!(
+            roundtrip_request(&DaemonRequest::GetSlowLog),
+            DaemonRequest::GetSlowLog
+        );
+        let response = DaemonResponse::SlowLog {
+            threshold_ms: Some(2000),
+            entries: vec![SlowRequest {
+                finished_at: 1_700_000_000,
+                app_id: Some("claude-mcp".to_string()),
+                style_id: 3,
+                text_chars: 1200,
+                elapsed_ms: 5400,
+                timings: SynthesisTimings {
+                    queue_ms: 10,
+                    model_load_ms: 2000,
+                    audio_query_ms: 300,
+                    synthesis_ms: 3000,
+                },
+            }],
+        };
+        assert_eq!(roundtrip_response(&response), response);
+    }
+
+    #[test]
+    fn lifecycle_roundtrip() {
+        for request in [DaemonRequest::Shutdown, DaemonRequest::Reload] {
+            assert_eq!(roundtrip_request(&request), request);
+        }
+        for response in [
+            DaemonResponse::ShuttingDown,
+            DaemonResponse::Reloaded { model_count: 27 },
+        ] {
+            assert_eq!(roundtrip_response(&response), response);
+        }
+    }
+
+    #[test]
+    fn synthesize_with_moras_roundtrip() {
+        let request = DaemonRequest::SynthesizeWithMoras {
+            text: "こんにちは".to_string(),
+            style_id: 3,
+            options: SynthesisOptions::default(),
+        };
+        assert_eq!(roundtrip_request(&request), request);
+        let response = DaemonResponse::SynthesizeWithMorasResult {
+            wav_data: vec![1, 2, 3],
+            warnings: Vec::new(),
+            timings: SynthesisTimings::default(),
+            moras: vec![MoraTiming {
+                text: "コ".to_string(),
+                consonant: Some("k".to_string()),
+                vowel: "o".to_string(),
+                start_ms: 100,
+                vowel_start_ms: 160,
+                end_ms: 250,
+            }],
+        };
+        assert_eq!(roundtrip_response(&response), response);
+    }
+
+    #[test]
+    fn authenticate_roundtrip() {
+        let request = DaemonRequest::Authenticate {
+            protocol_version: IPC_PROTOCOL_VERSION,
+            token: "0123abcd".to_string(),
+        };
+        assert_eq!(roundtrip_request(&request), request);
+        assert_eq!(
+            roundtrip_response(&DaemonResponse::Authenticated),
+            DaemonResponse::Authenticated
+        );
+    }
+
+    #[test]
+    fn hello_roundtrip() {
+        let request = DaemonRequest::Hello {
+            protocol_version: IPC_PROTOCOL_VERSION,
+            app_id: "claude-mcp".to_string(),
+        };
+        assert_eq!(roundtrip_request(&request), request);
+        let response = DaemonResponse::Hello {
+            profile_applied: true,
+        };
+        assert_eq!(roundtrip_response(&response), response);
+    }
+
+    #[test]
+    fn playback_policy_roundtrip() {
+        assert_eq!(
+            roundtrip_request(&DaemonRequest::GetPlaybackPolicy),
+            DaemonRequest::GetPlaybackPolicy
+        );
+        let response = DaemonResponse::PlaybackPolicy {
+            volume: 0.5,
+            streaming: false,
+        };
+        assert_eq!(roundtrip_response(&response), response);
+        assert_eq!(
+            roundtrip_response(&DaemonResponse::Skipped),
+            DaemonResponse::Skipped
+        );
+    }
+
+    #[test]
+    fn status_with_warnings_roundtrip() {
+        assert_eq!(
+            roundtrip_request(&DaemonRequest::Status),
+            DaemonRequest::Status
+        );
+        let response = DaemonResponse::Status {
+            queue_length: 1,
+            current_style_id: Some(3),
+            recent_warnings: vec![DaemonWarning {
+                code: DaemonWarningCode::ModelUnloadFailed,
+                message: "Failed to unload model 0".to_string(),
+            }],
+        };
+        assert_eq!(roundtrip_response(&response), response);
+    }
+
+    #[test]
+    fn event_responses_roundtrip() {
+        for event in [
+            DaemonEvent::SynthesisStarted {
+                style_id: 3,
+                text_chars: 12,
+            },
+            DaemonEvent::ModelLoaded { model_id: 0 },
+            DaemonEvent::ModelUnloaded { model_id: 0 },
+            DaemonEvent::SynthesisFinished {
+                style_id: 3,
+                succeeded: false,
+            },
+            DaemonEvent::QueueChanged { length: 2 },
+            DaemonEvent::ModelsReloaded { model_count: 7 },
+            DaemonEvent::Lagged { missed: 5 },
+        ] {
+            let response = DaemonResponse::Event { event };
+            assert_eq!(roundtrip_response(&response), response);
+        }
     }
 
     #[test]
@@ -132,11 +667,13 @@ mod tests {
         let wav_data: Vec<u8> = (0..65536).map(|i| (i % 256) as u8).collect();
         let response = DaemonResponse::SynthesizeResult {
             wav_data: wav_data.clone(),
+            warnings: Vec::new(),
         };
         let decoded = roundtrip_response(&response);
         assert_eq!(decoded, response);
         if let DaemonResponse::SynthesizeResult {
             wav_data: decoded_wav,
+            ..
         } = decoded
         {
             assert_eq!(decoded_wav.len(), 65536);
@@ -157,11 +694,13 @@ mod tests {
                         name: "ノーマル".to_string(),
                         id: 3,
                         style_type: Some("talk".to_string()),
+                        loaded: false,
                     },
                     IpcStyle {
                         name: "あまあま".to_string(),
                         id: 1,
                         style_type: None,
+                        loaded: false,
                     },
                 ],
                 version: "0.1.0".to_string(),
@@ -178,6 +717,7 @@ mod tests {
                 model_id: 0,
                 file_path: PathBuf::from("/path/to/0.vvm"),
                 speakers: vec![],
+                loaded: false,
             }],
         };
         assert_eq!(roundtrip_response(&response), response);
@@ -191,4 +731,64 @@ mod tests {
         };
         assert_eq!(roundtrip_response(&response), response);
     }
+
+    #[test]
+    fn synthesize_timed_roundtrip() {
+        let request = DaemonRequest::SynthesizeTimed {
+            text: "こんにちは".to_string(),
+            style_id: 3,
+            options: SynthesisOptions::default(),
+        };
+        assert_eq!(roundtrip_request(&request), request);
+
+        let response = DaemonResponse::SynthesizeTimedResult {
+            wav_data: vec![1, 2, 3],
+            warnings: Vec::new(),
+            timings: SynthesisTimings {
+                queue_ms: 1,
+                model_load_ms: 250,
+                audio_query_ms: 12,
+                synthesis_ms: 80,
+            },
+        };
+        assert_eq!(roundtrip_response(&response), response);
+    }
+
+    #[test]
+    fn stats_roundtrip() {
+        assert_eq!(
+            roundtrip_request(&DaemonRequest::GetStats),
+            DaemonRequest::GetStats
+        );
+        let response = DaemonResponse::Stats {
+            stats: DaemonStats {
+                uptime_secs: 3600,
+                syntheses: 42,
+                style_syntheses: BTreeMap::from([(3, 40), (1, 2)]),
+                latency: Some(LatencyStats {
+                    average_ms: 180,
+                    p50_ms: 150,
+                    p95_ms: 420,
+                    p99_ms: 900,
+                }),
+                model_loads: 3,
+                model_unloads: 1,
+                model_cache_hits: 39,
+                model_cache_misses: 3,
+            },
+        };
+        assert_eq!(roundtrip_response(&response), response);
+    }
+
+    #[test]
+    fn voice_mapping_roundtrip() {
+        assert_eq!(
+            roundtrip_request(&DaemonRequest::GetVoiceMapping),
+            DaemonRequest::GetVoiceMapping
+        );
+        let response = DaemonResponse::VoiceMapping {
+            aliases: BTreeMap::from([("zunda".to_string(), "3".to_string())]),
+        };
+        assert_eq!(roundtrip_response(&response), response);
+    }
 }
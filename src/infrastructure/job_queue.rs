@@ -0,0 +1,192 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::infrastructure::file_lock::FileLock;
+use crate::infrastructure::state_store::{load_json_or_default, save_json, state_file_path};
+
+const QUEUE_FILE_NAME: &str = "render_queue.json";
+const QUEUE_LOCK_FILE_NAME: &str = "render_queue.lock";
+const QUEUE_RUNNER_LOCK_FILE_NAME: &str = "render_queue.runner.lock";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Pending,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueuedJob {
+    pub id: u64,
+    pub text: String,
+    pub style_id: u32,
+    pub rate: f32,
+    pub output: PathBuf,
+    pub status: JobStatus,
+    #[serde(default)]
+    pub attempts: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub enqueued_at: u64,
+}
+
+/// New job contents supplied by `queue add`.
+pub struct NewJob {
+    pub text: String,
+    pub style_id: u32,
+    pub rate: f32,
+    pub output: PathBuf,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct JobQueue {
+    #[serde(default)]
+    next_id: u64,
+    #[serde(default)]
+    pub jobs: Vec<QueuedJob>,
+}
+
+impl JobQueue {
+    pub fn push(&mut self, job: NewJob) -> u64 {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.jobs.push(QueuedJob {
+            id,
+            text: job.text,
+            style_id: job.style_id,
+            rate: job.rate,
+            output: job.output,
+            status: JobStatus::Pending,
+            attempts: 0,
+            error: None,
+            enqueued_at: unix_now(),
+        });
+        id
+    }
+
+    #[must_use]
+    pub fn next_pending(&self, skip: impl Fn(u64) -> bool) -> Option<&QueuedJob> {
+        self.jobs
+            .iter()
+            .find(|job| job.status == JobStatus::Pending && !skip(job.id))
+    }
+
+    pub fn record_attempt(&mut self, id: u64, result: Result<(), String>) {
+        if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+            job.attempts += 1;
+            match result {
+                Ok(()) => {
+                    job.status = JobStatus::Done;
+                    job.error = None;
+                }
+                Err(error) => {
+                    job.status = JobStatus::Failed;
+                    job.error = Some(error);
+                }
+            }
+        }
+    }
+
+    /// Puts failed jobs back into the pending state; returns how many were requeued.
+    pub fn retry_failed(&mut self) -> usize {
+        let mut count = 0;
+        for job in &mut self.jobs {
+            if job.status == JobStatus::Failed {
+                job.status = JobStatus::Pending;
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Removes finished jobs (or every job when `all` is set); returns how many were removed.
+    pub fn clear(&mut self, all: bool) -> usize {
+        let before = self.jobs.len();
+        self.jobs
+            .retain(|job| !all && job.status != JobStatus::Done);
+        before - self.jobs.len()
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Loads the queue, applies `update` and saves it, all under the queue file lock.
+///
+/// # Errors
+///
+/// Returns an error if the queue cannot be locked, read, or written.
+pub fn update_job_queue<T>(update: impl FnOnce(&mut JobQueue) -> T) -> Result<T> {
+    let _lock = FileLock::exclusive(&state_file_path(QUEUE_LOCK_FILE_NAME))?;
+    let path = state_file_path(QUEUE_FILE_NAME);
+    let mut queue: JobQueue = load_json_or_default(&path)?;
+    let result = update(&mut queue);
+    save_json(&path, &queue)?;
+    Ok(result)
+}
+
+/// Reads a snapshot of the queue under the queue file lock.
+///
+/// # Errors
+///
+/// Returns an error if the queue cannot be locked or read.
+pub fn read_job_queue() -> Result<JobQueue> {
+    let _lock = FileLock::exclusive(&state_file_path(QUEUE_LOCK_FILE_NAME))?;
+    load_json_or_default(&state_file_path(QUEUE_FILE_NAME))
+}
+
+/// Claims the single-runner lock so only one `queue run` drains the queue at a time.
+///
+/// # Errors
+///
+/// Returns an error if the lock file cannot be opened.
+pub fn try_claim_queue_runner() -> Result<Option<FileLock>> {
+    FileLock::try_exclusive(&state_file_path(QUEUE_RUNNER_LOCK_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_job(output: &str) -> NewJob {
+        NewJob {
+            text: "テスト".to_owned(),
+            style_id: 3,
+            rate: 1.0,
+            output: PathBuf::from(output),
+        }
+    }
+
+    #[test]
+    fn jobs_move_through_pending_done_and_failed() {
+        let mut queue = JobQueue::default();
+        let first = queue.push(new_job("a.wav"));
+        let second = queue.push(new_job("b.wav"));
+        assert_eq!((first, second), (1, 2));
+
+        assert_eq!(
+            queue.next_pending(|id| id == first).map(|job| job.id),
+            Some(second)
+        );
+
+        queue.record_attempt(first, Ok(()));
+        queue.record_attempt(second, Err("daemon unavailable".to_owned()));
+        assert!(queue.next_pending(|_| false).is_none());
+
+        assert_eq!(queue.retry_failed(), 1);
+        assert_eq!(
+            queue.next_pending(|_| false).map(|job| job.attempts),
+            Some(1)
+        );
+
+        assert_eq!(queue.clear(false), 1);
+        assert_eq!(queue.jobs.len(), 1);
+        assert_eq!(queue.push(new_job("c.wav")), 3);
+    }
+}
@@ -0,0 +1,61 @@
+/// A moment in the system time zone, down to the minute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalTime {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl LocalTime {
+    /// The current time in the system time zone, or `None` if it cannot be determined.
+    #[must_use]
+    pub fn now() -> Option<Self> {
+        // SAFETY: `time` accepts a null output pointer and has no other preconditions.
+        let now: libc::time_t = unsafe { libc::time(std::ptr::null_mut()) };
+        // SAFETY: `tm` is plain old data, so the all-zero pattern is a valid value.
+        let mut local: libc::tm = unsafe { std::mem::zeroed() };
+        // SAFETY: both pointers refer to live stack values for the duration of the call.
+        if unsafe { libc::localtime_r(&raw const now, &raw mut local) }.is_null() {
+            return None;
+        }
+        Some(Self {
+            year: local.tm_year + 1900,
+            month: u8::try_from(local.tm_mon + 1).ok()?,
+            day: u8::try_from(local.tm_mday).ok()?,
+            hour: u8::try_from(local.tm_hour).ok()?,
+            minute: u8::try_from(local.tm_min).ok()?,
+        })
+    }
+
+    /// The date as `YYYY-MM-DD`.
+    #[must_use]
+    pub fn date(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
+    /// Minutes since local midnight.
+    #[must_use]
+    pub fn minutes_since_midnight(&self) -> u16 {
+        u16::from(self.hour) * 60 + u16::from(self.minute)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_and_minutes_are_read_from_the_fields() {
+        let time = LocalTime {
+            year: 2026,
+            month: 1,
+            day: 9,
+            hour: 22,
+            minute: 5,
+        };
+        assert_eq!(time.date(), "2026-01-09");
+        assert_eq!(time.minutes_since_midnight(), 22 * 60 + 5);
+    }
+}
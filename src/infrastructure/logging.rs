@@ -1,17 +1,60 @@
+use serde::Deserialize;
+use std::fs::File;
 use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum LogLevel {
+    #[default]
     Info,
     Warn,
     Error,
 }
 
+/// Least severe level written; set by [`configure`].
+static MIN_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Info as u8);
+/// File every line goes to instead of stdout and stderr; set by [`configure`].
+static LOG_FILE: OnceLock<Mutex<File>> = OnceLock::new();
+
+/// Drops messages less severe than `level` and, with `file`, appends every line
+/// to it instead of writing to stdout and stderr.
+///
+/// The file can be set only once per process; later calls only change the level.
+///
+/// # Errors
+///
+/// Returns an error if `file` or its directory cannot be created or opened.
+pub fn configure(level: LogLevel, file: Option<&Path>) -> io::Result<()> {
+    MIN_LEVEL.store(level as u8, Ordering::Relaxed);
+    if let Some(path) = file
+        && LOG_FILE.get().is_none()
+    {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::options().create(true).append(true).open(path)?;
+        let _ = LOG_FILE.set(Mutex::new(file));
+    }
+    Ok(())
+}
+
 fn write_line(mut writer: impl Write, message: &str) {
     let _ = writeln!(writer, "{message}");
 }
 
 pub fn log(level: LogLevel, message: &str) {
+    if (level as u8) < MIN_LEVEL.load(Ordering::Relaxed) {
+        return;
+    }
+    if let Some(file) = LOG_FILE.get() {
+        if let Ok(mut file) = file.lock() {
+            write_line(&mut *file, message);
+        }
+        return;
+    }
     match level {
         LogLevel::Info => write_line(io::stdout(), message),
         LogLevel::Warn | LogLevel::Error => write_line(io::stderr(), message),
@@ -1,3 +1,13 @@
+//! Allocator hooks for keeping the daemon's resident memory close to its idle size.
+//!
+//! Model weights are not memory-mapped or shared between synthesizers: VOICEVOX
+//! Core opens `.vvm` files by path and decompresses the weights into ONNX Runtime
+//! sessions, so a file mapping would not be the memory the sessions use. The
+//! daemon runs a single serialized synthesizer unless `[workers]` asks for more,
+//! and each extra worker deliberately holds its own copy. Unless `[model_cache]`
+//! keeps models resident, memory is reclaimed by unloading after each request and
+//! handing freed pages back to the OS here.
+
 /// Asks the platform allocator to return unused process memory to the OS.
 ///
 /// This is a best-effort RSS reduction hook for large native allocations released
@@ -17,7 +27,53 @@ pub fn release_unused_allocator_memory() -> usize {
     unsafe { malloc_zone_pressure_relief(std::ptr::null_mut(), 0) as usize }
 }
 
-#[cfg(not(target_os = "macos"))]
+/// glibc keeps freed model weights in its arenas until asked to trim them, so
+/// without this the daemon's RSS stays at the peak of the largest model loaded.
+/// glibc does not report a byte count; the return value is always zero.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+pub fn release_unused_allocator_memory() -> usize {
+    // SAFETY: `malloc_trim` only inspects allocator state and has no preconditions.
+    unsafe { libc::malloc_trim(0) };
+    0
+}
+
+#[cfg(not(any(target_os = "macos", all(target_os = "linux", target_env = "gnu"))))]
 pub fn release_unused_allocator_memory() -> usize {
     0
 }
+
+/// Memory the OS can hand out without swapping, in bytes, where the platform reports it.
+#[cfg(target_os = "linux")]
+#[must_use]
+pub fn available_memory_bytes() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    parse_mem_available(&meminfo)
+}
+
+#[cfg(not(target_os = "linux"))]
+#[must_use]
+pub fn available_memory_bytes() -> Option<u64> {
+    None
+}
+
+/// Reads the `MemAvailable` line of `/proc/meminfo`, which is given in KiB.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_mem_available(meminfo: &str) -> Option<u64> {
+    let line = meminfo
+        .lines()
+        .find_map(|line| line.strip_prefix("MemAvailable:"))?;
+    let kib: u64 = line.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    kib.checked_mul(1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mem_available_is_read_in_bytes() {
+        let meminfo = "MemTotal:       16315884 kB\nMemFree:         1033108 kB\nMemAvailable:    8123456 kB\n";
+        assert_eq!(parse_mem_available(meminfo), Some(8_123_456 * 1024));
+        assert_eq!(parse_mem_available("MemTotal: 1 kB\n"), None);
+    }
+}
@@ -1,11 +1,28 @@
 pub mod core;
+pub mod cpu_affinity;
 pub mod daemon;
 pub mod download;
+pub mod editor_settings;
+pub mod fifo;
+pub mod file_lock;
 pub mod ipc;
+pub mod job_queue;
+pub mod local_time;
 pub mod logging;
 pub mod mcp_instructions;
 pub mod memory;
+pub mod model_mirror;
+#[cfg(feature = "mp3")]
+pub mod mp3;
 pub mod onnxruntime;
 pub mod openjtalk;
+#[cfg(feature = "opus")]
+pub mod opus;
 pub mod paths;
+pub mod power;
+pub mod speaker_cache;
+pub mod state_store;
+pub mod usage_stats;
+pub mod user_dict;
+pub mod voice_aliases;
 pub mod voicevox;
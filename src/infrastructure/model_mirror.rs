@@ -0,0 +1,151 @@
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs::{File, Metadata};
+use std::path::{Path, PathBuf};
+
+use crate::infrastructure::paths::get_cache_dir;
+use crate::infrastructure::voicevox::find_vvm_files;
+
+const LOCAL_MODELS_SUBDIR: &str = "models";
+const PARTIAL_SUFFIX: &str = "partial";
+
+/// Local copy of the models directory used with `--copy-models-local`.
+#[must_use]
+pub fn local_models_dir() -> PathBuf {
+    get_cache_dir().join(LOCAL_MODELS_SUBDIR)
+}
+
+/// What a [`mirror_models`] pass changed in the local copy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MirrorSummary {
+    pub copied: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+}
+
+/// A copy is current when it matches the source in size and modification time;
+/// the copy takes the source's time, so any later edit on the share shows up.
+fn is_current(source: &Metadata, target: &Path) -> bool {
+    std::fs::metadata(target).is_ok_and(|target| {
+        target.len() == source.len()
+            && matches!(
+                (source.modified(), target.modified()),
+                (Ok(source), Ok(target)) if source == target
+            )
+    })
+}
+
+fn copy_model(source: &Path, metadata: &Metadata, target: &Path) -> Result<()> {
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    // Copy beside the target and rename, so a load never sees half a model.
+    let partial = target.with_extension(PARTIAL_SUFFIX);
+    std::fs::copy(source, &partial).with_context(|| {
+        format!(
+            "Failed to copy {} to {}",
+            source.display(),
+            partial.display()
+        )
+    })?;
+    if let Ok(modified) = metadata.modified() {
+        File::options()
+            .write(true)
+            .open(&partial)
+            .and_then(|file| file.set_modified(modified))
+            .with_context(|| format!("Failed to set the time of {}", partial.display()))?;
+    }
+    std::fs::rename(&partial, target)
+        .with_context(|| format!("Failed to move {} into place", target.display()))
+}
+
+/// Brings `target` up to date with the VVM files under `source`.
+///
+/// Missing or stale models are copied, keeping their paths relative to `source`;
+/// cached models no longer on the source are deleted. Other files are left alone.
+///
+/// # Errors
+///
+/// Returns an error if either directory cannot be walked or a model cannot be
+/// copied or removed. Models handled before the failure stay in place.
+pub fn mirror_models(source: &Path, target: &Path) -> Result<MirrorSummary> {
+    let mut summary = MirrorSummary::default();
+    let mut mirrored = HashSet::new();
+    for path in find_vvm_files(source)? {
+        let relative = path.strip_prefix(source).unwrap_or(&path).to_path_buf();
+        let destination = target.join(&relative);
+        let metadata = std::fs::metadata(&path)
+            .with_context(|| format!("Failed to inspect {}", path.display()))?;
+        if is_current(&metadata, &destination) {
+            summary.unchanged += 1;
+        } else {
+            copy_model(&path, &metadata, &destination)?;
+            summary.copied += 1;
+        }
+        mirrored.insert(relative);
+    }
+
+    for path in find_vvm_files(target)? {
+        let relative = path.strip_prefix(target).unwrap_or(&path);
+        if !mirrored.contains(relative) {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            summary.removed += 1;
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    fn write(path: &Path, contents: &[u8]) {
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("create dirs");
+        std::fs::write(path, contents).expect("write model");
+    }
+
+    #[test]
+    fn copies_new_and_stale_models_and_drops_removed_ones() {
+        let share = tempfile::tempdir().expect("share");
+        let cache = tempfile::tempdir().expect("cache");
+        write(&share.path().join("0.vvm"), b"zero");
+        write(&share.path().join("vvms/1.vvm"), b"one");
+        write(&share.path().join("readme.txt"), b"skip");
+
+        let first = mirror_models(share.path(), cache.path()).expect("first sync");
+        assert_eq!(first.copied, 2);
+        assert_eq!(
+            std::fs::read(cache.path().join("vvms/1.vvm")).expect("copied"),
+            b"one"
+        );
+        assert!(!cache.path().join("readme.txt").exists());
+
+        let second = mirror_models(share.path(), cache.path()).expect("second sync");
+        assert_eq!(
+            second,
+            MirrorSummary {
+                unchanged: 2,
+                ..MirrorSummary::default()
+            }
+        );
+
+        write(&share.path().join("0.vvm"), b"ZERO");
+        File::options()
+            .write(true)
+            .open(share.path().join("0.vvm"))
+            .and_then(|file| file.set_modified(SystemTime::now() + Duration::from_secs(60)))
+            .expect("touch");
+        std::fs::remove_file(share.path().join("vvms/1.vvm")).expect("remove");
+
+        let third = mirror_models(share.path(), cache.path()).expect("third sync");
+        assert_eq!((third.copied, third.removed), (1, 1));
+        assert_eq!(
+            std::fs::read(cache.path().join("0.vvm")).expect("updated"),
+            b"ZERO"
+        );
+        assert!(!cache.path().join("vvms/1.vvm").exists());
+    }
+}
@@ -0,0 +1,85 @@
+use anyhow::{Result, anyhow, ensure};
+use mp3lame_encoder::{Bitrate, Builder, FlushNoGap, InterleavedPcm, MonoPcm, Quality};
+
+use crate::domain::synthesis::wav::parse_wav_header;
+
+/// Constant bitrate for mono speech; stereo gets twice as much.
+const MP3_MONO_BITRATE: Bitrate = Bitrate::Kbps64;
+const MP3_STEREO_BITRATE: Bitrate = Bitrate::Kbps128;
+
+/// Encodes a 16-bit PCM WAV as a constant-bitrate MP3 with libmp3lame.
+///
+/// # Errors
+///
+/// Returns an error if the WAV is malformed, not 16-bit mono or stereo, or at a
+/// sample rate MP3 does not support.
+pub fn encode_mp3(wav_data: &[u8]) -> Result<Vec<u8>> {
+    let header = parse_wav_header(wav_data)?;
+    ensure!(
+        header.bits_per_sample == 16,
+        "MP3 encoding needs 16-bit PCM, got {} bits",
+        header.bits_per_sample
+    );
+    let samples: Vec<i16> = wav_data[header.data_offset..header.data_offset + header.data_size]
+        .chunks_exact(2)
+        .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+
+    let sample_rate = header.sample_rate;
+    let (channels, bitrate) = match header.channels {
+        1 => (1, MP3_MONO_BITRATE),
+        2 => (2, MP3_STEREO_BITRATE),
+        channels => return Err(anyhow!("MP3 cannot encode {channels} channels")),
+    };
+
+    let mut builder = Builder::new().ok_or_else(|| anyhow!("Failed to create MP3 encoder"))?;
+    builder
+        .set_num_channels(channels)
+        .map_err(|error| anyhow!("MP3 cannot encode {channels} channels: {error:?}"))?;
+    builder
+        .set_sample_rate(sample_rate)
+        .map_err(|error| anyhow!("MP3 cannot encode {sample_rate} Hz audio: {error:?}"))?;
+    builder
+        .set_brate(bitrate)
+        .map_err(|error| anyhow!("Failed to set MP3 bitrate: {error:?}"))?;
+    builder
+        .set_quality(Quality::Good)
+        .map_err(|error| anyhow!("Failed to set MP3 quality: {error:?}"))?;
+    let mut encoder = builder
+        .build()
+        .map_err(|error| anyhow!("Failed to create MP3 encoder: {error:?}"))?;
+
+    let frames = samples.len() / usize::from(channels);
+    let mut mp3 = Vec::with_capacity(mp3lame_encoder::max_required_buffer_size(frames));
+    let encoded = if channels == 1 {
+        encoder.encode_to_vec(MonoPcm(&samples), &mut mp3)
+    } else {
+        encoder.encode_to_vec(InterleavedPcm(&samples), &mut mp3)
+    };
+    encoded.map_err(|error| anyhow!("MP3 encoding failed: {error:?}"))?;
+    encoder
+        .flush_to_vec::<FlushNoGap>(&mut mp3)
+        .map_err(|error| anyhow!("MP3 encoding failed: {error:?}"))?;
+    Ok(mp3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::synthesis::wav::make_wav;
+
+    #[test]
+    fn mp3_starts_with_a_frame_sync() {
+        let wav = make_wav(&[0; 24_000 * 2], 1, 24_000, 16);
+        let mp3 = encode_mp3(&wav).expect("encoded");
+
+        assert_eq!(mp3[0], 0xFF);
+        assert_eq!(mp3[1] & 0xE0, 0xE0);
+    }
+
+    #[test]
+    fn mp3_rejects_8_bit_audio() {
+        let wav = make_wav(&[0; 100], 1, 24_000, 8);
+        assert!(encode_mp3(&wav).is_err());
+    }
+}
@@ -0,0 +1,351 @@
+use anyhow::{Context, Result, anyhow, ensure};
+use audiopus::coder::{Decoder, Encoder};
+use audiopus::packet::Packet;
+use audiopus::{Application, Bitrate, Channels, MutSignals, SampleRate};
+use std::time::Duration;
+
+use crate::domain::synthesis::ogg::OggWriter;
+use crate::domain::synthesis::opus_stream::{
+    MAX_OPUS_PACKET_LEN, OpusFrameReader, OpusStreamHeader, OpusStreamItem, write_opus_packet,
+};
+use crate::domain::synthesis::wav::{make_wav, parse_wav_header};
+
+/// Audio covered by one Opus packet; 20 ms is the codec's recommended frame size.
+pub const OPUS_FRAME_DURATION: Duration = Duration::from_millis(20);
+
+/// Target bitrate per channel; plenty for speech at VOICEVOX's 24 kHz output.
+pub const OPUS_BITRATE_PER_CHANNEL: i32 = 32_000;
+
+/// Rate Ogg Opus granule positions count in, whatever the input rate.
+const OGG_OPUS_GRANULE_RATE: u32 = 48_000;
+
+/// Encoder delay players skip, in 48 kHz samples; libopus's lookahead at these settings.
+const OGG_OPUS_PRE_SKIP: u16 = 312;
+
+/// Serial number of the single logical stream in Ogg Opus files written here.
+const OGG_OPUS_SERIAL: u32 = u32::from_be_bytes(*b"VVOG");
+
+/// Packets per Ogg page, about one second of audio.
+const OGG_OPUS_PACKETS_PER_PAGE: usize = 50;
+
+fn opus_format(sample_rate: u32, channels: u8) -> Result<(SampleRate, Channels)> {
+    let rate = i32::try_from(sample_rate)
+        .ok()
+        .and_then(|rate| SampleRate::try_from(rate).ok())
+        .ok_or_else(|| anyhow!("Opus cannot encode {sample_rate} Hz audio"))?;
+    let layout = Channels::try_from(i32::from(channels))
+        .map_err(|_| anyhow!("Opus cannot encode {channels} channels"))?;
+    Ok((rate, layout))
+}
+
+fn speech_encoder(sample_rate: u32, channels: u8) -> Result<Encoder> {
+    let (rate, layout) = opus_format(sample_rate, channels)?;
+    let mut encoder =
+        Encoder::new(rate, layout, Application::Voip).context("Failed to create Opus encoder")?;
+    encoder
+        .set_bitrate(Bitrate::BitsPerSecond(
+            OPUS_BITRATE_PER_CHANNEL * i32::from(channels),
+        ))
+        .context("Failed to set Opus bitrate")?;
+    Ok(encoder)
+}
+
+/// Sample rate and channel count of a 16-bit PCM WAV.
+fn wav_format(wav_data: &[u8]) -> Result<(u32, u8)> {
+    let header = parse_wav_header(wav_data)?;
+    ensure!(
+        header.bits_per_sample == 16,
+        "Opus encoding needs 16-bit PCM, got {} bits",
+        header.bits_per_sample
+    );
+    let channels = u8::try_from(header.channels)
+        .map_err(|_| anyhow!("Too many channels: {}", header.channels))?;
+    Ok((header.sample_rate, channels))
+}
+
+/// Encodes WAV segments into one framed Opus stream as they are synthesized.
+///
+/// The first call to [`OpusStreamEncoder::encode_wav`] emits the stream header.
+/// Samples that do not fill a whole frame are carried into the next segment, and
+/// [`OpusStreamEncoder::finish`] pads the remainder with silence.
+pub struct OpusStreamEncoder {
+    encoder: Encoder,
+    header: OpusStreamHeader,
+    header_sent: bool,
+    pending: Vec<i16>,
+}
+
+impl OpusStreamEncoder {
+    /// Creates an encoder for 16-bit PCM at `sample_rate` with `channels` channels.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if Opus does not support the format or the encoder cannot be created.
+    pub fn new(sample_rate: u32, channels: u8) -> Result<Self> {
+        let frame_samples = u64::from(sample_rate) * OPUS_FRAME_DURATION.as_millis() as u64 / 1000;
+        Ok(Self {
+            encoder: speech_encoder(sample_rate, channels)?,
+            header: OpusStreamHeader {
+                sample_rate,
+                channels,
+                frame_samples: u16::try_from(frame_samples)
+                    .context("Opus frame size does not fit the stream header")?,
+            },
+            header_sent: false,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Creates an encoder matching the format of a synthesized WAV.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the WAV is malformed, not 16-bit, or in a format Opus rejects.
+    pub fn for_wav(wav_data: &[u8]) -> Result<Self> {
+        let (sample_rate, channels) = wav_format(wav_data)?;
+        Self::new(sample_rate, channels)
+    }
+
+    /// Encodes one WAV segment and returns the framed bytes ready to send.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the segment's format differs from the stream or encoding fails.
+    pub fn encode_wav(&mut self, wav_data: &[u8]) -> Result<Vec<u8>> {
+        let header = parse_wav_header(wav_data)?;
+        ensure!(
+            header.sample_rate == self.header.sample_rate
+                && header.channels == u16::from(self.header.channels)
+                && header.bits_per_sample == 16,
+            "Segment format differs from the Opus stream"
+        );
+        self.pending.extend(
+            wav_data[header.data_offset..header.data_offset + header.data_size]
+                .chunks_exact(2)
+                .map(|pair| i16::from_le_bytes([pair[0], pair[1]])),
+        );
+
+        let mut output = Vec::new();
+        if !self.header_sent {
+            output.extend_from_slice(&self.header.encode());
+            self.header_sent = true;
+        }
+        let frame_len = self.frame_len();
+        let whole = self.pending.len() - self.pending.len() % frame_len;
+        for frame in self.pending[..whole].chunks_exact(frame_len) {
+            encode_frame(&self.encoder, frame, &mut output)?;
+        }
+        self.pending.drain(..whole);
+        Ok(output)
+    }
+
+    /// Flushes buffered samples as a final silence-padded frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if encoding fails.
+    pub fn finish(mut self) -> Result<Vec<u8>> {
+        let mut output = Vec::new();
+        if self.pending.is_empty() {
+            return Ok(output);
+        }
+        self.pending.resize(self.frame_len(), 0);
+        encode_frame(&self.encoder, &self.pending, &mut output)?;
+        Ok(output)
+    }
+
+    fn frame_len(&self) -> usize {
+        usize::from(self.header.frame_samples) * usize::from(self.header.channels)
+    }
+}
+
+fn encode_frame(encoder: &Encoder, frame: &[i16], output: &mut Vec<u8>) -> Result<()> {
+    let mut packet = [0u8; MAX_OPUS_PACKET_LEN];
+    let length = encoder
+        .encode(frame, &mut packet)
+        .context("Opus encoding failed")?;
+    write_opus_packet(&packet[..length], output)
+}
+
+/// Encodes a 16-bit PCM WAV as an Ogg Opus file (RFC 7845) that common players open.
+///
+/// # Errors
+///
+/// Returns an error if the WAV is malformed, not 16-bit, in a format Opus rejects,
+/// or encoding fails.
+pub fn encode_ogg_opus(wav_data: &[u8]) -> Result<Vec<u8>> {
+    let (sample_rate, channels) = wav_format(wav_data)?;
+    ensure!(
+        sample_rate > 0 && OGG_OPUS_GRANULE_RATE % sample_rate == 0,
+        "Opus cannot encode {sample_rate} Hz audio"
+    );
+    let encoder = speech_encoder(sample_rate, channels)?;
+    let header = parse_wav_header(wav_data)?;
+    let samples = wav_data[header.data_offset..header.data_offset + header.data_size]
+        .chunks_exact(2)
+        .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+        .collect::<Vec<_>>();
+    let channel_count = usize::from(channels);
+    let total_frames = (samples.len() / channel_count) as u64;
+    let frame_samples =
+        (u64::from(sample_rate) * OPUS_FRAME_DURATION.as_millis() as u64 / 1000) as usize;
+    let granule_scale = u64::from(OGG_OPUS_GRANULE_RATE / sample_rate);
+
+    let mut ogg = OggWriter::new(OGG_OPUS_SERIAL);
+    let mut head = b"OpusHead".to_vec();
+    head.push(1);
+    head.push(channels);
+    head.extend_from_slice(&OGG_OPUS_PRE_SKIP.to_le_bytes());
+    head.extend_from_slice(&sample_rate.to_le_bytes());
+    head.extend_from_slice(&0i16.to_le_bytes());
+    head.push(0);
+    ogg.push_packet(&head, 0);
+    ogg.flush_page();
+
+    let vendor = concat!("voicevox-cli ", env!("CARGO_PKG_VERSION"));
+    let mut tags = b"OpusTags".to_vec();
+    tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+    tags.extend_from_slice(vendor.as_bytes());
+    tags.extend_from_slice(&0u32.to_le_bytes());
+    ogg.push_packet(&tags, 0);
+    ogg.flush_page();
+
+    let mut packet = [0u8; MAX_OPUS_PACKET_LEN];
+    for (index, chunk) in samples.chunks(frame_samples * channel_count).enumerate() {
+        let mut frame = chunk.to_vec();
+        frame.resize(frame_samples * channel_count, 0);
+        let length = encoder
+            .encode(&frame, &mut packet)
+            .context("Opus encoding failed")?;
+        // The last granule position trims the silence padding out the final frame.
+        let end = (((index + 1) * frame_samples) as u64).min(total_frames);
+        ogg.push_packet(
+            &packet[..length],
+            u64::from(OGG_OPUS_PRE_SKIP) + end * granule_scale,
+        );
+        if (index + 1) % OGG_OPUS_PACKETS_PER_PAGE == 0 {
+            ogg.flush_page();
+        }
+    }
+    Ok(ogg.finish())
+}
+
+/// Decodes a framed Opus stream incrementally, for clients playing while receiving.
+#[derive(Default)]
+pub struct OpusStreamDecoder {
+    reader: OpusFrameReader,
+    decoder: Option<Decoder>,
+}
+
+impl OpusStreamDecoder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stream format, once the header has arrived.
+    #[must_use]
+    pub const fn header(&self) -> Option<OpusStreamHeader> {
+        self.reader.header()
+    }
+
+    /// Feeds received bytes and returns the interleaved samples of every complete packet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream is malformed or a packet fails to decode.
+    pub fn push(&mut self, bytes: &[u8]) -> Result<Vec<i16>> {
+        self.reader.push(bytes);
+        let mut samples = Vec::new();
+        while let Some(item) = self.reader.next_item()? {
+            match item {
+                OpusStreamItem::Header(header) => {
+                    let (rate, layout) = opus_format(header.sample_rate, header.channels)?;
+                    self.decoder =
+                        Some(Decoder::new(rate, layout).context("Failed to create Opus decoder")?);
+                }
+                OpusStreamItem::Packet(packet) => {
+                    let (Some(decoder), Some(header)) =
+                        (self.decoder.as_mut(), self.reader.header())
+                    else {
+                        return Err(anyhow!("Opus packet arrived before the stream header"));
+                    };
+                    let mut frame = vec![
+                        0i16;
+                        usize::from(header.frame_samples)
+                            * usize::from(header.channels)
+                    ];
+                    let packet = Packet::try_from(packet.as_slice())?;
+                    let signals = MutSignals::try_from(frame.as_mut_slice())?;
+                    let decoded = decoder
+                        .decode(Some(packet), signals, false)
+                        .context("Opus decoding failed")?;
+                    frame.truncate(decoded * usize::from(header.channels));
+                    samples.extend_from_slice(&frame);
+                }
+            }
+        }
+        Ok(samples)
+    }
+}
+
+/// Wraps decoded samples in a WAV so they can be saved or handed to a playback sink.
+#[must_use]
+pub fn opus_samples_to_wav(header: OpusStreamHeader, samples: &[i16]) -> Vec<u8> {
+    let pcm: Vec<u8> = samples
+        .iter()
+        .flat_map(|sample| sample.to_le_bytes())
+        .collect();
+    make_wav(&pcm, u16::from(header.channels), header.sample_rate, 16)
+}
+
+/// Decodes a complete framed Opus stream back into a WAV.
+///
+/// # Errors
+///
+/// Returns an error if the stream is empty, truncated, malformed, or fails to decode.
+pub fn decode_opus_stream(stream: &[u8]) -> Result<Vec<u8>> {
+    let mut decoder = OpusStreamDecoder::new();
+    let samples = decoder.push(stream)?;
+    let header = decoder
+        .header()
+        .ok_or_else(|| anyhow!("Opus stream ended before its header"))?;
+    ensure!(
+        decoder.reader.pending_len() == 0,
+        "Opus stream ends in the middle of a packet"
+    );
+    Ok(opus_samples_to_wav(header, &samples))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ogg_opus_starts_with_head_and_tags_pages() {
+        let wav = make_wav(&[0; 24_000 * 2], 1, 24_000, 16);
+        let ogg = encode_ogg_opus(&wav).expect("encoded");
+
+        assert_eq!(&ogg[..4], b"OggS");
+        assert_eq!(&ogg[28..36], b"OpusHead");
+        assert_eq!(ogg[37], 1);
+        assert_eq!(
+            u32::from_le_bytes(ogg[40..44].try_into().expect("rate")),
+            24_000
+        );
+        let tags_page = 28 + 19;
+        assert_eq!(&ogg[tags_page..tags_page + 4], b"OggS");
+        assert_eq!(&ogg[tags_page + 28..tags_page + 36], b"OpusTags");
+
+        let last_page = ogg
+            .windows(4)
+            .rposition(|window| window == b"OggS")
+            .expect("page");
+        let granule = u64::from_le_bytes(
+            ogg[last_page + 6..last_page + 14]
+                .try_into()
+                .expect("granule"),
+        );
+        assert_eq!(granule, u64::from(OGG_OPUS_PRE_SKIP) + 48_000);
+    }
+}
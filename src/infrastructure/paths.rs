@@ -1,5 +1,6 @@
-use anyhow::{Result, anyhow};
+use anyhow::{Result, anyhow, ensure};
 use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 
 const MODELS_SUBDIR: &str = "models";
 const VVM_SUBDIR: &str = "vvms";
@@ -96,10 +97,101 @@ pub fn get_default_voicevox_dir() -> PathBuf {
         .unwrap_or_else(|| PathBuf::from(".").join(crate::config::APP_NAME))
 }
 
+/// Get the per-user VOICEVOX state directory (queues, casting, usage records)
+/// Priority: $`XDG_STATE_HOME/voicevox` > ~/.local/state/voicevox
+#[must_use]
+pub fn get_state_dir() -> PathBuf {
+    std::env::var_os(crate::config::ENV_XDG_STATE_HOME)
+        .map(|p| PathBuf::from(p).join(crate::config::APP_NAME))
+        .or_else(|| dirs::state_dir().map(|d| d.join(crate::config::APP_NAME)))
+        .or_else(|| {
+            dirs::home_dir().map(|h| {
+                h.join(crate::config::USER_LOCAL_STATE_DIR)
+                    .join(crate::config::APP_NAME)
+            })
+        })
+        .unwrap_or_else(|| {
+            PathBuf::from(crate::config::DEFAULT_TMP_DIR).join(crate::config::APP_NAME)
+        })
+}
+
+/// Get the per-user VOICEVOX config directory (daemon.toml)
+/// Priority: $`XDG_CONFIG_HOME/voicevox` > ~/.config/voicevox
+#[must_use]
+pub fn get_config_dir() -> PathBuf {
+    std::env::var_os(crate::config::ENV_XDG_CONFIG_HOME)
+        .map(|p| PathBuf::from(p).join(crate::config::APP_NAME))
+        .or_else(|| {
+            dirs::home_dir().map(|h| {
+                h.join(crate::config::USER_CONFIG_DIR)
+                    .join(crate::config::APP_NAME)
+            })
+        })
+        .unwrap_or_else(|| PathBuf::from(".").join(crate::config::APP_NAME))
+}
+
+/// Get the per-user VOICEVOX cache directory (speaker catalog)
+/// Priority: $`XDG_CACHE_HOME/voicevox` > ~/.cache/voicevox
+#[must_use]
+pub fn get_cache_dir() -> PathBuf {
+    std::env::var_os(crate::config::ENV_XDG_CACHE_HOME)
+        .map(|p| PathBuf::from(p).join(crate::config::APP_NAME))
+        .or_else(|| {
+            dirs::home_dir().map(|h| {
+                h.join(crate::config::USER_CACHE_DIR)
+                    .join(crate::config::APP_NAME)
+            })
+        })
+        .unwrap_or_else(|| PathBuf::from(".").join(crate::config::APP_NAME))
+}
+
+/// Directory for temporary audio files.
+/// Priority: $`VOICEVOX_TMPDIR` > the system temp directory ($`TMPDIR`, /tmp)
+#[must_use]
+pub fn get_temp_dir() -> PathBuf {
+    std::env::var_os(crate::config::ENV_VOICEVOX_TMPDIR)
+        .filter(|dir| !dir.is_empty())
+        .map_or_else(std::env::temp_dir, PathBuf::from)
+}
+
+/// Location of the CLI's user dictionary, in the data directory.
+#[must_use]
+pub fn user_dict_path() -> PathBuf {
+    get_default_voicevox_dir().join(crate::config::USER_DICT_FILE)
+}
+
+/// Location of named synthesis presets, in the config directory.
+#[must_use]
+pub fn voice_presets_path() -> PathBuf {
+    get_config_dir().join(crate::config::VOICE_PRESETS_FILE)
+}
+
+/// Location of user-defined voice aliases, in the config directory.
+#[must_use]
+pub fn voice_aliases_path() -> PathBuf {
+    get_config_dir().join(crate::config::VOICE_ALIASES_FILE)
+}
+
+/// Location of post-synthesis hook commands, in the config directory.
+#[must_use]
+pub fn hooks_config_path() -> PathBuf {
+    get_config_dir().join(crate::config::HOOKS_CONFIG_FILE)
+}
+
+/// Socket of a daemon started with `--shared-socket`, reachable by its group.
+#[must_use]
+pub fn get_shared_socket_path() -> PathBuf {
+    PathBuf::from(crate::config::DEFAULT_TMP_DIR)
+        .join(crate::config::SHARED_SOCKET_DIR_NAME)
+        .join(crate::config::SOCKET_FILENAME)
+}
+
 #[must_use]
 pub fn get_socket_path() -> PathBuf {
     std::env::var_os(crate::config::ENV_VOICEVOX_SOCKET_PATH)
         .map(PathBuf::from)
+        .or_else(|| crate::config::use_shared_socket().then(get_shared_socket_path))
+        .or_else(|| crate::config::user_config().socket_path.clone())
         .or_else(|| {
             [
                 crate::config::ENV_XDG_RUNTIME_DIR,
@@ -130,20 +222,60 @@ pub fn get_socket_path() -> PathBuf {
         })
 }
 
-/// Finds the VOICEVOX models directory, honoring environment overrides first.
+/// Directory models are loaded from instead of the configured one, set by the
+/// daemon once it has copied them to local storage.
+static MODELS_DIR_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Makes [`find_models_dir`] return `dir` in this process; `None` restores discovery.
+pub fn set_models_dir_override(dir: Option<PathBuf>) {
+    *MODELS_DIR_OVERRIDE
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = dir;
+}
+
+/// The directory set with [`set_models_dir_override`], if any.
+#[must_use]
+pub fn models_dir_override() -> Option<PathBuf> {
+    MODELS_DIR_OVERRIDE
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone()
+}
+
+/// Finds the directory models are loaded from: the local copy when the daemon made
+/// one, otherwise the configured directory.
 ///
 /// # Errors
 ///
 /// Returns an error if no plausible models directory can be found.
 pub fn find_models_dir() -> Result<PathBuf> {
+    models_dir_override().map_or_else(find_configured_models_dir, Ok)
+}
+
+/// Finds the VOICEVOX models directory, honoring environment overrides first and
+/// `models_dir` in `config.toml` next.
+///
+/// # Errors
+///
+/// Returns an error if `models_dir` is set but is not a directory, or if no
+/// plausible models directory can be found.
+pub fn find_configured_models_dir() -> Result<PathBuf> {
+    if let Some(dir) = existing_dir_from_env(crate::config::ENV_VOICEVOX_MODELS_DIR) {
+        return Ok(dir);
+    }
+    if let Some(dir) = &crate::config::user_config().models_dir {
+        ensure!(
+            dir.is_dir(),
+            "models_dir in config.toml is not a directory: {}",
+            dir.display()
+        );
+        return Ok(dir.clone());
+    }
     let xdg_dirs = xdg_app_data_dirs();
-    existing_dir_from_env(crate::config::ENV_VOICEVOX_MODELS_DIR)
-        .or_else(|| {
-            xdg_dirs
-                .iter()
-                .flatten()
-                .find_map(|dir| preferred_models_dir(dir))
-        })
+    xdg_dirs
+        .iter()
+        .flatten()
+        .find_map(|dir| preferred_models_dir(dir))
         .or_else(|| {
             xdg_dirs
                 .into_iter()
@@ -0,0 +1,108 @@
+use std::path::Path;
+use std::process::Command;
+
+const SYSFS_POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+
+/// Where the machine currently draws power from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerSource {
+    Mains,
+    Battery,
+}
+
+/// Detects whether the machine runs on battery, through `pmset` on macOS and
+/// sysfs on Linux.
+///
+/// Returns `None` when it cannot tell, such as on desktops without a battery or
+/// when `pmset` is unavailable.
+#[must_use]
+pub fn power_source() -> Option<PowerSource> {
+    if cfg!(target_os = "macos") {
+        let output = Command::new(crate::config::command_path_or_fallback(
+            crate::config::SYSTEM_PMSET_PATH,
+            "pmset",
+        ))
+        .args(["-g", "batt"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+        parse_pmset_source(&String::from_utf8_lossy(&output.stdout))
+    } else {
+        sysfs_power_source(Path::new(SYSFS_POWER_SUPPLY_DIR))
+    }
+}
+
+/// Reads the first line of `pmset -g batt`: `Now drawing from 'Battery Power'`.
+fn parse_pmset_source(output: &str) -> Option<PowerSource> {
+    let first_line = output.lines().next()?;
+    if first_line.contains("'Battery Power'") {
+        Some(PowerSource::Battery)
+    } else if first_line.contains("'AC Power'") {
+        Some(PowerSource::Mains)
+    } else {
+        None
+    }
+}
+
+/// A machine with a battery is on battery unless some mains or USB supply is online.
+fn sysfs_power_source(dir: &Path) -> Option<PowerSource> {
+    let read = |path: &Path, name: &str| {
+        std::fs::read_to_string(path.join(name))
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default()
+    };
+    let mut has_battery = false;
+    for entry in std::fs::read_dir(dir).ok()?.filter_map(Result::ok) {
+        let supply = entry.path();
+        match read(&supply, "type").as_str() {
+            "Battery" => has_battery = true,
+            "Mains" | "USB" if read(&supply, "online") == "1" => {
+                return Some(PowerSource::Mains);
+            }
+            _ => {}
+        }
+    }
+    has_battery.then_some(PowerSource::Battery)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn supply(dir: &Path, name: &str, kind: &str, online: Option<&str>) {
+        let supply = dir.join(name);
+        std::fs::create_dir_all(&supply).expect("supply dir");
+        std::fs::write(supply.join("type"), format!("{kind}\n")).expect("type");
+        if let Some(online) = online {
+            std::fs::write(supply.join("online"), format!("{online}\n")).expect("online");
+        }
+    }
+
+    #[test]
+    fn reads_sysfs_power_supplies() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert_eq!(sysfs_power_source(dir.path()), None);
+
+        supply(dir.path(), "BAT0", "Battery", None);
+        supply(dir.path(), "AC", "Mains", Some("0"));
+        assert_eq!(sysfs_power_source(dir.path()), Some(PowerSource::Battery));
+
+        supply(dir.path(), "AC", "Mains", Some("1"));
+        assert_eq!(sysfs_power_source(dir.path()), Some(PowerSource::Mains));
+    }
+
+    #[test]
+    fn reads_pmset_output() {
+        assert_eq!(
+            parse_pmset_source(
+                "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=1)\t81%; discharging\n"
+            ),
+            Some(PowerSource::Battery)
+        );
+        assert_eq!(
+            parse_pmset_source("Now drawing from 'AC Power'\n"),
+            Some(PowerSource::Mains)
+        );
+        assert_eq!(parse_pmset_source(""), None);
+    }
+}
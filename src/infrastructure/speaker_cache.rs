@@ -0,0 +1,146 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::infrastructure::paths::get_cache_dir;
+use crate::infrastructure::state_store::save_json;
+use crate::infrastructure::voicevox::Speaker;
+
+/// How long a cached speaker catalog is trusted without asking the daemon again.
+pub const SPEAKER_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Speaker catalog last listed by a daemon, kept so listings skip the round-trip.
+///
+/// The daemon deletes the file whenever its model catalog may have changed, at
+/// startup and on `--reload`; the TTL covers daemons of other users sharing a socket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerCache {
+    /// Daemon socket the catalog came from; a different socket is a cache miss.
+    pub socket_path: PathBuf,
+    pub fetched_at_secs: u64,
+    pub speakers: Vec<Speaker>,
+    pub style_to_model: HashMap<u32, u32>,
+}
+
+impl SpeakerCache {
+    /// Captures a listing; `loaded` markers are cleared since they go stale at once.
+    #[must_use]
+    pub fn new(
+        socket_path: &Path,
+        mut speakers: Vec<Speaker>,
+        style_to_model: HashMap<u32, u32>,
+    ) -> Self {
+        for style in speakers.iter_mut().flat_map(|speaker| &mut speaker.styles) {
+            style.loaded = false;
+        }
+        Self {
+            socket_path: socket_path.to_path_buf(),
+            fetched_at_secs: unix_now_secs(),
+            speakers,
+            style_to_model,
+        }
+    }
+
+    /// Whether this catalog came from `socket_path` within the TTL as of `now_secs`.
+    #[must_use]
+    pub fn is_fresh_for(&self, socket_path: &Path, now_secs: u64) -> bool {
+        self.socket_path == socket_path
+            && now_secs.saturating_sub(self.fetched_at_secs) < SPEAKER_CACHE_TTL.as_secs()
+    }
+}
+
+/// Location of the cached speaker catalog, in the cache directory.
+#[must_use]
+pub fn speaker_cache_path() -> PathBuf {
+    get_cache_dir().join(crate::config::SPEAKER_CACHE_FILE)
+}
+
+/// Reads the cached catalog for `socket_path` if it is still fresh.
+///
+/// A missing, unreadable, or stale cache is a miss rather than an error.
+#[must_use]
+pub fn load_fresh_speakers(path: &Path, socket_path: &Path) -> Option<SpeakerCache> {
+    let contents = std::fs::read(path).ok()?;
+    let cache: SpeakerCache = serde_json::from_slice(&contents).ok()?;
+    cache
+        .is_fresh_for(socket_path, unix_now_secs())
+        .then_some(cache)
+}
+
+/// Replaces the cached catalog.
+///
+/// # Errors
+///
+/// Returns an error if the cache directory or file cannot be written.
+pub fn store_speakers(path: &Path, cache: &SpeakerCache) -> Result<()> {
+    save_json(path, cache)
+}
+
+/// Caches a catalog just listed by the daemon at `socket_path`.
+///
+/// Failing to write the cache only costs the next listing a round-trip, so it is ignored.
+pub fn remember_speakers(
+    socket_path: &Path,
+    speakers: Vec<Speaker>,
+    style_to_model: HashMap<u32, u32>,
+) {
+    let cache = SpeakerCache::new(socket_path, speakers, style_to_model);
+    let _ = store_speakers(&speaker_cache_path(), &cache);
+}
+
+/// Deletes the cached catalog so the next listing asks the daemon.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be removed.
+pub fn invalidate_speaker_cache(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+            Err(error).with_context(|| format!("Failed to remove {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn unix_now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_roundtrips_until_invalidated_or_for_another_socket() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("cache").join("speakers.json");
+        let socket = Path::new("/tmp/voicevox-daemon.sock");
+        let cache = SpeakerCache::new(socket, Vec::new(), HashMap::from([(3, 0)]));
+
+        store_speakers(&path, &cache).expect("store");
+        let loaded = load_fresh_speakers(&path, socket).expect("fresh cache");
+        assert_eq!(loaded.style_to_model, HashMap::from([(3, 0)]));
+        assert!(load_fresh_speakers(&path, Path::new("/tmp/other.sock")).is_none());
+
+        invalidate_speaker_cache(&path).expect("invalidate");
+        assert!(load_fresh_speakers(&path, socket).is_none());
+        invalidate_speaker_cache(&path).expect("already gone");
+    }
+
+    #[test]
+    fn cache_expires_after_ttl() {
+        let cache = SpeakerCache {
+            socket_path: PathBuf::from("/tmp/voicevox-daemon.sock"),
+            fetched_at_secs: 1_000,
+            speakers: Vec::new(),
+            style_to_model: HashMap::new(),
+        };
+        let socket = cache.socket_path.clone();
+        assert!(cache.is_fresh_for(&socket, 1_000 + SPEAKER_CACHE_TTL.as_secs() - 1));
+        assert!(!cache.is_fresh_for(&socket, 1_000 + SPEAKER_CACHE_TTL.as_secs()));
+    }
+}
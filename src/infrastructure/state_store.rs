@@ -0,0 +1,70 @@
+use anyhow::{Context, Result};
+use serde::{Serialize, de::DeserializeOwned};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::infrastructure::paths::get_state_dir;
+
+/// Returns the path of a named JSON document under the per-user state directory.
+#[must_use]
+pub fn state_file_path(file_name: &str) -> PathBuf {
+    get_state_dir().join(file_name)
+}
+
+/// Reads a JSON document, returning `T::default()` when the file does not exist yet.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read or parsed.
+pub fn load_json_or_default<T: DeserializeOwned + Default>(path: &Path) -> Result<T> {
+    let contents = match std::fs::read(path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(T::default()),
+        Err(error) => {
+            return Err(error).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+    serde_json::from_slice(&contents).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Atomically replaces a JSON document, creating parent directories as needed.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be created or the file cannot be written.
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let parent = path
+        .parent()
+        .with_context(|| format!("{} has no parent directory", path.display()))?;
+    std::fs::create_dir_all(parent)
+        .with_context(|| format!("Failed to create {}", parent.display()))?;
+
+    let mut temp = tempfile::NamedTempFile::new_in(parent)
+        .with_context(|| format!("Failed to create temporary file in {}", parent.display()))?;
+    serde_json::to_writer_pretty(&mut temp, value)
+        .with_context(|| format!("Failed to serialize {}", path.display()))?;
+    temp.write_all(b"\n")?;
+    temp.persist(path)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn missing_file_loads_default_and_saved_file_roundtrips() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("nested").join("state.json");
+
+        let empty: BTreeMap<String, u32> = load_json_or_default(&path).expect("default");
+        assert!(empty.is_empty());
+
+        let value = BTreeMap::from([("a".to_owned(), 1_u32)]);
+        save_json(&path, &value).expect("save");
+        let loaded: BTreeMap<String, u32> = load_json_or_default(&path).expect("load");
+        assert_eq!(loaded, value);
+    }
+}
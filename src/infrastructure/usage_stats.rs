@@ -0,0 +1,159 @@
+//! Local usage statistics, recorded by the daemon only when `daemon.toml` opts in.
+//!
+//! Nothing here leaves the machine: the file sits in the state directory and is
+//! read back by `voicevox-say --stats`.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::infrastructure::state_store::{load_json_or_default, save_json, state_file_path};
+
+/// Totals accumulated across daemon runs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageStats {
+    /// Requests served per style ID.
+    #[serde(default)]
+    pub voices: BTreeMap<u32, VoiceUsage>,
+    /// Lookups in the `[model_cache]` of loaded models.
+    #[serde(default)]
+    pub model_cache: CacheCounts,
+    /// Lookups in the `[dedup]` window of recent requests.
+    #[serde(default)]
+    pub dedup: CacheCounts,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct VoiceUsage {
+    pub requests: u64,
+    pub audio_seconds: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheCounts {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheCounts {
+    /// Share of lookups that hit, or `None` before the first lookup.
+    #[must_use]
+    pub fn hit_rate(self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+
+    pub fn add(&mut self, other: Self) {
+        self.hits += other.hits;
+        self.misses += other.misses;
+    }
+}
+
+impl UsageStats {
+    /// Counts one request for `style_id` that produced `audio`.
+    pub fn record_voice(&mut self, style_id: u32, audio: Duration) {
+        let usage = self.voices.entry(style_id).or_default();
+        usage.requests += 1;
+        usage.audio_seconds += audio.as_secs_f64();
+    }
+
+    /// Style IDs by how often they were requested, most requested first.
+    #[must_use]
+    pub fn most_used_styles(&self) -> Vec<u32> {
+        let mut voices = self.voices.iter().collect::<Vec<_>>();
+        voices.sort_by(|(left_id, left), (right_id, right)| {
+            right
+                .requests
+                .cmp(&left.requests)
+                .then(left_id.cmp(right_id))
+        });
+        voices.into_iter().map(|(&style_id, _)| style_id).collect()
+    }
+
+    /// Human-readable summary, most requested voices first.
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        if self.voices.is_empty() {
+            return vec!["No usage recorded yet".to_string()];
+        }
+
+        let total_seconds: f64 = self.voices.values().map(|usage| usage.audio_seconds).sum();
+        let mut lines = vec![format!("Total audio: {total_seconds:.1}s")];
+        lines.extend(self.most_used_styles().into_iter().map(|style_id| {
+            let usage = self.voices[&style_id];
+            format!(
+                "  style {style_id}: {} requests, {:.1}s",
+                usage.requests, usage.audio_seconds
+            )
+        }));
+        for (name, counts) in [("Model cache", self.model_cache), ("Dedup", self.dedup)] {
+            if let Some(rate) = counts.hit_rate() {
+                lines.push(format!(
+                    "{name} hit rate: {:.0}% ({} of {})",
+                    rate * 100.0,
+                    counts.hits,
+                    counts.hits + counts.misses
+                ));
+            }
+        }
+        lines
+    }
+}
+
+/// Location of the usage statistics file, in the state directory.
+#[must_use]
+pub fn usage_stats_path() -> PathBuf {
+    state_file_path(crate::config::USAGE_STATS_FILE)
+}
+
+/// Reads the statistics, or empty ones when nothing was recorded yet.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read or parsed.
+pub fn load_usage_stats(path: &Path) -> Result<UsageStats> {
+    load_json_or_default(path)
+}
+
+/// Replaces the statistics file.
+///
+/// # Errors
+///
+/// Returns an error if the state directory or file cannot be written.
+pub fn save_usage_stats(path: &Path, stats: &UsageStats) -> Result<()> {
+    save_json(path, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_roundtrip_and_summarize_by_requests() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("usage_stats.json");
+        let mut stats = load_usage_stats(&path).expect("empty stats");
+        assert_eq!(stats.lines(), vec!["No usage recorded yet".to_string()]);
+
+        stats.record_voice(3, Duration::from_millis(1500));
+        stats.record_voice(8, Duration::from_secs(1));
+        stats.record_voice(8, Duration::from_secs(2));
+        stats.model_cache.add(CacheCounts { hits: 3, misses: 1 });
+        save_usage_stats(&path, &stats).expect("save");
+
+        let loaded = load_usage_stats(&path).expect("load");
+        assert_eq!(loaded, stats);
+        assert_eq!(loaded.most_used_styles(), vec![8, 3]);
+        assert_eq!(
+            loaded.lines(),
+            vec![
+                "Total audio: 4.5s".to_string(),
+                "  style 8: 2 requests, 3.0s".to_string(),
+                "  style 3: 1 requests, 1.5s".to_string(),
+                "Model cache hit rate: 75% (3 of 4)".to_string(),
+            ]
+        );
+    }
+}
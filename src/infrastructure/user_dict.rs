@@ -0,0 +1,83 @@
+use anyhow::{Result, anyhow};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use voicevox_core::blocking::{OpenJtalk, UserDict};
+use voicevox_core::{UserDictWord, UserDictWordType};
+
+use crate::domain::user_dict::{DictionaryWord, WordType};
+use crate::infrastructure::state_store::{load_json_or_default, save_json};
+
+/// Raised each time the daemon is told the saved dictionary changed, so cores
+/// kept between requests know to pick it up again.
+static USER_DICT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Revision of the saved dictionary the process last heard about.
+#[must_use]
+pub fn user_dict_generation() -> u64 {
+    USER_DICT_GENERATION.load(Ordering::SeqCst)
+}
+
+/// Marks the saved dictionary as changed; returns the new generation.
+pub fn bump_user_dict_generation() -> u64 {
+    USER_DICT_GENERATION.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+/// Reads the dictionary words saved at `path`; a missing file has no words.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read or parsed.
+pub fn load_dictionary_words(path: &Path) -> Result<Vec<DictionaryWord>> {
+    load_json_or_default(path)
+}
+
+/// Replaces the dictionary saved at `path`.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn save_dictionary_words(path: &Path, words: &[DictionaryWord]) -> Result<()> {
+    save_json(path, &words)
+}
+
+const fn core_word_type(word_type: WordType) -> UserDictWordType {
+    match word_type {
+        WordType::ProperNoun => UserDictWordType::ProperNoun,
+        WordType::CommonNoun => UserDictWordType::CommonNoun,
+        WordType::Verb => UserDictWordType::Verb,
+        WordType::Adjective => UserDictWordType::Adjective,
+        WordType::Suffix => UserDictWordType::Suffix,
+    }
+}
+
+/// Builds the Core dictionary for `words`.
+///
+/// # Errors
+///
+/// Returns an error naming the first word Core rejects.
+pub fn build_user_dict(words: &[DictionaryWord]) -> Result<UserDict> {
+    let dict = UserDict::new();
+    for word in words {
+        let core_word = UserDictWord::builder()
+            .accent_type(word.accent_type)
+            .word_type(core_word_type(word.word_type))
+            .priority(word.priority)
+            .build(&word.surface, word.pronunciation.clone())
+            .map_err(|e| anyhow!("Invalid dictionary word '{}': {e}", word.surface))?;
+        dict.add_word(core_word)
+            .map_err(|e| anyhow!("Failed to add dictionary word '{}': {e}", word.surface))?;
+    }
+    Ok(dict)
+}
+
+/// Makes `open_jtalk` read `words` on top of its system dictionary.
+///
+/// # Errors
+///
+/// Returns an error if a word is rejected or OpenJTalk cannot rebuild its dictionary.
+pub fn use_dictionary_words(open_jtalk: &OpenJtalk, words: &[DictionaryWord]) -> Result<()> {
+    let dict = build_user_dict(words)?;
+    open_jtalk
+        .use_user_dict(&dict)
+        .map_err(|e| anyhow!("Failed to apply the user dictionary: {e}"))
+}
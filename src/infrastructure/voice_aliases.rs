@@ -0,0 +1,23 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::domain::voice_alias::VoiceAliases;
+use crate::infrastructure::state_store::{load_json_or_default, save_json};
+
+/// Reads the voice aliases saved at `path`; a missing file has none.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read or parsed.
+pub fn load_voice_aliases(path: &Path) -> Result<VoiceAliases> {
+    load_json_or_default(path)
+}
+
+/// Replaces the voice aliases saved at `path`.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn save_voice_aliases(path: &Path, aliases: &VoiceAliases) -> Result<()> {
+    save_json(path, aliases)
+}
@@ -2,6 +2,9 @@ use anyhow::{Result, anyhow};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use voicevox_core::CharacterMeta;
+
+use crate::domain::voice::VoiceId;
 use voicevox_core::blocking::{OpenJtalk, Synthesizer, VoiceModelFile};
 
 #[cfg(feature = "rayon")]
@@ -49,6 +52,10 @@ pub struct Style {
 
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub style_type: Option<VoiceString>,
+
+    /// The style's model is loaded in the daemon, so synthesis starts without a load delay.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub loaded: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +63,9 @@ pub struct AvailableModel {
     pub model_id: u32,
     pub file_path: PathBuf,
     pub speakers: SpeakerList,
+    /// The model is loaded in the daemon right now.
+    #[serde(default)]
+    pub loaded: bool,
 }
 
 pub type StyleModelMapBuildResult = (HashMap<u32, u32>, Vec<Speaker>, Vec<AvailableModel>);
@@ -90,27 +100,99 @@ pub fn open_voice_model_file_by_id(model_id: u32) -> Result<VoiceModelFile> {
 }
 
 #[allow(clippy::useless_conversion)] // voicevox_core may use CompactString
+fn speaker_from_meta(meta: &CharacterMeta) -> Speaker {
+    Speaker {
+        name: meta.name.clone().into(),
+        speaker_uuid: meta.speaker_uuid.clone().into(),
+        styles: meta
+            .styles
+            .iter()
+            .map(|style| Style {
+                name: style.name.clone().into(),
+                id: style.id.0,
+                style_type: Some(format!("{:?}", style.r#type).into()),
+                loaded: false,
+            })
+            .collect(),
+        version: meta.version.to_string().into(),
+    }
+}
+
 pub(crate) fn collect_speakers_from_synthesizer(
     synthesizer: &Synthesizer<OpenJtalk>,
 ) -> Vec<Speaker> {
-    synthesizer
-        .metas()
+    synthesizer.metas().iter().map(speaker_from_meta).collect()
+}
+
+/// Finds a speaker's style by UUID and style name, or its first style when no name is given.
+///
+/// Speakers whose styles are split across model files are searched as one.
+#[must_use]
+pub fn find_speaker_style<'a>(
+    speakers: &'a [Speaker],
+    speaker_uuid: &str,
+    style_name: Option<&str>,
+) -> Option<(&'a Speaker, &'a Style)> {
+    let mut styles = speakers
         .iter()
-        .map(|meta| Speaker {
-            name: meta.name.clone().into(),
-            speaker_uuid: meta.speaker_uuid.clone().into(),
-            styles: meta
-                .styles
-                .iter()
-                .map(|style| Style {
-                    name: style.name.clone().into(),
-                    id: style.id.0,
-                    style_type: Some(format!("{:?}", style.r#type).into()),
-                })
-                .collect(),
-            version: meta.version.to_string().into(),
-        })
-        .collect()
+        .filter(|speaker| speaker.speaker_uuid.eq_ignore_ascii_case(speaker_uuid))
+        .flat_map(|speaker| speaker.styles.iter().map(move |style| (speaker, style)));
+    match style_name {
+        Some(name) => styles.find(|(_, style)| style.name == name),
+        None => styles.next(),
+    }
+}
+
+/// Numeric style ID currently assigned to a canonical voice ID.
+#[must_use]
+pub fn style_id_for_voice(speakers: &[Speaker], voice: &VoiceId) -> Option<u32> {
+    find_speaker_style(speakers, &voice.speaker_uuid, Some(&voice.style_name))
+        .map(|(_, style)| style.id)
+}
+
+/// Canonical voice ID for a numeric style ID, if a speaker declares that style.
+#[must_use]
+pub fn voice_id_for_style(speakers: &[Speaker], style_id: u32) -> Option<VoiceId> {
+    speakers.iter().find_map(|speaker| {
+        speaker
+            .styles
+            .iter()
+            .find(|style| style.id == style_id)
+            .filter(|_| !speaker.speaker_uuid.is_empty())
+            .map(|style| VoiceId {
+                speaker_uuid: speaker.speaker_uuid.to_ascii_lowercase().to_string(),
+                style_name: style.name.to_string(),
+            })
+    })
+}
+
+/// Reads speaker metadata from every installed model file without loading any model.
+///
+/// Speakers split across several files appear once per file.
+///
+/// # Errors
+///
+/// Returns an error if the models directory cannot be resolved or traversed. Model
+/// files that cannot be opened are skipped.
+pub fn scan_speakers_from_model_files() -> Result<Vec<Speaker>> {
+    let models = scan_available_models()?;
+    let read = |model: &AvailableModel| {
+        open_voice_model_file(&model.file_path).map_or_else(
+            |error| {
+                crate::infrastructure::logging::warn(&error.to_string());
+                Vec::new()
+            },
+            |file| file.metas().iter().map(speaker_from_meta).collect(),
+        )
+    };
+
+    #[cfg(feature = "rayon")]
+    let per_model = models.par_iter().map(read).collect::<Vec<Vec<Speaker>>>();
+
+    #[cfg(not(feature = "rayon"))]
+    let per_model = models.iter().map(read).collect::<Vec<Vec<Speaker>>>();
+
+    Ok(per_model.into_iter().flatten().collect())
 }
 
 fn available_models_from_paths(model_files: Vec<PathBuf>) -> Vec<AvailableModel> {
@@ -121,6 +203,7 @@ fn available_models_from_paths(model_files: Vec<PathBuf>) -> Vec<AvailableModel>
                 model_id,
                 file_path,
                 speakers: SpeakerList::new(),
+                loaded: false,
             })
         })
         .collect()
@@ -133,6 +216,7 @@ fn available_models_from_entries(model_entries: Vec<(u32, PathBuf)>) -> Vec<Avai
             model_id,
             file_path,
             speakers: SpeakerList::new(),
+            loaded: false,
         })
         .collect()
 }
@@ -272,7 +356,7 @@ fn has_any_vvm_file(dir: &Path) -> bool {
     })
 }
 
-fn find_vvm_files(dir: &Path) -> Result<Vec<PathBuf>> {
+pub(crate) fn find_vvm_files(dir: &Path) -> Result<Vec<PathBuf>> {
     if !dir.exists() {
         return Ok(Vec::new());
     }
@@ -280,13 +364,16 @@ fn find_vvm_files(dir: &Path) -> Result<Vec<PathBuf>> {
     collect_vvm_files(dir)
 }
 
+/// Walks `dir` recursively; with the `rayon` feature, subdirectories are walked in parallel.
+///
+/// The order of the returned paths is unspecified; callers sort by model ID.
 fn collect_vvm_files(dir: &Path) -> Result<Vec<PathBuf>> {
     let entries = std::fs::read_dir(dir)
         .map_err(|e| anyhow!("Failed to read directory {}: {e}", dir.display()))?;
 
-    entries
-        .into_iter()
-        .try_fold(Vec::new(), |mut files, entry_result| {
+    let (mut files, subdirs) = entries.into_iter().try_fold(
+        (Vec::new(), Vec::new()),
+        |(mut files, mut subdirs), entry_result| {
             let entry = entry_result
                 .map_err(|e| anyhow!("Failed to read entry in {}: {e}", dir.display()))?;
             let file_type = entry
@@ -296,15 +383,57 @@ fn collect_vvm_files(dir: &Path) -> Result<Vec<PathBuf>> {
 
             if file_type.is_file() && is_vvm_path(&path) {
                 files.push(path);
-                return Ok(files);
+            } else if file_type.is_dir() {
+                subdirs.push(path);
             }
 
-            if file_type.is_dir() {
-                files.extend(collect_vvm_files(&path)?);
-            }
+            Ok::<_, anyhow::Error>((files, subdirs))
+        },
+    )?;
 
-            Ok(files)
-        })
+    #[cfg(feature = "rayon")]
+    let nested = subdirs
+        .par_iter()
+        .map(|subdir| collect_vvm_files(subdir))
+        .collect::<Result<Vec<_>>>()?;
+
+    #[cfg(not(feature = "rayon"))]
+    let nested = subdirs
+        .iter()
+        .map(|subdir| collect_vvm_files(subdir))
+        .collect::<Result<Vec<_>>>()?;
+
+    files.extend(nested.into_iter().flatten());
+    Ok(files)
+}
+
+/// Reads the style IDs declared in a model file's metadata without loading it into Core.
+fn read_model_style_ids(path: &Path) -> Result<Vec<u32>> {
+    let model = open_voice_model_file(path)?;
+    Ok(model
+        .metas()
+        .iter()
+        .flat_map(|meta| meta.styles.iter().map(|style| style.id.0))
+        .collect())
+}
+
+/// Reads every model's style IDs, in parallel with the `rayon` feature, keeping entry order.
+fn read_all_model_style_ids(model_entries: &[(u32, PathBuf)]) -> Vec<Result<Vec<u32>>> {
+    #[cfg(feature = "rayon")]
+    {
+        model_entries
+            .par_iter()
+            .map(|(_, path)| read_model_style_ids(path))
+            .collect()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        model_entries
+            .iter()
+            .map(|(_, path)| read_model_style_ids(path))
+            .collect()
+    }
 }
 
 fn extract_model_id_from_path(path: &Path) -> Option<u32> {
@@ -337,6 +466,10 @@ pub fn build_style_to_model_map_async(
 
 /// Builds a style-to-model map while reporting progress for each scanned model file.
 ///
+/// Style IDs come from each file's metadata, read concurrently; `progress_callback`
+/// then runs in model-ID order while the results are merged, so a style declared by
+/// several models always maps to the lowest model ID.
+///
 /// # Errors
 ///
 /// Returns an error if model directory scanning fails or core speaker metadata cannot be
@@ -364,7 +497,11 @@ where
     let total_models = model_entries.len();
     let mut cumulative_style_ids = initial_style_ids;
 
-    for (index, (model_id, path)) in model_entries.iter().enumerate() {
+    let model_style_ids = read_all_model_style_ids(&model_entries);
+
+    for (index, ((model_id, path), style_ids)) in
+        model_entries.iter().zip(model_style_ids).enumerate()
+    {
         let model_filename = path
             .file_name()
             .and_then(|s| s.to_str())
@@ -372,31 +509,21 @@ where
 
         progress_callback(index + 1, total_models, model_filename);
 
-        if let Err(error) = core.load_specific_model(*model_id) {
-            crate::infrastructure::logging::warn(&format!(
-                "Failed to load model {model_id} ({model_filename}): {error}"
-            ));
-            continue;
+        match style_ids {
+            Ok(style_ids) => {
+                record_new_style_ids(
+                    &mut style_map,
+                    &mut cumulative_style_ids,
+                    *model_id,
+                    style_ids,
+                );
+            }
+            Err(error) => {
+                crate::infrastructure::logging::warn(&format!(
+                    "Failed to read model {model_id} ({model_filename}): {error}"
+                ));
+            }
         }
-
-        let Ok(current_speakers) = core.get_speakers() else {
-            crate::infrastructure::logging::warn(&format!(
-                "Failed to read speakers after loading model {model_id} ({model_filename})"
-            ));
-            unload_model_quietly(core, path);
-            continue;
-        };
-
-        record_new_style_ids(
-            &mut style_map,
-            &mut cumulative_style_ids,
-            *model_id,
-            current_speakers
-                .into_iter()
-                .flat_map(|speaker| speaker.styles.into_iter().map(|style| style.id)),
-        );
-
-        unload_model_quietly(core, path);
     }
 
     let loaded_model_paths = model_entries
@@ -438,10 +565,45 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{AvailableModel, Speaker, SpeakerList, Style, StyleList, populate_model_speakers};
+    use super::{
+        AvailableModel, Speaker, SpeakerList, Style, StyleList, collect_vvm_files,
+        populate_model_speakers,
+    };
     use std::collections::HashMap;
     use std::path::PathBuf;
 
+    #[test]
+    fn collect_vvm_files_walks_nested_directories() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for relative in [
+            "0.vvm",
+            "a/1.VVM",
+            "a/b/2.vvm",
+            "a/readme.txt",
+            "c/d/e/3.vvm",
+        ] {
+            let path = dir.path().join(relative);
+            std::fs::create_dir_all(path.parent().expect("parent")).expect("create dirs");
+            std::fs::write(&path, b"").expect("write file");
+        }
+
+        let mut names = collect_vvm_files(dir.path())
+            .expect("walk models")
+            .into_iter()
+            .map(|path| {
+                path.strip_prefix(dir.path())
+                    .expect("inside dir")
+                    .to_path_buf()
+            })
+            .collect::<Vec<_>>();
+        names.sort();
+
+        assert_eq!(
+            names,
+            ["0.vvm", "a/1.VVM", "a/b/2.vvm", "c/d/e/3.vvm"].map(PathBuf::from)
+        );
+    }
+
     #[test]
     fn populate_model_speakers_groups_styles_by_model() {
         let mut models = vec![
@@ -449,11 +611,13 @@ mod tests {
                 model_id: 1,
                 file_path: PathBuf::from("1.vvm"),
                 speakers: SpeakerList::new(),
+                loaded: false,
             },
             AvailableModel {
                 model_id: 2,
                 file_path: PathBuf::from("2.vvm"),
                 speakers: SpeakerList::new(),
+                loaded: false,
             },
         ];
         let speakers = vec![Speaker {
@@ -464,11 +628,13 @@ mod tests {
                     name: "style-10".into(),
                     id: 10,
                     style_type: None,
+                    loaded: false,
                 },
                 Style {
                     name: "style-20".into(),
                     id: 20,
                     style_type: None,
+                    loaded: false,
                 },
             ]
             .into_iter()
@@ -1,120 +1,356 @@
-use anyhow::{Context, Result, anyhow};
-use std::process::Command;
-use std::{env, io::Write};
-use tempfile::{Builder, NamedTempFile};
-
-pub(crate) fn preferred_audio_players() -> Vec<&'static str> {
-    let mut players = Vec::new();
-    for path in crate::config::SYSTEM_AUDIO_PLAYER_PATHS {
-        if std::path::Path::new(path).is_file() {
-            players.push(path);
+use anyhow::{Context, Result, anyhow, bail};
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
+use serde::Serialize;
+use std::env;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::interface::sink::{PlaybackSink, RodioSink};
+
+/// Where synthesized audio is played.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AudioBackend {
+    /// An output device, in-process through rodio.
+    #[default]
+    Device,
+    /// Discard audio without opening any device, for headless hosts and containers.
+    Null,
+}
+
+impl AudioBackend {
+    /// Parses `device` or `null`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for any other name.
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "device" | "default" => Ok(Self::Device),
+            "null" | "none" => Ok(Self::Null),
+            _ => bail!("Unknown audio backend '{value}' (expected device or null)"),
+        }
+    }
+
+    /// Reads `VOICEVOX_AUDIO`, then `audio` in `config.toml`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `VOICEVOX_AUDIO` or `audio` names an unknown backend.
+    pub fn from_env() -> Result<Self> {
+        match env::var(crate::config::ENV_VOICEVOX_AUDIO) {
+            Ok(value) if !value.trim().is_empty() => Self::parse(&value)
+                .with_context(|| format!("Invalid {}", crate::config::ENV_VOICEVOX_AUDIO)),
+            _ => match &crate::config::user_config().audio {
+                Some(value) => Self::parse(value).context("Invalid audio in config.toml"),
+                None => Ok(Self::Device),
+            },
+        }
+    }
+}
+
+/// Encoding of audio written with `-o`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// The 16-bit PCM WAV the daemon returns, unchanged.
+    #[default]
+    Wav,
+    /// Lossless, about half the size of WAV for speech.
+    Flac,
+    /// Ogg Opus; needs a build with the `opus` feature.
+    Opus,
+    /// MP3 through libmp3lame; needs a build with the `mp3` feature.
+    Mp3,
+}
+
+impl OutputFormat {
+    /// Parses `wav`, `flac`, `opus` (also `ogg`), or `mp3`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for other names, explaining that Vorbis is not available
+    /// because there is no pure-Rust encoder for it.
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "wav" | "wave" => Ok(Self::Wav),
+            "flac" => Ok(Self::Flac),
+            "opus" | "ogg" | "oga" => Ok(Self::Opus),
+            "mp3" => Ok(Self::Mp3),
+            "vorbis" => bail!(
+                "{value} output is not supported: there is no pure-Rust encoder for it (use flac or opus)"
+            ),
+            _ => bail!("Unknown output format '{value}' (expected wav, flac, opus, or mp3)"),
+        }
+    }
+
+    /// The format named by `requested`, else the one `path`'s extension implies.
+    ///
+    /// Files without a recognized audio extension are written as WAV.
+    #[must_use]
+    pub fn resolve(requested: Option<Self>, path: Option<&Path>) -> Self {
+        if let Some(format) = requested {
+            return format;
+        }
+        path.and_then(Path::extension)
+            .and_then(|extension| extension.to_str())
+            .and_then(|extension| Self::parse(extension).ok())
+            .unwrap_or(Self::Wav)
+    }
+
+    /// Media type of audio encoded in this format.
+    #[must_use]
+    pub const fn mime_type(self) -> &'static str {
+        match self {
+            Self::Wav => "audio/wav",
+            Self::Flac => "audio/flac",
+            Self::Opus => "audio/ogg",
+            Self::Mp3 => "audio/mpeg",
         }
     }
-    if crate::config::allow_unsafe_path_commands() {
-        players.extend(crate::config::FALLBACK_AUDIO_PLAYERS);
+
+    /// File extension for audio encoded in this format.
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Wav => "wav",
+            Self::Flac => "flac",
+            Self::Opus => "opus",
+            Self::Mp3 => "mp3",
+        }
+    }
+
+    /// Encodes synthesized WAV audio in this format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the audio cannot be encoded, or for Opus or MP3 when this
+    /// build lacks the matching feature.
+    pub fn encode(self, wav_data: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            Self::Wav => Ok(wav_data.to_vec()),
+            Self::Flac => crate::domain::synthesis::flac::encode_flac(wav_data),
+            #[cfg(feature = "opus")]
+            Self::Opus => crate::infrastructure::opus::encode_ogg_opus(wav_data),
+            #[cfg(not(feature = "opus"))]
+            Self::Opus => bail!("Opus output needs voicevox-cli built with the 'opus' feature"),
+            #[cfg(feature = "mp3")]
+            Self::Mp3 => crate::infrastructure::mp3::encode_mp3(wav_data),
+            #[cfg(not(feature = "mp3"))]
+            Self::Mp3 => bail!("MP3 output needs voicevox-cli built with the 'mp3' feature"),
+        }
     }
-    players
 }
 
-/// Plays synthesized WAV audio from memory using rodio or a system player fallback.
+static AUDIO_BACKEND: OnceLock<AudioBackend> = OnceLock::new();
+
+/// Fixes the process's audio backend: `requested` if given, else the environment.
+///
+/// The first call wins; later calls return the backend already in use.
 ///
 /// # Errors
 ///
-/// Returns an error if audio decoding/playback fails and no compatible system player
-/// (such as `afplay` or `play`) succeeds.
-pub fn play_audio_from_memory(wav_data: &[u8]) -> Result<()> {
-    if env::var(crate::config::ENV_VOICEVOX_LOW_LATENCY).is_ok() {
-        play_audio_via_rodio(wav_data)
-    } else {
-        play_audio_via_system(wav_data)
-    }
+/// Returns an error if nothing was requested and `VOICEVOX_AUDIO` is invalid.
+pub fn init_audio_backend(requested: Option<AudioBackend>) -> Result<AudioBackend> {
+    let backend = match requested {
+        Some(backend) => backend,
+        None => AudioBackend::from_env()?,
+    };
+    Ok(*AUDIO_BACKEND.get_or_init(|| backend))
 }
 
-fn play_audio_via_rodio(wav_data: &[u8]) -> Result<()> {
-    use rodio::{Decoder, Player};
-    use std::io::Cursor;
+/// The backend chosen by [`init_audio_backend`], or the environment's if it was never called.
+#[must_use]
+pub fn audio_backend() -> AudioBackend {
+    *AUDIO_BACKEND.get_or_init(|| AudioBackend::from_env().unwrap_or_default())
+}
 
-    let Ok(stream) = rodio::DeviceSinkBuilder::open_default_sink() else {
-        return play_audio_via_system(wav_data);
-    };
+/// Where and how loud in-process playback is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioOutput {
+    /// Output device name as `--list-audio-devices` prints it; `None` is the
+    /// system default.
+    pub device: Option<String>,
+    /// Playback volume, from 0.0 (silent) to 1.0 (as synthesized).
+    pub volume: f32,
+}
 
-    let Ok(source) = Decoder::new(Cursor::new(wav_data.to_vec())) else {
-        return play_audio_via_system(wav_data);
-    };
+impl Default for AudioOutput {
+    fn default() -> Self {
+        Self {
+            device: None,
+            volume: 1.0,
+        }
+    }
+}
 
-    let sink = Player::connect_new(stream.mixer());
-    sink.append(source);
-    sink.play();
-    sink.sleep_until_end();
-    Ok(())
+impl AudioOutput {
+    fn from_config() -> Self {
+        let config = crate::config::user_config();
+        Self {
+            device: config.audio_device.clone(),
+            volume: config.playback_volume.unwrap_or(1.0),
+        }
+    }
 }
 
-fn play_audio_via_system(wav_data: &[u8]) -> Result<()> {
-    let temp_file = create_temp_wav_file(wav_data)?;
-    let temp_path = temp_file.path();
+/// Whether `volume` is a playback volume `--playback-volume` accepts.
+#[must_use]
+pub fn is_valid_volume(volume: f32) -> bool {
+    (0.0..=1.0).contains(&volume)
+}
 
-    try_players(preferred_audio_players(), |command| {
-        try_system_player(command, temp_path)
-    })
+/// Parses a `--playback-volume` value.
+///
+/// # Errors
+///
+/// Returns an error unless `value` is a number from 0.0 to 1.0.
+pub fn parse_volume(value: &str) -> Result<f32> {
+    value
+        .trim()
+        .parse::<f32>()
+        .ok()
+        .filter(|&volume| is_valid_volume(volume))
+        .with_context(|| format!("Playback volume must be between 0.0 and 1.0: '{value}'"))
 }
 
-fn try_players<I, F>(commands: I, mut try_command: F) -> Result<()>
-where
-    I: IntoIterator,
-    I::Item: AsRef<str>,
-    F: FnMut(&str) -> Result<Option<()>>,
-{
-    let mut last_error = None;
+static AUDIO_OUTPUT: OnceLock<AudioOutput> = OnceLock::new();
 
-    for command in commands {
-        match try_command(command.as_ref()) {
-            Ok(Some(())) => return Ok(()),
-            Ok(None) => {}
-            Err(error) => last_error = Some(error),
+/// Fixes the process's output device and volume: the requested ones, else
+/// `audio_device` and `playback_volume` from `config.toml`.
+///
+/// The first call wins; later calls return the settings already in use.
+pub fn init_audio_output(device: Option<String>, volume: Option<f32>) -> &'static AudioOutput {
+    AUDIO_OUTPUT.get_or_init(|| {
+        let config = AudioOutput::from_config();
+        AudioOutput {
+            device: device.or(config.device),
+            volume: volume.unwrap_or(config.volume),
         }
-    }
+    })
+}
 
-    Err(last_error
-        .unwrap_or_else(|| anyhow!("No audio player found. Install sox or use -o to save file")))
+/// The settings chosen by [`init_audio_output`], or `config.toml`'s if it was never called.
+#[must_use]
+pub fn audio_output() -> &'static AudioOutput {
+    AUDIO_OUTPUT.get_or_init(AudioOutput::from_config)
+}
+
+/// An output device playback can use.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutputDevice {
+    pub name: String,
+    /// Whether this is the system default, used without `--audio-device`.
+    pub default: bool,
+}
+
+/// Lists the output devices `--audio-device` accepts.
+///
+/// # Errors
+///
+/// Returns an error if the audio host cannot enumerate its devices.
+pub fn list_output_devices() -> Result<Vec<OutputDevice>> {
+    let host = rodio::cpal::default_host();
+    let default_name = host
+        .default_output_device()
+        .and_then(|device| device.name().ok());
+    let devices = host
+        .output_devices()
+        .map_err(|error| anyhow!("Failed to list audio output devices: {error}"))?;
+    Ok(devices
+        .filter_map(|device| device.name().ok())
+        .map(|name| OutputDevice {
+            default: default_name.as_deref() == Some(name.as_str()),
+            name,
+        })
+        .collect())
 }
 
-fn try_system_player(command: &str, temp_path: &std::path::Path) -> Result<Option<()>> {
-    let output = match Command::new(command).arg(temp_path).output() {
-        Ok(output) => output,
-        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
-        Err(error) => return Err(error).with_context(|| format!("Failed to launch {command}")),
+/// Opens the output device chosen by [`init_audio_output`], or the default one.
+///
+/// # Errors
+///
+/// Returns an error naming the device problem if the device cannot be found or opened.
+pub(crate) fn open_output_device() -> Result<rodio::MixerDeviceSink> {
+    let Some(name) = audio_output().device.as_deref() else {
+        return rodio::DeviceSinkBuilder::open_default_sink().map_err(|error| {
+            anyhow!("No usable audio output device: {error} (use -o to save to a file instead)")
+        });
     };
+    let device = rodio::cpal::default_host()
+        .output_devices()
+        .map_err(|error| anyhow!("Failed to list audio output devices: {error}"))?
+        .find(|device| device.name().is_ok_and(|device_name| device_name == name))
+        .with_context(|| {
+            format!("No audio output device named '{name}' (see --list-audio-devices)")
+        })?;
+    rodio::DeviceSinkBuilder::from_device(device)
+        .and_then(|builder| builder.open_sink())
+        .map_err(|error| anyhow!("Failed to open audio output device '{name}': {error}"))
+}
 
-    if output.status.success() {
-        return Ok(Some(()));
+/// Plays synthesized WAV audio from memory on the chosen output device.
+///
+/// The null backend returns at once.
+///
+/// # Errors
+///
+/// Returns an error if the output device cannot be opened or the audio cannot be decoded.
+pub fn play_audio_from_memory(wav_data: &[u8]) -> Result<()> {
+    match audio_backend() {
+        AudioBackend::Device => play_audio_via_rodio(wav_data),
+        AudioBackend::Null => Ok(()),
     }
+}
 
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    let message = stderr.trim();
-    if message.is_empty() {
-        Err(anyhow!(
-            "{command} exited with status {}",
-            output.status.code().map_or_else(
-                || "terminated by signal".to_string(),
-                |code| code.to_string()
-            )
-        ))
-    } else {
-        Err(anyhow!("{command} failed: {message}"))
-    }
+fn play_audio_via_rodio(wav_data: &[u8]) -> Result<()> {
+    let sink = RodioSink::open(1)?;
+    sink.append_wav(wav_data.to_vec())?;
+    sink.sleep_until_end();
+    Ok(())
 }
 
-pub(crate) fn create_temp_wav_file(wav_data: &[u8]) -> Result<NamedTempFile> {
-    let mut temp = Builder::new()
-        .prefix("voicevox_")
-        .suffix(".wav")
-        .tempfile()
-        .context("Failed to create temporary audio file")?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    temp.write_all(wav_data)
-        .context("Failed to write temporary audio file")?;
-    temp.flush()
-        .context("Failed to flush temporary audio file")?;
+    #[test]
+    fn parses_backend_names() {
+        assert_eq!(
+            AudioBackend::parse("null").expect("null"),
+            AudioBackend::Null
+        );
+        assert!(
+            AudioBackend::parse(" System ")
+                .expect_err("removed")
+                .to_string()
+                .contains("--audio-device")
+        );
+        assert_eq!(
+            AudioBackend::parse("device").expect("device"),
+            AudioBackend::Device
+        );
+        assert!(AudioBackend::parse("alsa").is_err());
+    }
+
+    #[test]
+    fn volume_accepts_zero_to_one() {
+        assert_eq!(parse_volume(" 0.5 ").expect("half"), 0.5);
+        assert_eq!(parse_volume("0").expect("mute"), 0.0);
+        assert!(parse_volume("1.5").is_err());
+        assert!(parse_volume("loud").is_err());
+    }
 
-    Ok(temp)
+    #[test]
+    fn output_format_follows_flag_then_extension() {
+        let resolve =
+            |requested, path: &str| OutputFormat::resolve(requested, Some(Path::new(path)));
+        assert_eq!(resolve(None, "out.FLAC"), OutputFormat::Flac);
+        assert_eq!(resolve(None, "out.ogg"), OutputFormat::Opus);
+        assert_eq!(resolve(None, "out.mp3"), OutputFormat::Mp3);
+        assert_eq!(resolve(None, "out.raw"), OutputFormat::Wav);
+        assert_eq!(
+            resolve(Some(OutputFormat::Wav), "out.flac"),
+            OutputFormat::Wav
+        );
+        assert!(OutputFormat::parse("vorbis").is_err());
+    }
 }
@@ -0,0 +1,101 @@
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+
+use crate::domain::voice_alias::{alias_key, validate_alias};
+use crate::infrastructure::voice_aliases::{load_voice_aliases, save_voice_aliases};
+use crate::infrastructure::voicevox::scan_speakers_from_model_files;
+use crate::interface::AppOutput;
+use crate::interface::cli::voice_selector::resolve_voice_among;
+
+/// Saves `alias` for the voice `target` in the aliases file at `aliases_path`,
+/// replacing an alias of the same name.
+///
+/// The daemon and the MCP server read the file on every lookup, so nothing needs
+/// reloading.
+///
+/// # Errors
+///
+/// Returns an error if the alias is invalid, `target` names no installed voice,
+/// or the file cannot be read or saved.
+pub fn run_alias_add(
+    alias: &str,
+    target: &str,
+    aliases_path: &Path,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let mut aliases = load_voice_aliases(aliases_path)?;
+    validate_alias(&aliases, alias, target)?;
+    let target = target.trim();
+    let speakers = scan_speakers_from_model_files().unwrap_or_default();
+    let style_id = resolve_voice_among(target, &speakers)
+        .with_context(|| format!("Cannot resolve voice '{target}'"))?;
+
+    aliases.insert(alias_key(alias), target.to_owned());
+    save_voice_aliases(aliases_path, &aliases)?;
+    output.info(&format!(
+        "'{}' now means {target} (style {style_id})",
+        alias_key(alias)
+    ));
+    Ok(())
+}
+
+/// Removes `alias` from the aliases file at `aliases_path`.
+///
+/// # Errors
+///
+/// Returns an error if no such alias exists or the file cannot be read or saved.
+pub fn run_alias_remove(alias: &str, aliases_path: &Path, output: &dyn AppOutput) -> Result<()> {
+    let mut aliases = load_voice_aliases(aliases_path)?;
+    let key = alias_key(alias);
+    if aliases.remove(&key).is_none() {
+        bail!("'{key}' is not an alias");
+    }
+    save_voice_aliases(aliases_path, &aliases)?;
+    output.info(&format!("Removed '{key}'"));
+    Ok(())
+}
+
+/// Prints every alias in the aliases file at `aliases_path`.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read.
+pub fn run_alias_list(aliases_path: &Path, output: &dyn AppOutput) -> Result<()> {
+    let aliases = load_voice_aliases(aliases_path)?;
+    if aliases.is_empty() {
+        output.info("No voice aliases defined");
+        return Ok(());
+    }
+    for (alias, target) in &aliases {
+        output.info(&format!("{alias}\t{target}"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::output::BufferAppOutput;
+
+    #[test]
+    fn add_list_and_remove_aliases() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("aliases.json");
+        let output = BufferAppOutput::default();
+
+        run_alias_add("Zunda", "3", &path, &output).expect("add");
+        assert!(run_alias_add("3", "1", &path, &output).is_err());
+        run_alias_list(&path, &output).expect("list");
+        assert_eq!(
+            output.infos(),
+            [
+                "'zunda' now means 3 (style 3)".to_string(),
+                "zunda\t3".to_string()
+            ]
+        );
+
+        run_alias_remove("ZUNDA", &path, &output).expect("remove");
+        assert!(load_voice_aliases(&path).expect("load").is_empty());
+        assert!(run_alias_remove("zunda", &path, &output).is_err());
+    }
+}
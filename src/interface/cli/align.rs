@@ -0,0 +1,197 @@
+use anyhow::{Context, Result, anyhow};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::domain::subtitles::{Cue, parse_srt};
+use crate::domain::synthesis::timing::fit_rate;
+use crate::domain::synthesis::wav::{TimelineClip, render_timeline, wav_duration};
+use crate::infrastructure::daemon::client::DaemonClient;
+use crate::infrastructure::ipc::{DEFAULT_SYNTHESIS_PITCH, SynthesisOptions};
+use crate::interface::cli::daemon_error::format_daemon_client_error_for_cli;
+use crate::interface::synthesis::flow::{
+    connect_daemon_client_auto_start, ensure_startup_resources, validate_text_synthesis_request,
+};
+use crate::interface::{AppOutput, StdAppOutput};
+
+/// Re-renders allowed per cue while speeding it up to fit its slot.
+const MAX_FIT_PASSES: usize = 2;
+
+pub struct AlignRequest<'a> {
+    pub srt_file: &'a Path,
+    pub output_file: &'a Path,
+    pub style_id: u32,
+    pub rate: f32,
+    pub quiet: bool,
+    pub socket_path: PathBuf,
+}
+
+struct RenderedCue {
+    wav_data: Vec<u8>,
+    duration: Duration,
+    rate: f32,
+}
+
+/// Renders an SRT file into one audio track whose cues start at their timecodes.
+///
+/// Cues that run longer than their slot are re-rendered faster (up to the maximum
+/// rate); anything still too long is cut at the slot end. Shorter cues are padded
+/// with silence, so the track can be muxed against the video as-is.
+///
+/// # Errors
+///
+/// Returns an error if the SRT cannot be read or parsed, any cue is invalid,
+/// synthesis fails, or the track cannot be written.
+pub async fn run_align_srt(request: AlignRequest<'_>) -> Result<()> {
+    let output = StdAppOutput;
+    run_align_srt_with_output(request, &output).await
+}
+
+pub async fn run_align_srt_with_output(
+    request: AlignRequest<'_>,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let source = tokio::fs::read_to_string(request.srt_file)
+        .await
+        .with_context(|| format!("Failed to read {}", request.srt_file.display()))?;
+    let cues = parse_srt(&source)
+        .with_context(|| format!("Invalid SRT file {}", request.srt_file.display()))?;
+    let Some(total) = cues.iter().map(|cue| cue.end).max() else {
+        return Err(anyhow!(
+            "SRT file {} has no cues",
+            request.srt_file.display()
+        ));
+    };
+    for cue in &cues {
+        validate_text_synthesis_request(
+            &cue.text,
+            request.style_id,
+            request.rate,
+            DEFAULT_SYNTHESIS_PITCH,
+        )
+        .with_context(|| format!("Cue {}", cue.number))?;
+    }
+
+    ensure_startup_resources(request.quiet, output).await?;
+    let mut client = connect_daemon_client_auto_start(&request.socket_path).await?;
+
+    let mut rendered = Vec::with_capacity(cues.len());
+    for cue in &cues {
+        let result = render_cue(&mut client, &request, cue).await;
+        let rendered_cue = match result {
+            Ok(rendered_cue) => rendered_cue,
+            Err(error) => {
+                if !request.quiet {
+                    output.error(&format!(
+                        "✗ cue {}: {}",
+                        cue.number,
+                        format_daemon_client_error_for_cli(&error)
+                    ));
+                }
+                return Err(error.context(format!("Failed to render cue {}", cue.number)));
+            }
+        };
+        if !request.quiet {
+            output.info(&describe_fit(cue, &rendered_cue));
+        }
+        rendered.push(rendered_cue);
+    }
+
+    let clips = cues
+        .iter()
+        .zip(&rendered)
+        .map(|(cue, rendered_cue)| TimelineClip {
+            start: cue.start,
+            max_length: cue.slot(),
+            wav: &rendered_cue.wav_data,
+        })
+        .collect::<Vec<_>>();
+    let track = render_timeline(&clips, total).context("Failed to assemble aligned track")?;
+    tokio::fs::write(request.output_file, track)
+        .await
+        .with_context(|| format!("Failed to write {}", request.output_file.display()))
+}
+
+async fn render_cue(
+    client: &mut DaemonClient,
+    request: &AlignRequest<'_>,
+    cue: &Cue,
+) -> Result<RenderedCue> {
+    let mut rate = request.rate;
+    let mut wav_data = synthesize(client, &cue.text, request.style_id, rate).await?;
+    let mut duration = wav_duration(&wav_data)?;
+
+    // Only speed up: short cues are padded with silence rather than slowed down.
+    for _ in 0..MAX_FIT_PASSES {
+        if duration <= cue.slot() {
+            break;
+        }
+        let fit = fit_rate(duration, rate, cue.slot());
+        if fit.rate <= rate {
+            break;
+        }
+        rate = fit.rate;
+        wav_data = synthesize(client, &cue.text, request.style_id, rate).await?;
+        duration = wav_duration(&wav_data)?;
+    }
+
+    Ok(RenderedCue {
+        wav_data,
+        duration,
+        rate,
+    })
+}
+
+async fn synthesize(
+    client: &mut DaemonClient,
+    text: &str,
+    style_id: u32,
+    rate: f32,
+) -> Result<Vec<u8>> {
+    let options = SynthesisOptions {
+        rate,
+        ..Default::default()
+    };
+    client.synthesize(text, style_id, options).await
+}
+
+fn describe_fit(cue: &Cue, rendered: &RenderedCue) -> String {
+    let slot = cue.slot();
+    let mut line = format!(
+        "✓ cue {} {:.2}s/{:.2}s at rate {:.2}",
+        cue.number,
+        rendered.duration.as_secs_f64(),
+        slot.as_secs_f64(),
+        rendered.rate
+    );
+    if rendered.duration > slot {
+        line.push_str(&format!(
+            " (cut {:.2}s)",
+            (rendered.duration - slot).as_secs_f64()
+        ));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_description_reports_cut_overruns() {
+        let cue = Cue {
+            number: 4,
+            start: Duration::from_secs(10),
+            end: Duration::from_secs(12),
+            text: "テスト".to_owned(),
+        };
+        let rendered = RenderedCue {
+            wav_data: Vec::new(),
+            duration: Duration::from_millis(2500),
+            rate: 2.0,
+        };
+        assert_eq!(
+            describe_fit(&cue, &rendered),
+            "✓ cue 4 2.50s/2.00s at rate 2.00 (cut 0.50s)"
+        );
+    }
+}
@@ -0,0 +1,443 @@
+use anyhow::{Context, Result, bail, ensure};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::domain::backup::{
+    BACKUP_MANIFEST_FILE, BackupManifest, BackupSection, is_contained_path,
+};
+use crate::infrastructure::paths::{
+    find_models_dir_client, get_config_dir, get_default_voicevox_dir, get_state_dir,
+};
+use crate::interface::cli::daemon_cli::notify_daemons_of_changes;
+use crate::interface::{AppOutput, StdAppOutput};
+
+/// The per-user directories a snapshot covers.
+#[derive(Debug, Clone)]
+pub struct BackupRoots {
+    pub config_dir: PathBuf,
+    pub data_dir: PathBuf,
+    pub state_dir: PathBuf,
+    pub models_dir: PathBuf,
+}
+
+impl BackupRoots {
+    /// Resolves the directories the CLI and daemon use on this machine.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the models directory cannot be determined.
+    pub fn from_environment() -> Result<Self> {
+        Ok(Self {
+            config_dir: get_config_dir(),
+            data_dir: get_default_voicevox_dir(),
+            state_dir: get_state_dir(),
+            models_dir: find_models_dir_client()?,
+        })
+    }
+
+    fn dir(&self, section: BackupSection) -> &Path {
+        match section {
+            BackupSection::Config => &self.config_dir,
+            BackupSection::Data => &self.data_dir,
+            BackupSection::State => &self.state_dir,
+            BackupSection::Models => &self.models_dir,
+        }
+    }
+}
+
+/// A file captured in a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupEntry {
+    pub section: BackupSection,
+    /// Path below the section's directory.
+    pub relative: PathBuf,
+}
+
+#[derive(Debug)]
+pub struct BackupCreateRequest<'a> {
+    pub snapshot: &'a Path,
+    pub include_models: bool,
+}
+
+#[derive(Debug)]
+pub struct BackupRestoreRequest<'a> {
+    pub snapshot: &'a Path,
+}
+
+/// Writes a snapshot of configuration, the user dictionary, and state records,
+/// and optionally installed models.
+///
+/// The archive is created with the system `tar`, compressed according to the
+/// snapshot's suffix (`.tar.zst`, `.tar.gz`, ...).
+///
+/// # Errors
+///
+/// Returns an error if the snapshot already exists, a directory cannot be read,
+/// or `tar` fails.
+pub fn run_backup_create(request: &BackupCreateRequest<'_>) -> Result<()> {
+    let output = StdAppOutput;
+    run_backup_create_with_output(request, &output)
+}
+
+pub fn run_backup_create_with_output(
+    request: &BackupCreateRequest<'_>,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    ensure!(
+        !request.snapshot.exists(),
+        "{} already exists",
+        request.snapshot.display()
+    );
+    let roots = BackupRoots::from_environment()?;
+    let entries = collect_backup_entries(&roots, request.include_models)?;
+
+    let staging = tempfile::tempdir().context("Failed to create staging directory")?;
+    let manifest = BackupManifest::current(request.include_models);
+    fs::write(
+        staging.path().join(BACKUP_MANIFEST_FILE),
+        toml::to_string(&manifest)?,
+    )
+    .context("Failed to write backup manifest")?;
+    for entry in &entries {
+        let staged = staging
+            .path()
+            .join(entry.section.dir_name())
+            .join(&entry.relative);
+        if let Some(parent) = staged.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Linked rather than copied so models are not duplicated; tar follows the links.
+        std::os::unix::fs::symlink(roots.dir(entry.section).join(&entry.relative), &staged)
+            .with_context(|| format!("Failed to stage {}", entry.relative.display()))?;
+    }
+
+    let mut members = vec![BACKUP_MANIFEST_FILE];
+    members.extend(
+        BackupSection::ALL
+            .iter()
+            .map(|section| section.dir_name())
+            .filter(|name| staging.path().join(name).is_dir()),
+    );
+    run_tar(
+        Command::new(tar_command())
+            .arg("-a")
+            .arg("-c")
+            .arg("-h")
+            .arg("-f")
+            .arg(request.snapshot)
+            .arg("-C")
+            .arg(staging.path())
+            .args(&members),
+    )
+    .with_context(|| format!("Failed to write {}", request.snapshot.display()))?;
+
+    output.info(&format!(
+        "Backed up {} file(s){} to {}",
+        entries.len(),
+        if request.include_models {
+            " including models"
+        } else {
+            ""
+        },
+        request.snapshot.display()
+    ));
+    Ok(())
+}
+
+/// Restores every file in a snapshot over the current configuration, leaving files
+/// the snapshot does not contain untouched, then asks running daemons to reload.
+///
+/// # Errors
+///
+/// Returns an error if the snapshot cannot be extracted, was written by a newer
+/// format, or a file cannot be written.
+pub fn run_backup_restore(request: &BackupRestoreRequest<'_>) -> Result<()> {
+    let output = StdAppOutput;
+    run_backup_restore_with_output(request, &output)
+}
+
+pub fn run_backup_restore_with_output(
+    request: &BackupRestoreRequest<'_>,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    ensure!(
+        request.snapshot.is_file(),
+        "{} is not a file",
+        request.snapshot.display()
+    );
+    let extracted = tempfile::tempdir().context("Failed to create extraction directory")?;
+    run_tar(
+        Command::new(tar_command())
+            .arg("-x")
+            .arg("-f")
+            .arg(request.snapshot)
+            .arg("-C")
+            .arg(extracted.path()),
+    )
+    .with_context(|| format!("Failed to extract {}", request.snapshot.display()))?;
+
+    let roots = BackupRoots::from_environment()?;
+    let restored = restore_snapshot(extracted.path(), &roots)?;
+    output.info(&format!(
+        "Restored {} file(s) from {}",
+        restored.len(),
+        request.snapshot.display()
+    ));
+    if restored
+        .iter()
+        .any(|entry| matches!(entry.section, BackupSection::Config | BackupSection::Models))
+    {
+        notify_daemons_of_changes(output);
+    }
+    Ok(())
+}
+
+/// Lists the files a snapshot of `roots` contains.
+///
+/// Only the user dictionary is taken from the data directory, since the rest of it
+/// is downloaded resources. Lock files and non-regular files such as the daemon
+/// socket are skipped, and models are included only on request.
+///
+/// # Errors
+///
+/// Returns an error if an existing directory cannot be read.
+pub fn collect_backup_entries(
+    roots: &BackupRoots,
+    include_models: bool,
+) -> Result<Vec<BackupEntry>> {
+    let mut entries = Vec::new();
+    for section in BackupSection::ALL {
+        let mut files = Vec::new();
+        let dir = roots.dir(section);
+        match section {
+            BackupSection::Data => {
+                let user_dict = PathBuf::from(crate::config::USER_DICT_FILE);
+                if dir.join(&user_dict).is_file() {
+                    files.push(user_dict);
+                }
+            }
+            BackupSection::Models if !include_models => {}
+            _ => collect_files(dir, Path::new(""), &mut files)?,
+        }
+        files.retain(|file| wanted(section, file));
+        files.sort();
+        entries.extend(
+            files
+                .into_iter()
+                .map(|relative| BackupEntry { section, relative }),
+        );
+    }
+    Ok(entries)
+}
+
+fn wanted(section: BackupSection, file: &Path) -> bool {
+    let extension = file.extension().and_then(|extension| extension.to_str());
+    match section {
+        BackupSection::State => extension != Some("lock"),
+        // The models directory may be the data directory itself; keep only models
+        // and the voice pack records next to them.
+        BackupSection::Models => {
+            extension == Some("vvm") || file.starts_with(crate::domain::voice_pack::PACKS_SUBDIR)
+        }
+        BackupSection::Config | BackupSection::Data => true,
+    }
+}
+
+/// Collects regular files below `dir`, following file symlinks but not directory ones.
+fn collect_files(dir: &Path, relative: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let entries = match fs::read_dir(dir.join(relative)) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(error) => {
+            return Err(error).with_context(|| format!("Failed to read {}", dir.display()));
+        }
+    };
+    for entry in entries {
+        let entry = entry?;
+        let path = relative.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(dir, &path, files)?;
+        } else if file_type.is_file()
+            || (file_type.is_symlink() && entry.path().metadata().is_ok_and(|meta| meta.is_file()))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Copies the files of an extracted snapshot into `roots`.
+///
+/// # Errors
+///
+/// Returns an error if the manifest is missing or unreadable, or a file cannot be
+/// written.
+pub fn restore_snapshot(extracted: &Path, roots: &BackupRoots) -> Result<Vec<BackupEntry>> {
+    let manifest_path = extracted.join(BACKUP_MANIFEST_FILE);
+    let manifest = fs::read_to_string(&manifest_path)
+        .map_err(anyhow::Error::from)
+        .and_then(|source| BackupManifest::parse(&source))
+        .with_context(|| format!("Snapshot has no valid {BACKUP_MANIFEST_FILE}"))?;
+
+    let mut restored = Vec::new();
+    for section in BackupSection::ALL {
+        if section == BackupSection::Models && !manifest.includes_models {
+            continue;
+        }
+        let source_dir = extracted.join(section.dir_name());
+        let mut files = Vec::new();
+        collect_regular_files(&source_dir, Path::new(""), &mut files)?;
+        files.sort();
+        for relative in files {
+            if !is_contained_path(&relative) {
+                bail!("Snapshot path {} escapes its section", relative.display());
+            }
+            replace_file(
+                &source_dir.join(&relative),
+                &roots.dir(section).join(&relative),
+            )?;
+            restored.push(BackupEntry { section, relative });
+        }
+    }
+    Ok(restored)
+}
+
+/// Like [`collect_files`], but never follows symlinks from an untrusted archive.
+fn collect_regular_files(dir: &Path, relative: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let entries = match fs::read_dir(dir.join(relative)) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(error) => return Err(error.into()),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let path = relative.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_regular_files(dir, &path, files)?;
+        } else if file_type.is_file() {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Atomically replaces `target` with a copy of `source`.
+fn replace_file(source: &Path, target: &Path) -> Result<()> {
+    let parent = target
+        .parent()
+        .with_context(|| format!("{} has no parent directory", target.display()))?;
+    fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+    let mut temp = tempfile::NamedTempFile::new_in(parent)
+        .with_context(|| format!("Failed to create temporary file in {}", parent.display()))?;
+    let mut reader =
+        fs::File::open(source).with_context(|| format!("Failed to read {}", source.display()))?;
+    std::io::copy(&mut reader, &mut temp)
+        .with_context(|| format!("Failed to write {}", target.display()))?;
+    temp.persist(target)
+        .with_context(|| format!("Failed to write {}", target.display()))?;
+    Ok(())
+}
+
+fn tar_command() -> &'static str {
+    crate::config::command_path_or_fallback(crate::config::SYSTEM_TAR_PATH, "tar")
+}
+
+fn run_tar(command: &mut Command) -> Result<()> {
+    let output = command.output().context("Failed to run tar")?;
+    if !output.status.success() {
+        bail!(
+            "tar failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roots(base: &Path) -> BackupRoots {
+        BackupRoots {
+            config_dir: base.join("config"),
+            data_dir: base.join("data"),
+            state_dir: base.join("state"),
+            models_dir: base.join("data/models"),
+        }
+    }
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        fs::write(path, contents).expect("write file");
+    }
+
+    #[test]
+    fn collects_user_files_and_skips_resources_and_locks() {
+        let base = tempfile::tempdir().expect("temp dir");
+        let roots = roots(base.path());
+        write(&roots.config_dir.join("daemon.toml"), "");
+        write(&roots.config_dir.join("presets.json"), "{}");
+        write(&roots.data_dir.join("user_dict.json"), "[]");
+        write(&roots.data_dir.join("openjtalk_dict/sys.dic"), "");
+        write(&roots.state_dir.join("render_queue.json"), "[]");
+        write(&roots.state_dir.join("render_queue.lock"), "");
+        write(&roots.models_dir.join("101.vvm"), "");
+        write(&roots.models_dir.join("packs/alpha/manifest.toml"), "");
+        write(&roots.models_dir.join("notes.txt"), "");
+
+        let listed = |include_models| {
+            collect_backup_entries(&roots, include_models)
+                .expect("collect")
+                .into_iter()
+                .map(|entry| format!("{}/{}", entry.section.dir_name(), entry.relative.display()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            listed(false),
+            vec![
+                "config/daemon.toml",
+                "config/presets.json",
+                "data/user_dict.json",
+                "state/render_queue.json",
+            ]
+        );
+        assert_eq!(
+            listed(true)[4..],
+            ["models/101.vvm", "models/packs/alpha/manifest.toml"]
+        );
+    }
+
+    #[test]
+    fn restore_overwrites_snapshot_files_and_keeps_the_rest() {
+        let base = tempfile::tempdir().expect("temp dir");
+        let roots = roots(base.path());
+        write(&roots.config_dir.join("presets.json"), "old");
+        write(&roots.config_dir.join("hooks.toml"), "kept");
+
+        let snapshot = tempfile::tempdir().expect("snapshot dir");
+        write(
+            &snapshot.path().join(BACKUP_MANIFEST_FILE),
+            &toml::to_string(&BackupManifest::current(false)).expect("manifest"),
+        );
+        write(&snapshot.path().join("config/presets.json"), "new");
+        write(&snapshot.path().join("state/dialogue_casting.json"), "{}");
+        write(&snapshot.path().join("models/101.vvm"), "ignored");
+
+        let restored = restore_snapshot(snapshot.path(), &roots).expect("restore");
+
+        assert_eq!(restored.len(), 2);
+        assert_eq!(
+            fs::read_to_string(roots.config_dir.join("presets.json")).expect("read"),
+            "new"
+        );
+        assert_eq!(
+            fs::read_to_string(roots.config_dir.join("hooks.toml")).expect("read"),
+            "kept"
+        );
+        assert!(roots.state_dir.join("dialogue_casting.json").is_file());
+        assert!(!roots.models_dir.join("101.vvm").exists());
+    }
+}
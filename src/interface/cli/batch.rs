@@ -0,0 +1,901 @@
+use anyhow::{Context, Result, anyhow};
+use futures_util::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::Mutex;
+
+use crate::domain::anki::{AnkiNote, render_anki_notes};
+use crate::domain::batch::{
+    BatchRow, TableDelimiter, parse_batch_lines, parse_batch_table, render_retry_table,
+};
+use crate::domain::checksums::{checksum_line, parse_checksums, sha256_hex};
+use crate::domain::synthesis::m4b::{M4bChapter, M4bMetadata, encode_m4b};
+use crate::domain::synthesis::wav::concatenate_wav_segments;
+use crate::infrastructure::daemon::client::DaemonClient;
+use crate::infrastructure::ipc::{DEFAULT_SYNTHESIS_PITCH, SynthesisOptions};
+use crate::infrastructure::voicevox::{
+    Speaker, scan_speakers_from_model_files, voice_id_for_style,
+};
+use crate::interface::cli::daemon_error::format_daemon_client_error_for_cli;
+use crate::interface::cli::low_power::wait_while_background_deferred;
+use crate::interface::cli::voice_selector::resolve_voice_input;
+use crate::interface::synthesis::flow::{
+    connect_daemon_client_auto_start, ensure_startup_resources, validate_text_synthesis_request,
+};
+use crate::interface::{AppOutput, StdAppOutput};
+
+const REPORT_SUFFIX: &str = "report.json";
+pub(crate) const CHECKSUMS_SUFFIX: &str = "sha256";
+const RETRY_SUFFIX: &str = "retry";
+const ANKI_NOTES_FILE_NAME: &str = "notes.txt";
+const ANKI_MEDIA_DIR_NAME: &str = "media";
+/// `--batch -` reads the batch from stdin.
+const STDIN_BATCH: &str = "-";
+/// Name the report, checksums, and retry files of a stdin batch start with.
+const STDIN_BATCH_NAME: &str = "batch";
+/// Characters of a row's text used as its chapter title when it has none.
+const CHAPTER_EXCERPT_CHARS: usize = 40;
+
+/// Most rows rendered at once.
+pub const MAX_BATCH_JOBS: usize = 8;
+
+pub struct BatchRequest<'a> {
+    pub batch_file: &'a Path,
+    pub report_file: Option<&'a Path>,
+    pub anki_export: Option<&'a Path>,
+    /// Also mux the rendered rows into one chaptered audiobook at this path.
+    pub m4b_export: Option<&'a Path>,
+    /// Audiobook title; defaults to the batch file's name.
+    pub m4b_title: Option<&'a str>,
+    pub m4b_author: Option<&'a str>,
+    /// Check existing outputs against the checksums file instead of rendering.
+    pub verify: bool,
+    pub default_style_id: u32,
+    pub default_rate: f32,
+    pub quiet: bool,
+    pub socket_path: PathBuf,
+    /// Read one utterance per line, optionally `VOICE<TAB>TEXT`, instead of a table
+    /// and write numbered WAVs into this directory.
+    pub lines_output_dir: Option<&'a Path>,
+    /// Rows rendered at once, each over its own daemon connection.
+    pub jobs: usize,
+}
+
+impl BatchRequest<'_> {
+    fn reads_stdin(&self) -> bool {
+        self.batch_file == Path::new(STDIN_BATCH)
+    }
+
+    /// Path the report, checksums, and retry files are named after: the batch file,
+    /// or `batch` in the output directory for a batch read from stdin.
+    fn side_file_base(&self) -> PathBuf {
+        if self.reads_stdin() {
+            self.lines_output_dir
+                .unwrap_or_else(|| Path::new("."))
+                .join(STDIN_BATCH_NAME)
+        } else {
+            self.batch_file.to_path_buf()
+        }
+    }
+
+    fn report_path(&self) -> PathBuf {
+        self.report_file.map_or_else(
+            || self.side_file_base().with_extension(REPORT_SUFFIX),
+            Path::to_path_buf,
+        )
+    }
+
+    fn checksums_path(&self) -> PathBuf {
+        self.side_file_base().with_extension(CHECKSUMS_SUFFIX)
+    }
+
+    fn retry_path(&self, delimiter: TableDelimiter) -> PathBuf {
+        self.side_file_base()
+            .with_extension(format!("{RETRY_SUFFIX}.{}", delimiter.extension()))
+    }
+
+    async fn read_source(&self) -> Result<String> {
+        if !self.reads_stdin() {
+            return tokio::fs::read_to_string(self.batch_file)
+                .await
+                .with_context(|| format!("Failed to read {}", self.batch_file.display()));
+        }
+        let mut source = String::new();
+        tokio::io::stdin()
+            .read_to_string(&mut source)
+            .await
+            .context("Failed to read the batch from stdin")?;
+        Ok(source)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RowStatus {
+    Succeeded,
+    Failed,
+    Invalid,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct RowReport {
+    pub(crate) line: usize,
+    pub(crate) output: String,
+    pub(crate) status: RowStatus,
+    /// Canonical `<speaker-uuid>/<style-name>` voice the row was rendered with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) voice: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct BatchReport {
+    pub(crate) source: PathBuf,
+    pub(crate) total: usize,
+    pub(crate) succeeded: usize,
+    pub(crate) failed: usize,
+    pub(crate) rows: Vec<RowReport>,
+}
+
+impl BatchReport {
+    fn new(source: &Path, rows: Vec<RowReport>) -> Self {
+        let succeeded = rows
+            .iter()
+            .filter(|row| row.status == RowStatus::Succeeded)
+            .count();
+        Self {
+            source: source.to_path_buf(),
+            total: rows.len(),
+            succeeded,
+            failed: rows.len() - succeeded,
+            rows,
+        }
+    }
+}
+
+/// A row that passed up-front validation and is ready for synthesis.
+#[derive(Debug, Clone, PartialEq)]
+struct PlannedRow {
+    line: usize,
+    text: String,
+    style_id: u32,
+    rate: f32,
+    output: PathBuf,
+    chapter: Option<String>,
+}
+
+/// Renders every row of a CSV/TSV batch file, or of a file with one utterance per
+/// line, and writes a JSON report.
+///
+/// All rows are validated before the daemon is contacted. Up to `jobs` rows render
+/// at once over separate daemon connections; the daemon's `[workers]` setting
+/// decides how many of them it synthesizes in parallel. Invalid rows and rows
+/// that fail to render are reported and skipped so one bad line never stops the
+/// rest; they are also written with their error reasons to `<batch>.retry.csv`
+/// (or `.tsv`), which can be fixed and rendered as a batch of its own. Each output's
+/// SHA-256 is appended to `<batch>.sha256` as soon as the file is written, so with
+/// `verify` set a crashed run can tell complete outputs from ones to regenerate.
+///
+/// # Errors
+///
+/// Returns an error if the batch file cannot be read, the report or retry file
+/// cannot be written, any row is invalid or fails to render (after the rest have
+/// been rendered), or (when verifying) any output is missing or does not match
+/// its checksum.
+pub async fn run_batch(request: BatchRequest<'_>) -> Result<()> {
+    let output = StdAppOutput;
+    run_batch_with_output(request, &output).await
+}
+
+pub async fn run_batch_with_output(
+    request: BatchRequest<'_>,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let source = request.read_source().await?;
+    let rows = match request.lines_output_dir {
+        Some(output_dir) => parse_batch_lines(&source, output_dir),
+        None => parse_batch_table(&source)
+            .with_context(|| format!("Invalid batch file {}", request.batch_file.display()))?,
+    };
+    if rows.is_empty() {
+        return Err(anyhow!(
+            "Batch file {} has no rows",
+            request.batch_file.display()
+        ));
+    }
+    if request.verify {
+        return verify_outputs(&request, &rows, output).await;
+    }
+
+    let planned = plan_rows(&rows, request.default_style_id, request.default_rate);
+    let valid = planned
+        .iter()
+        .filter_map(|planned| planned.as_ref().ok().cloned())
+        .collect::<Vec<_>>();
+    if request.anki_export.is_some() {
+        check_anki_media_names(&valid)?;
+    }
+    let rendered = if valid.is_empty() {
+        Vec::new()
+    } else {
+        render_rows(&request, &valid, output).await?
+    };
+    if let Some(export_dir) = request.anki_export {
+        write_anki_export(export_dir, &valid, &rendered, output).await?;
+    }
+
+    let report = BatchReport::new(request.batch_file, merge_reports(&rows, planned, rendered));
+    write_report(&request.report_path(), &report).await?;
+    let delimiter = if request.lines_output_dir.is_some() {
+        TableDelimiter::Tab
+    } else {
+        TableDelimiter::detect(source.lines().next().unwrap_or_default())
+    };
+    let retry_path = request.retry_path(delimiter);
+    write_retry_file(&retry_path, &rows, &report, delimiter).await?;
+    print_summary(&report, &request.report_path(), output);
+    if let Some(m4b_path) = request.m4b_export {
+        if report.failed == 0 {
+            write_m4b_export(m4b_path, &request, &valid, output).await?;
+        } else {
+            output.error(&format!(
+                "Audiobook {} not written: every row must render first",
+                m4b_path.display()
+            ));
+        }
+    }
+    if report.failed > 0 {
+        output.info(&format!(
+            "Failed rows written to {} for retry",
+            retry_path.display()
+        ));
+    }
+
+    if report.failed == 0 {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "{} of {} batch rows did not render",
+            report.failed,
+            report.total
+        ))
+    }
+}
+
+fn plan_rows(
+    rows: &[BatchRow],
+    default_style_id: u32,
+    default_rate: f32,
+) -> Vec<Result<PlannedRow>> {
+    let mut seen_outputs = HashSet::new();
+    rows.iter()
+        .map(|row| {
+            let planned = plan_row(row, default_style_id, default_rate)?;
+            if !seen_outputs.insert(planned.output.clone()) {
+                return Err(anyhow!(
+                    "Output {} is used by more than one row",
+                    planned.output.display()
+                ));
+            }
+            Ok(planned)
+        })
+        .collect()
+}
+
+fn plan_row(row: &BatchRow, default_style_id: u32, default_rate: f32) -> Result<PlannedRow> {
+    if row.output.is_empty() {
+        return Err(anyhow!("Missing output filename"));
+    }
+    let style_id = match row.voice.as_deref() {
+        Some(voice) => resolve_voice_input(voice)?.0,
+        None => default_style_id,
+    };
+    let rate = match row.rate.as_deref() {
+        Some(rate) => rate.parse().map_err(|_| anyhow!("Invalid rate '{rate}'"))?,
+        None => default_rate,
+    };
+    validate_text_synthesis_request(&row.text, style_id, rate, DEFAULT_SYNTHESIS_PITCH)?;
+
+    Ok(PlannedRow {
+        line: row.line,
+        text: row.text.clone(),
+        style_id,
+        rate,
+        output: PathBuf::from(&row.output),
+        chapter: row.chapter.clone(),
+    })
+}
+
+/// Puts reports back in source order: invalid rows in place, rendered rows in turn.
+fn merge_reports(
+    rows: &[BatchRow],
+    planned: Vec<Result<PlannedRow>>,
+    rendered: Vec<RowReport>,
+) -> Vec<RowReport> {
+    let mut rendered = rendered.into_iter();
+    rows.iter()
+        .zip(planned)
+        .filter_map(|(row, planned)| match planned {
+            Ok(_) => rendered.next(),
+            Err(error) => Some(RowReport {
+                line: row.line,
+                output: row.output.clone(),
+                status: RowStatus::Invalid,
+                voice: None,
+                error: Some(format!("{error:#}")),
+            }),
+        })
+        .collect()
+}
+
+/// Writes the rows that did not render, or removes a stale retry file when all did.
+async fn write_retry_file(
+    path: &Path,
+    rows: &[BatchRow],
+    report: &BatchReport,
+    delimiter: TableDelimiter,
+) -> Result<()> {
+    let failures = rows
+        .iter()
+        .zip(&report.rows)
+        .filter(|(_, report)| report.status != RowStatus::Succeeded)
+        .map(|(row, report)| (row, report.error.as_deref().unwrap_or_default()))
+        .collect::<Vec<_>>();
+    if failures.is_empty() {
+        return match tokio::fs::remove_file(path).await {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+                Err(error).with_context(|| format!("Failed to remove stale {}", path.display()))
+            }
+            _ => Ok(()),
+        };
+    }
+    tokio::fs::write(path, render_retry_table(&failures, delimiter))
+        .await
+        .with_context(|| format!("Failed to write retry file {}", path.display()))
+}
+
+/// What the render workers share: the rows, the next one to claim, and the
+/// checksums file they append to.
+struct RenderPlan<'a> {
+    request: &'a BatchRequest<'a>,
+    rows: &'a [PlannedRow],
+    next_row: AtomicUsize,
+    checksums: Mutex<tokio::fs::File>,
+    checksums_path: PathBuf,
+    /// The report names voices canonically; numeric IDs are only meaningful for this install.
+    speakers: Vec<Speaker>,
+}
+
+async fn render_rows(
+    request: &BatchRequest<'_>,
+    rows: &[PlannedRow],
+    output: &dyn AppOutput,
+) -> Result<Vec<RowReport>> {
+    ensure_startup_resources(request.quiet, output).await?;
+    // Connect once before the workers so only one of them would start the daemon.
+    let client = connect_daemon_client_auto_start(&request.socket_path).await?;
+    let checksums_path = request.checksums_path();
+    let checksums = tokio::fs::File::create(&checksums_path)
+        .await
+        .with_context(|| format!("Failed to create {}", checksums_path.display()))?;
+    let plan = RenderPlan {
+        request,
+        rows,
+        next_row: AtomicUsize::new(0),
+        checksums: Mutex::new(checksums),
+        checksums_path,
+        speakers: scan_speakers_from_model_files().unwrap_or_default(),
+    };
+
+    let workers = request.jobs.clamp(1, MAX_BATCH_JOBS).min(rows.len());
+    let mut first_client = Some(client);
+    let results =
+        join_all((0..workers).map(|_| render_worker(&plan, first_client.take(), output))).await;
+
+    let mut reports = results
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    reports.sort_unstable_by_key(|(index, _)| *index);
+    Ok(reports.into_iter().map(|(_, report)| report).collect())
+}
+
+/// Claims rows until none are left; returns each row's report with its index.
+async fn render_worker(
+    plan: &RenderPlan<'_>,
+    mut client: Option<DaemonClient>,
+    output: &dyn AppOutput,
+) -> Result<Vec<(usize, RowReport)>> {
+    let mut reports = Vec::new();
+    loop {
+        let index = plan.next_row.fetch_add(1, Ordering::SeqCst);
+        let Some(row) = plan.rows.get(index) else {
+            return Ok(reports);
+        };
+        let client = match client.as_mut() {
+            Some(client) => client,
+            None => {
+                client.insert(connect_daemon_client_auto_start(&plan.request.socket_path).await?)
+            }
+        };
+        wait_while_background_deferred(client, plan.request.quiet, output).await;
+        let result = match render_row(client, row).await {
+            Ok(digest) => {
+                let mut checksums = plan.checksums.lock().await;
+                record_checksum(&mut checksums, &plan.checksums_path, &digest, row).await
+            }
+            Err(error) => Err(error),
+        };
+        if !plan.request.quiet {
+            match &result {
+                Ok(()) => output.info(&format!("✓ {}", row.output.display())),
+                Err(error) => output.error(&format!(
+                    "✗ line {}: {}",
+                    row.line,
+                    format_daemon_client_error_for_cli(error)
+                )),
+            }
+        }
+        reports.push((
+            index,
+            RowReport {
+                line: row.line,
+                output: row.output.display().to_string(),
+                status: if result.is_ok() {
+                    RowStatus::Succeeded
+                } else {
+                    RowStatus::Failed
+                },
+                voice: voice_id_for_style(&plan.speakers, row.style_id)
+                    .map(|voice| voice.to_string()),
+                error: result.err().map(|error| format!("{error:#}")),
+            },
+        ));
+    }
+}
+
+/// Appends and syncs one checksum line so it survives a crash right after the row.
+async fn record_checksum(
+    file: &mut tokio::fs::File,
+    path: &Path,
+    digest: &str,
+    row: &PlannedRow,
+) -> Result<()> {
+    let line = checksum_line(digest, &row.output.display().to_string());
+    file.write_all(line.as_bytes())
+        .await
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    file.sync_data()
+        .await
+        .with_context(|| format!("Failed to sync {}", path.display()))
+}
+
+/// Renders one row and returns the SHA-256 of the audio written.
+async fn render_row(client: &mut DaemonClient, row: &PlannedRow) -> Result<String> {
+    let options = SynthesisOptions::with_rate(row.rate);
+    let wav_data = client.synthesize(&row.text, row.style_id, options).await?;
+
+    if let Some(parent) = row.output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    tokio::fs::write(&row.output, &wav_data)
+        .await
+        .with_context(|| format!("Failed to write {}", row.output.display()))?;
+    Ok(sha256_hex(&wav_data))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputState {
+    Complete,
+    Missing,
+    Unrecorded,
+    Mismatch,
+}
+
+impl OutputState {
+    const fn reason(self) -> &'static str {
+        match self {
+            Self::Complete => "complete",
+            Self::Missing => "output file is missing",
+            Self::Unrecorded => "no checksum recorded (render did not finish)",
+            Self::Mismatch => "checksum mismatch (partially written or modified)",
+        }
+    }
+}
+
+fn classify_output(recorded: Option<&str>, contents: Option<&[u8]>) -> OutputState {
+    match (recorded, contents) {
+        (_, None) => OutputState::Missing,
+        (None, Some(_)) => OutputState::Unrecorded,
+        (Some(digest), Some(data)) if sha256_hex(data) == digest => OutputState::Complete,
+        (Some(_), Some(_)) => OutputState::Mismatch,
+    }
+}
+
+/// Compares each row's output with the checksums recorded by the last render.
+async fn verify_outputs(
+    request: &BatchRequest<'_>,
+    rows: &[BatchRow],
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let checksums_path = request.checksums_path();
+    let recorded = match tokio::fs::read_to_string(&checksums_path).await {
+        Ok(source) => parse_checksums(&source)
+            .with_context(|| format!("Invalid checksums file {}", checksums_path.display()))?,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+        Err(error) => {
+            return Err(error)
+                .with_context(|| format!("Failed to read {}", checksums_path.display()));
+        }
+    };
+
+    let mut incomplete = 0;
+    for row in rows {
+        let contents = tokio::fs::read(&row.output).await.ok();
+        let state = classify_output(
+            recorded.get(&row.output).map(String::as_str),
+            contents.as_deref(),
+        );
+        if state == OutputState::Complete {
+            if !request.quiet {
+                output.info(&format!("✓ {}", row.output));
+            }
+        } else {
+            incomplete += 1;
+            output.error(&format!(
+                "✗ line {} {}: {}",
+                row.line,
+                row.output,
+                state.reason()
+            ));
+        }
+    }
+
+    output.info(&format!(
+        "Verify finished: {} complete, {incomplete} need regeneration (checksums: {})",
+        rows.len() - incomplete,
+        checksums_path.display()
+    ));
+    if incomplete == 0 {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "{incomplete} of {} batch outputs need regeneration",
+            rows.len()
+        ))
+    }
+}
+
+fn anki_media_name(output: &Path) -> Result<String> {
+    output
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(str::to_owned)
+        .ok_or_else(|| anyhow!("Output {} has no usable file name", output.display()))
+}
+
+/// Anki stores media in one flat folder, so output file names must not collide.
+fn check_anki_media_names(rows: &[PlannedRow]) -> Result<()> {
+    let mut seen = HashSet::new();
+    for row in rows {
+        let name = anki_media_name(&row.output)?;
+        if !seen.insert(name.clone()) {
+            return Err(anyhow!(
+                "line {}: file name {name} is used by more than one row; Anki media names must be unique",
+                row.line
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Writes `notes.txt` and copies rendered audio into `media/` for Anki import.
+async fn write_anki_export(
+    export_dir: &Path,
+    rows: &[PlannedRow],
+    reports: &[RowReport],
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let media_dir = export_dir.join(ANKI_MEDIA_DIR_NAME);
+    tokio::fs::create_dir_all(&media_dir)
+        .await
+        .with_context(|| format!("Failed to create {}", media_dir.display()))?;
+
+    let mut notes = Vec::with_capacity(rows.len());
+    for (row, report) in rows.iter().zip(reports) {
+        if report.status != RowStatus::Succeeded {
+            continue;
+        }
+        let audio_file = anki_media_name(&row.output)?;
+        let target = media_dir.join(&audio_file);
+        tokio::fs::copy(&row.output, &target)
+            .await
+            .with_context(|| format!("Failed to copy audio to {}", target.display()))?;
+        notes.push(AnkiNote {
+            text: row.text.clone(),
+            audio_file,
+        });
+    }
+
+    let notes_path = export_dir.join(ANKI_NOTES_FILE_NAME);
+    tokio::fs::write(&notes_path, render_anki_notes(&notes))
+        .await
+        .with_context(|| format!("Failed to write {}", notes_path.display()))?;
+    output.info(&format!(
+        "Anki export: {} note(s) in {} (copy {}/ into collection.media)",
+        notes.len(),
+        notes_path.display(),
+        media_dir.display()
+    ));
+    Ok(())
+}
+
+/// Splits rows into audiobook chapters: one per row, or, when the batch has a
+/// `chapter` column, one starting at each titled row. Untitled chapters are
+/// named after the start of their first row's text.
+fn group_chapters(rows: &[PlannedRow]) -> Vec<(String, Vec<&PlannedRow>)> {
+    let by_row = rows.iter().all(|row| row.chapter.is_none());
+    let mut chapters: Vec<(String, Vec<&PlannedRow>)> = Vec::new();
+    for row in rows {
+        match chapters.last_mut() {
+            Some((_, members)) if !by_row && row.chapter.is_none() => members.push(row),
+            _ => {
+                let title = row
+                    .chapter
+                    .clone()
+                    .unwrap_or_else(|| text_excerpt(&row.text));
+                chapters.push((title, vec![row]));
+            }
+        }
+    }
+    chapters
+}
+
+fn text_excerpt(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default().trim();
+    let mut excerpt = line.chars().take(CHAPTER_EXCERPT_CHARS).collect::<String>();
+    if excerpt.len() < line.len() {
+        excerpt.push('…');
+    }
+    excerpt
+}
+
+/// Joins each chapter's rendered rows and writes them as one M4B audiobook.
+async fn write_m4b_export(
+    path: &Path,
+    request: &BatchRequest<'_>,
+    rows: &[PlannedRow],
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let mut chapter_audio = Vec::new();
+    for (title, members) in group_chapters(rows) {
+        let mut segments = Vec::with_capacity(members.len());
+        for row in members {
+            segments.push(
+                tokio::fs::read(&row.output)
+                    .await
+                    .with_context(|| format!("Failed to read {}", row.output.display()))?,
+            );
+        }
+        chapter_audio.push((title, concatenate_wav_segments(&segments)?));
+    }
+    let chapters = chapter_audio
+        .iter()
+        .map(|(title, wav)| M4bChapter { title, wav })
+        .collect::<Vec<_>>();
+
+    let default_title = request
+        .side_file_base()
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let metadata = M4bMetadata {
+        title: request.m4b_title.unwrap_or(&default_title),
+        author: request.m4b_author,
+    };
+    let m4b = encode_m4b(&chapters, &metadata)
+        .with_context(|| format!("Failed to build audiobook {}", path.display()))?;
+    tokio::fs::write(path, m4b)
+        .await
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    output.info(&format!(
+        "Audiobook: {} chapter(s) in {}",
+        chapters.len(),
+        path.display()
+    ));
+    Ok(())
+}
+
+async fn write_report(path: &Path, report: &BatchReport) -> Result<()> {
+    let json = serde_json::to_vec_pretty(report).context("Failed to serialize batch report")?;
+    tokio::fs::write(path, json)
+        .await
+        .with_context(|| format!("Failed to write batch report {}", path.display()))
+}
+
+fn print_summary(report: &BatchReport, report_path: &Path, output: &dyn AppOutput) {
+    for row in report
+        .rows
+        .iter()
+        .filter(|row| row.status == RowStatus::Invalid)
+    {
+        output.error(&format!(
+            "line {}: {}",
+            row.line,
+            row.error.as_deref().unwrap_or_default()
+        ));
+    }
+    output.info(&format!(
+        "Batch finished: {} succeeded, {} failed (report: {})",
+        report.succeeded,
+        report.failed,
+        report_path.display()
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(line: usize, text: &str, rate: Option<&str>, output: &str) -> BatchRow {
+        BatchRow {
+            line,
+            text: text.to_owned(),
+            voice: Some("3".to_owned()),
+            rate: rate.map(str::to_owned),
+            output: output.to_owned(),
+            chapter: None,
+        }
+    }
+
+    #[test]
+    fn validation_flags_bad_rows_and_duplicate_outputs() {
+        let rows = vec![
+            row(2, "こんにちは", Some("1.5"), "a.wav"),
+            row(3, "こんにちは", Some("9"), "b.wav"),
+            row(4, "   ", None, "c.wav"),
+            row(5, "またね", None, "a.wav"),
+        ];
+
+        let planned = plan_rows(&rows, 1, 1.0);
+
+        assert_eq!(planned[0].as_ref().expect("valid row").rate, 1.5);
+        assert!(planned[1].is_err());
+        assert!(planned[2].is_err());
+        assert!(
+            planned[3]
+                .as_ref()
+                .expect_err("duplicate output")
+                .to_string()
+                .contains("more than one row")
+        );
+
+        let rendered = vec![RowReport {
+            line: 2,
+            output: "a.wav".to_owned(),
+            status: RowStatus::Failed,
+            voice: None,
+            error: Some("Synthesis error".to_owned()),
+        }];
+        let reports = merge_reports(&rows, planned, rendered);
+        assert_eq!(
+            reports
+                .iter()
+                .map(|report| (report.line, report.status))
+                .collect::<Vec<_>>(),
+            [
+                (2, RowStatus::Failed),
+                (3, RowStatus::Invalid),
+                (4, RowStatus::Invalid),
+                (5, RowStatus::Invalid)
+            ]
+        );
+    }
+
+    #[test]
+    fn report_path_defaults_next_to_batch_file() {
+        let request = BatchRequest {
+            batch_file: Path::new("/work/lines.csv"),
+            report_file: None,
+            anki_export: None,
+            m4b_export: None,
+            m4b_title: None,
+            m4b_author: None,
+            verify: false,
+            default_style_id: 3,
+            default_rate: 1.0,
+            quiet: true,
+            socket_path: PathBuf::from("/tmp/unused.sock"),
+            lines_output_dir: None,
+            jobs: 1,
+        };
+        assert_eq!(
+            request.report_path(),
+            PathBuf::from("/work/lines.report.json")
+        );
+        assert_eq!(
+            request.checksums_path(),
+            PathBuf::from("/work/lines.sha256")
+        );
+        assert_eq!(
+            request.retry_path(TableDelimiter::Tab),
+            PathBuf::from("/work/lines.retry.tsv")
+        );
+
+        let stdin = BatchRequest {
+            batch_file: Path::new(STDIN_BATCH),
+            lines_output_dir: Some(Path::new("/work/out")),
+            ..request
+        };
+        assert_eq!(
+            stdin.report_path(),
+            PathBuf::from("/work/out/batch.report.json")
+        );
+    }
+
+    #[test]
+    fn chapters_start_at_titled_rows_or_at_every_row() {
+        let planned = |text: &str, chapter: Option<&str>| PlannedRow {
+            line: 1,
+            text: text.to_owned(),
+            style_id: 3,
+            rate: 1.0,
+            output: PathBuf::from("a.wav"),
+            chapter: chapter.map(str::to_owned),
+        };
+        let titles = |rows: &[PlannedRow]| {
+            group_chapters(rows)
+                .into_iter()
+                .map(|(title, members)| (title, members.len()))
+                .collect::<Vec<_>>()
+        };
+        let long = "あ".repeat(CHAPTER_EXCERPT_CHARS + 1);
+
+        assert_eq!(
+            titles(&[planned("はじめに", None), planned(&long, None)]),
+            [
+                ("はじめに".to_owned(), 1),
+                (format!("{}…", "あ".repeat(CHAPTER_EXCERPT_CHARS)), 1)
+            ]
+        );
+        assert_eq!(
+            titles(&[
+                planned("前書き", None),
+                planned("一", Some("第一章")),
+                planned("二", None),
+                planned("三", Some("第二章")),
+            ]),
+            [
+                ("前書き".to_owned(), 1),
+                ("第一章".to_owned(), 2),
+                ("第二章".to_owned(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn verification_separates_complete_from_interrupted_outputs() {
+        let digest = sha256_hex(b"RIFF-complete");
+        assert_eq!(
+            classify_output(Some(&digest), Some(b"RIFF-complete")),
+            OutputState::Complete
+        );
+        assert_eq!(
+            classify_output(Some(&digest), Some(b"RIFF-comp")),
+            OutputState::Mismatch
+        );
+        assert_eq!(
+            classify_output(None, Some(b"RIFF-complete")),
+            OutputState::Unrecorded
+        );
+        assert_eq!(classify_output(Some(&digest), None), OutputState::Missing);
+    }
+}
@@ -0,0 +1,107 @@
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::infrastructure::ipc::IPC_PROTOCOL_VERSION;
+use crate::interface::AppOutput;
+
+/// What this build of the crate can do, for wrappers that adapt to different builds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Capabilities {
+    pub version: &'static str,
+    pub protocol_version: u32,
+    /// Audio encodings the tools can produce.
+    pub formats: Vec<&'static str>,
+    /// Ways to talk to this build besides the command line.
+    pub protocols: Vec<&'static str>,
+    /// Components compiled in; `mcp` and `effects` are part of every build.
+    pub features: Vec<&'static str>,
+}
+
+impl Capabilities {
+    #[must_use]
+    pub fn of_this_build() -> Self {
+        let optional_features = [
+            ("http", cfg!(feature = "http")),
+            ("menu-bar", cfg!(feature = "menu-bar")),
+            ("web-ui", cfg!(feature = "web-ui")),
+            ("opus", cfg!(feature = "opus")),
+            ("mp3", cfg!(feature = "mp3")),
+            ("ffi", cfg!(feature = "ffi")),
+            ("simd", cfg!(feature = "simd")),
+            ("fast-strings", cfg!(feature = "fast-strings")),
+            ("small-vectors", cfg!(feature = "small-vectors")),
+        ];
+        let mut formats = vec!["wav", "flac"];
+        if cfg!(feature = "opus") {
+            formats.push("opus");
+        }
+        if cfg!(feature = "mp3") {
+            formats.push("mp3");
+        }
+        let mut protocols = vec!["unix-socket", "mcp-stdio", "json-lines-stdio"];
+        if cfg!(feature = "http") {
+            protocols.push("http");
+        }
+
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            protocol_version: IPC_PROTOCOL_VERSION,
+            formats,
+            protocols,
+            features: ["mcp", "effects"]
+                .into_iter()
+                .chain(
+                    optional_features
+                        .into_iter()
+                        .filter_map(|(name, enabled)| enabled.then_some(name)),
+                )
+                .collect(),
+        }
+    }
+
+    fn lines(&self) -> Vec<String> {
+        vec![
+            format!("version: {}", self.version),
+            format!("protocol version: {}", self.protocol_version),
+            format!("formats: {}", self.formats.join(", ")),
+            format!("protocols: {}", self.protocols.join(", ")),
+            format!("features: {}", self.features.join(", ")),
+        ]
+    }
+}
+
+/// Prints this build's capabilities, as one JSON object when `json` is set.
+///
+/// # Errors
+///
+/// Returns an error if the JSON cannot be serialized.
+pub fn run_capabilities(json: bool, output: &dyn AppOutput) -> Result<()> {
+    let capabilities = Capabilities::of_this_build();
+    if json {
+        output.info(&serde_json::to_string_pretty(&capabilities)?);
+    } else {
+        for line in capabilities.lines() {
+            output.info(&line);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::output::BufferAppOutput;
+
+    #[test]
+    fn json_lists_the_always_built_components() {
+        let output = BufferAppOutput::default();
+        run_capabilities(true, &output).expect("capabilities");
+
+        let report: serde_json::Value =
+            serde_json::from_str(&output.infos().join("\n")).expect("json");
+        assert_eq!(report["protocol_version"], IPC_PROTOCOL_VERSION);
+        assert_eq!(report["formats"][0], "wav");
+        assert_eq!(report["features"][0], "mcp");
+        assert_eq!(report["features"][1], "effects");
+    }
+}
@@ -4,13 +4,20 @@ use std::path::{Path, PathBuf};
 use std::process::{Command as ProcessCommand, Stdio};
 use std::time::Duration;
 
+use crate::infrastructure::daemon::client::{DaemonClient, find_daemon_client_error};
+use crate::infrastructure::daemon::config::{daemon_config_path, load_daemon_config};
+use crate::infrastructure::daemon::process::{daemon_pid_is_running, recorded_daemon_pid};
+use crate::infrastructure::daemon::self_test::{SelfTestStep, run_self_test};
 use crate::infrastructure::daemon::{
-    DaemonError, check_and_prevent_duplicate, exit_codes as exit_daemon, is_socket_responsive,
-    pid_memory_info_line, terminate_process,
+    DaemonError, check_and_prevent_duplicate, exit_codes as exit_daemon, hangup_process,
+    is_socket_responsive, pid_memory_info_line, terminate_process,
 };
 use crate::interface::cli::daemon_invocation::{
     DaemonCliFlags, DaemonInvocation, decide_daemon_invocation,
 };
+use crate::interface::cli::daemon_stats::{
+    daemon_stats_lines, fetch_daemon_stats, fetch_slow_log, slow_log_lines,
+};
 use crate::interface::{AppOutput, StdAppOutput};
 
 enum ExecutionDecision {
@@ -30,6 +37,7 @@ trait DaemonControlOs {
     fn find_daemon_processes(&self) -> anyhow::Result<Vec<u32>>;
     fn pid_memory_info_line(&self, pid_num: u32) -> Option<String>;
     fn kill_term(&self, pid: u32) -> bool;
+    fn kill_hup(&self, pid: u32) -> bool;
     fn remove_stale_socket_if_present(
         &self,
         socket_path: &Path,
@@ -59,6 +67,10 @@ impl DaemonControlOs for SystemDaemonControlOs {
         terminate_process(pid)
     }
 
+    fn kill_hup(&self, pid: u32) -> bool {
+        hangup_process(pid)
+    }
+
     fn remove_stale_socket_if_present(
         &self,
         socket_path: &Path,
@@ -74,7 +86,16 @@ fn daemon_usage_lines(version: &str) -> Vec<String> {
         "  --start     Start the daemon (default)".to_string(),
         "  --stop      Stop the running daemon".to_string(),
         "  --status    Check daemon status".to_string(),
+        "  --stats     Show syntheses, latency, and model cache counters".to_string(),
+        "  --slow-log  Show recent requests slower than [slow_log] in daemon.toml".to_string(),
         "  --restart   Restart the daemon".to_string(),
+        "  --reload    Re-read daemon.toml without dropping connections (SIGHUP)".to_string(),
+        "  --reload-models Re-read daemon.toml and rescan models, waiting for the result"
+            .to_string(),
+        "  --self-test Synthesize a test phrase and exit (for health checks)".to_string(),
+        "  --preload IDS Load models (e.g. 3,2,8) into the running daemon, or at --start"
+            .to_string(),
+        "  --check-config Validate daemon.toml and exit".to_string(),
         "\nExecution Modes:".to_string(),
         "  --foreground Run in foreground (for development)".to_string(),
         "  --detach     Run as background process".to_string(),
@@ -111,28 +132,175 @@ fn print_usage_banner(output: &dyn AppOutput) {
 async fn maybe_handle_control_commands(
     socket_path: &Path,
     flags: DaemonCliFlags,
+    preload: &[u32],
     output: &dyn AppOutput,
-) -> Result<bool> {
+) -> Result<ExecutionDecision> {
     match decide_daemon_invocation(flags) {
         DaemonInvocation::Stop => {
             handle_stop_daemon(socket_path, output).await?;
-            Ok(true)
+            Ok(ExecutionDecision::exit(exit_daemon::SUCCESS))
         }
         DaemonInvocation::Status => {
             handle_status_daemon(socket_path, output).await?;
-            Ok(true)
+            Ok(ExecutionDecision::exit(exit_daemon::SUCCESS))
         }
         DaemonInvocation::Restart => {
             output.info("Restarting daemon...");
             let _ = handle_stop_daemon(socket_path, output).await;
             tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
-            Ok(false)
+            Ok(ExecutionDecision::Continue)
+        }
+        DaemonInvocation::Reload => {
+            let os = SystemDaemonControlOs;
+            Ok(ExecutionDecision::exit(handle_reload_daemon_with_os(
+                output, &os,
+            )))
+        }
+        DaemonInvocation::ReloadModels => Ok(ExecutionDecision::exit(
+            handle_reload_models_daemon(socket_path, output).await,
+        )),
+        DaemonInvocation::SelfTest => {
+            // Nothing else runs on the runtime, so blocking it for the test is fine.
+            Ok(ExecutionDecision::exit(handle_self_test(output)))
         }
+        DaemonInvocation::Preload => Ok(ExecutionDecision::exit(
+            handle_preload_daemon(socket_path, preload, output).await,
+        )),
+        DaemonInvocation::Stats => Ok(ExecutionDecision::exit(
+            handle_stats_daemon(socket_path, output).await,
+        )),
+        DaemonInvocation::SlowLog => Ok(ExecutionDecision::exit(
+            handle_slow_log_daemon(socket_path, output).await,
+        )),
+        DaemonInvocation::CheckConfig => Ok(ExecutionDecision::exit(handle_check_config(
+            &daemon_config_path(),
+            output,
+        ))),
         DaemonInvocation::ShowUsage => {
             print_usage_banner(output);
-            Ok(true)
+            Ok(ExecutionDecision::exit(exit_daemon::SUCCESS))
+        }
+        DaemonInvocation::Start => Ok(ExecutionDecision::Continue),
+    }
+}
+
+/// Asks the running daemon to load `model_ids` into its model cache.
+async fn handle_preload_daemon(
+    socket_path: &Path,
+    model_ids: &[u32],
+    output: &dyn AppOutput,
+) -> i32 {
+    let preloaded = match DaemonClient::new_at(socket_path).await {
+        Ok(mut client) => client.preload_models(model_ids).await,
+        Err(error) => Err(error),
+    };
+    match preloaded {
+        Ok(elapsed) => {
+            output.info(&format!(
+                "Preloaded models {} in {}ms",
+                model_ids
+                    .iter()
+                    .map(u32::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                elapsed.as_millis()
+            ));
+            exit_daemon::SUCCESS
+        }
+        Err(error) => {
+            output.error(&format!("Failed to preload models: {error:#}"));
+            if !is_socket_responsive(socket_path) {
+                output.error(
+                    "   Start the daemon with 'voicevox-daemon --start --preload IDS' instead.",
+                );
+            }
+            exit_daemon::FAILURE
+        }
+    }
+}
+
+/// Prints the counters the running daemon has kept since it started.
+async fn handle_stats_daemon(socket_path: &Path, output: &dyn AppOutput) -> i32 {
+    match fetch_daemon_stats(socket_path).await {
+        Ok(stats) => {
+            output.info("VOICEVOX Daemon Stats");
+            output.info("========================");
+            for line in daemon_stats_lines(&stats) {
+                output.info(&line);
+            }
+            exit_daemon::SUCCESS
+        }
+        Err(error) => {
+            output.error(&format!("Failed to read daemon stats: {error:#}"));
+            exit_daemon::FAILURE
+        }
+    }
+}
+
+/// Prints the requests the running daemon recorded as slow.
+async fn handle_slow_log_daemon(socket_path: &Path, output: &dyn AppOutput) -> i32 {
+    match fetch_slow_log(socket_path).await {
+        Ok((threshold_ms, entries)) => {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs());
+            for line in slow_log_lines(threshold_ms, &entries, now) {
+                output.info(&line);
+            }
+            exit_daemon::SUCCESS
+        }
+        Err(error) => {
+            output.error(&format!("Failed to read the slow log: {error:#}"));
+            exit_daemon::FAILURE
+        }
+    }
+}
+
+/// Loads `daemon.toml` the way a starting daemon would and reports the outcome.
+fn handle_check_config(config_path: &Path, output: &dyn AppOutput) -> i32 {
+    if !config_path.exists() {
+        output.info(&format!(
+            "{} does not exist; the daemon will use defaults",
+            config_path.display()
+        ));
+        return exit_daemon::SUCCESS;
+    }
+    match load_daemon_config(config_path) {
+        Ok(config) => {
+            output.info(&format!("Config OK: {}", config.summary(config_path)));
+            exit_daemon::SUCCESS
+        }
+        Err(error) => {
+            output.error(&format!("{error:#}"));
+            exit_daemon::FAILURE
+        }
+    }
+}
+
+fn self_test_step_line(step: &SelfTestStep) -> String {
+    format!(
+        "✓ {:<9} {:>6}ms  {}",
+        step.name,
+        step.elapsed.as_millis(),
+        step.detail
+    )
+}
+
+/// Runs the synthesis self-test and maps the outcome to a daemon exit code.
+fn handle_self_test(output: &dyn AppOutput) -> i32 {
+    output.info("VOICEVOX Daemon Self-Test");
+    match run_self_test(|step| output.info(&self_test_step_line(step))) {
+        Ok(()) => {
+            output.info("Self-test passed");
+            exit_daemon::SUCCESS
+        }
+        Err(error) => {
+            output.error(&format!("✗ Self-test failed: {error:#}"));
+            match error {
+                DaemonError::NoModelsAvailable => exit_daemon::NO_MODELS,
+                _ => exit_daemon::SELF_TEST_FAILED,
+            }
         }
-        DaemonInvocation::Start => Ok(false),
     }
 }
 
@@ -248,11 +416,82 @@ fn print_pid_memory_info(pid_num: u32, output: &dyn AppOutput, os: &dyn DaemonCo
     }
 }
 
+/// How long `--stop` waits for a daemon that accepted `Shutdown` to finish its
+/// running requests and exit.
+const SHUTDOWN_WAIT: Duration = Duration::from_secs(35);
+
 async fn handle_stop_daemon(socket_path: &Path, output: &dyn AppOutput) -> Result<()> {
+    if stop_daemon_over_ipc(socket_path, output).await {
+        return Ok(());
+    }
     let os = SystemDaemonControlOs;
     handle_stop_daemon_with_os(socket_path, output, &os, Duration::from_millis(1000)).await
 }
 
+/// Sends `Shutdown` to the daemon at `socket_path` and waits for its process to
+/// exit. Returns `false` when the daemon cannot take the request, as daemons from
+/// before `Shutdown` cannot, so the caller falls back to signals.
+async fn stop_daemon_over_ipc(socket_path: &Path, output: &dyn AppOutput) -> bool {
+    if !is_socket_responsive(socket_path) {
+        return false;
+    }
+    let pid = recorded_daemon_pid(socket_path);
+    let requested = match DaemonClient::new_at(socket_path).await {
+        Ok(mut client) => client.shutdown().await,
+        Err(error) => Err(error),
+    };
+    if let Err(error) = requested {
+        output.info(&format!(
+            "Shutdown request failed, falling back to signals: {error:#}"
+        ));
+        return false;
+    }
+
+    output.info("Stopping VOICEVOX daemon...");
+    let started = tokio::time::Instant::now();
+    let exited = || pid.map_or_else(|| !socket_path.exists(), |pid| !daemon_pid_is_running(pid));
+    while !exited() {
+        if started.elapsed() >= SHUTDOWN_WAIT {
+            output.info("Daemon is still finishing running requests");
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+    match pid {
+        Some(pid) => output.info(&format!("Daemon stopped (PID: {pid})")),
+        None => output.info("Daemon stopped"),
+    }
+    true
+}
+
+/// Asks the running daemon to reload and rescan over IPC and reports the model
+/// count, sending SIGHUP instead to daemons that cannot take the request.
+async fn handle_reload_models_daemon(socket_path: &Path, output: &dyn AppOutput) -> i32 {
+    let reloaded = match DaemonClient::new_at(socket_path).await {
+        Ok(mut client) => client.reload().await,
+        Err(error) => Err(error),
+    };
+    match reloaded {
+        Ok(model_count) => {
+            output.info(&format!(
+                "Reloaded daemon.toml; {model_count} model(s) available"
+            ));
+            exit_daemon::SUCCESS
+        }
+        // The daemon took the request, so a signal would fail the same way.
+        Err(error) if find_daemon_client_error(&error).is_some() => {
+            output.error(&format!("Reload failed: {error:#}"));
+            exit_daemon::FAILURE
+        }
+        Err(error) => {
+            output.info(&format!(
+                "Reload request failed, falling back to SIGHUP: {error:#}"
+            ));
+            handle_reload_daemon_with_os(output, &SystemDaemonControlOs)
+        }
+    }
+}
+
 async fn handle_stop_daemon_with_os(
     socket_path: &Path,
     output: &dyn AppOutput,
@@ -348,9 +587,90 @@ async fn stop_daemon_process_with_os(
     }
 }
 
+/// Signals every running daemon to reload `daemon.toml`; the daemon logs the result.
+fn handle_reload_daemon_with_os(output: &dyn AppOutput, os: &dyn DaemonControlOs) -> i32 {
+    let pids = match os.find_daemon_processes() {
+        Ok(pids) => pids,
+        Err(error) => {
+            output.error(&format!("Failed to find daemon process: {error}"));
+            return exit_daemon::FAILURE;
+        }
+    };
+    if pids.is_empty() {
+        output.error("No daemon process found");
+        return exit_daemon::FAILURE;
+    }
+
+    let mut code = exit_daemon::SUCCESS;
+    for pid in pids {
+        if os.kill_hup(pid) {
+            output.info(&format!("Reload requested (PID: {pid})"));
+        } else {
+            output.error(&format!("Failed to signal daemon (PID: {pid})"));
+            code = exit_daemon::FAILURE;
+        }
+    }
+    code
+}
+
+/// Asks running daemons to reload after `daemon.toml` or the models directory changed
+/// on their behalf. No daemon running is not an error: it reads both when it starts.
+pub(crate) fn notify_daemons_of_changes(output: &dyn AppOutput) {
+    notify_daemons_of_changes_with_os(output, &SystemDaemonControlOs);
+}
+
+fn notify_daemons_of_changes_with_os(output: &dyn AppOutput, os: &dyn DaemonControlOs) {
+    match os.find_daemon_processes() {
+        Ok(pids) if pids.is_empty() => {
+            output.info("Daemon not running; changes apply when it starts");
+        }
+        Ok(pids) => {
+            for pid in pids {
+                if os.kill_hup(pid) {
+                    output.info(&format!("Reload requested (PID: {pid})"));
+                } else {
+                    output.error(&format!("Failed to signal daemon (PID: {pid})"));
+                }
+            }
+        }
+        Err(error) => output.error(&format!(
+            "Failed to find daemon process: {error}; run `voicevox-daemon --reload` to apply changes"
+        )),
+    }
+}
+
 async fn handle_status_daemon(socket_path: &Path, output: &dyn AppOutput) -> Result<()> {
     let os = SystemDaemonControlOs;
-    handle_status_daemon_with_os(socket_path, output, &os).await
+    handle_status_daemon_with_os(socket_path, output, &os).await?;
+    if os.is_responsive(socket_path) {
+        print_daemon_activity(socket_path, output).await;
+    }
+    Ok(())
+}
+
+/// Prints queue activity and recent warnings from the daemon's `Status` RPC.
+async fn print_daemon_activity(socket_path: &Path, output: &dyn AppOutput) {
+    let status = match DaemonClient::new_at(socket_path).await {
+        Ok(mut client) => client.status().await,
+        Err(error) => Err(error),
+    };
+    let status = match status {
+        Ok(status) => status,
+        Err(error) => {
+            output.error(&format!("Failed to read daemon activity: {error}"));
+            return;
+        }
+    };
+    output.info(&format!("Queue:   {}", status.queue_length));
+    if let Some(style_id) = status.current_style_id {
+        output.info(&format!("Last style: {style_id}"));
+    }
+    if !status.recent_warnings.is_empty() {
+        output.info("Recent warnings:");
+        for warning in &status.recent_warnings {
+            output.info(&format!("  - {warning}"));
+        }
+    }
 }
 
 async fn handle_status_daemon_with_os(
@@ -388,22 +708,31 @@ async fn handle_status_daemon_with_os(
 
 /// Executes daemon CLI flow from already-parsed flags and exits the process when required.
 ///
+/// `preload` lists models to load into a running daemon, or at startup when the
+/// flags start one.
+///
 /// # Errors
 ///
 /// Returns an error if command dispatch or daemon runtime fails.
-pub async fn run_daemon_cli(socket_path: PathBuf, flags: DaemonCliFlags) -> Result<i32> {
+pub async fn run_daemon_cli(
+    socket_path: PathBuf,
+    flags: DaemonCliFlags,
+    preload: Vec<u32>,
+) -> Result<i32> {
     let output = StdAppOutput;
-    run_daemon_cli_with_output(socket_path, flags, &output).await
+    run_daemon_cli_with_output(socket_path, flags, preload, &output).await
 }
 
 pub async fn run_daemon_cli_with_output(
     socket_path: PathBuf,
     flags: DaemonCliFlags,
+    preload: Vec<u32>,
     output: &dyn AppOutput,
 ) -> Result<i32> {
-    if maybe_handle_control_commands(&socket_path, flags, output).await? {
-        return Ok(0);
-    }
+    match maybe_handle_control_commands(&socket_path, flags, &preload, output).await? {
+        ExecutionDecision::Exit(code) => return Ok(code),
+        ExecutionDecision::Continue => {}
+    };
 
     match maybe_detach(&socket_path, flags, output).await {
         ExecutionDecision::Exit(code) => return Ok(code),
@@ -416,8 +745,25 @@ pub async fn run_daemon_cli_with_output(
     };
 
     print_daemon_start_banner(&socket_path, output);
-    crate::infrastructure::daemon::run_daemon(socket_path, flags.start_mode.is_foreground())
-        .await?;
+    if let Some(shared) = flags.shared_socket {
+        output.info(&format!(
+            "Shared with group {}: requests are logged with the caller's UID",
+            shared.gid
+        ));
+    }
+    crate::infrastructure::daemon::run_daemon(
+        socket_path,
+        flags.start_mode.is_foreground(),
+        crate::infrastructure::daemon::NetworkListeners {
+            http: flags.http_address,
+            tcp: flags.listen_address,
+        },
+        flags.shared_socket,
+        preload,
+        flags.copy_models_local,
+        flags.low_power,
+    )
+    .await?;
     Ok(0)
 }
 
@@ -467,6 +813,10 @@ mod tests {
             self.kill_ok
         }
 
+        fn kill_hup(&self, _pid: u32) -> bool {
+            self.kill_ok
+        }
+
         fn remove_stale_socket_if_present(
             &self,
             _socket_path: &Path,
@@ -504,6 +854,71 @@ mod tests {
         assert!(text.contains("Memory Info: 20480 0.1 00:00:01"));
     }
 
+    #[test]
+    fn reload_signals_each_daemon_and_fails_without_one() {
+        let output = BufferAppOutput::default();
+        let os = FakeDaemonControlOs {
+            responsive: Mutex::new(VecDeque::new()),
+            socket_exists: true,
+            pids: vec![7, 8],
+            pids_error: None,
+            memory_line: None,
+            kill_ok: true,
+            stale_socket_cleanup: None,
+            stale_socket_cleanup_error: None,
+        };
+        assert_eq!(
+            handle_reload_daemon_with_os(&output, &os),
+            exit_daemon::SUCCESS
+        );
+        let text = output.infos().join("\n");
+        assert!(text.contains("Reload requested (PID: 7)"));
+        assert!(text.contains("Reload requested (PID: 8)"));
+
+        let idle = FakeDaemonControlOs {
+            pids: Vec::new(),
+            ..os
+        };
+        assert_eq!(
+            handle_reload_daemon_with_os(&output, &idle),
+            exit_daemon::FAILURE
+        );
+    }
+
+    #[test]
+    fn change_notification_tolerates_a_stopped_daemon() {
+        let output = BufferAppOutput::default();
+        let os = FakeDaemonControlOs {
+            responsive: Mutex::new(VecDeque::new()),
+            socket_exists: false,
+            pids: Vec::new(),
+            pids_error: None,
+            memory_line: None,
+            kill_ok: true,
+            stale_socket_cleanup: None,
+            stale_socket_cleanup_error: None,
+        };
+        notify_daemons_of_changes_with_os(&output, &os);
+        assert!(
+            output
+                .infos()
+                .join("\n")
+                .contains("changes apply when it starts")
+        );
+
+        let running = FakeDaemonControlOs {
+            pids: vec![9],
+            ..os
+        };
+        notify_daemons_of_changes_with_os(&output, &running);
+        assert!(
+            output
+                .infos()
+                .join("\n")
+                .contains("Reload requested (PID: 9)")
+        );
+    }
+
     #[tokio::test]
     async fn stop_reports_kill_failure_without_shelling_out() {
         let output = BufferAppOutput::default();
@@ -671,4 +1086,19 @@ mod tests {
         assert!(text.contains("Stale socket candidate: socket path exists but is not responsive"));
         assert!(text.contains("Try 'voicevox-daemon --stop' to clean up stale socket state."));
     }
+
+    #[test]
+    fn check_config_reports_the_invalid_key() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("daemon.toml");
+        let output = BufferAppOutput::default();
+
+        std::fs::write(&path, "[transport]\nhttp = \"0.0.0.0:50080\"\n").expect("write");
+        assert_eq!(handle_check_config(&path, &output), exit_daemon::FAILURE);
+        assert!(output.errors()[0].contains("transport.http must be a loopback address"));
+
+        std::fs::write(&path, "preload_models = [3]\n[model_cache]\n").expect("write");
+        assert_eq!(handle_check_config(&path, &output), exit_daemon::SUCCESS);
+        assert!(output.infos()[0].starts_with("Config OK: "));
+    }
 }
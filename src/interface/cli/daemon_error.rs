@@ -21,6 +21,12 @@ pub fn format_daemon_client_error_for_cli(error: &anyhow::Error) -> String {
         DaemonErrorCode::Internal => {
             format!("VOICEVOX daemon internal error. {}", daemon_error.message())
         }
+        DaemonErrorCode::ProtocolMismatch => {
+            format!(
+                "VOICEVOX daemon version mismatch. {}",
+                daemon_error.message()
+            )
+        }
     }
 }
 
@@ -33,5 +39,6 @@ pub fn daemon_client_exit_code(error: &anyhow::Error) -> Option<u8> {
         DaemonErrorCode::ModelLoadFailed => 3,
         DaemonErrorCode::SynthesisFailed => 4,
         DaemonErrorCode::Internal => 5,
+        DaemonErrorCode::ProtocolMismatch => 6,
     })
 }
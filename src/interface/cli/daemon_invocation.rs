@@ -1,3 +1,7 @@
+use std::net::SocketAddr;
+
+use crate::infrastructure::daemon::shared_socket::SharedSocket;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DaemonStartMode {
     Foreground,
@@ -31,6 +35,18 @@ pub enum DaemonControlCommand {
     Stop,
     Status,
     Restart,
+    Reload,
+    /// Reload and rescan over IPC, waiting for the result (`--reload-models`).
+    ReloadModels,
+    SelfTest,
+    /// Load models into the running daemon's cache (`--preload` without a start flag).
+    Preload,
+    /// Print the running daemon's counters (`--stats`).
+    Stats,
+    /// Validate `daemon.toml` without starting anything (`--check-config`).
+    CheckConfig,
+    /// Print the running daemon's recent slow requests (`--slow-log`).
+    SlowLog,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,6 +55,15 @@ pub struct DaemonCliFlags {
     pub mode_flag_explicit: bool,
     pub start: bool,
     pub control: DaemonControlCommand,
+    pub http_address: Option<SocketAddr>,
+    /// Accept token-authenticated clients over TCP (`--listen`).
+    pub listen_address: Option<SocketAddr>,
+    /// Serve a group instead of only the current user (`--shared-socket`).
+    pub shared_socket: Option<SharedSocket>,
+    /// Load models from a local copy of the models directory (`--copy-models-local`).
+    pub copy_models_local: bool,
+    /// Save energy and defer background work on battery (`--low-power`).
+    pub low_power: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -47,6 +72,13 @@ pub enum DaemonInvocation {
     Stop,
     Status,
     Restart,
+    Reload,
+    ReloadModels,
+    SelfTest,
+    Preload,
+    Stats,
+    CheckConfig,
+    SlowLog,
     Start,
 }
 
@@ -56,6 +88,13 @@ pub const fn decide_daemon_invocation(flags: DaemonCliFlags) -> DaemonInvocation
         DaemonControlCommand::Stop => DaemonInvocation::Stop,
         DaemonControlCommand::Status => DaemonInvocation::Status,
         DaemonControlCommand::Restart => DaemonInvocation::Restart,
+        DaemonControlCommand::Reload => DaemonInvocation::Reload,
+        DaemonControlCommand::ReloadModels => DaemonInvocation::ReloadModels,
+        DaemonControlCommand::SelfTest => DaemonInvocation::SelfTest,
+        DaemonControlCommand::Preload => DaemonInvocation::Preload,
+        DaemonControlCommand::Stats => DaemonInvocation::Stats,
+        DaemonControlCommand::CheckConfig => DaemonInvocation::CheckConfig,
+        DaemonControlCommand::SlowLog => DaemonInvocation::SlowLog,
         DaemonControlCommand::None if !flags.start && !flags.mode_flag_explicit => {
             DaemonInvocation::ShowUsage
         }
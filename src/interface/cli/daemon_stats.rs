@@ -0,0 +1,187 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::infrastructure::daemon::client::DaemonClient;
+use crate::infrastructure::ipc::{DaemonStats, SlowRequest};
+
+/// Reads the counters of the daemon already running at `socket_path`; never starts one.
+///
+/// # Errors
+///
+/// Returns an error if no daemon answers or it cannot report its stats.
+pub async fn fetch_daemon_stats(socket_path: &Path) -> Result<DaemonStats> {
+    DaemonClient::new_at(socket_path).await?.stats().await
+}
+
+/// Reads the slow log of the daemon already running at `socket_path`; never starts one.
+///
+/// # Errors
+///
+/// Returns an error if no daemon answers or it cannot report its slow log.
+pub async fn fetch_slow_log(socket_path: &Path) -> Result<(Option<u64>, Vec<SlowRequest>)> {
+    DaemonClient::new_at(socket_path).await?.slow_log().await
+}
+
+fn format_uptime(secs: u64) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes:02}m {seconds:02}s")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds:02}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+/// Human-readable summary of `stats`, most synthesized styles first.
+#[must_use]
+pub fn daemon_stats_lines(stats: &DaemonStats) -> Vec<String> {
+    let mut lines = vec![format!("Uptime:      {}", format_uptime(stats.uptime_secs))];
+
+    let mut styles = stats.style_syntheses.iter().collect::<Vec<_>>();
+    styles
+        .sort_by(|(left_id, left), (right_id, right)| right.cmp(left).then(left_id.cmp(right_id)));
+    let per_style = styles
+        .iter()
+        .map(|(style_id, count)| format!("style {style_id}: {count}"))
+        .collect::<Vec<_>>();
+    lines.push(if per_style.is_empty() {
+        format!("Syntheses:   {}", stats.syntheses)
+    } else {
+        format!(
+            "Syntheses:   {} ({})",
+            stats.syntheses,
+            per_style.join(", ")
+        )
+    });
+
+    if let Some(latency) = stats.latency {
+        lines.push(format!(
+            "Latency:     avg {}ms, p50 {}ms, p95 {}ms, p99 {}ms",
+            latency.average_ms, latency.p50_ms, latency.p95_ms, latency.p99_ms
+        ));
+    }
+    lines.push(format!(
+        "Models:      {} loaded, {} unloaded",
+        stats.model_loads, stats.model_unloads
+    ));
+    let lookups = stats.model_cache_hits + stats.model_cache_misses;
+    if lookups > 0 {
+        lines.push(format!(
+            "Model cache: {:.1}% hits ({} of {lookups} lookups)",
+            stats.model_cache_hits as f64 * 100.0 / lookups as f64,
+            stats.model_cache_hits
+        ));
+    }
+    lines
+}
+
+/// One line per slow request, newest first, with how long before `now` (seconds
+/// since the Unix epoch) it finished.
+#[must_use]
+pub fn slow_log_lines(threshold_ms: Option<u64>, entries: &[SlowRequest], now: u64) -> Vec<String> {
+    let Some(threshold_ms) = threshold_ms else {
+        return vec![
+            "The slow log is off; set threshold_ms under [slow_log] in daemon.toml".to_string(),
+        ];
+    };
+    if entries.is_empty() {
+        return vec![format!("No requests took {threshold_ms}ms or longer")];
+    }
+    let mut lines = vec![format!(
+        "{} request(s) took {threshold_ms}ms or longer:",
+        entries.len()
+    )];
+    lines.extend(entries.iter().rev().map(|entry| {
+        let timings = entry.timings;
+        format!(
+            "{:>9} ago  {:>6}ms  style {:<4} {:>5} chars  queue {}ms, load {}ms, query {}ms, synthesis {}ms{}",
+            format_uptime(now.saturating_sub(entry.finished_at)),
+            entry.elapsed_ms,
+            entry.style_id,
+            entry.text_chars,
+            timings.queue_ms,
+            timings.model_load_ms,
+            timings.audio_query_ms,
+            timings.synthesis_ms,
+            entry
+                .app_id
+                .as_ref()
+                .map_or_else(String::new, |app_id| format!("  [{app_id}]"))
+        )
+    }));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::ipc::LatencyStats;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn summarizes_counters_most_used_style_first() {
+        let stats = DaemonStats {
+            uptime_secs: 3723,
+            syntheses: 42,
+            style_syntheses: BTreeMap::from([(1, 2), (3, 40)]),
+            latency: Some(LatencyStats {
+                average_ms: 180,
+                p50_ms: 150,
+                p95_ms: 420,
+                p99_ms: 900,
+            }),
+            model_loads: 3,
+            model_unloads: 1,
+            model_cache_hits: 39,
+            model_cache_misses: 3,
+        };
+
+        assert_eq!(
+            daemon_stats_lines(&stats),
+            [
+                "Uptime:      1h 02m 03s",
+                "Syntheses:   42 (style 3: 40, style 1: 2)",
+                "Latency:     avg 180ms, p50 150ms, p95 420ms, p99 900ms",
+                "Models:      3 loaded, 1 unloaded",
+                "Model cache: 92.9% hits (39 of 42 lookups)",
+            ]
+        );
+        assert_eq!(
+            daemon_stats_lines(&DaemonStats::default()),
+            [
+                "Uptime:      0s",
+                "Syntheses:   0",
+                "Models:      0 loaded, 0 unloaded"
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_slow_requests_newest_first() {
+        let entry = |finished_at, elapsed_ms, app_id: Option<&str>| SlowRequest {
+            finished_at,
+            app_id: app_id.map(str::to_string),
+            style_id: 3,
+            text_chars: 1200,
+            elapsed_ms,
+            timings: crate::infrastructure::ipc::SynthesisTimings {
+                queue_ms: 10,
+                model_load_ms: 2000,
+                audio_query_ms: 300,
+                synthesis_ms: 3000,
+            },
+        };
+        let entries = [
+            entry(1000, 5400, None),
+            entry(1090, 2100, Some("claude-mcp")),
+        ];
+
+        let lines = slow_log_lines(Some(2000), &entries, 1100);
+        assert_eq!(lines[0], "2 request(s) took 2000ms or longer:");
+        assert!(lines[1].starts_with("      10s ago    2100ms  style 3"));
+        assert!(lines[1].ends_with("synthesis 3000ms  [claude-mcp]"));
+        assert!(lines[2].contains("1m 40s ago"));
+        assert!(slow_log_lines(None, &[], 0)[0].starts_with("The slow log is off"));
+    }
+}
@@ -0,0 +1,249 @@
+use anyhow::{Context, Result, anyhow};
+use std::path::{Path, PathBuf};
+
+use crate::domain::dialogue::{
+    CastVoice, Casting, DialogueLine, merge_casting, parse_dialogue_script,
+};
+use crate::domain::synthesis::Assembler;
+use crate::infrastructure::ipc::{DEFAULT_SYNTHESIS_PITCH, SynthesisOptions};
+use crate::infrastructure::state_store::{load_json_or_default, save_json, state_file_path};
+use crate::infrastructure::voicevox::{
+    Speaker, scan_speakers_from_model_files, style_id_for_voice, voice_id_for_style,
+};
+use crate::interface::audio::OutputFormat;
+use crate::interface::playback::{PlaybackRequest, emit_and_play};
+use crate::interface::synthesis::flow::{DaemonSynthesisBytesRequest, synthesize_bytes_via_daemon};
+use crate::interface::{AppOutput, StdAppOutput};
+
+const CASTING_FILE_NAME: &str = "dialogue_casting.json";
+
+pub struct DialogueRequest<'a> {
+    pub script_paths: &'a [PathBuf],
+    pub default_style_id: u32,
+    pub default_rate: f32,
+    pub output_file: Option<&'a Path>,
+    pub output_format: OutputFormat,
+    pub quiet: bool,
+    pub socket_path: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LineVoice {
+    style_id: u32,
+    rate: f32,
+    pitch: f32,
+}
+
+impl DialogueRequest<'_> {
+    fn voice_for(
+        &self,
+        casting: &Casting,
+        speakers: &[Speaker],
+        speaker: &str,
+    ) -> Result<LineVoice> {
+        let Some(cast) = casting.get(speaker) else {
+            return Ok(LineVoice {
+                style_id: self.default_style_id,
+                rate: self.default_rate,
+                pitch: DEFAULT_SYNTHESIS_PITCH,
+            });
+        };
+        let style_id = match &cast.voice {
+            None => self.default_style_id,
+            Some(CastVoice::StyleId(style_id)) => *style_id,
+            Some(CastVoice::Voice(voice)) => style_id_for_voice(speakers, voice)
+                .ok_or_else(|| anyhow!("Voice {voice} cast for {speaker} is not installed"))?,
+        };
+        Ok(LineVoice {
+            style_id,
+            rate: cast.rate.unwrap_or(self.default_rate),
+            pitch: cast.pitch.unwrap_or(DEFAULT_SYNTHESIS_PITCH),
+        })
+    }
+
+    const fn plays_audio(&self) -> bool {
+        !self.quiet && self.output_file.is_none()
+    }
+}
+
+/// Speaks one or more dialogue scripts, keeping per-character casting across runs.
+///
+/// Casting declared in a script's front-matter is merged into the stored casting so
+/// later scripts can omit it and still get the same voices. Numeric style IDs are
+/// stored as canonical voice IDs so the casting survives model renumbering.
+///
+/// # Errors
+///
+/// Returns an error if a script cannot be read or parsed, casting cannot be persisted,
+/// or synthesis/playback fails.
+pub async fn run_dialogue(request: DialogueRequest<'_>) -> Result<()> {
+    let output = StdAppOutput;
+    run_dialogue_with_output(request, &output).await
+}
+
+pub async fn run_dialogue_with_output(
+    request: DialogueRequest<'_>,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let casting_path = state_file_path(CASTING_FILE_NAME);
+    let mut casting: Casting = load_json_or_default(&casting_path)?;
+    // Metadata only; a missing models directory still allows numeric casting.
+    let speakers = scan_speakers_from_model_files().unwrap_or_default();
+    let mut rendered = Assembler::new();
+    let mut first_line = true;
+
+    for script_path in request.script_paths {
+        let source = tokio::fs::read_to_string(script_path)
+            .await
+            .with_context(|| format!("Failed to read {}", script_path.display()))?;
+        let script = parse_dialogue_script(&source)
+            .with_context(|| format!("Invalid dialogue script {}", script_path.display()))?;
+
+        if !script.cast.is_empty() {
+            casting = merge_casting(&casting, &script.cast);
+            canonicalize_casting(&mut casting, &speakers);
+            save_json(&casting_path, &casting)?;
+        }
+
+        for line in &script.lines {
+            let voice = request.voice_for(&casting, &speakers, &line.speaker)?;
+            let wav_data = synthesize_line(&request, voice, line, first_line, output).await?;
+            first_line = false;
+            if request.plays_audio() {
+                emit_and_play(PlaybackRequest {
+                    wav_data: &wav_data,
+                    output_file: None,
+                    output_format: OutputFormat::Wav,
+                    play: true,
+                    cancel_rx: None,
+                })
+                .await?;
+            }
+            if request.output_file.is_some() {
+                rendered
+                    .push_audio(&wav_data)
+                    .context("Failed to concatenate dialogue audio")?;
+            }
+        }
+    }
+
+    if let Some(output_file) = request.output_file {
+        let wav_data = rendered
+            .finish()
+            .context("Failed to concatenate dialogue audio")?;
+        tokio::fs::write(output_file, request.output_format.encode(&wav_data)?)
+            .await
+            .with_context(|| format!("Failed to write {}", output_file.display()))?;
+    }
+    Ok(())
+}
+
+/// Replaces numeric style IDs with canonical voice IDs where installed models know them.
+fn canonicalize_casting(casting: &mut Casting, speakers: &[Speaker]) {
+    for cast in casting.values_mut() {
+        if let Some(CastVoice::StyleId(style_id)) = cast.voice
+            && let Some(voice) = voice_id_for_style(speakers, style_id)
+        {
+            cast.voice = Some(CastVoice::Voice(voice));
+        }
+    }
+}
+
+async fn synthesize_line(
+    request: &DialogueRequest<'_>,
+    voice: LineVoice,
+    line: &DialogueLine,
+    ensure_models_if_missing: bool,
+    output: &dyn AppOutput,
+) -> Result<Vec<u8>> {
+    synthesize_bytes_via_daemon(
+        &DaemonSynthesisBytesRequest {
+            text: &line.text,
+            style_id: voice.style_id,
+            options: SynthesisOptions {
+                rate: voice.rate,
+                pitch: voice.pitch,
+                ..SynthesisOptions::default()
+            },
+            socket_path: &request.socket_path,
+            auto_start_daemon: true,
+            ensure_models_if_missing,
+            quiet_setup_messages: request.quiet,
+        },
+        output,
+    )
+    .await
+    .with_context(|| format!("Failed to synthesize line for {}", line.speaker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::dialogue::CastSettings;
+    use crate::domain::voice::VoiceId;
+    use crate::infrastructure::voicevox::Style;
+
+    fn installed_speakers() -> Vec<Speaker> {
+        vec![Speaker {
+            name: "四国めたん".into(),
+            speaker_uuid: "7ffcb7ce-00ec-4bdc-82cd-45a8889e43ff".into(),
+            styles: vec![Style {
+                name: "ノーマル".into(),
+                id: 2,
+                style_type: None,
+                loaded: false,
+            }]
+            .into(),
+            version: "0.1.0".into(),
+        }]
+    }
+
+    #[test]
+    fn uncast_speakers_fall_back_to_cli_defaults() {
+        let request = DialogueRequest {
+            script_paths: &[],
+            default_style_id: 3,
+            default_rate: 1.0,
+            output_file: None,
+            output_format: OutputFormat::Wav,
+            quiet: true,
+            socket_path: PathBuf::from("/tmp/unused.sock"),
+        };
+        let speakers = installed_speakers();
+        let mut casting = Casting::from([(
+            "めたん".to_owned(),
+            CastSettings {
+                voice: Some(CastVoice::StyleId(2)),
+                rate: None,
+                pitch: Some(0.1),
+            },
+        )]);
+        canonicalize_casting(&mut casting, &speakers);
+
+        assert_eq!(
+            casting["めたん"].voice,
+            Some(CastVoice::Voice(VoiceId {
+                speaker_uuid: "7ffcb7ce-00ec-4bdc-82cd-45a8889e43ff".to_owned(),
+                style_name: "ノーマル".to_owned(),
+            }))
+        );
+        assert_eq!(
+            request
+                .voice_for(&casting, &speakers, "めたん")
+                .expect("installed voice"),
+            LineVoice {
+                style_id: 2,
+                rate: 1.0,
+                pitch: 0.1,
+            }
+        );
+        assert_eq!(
+            request
+                .voice_for(&casting, &speakers, "ナレーター")
+                .expect("default voice")
+                .style_id,
+            3
+        );
+        assert!(request.voice_for(&casting, &[], "めたん").is_err());
+    }
+}
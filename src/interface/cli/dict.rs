@@ -0,0 +1,164 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::domain::user_dict::{
+    DEFAULT_WORD_PRIORITY, DictionaryWord, WordType, merge_dictionary_words, remove_dictionary_word,
+};
+use crate::infrastructure::daemon::client::DaemonClient;
+use crate::infrastructure::user_dict::{load_dictionary_words, save_dictionary_words};
+use crate::interface::AppOutput;
+
+/// A word to register with `dict add`.
+#[derive(Debug)]
+pub struct DictAddRequest<'a> {
+    pub surface: &'a str,
+    /// Reading in katakana.
+    pub pronunciation: &'a str,
+    /// Mora position of the accent drop (0 for a flat accent).
+    pub accent_type: usize,
+}
+
+/// Adds a word to the user dictionary at `dict_path`, replacing any word with the
+/// same surface, then has a running daemon reload it.
+///
+/// # Errors
+///
+/// Returns an error if the word is invalid or the dictionary cannot be read or saved.
+pub async fn run_dict_add(
+    request: &DictAddRequest<'_>,
+    dict_path: &Path,
+    socket_path: &Path,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let word = DictionaryWord {
+        surface: request.surface.to_owned(),
+        pronunciation: request.pronunciation.to_owned(),
+        accent_type: request.accent_type,
+        word_type: WordType::default(),
+        priority: DEFAULT_WORD_PRIORITY,
+    };
+    word.validate()?;
+
+    let mut words = load_dictionary_words(dict_path)?;
+    if merge_dictionary_words(&mut words, [word]) == 0 {
+        output.info(&format!("'{}' is already registered", request.surface));
+        return Ok(());
+    }
+    save_dictionary_words(dict_path, &words)?;
+    output.info(&format!(
+        "Registered '{}' as {}",
+        request.surface, request.pronunciation
+    ));
+    reload_daemon_dict(socket_path, output).await;
+    Ok(())
+}
+
+/// Removes the word with `surface` from the user dictionary at `dict_path`, then has
+/// a running daemon reload it.
+///
+/// # Errors
+///
+/// Returns an error if no such word is registered or the dictionary cannot be read
+/// or saved.
+pub async fn run_dict_remove(
+    surface: &str,
+    dict_path: &Path,
+    socket_path: &Path,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let mut words = load_dictionary_words(dict_path)?;
+    if !remove_dictionary_word(&mut words, surface) {
+        anyhow::bail!("'{surface}' is not in the user dictionary");
+    }
+    save_dictionary_words(dict_path, &words)?;
+    output.info(&format!("Removed '{surface}'"));
+    reload_daemon_dict(socket_path, output).await;
+    Ok(())
+}
+
+/// Prints every word in the user dictionary at `dict_path`.
+///
+/// # Errors
+///
+/// Returns an error if the dictionary cannot be read.
+pub fn run_dict_list(dict_path: &Path, output: &dyn AppOutput) -> Result<()> {
+    let words = load_dictionary_words(dict_path)?;
+    if words.is_empty() {
+        output.info("User dictionary is empty");
+        return Ok(());
+    }
+    for word in &words {
+        output.info(&format!(
+            "{}\t{}\t(accent: {}, priority: {})",
+            word.surface, word.pronunciation, word.accent_type, word.priority
+        ));
+    }
+    Ok(())
+}
+
+/// A daemon that is not running reads the dictionary when it starts, so only a
+/// running one is told about the change.
+async fn reload_daemon_dict(socket_path: &Path, output: &dyn AppOutput) {
+    let Ok(mut client) = DaemonClient::new_at(socket_path).await else {
+        return;
+    };
+    match client.reload_user_dict().await {
+        Ok(word_count) => output.info(&format!(
+            "Daemon reloaded the user dictionary ({word_count} words)"
+        )),
+        Err(error) => output.error(&format!(
+            "Saved, but the daemon could not reload the dictionary: {error:#}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::output::BufferAppOutput;
+
+    #[tokio::test]
+    async fn add_list_and_remove_words() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let dict_path = dir.path().join("user_dict.json");
+        let socket_path = dir.path().join("missing.sock");
+        let output = BufferAppOutput::default();
+        let request = DictAddRequest {
+            surface: "ずんだ",
+            pronunciation: "ズンダ",
+            accent_type: 1,
+        };
+
+        run_dict_add(&request, &dict_path, &socket_path, &output)
+            .await
+            .expect("add");
+        run_dict_list(&dict_path, &output).expect("list");
+        assert_eq!(
+            output.infos(),
+            [
+                "Registered 'ずんだ' as ズンダ".to_string(),
+                "ずんだ\tズンダ\t(accent: 1, priority: 5)".to_string(),
+            ]
+        );
+
+        let invalid = DictAddRequest {
+            pronunciation: "ずんだ",
+            ..request
+        };
+        assert!(
+            run_dict_add(&invalid, &dict_path, &socket_path, &output)
+                .await
+                .is_err()
+        );
+
+        run_dict_remove("ずんだ", &dict_path, &socket_path, &output)
+            .await
+            .expect("remove");
+        assert!(load_dictionary_words(&dict_path).expect("load").is_empty());
+        assert!(
+            run_dict_remove("ずんだ", &dict_path, &socket_path, &output)
+                .await
+                .is_err()
+        );
+    }
+}
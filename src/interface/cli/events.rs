@@ -0,0 +1,77 @@
+use anyhow::{Result, anyhow};
+use std::path::Path;
+
+use crate::infrastructure::daemon::client::DaemonClient;
+use crate::infrastructure::ipc::DaemonEvent;
+use crate::interface::{AppOutput, StdAppOutput};
+
+fn describe_event(event: &DaemonEvent) -> String {
+    match event {
+        DaemonEvent::SynthesisStarted {
+            style_id,
+            text_chars,
+        } => format!("synthesis started: style {style_id}, {text_chars} chars"),
+        DaemonEvent::ModelLoaded { model_id } => format!("model loaded: {model_id}"),
+        DaemonEvent::ModelUnloaded { model_id } => format!("model unloaded: {model_id}"),
+        DaemonEvent::SynthesisFinished {
+            style_id,
+            succeeded,
+        } => format!(
+            "synthesis {}: style {style_id}",
+            if *succeeded { "finished" } else { "failed" }
+        ),
+        DaemonEvent::QueueChanged { length } => format!("queue length: {length}"),
+        DaemonEvent::Lagged { missed } => format!("missed {missed} event(s)"),
+        DaemonEvent::ModelsReloaded { model_count } => {
+            format!("models reloaded: {model_count} available")
+        }
+    }
+}
+
+/// Prints daemon activity as it happens until the daemon stops.
+///
+/// # Errors
+///
+/// Returns an error if the daemon is not running or the subscription breaks.
+pub async fn run_watch_events(socket_path: &Path) -> Result<()> {
+    let output = StdAppOutput;
+    run_watch_events_with_output(socket_path, &output).await
+}
+
+pub async fn run_watch_events_with_output(
+    socket_path: &Path,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let client = DaemonClient::new_at(socket_path)
+        .await
+        .map_err(|error| anyhow!("Daemon is not running: {error}"))?;
+    let mut events = client.subscribe().await?;
+    while let Some(event) = events.next_event().await? {
+        output.info(&describe_event(&event));
+    }
+    output.info("Daemon closed the event stream");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_render_as_single_lines() {
+        assert_eq!(
+            describe_event(&DaemonEvent::SynthesisStarted {
+                style_id: 3,
+                text_chars: 5
+            }),
+            "synthesis started: style 3, 5 chars"
+        );
+        assert_eq!(
+            describe_event(&DaemonEvent::SynthesisFinished {
+                style_id: 3,
+                succeeded: false
+            }),
+            "synthesis failed: style 3"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::config::GreetingConfig;
+use crate::infrastructure::ipc::SynthesisOptions;
+use crate::infrastructure::local_time::LocalTime;
+use crate::infrastructure::state_store::{load_json_or_default, save_json, state_file_path};
+use crate::infrastructure::voicevox::Speaker;
+use crate::interface::AppOutput;
+use crate::interface::audio::OutputFormat;
+use crate::interface::interrupt::interrupt_receiver;
+use crate::interface::playback::{PlaybackRequest, emit_and_play};
+use crate::interface::synthesis::flow::connect_daemon_client_auto_start;
+
+/// Said when `[greeting]` sets no `text`.
+pub const DEFAULT_GREETING: &str = "{voice}です。音声モデル{models}個で準備ができました。";
+
+const GREETING_STATE_FILE: &str = "greeting.json";
+
+/// The day the daily greeting was last spoken, as `YYYY-MM-DD` in local time.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct GreetingState {
+    last_greeted: Option<String>,
+}
+
+/// Fills `{voice}` and `{models}` in a greeting template.
+#[must_use]
+pub fn render_greeting(template: &str, voice: &str, model_count: usize) -> String {
+    template
+        .replace("{voice}", voice)
+        .replace("{models}", &model_count.to_string())
+}
+
+/// `Speaker (style)` for `style_id`, or the bare ID when no speaker has it.
+fn voice_label(speakers: &[Speaker], style_id: u32) -> String {
+    speakers
+        .iter()
+        .find_map(|speaker| {
+            speaker
+                .styles
+                .iter()
+                .find(|style| style.id == style_id)
+                .map(|style| format!("{} ({})", speaker.name, style.name))
+        })
+        .unwrap_or_else(|| format!("style {style_id}"))
+}
+
+/// Speaks the greeting in `style_id`, starting the daemon if needed, and prints it.
+///
+/// # Errors
+///
+/// Returns an error if the daemon cannot be reached or synthesis or playback fails.
+pub async fn speak_greeting(
+    socket_path: &Path,
+    style_id: u32,
+    greeting: &GreetingConfig,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let mut client = connect_daemon_client_auto_start(socket_path).await?;
+    let speakers = client.list_speakers().await?;
+    let models = client.list_models().await?;
+    let text = render_greeting(
+        greeting.text.as_deref().unwrap_or(DEFAULT_GREETING),
+        &voice_label(&speakers, style_id),
+        models.len(),
+    );
+    output.info(&text);
+    let wav_data = client
+        .synthesize(&text, style_id, SynthesisOptions::default())
+        .await?;
+    emit_and_play(PlaybackRequest {
+        wav_data: &wav_data,
+        output_file: None,
+        output_format: OutputFormat::Wav,
+        play: true,
+        cancel_rx: interrupt_receiver(),
+    })
+    .await?;
+    Ok(())
+}
+
+/// Speaks the greeting if `[greeting]` asks for it daily and it has not been spoken
+/// today. A failed greeting is reported and left for the next run; it never stops
+/// the synthesis it precedes.
+pub async fn greet_if_first_today(socket_path: &Path, style_id: u32, output: &dyn AppOutput) {
+    let Some(greeting) = crate::config::user_config()
+        .greeting
+        .as_ref()
+        .filter(|greeting| greeting.daily)
+    else {
+        return;
+    };
+    let Some(today) = LocalTime::now().map(|now| now.date()) else {
+        return;
+    };
+    let greeted = greet_once_on(&state_file_path(GREETING_STATE_FILE), today, || {
+        speak_greeting(socket_path, style_id, greeting, output)
+    })
+    .await;
+    if let Err(error) = greeted {
+        output.error(&format!("warning: greeting failed: {error:#}"));
+    }
+}
+
+/// Runs `speak` unless the state at `path` says it already ran on `today`, and
+/// records `today` once it succeeds.
+async fn greet_once_on<F, Fut>(path: &Path, today: String, speak: F) -> Result<()>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let state: GreetingState = load_json_or_default(path).unwrap_or_default();
+    if state.last_greeted.as_deref() == Some(today.as_str()) {
+        return Ok(());
+    }
+    speak().await?;
+    save_json(
+        path,
+        &GreetingState {
+            last_greeted: Some(today),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn greeting_names_the_voice_and_model_count() {
+        assert_eq!(
+            render_greeting(DEFAULT_GREETING, "ずんだもん (ノーマル)", 3),
+            "ずんだもん (ノーマル)です。音声モデル3個で準備ができました。"
+        );
+        assert_eq!(render_greeting("Ready", "x", 1), "Ready");
+    }
+
+    #[tokio::test]
+    async fn the_greeting_is_spoken_once_per_day_and_retried_after_a_failure() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(GREETING_STATE_FILE);
+        let spoken = std::cell::Cell::new(0);
+        let greet = |today: &str| {
+            greet_once_on(&path, today.to_string(), || async {
+                spoken.set(spoken.get() + 1);
+                Ok(())
+            })
+        };
+
+        greet("2026-10-16").await.expect("first greeting");
+        greet("2026-10-16").await.expect("same day");
+        assert_eq!(spoken.get(), 1);
+
+        let failed = greet_once_on(&path, "2026-10-17".to_string(), || async {
+            anyhow::bail!("daemon unreachable")
+        })
+        .await;
+        assert!(failed.is_err());
+        greet("2026-10-17").await.expect("retry after failure");
+        assert_eq!(spoken.get(), 2);
+    }
+}
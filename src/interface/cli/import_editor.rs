@@ -0,0 +1,114 @@
+use anyhow::{Result, anyhow};
+use std::path::{Path, PathBuf};
+
+use crate::domain::preset::{VoicePreset, VoicePresets};
+use crate::domain::user_dict::{DictionaryWord, merge_dictionary_words};
+use crate::infrastructure::editor_settings::{
+    EditorPreset, editor_config_path, engine_user_dict_path, parse_editor_presets,
+    parse_engine_user_dict, read_optional,
+};
+use crate::infrastructure::paths::{user_dict_path, voice_presets_path};
+use crate::infrastructure::state_store::{load_json_or_default, save_json};
+use crate::infrastructure::voicevox::{
+    Speaker, scan_speakers_from_model_files, voice_id_for_style,
+};
+use crate::interface::{AppOutput, StdAppOutput};
+
+/// Overrides for where the editor's files are read from.
+#[derive(Debug, Default)]
+pub struct ImportEditorSettingsRequest<'a> {
+    pub editor_config: Option<&'a Path>,
+    pub user_dict: Option<&'a Path>,
+}
+
+/// Imports presets and user dictionary words from the official VOICEVOX editor.
+///
+/// Presets are stored by name in `presets.json` in the config directory, with the
+/// voice each one is the editor default for recorded as a canonical voice ID.
+/// Dictionary words are merged into `user_dict.json` in the data directory by
+/// surface form. A default location that does not exist is skipped; an explicit
+/// path that does not exist is an error.
+///
+/// # Errors
+///
+/// Returns an error if no editor settings are found, a file is malformed, or the
+/// converted settings cannot be saved.
+pub fn run_import_editor_settings(request: &ImportEditorSettingsRequest<'_>) -> Result<()> {
+    let output = StdAppOutput;
+    run_import_editor_settings_with_output(request, &output)
+}
+
+pub fn run_import_editor_settings_with_output(
+    request: &ImportEditorSettingsRequest<'_>,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let config = read_source(request.editor_config, editor_config_path())?;
+    let dictionary = read_source(request.user_dict, engine_user_dict_path())?;
+    if config.is_none() && dictionary.is_none() {
+        return Err(anyhow!(
+            "No VOICEVOX editor settings found (use --editor-config or --user-dict to point at them)"
+        ));
+    }
+
+    if let Some((path, source)) = config {
+        let presets = parse_editor_presets(&source)?;
+        let speakers = scan_speakers_from_model_files().unwrap_or_default();
+        let target = voice_presets_path();
+        let mut stored: VoicePresets = load_json_or_default(&target)?;
+        let imported = presets.len();
+        for preset in presets {
+            let (name, preset) = convert_preset(preset, &speakers);
+            stored.insert(name, preset);
+        }
+        save_json(&target, &stored)?;
+        output.info(&format!(
+            "Imported {imported} presets from {} into {}",
+            path.display(),
+            target.display()
+        ));
+    }
+
+    if let Some((path, source)) = dictionary {
+        let words = parse_engine_user_dict(&source)?;
+        let target = user_dict_path();
+        let mut stored: Vec<DictionaryWord> = load_json_or_default(&target)?;
+        let changed = merge_dictionary_words(&mut stored, words);
+        save_json(&target, &stored)?;
+        output.info(&format!(
+            "Imported {changed} dictionary words from {} into {}",
+            path.display(),
+            target.display()
+        ));
+    }
+    Ok(())
+}
+
+fn read_source(
+    explicit: Option<&Path>,
+    default: Option<PathBuf>,
+) -> Result<Option<(PathBuf, String)>> {
+    if let Some(path) = explicit {
+        let source =
+            read_optional(path)?.ok_or_else(|| anyhow!("{} does not exist", path.display()))?;
+        return Ok(Some((path.to_path_buf(), source)));
+    }
+    let Some(path) = default else {
+        return Ok(None);
+    };
+    Ok(read_optional(&path)?.map(|source| (path, source)))
+}
+
+/// Attaches the canonical voice ID when the preset's default voice is installed.
+fn convert_preset(preset: EditorPreset, speakers: &[Speaker]) -> (String, VoicePreset) {
+    let voice = preset.default_for.and_then(|(speaker_uuid, style_id)| {
+        voice_id_for_style(speakers, style_id)
+            .filter(|voice| voice.speaker_uuid.eq_ignore_ascii_case(&speaker_uuid))
+    });
+    (
+        preset.name,
+        VoicePreset {
+            voice,
+            ..preset.preset
+        },
+    )
+}
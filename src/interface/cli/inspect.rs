@@ -1,26 +1,42 @@
 use anyhow::Result;
+use serde::Serialize;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::domain::voice_similarity::{SimilarityReason, StyleEntry, suggest_similar};
 use crate::infrastructure::daemon::client::DaemonClient;
+use crate::infrastructure::ipc::DaemonStats;
+use crate::infrastructure::speaker_cache::{
+    load_fresh_speakers, remember_speakers, speaker_cache_path,
+};
+use crate::infrastructure::usage_stats::{load_usage_stats, usage_stats_path};
 use crate::infrastructure::voicevox::{AvailableModel, Speaker, scan_available_models};
+use crate::interface::audio::{OutputDevice, list_output_devices};
+use crate::interface::cli::daemon_stats::{daemon_stats_lines, fetch_daemon_stats};
 use crate::interface::synthesis::flow::connect_daemon_client_auto_start;
 use crate::interface::{AppOutput, StdAppOutput};
 
+/// Marks voices that will respond without a model load delay.
+const LOADED_MARKER: &str = " [loaded]";
+
 const NO_MODELS_MESSAGE: &str =
     "No voice models found. Please run 'voicevox-setup' to download required resources.";
 
+const ENABLE_STATS_HINT: &str =
+    "Set 'usage_stats = true' in daemon.toml to record usage statistics locally.";
+
 fn format_speaker_block(speaker: &Speaker, style_to_model: Option<&HashMap<u32, u32>>) -> String {
     let style_lines = speaker
         .styles
         .iter()
         .flat_map(|style| {
+            let marker = if style.loaded { LOADED_MARKER } else { "" };
             let main_line = match style_to_model.and_then(|map| map.get(&style.id)) {
                 Some(model_id) => format!(
-                    "    {} (Model: {model_id}, Style ID: {})",
+                    "    {} (Model: {model_id}, Style ID: {}){marker}",
                     style.name, style.id
                 ),
-                None => format!("    {} (Style ID: {})", style.name, style.id),
+                None => format!("    {} (Style ID: {}){marker}", style.name, style.id),
             };
 
             std::iter::once(main_line).chain(
@@ -33,7 +49,14 @@ fn format_speaker_block(speaker: &Speaker, style_to_model: Option<&HashMap<u32,
         .collect::<Vec<_>>()
         .join("\n");
 
-    format!("  {}\n{style_lines}", speaker.name)
+    if speaker.speaker_uuid.is_empty() {
+        format!("  {}\n{style_lines}", speaker.name)
+    } else {
+        format!(
+            "  {} (UUID: {})\n{style_lines}",
+            speaker.name, speaker.speaker_uuid
+        )
+    }
 }
 
 fn format_speakers_output(
@@ -59,6 +82,7 @@ struct ModelView {
     model_id: u32,
     file_path: String,
     default_style_id: Option<u32>,
+    loaded: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -69,6 +93,107 @@ struct InstalledModelView {
     size_kb: Option<u64>,
 }
 
+#[derive(Debug, Serialize)]
+struct StyleJson<'a> {
+    name: &'a str,
+    id: u32,
+    #[serde(rename = "type")]
+    style_type: Option<&'a str>,
+    model_id: Option<u32>,
+    loaded: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct SpeakerJson<'a> {
+    name: &'a str,
+    speaker_uuid: &'a str,
+    styles: Vec<StyleJson<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ModelJson {
+    model_id: u32,
+    path: PathBuf,
+    size_bytes: Option<u64>,
+    default_style_id: Option<u32>,
+    loaded: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct StatusJson {
+    version: &'static str,
+    onnxruntime: Option<PathBuf>,
+    dictionary: Option<PathBuf>,
+    models_dir: Option<PathBuf>,
+    models: Vec<ModelJson>,
+    /// Why the models could not be scanned, if they could not.
+    models_error: Option<String>,
+    /// Counters of the running daemon; `None` when none is running.
+    daemon: Option<DaemonStats>,
+}
+
+fn speakers_json<'a>(
+    speakers: &'a [Speaker],
+    style_to_model: Option<&HashMap<u32, u32>>,
+) -> Vec<SpeakerJson<'a>> {
+    speakers
+        .iter()
+        .map(|speaker| SpeakerJson {
+            name: &speaker.name,
+            speaker_uuid: &speaker.speaker_uuid,
+            styles: speaker
+                .styles
+                .iter()
+                .map(|style| StyleJson {
+                    name: &style.name,
+                    id: style.id,
+                    style_type: style.style_type.as_deref(),
+                    model_id: style_to_model.and_then(|map| map.get(&style.id).copied()),
+                    loaded: style.loaded,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn models_json(models: &[AvailableModel]) -> Vec<ModelJson> {
+    models
+        .iter()
+        .map(|model| ModelJson {
+            model_id: model.model_id,
+            path: model.file_path.clone(),
+            size_bytes: std::fs::metadata(&model.file_path)
+                .ok()
+                .map(|metadata| metadata.len()),
+            default_style_id: default_style_id(model),
+            loaded: model.loaded,
+        })
+        .collect()
+}
+
+fn print_json(value: &impl Serialize, output: &dyn AppOutput) -> Result<()> {
+    output.info(&serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn print_speakers_json(
+    speakers: &[Speaker],
+    style_to_model: Option<&HashMap<u32, u32>>,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    print_json(
+        &serde_json::json!({ "speakers": speakers_json(speakers, style_to_model) }),
+        output,
+    )
+}
+
+fn print_models_json(models: &[AvailableModel], output: &dyn AppOutput) -> Result<()> {
+    print_json(
+        &serde_json::json!({ "models": models_json(models) }),
+        output,
+    )
+}
+
 fn missing_status_lines(name: &str) -> [String; 2] {
     [
         format!("{name}: Not found"),
@@ -83,7 +208,11 @@ fn list_models_lines(models: &[ModelView]) -> Vec<String> {
 
     let mut lines = vec!["Available voice models:".to_string()];
     for model in models {
-        lines.push(format!("  Model {} ({})", model.model_id, model.file_path));
+        let marker = if model.loaded { LOADED_MARKER } else { "" };
+        lines.push(format!(
+            "  Model {} ({}){marker}",
+            model.model_id, model.file_path
+        ));
         lines.push(format!(
             "    Usage: --model {} or --speaker-id <STYLE_ID>",
             model.model_id
@@ -123,27 +252,33 @@ fn print_no_models_message(output: &dyn AppOutput) {
     output.info(NO_MODELS_MESSAGE);
 }
 
-fn handle_missing_models_error(error: anyhow::Error, output: &dyn AppOutput) -> Result<()> {
+/// Treats a failed daemon connection as "nothing installed" when the models
+/// directory itself is missing.
+fn ensure_models_missing(error: anyhow::Error) -> Result<()> {
     if crate::infrastructure::paths::find_models_dir().is_err() {
-        print_no_models_message(output);
         return Ok(());
     }
 
     Err(error)
 }
 
+fn default_style_id(model: &AvailableModel) -> Option<u32> {
+    model
+        .speakers
+        .iter()
+        .flat_map(|speaker| speaker.styles.iter())
+        .map(|style| style.id)
+        .min()
+}
+
 fn print_list_models_output(models: &[AvailableModel], output: &dyn AppOutput) {
     let views = models
         .iter()
         .map(|model| ModelView {
             model_id: model.model_id,
             file_path: model.file_path.display().to_string(),
-            default_style_id: model
-                .speakers
-                .iter()
-                .flat_map(|speaker| speaker.styles.iter())
-                .map(|style| style.id)
-                .min(),
+            default_style_id: default_style_id(model),
+            loaded: model.loaded,
         })
         .collect::<Vec<_>>();
     for line in list_models_lines(&views) {
@@ -151,22 +286,38 @@ fn print_list_models_output(models: &[AvailableModel], output: &dyn AppOutput) {
     }
 }
 
-pub async fn run_list_models_command(socket_path: &Path) -> Result<()> {
+pub async fn run_list_models_command(socket_path: &Path, json: bool) -> Result<()> {
     let output = StdAppOutput;
-    run_list_models_command_with_output(socket_path, &output).await
+    run_list_models_command_with_output(socket_path, json, &output).await
 }
 
+/// Lists the daemon's voice models, as one JSON object when `json` is set.
+///
+/// # Errors
+///
+/// Returns an error if the daemon cannot list its models.
 pub async fn run_list_models_command_with_output(
     socket_path: &Path,
+    json: bool,
     output: &dyn AppOutput,
 ) -> Result<()> {
     match connect_daemon_client_auto_start(socket_path).await {
         Ok(mut client) => {
             let models = client.list_models().await?;
+            if json {
+                return print_models_json(&models, output);
+            }
             print_list_models_output(&models, output);
             Ok(())
         }
-        Err(error) => handle_missing_models_error(error, output),
+        Err(error) => {
+            ensure_models_missing(error)?;
+            if json {
+                return print_models_json(&[], output);
+            }
+            print_no_models_message(output);
+            Ok(())
+        }
     }
 }
 
@@ -210,12 +361,49 @@ fn print_status_dictionary(output: &dyn AppOutput) {
     }
 }
 
-pub fn run_status_command() {
+/// Prints the installation status, plus the counters of the daemon at
+/// `socket_path` if one is already running.
+///
+/// # Errors
+///
+/// Returns an error if the JSON cannot be serialized.
+pub async fn run_status_command(socket_path: &Path, json: bool) -> Result<()> {
+    let daemon = fetch_daemon_stats(socket_path).await.ok();
     let output = StdAppOutput;
-    run_status_command_with_output(&output);
+    run_status_command_with_output(json, daemon, &output)
+}
+
+fn status_json(daemon: Option<DaemonStats>) -> StatusJson {
+    let (models, models_error) = match scan_available_models() {
+        Ok(models) => (models_json(&models), None),
+        Err(error) => (Vec::new(), Some(error.to_string())),
+    };
+    StatusJson {
+        version: env!("CARGO_PKG_VERSION"),
+        onnxruntime: crate::infrastructure::paths::find_onnxruntime().ok(),
+        dictionary: crate::infrastructure::paths::find_openjtalk_dict().ok(),
+        models_dir: crate::infrastructure::paths::find_models_dir().ok(),
+        models,
+        models_error,
+        daemon,
+    }
 }
 
-pub fn run_status_command_with_output(output: &dyn AppOutput) {
+/// Prints where the runtime, models, and dictionary are installed and the
+/// `daemon` counters if given, as one JSON object when `json` is set.
+///
+/// # Errors
+///
+/// Returns an error if the JSON cannot be serialized.
+pub fn run_status_command_with_output(
+    json: bool,
+    daemon: Option<DaemonStats>,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    if json {
+        return print_json(&status_json(daemon), output);
+    }
+
     output.info("VOICEVOX CLI Installation Status");
     output.info("=====================================");
     output.info(&format!("Application: v{}", env!("CARGO_PKG_VERSION")));
@@ -239,6 +427,63 @@ pub fn run_status_command_with_output(output: &dyn AppOutput) {
             output.error(&format!("Error scanning models: {error}"));
         }
     }
+
+    match daemon {
+        Some(stats) => {
+            output.info("Daemon: running");
+            for line in daemon_stats_lines(&stats) {
+                output.info(&format!("  {line}"));
+            }
+        }
+        None => output.info("Daemon: not running"),
+    }
+    Ok(())
+}
+
+/// Prints the output devices `--audio-device` accepts, marking the default, as a
+/// JSON array when `json` is set.
+///
+/// # Errors
+///
+/// Returns an error if the audio host cannot list its devices.
+pub fn run_list_audio_devices_command(json: bool, output: &dyn AppOutput) -> Result<()> {
+    print_audio_devices(&list_output_devices()?, json, output)
+}
+
+fn print_audio_devices(devices: &[OutputDevice], json: bool, output: &dyn AppOutput) -> Result<()> {
+    if json {
+        output.info(&serde_json::to_string_pretty(devices)?);
+    } else if devices.is_empty() {
+        output.info("No audio output devices found");
+    } else {
+        output.info("Audio output devices:");
+        for device in devices {
+            let marker = if device.default { " (default)" } else { "" };
+            output.info(&format!("  {}{marker}", device.name));
+        }
+    }
+    Ok(())
+}
+
+pub fn run_stats_command() -> Result<()> {
+    run_stats_command_with_output(&usage_stats_path(), &StdAppOutput)
+}
+
+/// Prints the usage statistics the daemon recorded in `path`.
+///
+/// # Errors
+///
+/// Returns an error if the statistics file exists but cannot be read.
+pub fn run_stats_command_with_output(path: &Path, output: &dyn AppOutput) -> Result<()> {
+    let stats = load_usage_stats(path)?;
+    output.info(&format!("Usage statistics ({}):", path.display()));
+    for line in stats.lines() {
+        output.info(&line);
+    }
+    if stats.voices.is_empty() {
+        output.info(ENABLE_STATS_HINT);
+    }
+    Ok(())
 }
 
 fn print_speakers(speakers: &[Speaker], output: &dyn AppOutput) {
@@ -249,33 +494,128 @@ fn print_speakers(speakers: &[Speaker], output: &dyn AppOutput) {
     ));
 }
 
-pub async fn run_list_speakers_command(socket_path: &Path) -> Result<()> {
+pub async fn run_list_speakers_command(socket_path: &Path, json: bool) -> Result<()> {
     let output = StdAppOutput;
-    run_list_speakers_command_with_output(socket_path, &output).await
+    run_list_speakers_command_with_output(socket_path, json, &output).await
 }
 
+/// Lists speakers with their styles and models, as one JSON object when `json`
+/// is set.
+///
+/// # Errors
+///
+/// Returns an error if the daemon cannot list its speakers.
 pub async fn run_list_speakers_command_with_output(
     socket_path: &Path,
+    json: bool,
     output: &dyn AppOutput,
 ) -> Result<()> {
-    if let Ok(mut client) = DaemonClient::new_at(socket_path).await {
-        let (speakers, style_to_model) = client.list_speakers_with_models().await?;
+    if let Some(cache) = load_fresh_speakers(&speaker_cache_path(), socket_path) {
+        if json {
+            return print_speakers_json(&cache.speakers, Some(&cache.style_to_model), output);
+        }
         output.info(&format_speakers_output(
-            "All available speakers and styles from daemon:",
-            &speakers,
-            Some(&style_to_model),
+            "All available speakers and styles from daemon (cached):",
+            &cache.speakers,
+            Some(&cache.style_to_model),
         ));
         return Ok(());
     }
 
+    if let Ok(mut client) = DaemonClient::new_at(socket_path).await {
+        let (speakers, style_to_model) = client.list_speakers_with_models().await?;
+        if json {
+            print_speakers_json(&speakers, Some(&style_to_model), output)?;
+        } else {
+            output.info(&format_speakers_output(
+                "All available speakers and styles from daemon:",
+                &speakers,
+                Some(&style_to_model),
+            ));
+        }
+        remember_speakers(socket_path, speakers, style_to_model);
+        return Ok(());
+    }
+
     match connect_daemon_client_auto_start(socket_path).await {
         Ok(mut client) => {
-            let speakers = client.list_speakers().await?;
-            print_speakers(&speakers, output);
+            let (speakers, style_to_model) = client.list_speakers_with_models().await?;
+            if json {
+                print_speakers_json(&speakers, Some(&style_to_model), output)?;
+            } else {
+                print_speakers(&speakers, output);
+            }
+            remember_speakers(socket_path, speakers, style_to_model);
             Ok(())
         }
-        Err(error) => handle_missing_models_error(error, output),
+        Err(error) => {
+            ensure_models_missing(error)?;
+            if json {
+                return print_speakers_json(&[], None, output);
+            }
+            print_no_models_message(output);
+            Ok(())
+        }
+    }
+}
+
+fn style_entries(speakers: &[Speaker]) -> Vec<StyleEntry> {
+    speakers
+        .iter()
+        .flat_map(|speaker| {
+            speaker.styles.iter().map(|style| StyleEntry {
+                speaker_name: speaker.name.to_string(),
+                style_name: style.name.to_string(),
+                style_id: style.id,
+                style_type: style.style_type.as_ref().map(ToString::to_string),
+            })
+        })
+        .collect()
+}
+
+fn suggest_similar_lines(speakers: &[Speaker], style_id: u32, count: usize) -> Result<Vec<String>> {
+    let entries = style_entries(speakers);
+    let suggestions = suggest_similar(&entries, style_id, count)?;
+    let Some(target) = entries.iter().find(|entry| entry.style_id == style_id) else {
+        return Ok(Vec::new());
+    };
+    let mut lines = vec![format!(
+        "Styles similar to {} {} (Style ID: {}):",
+        target.speaker_name, target.style_name, target.style_id
+    )];
+    if suggestions.is_empty() {
+        lines.push("  No similar styles installed.".to_string());
+    }
+    for suggestion in suggestions {
+        let reason = match suggestion.reason {
+            SimilarityReason::SameSpeaker => "same speaker".to_string(),
+            SimilarityReason::SameCategory(category) => format!("also {}", category.label()),
+        };
+        lines.push(format!(
+            "  {} {} (Style ID: {}) - {reason}",
+            suggestion.entry.speaker_name, suggestion.entry.style_name, suggestion.entry.style_id
+        ));
+    }
+    Ok(lines)
+}
+
+/// Prints up to `count` installed styles similar to `style_id`.
+///
+/// # Errors
+///
+/// Returns an error if the daemon cannot be reached or `style_id` is not installed.
+pub async fn run_suggest_similar_command(
+    socket_path: &Path,
+    style_id: u32,
+    count: usize,
+) -> Result<()> {
+    let output = StdAppOutput;
+    let mut client = connect_daemon_client_auto_start(socket_path).await?;
+    let speakers = client.list_speakers().await?;
+    for line in suggest_similar_lines(&speakers, style_id, count)? {
+        output.info(&line);
     }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -285,6 +625,68 @@ mod tests {
     use crate::interface::output::BufferAppOutput;
     use std::path::PathBuf;
 
+    #[test]
+    fn stats_command_hints_at_opting_in_when_nothing_is_recorded() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("usage_stats.json");
+        let output = BufferAppOutput::default();
+
+        run_stats_command_with_output(&path, &output).expect("empty stats");
+
+        assert_eq!(
+            output.infos()[1..],
+            [
+                "No usage recorded yet".to_string(),
+                ENABLE_STATS_HINT.to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn audio_devices_mark_the_default() {
+        let devices = [("Built-in Output", true), ("USB Headset", false)].map(|(name, default)| {
+            OutputDevice {
+                name: name.to_string(),
+                default,
+            }
+        });
+        let output = BufferAppOutput::default();
+
+        print_audio_devices(&devices, false, &output).expect("text");
+        assert_eq!(
+            output.infos(),
+            [
+                "Audio output devices:".to_string(),
+                "  Built-in Output (default)".to_string(),
+                "  USB Headset".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn status_ends_with_the_running_daemon_counters() {
+        let output = BufferAppOutput::default();
+        run_status_command_with_output(false, None, &output).expect("status");
+        assert_eq!(
+            output.infos().last().map(String::as_str),
+            Some("Daemon: not running")
+        );
+
+        let output = BufferAppOutput::default();
+        run_status_command_with_output(false, Some(DaemonStats::default()), &output)
+            .expect("status");
+        let infos = output.infos();
+        assert_eq!(
+            infos[infos.len() - 4..],
+            [
+                "Daemon: running",
+                "  Uptime:      0s",
+                "  Syntheses:   0",
+                "  Models:      0 loaded, 0 unloaded",
+            ]
+        );
+    }
+
     #[test]
     fn print_list_models_output_shows_no_models_message() {
         let output = BufferAppOutput::default();
@@ -308,25 +710,91 @@ mod tests {
                         name: "Normal".into(),
                         id: 42,
                         style_type: None,
+                        loaded: false,
                     },
                     Style {
                         name: "Happy".into(),
                         id: 7,
                         style_type: Some("talk".into()),
+                        loaded: false,
                     },
                 ]
                 .into(),
                 version: String::new().into(),
             }]
             .into(),
+            loaded: true,
         }];
 
         print_list_models_output(&models, &output);
 
         let infos = output.infos().join("\n");
         assert!(infos.contains("Available voice models:"));
-        assert!(infos.contains("Model 12 (/tmp/12.vvm)"));
+        assert!(infos.contains("Model 12 (/tmp/12.vvm) [loaded]"));
         assert!(infos.contains("Default style ID (auto-selected by --model): 7"));
         assert!(infos.contains("Use --list-speakers for detailed speaker information"));
     }
+
+    #[test]
+    fn speakers_json_carries_styles_and_their_models() {
+        let speakers = vec![Speaker {
+            name: "Test Speaker".into(),
+            speaker_uuid: "uuid-1".to_string().into(),
+            styles: vec![Style {
+                name: "Normal".into(),
+                id: 3,
+                style_type: Some("talk".into()),
+                loaded: true,
+            }]
+            .into(),
+            version: String::new().into(),
+        }];
+        let output = BufferAppOutput::default();
+
+        print_speakers_json(&speakers, Some(&HashMap::from([(3, 1)])), &output).expect("json");
+
+        let report: serde_json::Value =
+            serde_json::from_str(&output.infos().join("\n")).expect("valid json");
+        assert_eq!(
+            report,
+            serde_json::json!({
+                "speakers": [{
+                    "name": "Test Speaker",
+                    "speaker_uuid": "uuid-1",
+                    "styles": [{
+                        "name": "Normal",
+                        "id": 3,
+                        "type": "talk",
+                        "model_id": 1,
+                        "loaded": true,
+                    }],
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn models_json_reports_paths_and_sizes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("4.vvm");
+        std::fs::write(&path, b"model").expect("write model");
+        let models = vec![AvailableModel {
+            model_id: 4,
+            file_path: path.clone(),
+            speakers: Vec::new().into(),
+            loaded: false,
+        }];
+        let output = BufferAppOutput::default();
+
+        print_models_json(&models, &output).expect("json");
+
+        let report: serde_json::Value =
+            serde_json::from_str(&output.infos().join("\n")).expect("valid json");
+        assert_eq!(report["models"][0]["path"], path.display().to_string());
+        assert_eq!(report["models"][0]["size_bytes"], 5);
+        assert_eq!(
+            report["models"][0]["default_style_id"],
+            serde_json::Value::Null
+        );
+    }
 }
@@ -0,0 +1,229 @@
+use anyhow::{Context, Result, bail, ensure};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::domain::checksums::{parse_checksums, sha256_hex};
+use crate::domain::synthesis::loudness::{WavSummary, summarize_wav};
+use crate::interface::cli::batch::{BatchReport, CHECKSUMS_SUFFIX, RowReport, RowStatus};
+use crate::interface::{AppOutput, StdAppOutput};
+
+pub struct InspectWavRequest<'a> {
+    pub wav: &'a Path,
+    /// Batch report (`<batch>.report.json`) the WAV is expected to appear in.
+    pub manifest: Option<&'a Path>,
+}
+
+/// Prints a produced WAV's format, duration, and levels, and checks it against the
+/// batch report that listed it.
+///
+/// # Errors
+///
+/// Returns an error if the WAV cannot be read or measured, or when a manifest is
+/// given and the WAV is missing from it, was not rendered successfully, or no
+/// longer matches its recorded checksum.
+pub fn run_inspect_wav(request: &InspectWavRequest<'_>) -> Result<()> {
+    let output = StdAppOutput;
+    run_inspect_wav_with_output(request, &output)
+}
+
+pub fn run_inspect_wav_with_output(
+    request: &InspectWavRequest<'_>,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let wav = std::fs::read(request.wav)
+        .with_context(|| format!("Failed to read {}", request.wav.display()))?;
+    let summary = summarize_wav(&wav)
+        .with_context(|| format!("Failed to inspect {}", request.wav.display()))?;
+    output.info(&format!("File: {}", request.wav.display()));
+    for line in summary_lines(&summary) {
+        output.info(&line);
+    }
+
+    if let Some(manifest) = request.manifest {
+        check_manifest(request.wav, &wav, manifest, output)
+            .with_context(|| format!("{} failed validation", request.wav.display()))?;
+    }
+    Ok(())
+}
+
+fn format_level(dbfs: f64) -> String {
+    if dbfs.is_finite() {
+        format!("{dbfs:.1} dBFS")
+    } else {
+        "silent".to_string()
+    }
+}
+
+fn format_seconds(duration: Duration) -> String {
+    format!("{:.3}s", duration.as_secs_f64())
+}
+
+fn summary_lines(summary: &WavSummary) -> Vec<String> {
+    let mut lines = vec![
+        format!(
+            "Format: {} Hz, {} channel(s), {}-bit PCM",
+            summary.sample_rate, summary.channels, summary.bits_per_sample
+        ),
+        format!("Duration: {}", format_seconds(summary.duration)),
+        format!(
+            "Level: peak {}, RMS {}",
+            format_level(summary.peak_dbfs),
+            format_level(summary.rms_dbfs)
+        ),
+        format!(
+            "Edge silence: {} leading, {} trailing",
+            format_seconds(summary.leading_silence),
+            format_seconds(summary.trailing_silence)
+        ),
+    ];
+    if summary.clipped_samples > 0 {
+        lines.push(format!(
+            "Warning: {} sample(s) at full scale; the audio may be clipped",
+            summary.clipped_samples
+        ));
+    }
+    lines
+}
+
+fn check_manifest(
+    wav_path: &Path,
+    wav: &[u8],
+    manifest: &Path,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let report: BatchReport = serde_json::from_str(
+        &std::fs::read_to_string(manifest)
+            .with_context(|| format!("Failed to read {}", manifest.display()))?,
+    )
+    .with_context(|| format!("{} is not a batch report", manifest.display()))?;
+    let row = find_row(&report, wav_path)
+        .with_context(|| format!("Not listed in {}", manifest.display()))?;
+
+    output.info(&format!("Batch row: line {}", row.line));
+    if let Some(voice) = &row.voice {
+        output.info(&format!("Voice: {voice}"));
+    }
+    if row.status != RowStatus::Succeeded {
+        bail!(
+            "Row {} did not render: {}",
+            row.line,
+            row.error.as_deref().unwrap_or("no reason recorded")
+        );
+    }
+
+    let checksums_path = report.source.with_extension(CHECKSUMS_SUFFIX);
+    if !checksums_path.exists() {
+        output.info(&format!(
+            "Checksum: not checked ({} not found)",
+            checksums_path.display()
+        ));
+        return Ok(());
+    }
+    let checksums = parse_checksums(
+        &std::fs::read_to_string(&checksums_path)
+            .with_context(|| format!("Failed to read {}", checksums_path.display()))?,
+    )?;
+    let recorded = checksums
+        .get(&row.output)
+        .with_context(|| format!("No checksum recorded in {}", checksums_path.display()))?;
+    ensure!(
+        *recorded == sha256_hex(wav),
+        "Contents changed since the batch wrote them ({})",
+        checksums_path.display()
+    );
+    output.info("Checksum: OK");
+    Ok(())
+}
+
+/// Finds the report row for `wav_path`, comparing resolved paths when the report
+/// was written relative to another directory.
+fn find_row<'a>(report: &'a BatchReport, wav_path: &Path) -> Option<&'a RowReport> {
+    let resolved = wav_path.canonicalize().ok();
+    report.rows.iter().find(|row| {
+        let output = Path::new(&row.output);
+        output == wav_path || (resolved.is_some() && output.canonicalize().ok() == resolved)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::checksums::checksum_line;
+    use crate::domain::synthesis::wav::make_wav;
+    use crate::interface::output::BufferAppOutput;
+
+    fn write_batch(dir: &Path, wav: &[u8], status: &str) -> (std::path::PathBuf, String) {
+        let output = dir.join("hello.wav").display().to_string();
+        std::fs::write(&output, wav).expect("wav");
+        let report = dir.join("lines.report.json");
+        std::fs::write(
+            &report,
+            serde_json::json!({
+                "source": dir.join("lines.csv"),
+                "total": 1,
+                "succeeded": 1,
+                "failed": 0,
+                "rows": [{"line": 2, "output": output, "status": status, "voice": "uuid/ノーマル"}],
+            })
+            .to_string(),
+        )
+        .expect("report");
+        std::fs::write(
+            dir.join("lines.sha256"),
+            checksum_line(&sha256_hex(wav), &output),
+        )
+        .expect("checksums");
+        (report, output)
+    }
+
+    #[test]
+    fn validates_wav_against_batch_report() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let wav = make_wav(&[0, 0, 0, 64, 0, 0], 1, 1000, 16);
+        let (report, output) = write_batch(dir.path(), &wav, "succeeded");
+        let buffer = BufferAppOutput::default();
+
+        run_inspect_wav_with_output(
+            &InspectWavRequest {
+                wav: Path::new(&output),
+                manifest: Some(&report),
+            },
+            &buffer,
+        )
+        .expect("valid asset");
+        let infos = buffer.infos();
+        assert!(infos.contains(&"Duration: 0.003s".to_string()));
+        assert!(infos.contains(&"Voice: uuid/ノーマル".to_string()));
+        assert!(infos.contains(&"Checksum: OK".to_string()));
+
+        std::fs::write(&output, make_wav(&[0, 0], 1, 1000, 16)).expect("overwrite");
+        assert!(
+            run_inspect_wav_with_output(
+                &InspectWavRequest {
+                    wav: Path::new(&output),
+                    manifest: Some(&report),
+                },
+                &buffer,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_rows_that_did_not_render() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let wav = make_wav(&[0, 0], 1, 1000, 16);
+        let (report, output) = write_batch(dir.path(), &wav, "failed");
+
+        assert!(
+            run_inspect_wav_with_output(
+                &InspectWavRequest {
+                    wav: Path::new(&output),
+                    manifest: Some(&report),
+                },
+                &BufferAppOutput::default(),
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::infrastructure::fifo::ensure_fifo;
+use crate::infrastructure::ipc::{DEFAULT_SYNTHESIS_PITCH, SynthesisOptions};
+use crate::interface::audio::OutputFormat;
+use crate::interface::cli::daemon_error::format_daemon_client_error_for_cli;
+use crate::interface::playback::{PlaybackOutcome, PlaybackRequest, emit_and_play};
+use crate::interface::synthesis::flow::{
+    DaemonSynthesisBytesRequest, SynthesisFlowOutcome, synthesize_bytes_via_daemon_cancellable,
+    validate_text_synthesis_request,
+};
+use crate::interface::{AppOutput, StdAppOutput};
+
+const INTERRUPT_REASON: &str = "interrupted by newer input";
+const LINE_CHANNEL_CAPACITY: usize = 64;
+
+/// Listen-mode input events. A blank line silences the current utterance
+/// without starting a new one, matching the "stop" convention of speech servers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ListenEvent {
+    Speak(String),
+    Silence,
+}
+
+impl ListenEvent {
+    fn from_line(line: &str) -> Self {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            Self::Silence
+        } else {
+            Self::Speak(trimmed.to_owned())
+        }
+    }
+}
+
+pub struct ListenRequest<'a> {
+    pub fifo_path: &'a Path,
+    pub style_id: u32,
+    pub rate: f32,
+    pub socket_path: PathBuf,
+}
+
+/// Speaks each line written to a FIFO, interrupting any utterance still in flight.
+///
+/// Intended as a companion for screen readers and scripts that emit short,
+/// frequently superseded announcements.
+///
+/// # Errors
+///
+/// Returns an error if the FIFO cannot be created or the voice settings are invalid.
+pub async fn run_listen_mode(request: ListenRequest<'_>) -> Result<()> {
+    let output = StdAppOutput;
+    run_listen_mode_with_output(request, &output).await
+}
+
+pub async fn run_listen_mode_with_output(
+    request: ListenRequest<'_>,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    validate_text_synthesis_request("-", request.style_id, request.rate, DEFAULT_SYNTHESIS_PITCH)?;
+    ensure_fifo(request.fifo_path)?;
+    output.info(&format!(
+        "Listening for text on {}",
+        request.fifo_path.display()
+    ));
+
+    let (line_tx, mut line_rx) = mpsc::channel(LINE_CHANNEL_CAPACITY);
+    let reader = tokio::spawn(read_fifo_lines(request.fifo_path.to_path_buf(), line_tx));
+
+    let mut pending = None;
+    loop {
+        let event = match pending.take() {
+            Some(event) => event,
+            None => match line_rx.recv().await {
+                Some(event) => event,
+                None => break,
+            },
+        };
+        let event = take_latest_event(event, &mut line_rx);
+
+        let ListenEvent::Speak(text) = event else {
+            continue;
+        };
+
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+        let utterance = speak_utterance(&request, &text, cancel_rx, output);
+        tokio::pin!(utterance);
+
+        tokio::select! {
+            result = &mut utterance => report_utterance_error(result, output),
+            next = line_rx.recv() => {
+                let _ = cancel_tx.send(INTERRUPT_REASON.to_owned());
+                report_utterance_error(utterance.await, output);
+                match next {
+                    Some(event) => pending = Some(event),
+                    None => break,
+                }
+            }
+        }
+    }
+
+    reader.await.context("FIFO reader task failed")?
+}
+
+/// Skips every queued event but the newest so stale announcements are never spoken.
+fn take_latest_event(
+    mut latest: ListenEvent,
+    line_rx: &mut mpsc::Receiver<ListenEvent>,
+) -> ListenEvent {
+    while let Ok(next) = line_rx.try_recv() {
+        latest = next;
+    }
+    latest
+}
+
+async fn read_fifo_lines(fifo_path: PathBuf, line_tx: mpsc::Sender<ListenEvent>) -> Result<()> {
+    loop {
+        // Opening a FIFO blocks until a writer connects; reopen after each writer closes.
+        let file = tokio::fs::File::open(&fifo_path)
+            .await
+            .with_context(|| format!("Failed to open FIFO {}", fifo_path.display()))?;
+        let mut lines = BufReader::new(file).lines();
+        while let Some(line) = lines.next_line().await? {
+            if line_tx.send(ListenEvent::from_line(&line)).await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[allow(clippy::future_not_send)]
+async fn speak_utterance(
+    request: &ListenRequest<'_>,
+    text: &str,
+    mut cancel_rx: oneshot::Receiver<String>,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let synth_request = DaemonSynthesisBytesRequest {
+        text,
+        style_id: request.style_id,
+        options: SynthesisOptions::with_rate(request.rate),
+        socket_path: &request.socket_path,
+        auto_start_daemon: true,
+        ensure_models_if_missing: false,
+        quiet_setup_messages: true,
+    };
+
+    let wav_data =
+        match synthesize_bytes_via_daemon_cancellable(&synth_request, output, Some(&mut cancel_rx))
+            .await?
+        {
+            SynthesisFlowOutcome::Completed(wav_data) => wav_data,
+            SynthesisFlowOutcome::Canceled(_) => return Ok(()),
+        };
+
+    match emit_and_play(PlaybackRequest {
+        wav_data: &wav_data,
+        output_file: None,
+        output_format: OutputFormat::Wav,
+        play: true,
+        cancel_rx: Some(cancel_rx),
+    })
+    .await?
+    {
+        PlaybackOutcome::Completed | PlaybackOutcome::Cancelled(_) => Ok(()),
+    }
+}
+
+fn report_utterance_error(result: Result<()>, output: &dyn AppOutput) {
+    if let Err(error) = result {
+        output.error(&format_daemon_client_error_for_cli(&error));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_lines_silence_current_utterance() {
+        assert_eq!(ListenEvent::from_line("  \t"), ListenEvent::Silence);
+        assert_eq!(
+            ListenEvent::from_line(" こんにちは \n"),
+            ListenEvent::Speak("こんにちは".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn only_newest_queued_event_is_kept() {
+        let (line_tx, mut line_rx) = mpsc::channel(4);
+        line_tx
+            .send(ListenEvent::Speak("second".to_owned()))
+            .await
+            .expect("send");
+        line_tx
+            .send(ListenEvent::Speak("third".to_owned()))
+            .await
+            .expect("send");
+
+        let latest = take_latest_event(ListenEvent::Speak("first".to_owned()), &mut line_rx);
+
+        assert_eq!(latest, ListenEvent::Speak("third".to_owned()));
+        assert!(line_rx.try_recv().is_err());
+    }
+}
@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+use crate::infrastructure::daemon::client::DaemonClient;
+use crate::interface::AppOutput;
+
+/// How often deferred background work asks the daemon again; shorter than the
+/// daemon's idle timeout so the connection stays open while waiting.
+const POWER_POLL_INTERVAL: Duration = Duration::from_secs(20);
+
+/// Holds background work such as batch rows and queued jobs while the daemon asks
+/// for it, as a `--low-power` daemon does while the machine runs on battery.
+///
+/// Daemons that predate the power policy never defer.
+pub(crate) async fn wait_while_background_deferred(
+    client: &mut DaemonClient,
+    quiet: bool,
+    output: &dyn AppOutput,
+) {
+    let mut waited = false;
+    while client.background_deferred().await.unwrap_or(false) {
+        if !waited && !quiet {
+            output.info("On battery: waiting for AC power before rendering (low-power daemon)");
+        }
+        waited = true;
+        tokio::time::sleep(POWER_POLL_INTERVAL).await;
+    }
+    if waited && !quiet {
+        output.info("On AC power: resuming");
+    }
+}
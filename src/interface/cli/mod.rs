@@ -1,9 +1,30 @@
+pub mod alias;
+pub mod align;
+pub mod backup;
+pub mod batch;
+pub mod capabilities;
 pub mod daemon_cli;
 pub mod daemon_error;
 pub mod daemon_invocation;
+pub mod daemon_stats;
+pub mod dialogue;
+pub mod dict;
 pub mod download;
+pub mod events;
+pub mod greeting;
+pub mod import_editor;
 pub mod input;
 pub mod inspect;
+pub mod inspect_wav;
+pub mod listen;
+pub mod low_power;
+pub mod pipeline;
+pub mod progress;
+pub mod queue;
+pub mod regression;
 pub mod say;
+pub mod serve_stdio;
 pub mod voice_help;
+pub mod voice_pack;
 pub mod voice_selector;
+pub mod vvproj;
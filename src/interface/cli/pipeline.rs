@@ -0,0 +1,195 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+use crate::domain::synthesis::wav::wav_duration;
+use crate::infrastructure::ipc::{DEFAULT_SYNTHESIS_PITCH, SynthesisOptions};
+use crate::interface::audio::OutputFormat;
+use crate::interface::cli::serve_stdio::{DaemonSession, parse_request_line, write_json_line};
+use crate::interface::cli::voice_selector::resolve_voice_input;
+use crate::interface::playback::{PlaybackRequest, emit_and_play};
+use crate::interface::synthesis::flow::validate_text_synthesis_request;
+
+pub struct PipelineRequest {
+    pub default_style_id: u32,
+    pub default_rate: f32,
+    pub socket_path: PathBuf,
+}
+
+/// One stdin line: what to say, and where the audio goes.
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PipelineJob {
+    text: String,
+    /// Voice name, canonical voice ID, or style ID, as accepted by `--voice`.
+    voice: Option<String>,
+    rate: Option<f32>,
+    /// WAV file to write; without it the audio is played.
+    output: Option<PathBuf>,
+}
+
+/// One stdout line reporting how a job went.
+#[derive(Debug, Serialize)]
+struct PipelineResult {
+    id: Value,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl PipelineResult {
+    fn from_result(id: Value, result: Result<(Option<PathBuf>, u64)>) -> Self {
+        match result {
+            Ok((output, duration_ms)) => Self {
+                id,
+                ok: true,
+                output,
+                duration_ms: Some(duration_ms),
+                error: None,
+            },
+            Err(error) => Self {
+                id,
+                ok: false,
+                output: None,
+                duration_ms: None,
+                error: Some(format!("{error:#}")),
+            },
+        }
+    }
+}
+
+struct PipelineSession {
+    request: PipelineRequest,
+    daemon: DaemonSession,
+}
+
+impl PipelineSession {
+    /// Synthesizes one job, then writes or plays it; returns the output and duration.
+    #[allow(clippy::future_not_send)]
+    async fn run(&mut self, job: PipelineJob) -> Result<(Option<PathBuf>, u64)> {
+        let style_id = match job.voice.as_deref() {
+            Some(voice) => resolve_voice_input(voice)?.0,
+            None => self.request.default_style_id,
+        };
+        let options = SynthesisOptions {
+            rate: job.rate.unwrap_or(self.request.default_rate),
+            pitch: DEFAULT_SYNTHESIS_PITCH,
+            ..SynthesisOptions::default()
+        };
+        validate_text_synthesis_request(&job.text, style_id, options.rate, options.pitch)?;
+
+        let synthesized = self
+            .daemon
+            .client()
+            .await?
+            .synthesize(&job.text, style_id, options)
+            .await;
+        let wav_data = synthesized.inspect_err(|_| self.daemon.reset())?;
+        let duration = wav_duration(&wav_data)?;
+
+        emit_and_play(PlaybackRequest {
+            wav_data: &wav_data,
+            output_file: job.output.as_deref(),
+            output_format: OutputFormat::resolve(None, job.output.as_deref()),
+            play: job.output.is_none(),
+            cancel_rx: None,
+        })
+        .await
+        .with_context(|| match &job.output {
+            Some(output) => format!("Failed to write {}", output.display()),
+            None => "Failed to play audio".to_string(),
+        })?;
+        Ok((
+            job.output,
+            u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+        ))
+    }
+}
+
+/// Runs JSON jobs from stdin one at a time, answering each with a JSON result line.
+///
+/// Each stdin line is an object with `text` and optional `voice`, `rate`, `output`,
+/// and `id`. The job is written to `output` as WAV, or played when `output` is
+/// absent, before the next line is read. Its result line echoes `id` with `ok` and
+/// either `output` and `duration_ms` or `error`. The loop ends at EOF.
+///
+/// # Errors
+///
+/// Returns an error only if stdin cannot be read or stdout cannot be written;
+/// failed jobs are reported in their result line.
+#[allow(clippy::future_not_send)]
+pub async fn run_pipeline(request: PipelineRequest) -> Result<()> {
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+    let mut session = PipelineSession {
+        daemon: DaemonSession::new(request.socket_path.clone()),
+        request,
+    };
+
+    while let Some(line) = lines.next_line().await.context("Failed to read stdin")? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (id, job) = parse_request_line::<PipelineJob>(&line);
+        let result = match job {
+            Ok(job) => session.run(job).await,
+            Err(error) => Err(error),
+        };
+        write_json_line(&mut stdout, &PipelineResult::from_result(id, result)).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_jobs_and_rejects_unknown_fields() {
+        let (id, job) = parse_request_line::<PipelineJob>(
+            r#"{"id": 1, "text": "こんにちは", "voice": "zundamon", "output": "a.wav"}"#,
+        );
+        assert_eq!(id, Value::from(1));
+        assert_eq!(
+            job.expect("valid job"),
+            PipelineJob {
+                text: "こんにちは".to_owned(),
+                voice: Some("zundamon".to_owned()),
+                rate: None,
+                output: Some(PathBuf::from("a.wav")),
+            }
+        );
+
+        let (id, job) = parse_request_line::<PipelineJob>(r#"{"id": "x", "txt": "typo"}"#);
+        assert_eq!(id, Value::from("x"));
+        assert!(job.is_err());
+    }
+
+    #[test]
+    fn results_omit_fields_that_do_not_apply() {
+        let done =
+            PipelineResult::from_result(Value::from(1), Ok((Some(PathBuf::from("a.wav")), 1250)));
+        assert_eq!(
+            serde_json::to_value(done).expect("serializable"),
+            serde_json::json!({"id": 1, "ok": true, "output": "a.wav", "duration_ms": 1250})
+        );
+
+        let played = PipelineResult::from_result(Value::Null, Ok((None, 800)));
+        assert_eq!(
+            serde_json::to_value(played).expect("serializable"),
+            serde_json::json!({"id": null, "ok": true, "duration_ms": 800})
+        );
+
+        let failed = PipelineResult::from_result(Value::from(2), Err(anyhow::anyhow!("boom")));
+        assert_eq!(
+            serde_json::to_value(failed).expect("serializable"),
+            serde_json::json!({"id": 2, "ok": false, "error": "boom"})
+        );
+    }
+}
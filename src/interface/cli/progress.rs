@@ -0,0 +1,378 @@
+use anyhow::{Context, Result, bail, ensure};
+use serde::Serialize;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::config::user_config;
+use crate::domain::inline_voice::split_voice_markers;
+use crate::infrastructure::daemon::client::DuplicateSkipped;
+use crate::infrastructure::ipc::SynthesisOptions;
+use crate::interface::AppOutput;
+use crate::interface::audio::{AudioBackend, audio_backend};
+use crate::interface::interrupt::{Interrupted, interrupt_receiver};
+use crate::interface::sink::{DEFAULT_SINK_CAPACITY, PlaybackSink, RodioSink, wait_until_drained};
+use crate::interface::synthesis::flow::connect_daemon_client_auto_start;
+use crate::interface::synthesis::{SegmentProgress, StreamingSynthesizer};
+
+/// How often the sink is checked for the next sentence starting to play.
+const PLAYBACK_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How `--progress` reports the reading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressFormat {
+    /// One line per sentence as it starts playing.
+    Text,
+    /// One JSON object per line for every synthesized, playing, and finished event.
+    Json,
+}
+
+impl ProgressFormat {
+    /// Parses `text` or `json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for any other name.
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => bail!("Unknown progress format '{value}' (expected text or json)"),
+        }
+    }
+
+    fn render(self, event: &ProgressEvent) -> Option<String> {
+        match (self, event) {
+            (Self::Json, event) => serde_json::to_string(event).ok(),
+            (
+                Self::Text,
+                ProgressEvent::Playing {
+                    index,
+                    total,
+                    text,
+                    percent,
+                    eta_ms,
+                },
+            ) => Some(format!(
+                "[{}/{total} {percent:>3}% ETA {}] {text}",
+                index + 1,
+                format_eta(Duration::from_millis(*eta_ms))
+            )),
+            (Self::Text, _) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum ProgressEvent {
+    Synthesized {
+        index: usize,
+        total: usize,
+        text: String,
+        duration_ms: u64,
+    },
+    Playing {
+        index: usize,
+        total: usize,
+        text: String,
+        /// Share of the text spoken before this sentence.
+        percent: u8,
+        /// Audio left from the start of this sentence, estimated for sentences not
+        /// synthesized yet.
+        eta_ms: u64,
+    },
+    Finished {
+        total: usize,
+    },
+}
+
+struct TrackedSegment {
+    index: usize,
+    text: String,
+    chars: usize,
+    /// `None` for a repeat the daemon skipped, which never reaches the sink.
+    duration: Option<Duration>,
+}
+
+/// Follows which sentence is playing from what was queued and what the sink still holds.
+struct ReadingProgress {
+    total_chars: usize,
+    /// Characters of every segment synthesized or skipped so far.
+    recorded_chars: usize,
+    total: usize,
+    /// Segments reported by the synthesizer, in text order, with those finished dropped.
+    queued: VecDeque<TrackedSegment>,
+    finished_chars: usize,
+    /// Characters and length of the finished segments that had audio.
+    heard_chars: usize,
+    heard_duration: Duration,
+    playing: Option<usize>,
+}
+
+impl ReadingProgress {
+    fn new(text: &str) -> Self {
+        Self {
+            total_chars: spoken_chars(text),
+            recorded_chars: 0,
+            total: 0,
+            queued: VecDeque::new(),
+            finished_chars: 0,
+            heard_chars: 0,
+            heard_duration: Duration::ZERO,
+            playing: None,
+        }
+    }
+
+    fn record(&mut self, segment: SegmentProgress<'_>) -> Option<ProgressEvent> {
+        let chars = spoken_chars(segment.text);
+        self.recorded_chars += chars;
+        self.total = segment.total;
+        self.queued.push_back(TrackedSegment {
+            index: segment.index,
+            text: segment.text.to_owned(),
+            chars,
+            duration: segment.duration,
+        });
+        segment.duration.map(|duration| ProgressEvent::Synthesized {
+            index: segment.index,
+            total: segment.total,
+            text: segment.text.to_owned(),
+            duration_ms: millis(duration),
+        })
+    }
+
+    /// Reports the sentence now at the head of a sink holding `in_sink` segments,
+    /// if it has changed.
+    fn poll(&mut self, in_sink: usize) -> Option<ProgressEvent> {
+        let mut audible = self.queued.iter().filter(|s| s.duration.is_some()).count();
+        while let Some(front) = self.queued.front() {
+            match front.duration {
+                Some(_) if audible <= in_sink => break,
+                Some(duration) => {
+                    audible -= 1;
+                    self.heard_chars += front.chars;
+                    self.heard_duration += duration;
+                }
+                None => {}
+            }
+            self.finished_chars += front.chars;
+            self.queued.pop_front();
+        }
+        let current = self.queued.front()?;
+        if self.playing == Some(current.index) {
+            return None;
+        }
+        self.playing = Some(current.index);
+
+        let queued_duration: Duration = self.queued.iter().filter_map(|s| s.duration).sum();
+        let queued_chars: usize = self
+            .queued
+            .iter()
+            .filter(|s| s.duration.is_some())
+            .map(|s| s.chars)
+            .sum();
+        let per_char = (self.heard_duration + queued_duration)
+            .checked_div(u32::try_from(self.heard_chars + queued_chars).unwrap_or(u32::MAX))
+            .unwrap_or_default();
+        let unsynthesized = self.total_chars.saturating_sub(self.recorded_chars);
+        let eta = queued_duration + per_char * u32::try_from(unsynthesized).unwrap_or(u32::MAX);
+        Some(ProgressEvent::Playing {
+            index: current.index,
+            total: self.total,
+            text: current.text.clone(),
+            percent: percent_of(self.finished_chars, self.total_chars),
+            eta_ms: millis(eta),
+        })
+    }
+}
+
+fn spoken_chars(text: &str) -> usize {
+    text.chars().filter(|ch| !ch.is_whitespace()).count()
+}
+
+fn percent_of(part: usize, whole: usize) -> u8 {
+    if whole == 0 {
+        return 100;
+    }
+    u8::try_from((part.min(whole) * 100) / whole).unwrap_or(100)
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+fn format_eta(eta: Duration) -> String {
+    let seconds = eta.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+pub struct ProgressReadingRequest<'a> {
+    pub text: &'a str,
+    pub style_id: u32,
+    pub options: SynthesisOptions,
+    pub format: ProgressFormat,
+    pub socket_path: &'a Path,
+}
+
+/// Reads `text` sentence by sentence, playing each as soon as it is synthesized
+/// and reporting which one is playing, how much is done, and how long is left.
+///
+/// # Errors
+///
+/// Returns an error if the text has voice markers, audio is disabled, the daemon
+/// cannot be reached or does not allow streaming right now, or synthesis or playback
+/// fails.
+#[allow(clippy::future_not_send)]
+pub async fn run_progress_reading(
+    request: ProgressReadingRequest<'_>,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    ensure!(
+        split_voice_markers(request.text)?
+            .iter()
+            .all(|run| run.voice.is_none()),
+        "--progress reads in one voice; remove the ((voice: ...)) markers"
+    );
+    ensure!(
+        audio_backend() == AudioBackend::Device,
+        "--progress follows playback, so it needs audio; it cannot be used with --audio null"
+    );
+    let mut client = connect_daemon_client_auto_start(request.socket_path).await?;
+    ensure!(
+        client.streaming_allowed().await?,
+        "The daemon's playback policy does not allow streaming right now; run without --progress"
+    );
+    let mut synthesizer = StreamingSynthesizer::new_with_client_and_config(client, user_config())?;
+    let sink =
+        RodioSink::open(DEFAULT_SINK_CAPACITY).context("Failed to play synthesized audio")?;
+
+    let progress = RefCell::new(ReadingProgress::new(request.text));
+    let report = |event: Option<ProgressEvent>| {
+        if let Some(line) = event.and_then(|event| request.format.render(&event)) {
+            output.info(&line);
+        }
+    };
+    let reading = async {
+        let queued = synthesizer
+            .synthesize_streaming_with_progress(
+                request.text,
+                request.style_id,
+                request.options,
+                &sink,
+                &mut |segment| report(progress.borrow_mut().record(segment)),
+            )
+            .await?;
+        wait_until_drained(&sink).await;
+        anyhow::Ok(queued)
+    };
+    let followed = async {
+        let mut reading = std::pin::pin!(reading);
+        loop {
+            report(progress.borrow_mut().poll(sink.queued()));
+            tokio::select! {
+                result = &mut reading => break result,
+                () = tokio::time::sleep(PLAYBACK_POLL_INTERVAL) => {}
+            }
+        }
+    };
+    let result = match interrupt_receiver() {
+        Some(cancel_rx) => tokio::select! {
+            result = followed => Some(result),
+            Ok(_) = cancel_rx => None,
+        },
+        None => Some(followed.await),
+    };
+    for warning in synthesizer.take_warnings() {
+        output.error(&format!("warning: {warning}"));
+    }
+    let Some(result) = result else {
+        sink.stop();
+        return Err(Interrupted.into());
+    };
+
+    if result? == 0 {
+        output.info(&DuplicateSkipped.to_string());
+        return Ok(());
+    }
+    let total = progress.borrow().total;
+    report(Some(ProgressEvent::Finished { total }));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(index: usize, text: &str, millis: Option<u64>) -> SegmentProgress<'_> {
+        SegmentProgress {
+            index,
+            total: 4,
+            text,
+            duration: millis.map(Duration::from_millis),
+        }
+    }
+
+    #[test]
+    fn playing_sentences_report_progress_and_time_left() {
+        let mut progress = ReadingProgress::new("あいうえ。かきくけ。さしすせ。たちつてと。");
+        assert!(progress.poll(0).is_none());
+
+        progress.record(segment(0, "あいうえ。", Some(1000)));
+        progress.record(segment(1, "かきくけ。", None));
+        progress.record(segment(2, "さしすせ。", Some(1000)));
+        let Some(ProgressEvent::Playing {
+            index: 0,
+            percent: 0,
+            eta_ms,
+            ..
+        }) = progress.poll(2)
+        else {
+            panic!("first sentence should be playing");
+        };
+        // Two sentences queued, and the unsynthesized six characters at 200ms each.
+        assert_eq!(eta_ms, 3200);
+        assert!(progress.poll(2).is_none());
+
+        // The skipped repeat is passed over without being announced.
+        assert_eq!(
+            progress.poll(1),
+            Some(ProgressEvent::Playing {
+                index: 2,
+                total: 4,
+                text: "さしすせ。".to_string(),
+                percent: 47,
+                eta_ms: 2200,
+            })
+        );
+        assert!(progress.poll(0).is_none());
+    }
+
+    #[test]
+    fn text_progress_lines_only_show_playing_sentences() {
+        let playing = ProgressEvent::Playing {
+            index: 2,
+            total: 10,
+            text: "三番目。".to_string(),
+            percent: 20,
+            eta_ms: 75_000,
+        };
+        assert_eq!(
+            ProgressFormat::Text.render(&playing).as_deref(),
+            Some("[3/10  20% ETA 1:15] 三番目。")
+        );
+        assert_eq!(
+            ProgressFormat::Json
+                .render(&ProgressEvent::Finished { total: 10 })
+                .as_deref(),
+            Some(r#"{"event":"finished","total":10}"#)
+        );
+        assert!(
+            ProgressFormat::Text
+                .render(&ProgressEvent::Finished { total: 10 })
+                .is_none()
+        );
+        assert!(ProgressFormat::parse("xml").is_err());
+    }
+}
@@ -0,0 +1,219 @@
+use anyhow::{Context, Result, anyhow};
+use futures_util::future::join_all;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::infrastructure::daemon::client::DaemonClient;
+use crate::infrastructure::ipc::{DEFAULT_SYNTHESIS_PITCH, SynthesisOptions};
+use crate::infrastructure::job_queue::{
+    JobStatus, NewJob, QueuedJob, read_job_queue, try_claim_queue_runner, update_job_queue,
+};
+use crate::interface::cli::daemon_error::format_daemon_client_error_for_cli;
+use crate::interface::cli::low_power::wait_while_background_deferred;
+use crate::interface::synthesis::flow::{
+    connect_daemon_client_auto_start, ensure_startup_resources, validate_text_synthesis_request,
+};
+use crate::interface::{AppOutput, StdAppOutput};
+
+pub const MAX_QUEUE_WORKERS: usize = 8;
+
+pub struct QueueAddRequest {
+    pub text: String,
+    pub style_id: u32,
+    pub rate: f32,
+    pub output: PathBuf,
+}
+
+pub struct QueueRunRequest {
+    pub workers: usize,
+    pub retry_failed: bool,
+    pub quiet: bool,
+    pub socket_path: PathBuf,
+}
+
+/// Validates a render job and appends it to the persistent queue.
+///
+/// # Errors
+///
+/// Returns an error if the job is invalid or the queue file cannot be updated.
+pub fn run_queue_add(request: QueueAddRequest, output: &dyn AppOutput) -> Result<()> {
+    validate_text_synthesis_request(
+        &request.text,
+        request.style_id,
+        request.rate,
+        DEFAULT_SYNTHESIS_PITCH,
+    )?;
+    let output_path = std::path::absolute(&request.output)
+        .with_context(|| format!("Invalid output path {}", request.output.display()))?;
+
+    let id = update_job_queue(|queue| {
+        queue.push(NewJob {
+            text: request.text,
+            style_id: request.style_id,
+            rate: request.rate,
+            output: output_path,
+        })
+    })?;
+    output.info(&format!("Queued job {id}"));
+    Ok(())
+}
+
+/// Prints every queued job with its status.
+///
+/// # Errors
+///
+/// Returns an error if the queue file cannot be read.
+pub fn run_queue_list(output: &dyn AppOutput) -> Result<()> {
+    let queue = read_job_queue()?;
+    if queue.jobs.is_empty() {
+        output.info("Queue is empty");
+        return Ok(());
+    }
+    for job in &queue.jobs {
+        output.info(&format_job_line(job));
+    }
+    Ok(())
+}
+
+/// Removes finished jobs, or every job when `all` is set.
+///
+/// # Errors
+///
+/// Returns an error if the queue file cannot be updated.
+pub fn run_queue_clear(all: bool, output: &dyn AppOutput) -> Result<()> {
+    let removed = update_job_queue(|queue| queue.clear(all))?;
+    output.info(&format!("Removed {removed} job(s)"));
+    Ok(())
+}
+
+fn format_job_line(job: &QueuedJob) -> String {
+    let status = match job.status {
+        JobStatus::Pending => "pending",
+        JobStatus::Done => "done",
+        JobStatus::Failed => "failed",
+    };
+    let mut line = format!(
+        "#{:<4} {status:<7} style={} rate={:.2} -> {}",
+        job.id,
+        job.style_id,
+        job.rate,
+        job.output.display()
+    );
+    if let Some(error) = &job.error {
+        line.push_str(&format!(" ({error})"));
+    }
+    line
+}
+
+/// Drains pending jobs with a bounded number of daemon connections.
+///
+/// Jobs added while the runner is active are picked up before it exits. Progress is
+/// written back after every job so an interrupted run resumes where it stopped.
+///
+/// # Errors
+///
+/// Returns an error if another runner is active, resources cannot be prepared, the
+/// queue cannot be updated, or any job fails.
+pub async fn run_queue_run(request: QueueRunRequest) -> Result<()> {
+    let output = StdAppOutput;
+    run_queue_run_with_output(request, &output).await
+}
+
+pub async fn run_queue_run_with_output(
+    request: QueueRunRequest,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let Some(_runner_lock) = try_claim_queue_runner()? else {
+        return Err(anyhow!(
+            "Another `queue run` is already processing the queue"
+        ));
+    };
+
+    if request.retry_failed {
+        let requeued = update_job_queue(|queue| queue.retry_failed())?;
+        if requeued > 0 && !request.quiet {
+            output.info(&format!("Requeued {requeued} failed job(s)"));
+        }
+    }
+
+    ensure_startup_resources(request.quiet, output).await?;
+
+    let claimed = Mutex::new(HashSet::new());
+    let workers = request.workers.clamp(1, MAX_QUEUE_WORKERS);
+    let results = join_all((0..workers).map(|_| run_worker(&request, &claimed, output))).await;
+
+    let failed = results
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?
+        .iter()
+        .sum::<usize>();
+    if failed == 0 {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "{failed} job(s) failed; rerun with `queue run --retry-failed`"
+        ))
+    }
+}
+
+async fn run_worker(
+    request: &QueueRunRequest,
+    claimed: &Mutex<HashSet<u64>>,
+    output: &dyn AppOutput,
+) -> Result<usize> {
+    let mut client: Option<DaemonClient> = None;
+    let mut failed = 0;
+
+    while let Some(job) = claim_next_job(claimed)? {
+        let client = match client.as_mut() {
+            Some(client) => client,
+            None => client.insert(connect_daemon_client_auto_start(&request.socket_path).await?),
+        };
+        wait_while_background_deferred(client, request.quiet, output).await;
+
+        let result = render_job(client, &job).await;
+        if !request.quiet {
+            match &result {
+                Ok(()) => output.info(&format!("✓ #{} {}", job.id, job.output.display())),
+                Err(error) => output.error(&format!(
+                    "✗ #{} {}",
+                    job.id,
+                    format_daemon_client_error_for_cli(error)
+                )),
+            }
+        }
+        if result.is_err() {
+            failed += 1;
+        }
+        let result = result.map_err(|error| format!("{error:#}"));
+        update_job_queue(|queue| queue.record_attempt(job.id, result))?;
+    }
+
+    Ok(failed)
+}
+
+fn claim_next_job(claimed: &Mutex<HashSet<u64>>) -> Result<Option<QueuedJob>> {
+    let queue = read_job_queue()?;
+    let mut claimed = claimed
+        .lock()
+        .map_err(|_| anyhow!("Queue claim state poisoned"))?;
+    let job = queue.next_pending(|id| claimed.contains(&id)).cloned();
+    if let Some(job) = &job {
+        claimed.insert(job.id);
+    }
+    Ok(job)
+}
+
+async fn render_job(client: &mut DaemonClient, job: &QueuedJob) -> Result<()> {
+    let options = SynthesisOptions::with_rate(job.rate);
+    let wav_data = client.synthesize(&job.text, job.style_id, options).await?;
+    if let Some(parent) = job.output.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    tokio::fs::write(&job.output, wav_data)
+        .await
+        .with_context(|| format!("Failed to write {}", job.output.display()))
+}
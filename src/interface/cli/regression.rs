@@ -0,0 +1,224 @@
+use anyhow::{Context, Result, anyhow, ensure};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::domain::regression::{
+    REGRESSION_BASELINE_VERSION, REGRESSION_CORPUS, RegressionBaseline, RegressionClip,
+    SpectralFingerprint, compare_fingerprints, fingerprint_wav,
+};
+use crate::infrastructure::daemon::client::DaemonClient;
+use crate::infrastructure::ipc::SynthesisOptions;
+use crate::interface::synthesis::flow::{
+    connect_daemon_client_auto_start, ensure_startup_resources,
+};
+use crate::interface::{AppOutput, StdAppOutput};
+
+const BASELINE_FILE_NAME: &str = "baseline.json";
+const CURRENT_DIR_NAME: &str = "current";
+
+/// What `--regression` does with its directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegressionAction {
+    /// Synthesize the corpus and store fingerprints as the new baseline.
+    Record,
+    /// Synthesize the corpus again and compare against the stored baseline.
+    Check,
+}
+
+impl RegressionAction {
+    /// Parses `record` or `check`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for any other value.
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "record" => Ok(Self::Record),
+            "check" => Ok(Self::Check),
+            _ => Err(anyhow!(
+                "Unknown regression action '{value}' (expected record or check)"
+            )),
+        }
+    }
+}
+
+pub struct RegressionRequest<'a> {
+    pub action: RegressionAction,
+    pub dir: &'a Path,
+    /// Voice used by `record`; `check` always uses the voice stored in the baseline.
+    pub style_id: u32,
+    pub rate: f32,
+    pub quiet: bool,
+    pub socket_path: PathBuf,
+}
+
+/// Records or checks the voice regression corpus.
+///
+/// `record` writes each corpus sentence as `<id>.wav` plus `baseline.json` with
+/// its spectral fingerprint. `check` re-synthesizes the recorded sentences into
+/// `current/` and reports every clip whose spectrum or length drifted.
+///
+/// # Errors
+///
+/// Returns an error if synthesis or file access fails, the baseline is missing or
+/// from a newer version, or any clip drifted significantly.
+pub async fn run_regression(request: RegressionRequest<'_>) -> Result<()> {
+    let output = StdAppOutput;
+    run_regression_with_output(request, &output).await
+}
+
+pub async fn run_regression_with_output(
+    request: RegressionRequest<'_>,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    match request.action {
+        RegressionAction::Record => record_baseline(&request, output).await,
+        RegressionAction::Check => check_baseline(&request, output).await,
+    }
+}
+
+async fn connect(request: &RegressionRequest<'_>, output: &dyn AppOutput) -> Result<DaemonClient> {
+    ensure_startup_resources(request.quiet, output).await?;
+    connect_daemon_client_auto_start(&request.socket_path).await
+}
+
+async fn render_clip(
+    client: &mut DaemonClient,
+    text: &str,
+    style_id: u32,
+    rate: f32,
+    wav_path: &Path,
+) -> Result<SpectralFingerprint> {
+    let wav_data = client
+        .synthesize(text, style_id, SynthesisOptions::with_rate(rate))
+        .await?;
+    tokio::fs::write(wav_path, &wav_data)
+        .await
+        .with_context(|| format!("Failed to write {}", wav_path.display()))?;
+    fingerprint_wav(&wav_data).with_context(|| format!("Failed to analyze {}", wav_path.display()))
+}
+
+async fn record_baseline(request: &RegressionRequest<'_>, output: &dyn AppOutput) -> Result<()> {
+    tokio::fs::create_dir_all(request.dir)
+        .await
+        .with_context(|| format!("Failed to create {}", request.dir.display()))?;
+    let mut client = connect(request, output).await?;
+
+    let mut clips = BTreeMap::new();
+    for (id, text) in REGRESSION_CORPUS {
+        let wav_path = request.dir.join(format!("{id}.wav"));
+        let fingerprint = render_clip(&mut client, text, request.style_id, request.rate, &wav_path)
+            .await
+            .with_context(|| format!("Failed to record '{id}'"))?;
+        if !request.quiet {
+            output.info(&format!("recorded {id} ({} ms)", fingerprint.duration_ms));
+        }
+        clips.insert(
+            (*id).to_owned(),
+            RegressionClip {
+                text: (*text).to_owned(),
+                fingerprint,
+            },
+        );
+    }
+
+    let baseline = RegressionBaseline {
+        version: REGRESSION_BASELINE_VERSION,
+        style_id: request.style_id,
+        rate: request.rate,
+        clips,
+    };
+    let baseline_path = request.dir.join(BASELINE_FILE_NAME);
+    tokio::fs::write(&baseline_path, serde_json::to_vec_pretty(&baseline)?)
+        .await
+        .with_context(|| format!("Failed to write {}", baseline_path.display()))?;
+    output.info(&format!(
+        "Recorded {} clips to {}",
+        baseline.clips.len(),
+        baseline_path.display()
+    ));
+    Ok(())
+}
+
+async fn check_baseline(request: &RegressionRequest<'_>, output: &dyn AppOutput) -> Result<()> {
+    let baseline_path = request.dir.join(BASELINE_FILE_NAME);
+    let source = tokio::fs::read(&baseline_path).await.with_context(|| {
+        format!(
+            "Failed to read {} (run --regression record first)",
+            baseline_path.display()
+        )
+    })?;
+    let baseline: RegressionBaseline = serde_json::from_slice(&source)
+        .with_context(|| format!("Invalid baseline {}", baseline_path.display()))?;
+    ensure!(
+        baseline.version <= REGRESSION_BASELINE_VERSION,
+        "Baseline version {} is newer than this build supports ({REGRESSION_BASELINE_VERSION})",
+        baseline.version
+    );
+
+    let current_dir = request.dir.join(CURRENT_DIR_NAME);
+    tokio::fs::create_dir_all(&current_dir)
+        .await
+        .with_context(|| format!("Failed to create {}", current_dir.display()))?;
+    let mut client = connect(request, output).await?;
+
+    let mut drifted = 0;
+    for (id, clip) in &baseline.clips {
+        let fingerprint = render_clip(
+            &mut client,
+            &clip.text,
+            baseline.style_id,
+            baseline.rate,
+            &current_dir.join(format!("{id}.wav")),
+        )
+        .await
+        .with_context(|| format!("Failed to check '{id}'"))?;
+        let drift = compare_fingerprints(&clip.fingerprint, &fingerprint)
+            .with_context(|| format!("Cannot compare '{id}'"))?;
+        let length_change = (fingerprint.duration_ms as f64 - clip.fingerprint.duration_ms as f64)
+            / (clip.fingerprint.duration_ms.max(1) as f64)
+            * 100.0;
+        let summary = format!(
+            "{id}: spectrum {:.1} dB, length {length_change:+.1}%",
+            drift.band_drift_db
+        );
+        if drift.is_significant() {
+            drifted += 1;
+            output.error(&format!("✗ {summary}"));
+        } else if !request.quiet {
+            output.info(&format!("✓ {summary}"));
+        }
+    }
+
+    output.info(&format!(
+        "Regression check finished: {} unchanged, {drifted} drifted (new audio in {})",
+        baseline.clips.len() - drifted,
+        current_dir.display()
+    ));
+    if drifted == 0 {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "{drifted} of {} clips drifted from the baseline",
+            baseline.clips.len()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_actions() {
+        assert_eq!(
+            RegressionAction::parse("record").expect("record"),
+            RegressionAction::Record
+        );
+        assert_eq!(
+            RegressionAction::parse("check").expect("check"),
+            RegressionAction::Check
+        );
+        assert!(RegressionAction::parse("compare").is_err());
+    }
+}
@@ -1,11 +1,38 @@
-use anyhow::Result;
+use anyhow::{Context, Result, anyhow, bail, ensure};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
+use crate::domain::inline_voice::split_voice_markers;
+use crate::domain::markup::{MarkupPiece, Prosody, parse_markup};
+use crate::domain::sentiment::{classify_sentence, mood_style};
+use crate::domain::synthesis::Assembler;
+use crate::domain::synthesis::breath::{BreathPauses, SplitMix64, plan_breaths};
+use crate::domain::synthesis::chime::{ChimeSpec, Chimes, surround_with_chimes};
+use crate::domain::synthesis::length_limit::{
+    LengthLimit, OverlongPolicy, cut_segments_to_estimate, estimate_speech_duration,
+};
+use crate::domain::synthesis::postprocess::{AudioEffects, apply_effects};
+use crate::domain::synthesis::timing::{fit_rate, is_within_fit_tolerance};
+use crate::domain::synthesis::wav::{truncate_wav, wav_duration};
+use crate::domain::voice::VoiceStyle;
+use voicevox_text::{TextSplitter, lint_text};
+
+use crate::infrastructure::daemon::client::{DuplicateSkipped, is_duplicate_skipped};
+use crate::infrastructure::ipc::{MoraTiming, SynthesisOptions};
+use crate::infrastructure::voicevox::{Speaker, scan_speakers_from_model_files};
+use crate::interface::audio::OutputFormat;
 use crate::interface::cli::daemon_error::format_daemon_client_error_for_cli;
-use crate::interface::playback::{PlaybackRequest, emit_and_play};
+use crate::interface::cli::voice_selector::resolve_voice_among;
+use crate::interface::hooks::{HookContext, HookEvent, HooksConfig, run_hook};
+use crate::interface::interrupt::{Interrupted, interrupt_receiver, is_interrupted};
+use crate::interface::mora_timings;
+use crate::interface::playback::{PlaybackOutcome, PlaybackRequest, emit_and_play};
 use crate::interface::synthesis::flow::{
-    DaemonSynthesisBytesRequest, synthesize_bytes_via_daemon, validate_text_synthesis_request,
+    DaemonSynthesisBytesRequest, SynthesisFlowOutcome, synthesize_bytes_via_daemon_cancellable,
+    validate_text_synthesis_request,
 };
+use crate::interface::tee::{TeeTarget, send_to_tee};
+use crate::interface::temp_audio::create_temp_wav_file;
 use crate::interface::{AppOutput, StdAppOutput};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -18,8 +45,30 @@ enum SayPhase {
 pub struct SaySynthesisRequest<'a> {
     pub text: &'a str,
     pub style_id: u32,
-    pub rate: f32,
+    /// Rate, pitch, intonation, volume, and pauses sent with every daemon request.
+    pub options: SynthesisOptions,
+    pub effects: AudioEffects,
+    /// Earcons played around the utterance and written into `output_file`.
+    pub chimes: &'a Chimes,
+    pub hooks: &'a HooksConfig,
+    /// Re-render at an adjusted rate until the audio lasts this long (within limits).
+    pub fit_duration: Option<Duration>,
+    /// Refuse or cut text whose speech would run past this length.
+    pub length_limit: Option<LengthLimit>,
     pub output_file: Option<&'a Path>,
+    pub output_format: OutputFormat,
+    /// JSON file to write where each mora falls in the audio, chimes included.
+    pub timings_file: Option<&'a Path>,
+    /// Network sink sent a copy of the audio while it plays or is saved.
+    pub tee: Option<&'a TeeTarget>,
+    /// Read `text` as SSML-like markup instead of plain text with voice markers.
+    pub markup: bool,
+    /// Pause briefly at commas and clause ends, as a reader taking breaths would.
+    pub breath_pauses: Option<BreathPauses>,
+    /// Read each sentence in the voice's happy or sad style when its wording suggests one.
+    pub auto_emotion: bool,
+    /// Fail instead of warning when the text contains characters likely to synthesize poorly.
+    pub strict: bool,
     pub quiet: bool,
     pub socket_path: PathBuf,
 }
@@ -38,11 +87,20 @@ pub async fn run_say_synthesis_with_output(
     request: SaySynthesisRequest<'_>,
     output: &dyn AppOutput,
 ) -> Result<()> {
+    let limited_text = limit_text_length(&request, output)?;
+    let request = match limited_text.as_deref() {
+        Some(text) => SaySynthesisRequest { text, ..request },
+        None => request,
+    };
+    let pieces = resolve_pieces(&request)?;
+    if request.timings_file.is_some() {
+        mora_timings::enable();
+    }
     let mut phase = SayPhase::Validate;
     let mut wav_data: Option<Vec<u8>> = None;
 
     loop {
-        match run_say_phase(phase, &request, output, &mut wav_data).await? {
+        match run_say_phase(phase, &request, &pieces, output, &mut wav_data).await? {
             SayStep::Next(next) => phase = next,
             SayStep::Done => return Ok(()),
         }
@@ -57,51 +115,564 @@ enum SayStep {
 async fn run_say_phase(
     phase: SayPhase,
     request: &SaySynthesisRequest<'_>,
+    pieces: &[SayPiece],
     output: &dyn AppOutput,
     wav_data: &mut Option<Vec<u8>>,
 ) -> Result<SayStep> {
     match phase {
         SayPhase::Validate => {
-            validate_text_synthesis_request(request.text, request.style_id, request.rate)?;
+            validate_text_synthesis_request(
+                request.text,
+                request.style_id,
+                request.options.rate,
+                request.options.pitch,
+            )?;
+            request.options.validate()?;
+            ensure!(
+                pieces
+                    .iter()
+                    .any(|piece| matches!(piece, SayPiece::Speech { .. })),
+                "No text to speak outside the voice markers"
+            );
+            for piece in pieces {
+                if let SayPiece::Speech { prosody, .. } = piece {
+                    piece_options(request.options, request.options.rate, *prosody).validate()?;
+                }
+            }
+            request.effects.validate()?;
+            check_text_issues(request, pieces, output)?;
             Ok(SayStep::Next(SayPhase::Synthesize))
         }
-        SayPhase::Synthesize => {
-            let synth_request = DaemonSynthesisBytesRequest {
-                text: request.text,
-                style_id: request.style_id,
-                rate: request.rate,
-                socket_path: &request.socket_path,
-                ensure_models_if_missing: true,
-                quiet_setup_messages: request.quiet,
-            };
-
-            match synthesize_bytes_via_daemon(&synth_request, output).await {
-                Ok(data) => {
-                    *wav_data = Some(data);
-                    Ok(SayStep::Next(SayPhase::Emit))
+        SayPhase::Synthesize => match synthesize_fitted(request, pieces, output).await {
+            Ok((data, moras)) => {
+                let data = limit_audio_length(request, data)?;
+                if let Some(path) = request.timings_file {
+                    write_mora_timings(request, path, moras, &data)?;
                 }
-                Err(error) => {
-                    if !request.quiet {
-                        output.error(&format_daemon_client_error_for_cli(&error));
-                    }
-                    Err(error)
+                *wav_data = Some(data);
+                Ok(SayStep::Next(SayPhase::Emit))
+            }
+            // Nothing to play: the daemon already spoke this text moments ago.
+            Err(error) if is_duplicate_skipped(&error) && request.output_file.is_none() => {
+                if !request.quiet {
+                    output.info(&DuplicateSkipped.to_string());
                 }
+                Ok(SayStep::Done)
             }
-        }
+            Err(error) => {
+                if !request.quiet && !is_interrupted(&error) {
+                    output.error(&format_daemon_client_error_for_cli(&error));
+                }
+                Err(error)
+            }
+        },
         SayPhase::Emit => {
-            let wav_data = wav_data
+            let mut wav_data = wav_data
                 .take()
                 .expect("wav_data must be present in emit phase");
-            emit_and_play(PlaybackRequest {
+            apply_effects(&mut wav_data, &request.effects)?;
+            let wav_data = surround_with_chimes(wav_data, request.chimes)?;
+            let play = !request.quiet && request.output_file.is_none();
+            // Played audio has no file of its own, so the hook gets a temporary copy.
+            if play && let Some(command) = &request.hooks.on_synthesized {
+                let temp = create_temp_wav_file(&wav_data)?;
+                run_reported_hook(
+                    command,
+                    HookEvent::Synthesized,
+                    Some(temp.path()),
+                    &wav_data,
+                    request,
+                    output,
+                )
+                .await;
+            }
+            let playback = emit_and_play(PlaybackRequest {
                 wav_data: &wav_data,
                 output_file: request.output_file,
-                play: !request.quiet && request.output_file.is_none(),
-                cancel_rx: None,
+                output_format: request.output_format,
+                play,
+                cancel_rx: interrupt_receiver(),
+            });
+            let outcome = match request.tee {
+                Some(target) => {
+                    let (outcome, teed) = tokio::join!(playback, send_to_tee(target, &wav_data));
+                    let outcome = outcome?;
+                    teed?;
+                    outcome
+                }
+                None => playback.await?,
+            };
+            match outcome {
+                PlaybackOutcome::Completed => {
+                    if let (Some(path), Some(command)) =
+                        (request.output_file, &request.hooks.on_synthesized)
+                    {
+                        run_reported_hook(
+                            command,
+                            HookEvent::Synthesized,
+                            Some(path),
+                            &wav_data,
+                            request,
+                            output,
+                        )
+                        .await;
+                    }
+                    if play && let Some(command) = &request.hooks.on_spoken {
+                        run_reported_hook(
+                            command,
+                            HookEvent::Spoken,
+                            None,
+                            &wav_data,
+                            request,
+                            output,
+                        )
+                        .await;
+                    }
+                    Ok(SayStep::Done)
+                }
+                PlaybackOutcome::Cancelled(_) => Err(Interrupted.into()),
+            }
+        }
+    }
+}
+
+/// Runs a user hook; a failing hook is reported but does not fail the utterance.
+async fn run_reported_hook(
+    command: &str,
+    event: HookEvent,
+    file: Option<&Path>,
+    wav_data: &[u8],
+    request: &SaySynthesisRequest<'_>,
+    output: &dyn AppOutput,
+) {
+    let context = HookContext {
+        file,
+        text: request.text,
+        style_id: request.style_id,
+        duration: wav_duration(wav_data).unwrap_or_default(),
+    };
+    if let Err(error) = run_hook(command, event, &context).await
+        && !request.quiet
+    {
+        output.error(&format!("warning: {error:#}"));
+    }
+}
+
+/// Reads the earcon files named by `--chime`.
+///
+/// # Errors
+///
+/// Returns an error if a named file cannot be read.
+pub fn load_chimes(spec: &ChimeSpec) -> Result<Chimes> {
+    let read = |path: &Option<PathBuf>| {
+        path.as_deref()
+            .map(|path| {
+                std::fs::read(path)
+                    .with_context(|| format!("Failed to read chime {}", path.display()))
             })
-            .await?;
-            Ok(SayStep::Done)
+            .transpose()
+    };
+    Ok(Chimes {
+        start: read(&spec.start)?,
+        end: read(&spec.end)?,
+    })
+}
+
+fn check_text_issues(
+    request: &SaySynthesisRequest<'_>,
+    pieces: &[SayPiece],
+    output: &dyn AppOutput,
+) -> Result<()> {
+    // Tags are not spoken, so marked-up input is checked by its spoken text alone.
+    let spoken;
+    let text = if request.markup {
+        spoken = pieces
+            .iter()
+            .filter_map(|piece| match piece {
+                SayPiece::Speech { text, .. } => Some(text.as_str()),
+                SayPiece::Pause(_) => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        spoken.as_str()
+    } else {
+        request.text
+    };
+    let issues = lint_text(text);
+    if issues.is_empty() {
+        return Ok(());
+    }
+    if !request.quiet || request.strict {
+        for issue in &issues {
+            output.error(&format!("warning: {issue}"));
         }
     }
+    if request.strict {
+        return Err(anyhow!(
+            "Text has {} character issue(s); fix them or drop --strict",
+            issues.len()
+        ));
+    }
+    Ok(())
+}
+
+/// What gets spoken, in order, once voices are resolved.
+#[derive(Debug, Clone, PartialEq)]
+enum SayPiece {
+    Speech {
+        style_id: u32,
+        text: String,
+        prosody: Prosody,
+    },
+    Pause(Duration),
+}
+
+/// Options for one piece: the utterance's settings at `rate`, adjusted by `prosody`.
+fn piece_options(base: SynthesisOptions, rate: f32, prosody: Prosody) -> SynthesisOptions {
+    SynthesisOptions {
+        rate: rate * prosody.rate_scale,
+        pitch: base.pitch + prosody.pitch_shift,
+        intonation: base.intonation * prosody.intonation_scale,
+        ..base
+    }
+}
+
+/// Splits the text into pieces and resolves each piece's voice.
+///
+/// Plain text splits at `((voice: NAME))` markers; with `markup` the SSML-like tags
+/// also set prosody and pauses. Text without either is a single piece in the
+/// requested style. With `auto_emotion`, text in the requested style is split
+/// into sentences read in that speaker's happy or sad style where they sound it.
+/// With `breath_pauses`, speech is further split at commas and clause ends with a
+/// randomized pause between the parts.
+fn resolve_pieces(request: &SaySynthesisRequest<'_>) -> Result<Vec<SayPiece>> {
+    let pieces = resolve_voice_pieces(request)?;
+    Ok(match &request.breath_pauses {
+        Some(pauses) => insert_breaths(pieces, pauses, request.text),
+        None => pieces,
+    })
+}
+
+fn insert_breaths(pieces: Vec<SayPiece>, pauses: &BreathPauses, text: &str) -> Vec<SayPiece> {
+    // Seeded by the whole text so re-renders (e.g. for `fit_duration`) match.
+    let mut rng = SplitMix64::for_text(text);
+    let mut planned = Vec::new();
+    for piece in pieces {
+        let SayPiece::Speech {
+            style_id,
+            text,
+            prosody,
+        } = piece
+        else {
+            planned.push(piece);
+            continue;
+        };
+        for run in plan_breaths(&text, pauses, &mut rng) {
+            planned.push(SayPiece::Speech {
+                style_id,
+                text: run.text.to_string(),
+                prosody,
+            });
+            if !run.pause_after.is_zero() {
+                planned.push(SayPiece::Pause(run.pause_after));
+            }
+        }
+    }
+    planned
+}
+
+fn resolve_voice_pieces(request: &SaySynthesisRequest<'_>) -> Result<Vec<SayPiece>> {
+    let pieces = if request.markup {
+        parse_markup(request.text)?
+    } else {
+        split_voice_markers(request.text)?
+            .into_iter()
+            .map(|run| MarkupPiece::Speech {
+                text: run.text.to_string(),
+                voice: run.voice,
+                prosody: Prosody::default(),
+            })
+            .collect()
+    };
+
+    let names_voices = pieces
+        .iter()
+        .any(|piece| matches!(piece, MarkupPiece::Speech { voice: Some(_), .. }));
+    // Metadata only; numeric voices still work without a models directory.
+    let speakers = if names_voices || request.auto_emotion {
+        scan_speakers_from_model_files().unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    let mood_styles = if request.auto_emotion {
+        speaker_styles_of(request.style_id, &speakers)
+    } else {
+        Vec::new()
+    };
+
+    let mut resolved = Vec::new();
+    for piece in pieces {
+        match piece {
+            MarkupPiece::Speech {
+                text,
+                voice: None,
+                prosody,
+            } if request.auto_emotion => {
+                push_mood_sentences(
+                    &mut resolved,
+                    &text,
+                    request.style_id,
+                    &mood_styles,
+                    prosody,
+                );
+            }
+            MarkupPiece::Speech {
+                text,
+                voice,
+                prosody,
+            } => {
+                let style_id = match voice {
+                    Some(voice) => resolve_voice_among(voice, &speakers)
+                        .with_context(|| format!("Cannot resolve voice '{voice}'"))?,
+                    None => request.style_id,
+                };
+                resolved.push(SayPiece::Speech {
+                    style_id,
+                    text,
+                    prosody,
+                });
+            }
+            MarkupPiece::Break(pause) => resolved.push(SayPiece::Pause(pause)),
+        }
+    }
+    Ok(resolved)
+}
+
+/// Styles of the speaker `style_id` belongs to; empty if it is not installed.
+fn speaker_styles_of(style_id: u32, speakers: &[Speaker]) -> Vec<VoiceStyle> {
+    speakers
+        .iter()
+        .find(|speaker| speaker.styles.iter().any(|style| style.id == style_id))
+        .map(|speaker| {
+            speaker
+                .styles
+                .iter()
+                .map(|style| VoiceStyle {
+                    name: style.name.to_string(),
+                    id: style.id,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Splits `text` into sentences and reads each in the style its mood suggests,
+/// joining neighbours that end up in the same style so intonation carries over.
+fn push_mood_sentences(
+    pieces: &mut Vec<SayPiece>,
+    text: &str,
+    style_id: u32,
+    styles: &[VoiceStyle],
+    prosody: Prosody,
+) {
+    let mut current: Option<(u32, String)> = None;
+    for sentence in TextSplitter::default().split(text) {
+        let sentence_style = mood_style(classify_sentence(&sentence), styles).unwrap_or(style_id);
+        match &mut current {
+            Some((current_style, joined)) if *current_style == sentence_style => {
+                joined.push_str(&sentence);
+            }
+            _ => {
+                if let Some((style_id, text)) = current.replace((sentence_style, sentence)) {
+                    pieces.push(SayPiece::Speech {
+                        style_id,
+                        text,
+                        prosody,
+                    });
+                }
+            }
+        }
+    }
+    if let Some((style_id, text)) = current {
+        pieces.push(SayPiece::Speech {
+            style_id,
+            text,
+            prosody,
+        });
+    }
+}
+
+/// Applies the length limit to the text before anything is synthesized.
+///
+/// Returns the shortened text when the limit truncates, `None` when the text fits.
+fn limit_text_length(
+    request: &SaySynthesisRequest<'_>,
+    output: &dyn AppOutput,
+) -> Result<Option<String>> {
+    let Some(limit) = request.length_limit else {
+        return Ok(None);
+    };
+    let estimate = estimate_speech_duration(request.text, request.options.rate);
+    if estimate <= limit.max {
+        return Ok(None);
+    }
+    match limit.policy {
+        OverlongPolicy::Error => bail!(
+            "Text would take about {:.0}s to speak, over --max-duration {:.1}s; \
+             shorten it or pass --on-overlong truncate",
+            estimate.as_secs_f64(),
+            limit.max.as_secs_f64()
+        ),
+        OverlongPolicy::Truncate => {
+            let segments = crate::config::Config::default()
+                .text_splitter
+                .splitter()
+                .split(request.text);
+            let cut = cut_segments_to_estimate(&segments, request.options.rate, limit.max);
+            if !request.quiet {
+                let kept = match cut.kept_segments {
+                    0 => "part of the first sentence".to_string(),
+                    kept => format!("the first {kept} of {} sentence(s)", cut.total_segments),
+                };
+                output.info(&format!(
+                    "Truncated to {kept} to stay within --max-duration {:.1}s",
+                    limit.max.as_secs_f64()
+                ));
+            }
+            Ok(Some(cut.text))
+        }
+    }
+}
+
+/// Holds synthesized audio to the length limit, since the text estimate is approximate.
+fn limit_audio_length(request: &SaySynthesisRequest<'_>, wav_data: Vec<u8>) -> Result<Vec<u8>> {
+    let Some(limit) = request.length_limit else {
+        return Ok(wav_data);
+    };
+    let actual = wav_duration(&wav_data)?;
+    if actual <= limit.max {
+        return Ok(wav_data);
+    }
+    match limit.policy {
+        OverlongPolicy::Error => bail!(
+            "Synthesized audio runs {:.1}s, over --max-duration {:.1}s",
+            actual.as_secs_f64(),
+            limit.max.as_secs_f64()
+        ),
+        OverlongPolicy::Truncate => truncate_wav(&wav_data, limit.max),
+    }
+}
+
+/// Writes the moras as they fall in the emitted audio: after the start chime and
+/// no further than the possibly truncated speech.
+fn write_mora_timings(
+    request: &SaySynthesisRequest<'_>,
+    path: &Path,
+    moras: Vec<MoraTiming>,
+    speech: &[u8],
+) -> Result<()> {
+    let lead = request
+        .chimes
+        .start
+        .as_deref()
+        .map(wav_duration)
+        .transpose()
+        .context("Invalid start chime")?
+        .unwrap_or_default();
+    let moras = mora_timings::place(moras, Duration::ZERO, wav_duration(speech)?);
+    mora_timings::write_timings_file(path, &mora_timings::place(moras, lead, Duration::MAX))
+}
+
+/// Upper bound on re-renders while calibrating the rate for `fit_duration`.
+const MAX_FIT_PASSES: usize = 3;
+
+/// Synthesizes every piece at `rate`, along with the moras of each at its place in
+/// the joined audio while mora timings are on.
+async fn synthesize_at_rate(
+    request: &SaySynthesisRequest<'_>,
+    pieces: &[SayPiece],
+    rate: f32,
+    output: &dyn AppOutput,
+) -> Result<(Vec<u8>, Vec<MoraTiming>)> {
+    let mut assembler = Assembler::new();
+    let mut moras = Vec::new();
+    let mut elapsed = Duration::ZERO;
+    for piece in pieces {
+        let (style_id, text, prosody) = match piece {
+            SayPiece::Speech {
+                style_id,
+                text,
+                prosody,
+            } => (*style_id, text, *prosody),
+            SayPiece::Pause(pause) => {
+                assembler.push_silence(*pause);
+                elapsed += *pause;
+                continue;
+            }
+        };
+        let synth_request = DaemonSynthesisBytesRequest {
+            text,
+            style_id,
+            options: piece_options(request.options, rate, prosody),
+            socket_path: &request.socket_path,
+            auto_start_daemon: true,
+            ensure_models_if_missing: true,
+            quiet_setup_messages: request.quiet,
+        };
+        let mut cancel_rx = interrupt_receiver();
+        match synthesize_bytes_via_daemon_cancellable(&synth_request, output, cancel_rx.as_mut())
+            .await?
+        {
+            SynthesisFlowOutcome::Completed(wav_data) => {
+                assembler.push_audio(&wav_data)?;
+                if mora_timings::is_enabled() {
+                    moras.extend(mora_timings::place(
+                        mora_timings::take(),
+                        elapsed,
+                        Duration::MAX,
+                    ));
+                    elapsed += wav_duration(&wav_data)?;
+                }
+            }
+            SynthesisFlowOutcome::Canceled(_) => return Err(Interrupted.into()),
+        }
+    }
+    Ok((assembler.finish()?, moras))
+}
+
+/// Synthesizes at the requested rate, then re-renders toward `fit_duration` if set.
+async fn synthesize_fitted(
+    request: &SaySynthesisRequest<'_>,
+    pieces: &[SayPiece],
+    output: &dyn AppOutput,
+) -> Result<(Vec<u8>, Vec<MoraTiming>)> {
+    let mut rate = request.options.rate;
+    let mut rendered = synthesize_at_rate(request, pieces, rate, output).await?;
+    let Some(target) = request.fit_duration else {
+        return Ok(rendered);
+    };
+
+    for _ in 0..MAX_FIT_PASSES {
+        let actual = wav_duration(&rendered.0)?;
+        if is_within_fit_tolerance(actual, target) {
+            return Ok(rendered);
+        }
+        let fit = fit_rate(actual, rate, target);
+        if (fit.rate - rate).abs() <= f32::EPSILON {
+            break;
+        }
+        rate = fit.rate;
+        rendered = synthesize_at_rate(request, pieces, rate, output).await?;
+    }
+
+    if !request.quiet {
+        output.info(&format!(
+            "Could not fit {:.2}s exactly; rendered {:.2}s at rate {rate:.2}",
+            target.as_secs_f64(),
+            wav_duration(&rendered.0)?.as_secs_f64()
+        ));
+    }
+    Ok(rendered)
 }
 
 #[cfg(test)]
@@ -109,14 +680,57 @@ mod tests {
     use super::*;
     use crate::interface::output::BufferAppOutput;
 
+    #[test]
+    fn mood_sentences_switch_styles_and_join_neighbours() {
+        let styles =
+            [("ノーマル", 3), ("あまあま", 1), ("なみだめ", 76)].map(|(name, id)| VoiceStyle {
+                name: name.to_string(),
+                id,
+            });
+        let mut pieces = Vec::new();
+        push_mood_sentences(
+            &mut pieces,
+            "おはよう。今日は晴れ。やった！最悪だ。",
+            3,
+            &styles,
+            Prosody::default(),
+        );
+
+        let speech = |style_id, text: &str| SayPiece::Speech {
+            style_id,
+            text: text.to_string(),
+            prosody: Prosody::default(),
+        };
+        assert_eq!(
+            pieces,
+            [
+                speech(3, "おはよう。今日は晴れ。"),
+                speech(1, "やった！"),
+                speech(76, "最悪だ。"),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn rejects_empty_text_before_side_effects() {
         let output = BufferAppOutput::default();
         let request = SaySynthesisRequest {
             text: "   ",
             style_id: 1,
-            rate: 1.0,
+            options: SynthesisOptions::default(),
+            effects: AudioEffects::default(),
+            chimes: &Chimes::default(),
+            hooks: &HooksConfig::default(),
+            fit_duration: None,
+            length_limit: None,
             output_file: None,
+            output_format: OutputFormat::Wav,
+            timings_file: None,
+            tee: None,
+            markup: false,
+            breath_pauses: None,
+            auto_emotion: false,
+            strict: false,
             quiet: true,
             socket_path: PathBuf::from("/tmp/unused.sock"),
         };
@@ -133,4 +747,198 @@ mod tests {
         assert!(output.infos().is_empty());
         assert!(output.errors().is_empty());
     }
+
+    #[tokio::test]
+    async fn strict_mode_rejects_flagged_text_before_synthesis() {
+        let output = BufferAppOutput::default();
+        let request = SaySynthesisRequest {
+            text: "こんにちは\u{7}",
+            style_id: 1,
+            options: SynthesisOptions::default(),
+            effects: AudioEffects::default(),
+            chimes: &Chimes::default(),
+            hooks: &HooksConfig::default(),
+            fit_duration: None,
+            length_limit: None,
+            output_file: None,
+            output_format: OutputFormat::Wav,
+            timings_file: None,
+            tee: None,
+            markup: false,
+            breath_pauses: None,
+            auto_emotion: false,
+            strict: true,
+            quiet: true,
+            socket_path: PathBuf::from("/tmp/unused.sock"),
+        };
+
+        let error = run_say_synthesis_with_output(request, &output)
+            .await
+            .expect_err("expected strict failure");
+
+        assert!(error.to_string().contains("--strict"));
+        assert_eq!(output.errors().len(), 1);
+        assert!(output.errors()[0].contains("1:6: control character U+0007"));
+    }
+
+    #[tokio::test]
+    async fn overlong_text_fails_before_synthesis_by_default() {
+        let output = BufferAppOutput::default();
+        let text = "あいうえお。".repeat(200);
+        let request = SaySynthesisRequest {
+            text: &text,
+            style_id: 1,
+            options: SynthesisOptions::default(),
+            effects: AudioEffects::default(),
+            chimes: &Chimes::default(),
+            hooks: &HooksConfig::default(),
+            fit_duration: None,
+            length_limit: Some(LengthLimit {
+                max: Duration::from_secs(60),
+                policy: OverlongPolicy::default(),
+            }),
+            output_file: None,
+            output_format: OutputFormat::Wav,
+            timings_file: None,
+            tee: None,
+            markup: false,
+            breath_pauses: None,
+            auto_emotion: false,
+            strict: false,
+            quiet: true,
+            socket_path: PathBuf::from("/tmp/unused.sock"),
+        };
+
+        let error = run_say_synthesis_with_output(request, &output)
+            .await
+            .expect_err("expected length limit failure");
+
+        assert!(error.to_string().contains("--max-duration 60.0s"));
+        assert!(output.infos().is_empty());
+    }
+
+    #[tokio::test]
+    async fn out_of_range_intonation_fails_before_synthesis() {
+        let output = BufferAppOutput::default();
+        let request = SaySynthesisRequest {
+            text: "こんにちは",
+            style_id: 1,
+            options: SynthesisOptions {
+                intonation: 3.0,
+                ..SynthesisOptions::default()
+            },
+            effects: AudioEffects::default(),
+            chimes: &Chimes::default(),
+            hooks: &HooksConfig::default(),
+            fit_duration: None,
+            length_limit: None,
+            output_file: None,
+            output_format: OutputFormat::Wav,
+            timings_file: None,
+            tee: None,
+            markup: false,
+            breath_pauses: None,
+            auto_emotion: false,
+            strict: false,
+            quiet: true,
+            socket_path: PathBuf::from("/tmp/unused.sock"),
+        };
+
+        let error = run_say_synthesis_with_output(request, &output)
+            .await
+            .expect_err("expected intonation validation error");
+
+        assert!(error.to_string().contains("Intonation must be between"));
+    }
+
+    #[test]
+    fn voice_markers_switch_style_ids() {
+        let request = SaySynthesisRequest {
+            text: "こんにちは。((voice: 8))やっほー。((voice: 3))なのだ",
+            style_id: 1,
+            options: SynthesisOptions::default(),
+            effects: AudioEffects::default(),
+            chimes: &Chimes::default(),
+            hooks: &HooksConfig::default(),
+            fit_duration: None,
+            length_limit: None,
+            output_file: None,
+            output_format: OutputFormat::Wav,
+            timings_file: None,
+            tee: None,
+            markup: false,
+            breath_pauses: None,
+            auto_emotion: false,
+            strict: false,
+            quiet: true,
+            socket_path: PathBuf::from("/tmp/unused.sock"),
+        };
+
+        assert_eq!(
+            resolve_pieces(&request).expect("numeric markers"),
+            [(1, "こんにちは。"), (8, "やっほー。"), (3, "なのだ")].map(|(style_id, text)| {
+                SayPiece::Speech {
+                    style_id,
+                    text: text.to_string(),
+                    prosody: Prosody::default(),
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn markup_sets_voices_prosody_and_pauses() {
+        let request = SaySynthesisRequest {
+            text: "はじめ<break time=\"1s\"/><voice id=\"8\"><prosody rate=\"1.5\">はやい</prosody></voice>",
+            style_id: 1,
+            options: SynthesisOptions::default(),
+            effects: AudioEffects::default(),
+            chimes: &Chimes::default(),
+            hooks: &HooksConfig::default(),
+            fit_duration: None,
+            length_limit: None,
+            output_file: None,
+            output_format: OutputFormat::Wav,
+            timings_file: None,
+            tee: None,
+            markup: true,
+            breath_pauses: None,
+            auto_emotion: false,
+            strict: false,
+            quiet: true,
+            socket_path: PathBuf::from("/tmp/unused.sock"),
+        };
+
+        let pieces = resolve_pieces(&request).expect("valid markup");
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(pieces[1], SayPiece::Pause(Duration::from_secs(1)));
+        let SayPiece::Speech {
+            style_id, prosody, ..
+        } = &pieces[2]
+        else {
+            panic!("expected speech, got {:?}", pieces[2]);
+        };
+        assert_eq!(*style_id, 8);
+        assert_eq!(piece_options(request.options, 1.0, *prosody).rate, 1.5);
+    }
+
+    #[test]
+    fn breath_pauses_split_speech_between_explicit_breaks() {
+        let pieces = vec![
+            SayPiece::Speech {
+                style_id: 3,
+                text: "はい、そうです。".to_string(),
+                prosody: Prosody::default(),
+            },
+            SayPiece::Pause(Duration::from_secs(1)),
+        ];
+        let pauses = BreathPauses::parse("comma=150ms").expect("spec");
+
+        let planned = insert_breaths(pieces, &pauses, "はい、そうです。");
+        assert_eq!(planned.len(), 4);
+        assert!(matches!(&planned[0], SayPiece::Speech { text, .. } if text == "はい、"));
+        assert_eq!(planned[1], SayPiece::Pause(Duration::from_millis(150)));
+        assert!(matches!(&planned[2], SayPiece::Speech { text, .. } if text == "そうです。"));
+        assert_eq!(planned[3], SayPiece::Pause(Duration::from_secs(1)));
+    }
 }
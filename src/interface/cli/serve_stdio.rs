@@ -0,0 +1,265 @@
+use anyhow::{Context, Result, anyhow};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::infrastructure::daemon::client::DaemonClient;
+use crate::infrastructure::ipc::{DEFAULT_SYNTHESIS_PITCH, SynthesisOptions};
+use crate::infrastructure::voicevox::Speaker;
+use crate::interface::cli::voice_selector::resolve_voice_input;
+use crate::interface::synthesis::flow::{
+    connect_daemon_client_auto_start, validate_text_synthesis_request,
+};
+
+pub struct ServeStdioRequest {
+    pub default_style_id: u32,
+    pub default_rate: f32,
+    pub socket_path: PathBuf,
+}
+
+/// One call on the JSON-lines protocol, selected by the `method` field.
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+enum StdioCall {
+    Synthesize {
+        text: String,
+        style_id: Option<u32>,
+        voice: Option<String>,
+        rate: Option<f32>,
+        pitch: Option<f32>,
+    },
+    Speakers,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum StdioPayload {
+    Audio { wav_base64: String },
+    Speakers { speakers: Vec<Speaker> },
+    Error { error: String },
+}
+
+#[derive(Debug, Serialize)]
+struct StdioResponse {
+    id: Value,
+    ok: bool,
+    #[serde(flatten)]
+    payload: StdioPayload,
+}
+
+impl StdioResponse {
+    fn from_result(id: Value, result: Result<StdioPayload>) -> Self {
+        match result {
+            Ok(payload) => Self {
+                id,
+                ok: true,
+                payload,
+            },
+            Err(error) => Self {
+                id,
+                ok: false,
+                payload: StdioPayload::Error {
+                    error: format!("{error:#}"),
+                },
+            },
+        }
+    }
+}
+
+/// Splits a request line into its echoed `id` and the parsed call.
+///
+/// The `id` is recovered even when the call itself is malformed so the caller can
+/// still correlate the error response.
+pub(crate) fn parse_request_line<T: DeserializeOwned>(line: &str) -> (Value, Result<T>) {
+    let mut value: Value = match serde_json::from_str(line) {
+        Ok(value) => value,
+        Err(error) => return (Value::Null, Err(anyhow!("Invalid JSON: {error}"))),
+    };
+    let id = value
+        .as_object_mut()
+        .and_then(|object| object.remove("id"))
+        .unwrap_or(Value::Null);
+    let call = serde_json::from_value(value).map_err(|error| anyhow!("Invalid request: {error}"));
+    (id, call)
+}
+
+/// A daemon connection kept across the requests of a line-oriented session.
+pub(crate) struct DaemonSession {
+    socket_path: PathBuf,
+    client: Option<DaemonClient>,
+}
+
+impl DaemonSession {
+    pub(crate) const fn new(socket_path: PathBuf) -> Self {
+        Self {
+            socket_path,
+            client: None,
+        }
+    }
+
+    /// Returns the open connection, connecting (and starting the daemon) if needed.
+    pub(crate) async fn client(&mut self) -> Result<&mut DaemonClient> {
+        let client = match self.client.take() {
+            Some(client) => client,
+            None => connect_daemon_client_auto_start(&self.socket_path).await?,
+        };
+        Ok(self.client.insert(client))
+    }
+
+    /// Drops the connection so the next request reconnects, in case the daemon went away.
+    pub(crate) fn reset(&mut self) {
+        self.client = None;
+    }
+}
+
+struct StdioSession {
+    request: ServeStdioRequest,
+    daemon: DaemonSession,
+}
+
+impl StdioSession {
+    async fn handle(&mut self, call: StdioCall) -> Result<StdioPayload> {
+        let result = self.dispatch(call).await;
+        if result.is_err() {
+            self.daemon.reset();
+        }
+        result
+    }
+
+    async fn dispatch(&mut self, call: StdioCall) -> Result<StdioPayload> {
+        match call {
+            StdioCall::Synthesize {
+                text,
+                style_id,
+                voice,
+                rate,
+                pitch,
+            } => {
+                let style_id = match (style_id, voice.as_deref()) {
+                    (Some(id), _) => id,
+                    (None, Some(voice)) => resolve_voice_input(voice)?.0,
+                    (None, None) => self.request.default_style_id,
+                };
+                let options = SynthesisOptions {
+                    rate: rate.unwrap_or(self.request.default_rate),
+                    pitch: pitch.unwrap_or(DEFAULT_SYNTHESIS_PITCH),
+                    ..SynthesisOptions::default()
+                };
+                validate_text_synthesis_request(&text, style_id, options.rate, options.pitch)?;
+                let wav_data = self
+                    .daemon
+                    .client()
+                    .await?
+                    .synthesize(&text, style_id, options)
+                    .await?;
+                Ok(StdioPayload::Audio {
+                    wav_base64: BASE64.encode(wav_data),
+                })
+            }
+            StdioCall::Speakers => Ok(StdioPayload::Speakers {
+                speakers: self.daemon.client().await?.list_speakers().await?,
+            }),
+        }
+    }
+}
+
+/// Serves a JSON-lines request/response protocol over stdin/stdout.
+///
+/// Each stdin line is one request object with a `method` (`synthesize` or `speakers`)
+/// and an optional `id` that is echoed back. Each request produces exactly one stdout
+/// line; synthesized audio is returned as base64-encoded WAV. The loop ends at EOF.
+///
+/// # Errors
+///
+/// Returns an error only if stdin cannot be read or stdout cannot be written;
+/// per-request failures are reported in the response line.
+pub async fn run_serve_stdio(request: ServeStdioRequest) -> Result<()> {
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+    let mut session = StdioSession {
+        daemon: DaemonSession::new(request.socket_path.clone()),
+        request,
+    };
+
+    while let Some(line) = lines.next_line().await.context("Failed to read stdin")? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (id, call) = parse_request_line::<StdioCall>(&line);
+        let result = match call {
+            Ok(call) => session.handle(call).await,
+            Err(error) => Err(error),
+        };
+        write_json_line(&mut stdout, &StdioResponse::from_result(id, result)).await?;
+    }
+    Ok(())
+}
+
+/// Writes one JSON response line and flushes it, so the driving program sees it at once.
+pub(crate) async fn write_json_line(
+    stdout: &mut tokio::io::Stdout,
+    response: &impl Serialize,
+) -> Result<()> {
+    let mut encoded = serde_json::to_vec(response).context("Failed to encode response")?;
+    encoded.push(b'\n');
+    stdout
+        .write_all(&encoded)
+        .await
+        .context("Failed to write stdout")?;
+    stdout.flush().await.context("Failed to flush stdout")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_calls_and_keeps_id_for_bad_requests() {
+        let (id, call) = parse_request_line::<StdioCall>(
+            r#"{"id": 7, "method": "synthesize", "text": "こんにちは", "rate": 1.2}"#,
+        );
+        assert_eq!(id, Value::from(7));
+        assert_eq!(
+            call.expect("valid call"),
+            StdioCall::Synthesize {
+                text: "こんにちは".to_owned(),
+                style_id: None,
+                voice: None,
+                rate: Some(1.2),
+                pitch: None,
+            }
+        );
+
+        let (id, call) = parse_request_line::<StdioCall>(r#"{"id": "a", "method": "sing"}"#);
+        assert_eq!(id, Value::from("a"));
+        assert!(call.is_err());
+
+        let (id, call) = parse_request_line::<StdioCall>("not json");
+        assert_eq!(id, Value::Null);
+        assert!(call.is_err());
+    }
+
+    #[test]
+    fn responses_flatten_payload_next_to_id() {
+        let response = StdioResponse::from_result(
+            Value::from(1),
+            Ok(StdioPayload::Audio {
+                wav_base64: BASE64.encode(b"RIFF"),
+            }),
+        );
+        assert_eq!(
+            serde_json::to_value(response).expect("serializable"),
+            serde_json::json!({"id": 1, "ok": true, "wav_base64": "UklGRg=="})
+        );
+
+        let response = StdioResponse::from_result(Value::Null, Err(anyhow!("boom")));
+        assert_eq!(
+            serde_json::to_value(response).expect("serializable"),
+            serde_json::json!({"id": null, "ok": false, "error": "boom"})
+        );
+    }
+}
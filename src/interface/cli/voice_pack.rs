@@ -0,0 +1,328 @@
+use anyhow::{Context, Result, anyhow, bail, ensure};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::domain::checksums::sha256_hex;
+use crate::domain::voice_pack::{PACK_MANIFEST_FILE, PACKS_SUBDIR, PackManifest};
+use crate::infrastructure::paths::find_models_dir_client;
+use crate::interface::cli::daemon_cli::notify_daemons_of_changes;
+use crate::interface::{AppOutput, StdAppOutput};
+
+/// Every VVM is itself a zip archive.
+const VVM_MAGIC: &[u8] = b"PK\x03\x04";
+
+#[derive(Debug)]
+pub struct InstallPackRequest<'a> {
+    pub pack: &'a Path,
+    /// Replace models that another pack, or no pack, installed.
+    pub force: bool,
+}
+
+/// What an install did to each model file in the pack.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PackInstallSummary {
+    pub installed: Vec<String>,
+    pub updated: Vec<String>,
+    pub unchanged: Vec<String>,
+}
+
+impl PackInstallSummary {
+    fn changed(&self) -> bool {
+        !self.installed.is_empty() || !self.updated.is_empty()
+    }
+}
+
+/// Installs a voice pack zip into the models directory and asks running daemons
+/// to rescan it.
+///
+/// The manifest, license, and models are extracted and verified before anything
+/// in the models directory is touched. The manifest and license are kept under
+/// `packs/<name>/` there, which is how later installs tell which models a pack owns.
+///
+/// # Errors
+///
+/// Returns an error if the archive cannot be read, the manifest is invalid, a file
+/// is missing or fails its checksum, a model would replace one the pack does not
+/// own without `force`, or the models cannot be written.
+pub fn run_install_pack(request: &InstallPackRequest<'_>) -> Result<()> {
+    let output = StdAppOutput;
+    run_install_pack_with_output(request, &output)
+}
+
+pub fn run_install_pack_with_output(
+    request: &InstallPackRequest<'_>,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let extracted = tempfile::tempdir().context("Failed to create extraction directory")?;
+    let manifest = extract_pack(request.pack, extracted.path())
+        .with_context(|| format!("Invalid voice pack {}", request.pack.display()))?;
+    let models_dir = find_models_dir_client()?;
+    let summary = install_extracted_pack(&manifest, extracted.path(), &models_dir, request.force)?;
+
+    output.info(&format!(
+        "Installed {} {} into {}: {} new, {} updated, {} unchanged (license: {})",
+        manifest.name,
+        manifest.version,
+        models_dir.display(),
+        summary.installed.len(),
+        summary.updated.len(),
+        summary.unchanged.len(),
+        pack_record_dir(&models_dir, &manifest.name)
+            .join(&manifest.license)
+            .display()
+    ));
+    if summary.changed() {
+        notify_daemons_of_changes(output);
+    }
+    Ok(())
+}
+
+/// Extracts the manifest, then exactly the files it names, from the archive root.
+fn extract_pack(pack: &Path, dir: &Path) -> Result<PackManifest> {
+    ensure!(pack.is_file(), "{} is not a file", pack.display());
+    unzip_entries(pack, dir, &[PACK_MANIFEST_FILE])?;
+    let manifest = PackManifest::parse(&read_extracted(dir, PACK_MANIFEST_FILE)?)
+        .with_context(|| format!("Invalid {PACK_MANIFEST_FILE}"))?;
+    unzip_entries(pack, dir, &manifest.files().collect::<Vec<_>>())?;
+    Ok(manifest)
+}
+
+fn unzip_entries(pack: &Path, dir: &Path, entries: &[&str]) -> Result<()> {
+    let output = Command::new(crate::config::command_path_or_fallback(
+        crate::config::SYSTEM_UNZIP_PATH,
+        "unzip",
+    ))
+    .arg("-qq")
+    .arg("-o")
+    .arg(pack)
+    .args(entries)
+    .arg("-d")
+    .arg(dir)
+    .output()
+    .context("Failed to run unzip")?;
+    if !output.status.success() {
+        bail!(
+            "unzip could not extract {}: {}",
+            entries.join(", "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Reads an extracted file, refusing anything but a regular file.
+fn read_extracted(dir: &Path, name: &str) -> Result<String> {
+    let path = regular_file(dir, name)?;
+    fs::read_to_string(&path).with_context(|| format!("Failed to read {name}"))
+}
+
+fn regular_file(dir: &Path, name: &str) -> Result<PathBuf> {
+    let path = dir.join(name);
+    let metadata = fs::symlink_metadata(&path).with_context(|| format!("Pack has no {name}"))?;
+    ensure!(
+        metadata.is_file(),
+        "{name} in the pack is not a regular file"
+    );
+    Ok(path)
+}
+
+/// Verifies an extracted pack and copies its models into `models_dir`.
+///
+/// A model whose file already holds the same content is left alone. A different
+/// file is replaced only if this pack installed it before, or with `force`.
+///
+/// # Errors
+///
+/// Returns an error if a file is missing, a model fails its checksum or is not a
+/// VVM, a conflict is found without `force`, or writing fails. Conflicts and bad
+/// files are found before any model is written.
+pub fn install_extracted_pack(
+    manifest: &PackManifest,
+    dir: &Path,
+    models_dir: &Path,
+    force: bool,
+) -> Result<PackInstallSummary> {
+    regular_file(dir, &manifest.license)?;
+    let owners = model_owners(models_dir)?;
+
+    let mut summary = PackInstallSummary::default();
+    let mut pending = Vec::new();
+    for model in &manifest.models {
+        let source = regular_file(dir, &model.file)?;
+        let data = fs::read(&source).with_context(|| format!("Failed to read {}", model.file))?;
+        ensure!(
+            sha256_hex(&data) == model.sha256,
+            "{} does not match its sha256 in {PACK_MANIFEST_FILE}",
+            model.file
+        );
+        ensure!(
+            data.starts_with(VVM_MAGIC),
+            "{} is not a VVM model",
+            model.file
+        );
+
+        let target = models_dir.join(&model.file);
+        let existing = match fs::read(&target) {
+            Ok(existing) => Some(existing),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => None,
+            Err(error) => {
+                return Err(error).with_context(|| format!("Failed to read {}", target.display()));
+            }
+        };
+        match existing {
+            None => summary.installed.push(model.file.clone()),
+            Some(existing) if sha256_hex(&existing) == model.sha256 => {
+                summary.unchanged.push(model.file.clone());
+                continue;
+            }
+            Some(_) => {
+                let owner = owners.get(&model.file).map(String::as_str);
+                if owner != Some(manifest.name.as_str()) && !force {
+                    let installed_by = owner.map_or_else(
+                        || "not from a voice pack".to_string(),
+                        |owner| format!("installed by pack '{owner}'"),
+                    );
+                    bail!(
+                        "{} already exists ({installed_by}); use --force to replace it",
+                        target.display()
+                    );
+                }
+                summary.updated.push(model.file.clone());
+            }
+        }
+        pending.push((target, data));
+    }
+
+    fs::create_dir_all(models_dir)
+        .with_context(|| format!("Failed to create {}", models_dir.display()))?;
+    for (target, data) in pending {
+        write_replacing(&target, &data)?;
+    }
+
+    let record_dir = pack_record_dir(models_dir, &manifest.name);
+    fs::create_dir_all(&record_dir)
+        .with_context(|| format!("Failed to create {}", record_dir.display()))?;
+    for name in [PACK_MANIFEST_FILE, manifest.license.as_str()] {
+        let data = fs::read(dir.join(name)).with_context(|| format!("Failed to read {name}"))?;
+        write_replacing(&record_dir.join(name), &data)?;
+    }
+    Ok(summary)
+}
+
+fn pack_record_dir(models_dir: &Path, pack_name: &str) -> PathBuf {
+    models_dir.join(PACKS_SUBDIR).join(pack_name)
+}
+
+/// Maps each model file to the pack whose record lists it.
+fn model_owners(models_dir: &Path) -> Result<HashMap<String, String>> {
+    let packs_dir = models_dir.join(PACKS_SUBDIR);
+    let entries = match fs::read_dir(&packs_dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(error) => {
+            return Err(error).with_context(|| format!("Failed to read {}", packs_dir.display()));
+        }
+    };
+
+    let mut owners = HashMap::new();
+    for entry in entries {
+        let record = entry?.path().join(PACK_MANIFEST_FILE);
+        if !record.is_file() {
+            continue;
+        }
+        let manifest = fs::read_to_string(&record)
+            .map_err(anyhow::Error::from)
+            .and_then(|source| PackManifest::parse(&source))
+            .with_context(|| format!("Invalid pack record {}", record.display()))?;
+        for model in manifest.models {
+            owners.insert(model.file, manifest.name.clone());
+        }
+    }
+    Ok(owners)
+}
+
+/// Writes through a sibling temporary file so a daemon never loads a partial model.
+fn write_replacing(target: &Path, data: &[u8]) -> Result<()> {
+    let file_name = target
+        .file_name()
+        .ok_or_else(|| anyhow!("{} has no file name", target.display()))?;
+    let mut temp_name = file_name.to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = target.with_file_name(temp_name);
+    fs::write(&temp_path, data)
+        .with_context(|| format!("Failed to write {}", temp_path.display()))?;
+    fs::rename(&temp_path, target)
+        .with_context(|| format!("Failed to install {}", target.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Pack {
+        dir: tempfile::TempDir,
+        manifest: PackManifest,
+    }
+
+    fn pack(name: &str, models: &[(&str, &[u8])]) -> Pack {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let mut source = format!("name = \"{name}\"\nversion = \"1\"\nlicense = \"LICENSE\"\n");
+        for (file, body) in models {
+            let data = [VVM_MAGIC, body].concat();
+            fs::write(dir.path().join(file), &data).expect("write model");
+            source.push_str(&format!(
+                "[[models]]\nfile = \"{file}\"\nsha256 = \"{}\"\n",
+                sha256_hex(&data)
+            ));
+        }
+        fs::write(dir.path().join("LICENSE"), "terms").expect("write license");
+        fs::write(dir.path().join(PACK_MANIFEST_FILE), &source).expect("write manifest");
+        let manifest = PackManifest::parse(&source).expect("valid manifest");
+        Pack { dir, manifest }
+    }
+
+    fn install(pack: &Pack, models_dir: &Path, force: bool) -> Result<PackInstallSummary> {
+        install_extracted_pack(&pack.manifest, pack.dir.path(), models_dir, force)
+    }
+
+    #[test]
+    fn installs_updates_and_protects_other_packs_models() {
+        let models = tempfile::tempdir().expect("models dir");
+        let first = pack("alpha", &[("101.vvm", b"v1"), ("102.vvm", b"v1")]);
+        let summary = install(&first, models.path(), false).expect("fresh install");
+        assert_eq!(summary.installed, vec!["101.vvm", "102.vvm"]);
+        assert!(models.path().join("packs/alpha/LICENSE").is_file());
+
+        let upgrade = pack("alpha", &[("101.vvm", b"v2"), ("102.vvm", b"v1")]);
+        let summary = install(&upgrade, models.path(), false).expect("same pack updates");
+        assert_eq!(summary.updated, vec!["101.vvm"]);
+        assert_eq!(summary.unchanged, vec!["102.vvm"]);
+
+        let other = pack("beta", &[("101.vvm", b"other"), ("103.vvm", b"new")]);
+        let error = install(&other, models.path(), false).expect_err("owned by alpha");
+        assert!(error.to_string().contains("installed by pack 'alpha'"));
+        assert!(!models.path().join("103.vvm").exists());
+
+        let summary = install(&other, models.path(), true).expect("forced");
+        assert_eq!(summary.updated, vec!["101.vvm"]);
+        assert_eq!(
+            fs::read(models.path().join("101.vvm")).expect("model"),
+            [VVM_MAGIC, b"other"].concat()
+        );
+    }
+
+    #[test]
+    fn rejects_checksum_mismatches_before_writing() {
+        let models = tempfile::tempdir().expect("models dir");
+        let broken = pack("alpha", &[("101.vvm", b"v1")]);
+        fs::write(broken.dir.path().join("101.vvm"), b"tampered").expect("tamper");
+
+        let error = install(&broken, models.path(), false).expect_err("checksum mismatch");
+
+        assert!(error.to_string().contains("does not match its sha256"));
+        assert!(!models.path().join("101.vvm").exists());
+        assert!(!models.path().join(PACKS_SUBDIR).exists());
+    }
+}
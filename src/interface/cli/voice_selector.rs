@@ -1,18 +1,127 @@
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 
-use crate::infrastructure::voicevox::scan_available_models;
+use crate::domain::voice::{SpeakerStyles, VoiceId, VoiceStyle, is_speaker_uuid};
+use crate::domain::voice_alias::{alias_key, expand_alias};
+use crate::domain::voice_name::resolve_voice_name;
+use crate::infrastructure::paths::voice_aliases_path;
+use crate::infrastructure::voice_aliases::load_voice_aliases;
+use crate::infrastructure::voicevox::{
+    Speaker, find_speaker_style, scan_available_models, scan_speakers_from_model_files,
+};
+
+/// A `<speaker-uuid>[:<style-name>]` voice reference, the partial form of [`VoiceId`].
+///
+/// Speaker UUIDs come from the model metadata and stay the same when model files are
+/// renumbered between releases, unlike style and model IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpeakerUuidRef<'a> {
+    pub uuid: &'a str,
+    pub style_name: Option<&'a str>,
+}
+
+impl<'a> SpeakerUuidRef<'a> {
+    /// Parses `uuid`, `uuid:style`, or the canonical `uuid/style` [`VoiceId`] form;
+    /// returns `None` unless the first part is a UUID.
+    #[must_use]
+    pub fn parse(input: &'a str) -> Option<Self> {
+        let (uuid, style_name) = match input.trim().split_once([':', VoiceId::SEPARATOR]) {
+            Some((uuid, style_name)) => (uuid, Some(style_name.trim())),
+            None => (input.trim(), None),
+        };
+        is_speaker_uuid(uuid).then_some(Self {
+            uuid,
+            style_name: style_name.filter(|name| !name.is_empty()),
+        })
+    }
+
+    /// Picks the named style, or the speaker's first style when no name is given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the available styles if the speaker or style is unknown.
+    pub fn select(&self, speakers: &[Speaker]) -> Result<(u32, String)> {
+        let matching = speakers
+            .iter()
+            .filter(|speaker| speaker.speaker_uuid.eq_ignore_ascii_case(self.uuid))
+            .collect::<Vec<_>>();
+        let Some(first) = matching.first() else {
+            return Err(anyhow!(
+                "No installed speaker has UUID {}. Use --list-speakers to see installed voices.",
+                self.uuid
+            ));
+        };
+        find_speaker_style(speakers, self.uuid, self.style_name)
+            .map(|(speaker, style)| (style.id, format!("{} ({})", speaker.name, style.name)))
+            .ok_or_else(|| {
+                let names = matching
+                    .iter()
+                    .flat_map(|speaker| speaker.styles.iter())
+                    .map(|style| style.name.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                anyhow!(
+                    "Speaker {} has no style '{}' (available: {names})",
+                    first.name,
+                    self.style_name.unwrap_or_default()
+                )
+            })
+    }
+}
+
+/// Resolves a speaker UUID reference or [`VoiceId`] against installed model metadata.
+///
+/// # Errors
+///
+/// Returns an error if the reference is malformed, models cannot be scanned, or no
+/// installed speaker/style matches.
+pub fn resolve_speaker_uuid(reference: &str) -> Result<(u32, String)> {
+    let reference = SpeakerUuidRef::parse(reference).ok_or_else(|| {
+        anyhow!("Invalid speaker UUID '{reference}' (expected <uuid>[/<style-name>])")
+    })?;
+    let speakers = scan_speakers_from_model_files()
+        .map_err(|e| anyhow!("Failed to read speaker metadata from models: {e}"))?;
+    reference.select(&speakers)
+}
+
+/// Rejects `morph(base, target, weight)` blends up front: VOICEVOX Core has no
+/// style morphing, so such a voice would otherwise fail as an unknown name.
+fn reject_style_morph(voice_input: &str) -> Result<()> {
+    if voice_input.starts_with("morph(") {
+        return Err(anyhow!(
+            "Voice '{voice_input}' blends styles, which VOICEVOX Core cannot synthesize. \
+             Pick the closer style and adjust --pitch and --intonation"
+        ));
+    }
+    Ok(())
+}
 
 /// Resolves CLI voice input into a style/model ID and description.
 ///
+/// Accepts a style ID, a model ID, a canonical `<speaker-uuid>/<style-name>` voice ID,
+/// a bare speaker UUID, or a user-defined alias for any voice
+/// [`resolve_voice_among`] accepts.
+///
 /// # Errors
 ///
-/// Returns an error if model discovery fails or the input cannot be resolved.
+/// Returns an error if the aliases file is unreadable, model discovery fails, or
+/// the input cannot be resolved.
 pub fn resolve_voice_input(voice_input: &str) -> Result<(u32, String)> {
     let voice_input = voice_input.trim();
 
     if voice_input == "?" {
         return Err(anyhow!("Voice help is a CLI concern."));
     }
+    reject_style_morph(voice_input)?;
+    if let Some(target) = load_voice_aliases(&voice_aliases_path())?.remove(&alias_key(voice_input))
+    {
+        let speakers = scan_speakers_from_model_files().unwrap_or_default();
+        let style_id = resolve_voice_among(&target, &speakers)
+            .with_context(|| format!("Alias '{voice_input}' points at '{target}'"))?;
+        return Ok((style_id, format!("Alias '{voice_input}' ({target})")));
+    }
+    if SpeakerUuidRef::parse(voice_input).is_some() {
+        return resolve_speaker_uuid(voice_input);
+    }
 
     voice_input
         .parse::<u32>()
@@ -22,6 +131,44 @@ pub fn resolve_voice_input(voice_input: &str) -> Result<(u32, String)> {
         .map_or_else(|| try_resolve_from_available_models(voice_input), Ok)
 }
 
+/// Resolves a style ID, speaker UUID, [`VoiceId`], voice name such as `tsumugi`
+/// or `zundamon-whisper`, or user-defined alias against already scanned speakers.
+///
+/// # Errors
+///
+/// Returns an error if the aliases file is unreadable or no installed
+/// speaker/style matches.
+pub fn resolve_voice_among(voice_input: &str, speakers: &[Speaker]) -> Result<u32> {
+    let aliases = load_voice_aliases(&voice_aliases_path())?;
+    let voice_input = expand_alias(&aliases, voice_input.trim()).trim();
+    reject_style_morph(voice_input)?;
+    if let Some(style_id) = voice_input
+        .parse::<u32>()
+        .ok()
+        .filter(|&id| id > 0 && id < 1000)
+    {
+        return Ok(style_id);
+    }
+    if let Some(reference) = SpeakerUuidRef::parse(voice_input) {
+        return reference.select(speakers).map(|(style_id, _)| style_id);
+    }
+    let speaker_styles = speakers
+        .iter()
+        .map(|speaker| SpeakerStyles {
+            speaker_name: speaker.name.to_string(),
+            styles: speaker
+                .styles
+                .iter()
+                .map(|style| VoiceStyle {
+                    name: style.name.to_string(),
+                    id: style.id,
+                })
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+    resolve_voice_name(voice_input, &speaker_styles)
+}
+
 fn try_resolve_from_available_models(voice_input: &str) -> Result<(u32, String)> {
     let available_models = scan_available_models().map_err(|e| {
         anyhow!(
@@ -64,7 +211,8 @@ fn try_resolve_from_available_models(voice_input: &str) -> Result<(u32, String)>
 
 #[cfg(test)]
 mod tests {
-    use super::resolve_voice_input;
+    use super::{SpeakerUuidRef, resolve_voice_among, resolve_voice_input};
+    use crate::infrastructure::voicevox::{Speaker, Style};
 
     #[test]
     fn resolve_voice_input_trims_direct_style_id() {
@@ -73,4 +221,50 @@ mod tests {
         assert_eq!(style_id, 3);
         assert_eq!(description, "Style ID 3");
     }
+
+    #[test]
+    fn style_morphs_are_refused_with_an_explanation() {
+        for error in [
+            resolve_voice_input("morph(3,76,0.3)").expect_err("morph"),
+            resolve_voice_among(" morph(3, 76, 0.3)", &[]).expect_err("morph"),
+        ] {
+            assert!(error.to_string().contains("cannot synthesize"), "{error}");
+        }
+    }
+
+    #[test]
+    fn speaker_uuid_selects_named_or_first_style() {
+        // Covers both the `--speaker-uuid` form and the canonical `uuid/style` form.
+        let style = |name: &str, id| Style {
+            name: name.into(),
+            id,
+            style_type: None,
+            loaded: false,
+        };
+        let speakers = vec![Speaker {
+            name: "ずんだもん".into(),
+            speaker_uuid: "388f246b-8c41-4ac1-8e2d-5d79f3ff56d9".into(),
+            styles: vec![style("ノーマル", 3), style("あまあま", 1)].into(),
+            version: "0.1.0".into(),
+        }];
+
+        let named = SpeakerUuidRef::parse("388F246B-8C41-4AC1-8E2D-5D79F3FF56D9/あまあま")
+            .expect("uuid with style");
+        assert_eq!(
+            named.select(&speakers).expect("style exists"),
+            (1, "ずんだもん (あまあま)".to_owned())
+        );
+        let first =
+            SpeakerUuidRef::parse("388f246b-8c41-4ac1-8e2d-5d79f3ff56d9").expect("bare uuid");
+        assert_eq!(first.select(&speakers).expect("first style").0, 3);
+
+        assert!(
+            SpeakerUuidRef::parse("388f246b-8c41-4ac1-8e2d-5d79f3ff56d9:ささやき")
+                .expect("uuid")
+                .select(&speakers)
+                .is_err()
+        );
+        assert_eq!(SpeakerUuidRef::parse("zundamon"), None);
+        assert_eq!(SpeakerUuidRef::parse("3"), None);
+    }
 }
@@ -0,0 +1,98 @@
+use anyhow::{Context, Result, anyhow};
+use std::path::{Path, PathBuf};
+
+use crate::domain::vvproj::{VvprojLine, build_vvproj};
+use crate::infrastructure::ipc::{DEFAULT_SYNTHESIS_PITCH, SynthesisOptions};
+use crate::interface::synthesis::flow::{
+    connect_daemon_client_auto_start, ensure_startup_resources, validate_text_synthesis_request,
+};
+use crate::interface::{AppOutput, StdAppOutput};
+
+pub struct VvprojExportRequest<'a> {
+    pub text: &'a str,
+    pub style_id: u32,
+    pub rate: f32,
+    pub output_file: &'a Path,
+    pub quiet: bool,
+    pub socket_path: PathBuf,
+}
+
+/// Writes the text as a VOICEVOX editor project instead of speaking it.
+///
+/// Each non-empty input line becomes one editor line carrying the daemon's audio
+/// query, so accents and timing can be fine-tuned in the GUI from where the CLI
+/// left off.
+///
+/// # Errors
+///
+/// Returns an error if the text has no lines, a line is invalid, the daemon cannot
+/// build a query, or the project cannot be written.
+pub async fn run_export_vvproj(request: VvprojExportRequest<'_>) -> Result<()> {
+    let output = StdAppOutput;
+    run_export_vvproj_with_output(request, &output).await
+}
+
+pub async fn run_export_vvproj_with_output(
+    request: VvprojExportRequest<'_>,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    let texts = request
+        .text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    if texts.is_empty() {
+        return Err(anyhow!("No text to export"));
+    }
+    for text in &texts {
+        validate_text_synthesis_request(
+            text,
+            request.style_id,
+            request.rate,
+            DEFAULT_SYNTHESIS_PITCH,
+        )?;
+    }
+
+    ensure_startup_resources(request.quiet, output).await?;
+    let mut client = connect_daemon_client_auto_start(&request.socket_path).await?;
+    let speaker_uuid = client
+        .list_speakers()
+        .await?
+        .into_iter()
+        .find(|speaker| {
+            speaker
+                .styles
+                .iter()
+                .any(|style| style.id == request.style_id)
+        })
+        .map(|speaker| speaker.speaker_uuid.to_string())
+        .ok_or_else(|| anyhow!("Style {} is not installed", request.style_id))?;
+
+    let options = SynthesisOptions::with_rate(request.rate);
+    let mut lines = Vec::with_capacity(texts.len());
+    for text in texts {
+        let query_json = client.audio_query(text, request.style_id, options).await?;
+        let query =
+            serde_json::from_str(&query_json).context("Daemon returned a malformed audio query")?;
+        lines.push(VvprojLine {
+            text: text.to_owned(),
+            speaker_uuid: speaker_uuid.clone(),
+            style_id: request.style_id,
+            query,
+        });
+    }
+
+    let line_count = lines.len();
+    let project = serde_json::to_vec_pretty(&build_vvproj(lines))?;
+    tokio::fs::write(request.output_file, project)
+        .await
+        .with_context(|| format!("Failed to write {}", request.output_file.display()))?;
+    if !request.quiet {
+        output.info(&format!(
+            "Exported {line_count} lines to {}",
+            request.output_file.display()
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,199 @@
+//! C ABI over the daemon client, for embedding daemon-backed synthesis in GUI apps.
+//!
+//! Build a linkable library with
+//! `cargo rustc --release --lib --features ffi --crate-type cdylib` (or `staticlib`)
+//! and include `include/voicevox_cli.h`.
+
+use anyhow::{Context, Result, anyhow};
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::path::PathBuf;
+
+use crate::infrastructure::daemon::client::DaemonClient;
+use crate::infrastructure::ipc::{DEFAULT_SYNTHESIS_PITCH, SynthesisOptions};
+use crate::infrastructure::paths::get_socket_path;
+use crate::interface::synthesis::flow::validate_text_synthesis_request;
+
+pub const VOICEVOX_CLI_OK: i32 = 0;
+pub const VOICEVOX_CLI_INVALID_ARGUMENT: i32 = 1;
+pub const VOICEVOX_CLI_SYNTHESIS_FAILED: i32 = 2;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(error: &anyhow::Error) {
+    let message = format!("{error:#}").replace('\0', " ");
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = CString::new(message).ok());
+}
+
+fn fail(code: i32, error: &anyhow::Error) -> i32 {
+    set_last_error(error);
+    code
+}
+
+/// Reads an optional NUL-terminated UTF-8 argument; null maps to `None`.
+///
+/// # Safety
+///
+/// `value` must be null or point to a valid NUL-terminated string.
+unsafe fn optional_str<'a>(value: *const c_char, name: &str) -> Result<Option<&'a str>> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    // SAFETY: the caller guarantees a valid NUL-terminated string.
+    unsafe { CStr::from_ptr(value) }
+        .to_str()
+        .map(Some)
+        .map_err(|_| anyhow!("`{name}` is not valid UTF-8"))
+}
+
+fn synthesize_blocking(
+    text: &str,
+    style_id: u32,
+    rate: f32,
+    socket_path: PathBuf,
+) -> Result<Vec<u8>> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("Failed to start async runtime")?;
+    runtime.block_on(async {
+        let mut client = DaemonClient::new_with_auto_start_at(&socket_path).await?;
+        client
+            .synthesize(
+                text,
+                style_id,
+                SynthesisOptions {
+                    rate,
+                    ..Default::default()
+                },
+            )
+            .await
+    })
+}
+
+/// Synthesizes `text` through the user daemon, starting it if needed.
+///
+/// On success the WAV bytes are written to `*out_wav`/`*out_len` and must be released
+/// with [`voicevox_cli_free_wav`]. `socket_path` may be null to use the default socket.
+/// On failure a non-zero status is returned and [`voicevox_cli_last_error`] describes it.
+///
+/// # Safety
+///
+/// `text` must be a valid NUL-terminated string, `socket_path` must be null or a valid
+/// NUL-terminated string, and `out_wav`/`out_len` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn voicevox_cli_synthesize(
+    text: *const c_char,
+    style_id: u32,
+    rate: f32,
+    socket_path: *const c_char,
+    out_wav: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if out_wav.is_null() || out_len.is_null() {
+        return fail(
+            VOICEVOX_CLI_INVALID_ARGUMENT,
+            &anyhow!("`out_wav` and `out_len` must not be null"),
+        );
+    }
+    // SAFETY: the caller guarantees both arguments are null or valid C strings.
+    let arguments = unsafe { optional_str(text, "text") }.and_then(|text| {
+        let text = text.ok_or_else(|| anyhow!("`text` must not be null"))?;
+        // SAFETY: as above.
+        let socket_path = unsafe { optional_str(socket_path, "socket_path") }?
+            .map_or_else(get_socket_path, PathBuf::from);
+        Ok((text, socket_path))
+    });
+    let (text, socket_path) = match arguments {
+        Ok(arguments) => arguments,
+        Err(error) => return fail(VOICEVOX_CLI_INVALID_ARGUMENT, &error),
+    };
+    if let Err(error) =
+        validate_text_synthesis_request(text, style_id, rate, DEFAULT_SYNTHESIS_PITCH)
+    {
+        return fail(VOICEVOX_CLI_INVALID_ARGUMENT, &error);
+    }
+
+    match synthesize_blocking(text, style_id, rate, socket_path) {
+        Ok(wav_data) => {
+            let wav_data = wav_data.into_boxed_slice();
+            // SAFETY: both out-pointers were checked for null and are valid per contract.
+            unsafe {
+                *out_len = wav_data.len();
+                *out_wav = Box::into_raw(wav_data).cast::<u8>();
+            }
+            VOICEVOX_CLI_OK
+        }
+        Err(error) => fail(VOICEVOX_CLI_SYNTHESIS_FAILED, &error),
+    }
+}
+
+/// Releases WAV bytes returned by [`voicevox_cli_synthesize`]. Null is ignored.
+///
+/// # Safety
+///
+/// `wav` and `len` must come from a single successful `voicevox_cli_synthesize` call
+/// and must not be freed twice.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn voicevox_cli_free_wav(wav: *mut u8, len: usize) {
+    if wav.is_null() {
+        return;
+    }
+    // SAFETY: the pointer and length describe a boxed slice leaked by `voicevox_cli_synthesize`.
+    drop(unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(wav, len)) });
+}
+
+/// Returns the last error message on the calling thread, or null if none.
+///
+/// The string stays valid until the next failing call on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn voicevox_cli_last_error() -> *const c_char {
+    LAST_ERROR.with(|slot| {
+        slot.borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |message| message.as_ptr())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_arguments_report_error_without_touching_daemon() {
+        let mut wav = std::ptr::null_mut();
+        let mut len = 0;
+        // SAFETY: null text is an accepted input that must be rejected.
+        let status = unsafe {
+            voicevox_cli_synthesize(
+                std::ptr::null(),
+                3,
+                1.0,
+                std::ptr::null(),
+                &raw mut wav,
+                &raw mut len,
+            )
+        };
+        assert_eq!(status, VOICEVOX_CLI_INVALID_ARGUMENT);
+        assert!(wav.is_null());
+
+        let text = CString::new("こんにちは").expect("no interior nul");
+        // SAFETY: all pointers are valid for the duration of the call.
+        let status = unsafe {
+            voicevox_cli_synthesize(
+                text.as_ptr(),
+                3,
+                9.0,
+                std::ptr::null(),
+                &raw mut wav,
+                &raw mut len,
+            )
+        };
+        assert_eq!(status, VOICEVOX_CLI_INVALID_ARGUMENT);
+        // SAFETY: `voicevox_cli_last_error` returns a valid C string after a failure.
+        let message = unsafe { CStr::from_ptr(voicevox_cli_last_error()) };
+        assert!(message.to_string_lossy().contains("Rate"));
+    }
+}
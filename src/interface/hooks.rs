@@ -0,0 +1,141 @@
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::path::Path;
+use std::time::Duration;
+
+/// Shell commands run after synthesis, read from `hooks.toml` in the config directory.
+///
+/// `{file}` in a command is replaced with the shell-quoted audio path; the same path
+/// and request metadata are exported as `VOICEVOX_HOOK_*` environment variables.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HooksConfig {
+    /// Runs once the audio exists: the `-o` file, or a temporary copy before playback.
+    pub on_synthesized: Option<String>,
+    /// Runs after playback finishes without interruption.
+    pub on_spoken: Option<String>,
+}
+
+impl HooksConfig {
+    /// # Errors
+    ///
+    /// Returns an error on TOML syntax errors or unknown keys.
+    pub fn parse(source: &str) -> Result<Self> {
+        Ok(toml::from_str(source)?)
+    }
+}
+
+/// Loads `hooks.toml`, treating a missing file as no hooks.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read or is invalid.
+pub fn load_hooks_config(path: &Path) -> Result<HooksConfig> {
+    match std::fs::read_to_string(path) {
+        Ok(source) => HooksConfig::parse(&source)
+            .with_context(|| format!("Invalid hooks config {}", path.display())),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(HooksConfig::default()),
+        Err(error) => {
+            Err(error).with_context(|| format!("Failed to read hooks config {}", path.display()))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    Synthesized,
+    Spoken,
+}
+
+impl HookEvent {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Synthesized => "on_synthesized",
+            Self::Spoken => "on_spoken",
+        }
+    }
+}
+
+/// What a hook is told about the utterance.
+pub struct HookContext<'a> {
+    /// Audio on disk, when there is one for this event.
+    pub file: Option<&'a Path>,
+    pub text: &'a str,
+    pub style_id: u32,
+    pub duration: Duration,
+}
+
+/// Runs `command` through `/bin/sh -c` and waits for it.
+///
+/// # Errors
+///
+/// Returns an error if the shell cannot be started or the command exits non-zero.
+pub async fn run_hook(command: &str, event: HookEvent, context: &HookContext<'_>) -> Result<()> {
+    let file = context
+        .file
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let status = tokio::process::Command::new(crate::config::SYSTEM_SH_PATH)
+        .arg("-c")
+        .arg(command.replace("{file}", &shell_quote(&file)))
+        .env("VOICEVOX_HOOK_EVENT", event.name())
+        .env("VOICEVOX_HOOK_FILE", &file)
+        .env("VOICEVOX_HOOK_TEXT", context.text)
+        .env("VOICEVOX_HOOK_STYLE_ID", context.style_id.to_string())
+        .env(
+            "VOICEVOX_HOOK_DURATION_MS",
+            context.duration.as_millis().to_string(),
+        )
+        .status()
+        .await
+        .with_context(|| format!("Failed to run {} hook", event.name()))?;
+    if !status.success() {
+        bail!("{} hook exited with {status}", event.name());
+    }
+    Ok(())
+}
+
+/// Single-quotes `value` for `sh`, so paths with spaces or quotes survive substitution.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn hooks_see_the_quoted_file_and_metadata() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let audio = dir.path().join("it's here.wav");
+        std::fs::write(&audio, b"RIFF").expect("write");
+        let record = dir.path().join("record.txt");
+
+        let command = format!(
+            "cat {{file}} > '{}' && echo \" $VOICEVOX_HOOK_EVENT $VOICEVOX_HOOK_STYLE_ID $VOICEVOX_HOOK_DURATION_MS\" >> '{}'",
+            record.display(),
+            record.display()
+        );
+        let context = HookContext {
+            file: Some(&audio),
+            text: "こんにちは",
+            style_id: 3,
+            duration: Duration::from_millis(1250),
+        };
+        run_hook(&command, HookEvent::Synthesized, &context)
+            .await
+            .expect("hook");
+        assert_eq!(
+            std::fs::read_to_string(&record).expect("record"),
+            "RIFF on_synthesized 3 1250\n"
+        );
+
+        assert!(
+            run_hook("exit 4", HookEvent::Spoken, &context)
+                .await
+                .is_err()
+        );
+        assert!(HooksConfig::parse("on_played = \"true\"\n").is_err());
+    }
+}
@@ -0,0 +1,187 @@
+use anyhow::{Context, Result};
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::{oneshot, watch};
+
+use crate::interface::temp_audio::remove_live_temp_files;
+
+/// How long the running command gets to stop on its own after the first Ctrl+C
+/// before the handler exits for it.
+const INTERRUPT_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// Exit status after Ctrl+C, following the shell's `128 + SIGINT` convention.
+pub const EXIT_CODE_INTERRUPTED: u8 = 130;
+
+/// Reason sent to cancellation receivers when Ctrl+C arrives.
+pub const INTERRUPT_REASON: &str = "Interrupted";
+
+/// Set once by [`install_interrupt_handler`]; flips to `true` on the first Ctrl+C.
+static INTERRUPTS: OnceLock<watch::Sender<bool>> = OnceLock::new();
+
+/// Output files being written, removed if the process is interrupted mid-write.
+static PARTIAL_OUTPUTS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// The command stopped because the user pressed Ctrl+C.
+#[derive(Debug, thiserror::Error)]
+#[error("Interrupted")]
+pub struct Interrupted;
+
+#[must_use]
+pub fn is_interrupted(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .any(|cause| cause.downcast_ref::<Interrupted>().is_some())
+}
+
+/// Installs the CLI's signal handling.
+///
+/// The first Ctrl+C is delivered to [`interrupt_receiver`] receivers so the
+/// running command can stop playback and cancel its daemon request. Ctrl+C with
+/// no receivers, a second Ctrl+C, a command that does not stop within the grace
+/// period, SIGTERM, or SIGHUP exits at once after removing temporary audio,
+/// partial output files, and terminal styling.
+///
+/// # Errors
+///
+/// Returns an error if the signal handlers cannot be installed.
+pub fn install_interrupt_handler() -> Result<()> {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut interrupt =
+        signal(SignalKind::interrupt()).context("Failed to install SIGINT handler")?;
+    let mut terminate =
+        signal(SignalKind::terminate()).context("Failed to install SIGTERM handler")?;
+    let mut hangup = signal(SignalKind::hangup()).context("Failed to install SIGHUP handler")?;
+
+    let interrupts = INTERRUPTS.get_or_init(|| watch::channel(false).0).clone();
+    tokio::spawn(async move {
+        let signal_number = tokio::select! {
+            _ = interrupt.recv() => libc::SIGINT,
+            _ = terminate.recv() => libc::SIGTERM,
+            _ = hangup.recv() => libc::SIGHUP,
+        };
+        // Commands that never asked for a receiver cannot wind down; exit for them.
+        if signal_number == libc::SIGINT && interrupts.receiver_count() > 0 {
+            interrupts.send_replace(true);
+            let _ = tokio::time::timeout(INTERRUPT_GRACE_PERIOD, interrupt.recv()).await;
+        }
+        exit_after_signal(signal_number)
+    });
+    Ok(())
+}
+
+fn exit_after_signal(signal_number: i32) -> ! {
+    remove_live_temp_files();
+    remove_partial_outputs();
+    restore_terminal();
+    std::process::exit(128 + signal_number)
+}
+
+/// Returns a receiver that fires with [`INTERRUPT_REASON`] on Ctrl+C.
+///
+/// `None` when no handler is installed, as in the MCP server and tests, so the
+/// caller runs without interruption.
+#[must_use]
+pub fn interrupt_receiver() -> Option<oneshot::Receiver<String>> {
+    let mut interrupts = INTERRUPTS.get()?.subscribe();
+    let (mut sender, receiver) = oneshot::channel();
+    tokio::spawn(async move {
+        let interrupted = tokio::select! {
+            result = interrupts.wait_for(|interrupted| *interrupted) => result.is_ok(),
+            () = sender.closed() => false,
+        };
+        if interrupted {
+            let _ = sender.send(INTERRUPT_REASON.to_string());
+        }
+    });
+    Some(receiver)
+}
+
+/// Resets colors and shows the cursor, then ends the line the `^C` echo left open.
+pub fn restore_terminal() {
+    let mut stderr = std::io::stderr();
+    if stderr.is_terminal() {
+        let _ = stderr.write_all(b"\x1b[0m\x1b[?25h\n");
+        let _ = stderr.flush();
+    }
+}
+
+/// An output file being written; deleted unless [`PartialOutput::complete`] is called.
+///
+/// The path is also registered so a signal that ends the process mid-write
+/// removes the truncated file.
+pub struct PartialOutput {
+    path: PathBuf,
+    completed: bool,
+}
+
+impl PartialOutput {
+    #[must_use]
+    pub fn track(path: &Path) -> Self {
+        if let Ok(mut partial) = PARTIAL_OUTPUTS.lock() {
+            partial.push(path.to_owned());
+        }
+        Self {
+            path: path.to_owned(),
+            completed: false,
+        }
+    }
+
+    /// Keeps the file: it was written in full.
+    pub fn complete(mut self) {
+        self.completed = true;
+    }
+}
+
+impl Drop for PartialOutput {
+    fn drop(&mut self) {
+        if let Ok(mut partial) = PARTIAL_OUTPUTS.lock() {
+            partial.retain(|path| path != &self.path);
+        }
+        if !self.completed {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+fn remove_partial_outputs() {
+    let paths = PARTIAL_OUTPUTS
+        .lock()
+        .map(|mut partial| std::mem::take(&mut *partial))
+        .unwrap_or_default();
+    for path in paths {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unfinished_outputs_are_removed() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let finished = dir.path().join("finished.wav");
+        let unfinished = dir.path().join("unfinished.wav");
+
+        let output = PartialOutput::track(&finished);
+        std::fs::write(&finished, b"RIFF").expect("write");
+        output.complete();
+        assert!(finished.exists());
+
+        let output = PartialOutput::track(&unfinished);
+        std::fs::write(&unfinished, b"RI").expect("write");
+        drop(output);
+        assert!(!unfinished.exists());
+    }
+
+    #[tokio::test]
+    async fn no_receiver_without_a_handler() {
+        assert!(interrupt_receiver().is_none());
+        assert!(is_interrupted(
+            &anyhow::Error::new(Interrupted).context("say")
+        ));
+    }
+}
@@ -0,0 +1,181 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::infrastructure::ipc::SynthesisTimings;
+use crate::interface::AppOutput;
+
+/// A step of one `voicevox-say` request, in the order the request goes through them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    VoiceResolution,
+    DaemonConnect,
+    /// Waiting in the daemon for a free synthesis executor.
+    Queue,
+    ModelLoad,
+    AudioQuery,
+    Synthesis,
+    /// Sending the request and receiving the audio, outside the daemon's own work.
+    Transfer,
+    Decode,
+    /// Opening the output device or player until the first sample is queued.
+    PlaybackStart,
+}
+
+impl Stage {
+    const ALL: [Self; 9] = [
+        Self::VoiceResolution,
+        Self::DaemonConnect,
+        Self::Queue,
+        Self::ModelLoad,
+        Self::AudioQuery,
+        Self::Synthesis,
+        Self::Transfer,
+        Self::Decode,
+        Self::PlaybackStart,
+    ];
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::VoiceResolution => "voice resolution",
+            Self::DaemonConnect => "daemon connect",
+            Self::Queue => "daemon queue",
+            Self::ModelLoad => "model load",
+            Self::AudioQuery => "audio query",
+            Self::Synthesis => "synthesis",
+            Self::Transfer => "transfer",
+            Self::Decode => "decode",
+            Self::PlaybackStart => "playback start",
+        }
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SPANS: Mutex<Vec<(Stage, Duration)>> = Mutex::new(Vec::new());
+
+/// Starts recording stage timings for this process; off unless `--verbose` asks.
+pub fn enable() {
+    ENABLED.store(true, Ordering::SeqCst);
+}
+
+#[must_use]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// Adds `elapsed` to `stage`; stages hit more than once, such as synthesis of
+/// several voice segments, add up.
+pub fn record(stage: Stage, elapsed: Duration) {
+    if is_enabled() {
+        SPANS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push((stage, elapsed));
+    }
+}
+
+/// Records the daemon's share of a synthesis round trip that took `round_trip`;
+/// whatever the daemon did not account for is transfer.
+pub fn record_daemon_timings(timings: SynthesisTimings, round_trip: Duration) {
+    let daemon = [
+        (Stage::Queue, timings.queue_ms),
+        (Stage::ModelLoad, timings.model_load_ms),
+        (Stage::AudioQuery, timings.audio_query_ms),
+        (Stage::Synthesis, timings.synthesis_ms),
+    ];
+    let mut accounted = Duration::ZERO;
+    for (stage, ms) in daemon {
+        let elapsed = Duration::from_millis(ms);
+        accounted += elapsed;
+        record(stage, elapsed);
+    }
+    record(Stage::Transfer, round_trip.saturating_sub(accounted));
+}
+
+/// Times a stage until dropped.
+#[must_use = "the stage is timed until the span is dropped"]
+pub struct Span {
+    stage: Stage,
+    started: Instant,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        record(self.stage, self.started.elapsed());
+    }
+}
+
+/// Starts timing `stage`; the time is recorded when the returned span is dropped.
+pub fn span(stage: Stage) -> Span {
+    Span {
+        stage,
+        started: Instant::now(),
+    }
+}
+
+fn format_report(spans: &[(Stage, Duration)]) -> Vec<String> {
+    let mut lines = vec!["Latency breakdown:".to_string()];
+    let mut total = Duration::ZERO;
+    for stage in Stage::ALL {
+        let times = spans.iter().filter(|(s, _)| *s == stage);
+        let count = times.clone().count();
+        if count == 0 {
+            continue;
+        }
+        let elapsed = times.map(|(_, elapsed)| *elapsed).sum::<Duration>();
+        total += elapsed;
+        let repeats = if count > 1 {
+            format!(" ({count}x)")
+        } else {
+            String::new()
+        };
+        lines.push(format!(
+            "  {:<18}{:>9.1} ms{repeats}",
+            stage.label(),
+            elapsed.as_secs_f64() * 1000.0
+        ));
+    }
+    lines.push(format!(
+        "  {:<18}{:>9.1} ms",
+        "total",
+        total.as_secs_f64() * 1000.0
+    ));
+    lines
+}
+
+/// Prints the time spent in each stage so far, in request order.
+pub fn print_report(output: &dyn AppOutput) {
+    if !is_enabled() {
+        return;
+    }
+    let spans = SPANS.lock().unwrap_or_else(PoisonError::into_inner).clone();
+    for line in format_report(&spans) {
+        output.info(&line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_lists_stages_in_request_order_with_totals() {
+        let spans = [
+            (Stage::Synthesis, Duration::from_millis(40)),
+            (Stage::VoiceResolution, Duration::from_micros(1500)),
+            (Stage::Synthesis, Duration::from_millis(60)),
+            (Stage::PlaybackStart, Duration::from_millis(8)),
+        ];
+
+        assert_eq!(
+            format_report(&spans),
+            [
+                "Latency breakdown:",
+                "  voice resolution        1.5 ms",
+                "  synthesis             100.0 ms (2x)",
+                "  playback start          8.0 ms",
+                "  total                 109.5 ms",
+            ]
+        );
+    }
+}
@@ -20,6 +20,7 @@ fn infer_voice_target_state(error: &anyhow::Error) -> VoiceTargetState {
             VoiceTargetState::Missing
         }
         DaemonErrorCode::SynthesisFailed | DaemonErrorCode::Internal => VoiceTargetState::Exists,
+        DaemonErrorCode::ProtocolMismatch => VoiceTargetState::Unknown,
     }
 }
 
@@ -43,6 +44,12 @@ pub fn format_daemon_client_error_for_mcp(error: &anyhow::Error) -> String {
         DaemonErrorCode::Internal => {
             format!("VOICEVOX daemon internal error: {}", daemon_error.message())
         }
+        DaemonErrorCode::ProtocolMismatch => {
+            format!(
+                "VOICEVOX daemon version mismatch: {}",
+                daemon_error.message()
+            )
+        }
     }
 }
 
@@ -0,0 +1,208 @@
+use anyhow::{Context, Result, anyhow};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::domain::synthesis::timing::parse_duration_spec;
+use crate::infrastructure::daemon::client::DaemonClient;
+use crate::infrastructure::paths::get_socket_path;
+
+/// Default bound on reaching the daemon, including starting it. Kept below the
+/// 30 s that MCP hosts commonly allow a tool call before giving up on it.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Default bound on synthesizing and playing one chunk of `text_to_speech` input.
+pub const DEFAULT_SYNTHESIS_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Style whose model is warmed when a session starts, unless overridden.
+pub const DEFAULT_PREWARM_STYLE_ID: u32 = 3;
+
+/// Where the MCP server finds its daemon, whether it may start one, and how long
+/// tool calls wait on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct McpDaemonOptions {
+    /// Spawn `voicevox-daemon` when none answers. Hosts that manage the daemon
+    /// themselves turn this off so the MCP process never launches its own.
+    pub auto_start: bool,
+    pub socket_path: PathBuf,
+    /// How long a tool waits to connect to (or start) the daemon.
+    pub connect_timeout: Duration,
+    /// How long `text_to_speech` waits for one chunk to be synthesized and played.
+    pub synthesis_timeout: Duration,
+    /// Style to prewarm when a client initializes a session; `None` skips prewarming.
+    pub prewarm_style: Option<u32>,
+}
+
+impl Default for McpDaemonOptions {
+    fn default() -> Self {
+        Self {
+            auto_start: true,
+            socket_path: get_socket_path(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            synthesis_timeout: DEFAULT_SYNTHESIS_TIMEOUT,
+            prewarm_style: Some(DEFAULT_PREWARM_STYLE_ID),
+        }
+    }
+}
+
+impl McpDaemonOptions {
+    /// Reads `VOICEVOX_MCP_NO_AUTO_START_DAEMON`, the `VOICEVOX_MCP_*_TIMEOUT`
+    /// durations, and the usual socket path lookup.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a timeout variable is not a positive duration.
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            auto_start: !crate::config::mcp_auto_start_daemon_disabled(),
+            connect_timeout: timeout_from_env(crate::config::ENV_VOICEVOX_MCP_CONNECT_TIMEOUT)?
+                .unwrap_or(DEFAULT_CONNECT_TIMEOUT),
+            synthesis_timeout: timeout_from_env(crate::config::ENV_VOICEVOX_MCP_SYNTHESIS_TIMEOUT)?
+                .unwrap_or(DEFAULT_SYNTHESIS_TIMEOUT),
+            ..Self::default()
+        })
+    }
+}
+
+fn timeout_from_env(name: &str) -> Result<Option<Duration>> {
+    match std::env::var(name) {
+        Ok(value) if !value.trim().is_empty() => parse_duration_spec(&value)
+            .map(Some)
+            .with_context(|| format!("Invalid {name}")),
+        _ => Ok(None),
+    }
+}
+
+static MCP_DAEMON_OPTIONS: OnceLock<McpDaemonOptions> = OnceLock::new();
+
+/// Fixes the daemon options for this MCP server process.
+///
+/// The first call wins; later calls return the options already in use.
+pub fn init_mcp_daemon_options(options: McpDaemonOptions) -> &'static McpDaemonOptions {
+    MCP_DAEMON_OPTIONS.get_or_init(|| options)
+}
+
+/// The options chosen by [`init_mcp_daemon_options`], or the environment's if it was never called.
+#[must_use]
+pub fn mcp_daemon_options() -> &'static McpDaemonOptions {
+    MCP_DAEMON_OPTIONS.get_or_init(|| McpDaemonOptions::from_env().unwrap_or_default())
+}
+
+/// Connects to the daemon, starting it first only when auto-start is enabled.
+///
+/// # Errors
+///
+/// Returns an error if the daemon cannot be reached (or started) within the
+/// connect timeout.
+pub(crate) async fn connect_mcp_daemon_client(options: &McpDaemonOptions) -> Result<DaemonClient> {
+    let connect = async {
+        if options.auto_start {
+            DaemonClient::new_with_auto_start_at(&options.socket_path).await
+        } else {
+            DaemonClient::connect_with_retry_at(&options.socket_path).await
+        }
+    };
+    let client = tokio::time::timeout(options.connect_timeout, connect)
+        .await
+        .map_err(|_| {
+            anyhow!(
+                "Timed out after {} connecting to VOICEVOX daemon",
+                format_timeout(options.connect_timeout)
+            )
+        })
+        .and_then(|client| client);
+    client.with_context(|| {
+        format!(
+            "Failed to connect to VOICEVOX daemon at {}",
+            options.socket_path.display()
+        )
+    })
+}
+
+/// Asks the daemon to warm the prewarm style's model so a session's first
+/// `text_to_speech` does not pay for cold model files.
+///
+/// Runs in the background of `initialize`; a failure only costs that first call its
+/// speed, so it is logged to stderr and otherwise ignored.
+pub(crate) async fn prewarm_daemon(options: &McpDaemonOptions) {
+    let Some(style_id) = options.prewarm_style else {
+        return;
+    };
+    let prewarm = async {
+        let mut client = connect_mcp_daemon_client(options).await?;
+        tokio::time::timeout(options.synthesis_timeout, client.prewarm(style_id))
+            .await
+            .map_err(|_| {
+                anyhow!(
+                    "Timed out after {}",
+                    format_timeout(options.synthesis_timeout)
+                )
+            })?
+    };
+    if let Err(error) = prewarm.await {
+        crate::infrastructure::logging::warn(&format!(
+            "Prewarming style {style_id} failed: {error:#}"
+        ));
+    }
+}
+
+/// Formats a timeout for tool results, e.g. `20s` or `1.5s`.
+#[must_use]
+pub fn format_timeout(timeout: Duration) -> String {
+    format!("{}s", timeout.as_secs_f64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn disabled_auto_start_never_spawns_a_daemon() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let options = McpDaemonOptions {
+            auto_start: false,
+            socket_path: dir.path().join("voicevox-daemon.sock"),
+            ..McpDaemonOptions::default()
+        };
+
+        let Err(error) = connect_mcp_daemon_client(&options).await else {
+            panic!("no daemon is listening");
+        };
+
+        assert!(error.to_string().contains("voicevox-daemon.sock"));
+        assert!(!options.socket_path.exists());
+    }
+
+    #[tokio::test]
+    async fn connecting_gives_up_at_the_connect_timeout() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let options = McpDaemonOptions {
+            auto_start: false,
+            socket_path: dir.path().join("voicevox-daemon.sock"),
+            connect_timeout: Duration::from_millis(50),
+            ..McpDaemonOptions::default()
+        };
+
+        let started = std::time::Instant::now();
+        let Err(error) = connect_mcp_daemon_client(&options).await else {
+            panic!("no daemon is listening");
+        };
+
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(format!("{error:#}").contains("Timed out after 0.05s"));
+    }
+
+    #[tokio::test]
+    async fn prewarm_without_a_style_never_connects() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let options = McpDaemonOptions {
+            socket_path: dir.path().join("voicevox-daemon.sock"),
+            prewarm_style: None,
+            ..McpDaemonOptions::default()
+        };
+
+        prewarm_daemon(&options).await;
+
+        assert!(!options.socket_path.exists());
+    }
+}
@@ -1,8 +1,10 @@
 pub mod daemon_error;
+pub mod daemon_options;
 pub mod protocol;
 pub mod server;
 pub mod startup;
 pub mod tools;
 
+pub use daemon_options::McpDaemonOptions;
 pub use server::run_mcp_server;
 pub use startup::run_mcp_server_app;
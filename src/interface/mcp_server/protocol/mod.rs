@@ -1,8 +1,38 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+/// Newest MCP revision the server speaks, offered to clients that propose one it
+/// does not know.
 pub const MCP_VERSION: &str = "2025-06-18";
 
+/// Every MCP revision the server can serve, newest first. The tools and
+/// notifications exist in all of them; `audio` tool content only from
+/// [`AUDIO_CONTENT_VERSION`] on, so older sessions get audio as an embedded resource.
+pub const SUPPORTED_MCP_VERSIONS: [&str; 3] = [MCP_VERSION, "2025-03-26", "2024-11-05"];
+
+/// First MCP revision with the `audio` content type.
+pub const AUDIO_CONTENT_VERSION: &str = "2025-03-26";
+
+/// Whether a session on revision `version` can receive `audio` content.
+#[must_use]
+pub fn supports_audio_content(version: &str) -> bool {
+    // Revisions are dates, so they order as strings.
+    version >= AUDIO_CONTENT_VERSION
+}
+
+/// Picks the revision to answer `initialize` with: the client's own proposal when
+/// it is supported, otherwise [`MCP_VERSION`] for the client to accept or reject.
+#[must_use]
+pub fn negotiate_protocol_version(requested: Option<&str>) -> &'static str {
+    requested
+        .and_then(|requested| {
+            SUPPORTED_MCP_VERSIONS
+                .into_iter()
+                .find(|supported| *supported == requested)
+        })
+        .unwrap_or(MCP_VERSION)
+}
+
 pub const PARSE_ERROR: i32 = -32700;
 pub const INVALID_REQUEST: i32 = -32600;
 pub const METHOD_NOT_FOUND: i32 = -32601;
@@ -19,6 +49,45 @@ pub struct JsonRpcResponse {
     pub id: Value,
 }
 
+/// A server-initiated message that expects no reply, such as progress.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonRpcNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    pub params: Value,
+}
+
+impl JsonRpcNotification {
+    #[must_use]
+    pub fn new(method: impl Into<String>, params: Value) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            method: method.into(),
+            params,
+        }
+    }
+}
+
+/// Anything the server writes to stdout.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum OutgoingMessage {
+    Response(JsonRpcResponse),
+    Notification(JsonRpcNotification),
+}
+
+impl From<JsonRpcResponse> for OutgoingMessage {
+    fn from(response: JsonRpcResponse) -> Self {
+        Self::Response(response)
+    }
+}
+
+impl From<JsonRpcNotification> for OutgoingMessage {
+    fn from(notification: JsonRpcNotification) -> Self {
+        Self::Notification(notification)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct JsonRpcError {
     pub code: i32,
@@ -54,9 +123,15 @@ pub struct ToolsListResult<T> {
     pub tools: Vec<T>,
 }
 
+#[derive(Debug)]
+pub struct InitializeParams {
+    /// `protocolVersion` the client proposed, if it sent one.
+    pub protocol_version: Option<String>,
+}
+
 #[derive(Debug)]
 pub enum RequestMethod {
-    Initialize,
+    Initialize(InitializeParams),
     ToolsList,
     ToolsCall(ToolsCallParams),
     Unknown(String),
@@ -84,6 +159,8 @@ pub struct NotificationMessage {
 pub struct ToolsCallParams {
     pub name: String,
     pub arguments: Value,
+    /// `_meta.progressToken`; when present the tool may send `notifications/progress`.
+    pub progress_token: Option<Value>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -179,7 +256,7 @@ pub fn parse_request_message(raw: Value) -> Result<RequestMessage, ParseRequestE
 
     let params = raw.get("params").cloned();
     let method = match method {
-        "initialize" => RequestMethod::Initialize,
+        "initialize" => RequestMethod::Initialize(parse_initialize_params(params.as_ref())),
         "tools/list" => RequestMethod::ToolsList,
         "tools/call" => RequestMethod::ToolsCall(parse_tools_call_params(params)?),
         other => RequestMethod::Unknown(other.to_string()),
@@ -188,6 +265,15 @@ pub fn parse_request_message(raw: Value) -> Result<RequestMessage, ParseRequestE
     Ok(RequestMessage { id, method })
 }
 
+fn parse_initialize_params(params: Option<&Value>) -> InitializeParams {
+    InitializeParams {
+        protocol_version: params
+            .and_then(|params| params.get("protocolVersion"))
+            .and_then(Value::as_str)
+            .map(str::to_owned),
+    }
+}
+
 fn parse_tools_call_params(params: Option<Value>) -> Result<ToolsCallParams, ParseRequestError> {
     let params = params.ok_or(ParseRequestError::new(INVALID_PARAMS, "Missing params"))?;
 
@@ -214,7 +300,16 @@ fn parse_tools_call_params(params: Option<Value>) -> Result<ToolsCallParams, Par
         },
     )?;
 
-    Ok(ToolsCallParams { name, arguments })
+    let progress_token = params_obj
+        .remove("_meta")
+        .and_then(|mut meta| meta.get_mut("progressToken").map(Value::take))
+        .filter(|token| token.is_string() || token.is_number());
+
+    Ok(ToolsCallParams {
+        name,
+        arguments,
+        progress_token,
+    })
 }
 
 #[must_use]
@@ -260,4 +355,47 @@ mod tests {
         assert_eq!(error.code, INVALID_PARAMS);
         assert!(error.message.contains("expected object"));
     }
+
+    #[test]
+    fn tools_call_reads_progress_token() {
+        let raw = json!({
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "text_to_speech",
+                "arguments": { "text": "テスト" },
+                "_meta": { "progressToken": "tts-1" }
+            }
+        });
+
+        let RequestMethod::ToolsCall(call) = parse_request_message(raw).expect("valid").method
+        else {
+            panic!("expected tools/call");
+        };
+        assert_eq!(call.progress_token, Some(json!("tts-1")));
+    }
+
+    #[test]
+    fn initialize_negotiates_the_client_version_when_supported() {
+        let raw = json!({
+            "id": 0,
+            "method": "initialize",
+            "params": { "protocolVersion": "2024-11-05", "capabilities": {} }
+        });
+        let RequestMethod::Initialize(params) = parse_request_message(raw).expect("valid").method
+        else {
+            panic!("expected initialize");
+        };
+        assert_eq!(
+            negotiate_protocol_version(params.protocol_version.as_deref()),
+            "2024-11-05"
+        );
+
+        assert_eq!(negotiate_protocol_version(Some("2099-01-01")), MCP_VERSION);
+        assert_eq!(negotiate_protocol_version(None), MCP_VERSION);
+
+        assert!(!supports_audio_content("2024-11-05"));
+        assert!(supports_audio_content("2025-03-26"));
+        assert!(supports_audio_content(MCP_VERSION));
+    }
 }
@@ -11,7 +11,11 @@ pub async fn run_tool_request(
     cancel_rx: Option<tokio::sync::oneshot::Receiver<String>>,
 ) -> Result<crate::interface::mcp_server::tools::types::ToolCallResult> {
     crate::interface::mcp_server::tools::registry::execute_tool_request(
-        tool_name, arguments, cancel_rx,
+        tool_name,
+        arguments,
+        cancel_rx,
+        None,
+        crate::interface::mcp_server::protocol::MCP_VERSION,
     )
     .await
 }
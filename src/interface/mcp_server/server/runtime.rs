@@ -1,9 +1,13 @@
 use serde_json::Value;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, PoisonError, RwLock};
 use tokio::sync::{Mutex, Semaphore, mpsc, oneshot};
 
-use crate::interface::mcp_server::protocol::{INTERNAL_ERROR, JsonRpcResponse};
+use crate::interface::mcp_server::protocol::{
+    INTERNAL_ERROR, JsonRpcResponse, MCP_VERSION, OutgoingMessage,
+};
+use crate::interface::mcp_server::tools::progress::ProgressReporter;
+use crate::interface::mcp_server::tools::stop_speech::{STOP_SPEECH_REASON, stop_speech_result};
 use crate::interface::mcp_server::tools::text_to_speech::spawn_non_send_text_to_speech_task;
 use crate::interface::mcp_server::tools::types::{ToolCallResult, text_result};
 
@@ -24,31 +28,76 @@ fn tool_handler_error_result(error: &anyhow::Error) -> ToolCallResult {
     text_result(format!("Tool handler error: {error}"), true)
 }
 
+/// A cancellable tool call in flight.
+#[derive(Debug)]
+struct ActiveCall {
+    abort: oneshot::Sender<String>,
+    /// Whether the call plays audio, so `stop_speech` interrupts it.
+    speaks: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct ActiveRequests {
-    abort_channels: Arc<Mutex<HashMap<String, oneshot::Sender<String>>>>,
-    response_sender: mpsc::Sender<JsonRpcResponse>,
+    abort_channels: Arc<Mutex<HashMap<String, ActiveCall>>>,
+    response_sender: mpsc::Sender<OutgoingMessage>,
     handler_slots: Arc<Semaphore>,
+    /// MCP revision agreed in `initialize`, which decides how tools shape results.
+    protocol_version: Arc<RwLock<&'static str>>,
 }
 
 impl ActiveRequests {
     #[must_use]
-    pub fn new(response_sender: mpsc::Sender<JsonRpcResponse>) -> Self {
+    pub fn new(response_sender: mpsc::Sender<OutgoingMessage>) -> Self {
         Self {
             abort_channels: Arc::new(Mutex::new(HashMap::new())),
             response_sender,
             handler_slots: Arc::new(Semaphore::new(MAX_CONCURRENT_TOOL_HANDLERS)),
+            protocol_version: Arc::new(RwLock::new(MCP_VERSION)),
         }
     }
 
+    /// Records the revision answered to `initialize` for the rest of the session.
+    pub fn set_protocol_version(&self, version: &'static str) {
+        *self
+            .protocol_version
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = version;
+    }
+
+    fn protocol_version(&self) -> &'static str {
+        *self
+            .protocol_version
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
     pub async fn cancel(&self, request_id: &str, reason: Option<String>) -> bool {
-        let sender = self.abort_channels.lock().await.remove(request_id);
-        sender.is_some_and(|sender| {
-            let _ = sender.send(reason.unwrap_or_default());
+        let call = self.abort_channels.lock().await.remove(request_id);
+        call.is_some_and(|call| {
+            let _ = call.abort.send(reason.unwrap_or_default());
             true
         })
     }
 
+    /// Cancels every `text_to_speech` call in flight, which stops its playback, and
+    /// returns how many there were.
+    pub async fn stop_speech(&self) -> usize {
+        let speaking: Vec<ActiveCall> = {
+            let mut channels = self.abort_channels.lock().await;
+            let ids: Vec<String> = channels
+                .iter()
+                .filter(|(_, call)| call.speaks)
+                .map(|(id, _)| id.clone())
+                .collect();
+            ids.iter().filter_map(|id| channels.remove(id)).collect()
+        };
+        let count = speaking.len();
+        for call in speaking {
+            let _ = call.abort.send(STOP_SPEECH_REASON.to_string());
+        }
+        count
+    }
+
     pub async fn cancel_all_requests(&self, reason: &str) -> usize {
         let channels = {
             let mut channels = self.abort_channels.lock().await;
@@ -57,8 +106,8 @@ impl ActiveRequests {
         let count = channels.len();
         let reason = reason.to_string();
 
-        for sender in channels.into_values() {
-            let _ = sender.send(reason.clone());
+        for call in channels.into_values() {
+            let _ = call.abort.send(reason.clone());
         }
 
         count
@@ -70,19 +119,33 @@ impl ActiveRequests {
         id: Value,
         tool_name: String,
         arguments: Value,
+        progress_token: Option<Value>,
     ) {
+        // Answered before taking a handler slot, so speech can be stopped even when
+        // every slot is busy.
+        if tool_name == "stop_speech" {
+            let stopped = self.stop_speech().await;
+            let response = serialize_result_response(
+                id,
+                stop_speech_result(stopped),
+                "Failed to serialize response",
+            );
+            let _ = self.response_sender.send(response.into()).await;
+            return;
+        }
+
         let permit = match Arc::clone(&self.handler_slots).try_acquire_owned() {
             Ok(permit) => permit,
             Err(_) => {
                 let response =
                     JsonRpcResponse::error(id, INTERNAL_ERROR, "Too many concurrent tool handlers");
-                let _ = self.response_sender.send(response).await;
+                let _ = self.response_sender.send(response.into()).await;
                 return;
             }
         };
 
         let active_requests = self.clone();
-        if tool_name == "text_to_speech" {
+        if matches!(tool_name.as_str(), "text_to_speech" | "synthesize_to_file") {
             let (abort_tx, abort_rx) = oneshot::channel::<String>();
             {
                 let mut channels = self.abort_channels.lock().await;
@@ -92,11 +155,20 @@ impl ActiveRequests {
                         INTERNAL_ERROR,
                         "Duplicate request ID for in-flight request",
                     );
-                    let _ = self.response_sender.send(response).await;
+                    let _ = self.response_sender.send(response.into()).await;
                     return;
                 }
-                channels.insert(request_id.clone(), abort_tx);
+                channels.insert(
+                    request_id.clone(),
+                    ActiveCall {
+                        abort: abort_tx,
+                        speaks: tool_name == "text_to_speech",
+                    },
+                );
             }
+            let progress = progress_token
+                .map(|token| ProgressReporter::new(token, self.response_sender.clone()));
+            let protocol_version = self.protocol_version();
             spawn_non_send_text_to_speech_task(move || {
                 Box::pin(async move {
                     let _permit = permit;
@@ -105,6 +177,8 @@ impl ActiveRequests {
                             &tool_name,
                             arguments,
                             Some(abort_rx),
+                            progress,
+                            protocol_version,
                         )
                         .await;
 
@@ -127,7 +201,7 @@ impl ActiveRequests {
                         ),
                     };
 
-                    let _ = active_requests.response_sender.send(response).await;
+                    let _ = active_requests.response_sender.send(response.into()).await;
                 })
             });
             return;
@@ -157,7 +231,57 @@ impl ActiveRequests {
                 ),
             };
 
-            let _ = active_requests.response_sender.send(response).await;
+            let _ = active_requests.response_sender.send(response.into()).await;
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn stop_speech_interrupts_only_calls_that_speak() {
+        let (response_tx, mut response_rx) = mpsc::channel(4);
+        let active_requests = ActiveRequests::new(response_tx);
+        let (speech_tx, speech_rx) = oneshot::channel();
+        let (file_tx, mut file_rx) = oneshot::channel();
+        {
+            let mut channels = active_requests.abort_channels.lock().await;
+            channels.insert(
+                "1".to_string(),
+                ActiveCall {
+                    abort: speech_tx,
+                    speaks: true,
+                },
+            );
+            channels.insert(
+                "2".to_string(),
+                ActiveCall {
+                    abort: file_tx,
+                    speaks: false,
+                },
+            );
+        }
+
+        active_requests
+            .spawn_tool_handler(
+                "3".to_string(),
+                Value::from(3),
+                "stop_speech".to_string(),
+                Value::Null,
+                None,
+            )
+            .await;
+
+        assert_eq!(speech_rx.await.expect("stopped"), STOP_SPEECH_REASON);
+        assert!(file_rx.try_recv().is_err());
+        let response = serde_json::to_value(response_rx.recv().await.expect("response"))
+            .expect("serializable");
+        assert_eq!(
+            response["result"]["content"][0]["text"],
+            "Stopped 1 text_to_speech call"
+        );
+        assert_eq!(active_requests.stop_speech().await, 0);
+    }
+}
@@ -5,10 +5,12 @@ use tokio::io::AsyncWriteExt;
 use tokio::sync::mpsc;
 use tokio_util::codec::{FramedRead, LinesCodec};
 
+use crate::interface::mcp_server::daemon_options::{mcp_daemon_options, prewarm_daemon};
 use crate::interface::mcp_server::protocol::{
     INVALID_REQUEST, InitializeResult, JsonRpcResponse, METHOD_NOT_FOUND, NotificationMethod,
-    PARSE_ERROR, RequestMethod, ServerCapabilities, ServerInfo, ToolsListResult,
-    parse_notification_message, parse_request_message, serialize_success_response,
+    OutgoingMessage, PARSE_ERROR, RequestMethod, ServerCapabilities, ServerInfo, ToolsListResult,
+    negotiate_protocol_version, parse_notification_message, parse_request_message,
+    serialize_success_response,
 };
 use crate::interface::mcp_server::server::runtime::ActiveRequests;
 use crate::interface::mcp_server::tools::registry::tool_definitions;
@@ -22,7 +24,7 @@ pub async fn run_stdio_server() -> Result<()> {
     let codec = LinesCodec::new_with_max_length(MAX_JSONRPC_LINE_BYTES);
     let mut lines = FramedRead::new(stdin, codec);
 
-    let (response_tx, mut response_rx) = mpsc::channel::<JsonRpcResponse>(RESPONSE_QUEUE_CAPACITY);
+    let (response_tx, mut response_rx) = mpsc::channel::<OutgoingMessage>(RESPONSE_QUEUE_CAPACITY);
     let active_requests = ActiveRequests::new(response_tx);
 
     let shutdown = tokio::signal::ctrl_c();
@@ -39,8 +41,8 @@ pub async fn run_stdio_server() -> Result<()> {
                     }
                 }
             }
-            Some(response) = response_rx.recv() => {
-                if send_response(&response, &mut stdout).await.is_err() {
+            Some(message) = response_rx.recv() => {
+                if send_message(&message, &mut stdout).await.is_err() {
                     active_requests.cancel_all_requests("Failed to write response").await;
                     break;
                 }
@@ -52,6 +54,7 @@ pub async fn run_stdio_server() -> Result<()> {
         }
     }
 
+    crate::interface::temp_audio::remove_live_temp_files();
     Ok(())
 }
 
@@ -118,9 +121,11 @@ async fn handle_request(
     };
 
     match request.method {
-        RequestMethod::Initialize => {
+        RequestMethod::Initialize(params) => {
+            let protocol_version = negotiate_protocol_version(params.protocol_version.as_deref());
+            active_requests.set_protocol_version(protocol_version);
             let result = InitializeResult {
-                protocol_version: crate::interface::mcp_server::protocol::MCP_VERSION.to_string(),
+                protocol_version: protocol_version.to_string(),
                 server_info: ServerInfo {
                     name: "voicevox-mcp".to_string(),
                     version: env!("CARGO_PKG_VERSION").to_string(),
@@ -132,6 +137,8 @@ async fn handle_request(
             };
             let response = serialize_success_response(request.id, result);
             send_response(&response, stdout).await?;
+            // A session is starting: warm the model before its first tool call.
+            tokio::spawn(prewarm_daemon(mcp_daemon_options()));
         }
         RequestMethod::ToolsList => {
             let result = ToolsListResult {
@@ -155,7 +162,13 @@ async fn handle_request(
                 }
             };
             active_requests
-                .spawn_tool_handler(request_id, request.id, call.name, call.arguments)
+                .spawn_tool_handler(
+                    request_id,
+                    request.id,
+                    call.name,
+                    call.arguments,
+                    call.progress_token,
+                )
                 .await;
         }
         RequestMethod::Unknown(method) => {
@@ -184,7 +197,14 @@ async fn handle_notification(raw_message: Value, active_requests: &ActiveRequest
 }
 
 async fn send_response(response: &JsonRpcResponse, stdout: &mut tokio::io::Stdout) -> Result<()> {
-    let response_str = serde_json::to_string(response)?;
+    write_line(serde_json::to_string(response)?, stdout).await
+}
+
+async fn send_message(message: &OutgoingMessage, stdout: &mut tokio::io::Stdout) -> Result<()> {
+    write_line(serde_json::to_string(message)?, stdout).await
+}
+
+async fn write_line(response_str: String, stdout: &mut tokio::io::Stdout) -> Result<()> {
     stdout.write_all(response_str.as_bytes()).await?;
     stdout.write_all(b"\n").await?;
     stdout.flush().await?;
@@ -3,7 +3,9 @@ use crate::infrastructure::daemon::{
     DaemonError, DaemonResult, ensure_daemon_running,
     recover_stuck_daemon_and_retry as recover_stuck_daemon_and_retry_impl,
 };
-use crate::infrastructure::paths::get_socket_path;
+use crate::interface::mcp_server::daemon_options::{
+    McpDaemonOptions, connect_mcp_daemon_client, init_mcp_daemon_options,
+};
 use crate::interface::{AppOutput, StdAppOutput};
 use anyhow::Result;
 use std::path::Path;
@@ -51,12 +53,14 @@ async fn run_mcp_startup_phase(
     }
 }
 
-async fn ensure_daemon_running_for_mcp(output: &dyn AppOutput) -> DaemonResult<()> {
-    let socket_path = get_socket_path();
+async fn ensure_daemon_running_for_mcp(
+    socket_path: &Path,
+    output: &dyn AppOutput,
+) -> DaemonResult<()> {
     let mut phase = McpStartupPhase::InitialStart;
 
     loop {
-        match run_mcp_startup_phase(phase, &socket_path, output).await? {
+        match run_mcp_startup_phase(phase, socket_path, output).await? {
             None => return Ok(()),
             Some(next) => {
                 phase = next;
@@ -107,18 +111,40 @@ fn warn_nonfatal_daemon_issue(error: &DaemonError, output: &dyn AppOutput) {
     }
 }
 
-/// Runs the MCP server application flow, attempting daemon startup first.
+/// Checks that a host-managed daemon answers, without starting one.
+async fn check_external_daemon(options: &McpDaemonOptions, output: &dyn AppOutput) {
+    if connect_mcp_daemon_client(options).await.is_err() {
+        print_mcp_warning_with_detail(
+            "Daemon auto-start is disabled and no daemon is answering.",
+            &format!(
+                "Start voicevox-daemon with --socket-path {}",
+                options.socket_path.display()
+            ),
+            output,
+        );
+    }
+}
+
+/// Runs the MCP server application flow, attempting daemon startup first
+/// unless `options` leave the daemon to the host.
 ///
 /// # Errors
 ///
 /// Returns an error only if the MCP stdio server itself fails.
-pub async fn run_mcp_server_app() -> Result<()> {
+pub async fn run_mcp_server_app(options: McpDaemonOptions) -> Result<()> {
     let output = StdAppOutput;
-    run_mcp_server_app_with_output(&output).await
+    run_mcp_server_app_with_output(options, &output).await
 }
 
-pub async fn run_mcp_server_app_with_output(output: &dyn AppOutput) -> Result<()> {
-    if let Err(error) = ensure_daemon_running_for_mcp(output).await {
+pub async fn run_mcp_server_app_with_output(
+    options: McpDaemonOptions,
+    output: &dyn AppOutput,
+) -> Result<()> {
+    crate::interface::audio::init_audio_backend(None)?;
+    let options = init_mcp_daemon_options(options);
+    if !options.auto_start {
+        check_external_daemon(options, output).await;
+    } else if let Err(error) = ensure_daemon_running_for_mcp(&options.socket_path, output).await {
         warn_nonfatal_daemon_issue(&error, output);
     }
 
@@ -36,12 +36,16 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
                 properties: json_object(json!({
                     "text": {
                         "type": "string",
-                        "description": "Japanese text (15-50 chars optimal, 100+ may need splitting)"
+                        "description": "Japanese text; long text is split into sentence chunks and spoken in order"
                     },
                     "style_id": {
                         "type": "integer",
                         "description": "3=normal, 1=happy, 22=whisper, 76=sad, 75=confused"
                     },
+                    "voice": {
+                        "type": "string",
+                        "description": "Voice name instead of style_id, e.g. zundamon, zundamon-whisper, shikoku-metan-sweet, ずんだもん/ささやき"
+                    },
                     "rate": {
                         "type": "number",
                         "description": "Speed (0.5-2.0, default 1.0)",
@@ -53,9 +57,66 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
                         "type": "boolean",
                         "description": "Lower latency mode",
                         "default": true
+                    },
+                    "rechunk_on_failure": {
+                        "type": "boolean",
+                        "description": "For long text: after retries, speak a failing chunk as smaller pieces before giving up",
+                        "default": true
+                    }
+                })),
+                required: Some(vec!["text".to_string()]),
+            },
+        },
+        ToolDefinition {
+            name: "synthesize_to_file".to_string(),
+            description: "Synthesize Japanese text with VOICEVOX without playing it. Saves the audio to output_path on the server's machine, or returns it as base64 audio content when output_path is omitted, so clients on another host can play it themselves.".to_string(),
+            input_schema: ToolInputSchema {
+                schema_type: "object".to_string(),
+                properties: json_object(json!({
+                    "text": {
+                        "type": "string",
+                        "description": "Japanese text to synthesize"
+                    },
+                    "style_id": {
+                        "type": "integer",
+                        "description": "3=normal, 1=happy, 22=whisper, 76=sad, 75=confused"
+                    },
+                    "voice": {
+                        "type": "string",
+                        "description": "Voice name instead of style_id, e.g. zundamon, zundamon-whisper"
+                    },
+                    "rate": {
+                        "type": "number",
+                        "description": "Speed (0.5-2.0, default 1.0)",
+                        "minimum": 0.5,
+                        "maximum": 2.0,
+                        "default": 1.0
+                    },
+                    "output_path": {
+                        "type": "string",
+                        "description": "Absolute path on the server to write, ending in .wav, .flac, .opus, .ogg, or .mp3; omit to get the audio back as base64"
+                    },
+                    "format": {
+                        "type": "string",
+                        "enum": ["wav", "flac", "opus", "mp3"],
+                        "description": "Audio encoding; defaults to the output_path extension, else wav"
+                    },
+                    "overwrite": {
+                        "type": "boolean",
+                        "description": "Replace an existing file at output_path instead of failing",
+                        "default": false
                     }
                 })),
-                required: Some(vec!["text".to_string(), "style_id".to_string()]),
+                required: Some(vec!["text".to_string()]),
+            },
+        },
+        ToolDefinition {
+            name: "stop_speech".to_string(),
+            description: "Immediately stop any speech text_to_speech is playing, e.g. when the user starts talking. The interrupted text_to_speech calls end with a cancellation result. Files being written by synthesize_to_file are left alone.".to_string(),
+            input_schema: ToolInputSchema {
+                schema_type: "object".to_string(),
+                properties: serde_json::Map::new(),
+                required: None,
             },
         },
         ToolDefinition {
@@ -6,14 +6,60 @@ use super::types::{ToolCallResult, text_result};
 use crate::domain::voice::{
     ListVoiceStylesFilter, SpeakerStyles, VoiceStyle, filter_speakers, normalized_filters,
 };
-use crate::infrastructure::daemon::client::DaemonClient;
-use crate::interface::synthesis::flow::connect_daemon_client_auto_start;
+use crate::domain::voice_alias::VoiceAliases;
+use crate::infrastructure::paths::voice_aliases_path;
+use crate::infrastructure::speaker_cache::{
+    load_fresh_speakers, remember_speakers, speaker_cache_path,
+};
+use crate::infrastructure::voice_aliases::load_voice_aliases;
+use crate::interface::mcp_server::daemon_options::{connect_mcp_daemon_client, mcp_daemon_options};
 
-async fn connect_daemon_client_for_tool() -> Result<DaemonClient> {
-    let socket_path = crate::infrastructure::paths::get_socket_path();
-    connect_daemon_client_auto_start(&socket_path)
-        .await
-        .context("Failed to connect to VOICEVOX daemon")
+/// The user's voice aliases as the daemon serves them, so tools and the daemon
+/// agree on names; the local aliases file stands in when no daemon answers.
+///
+/// # Errors
+///
+/// Returns an error if the daemon is unreachable and the local file is unreadable.
+pub(crate) async fn fetch_voice_aliases() -> Result<VoiceAliases> {
+    if let Ok(mut client) = connect_mcp_daemon_client(mcp_daemon_options()).await
+        && let Ok(aliases) = client.voice_mapping().await
+    {
+        return Ok(aliases);
+    }
+    load_voice_aliases(&voice_aliases_path())
+}
+
+/// Lists every installed speaker and style as the daemon reports them, reusing the
+/// client-side speaker cache while it is fresh.
+///
+/// # Errors
+///
+/// Returns an error if the daemon cannot be contacted.
+pub(crate) async fn fetch_speaker_styles() -> Result<Vec<SpeakerStyles>> {
+    let options = mcp_daemon_options();
+    let speakers = match load_fresh_speakers(&speaker_cache_path(), &options.socket_path) {
+        Some(cache) => cache.speakers,
+        None => {
+            let mut client = connect_mcp_daemon_client(options).await?;
+            let (speakers, style_to_model) = client.list_speakers_with_models().await?;
+            remember_speakers(&options.socket_path, speakers.clone(), style_to_model);
+            speakers
+        }
+    };
+    Ok(speakers
+        .into_iter()
+        .map(|speaker| SpeakerStyles {
+            speaker_name: speaker.name.to_string(),
+            styles: speaker
+                .styles
+                .into_iter()
+                .map(|style| VoiceStyle {
+                    name: style.name.to_string(),
+                    id: style.id,
+                })
+                .collect(),
+        })
+        .collect())
 }
 
 #[derive(Debug, Deserialize)]
@@ -57,23 +103,7 @@ pub async fn handle_voice_style_list_tool(arguments: Value) -> Result<ToolCallRe
         style_name: params.style_name,
     };
 
-    let mut client = connect_daemon_client_for_tool().await?;
-    let speakers = client
-        .list_speakers()
-        .await?
-        .into_iter()
-        .map(|speaker| SpeakerStyles {
-            speaker_name: speaker.name.to_string(),
-            styles: speaker
-                .styles
-                .into_iter()
-                .map(|style| VoiceStyle {
-                    name: style.name.to_string(),
-                    id: style.id,
-                })
-                .collect(),
-        })
-        .collect::<Vec<_>>();
+    let speakers = fetch_speaker_styles().await?;
 
     let (speaker_name_filter, style_name_filter) = normalized_filters(&filter);
     let filtered_results = filter_speakers(
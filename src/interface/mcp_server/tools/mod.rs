@@ -1,5 +1,8 @@
 pub mod list;
 pub mod list_voice_styles;
+pub mod progress;
 pub mod registry;
+pub mod stop_speech;
+pub mod synthesize_to_file;
 pub mod text_to_speech;
 pub mod types;
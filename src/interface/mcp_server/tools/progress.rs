@@ -0,0 +1,32 @@
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+
+use crate::interface::mcp_server::protocol::{JsonRpcNotification, OutgoingMessage};
+
+/// Sends `notifications/progress` for one tool call that supplied a progress token.
+#[derive(Debug, Clone)]
+pub struct ProgressReporter {
+    token: Value,
+    sender: mpsc::Sender<OutgoingMessage>,
+}
+
+impl ProgressReporter {
+    #[must_use]
+    pub const fn new(token: Value, sender: mpsc::Sender<OutgoingMessage>) -> Self {
+        Self { token, sender }
+    }
+
+    /// Reports `progress` of `total` steps; a closed connection is ignored.
+    pub async fn report(&self, progress: usize, total: usize, message: &str) {
+        let notification = JsonRpcNotification::new(
+            "notifications/progress",
+            json!({
+                "progressToken": self.token,
+                "progress": progress,
+                "total": total,
+                "message": message,
+            }),
+        );
+        let _ = self.sender.send(notification.into()).await;
+    }
+}
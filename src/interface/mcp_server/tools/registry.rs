@@ -3,6 +3,7 @@ use serde_json::Value;
 use tokio::sync::oneshot;
 
 use super::list::{ToolDefinition, get_tool_definitions};
+use super::progress::ProgressReporter;
 use super::types::ToolCallResult;
 
 #[must_use]
@@ -10,15 +11,27 @@ pub fn tool_definitions() -> Vec<ToolDefinition> {
     get_tool_definitions()
 }
 
+/// Runs a cancellable tool for a session that negotiated `protocol_version`.
 #[allow(clippy::future_not_send)]
 pub async fn execute_tool_request(
     tool_name: &str,
     arguments: Value,
     cancel_rx: Option<oneshot::Receiver<String>>,
+    progress: Option<ProgressReporter>,
+    protocol_version: &str,
 ) -> Result<ToolCallResult> {
     match tool_name {
         "text_to_speech" => {
-            super::text_to_speech::handle_text_to_speech_cancellable(arguments, cancel_rx).await
+            super::text_to_speech::handle_text_to_speech_cancellable(arguments, cancel_rx, progress)
+                .await
+        }
+        "synthesize_to_file" => {
+            super::synthesize_to_file::handle_synthesize_to_file(
+                arguments,
+                cancel_rx,
+                protocol_version,
+            )
+            .await
         }
         "list_voice_styles" => {
             super::list_voice_styles::handle_voice_style_list_tool(arguments).await
@@ -0,0 +1,14 @@
+use super::types::{ToolCallResult, text_result};
+
+/// Cancellation reason given to the `text_to_speech` calls `stop_speech` interrupts.
+pub const STOP_SPEECH_REASON: &str = "Stopped by stop_speech";
+
+/// Reports how many `text_to_speech` calls were stopped; stopping nothing is not an error.
+#[must_use]
+pub fn stop_speech_result(stopped: usize) -> ToolCallResult {
+    match stopped {
+        0 => text_result("Nothing was being spoken", false),
+        1 => text_result("Stopped 1 text_to_speech call", false),
+        count => text_result(format!("Stopped {count} text_to_speech calls"), false),
+    }
+}
@@ -0,0 +1,363 @@
+use anyhow::{Context, Result, bail, ensure};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::Deserialize;
+use serde_json::Value;
+use std::io::Write;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use tokio::sync::oneshot;
+
+use super::text_to_speech::{
+    WarningCollector, cancellation_result, default_tool_rate, resolve_tool_style_id,
+    timed_out_message,
+};
+use super::types::{EmbeddedResource, ToolCallResult, ToolContent, text_result};
+use crate::domain::synthesis::{TextSynthesisRequest, validate_basic_request};
+use crate::infrastructure::daemon::client::is_duplicate_skipped;
+use crate::infrastructure::ipc::{DEFAULT_SYNTHESIS_PITCH, SynthesisOptions};
+use crate::interface::audio::OutputFormat;
+use crate::interface::mcp_server::daemon_error::format_daemon_client_error_for_mcp;
+use crate::interface::mcp_server::daemon_options::mcp_daemon_options;
+use crate::interface::mcp_server::protocol::supports_audio_content;
+use crate::interface::synthesis::flow::{
+    DaemonSynthesisBytesRequest, SynthesisFlowOutcome, synthesize_bytes_via_daemon_cancellable,
+};
+
+/// Mode of written audio files, as they would get from a typical umask.
+const OUTPUT_FILE_MODE: u32 = 0o644;
+
+#[derive(Debug, Deserialize)]
+struct SynthesizeToFileInput {
+    text: String,
+    style_id: Option<u32>,
+    voice: Option<String>,
+    #[serde(default = "default_tool_rate")]
+    rate: f32,
+    /// Where to write the audio on this machine; `None` returns it inline.
+    output_path: Option<PathBuf>,
+    format: Option<String>,
+    #[serde(default)]
+    overwrite: bool,
+}
+
+/// File extensions `output_path` may have, so a tool call can only ever create or
+/// replace audio files.
+const AUDIO_EXTENSIONS: [&str; 5] = ["wav", "flac", "opus", "ogg", "mp3"];
+
+/// Resolves where the tool may write `path`: an absolute path to an audio file in an
+/// existing directory, inside `output_dir` (symlinks resolved) when one is set, and
+/// never over a symlink, a non-file, or an existing file unless `overwrite`.
+fn check_output_path(path: &Path, overwrite: bool, output_dir: Option<&Path>) -> Result<PathBuf> {
+    ensure!(
+        path.is_absolute(),
+        "output_path must be absolute, got '{}'",
+        path.display()
+    );
+    ensure!(
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| AUDIO_EXTENSIONS
+                .iter()
+                .any(|allowed| extension.eq_ignore_ascii_case(allowed))),
+        "output_path must end in .{}, got '{}'",
+        AUDIO_EXTENSIONS.join(", ."),
+        path.display()
+    );
+    let (Some(parent), Some(file_name)) = (path.parent(), path.file_name()) else {
+        bail!("output_path '{}' names no file", path.display());
+    };
+    let parent = parent
+        .canonicalize()
+        .with_context(|| format!("The directory of '{}' does not exist", path.display()))?;
+    if let Some(output_dir) = output_dir {
+        let output_dir = output_dir.canonicalize().with_context(|| {
+            format!(
+                "The output directory '{}' does not exist",
+                output_dir.display()
+            )
+        })?;
+        ensure!(
+            parent.starts_with(&output_dir),
+            "output_path must be inside '{}', got '{}'",
+            output_dir.display(),
+            path.display()
+        );
+    }
+
+    let target = parent.join(file_name);
+    if let Ok(metadata) = std::fs::symlink_metadata(&target) {
+        ensure!(
+            metadata.file_type().is_file(),
+            "'{}' exists and is not a regular file",
+            path.display()
+        );
+        ensure!(
+            overwrite,
+            "'{}' already exists; pass overwrite: true to replace it",
+            path.display()
+        );
+    }
+    Ok(target)
+}
+
+/// Writes `audio` to `path` through a temporary file renamed into place, so a failed
+/// write never leaves a truncated file, failing rather than replacing one unless
+/// `overwrite`.
+async fn write_audio(path: &Path, audio: Vec<u8>, overwrite: bool) -> Result<()> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let parent = path.parent().unwrap_or_else(|| Path::new("/"));
+        let mut temp = tempfile::Builder::new()
+            .prefix(".voicevox-")
+            .tempfile_in(parent)
+            .with_context(|| format!("Failed to create a file in '{}'", parent.display()))?;
+        temp.write_all(&audio)
+            .and_then(|()| temp.as_file().sync_all())
+            .with_context(|| format!("Failed to write '{}'", path.display()))?;
+        temp.as_file()
+            .set_permissions(std::fs::Permissions::from_mode(OUTPUT_FILE_MODE))?;
+        if overwrite {
+            temp.persist(&path)
+                .map(drop)
+                .with_context(|| format!("Failed to replace '{}'", path.display()))
+        } else {
+            temp.persist_noclobber(&path)
+                .map(drop)
+                .with_context(|| format!("Failed to create '{}'", path.display()))
+        }
+    })
+    .await
+    .context("Audio writer task failed")?
+}
+
+fn saved_result(path: &Path, format: OutputFormat, bytes: usize, warnings: &[String]) -> String {
+    let mut text = format!(
+        "Saved {bytes} bytes of {} to {}",
+        format.mime_type(),
+        path.display()
+    );
+    for warning in warnings {
+        text.push('\n');
+        text.push_str(warning);
+    }
+    text
+}
+
+/// The audio as `audio` content, or as an embedded resource for sessions on a
+/// revision that predates `audio` content.
+fn inline_audio_result(
+    audio: &[u8],
+    format: OutputFormat,
+    warnings: &[String],
+    protocol_version: &str,
+) -> ToolCallResult {
+    let mut text = "ok".to_string();
+    for warning in warnings {
+        text.push('\n');
+        text.push_str(warning);
+    }
+    let data = BASE64.encode(audio);
+    let mime_type = format.mime_type().to_string();
+    let audio = if supports_audio_content(protocol_version) {
+        ToolContent::Audio { data, mime_type }
+    } else {
+        ToolContent::Resource {
+            resource: EmbeddedResource {
+                uri: format!("voicevox:synthesized.{}", format.extension()),
+                mime_type,
+                blob: data,
+            },
+        }
+    };
+    ToolCallResult {
+        content: vec![audio, ToolContent::Text { text }],
+        is_error: None,
+    }
+}
+
+/// Executes the `synthesize_to_file` tool: synthesizes without playing, then saves
+/// the audio at `output_path` or returns it as base64 content suited to the
+/// session's `protocol_version`.
+///
+/// # Errors
+///
+/// Returns an error if parameters are invalid, the output path is refused, or the
+/// audio cannot be encoded or written.
+#[allow(clippy::future_not_send)]
+pub async fn handle_synthesize_to_file(
+    arguments: Value,
+    cancel_rx: Option<oneshot::Receiver<String>>,
+    protocol_version: &str,
+) -> Result<ToolCallResult> {
+    let input: SynthesizeToFileInput =
+        serde_json::from_value(arguments).context("Invalid parameters for synthesize_to_file")?;
+    let requested_format = input
+        .format
+        .as_deref()
+        .map(OutputFormat::parse)
+        .transpose()?;
+    let format = OutputFormat::resolve(requested_format, input.output_path.as_deref());
+    let output_path = input
+        .output_path
+        .as_deref()
+        .map(|path| {
+            check_output_path(
+                path,
+                input.overwrite,
+                crate::config::mcp_output_dir().as_deref(),
+            )
+        })
+        .transpose()?;
+    let style_id = resolve_tool_style_id(input.style_id, input.voice.as_deref()).await?;
+    validate_basic_request(&TextSynthesisRequest {
+        text: &input.text,
+        style_id,
+        rate: input.rate,
+        pitch: DEFAULT_SYNTHESIS_PITCH,
+    })?;
+
+    let daemon = mcp_daemon_options();
+    let output = WarningCollector::default();
+    let request = DaemonSynthesisBytesRequest {
+        text: &input.text,
+        style_id,
+        options: SynthesisOptions::with_rate(input.rate),
+        socket_path: &daemon.socket_path,
+        auto_start_daemon: daemon.auto_start,
+        ensure_models_if_missing: false,
+        quiet_setup_messages: true,
+    };
+    let mut cancel_rx = cancel_rx;
+    let synthesis = synthesize_bytes_via_daemon_cancellable(&request, &output, cancel_rx.as_mut());
+    let wav_data = match tokio::time::timeout(daemon.synthesis_timeout, synthesis).await {
+        Err(_) => {
+            return Ok(text_result(
+                timed_out_message(daemon.synthesis_timeout),
+                true,
+            ));
+        }
+        Ok(Ok(SynthesisFlowOutcome::Completed(wav_data))) => wav_data,
+        Ok(Ok(SynthesisFlowOutcome::Canceled(reason))) => return Ok(cancellation_result(reason)),
+        // A repeat the daemon suppressed has no audio to hand back.
+        Ok(Err(error)) if is_duplicate_skipped(&error) => {
+            return Ok(text_result(
+                "The daemon skipped this text as a repeat of a recent request; no audio was produced",
+                true,
+            ));
+        }
+        Ok(Err(error)) => {
+            return Ok(text_result(
+                format_daemon_client_error_for_mcp(&error),
+                true,
+            ));
+        }
+    };
+
+    let audio = format.encode(&wav_data)?;
+    let warnings = output.take();
+    match &output_path {
+        Some(path) => {
+            let bytes = audio.len();
+            write_audio(path, audio, input.overwrite).await?;
+            Ok(text_result(
+                saved_result(path, format, bytes, &warnings),
+                false,
+            ))
+        }
+        None => Ok(inline_audio_result(
+            &audio,
+            format,
+            &warnings,
+            protocol_version,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn output_paths_respect_the_overwrite_policy() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("out.wav");
+
+        assert!(check_output_path(Path::new("out.wav"), true, None).is_err());
+        let target = check_output_path(&path, false, None).expect("new file");
+        write_audio(&target, b"first".to_vec(), false)
+            .await
+            .expect("write");
+
+        assert!(check_output_path(&path, false, None).is_err());
+        assert!(
+            write_audio(&target, b"second".to_vec(), false)
+                .await
+                .is_err()
+        );
+        check_output_path(&path, true, None).expect("overwrite allowed");
+        write_audio(&target, b"2nd".to_vec(), true)
+            .await
+            .expect("overwrite");
+        assert_eq!(std::fs::read(&path).expect("read"), b"2nd");
+        assert_eq!(std::fs::read_dir(dir.path()).expect("list").count(), 1);
+    }
+
+    #[test]
+    fn output_paths_stay_audio_files_inside_the_output_dir() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let outside = tempfile::tempdir().expect("tempdir");
+        let bashrc = dir.path().join(".bashrc");
+        std::fs::write(&bashrc, "export PATH").expect("write");
+
+        assert!(check_output_path(&bashrc, true, None).is_err());
+        assert!(check_output_path(&dir.path().join("notes.txt"), true, None).is_err());
+
+        let escape = dir.path().join("escape");
+        std::os::unix::fs::symlink(outside.path(), &escape).expect("symlink");
+        let inside = check_output_path(&dir.path().join("a.flac"), false, Some(dir.path()));
+        assert!(inside.is_ok());
+        assert!(check_output_path(&escape.join("a.flac"), false, Some(dir.path())).is_err());
+
+        let linked = dir.path().join("linked.wav");
+        std::os::unix::fs::symlink(&bashrc, &linked).expect("symlink");
+        assert!(check_output_path(&linked, true, None).is_err());
+    }
+
+    #[test]
+    fn inline_audio_is_base64_audio_content() {
+        let result = inline_audio_result(b"RIFF", OutputFormat::Wav, &[], "2025-06-18");
+
+        assert_eq!(
+            serde_json::to_value(&result).expect("json"),
+            serde_json::json!({
+                "content": [
+                    { "type": "audio", "data": "UklGRg==", "mimeType": "audio/wav" },
+                    { "type": "text", "text": "ok" }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn sessions_without_audio_content_get_an_embedded_resource() {
+        let result = inline_audio_result(b"RIFF", OutputFormat::Flac, &[], "2024-11-05");
+
+        assert_eq!(
+            serde_json::to_value(&result).expect("json"),
+            serde_json::json!({
+                "content": [
+                    {
+                        "type": "resource",
+                        "resource": {
+                            "uri": "voicevox:synthesized.flac",
+                            "mimeType": "audio/flac",
+                            "blob": "UklGRg=="
+                        }
+                    },
+                    { "type": "text", "text": "ok" }
+                ]
+            })
+        );
+    }
+}
@@ -1,29 +1,49 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use serde::Deserialize;
 use serde_json::Value;
+use std::sync::{Mutex, PoisonError};
 use std::time::Duration;
 use tokio::runtime::Handle;
 use tokio::sync::oneshot;
 
-use super::types::{ToolCallResult, success_result, text_result};
+use super::list_voice_styles::{fetch_speaker_styles, fetch_voice_aliases};
+use super::progress::ProgressReporter;
+use super::types::{ToolCallResult, ToolContent, success_result, text_result};
 use crate::domain::synthesis::wav::concatenate_wav_segments;
 use crate::domain::synthesis::{TextSynthesisRequest, validate_basic_request};
 use crate::domain::text_to_speech::{
-    SynthesizeParams, default_rate, default_streaming, validate_style_id,
+    MAX_CHUNK_CHARS, SynthesizeParams, chunk_sentences, default_rate, default_streaming,
+    rechunk_smaller, validate_style_id,
 };
+use crate::domain::voice_alias::expand_alias;
+use crate::domain::voice_name::resolve_voice_name;
+use crate::infrastructure::daemon::client::{DuplicateSkipped, is_duplicate_skipped};
 use crate::infrastructure::daemon::startup;
+use crate::infrastructure::ipc::{DEFAULT_SYNTHESIS_PITCH, DaemonWarning, SynthesisOptions};
+use crate::interface::AppOutput;
+use crate::interface::audio::{AudioBackend, OutputFormat, audio_backend};
 use crate::interface::mcp_server::daemon_error::{
     format_daemon_client_error_for_mcp, is_retryable_daemon_synthesis_error,
 };
-use crate::interface::playback::{PlaybackOutcome, PlaybackRequest, emit_and_play};
+use crate::interface::mcp_server::daemon_options::{format_timeout, mcp_daemon_options};
+use crate::interface::playback::{
+    PlaybackOutcome, PlaybackRequest, emit_and_play, play_sink_with_cancel,
+};
+use crate::interface::sink::{DEFAULT_SINK_CAPACITY, PlaybackSink, RodioSink, wait_until_drained};
+use crate::interface::synthesis::StreamingSynthesizer;
 use crate::interface::synthesis::flow::{
-    DaemonSynthesisBytesRequest, NoopAppOutput, SynthesisFlowOutcome,
-    synthesize_bytes_via_daemon_cancellable,
+    DaemonSynthesisBytesRequest, SynthesisFlowOutcome, synthesize_bytes_via_daemon_cancellable,
 };
 use crate::interface::synthesis::mode::{SynthesisMode, select_synthesis_mode_with_config};
 
 const MCP_DAEMON_MAX_RETRIES: u32 = 2;
 
+/// Tries per chunk of a chunked call before it is re-chunked or the call fails.
+const CHUNK_MAX_ATTEMPTS: u32 = 3;
+
+/// Wait before the first retry of a failed chunk, doubled for each later one.
+const CHUNK_RETRY_DELAY: Duration = Duration::from_millis(500);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum McpTtsPhase {
     Attempt,
@@ -31,14 +51,29 @@ enum McpTtsPhase {
     Finish,
 }
 
+pub(super) fn default_tool_rate() -> f32 {
+    crate::config::user_config()
+        .rate
+        .unwrap_or_else(default_rate)
+}
+
 #[derive(Debug, Deserialize)]
 struct TextToSpeechToolInput {
     text: String,
-    style_id: u32,
-    #[serde(default = "default_rate")]
+    style_id: Option<u32>,
+    /// Voice name such as `zundamon-whisper`, resolved against installed voices.
+    voice: Option<String>,
+    #[serde(default = "default_tool_rate")]
     rate: f32,
     #[serde(default = "default_streaming")]
     streaming: bool,
+    /// Speak a chunk that keeps failing as smaller pieces before giving up.
+    #[serde(default = "default_rechunk_on_failure")]
+    rechunk_on_failure: bool,
+}
+
+const fn default_rechunk_on_failure() -> bool {
+    true
 }
 
 enum DaemonRetryStep {
@@ -52,7 +87,8 @@ struct DaemonRetryContext<'a> {
     style_id: u32,
     rate: f32,
     socket_path: &'a std::path::Path,
-    output: &'a NoopAppOutput,
+    auto_start_daemon: bool,
+    output: &'a WarningCollector,
     attempt: &'a mut u32,
     retry_delay: &'a mut Duration,
     last_error: &'a mut Option<anyhow::Error>,
@@ -67,11 +103,14 @@ struct DaemonRetryContext<'a> {
 /// Returns an error if parameter validation or synthesis fails.
 #[allow(clippy::future_not_send)]
 pub async fn handle_text_to_speech(arguments: Value) -> Result<ToolCallResult> {
-    handle_text_to_speech_cancellable(arguments, None).await
+    handle_text_to_speech_cancellable(arguments, None, None).await
 }
 
 /// Executes the `text_to_speech` tool with optional cancellation support.
 ///
+/// Text longer than [`MAX_CHUNK_CHARS`] is spoken in sentence-aligned chunks, with a
+/// progress notification after each chunk when the caller supplied a progress token.
+///
 /// # Errors
 ///
 /// Returns an error if parameters are invalid or synthesis fails.
@@ -79,13 +118,15 @@ pub async fn handle_text_to_speech(arguments: Value) -> Result<ToolCallResult> {
 pub async fn handle_text_to_speech_cancellable(
     arguments: Value,
     cancel_rx: Option<oneshot::Receiver<String>>,
+    progress: Option<ProgressReporter>,
 ) -> Result<ToolCallResult> {
     let parsed: TextToSpeechToolInput =
         serde_json::from_value(arguments).context("Invalid parameters for text_to_speech")?;
-    validate_style_id(parsed.style_id)?;
+    let style_id = resolve_tool_style_id(parsed.style_id, parsed.voice.as_deref()).await?;
+    let rechunk_on_failure = parsed.rechunk_on_failure;
     let params = SynthesizeParams {
         text: parsed.text,
-        style_id: parsed.style_id,
+        style_id,
         rate: parsed.rate,
         streaming: parsed.streaming,
     };
@@ -93,12 +134,359 @@ pub async fn handle_text_to_speech_cancellable(
         text: &params.text,
         style_id: params.style_id,
         rate: params.rate,
+        pitch: DEFAULT_SYNTHESIS_PITCH,
     })?;
 
-    if params.streaming {
+    let chunks = if params.text.chars().count() > MAX_CHUNK_CHARS {
+        let sentences = crate::config::Config::default()
+            .text_splitter
+            .splitter()
+            .split(&params.text);
+        chunk_sentences(sentences, MAX_CHUNK_CHARS)
+    } else {
+        Vec::new()
+    };
+    let timeout = mcp_daemon_options().synthesis_timeout;
+    if chunks.len() > 1 {
+        let chunked = ChunkedSpeech {
+            params,
+            rechunk_on_failure,
+            progress: progress.as_ref(),
+            timeout,
+        };
+        speak_chunks(&chunked, chunks, cancel_rx).await
+    } else {
+        let mut cancel_rx = cancel_rx;
+        match speak_chunk(params, &mut cancel_rx, timeout).await? {
+            (_, Some(Interruption::TimedOut(timeout))) => {
+                Ok(text_result(timed_out_message(timeout), true))
+            }
+            (result, _) => Ok(result),
+        }
+    }
+}
+
+/// Why a chunk stopped before it finished on its own.
+enum Interruption {
+    Cancelled(String),
+    TimedOut(Duration),
+}
+
+pub(super) fn timed_out_message(timeout: Duration) -> String {
+    format!("Synthesis timed out after {}", format_timeout(timeout))
+}
+
+#[allow(clippy::future_not_send)]
+async fn speak(
+    params: SynthesizeParams,
+    cancel_rx: Option<oneshot::Receiver<String>>,
+) -> Result<ToolCallResult> {
+    let result = if params.streaming {
         handle_streaming_synthesis(params, cancel_rx).await
     } else {
         handle_daemon_synthesis(params, cancel_rx).await
+    };
+    match result {
+        Err(error) if is_duplicate_skipped(&error) => Ok(skipped_result()),
+        result => result,
+    }
+}
+
+/// Settings shared by every chunk of a chunked `text_to_speech` call.
+struct ChunkedSpeech<'a> {
+    params: SynthesizeParams,
+    rechunk_on_failure: bool,
+    progress: Option<&'a ProgressReporter>,
+    timeout: Duration,
+}
+
+/// Speaks `chunks` in order, stopping at the first failure, timeout, or cancellation.
+///
+/// A chunk that fails with a transient error is retried with backoff, then spoken as
+/// smaller pieces when allowed. Each retry is reported as progress and listed in the
+/// result, which also says how many chunks were spoken, so an agent can resume from there.
+#[allow(clippy::future_not_send)]
+async fn speak_chunks(
+    chunked: &ChunkedSpeech<'_>,
+    chunks: Vec<String>,
+    mut cancel_rx: Option<oneshot::Receiver<String>>,
+) -> Result<ToolCallResult> {
+    let total = chunks.len();
+    let mut warnings = Vec::new();
+    let mut retries = Vec::new();
+    for (index, chunk) in chunks.into_iter().enumerate() {
+        let mut log = ChunkRetryLog {
+            chunk: index + 1,
+            total,
+            progress: chunked.progress,
+            notes: &mut retries,
+        };
+        let (result, interruption) =
+            speak_chunk_with_retry(chunked, chunk, &mut cancel_rx, &mut log).await?;
+        let text = result_text(&result);
+        match interruption {
+            Some(Interruption::Cancelled(reason)) => {
+                return Ok(text_result(
+                    format!(
+                        "{} after {index} of {total} chunks",
+                        cancellation_message(&reason)
+                    ),
+                    true,
+                ));
+            }
+            Some(Interruption::TimedOut(timeout)) => {
+                return Ok(text_result(
+                    format!(
+                        "{} on chunk {} of {total}; {index} spoken",
+                        timed_out_message(timeout),
+                        index + 1
+                    ),
+                    true,
+                ));
+            }
+            None => {}
+        }
+        if result.is_error == Some(true) {
+            let mut message = format!(
+                "Chunk {} of {total} failed after {index} spoken: {text}",
+                index + 1
+            );
+            append_lines(&mut message, &retries);
+            return Ok(text_result(message, true));
+        }
+        warnings.extend(
+            text.lines()
+                .filter(|line| line.starts_with("warning:"))
+                .map(str::to_owned),
+        );
+        if let Some(progress) = chunked.progress {
+            progress
+                .report(
+                    index + 1,
+                    total,
+                    &format!("Spoke chunk {} of {total}", index + 1),
+                )
+                .await;
+        }
+    }
+    let mut summary = format!("ok: spoke {total} chunks");
+    append_lines(&mut summary, &warnings);
+    append_lines(&mut summary, &retries);
+    Ok(text_result(summary, false))
+}
+
+fn append_lines(text: &mut String, lines: &[String]) {
+    for line in lines {
+        text.push('\n');
+        text.push_str(line);
+    }
+}
+
+/// Records the retries of one chunk as progress and as `retry:` lines for the result.
+struct ChunkRetryLog<'a> {
+    chunk: usize,
+    total: usize,
+    progress: Option<&'a ProgressReporter>,
+    notes: &'a mut Vec<String>,
+}
+
+impl ChunkRetryLog<'_> {
+    async fn record(&mut self, detail: &str) {
+        let note = format!("chunk {} of {}: {detail}", self.chunk, self.total);
+        if let Some(progress) = self.progress {
+            progress.report(self.chunk - 1, self.total, &note).await;
+        }
+        self.notes.push(format!("retry: {note}"));
+    }
+}
+
+/// How a chunk's attempts ended.
+enum ChunkAttempts {
+    /// Spoken, interrupted, or failed in a way retrying cannot help.
+    Done((ToolCallResult, Option<Interruption>)),
+    /// Still failing with a transient error after every attempt.
+    Exhausted(anyhow::Error),
+}
+
+/// Speaks one chunk, falling back to smaller pieces when its attempts are exhausted.
+#[allow(clippy::future_not_send)]
+async fn speak_chunk_with_retry(
+    chunked: &ChunkedSpeech<'_>,
+    chunk: String,
+    cancel_rx: &mut Option<oneshot::Receiver<String>>,
+    log: &mut ChunkRetryLog<'_>,
+) -> Result<(ToolCallResult, Option<Interruption>)> {
+    let error = match speak_chunk_attempts(chunked, &chunk, cancel_rx, log).await? {
+        ChunkAttempts::Done(done) => return Ok(done),
+        ChunkAttempts::Exhausted(error) => error,
+    };
+    let pieces = chunked
+        .rechunk_on_failure
+        .then(|| {
+            rechunk_smaller(
+                crate::config::Config::default()
+                    .text_splitter
+                    .splitter()
+                    .split(&chunk),
+            )
+        })
+        .flatten();
+    let Some(pieces) = pieces else {
+        return Ok((exhausted_result(&error), None));
+    };
+
+    log.record(&format!(
+        "failed {CHUNK_MAX_ATTEMPTS} times; speaking it as {} smaller pieces",
+        pieces.len()
+    ))
+    .await;
+    let mut warnings = Vec::new();
+    for piece in pieces {
+        match speak_chunk_attempts(chunked, &piece, cancel_rx, log).await? {
+            ChunkAttempts::Done((result, None)) if result.is_error != Some(true) => {
+                warnings.extend(
+                    result_text(&result)
+                        .lines()
+                        .filter(|line| line.starts_with("warning:"))
+                        .map(str::to_owned),
+                );
+            }
+            ChunkAttempts::Done(done) => return Ok(done),
+            ChunkAttempts::Exhausted(error) => return Ok((exhausted_result(&error), None)),
+        }
+    }
+    Ok((success_result_with_warnings(&warnings), None))
+}
+
+fn exhausted_result(error: &anyhow::Error) -> ToolCallResult {
+    text_result(format_daemon_client_error_for_mcp(error), true)
+}
+
+/// Speaks `text`, retrying transient failures with exponential backoff.
+#[allow(clippy::future_not_send)]
+async fn speak_chunk_attempts(
+    chunked: &ChunkedSpeech<'_>,
+    text: &str,
+    cancel_rx: &mut Option<oneshot::Receiver<String>>,
+    log: &mut ChunkRetryLog<'_>,
+) -> Result<ChunkAttempts> {
+    let mut delay = CHUNK_RETRY_DELAY;
+    let mut attempt = 1;
+    loop {
+        let params = SynthesizeParams {
+            text: text.to_owned(),
+            ..chunked.params.clone()
+        };
+        let error = match speak_chunk(params, cancel_rx, chunked.timeout).await {
+            Ok(done) => return Ok(ChunkAttempts::Done(done)),
+            Err(error) => error,
+        };
+        if !is_retryable_daemon_synthesis_error(&error) {
+            return Err(error);
+        }
+        if attempt == CHUNK_MAX_ATTEMPTS {
+            return Ok(ChunkAttempts::Exhausted(error));
+        }
+        log.record(&format!(
+            "attempt {attempt} failed ({}); retrying in {}",
+            format_daemon_client_error_for_mcp(&error),
+            format_timeout(delay)
+        ))
+        .await;
+        tokio::select! {
+            biased;
+            reason = caller_cancellation(cancel_rx) => {
+                return Ok(ChunkAttempts::Done((
+                    cancellation_result(reason.clone()),
+                    Some(Interruption::Cancelled(reason)),
+                )));
+            }
+            () = tokio::time::sleep(delay) => {}
+        }
+        delay *= 2;
+        attempt += 1;
+    }
+}
+
+/// Speaks one chunk, passing on a cancellation from the caller or one raised when
+/// the chunk outlives `timeout`, so the daemon request is cancelled cleanly either way.
+///
+/// Returns the chunk's result and what interrupted it, if anything did.
+#[allow(clippy::future_not_send)]
+async fn speak_chunk(
+    params: SynthesizeParams,
+    cancel_rx: &mut Option<oneshot::Receiver<String>>,
+    timeout: Duration,
+) -> Result<(ToolCallResult, Option<Interruption>)> {
+    // The sender lives until the chunk finishes, so the chunk does not read its drop as a cancel.
+    let (forward_tx, forward_rx) = oneshot::channel();
+    let speaking = speak(params, Some(forward_rx));
+    tokio::pin!(speaking);
+    let interruption = tokio::select! {
+        biased;
+        reason = caller_cancellation(cancel_rx) => Interruption::Cancelled(reason),
+        () = tokio::time::sleep(timeout) => Interruption::TimedOut(timeout),
+        result = &mut speaking => return Ok((result?, None)),
+    };
+    let reason = match &interruption {
+        Interruption::Cancelled(reason) => reason.clone(),
+        Interruption::TimedOut(timeout) => format!("timed out after {}", format_timeout(*timeout)),
+    };
+    let _ = forward_tx.send(reason);
+    Ok((speaking.await?, Some(interruption)))
+}
+
+/// Resolves with the caller's cancellation reason; never resolves if there is no
+/// receiver or its sender is dropped without cancelling.
+async fn caller_cancellation(cancel_rx: &mut Option<oneshot::Receiver<String>>) -> String {
+    if let Some(receiver) = cancel_rx.as_mut() {
+        let reason = receiver.await;
+        // Either way the caller's receiver is spent: it fired, or its sender is gone.
+        *cancel_rx = None;
+        if let Ok(reason) = reason {
+            return reason;
+        }
+    }
+    std::future::pending().await
+}
+
+fn result_text(result: &ToolCallResult) -> &str {
+    match result.content.first() {
+        Some(ToolContent::Text { text }) => text,
+        Some(ToolContent::Audio { .. } | ToolContent::Resource { .. }) | None => "",
+    }
+}
+
+/// Picks the style from `style_id`, or resolves `voice` (else the configured voice)
+/// against the daemon's voices.
+pub(super) async fn resolve_tool_style_id(
+    style_id: Option<u32>,
+    voice: Option<&str>,
+) -> Result<u32> {
+    match (style_id, voice) {
+        (Some(_), Some(_)) => bail!("Pass either style_id or voice, not both"),
+        (Some(style_id), None) => {
+            validate_style_id(style_id)?;
+            Ok(style_id)
+        }
+        (None, Some(voice)) => resolve_tool_voice(voice).await,
+        (None, None) => match crate::config::user_config().voice.as_deref() {
+            Some(voice) => resolve_tool_voice(voice).await,
+            None => bail!("text_to_speech needs a style_id or a voice name"),
+        },
+    }
+}
+
+/// Resolves a style ID, voice name, or user alias of either.
+async fn resolve_tool_voice(voice: &str) -> Result<u32> {
+    let aliases = fetch_voice_aliases().await?;
+    let voice = expand_alias(&aliases, voice.trim()).trim();
+    match voice.parse::<u32>() {
+        Ok(style_id) => {
+            validate_style_id(style_id)?;
+            Ok(style_id)
+        }
+        Err(_) => resolve_voice_name(voice, &fetch_speaker_styles().await?),
     }
 }
 
@@ -124,52 +512,181 @@ async fn handle_streaming_synthesis(
         rate,
         streaming: _,
     } = params;
-    let synthesis = do_streaming_synthesis(&text, style_id, rate);
+    let request = TextSynthesisRequest {
+        text: &text,
+        style_id,
+        rate,
+        pitch: DEFAULT_SYNTHESIS_PITCH,
+    };
+    let mut cancel_rx = cancel_rx;
+    if let Some(receiver) = cancel_rx.as_mut()
+        && let Some(reason) = try_take_cancellation(receiver)
+    {
+        return Ok(cancellation_result(reason));
+    }
 
-    if let Some(mut cancel_rx) = cancel_rx {
-        if let Some(reason) = try_take_cancellation(&mut cancel_rx) {
-            return Ok(cancellation_result(reason));
+    let config = crate::config::Config::default();
+    let daemon = mcp_daemon_options();
+    let connect = tokio::time::timeout(
+        daemon.connect_timeout,
+        select_synthesis_mode_with_config(true, &config, &daemon.socket_path),
+    );
+    let mode = match until_cancelled(connect, cancel_rx.as_mut()).await {
+        Cancellable::Done(Err(_)) => {
+            bail!(
+                "Timed out after {} connecting to VOICEVOX daemon",
+                format_timeout(daemon.connect_timeout)
+            )
+        }
+        Cancellable::Done(Ok(mode)) => mode.context("Failed to create streaming synthesizer")?,
+        Cancellable::Cancelled(reason) => return Ok(cancellation_result(reason)),
+    };
+    match mode {
+        // The null backend opens no device, so it takes the whole-file path and skips playback.
+        SynthesisMode::Streaming(synthesizer) if audio_backend() == AudioBackend::Device => {
+            stream_to_output_device(synthesizer, &request, cancel_rx).await
         }
-        let wav_data = tokio::select! {
-            result = synthesis => result,
-            reason = &mut cancel_rx => {
-                return Ok(cancellation_result(reason.unwrap_or_default()));
+        mode => {
+            let (wav_data, warnings) = match until_cancelled(
+                collect_synthesis(mode, &request),
+                cancel_rx.as_mut(),
+            )
+            .await
+            {
+                Cancellable::Done(result) => result?,
+                Cancellable::Cancelled(reason) => return Ok(cancellation_result(reason)),
+            };
+            if let Some(cancelled_result) = play_generated_audio(&wav_data, cancel_rx).await? {
+                return Ok(cancelled_result);
             }
-        }?;
-        if let Some(cancelled_result) = play_generated_audio(&wav_data, Some(cancel_rx)).await? {
-            return Ok(cancelled_result);
+            Ok(success_result_with_warnings(&warnings))
         }
-        Ok(success_result())
-    } else {
-        let wav_data = synthesis.await?;
-        play_generated_audio(&wav_data, None).await?;
-        Ok(success_result())
     }
 }
 
+enum Cancellable<T> {
+    Done(T),
+    Cancelled(String),
+}
+
+/// Runs `future` to completion unless cancellation arrives first.
+#[allow(clippy::future_not_send)]
+async fn until_cancelled<F: std::future::Future>(
+    future: F,
+    cancel_rx: Option<&mut oneshot::Receiver<String>>,
+) -> Cancellable<F::Output> {
+    match cancel_rx {
+        Some(receiver) => tokio::select! {
+            output = future => Cancellable::Done(output),
+            reason = receiver => Cancellable::Cancelled(reason.unwrap_or_default()),
+        },
+        None => Cancellable::Done(future.await),
+    }
+}
+
+/// Plays segments as they are synthesized, pausing synthesis while the sink is full.
 #[allow(clippy::future_not_send)]
-async fn do_streaming_synthesis(text: &str, style_id: u32, rate: f32) -> Result<Vec<u8>> {
-    let config = crate::config::Config::default();
-    let mut synthesizer = match select_synthesis_mode_with_config(true, &config).await {
-        Ok(SynthesisMode::Streaming(synthesizer)) => synthesizer,
-        Ok(SynthesisMode::Daemon(_)) => unreachable!(),
-        Err(error) => return Err(error.context("Failed to create streaming synthesizer")),
+async fn stream_to_output_device(
+    mut synthesizer: StreamingSynthesizer,
+    request: &TextSynthesisRequest<'_>,
+    mut cancel_rx: Option<oneshot::Receiver<String>>,
+) -> Result<ToolCallResult> {
+    let sink =
+        RodioSink::open(DEFAULT_SINK_CAPACITY).context("Failed to play synthesized audio")?;
+    let streaming =
+        synthesizer.synthesize_streaming(request.text, request.style_id, request.rate, &sink);
+    let queued = match until_cancelled(streaming, cancel_rx.as_mut()).await {
+        Cancellable::Done(result) => result.context("Streaming synthesis failed")?,
+        Cancellable::Cancelled(reason) => {
+            sink.stop();
+            return Ok(cancellation_result(reason));
+        }
     };
+    if queued == 0 {
+        return Err(DuplicateSkipped.into());
+    }
 
-    let request = TextSynthesisRequest {
-        text,
-        style_id,
-        rate,
+    let warnings = warning_lines(synthesizer.take_warnings());
+    let outcome = match cancel_rx.as_mut() {
+        Some(receiver) => play_sink_with_cancel(&sink, receiver).await,
+        None => {
+            wait_until_drained(&sink).await;
+            PlaybackOutcome::Completed
+        }
+    };
+    match outcome {
+        PlaybackOutcome::Completed => Ok(success_result_with_warnings(&warnings)),
+        PlaybackOutcome::Cancelled(reason) => Ok(cancellation_result(reason)),
+    }
+}
+
+/// Synthesizes the whole utterance, segment by segment when streaming is allowed.
+#[allow(clippy::future_not_send)]
+async fn collect_synthesis(
+    mode: SynthesisMode,
+    request: &TextSynthesisRequest<'_>,
+) -> Result<(Vec<u8>, Vec<String>)> {
+    let mut synthesizer = match mode {
+        SynthesisMode::Streaming(synthesizer) => synthesizer,
+        SynthesisMode::Daemon(mut synthesizer) => {
+            let wav_data = synthesizer
+                .synthesize_bytes(request)
+                .await
+                .context("Synthesis failed")?;
+            return Ok((wav_data, warning_lines(synthesizer.take_warnings())));
+        }
     };
     let wav_segments = synthesizer
         .request_streaming_synthesis_segments(request.text, request.style_id, request.rate)
         .await
         .context("Streaming synthesis failed")?;
+    if wav_segments.is_empty() {
+        return Err(DuplicateSkipped.into());
+    }
 
     let wav_data =
         concatenate_wav_segments(&wav_segments).context("Failed to concatenate WAV segments")?;
 
-    Ok(wav_data)
+    Ok((wav_data, warning_lines(synthesizer.take_warnings())))
+}
+
+fn warning_lines(warnings: Vec<DaemonWarning>) -> Vec<String> {
+    warnings
+        .into_iter()
+        .map(|warning| format!("warning: {warning}"))
+        .collect()
+}
+
+/// Reports success, appending daemon warnings so the agent can relay them to the user.
+fn success_result_with_warnings(warnings: &[String]) -> ToolCallResult {
+    if warnings.is_empty() {
+        success_result()
+    } else {
+        text_result(format!("ok\n{}", warnings.join("\n")), false)
+    }
+}
+
+/// Keeps the warnings the synthesis flow reports; everything else stays silent.
+#[derive(Default)]
+pub(super) struct WarningCollector {
+    warnings: Mutex<Vec<String>>,
+}
+
+impl WarningCollector {
+    pub(super) fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.warnings.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl AppOutput for WarningCollector {
+    fn info(&self, _message: &str) {}
+
+    fn error(&self, message: &str) {
+        self.warnings
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(message.to_owned());
+    }
 }
 
 #[allow(clippy::future_not_send)]
@@ -184,8 +701,8 @@ async fn handle_daemon_synthesis(
         streaming: _,
     } = params;
 
-    let socket_path = crate::infrastructure::paths::get_socket_path();
-    let output = NoopAppOutput;
+    let daemon = mcp_daemon_options();
+    let output = WarningCollector::default();
     let mut retry_delay = startup::initial_retry_delay();
     let mut last_error = None;
     let mut wav_data = None;
@@ -197,7 +714,8 @@ async fn handle_daemon_synthesis(
         text: &text,
         style_id,
         rate,
-        socket_path: &socket_path,
+        socket_path: &daemon.socket_path,
+        auto_start_daemon: daemon.auto_start,
         output: &output,
         attempt: &mut attempt,
         retry_delay: &mut retry_delay,
@@ -226,7 +744,7 @@ async fn handle_daemon_synthesis(
         return Ok(cancelled_result);
     }
 
-    Ok(success_result())
+    Ok(success_result_with_warnings(&output.take()))
 }
 
 #[allow(clippy::future_not_send)]
@@ -245,8 +763,9 @@ async fn run_daemon_retry_phase(
             let synth_request = DaemonSynthesisBytesRequest {
                 text: ctx.text,
                 style_id: ctx.style_id,
-                rate: ctx.rate,
+                options: SynthesisOptions::with_rate(ctx.rate),
                 socket_path: ctx.socket_path,
+                auto_start_daemon: ctx.auto_start_daemon,
                 ensure_models_if_missing: false,
                 quiet_setup_messages: true,
             };
@@ -265,6 +784,9 @@ async fn run_daemon_retry_phase(
                 Ok(SynthesisFlowOutcome::Canceled(reason)) => {
                     Ok(DaemonRetryStep::Return(cancellation_result(reason)))
                 }
+                Err(error) if is_duplicate_skipped(&error) => {
+                    Ok(DaemonRetryStep::Return(skipped_result()))
+                }
                 Err(error) => {
                     let retryable = is_retryable_daemon_synthesis_error(&error);
                     *ctx.last_error = Some(error);
@@ -297,6 +819,11 @@ async fn run_daemon_retry_phase(
     }
 }
 
+/// A repeat suppressed by the daemon is reported as success so agents do not retry it.
+fn skipped_result() -> ToolCallResult {
+    text_result(DuplicateSkipped.to_string(), false)
+}
+
 fn cancellation_message(reason: &str) -> String {
     if reason.is_empty() {
         "Synthesis cancelled".to_string()
@@ -305,7 +832,7 @@ fn cancellation_message(reason: &str) -> String {
     }
 }
 
-fn cancellation_result(reason: String) -> ToolCallResult {
+pub(super) fn cancellation_result(reason: String) -> ToolCallResult {
     text_result(cancellation_message(&reason), true)
 }
 
@@ -325,6 +852,7 @@ async fn play_generated_audio(
     match emit_and_play(PlaybackRequest {
         wav_data,
         output_file: None,
+        output_format: OutputFormat::Wav,
         play: true,
         cancel_rx,
     })
@@ -341,7 +869,6 @@ mod tests {
     use super::*;
     use crate::infrastructure::daemon::client::daemon_response_error;
     use crate::infrastructure::ipc::DaemonErrorCode;
-    use crate::interface::mcp_server::tools::types::ToolContent;
     use serde_json::json;
     use tokio::sync::oneshot;
 
@@ -372,7 +899,7 @@ mod tests {
         let (cancel_tx, cancel_rx) = oneshot::channel::<String>();
         let _ = cancel_tx.send("ESC pressed".to_string());
 
-        let result = handle_text_to_speech_cancellable(args, Some(cancel_rx))
+        let result = handle_text_to_speech_cancellable(args, Some(cancel_rx), None)
             .await
             .expect("cancellation should return tool result");
 
@@ -383,4 +910,95 @@ mod tests {
         assert!(text.contains("cancelled"));
         assert!(text.contains("ESC pressed"));
     }
+
+    #[tokio::test]
+    async fn cancelled_long_text_reports_chunks_spoken() {
+        let args = json!({
+            "text": "ずんだもんなのだ。".repeat(40),
+            "style_id": 3,
+            "streaming": false,
+        });
+        let (cancel_tx, cancel_rx) = oneshot::channel::<String>();
+        let _ = cancel_tx.send("ESC pressed".to_string());
+
+        let result = handle_text_to_speech_cancellable(args, Some(cancel_rx), None)
+            .await
+            .expect("cancellation should return tool result");
+
+        assert_eq!(result.is_error, Some(true));
+        let text = result_text(&result);
+        assert!(text.contains("ESC pressed"));
+        assert!(text.contains("after 0 of 2 chunks"));
+    }
+
+    #[tokio::test]
+    async fn timed_out_chunk_reports_partial_progress() {
+        let chunked = ChunkedSpeech {
+            params: SynthesizeParams {
+                text: String::new(),
+                style_id: 3,
+                rate: 1.0,
+                streaming: false,
+            },
+            rechunk_on_failure: true,
+            progress: None,
+            // A zero timeout fires before the first chunk reaches the daemon.
+            timeout: Duration::ZERO,
+        };
+        let chunks = vec!["ずんだもんなのだ。".to_string(); 2];
+
+        let result = speak_chunks(&chunked, chunks, None)
+            .await
+            .expect("timeout should return tool result");
+
+        assert_eq!(result.is_error, Some(true));
+        assert_eq!(
+            result_text(&result),
+            "Synthesis timed out after 0s on chunk 1 of 2; 0 spoken"
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_log_reports_progress_and_result_lines() {
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(1);
+        let progress = ProgressReporter::new(json!("token"), sender);
+        let mut notes = Vec::new();
+        let mut log = ChunkRetryLog {
+            chunk: 3,
+            total: 5,
+            progress: Some(&progress),
+            notes: &mut notes,
+        };
+
+        log.record("attempt 1 failed (boom); retrying in 0.5s")
+            .await;
+
+        assert_eq!(
+            notes,
+            vec!["retry: chunk 3 of 5: attempt 1 failed (boom); retrying in 0.5s"]
+        );
+        let notification = serde_json::to_value(receiver.recv().await.expect("progress sent"))
+            .expect("serializable");
+        assert_eq!(notification["params"]["progress"], 2);
+        assert_eq!(
+            notification["params"]["message"],
+            "chunk 3 of 5: attempt 1 failed (boom); retrying in 0.5s"
+        );
+    }
+
+    #[tokio::test]
+    async fn requires_exactly_one_of_style_id_and_voice() {
+        let neither = handle_text_to_speech(json!({ "text": "テスト" })).await;
+        assert!(
+            neither
+                .expect_err("no voice")
+                .to_string()
+                .contains("style_id")
+        );
+
+        let both =
+            handle_text_to_speech(json!({ "text": "テスト", "style_id": 3, "voice": "zundamon" }))
+                .await;
+        assert!(both.expect_err("both").to_string().contains("not both"));
+    }
 }
@@ -12,6 +12,25 @@ pub struct ToolCallResult {
 pub enum ToolContent {
     #[serde(rename = "text")]
     Text { text: String },
+    /// Base64-encoded audio, for clients that cannot hear the server's speakers.
+    #[serde(rename = "audio")]
+    Audio {
+        data: String,
+        #[serde(rename = "mimeType")]
+        mime_type: String,
+    },
+    /// Binary content embedded as a resource, which every MCP revision accepts.
+    #[serde(rename = "resource")]
+    Resource { resource: EmbeddedResource },
+}
+
+/// A resource carried in a tool result, its bytes base64-encoded in `blob`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EmbeddedResource {
+    pub uri: String,
+    #[serde(rename = "mimeType")]
+    pub mime_type: String,
+    pub blob: String,
 }
 
 fn text_content(text: impl Into<String>) -> ToolContent {
@@ -1,8 +1,17 @@
 pub mod audio;
 pub mod cli;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod hooks;
+pub mod interrupt;
+pub mod latency;
 pub mod mcp_server;
+pub mod mora_timings;
 pub mod output;
 pub mod playback;
+pub mod sink;
 pub mod synthesis;
+pub mod tee;
+pub mod temp_audio;
 
 pub use output::{AppOutput, StdAppOutput};
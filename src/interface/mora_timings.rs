@@ -0,0 +1,96 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::infrastructure::ipc::MoraTiming;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static LAST: Mutex<Vec<MoraTiming>> = Mutex::new(Vec::new());
+
+/// Asks the daemon for mora timings with every synthesis in this process; off
+/// unless `--timings` asks.
+pub fn enable() {
+    ENABLED.store(true, Ordering::SeqCst);
+}
+
+#[must_use]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// Keeps the moras of the synthesis that just finished, replacing the previous ones.
+pub fn record(moras: Vec<MoraTiming>) {
+    if is_enabled() {
+        *LAST.lock().unwrap_or_else(PoisonError::into_inner) = moras;
+    }
+}
+
+/// Takes the moras of the last synthesis.
+pub fn take() -> Vec<MoraTiming> {
+    std::mem::take(&mut *LAST.lock().unwrap_or_else(PoisonError::into_inner))
+}
+
+/// Moves `moras` `offset` later and drops those starting at or after `length`,
+/// clipping the last one to it.
+#[must_use]
+pub fn place(moras: Vec<MoraTiming>, offset: Duration, length: Duration) -> Vec<MoraTiming> {
+    let offset = u32::try_from(offset.as_millis()).unwrap_or(u32::MAX);
+    let length = u32::try_from(length.as_millis()).unwrap_or(u32::MAX);
+    moras
+        .into_iter()
+        .map(|mora| MoraTiming {
+            start_ms: mora.start_ms.saturating_add(offset),
+            vowel_start_ms: mora.vowel_start_ms.saturating_add(offset).min(length),
+            end_ms: mora.end_ms.saturating_add(offset).min(length),
+            ..mora
+        })
+        .filter(|mora| mora.start_ms < length)
+        .collect()
+}
+
+/// Writes `moras` to `path` as a JSON array.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn write_timings_file(path: &Path, moras: &[MoraTiming]) -> Result<()> {
+    let json = serde_json::to_string_pretty(moras)?;
+    std::fs::write(path, json + "\n")
+        .with_context(|| format!("Failed to write mora timings to {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placed_moras_shift_and_stop_at_the_audio_end() {
+        let mora = |text: &str, start_ms, end_ms| MoraTiming {
+            text: text.to_string(),
+            consonant: None,
+            vowel: "a".to_string(),
+            start_ms,
+            vowel_start_ms: start_ms,
+            end_ms,
+        };
+        let moras = vec![
+            mora("ア", 100, 200),
+            mora("イ", 200, 300),
+            mora("ウ", 300, 400),
+        ];
+
+        let placed = place(
+            moras,
+            Duration::from_millis(500),
+            Duration::from_millis(750),
+        );
+
+        let spans: Vec<_> = placed
+            .iter()
+            .map(|mora| (mora.text.as_str(), mora.start_ms, mora.end_ms))
+            .collect();
+        assert_eq!(spans, [("ア", 600, 700), ("イ", 700, 750)]);
+    }
+}
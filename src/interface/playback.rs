@@ -1,11 +1,11 @@
-use anyhow::{Context, Result, anyhow};
-use rodio::Player;
-use std::{env, path::Path, sync::Arc};
+use anyhow::{Context, Result};
+use std::path::Path;
 use tokio::sync::oneshot;
 
-use crate::interface::audio::{
-    create_temp_wav_file, play_audio_from_memory, preferred_audio_players,
-};
+use crate::interface::audio::{AudioBackend, OutputFormat, audio_backend, play_audio_from_memory};
+use crate::interface::interrupt::PartialOutput;
+use crate::interface::latency::{self, Stage};
+use crate::interface::sink::{PlaybackSink, RodioSink, wait_until_drained};
 
 pub enum PlaybackOutcome {
     Completed,
@@ -15,6 +15,8 @@ pub enum PlaybackOutcome {
 pub struct PlaybackRequest<'a> {
     pub wav_data: &'a [u8],
     pub output_file: Option<&'a Path>,
+    /// Encoding of `output_file`; playback always uses the WAV.
+    pub output_format: OutputFormat,
     pub play: bool,
     pub cancel_rx: Option<oneshot::Receiver<String>>,
 }
@@ -22,19 +24,21 @@ pub struct PlaybackRequest<'a> {
 #[allow(clippy::future_not_send)]
 pub async fn emit_and_play(request: PlaybackRequest<'_>) -> Result<PlaybackOutcome> {
     if let Some(output_file) = request.output_file {
-        tokio::fs::write(output_file, request.wav_data).await?;
+        // Encoded before tracking, so a format that cannot be produced leaves an
+        // existing file alone instead of deleting it.
+        let encoded = request.output_format.encode(request.wav_data)?;
+        let partial = PartialOutput::track(output_file);
+        tokio::fs::write(output_file, encoded).await?;
+        partial.complete();
     }
 
-    if !request.play {
+    // Headless hosts never open an output device, even when asked to play.
+    if !request.play || audio_backend() == AudioBackend::Null {
         return Ok(PlaybackOutcome::Completed);
     }
 
     if let Some(mut cancel_rx) = request.cancel_rx {
-        if env::var(crate::config::ENV_VOICEVOX_LOW_LATENCY).is_ok() {
-            play_low_latency_with_cancel(request.wav_data.to_vec(), &mut cancel_rx).await
-        } else {
-            play_system_player_with_cancel(request.wav_data, &mut cancel_rx).await
-        }
+        play_rodio_with_cancel(request.wav_data.to_vec(), &mut cancel_rx).await
     } else {
         play_audio_from_memory(request.wav_data).context("Failed to play audio")?;
         Ok(PlaybackOutcome::Completed)
@@ -42,118 +46,67 @@ pub async fn emit_and_play(request: PlaybackRequest<'_>) -> Result<PlaybackOutco
 }
 
 #[allow(clippy::future_not_send)]
-async fn play_low_latency_with_cancel(
+async fn play_rodio_with_cancel(
     wav_data: Vec<u8>,
     cancel_rx: &mut oneshot::Receiver<String>,
 ) -> Result<PlaybackOutcome> {
-    let stream = rodio::DeviceSinkBuilder::open_default_sink()
-        .context("Failed to create audio output stream")?;
-    let sink = Arc::new(Player::connect_new(stream.mixer()));
-    let _stream_guard = stream;
-
-    let cursor = std::io::Cursor::new(wav_data);
-    let source = rodio::Decoder::new(cursor).context("Failed to decode audio")?;
-    sink.append(source);
-    sink.play();
-
-    let playback_task = tokio::task::spawn_blocking({
-        let sink_for_task = Arc::clone(&sink);
-        move || -> Result<()> {
-            sink_for_task.sleep_until_end();
-            Ok(())
-        }
-    });
-    tokio::pin!(playback_task);
+    let sink = {
+        let _span = latency::span(Stage::PlaybackStart);
+        RodioSink::open(1)?
+    };
+    sink.append_wav(wav_data)?;
+    Ok(play_sink_with_cancel(&sink, cancel_rx).await)
+}
 
+/// Waits for `sink` to finish playing, stopping it early if cancellation arrives.
+///
+/// A dropped cancellation sender means nobody can cancel any more, so playback
+/// runs to the end.
+#[allow(clippy::future_not_send)]
+pub async fn play_sink_with_cancel(
+    sink: &dyn PlaybackSink,
+    cancel_rx: &mut oneshot::Receiver<String>,
+) -> PlaybackOutcome {
     tokio::select! {
-        res = &mut playback_task => {
-            res.context("Audio playback task failed")??;
-            Ok(PlaybackOutcome::Completed)
-        }
+        () = wait_until_drained(sink) => PlaybackOutcome::Completed,
         result = cancel_rx => {
             match result {
                 Ok(reason) => {
                     sink.stop();
-                    let _ = playback_task.await;
-                    Ok(PlaybackOutcome::Cancelled(reason))
+                    PlaybackOutcome::Cancelled(reason)
                 }
                 Err(_) => {
-                    playback_task.await.context("Audio playback task failed")??;
-                    Ok(PlaybackOutcome::Completed)
+                    wait_until_drained(sink).await;
+                    PlaybackOutcome::Completed
                 }
             }
         }
     }
 }
 
-async fn play_system_player_with_cancel(
-    wav_data: &[u8],
-    cancel_rx: &mut oneshot::Receiver<String>,
-) -> Result<PlaybackOutcome> {
-    let temp_file = create_temp_wav_file(wav_data)?;
-    let temp_path = temp_file.path().to_owned();
-
-    let mut last_error = None;
-
-    for command in preferred_audio_players() {
-        match run_player_with_cancel(command, &temp_path, cancel_rx).await {
-            Ok(Some(outcome)) => return Ok(outcome),
-            Ok(None) => {}
-            Err(error) => last_error = Some(error),
-        }
-    }
-
-    Err(last_error
-        .unwrap_or_else(|| anyhow!("No audio player found. Install sox or use -o to save file")))
-}
-
-async fn run_player_with_cancel(
-    command: &str,
-    temp_path: &Path,
-    cancel_rx: &mut oneshot::Receiver<String>,
-) -> Result<Option<PlaybackOutcome>> {
-    let mut child = match tokio::process::Command::new(command).arg(temp_path).spawn() {
-        Ok(child) => child,
-        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
-        Err(error) => return Err(error).with_context(|| format!("Failed to spawn {command}")),
-    };
-
-    tokio::select! {
-        status = child.wait() => {
-            let status = status.with_context(|| format!("Failed to wait for {command}"))?;
-            if status.success() {
-                Ok(Some(PlaybackOutcome::Completed))
-            } else {
-                Err(anyhow!(
-                    "{command} exited with status {}",
-                    status
-                        .code()
-                        .map_or_else(|| "terminated by signal".to_string(), |code| code.to_string())
-                ))
-            }
-        }
-        result = cancel_rx => {
-            match result {
-                Ok(reason) => {
-                    let _ = child.kill().await;
-                    let _ = child.wait().await;
-                    Ok(Some(PlaybackOutcome::Cancelled(reason)))
-                }
-                Err(_) => {
-                    let status = child.wait().await
-                        .with_context(|| format!("Failed to wait for {command}"))?;
-                    if status.success() {
-                        Ok(Some(PlaybackOutcome::Completed))
-                    } else {
-                        Err(anyhow!(
-                            "{command} exited with status {}",
-                            status
-                                .code()
-                                .map_or_else(|| "terminated by signal".to_string(), |code| code.to_string())
-                        ))
-                    }
-                }
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn an_encode_error_leaves_an_existing_output_file_untouched() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let output_file = dir.path().join("speech.flac");
+        std::fs::write(&output_file, b"earlier take").expect("existing file");
+
+        let result = emit_and_play(PlaybackRequest {
+            wav_data: b"not a wav",
+            output_file: Some(&output_file),
+            output_format: OutputFormat::Flac,
+            play: false,
+            cancel_rx: None,
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::fs::read(&output_file).expect("file kept"),
+            b"earlier take"
+        );
     }
 }
@@ -0,0 +1,183 @@
+use anyhow::{Context, Result};
+use rodio::{Decoder, MixerDeviceSink, Player};
+use std::io::Cursor;
+use std::time::Duration;
+
+use crate::interface::audio::{audio_output, open_output_device};
+use crate::interface::latency::{self, Stage};
+
+/// Segments queued ahead of playback before producers are asked to wait.
+///
+/// Three sentence-sized segments keep playback gapless while synthesis of the
+/// next one runs, without holding minutes of decoded audio in memory.
+pub const DEFAULT_SINK_CAPACITY: usize = 3;
+
+/// How often a waiting producer rechecks a full sink.
+const SINK_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Whether a sink can take another segment right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SinkPressure {
+    Ready,
+    Full,
+}
+
+/// Destination for synthesized WAV segments with a bounded queue.
+///
+/// Producers await [`wait_for_room`] before synthesizing the next segment, so
+/// an hour-long input is synthesized at playback speed instead of being
+/// buffered whole in memory.
+pub trait PlaybackSink {
+    /// Queues one WAV segment, starting playback if the sink was idle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the segment cannot be decoded.
+    fn append_wav(&self, wav_data: Vec<u8>) -> Result<()>;
+
+    /// Segments queued or currently playing.
+    fn queued(&self) -> usize;
+
+    /// Most segments the sink wants queued at once.
+    fn capacity(&self) -> usize;
+
+    /// Drops every queued segment and stops playback.
+    fn stop(&self);
+
+    fn pressure(&self) -> SinkPressure {
+        if self.queued() < self.capacity() {
+            SinkPressure::Ready
+        } else {
+            SinkPressure::Full
+        }
+    }
+}
+
+/// Waits until `sink` can take another segment.
+#[allow(clippy::future_not_send)]
+pub async fn wait_for_room(sink: &dyn PlaybackSink) {
+    while sink.pressure() == SinkPressure::Full {
+        tokio::time::sleep(SINK_POLL_INTERVAL).await;
+    }
+}
+
+/// Waits until everything queued on `sink` has finished playing.
+#[allow(clippy::future_not_send)]
+pub async fn wait_until_drained(sink: &dyn PlaybackSink) {
+    while sink.queued() > 0 {
+        tokio::time::sleep(SINK_POLL_INTERVAL).await;
+    }
+}
+
+/// Plays segments on the chosen output device through rodio.
+pub struct RodioSink {
+    player: Player,
+    capacity: usize,
+    // Dropped after `player` so the device outlives the queue feeding it.
+    _device: MixerDeviceSink,
+}
+
+impl RodioSink {
+    /// Opens the chosen output device, at the chosen volume, with room for
+    /// `capacity` queued segments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output device cannot be opened.
+    pub fn open(capacity: usize) -> Result<Self> {
+        let device = open_output_device()?;
+        let player = Player::connect_new(device.mixer());
+        player.set_volume(audio_output().volume);
+        Ok(Self {
+            player,
+            capacity: capacity.max(1),
+            _device: device,
+        })
+    }
+
+    /// Blocks the current thread until every queued segment has played.
+    pub fn sleep_until_end(&self) {
+        self.player.sleep_until_end();
+    }
+}
+
+impl PlaybackSink for RodioSink {
+    fn append_wav(&self, wav_data: Vec<u8>) -> Result<()> {
+        let decode = latency::span(Stage::Decode);
+        let source = Decoder::new(Cursor::new(wav_data)).context("Failed to decode audio")?;
+        drop(decode);
+        self.player.append(source);
+        self.player.play();
+        Ok(())
+    }
+
+    fn queued(&self) -> usize {
+        self.player.len()
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn stop(&self) {
+        self.player.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct CountingSink {
+        queued: Cell<usize>,
+        capacity: usize,
+    }
+
+    impl PlaybackSink for CountingSink {
+        fn append_wav(&self, _wav_data: Vec<u8>) -> Result<()> {
+            self.queued.set(self.queued.get() + 1);
+            Ok(())
+        }
+
+        fn queued(&self) -> usize {
+            self.queued.get()
+        }
+
+        fn capacity(&self) -> usize {
+            self.capacity
+        }
+
+        fn stop(&self) {
+            self.queued.set(0);
+        }
+    }
+
+    #[tokio::test]
+    async fn producers_wait_while_the_sink_is_full() {
+        let sink = CountingSink {
+            queued: Cell::new(0),
+            capacity: 2,
+        };
+        sink.append_wav(Vec::new()).expect("append");
+        assert_eq!(sink.pressure(), SinkPressure::Ready);
+        sink.append_wav(Vec::new()).expect("append");
+        assert_eq!(sink.pressure(), SinkPressure::Full);
+
+        let waiting = wait_for_room(&sink);
+        tokio::pin!(waiting);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), &mut waiting)
+                .await
+                .is_err()
+        );
+
+        sink.queued.set(1);
+        tokio::time::timeout(Duration::from_millis(100), waiting)
+            .await
+            .expect("room after a segment finished");
+
+        sink.stop();
+        wait_until_drained(&sink).await;
+    }
+}
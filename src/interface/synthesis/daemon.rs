@@ -1,8 +1,10 @@
 use anyhow::Result;
+use std::time::Instant;
 
 use crate::domain::synthesis::TextSynthesisRequest;
 use crate::infrastructure::daemon::client::DaemonClient;
-use crate::infrastructure::ipc::OwnedSynthesizeOptions;
+use crate::infrastructure::ipc::{DaemonWarning, SynthesisOptions};
+use crate::interface::{latency, mora_timings};
 
 pub struct DaemonSynthesizer {
     daemon_rpc: DaemonClient,
@@ -18,9 +20,59 @@ impl DaemonSynthesizer {
         &mut self,
         request: &TextSynthesisRequest<'_>,
     ) -> Result<Vec<u8>> {
-        let options = OwnedSynthesizeOptions { rate: request.rate };
-        self.daemon_rpc
-            .synthesize(request.text, request.style_id, options)
+        let options = SynthesisOptions {
+            rate: request.rate,
+            pitch: request.pitch,
+            ..SynthesisOptions::default()
+        };
+        self.synthesize_with_options(request.text, request.style_id, options)
             .await
     }
+
+    /// Synthesizes with every option, including intonation, volume, and pauses.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the daemon rejects the request or synthesis fails.
+    pub async fn synthesize_with_options(
+        &mut self,
+        text: &str,
+        style_id: u32,
+        options: SynthesisOptions,
+    ) -> Result<Vec<u8>> {
+        if mora_timings::is_enabled() {
+            let started = Instant::now();
+            let (wav_data, timings, moras) = self
+                .daemon_rpc
+                .synthesize_with_moras(text, style_id, options)
+                .await?;
+            latency::record_daemon_timings(timings, started.elapsed());
+            mora_timings::record(moras);
+            return Ok(wav_data);
+        }
+        if !latency::is_enabled() {
+            return self.daemon_rpc.synthesize(text, style_id, options).await;
+        }
+        let started = Instant::now();
+        let (wav_data, timings) = self
+            .daemon_rpc
+            .synthesize_timed(text, style_id, options)
+            .await?;
+        latency::record_daemon_timings(timings, started.elapsed());
+        Ok(wav_data)
+    }
+
+    /// Cancels the synthesis in flight on this connection and closes it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the daemon cannot be told to cancel.
+    pub async fn cancel(self) -> Result<()> {
+        self.daemon_rpc.cancel().await
+    }
+
+    /// Returns and clears warnings the daemon reported for earlier requests.
+    pub fn take_warnings(&mut self) -> Vec<DaemonWarning> {
+        self.daemon_rpc.take_warnings()
+    }
 }
@@ -1,11 +1,14 @@
 use anyhow::{Result, anyhow};
 use std::path::Path;
+use std::time::Duration;
 use tokio::sync::oneshot;
 
 use crate::domain::synthesis::{TextSynthesisRequest, validate_basic_request};
 use crate::infrastructure::daemon::client::DaemonClient;
+use crate::infrastructure::ipc::SynthesisOptions;
 use crate::interface::AppOutput;
 use crate::interface::cli::download::{ensure_models_available, missing_startup_resources};
+use crate::interface::latency::{self, Stage};
 use crate::interface::synthesis::daemon::DaemonSynthesizer;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -18,8 +21,7 @@ enum SynthesisPhase {
 
 // This lifecycle mirrors modeling/tla/Synthesis.tla at the same abstraction level:
 // Idle -> Queued -> Synthesizing -> Done / Failed / Canceled.
-// Rust currently has no explicit cancellation path in this flow, but we keep the
-// state for model alignment and future cancellation integration.
+// Cancellation during `Synthesizing` also tells the daemon to drop the request.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum SynthesisLifecycleState {
     Idle,
@@ -83,17 +85,25 @@ impl AppOutput for NoopAppOutput {
 pub struct DaemonSynthesisBytesRequest<'a> {
     pub text: &'a str,
     pub style_id: u32,
-    pub rate: f32,
+    pub options: SynthesisOptions,
     pub socket_path: &'a Path,
+    /// Start the daemon when none answers at `socket_path`.
+    pub auto_start_daemon: bool,
     pub ensure_models_if_missing: bool,
     pub quiet_setup_messages: bool,
 }
 
-pub fn validate_text_synthesis_request(text: &str, style_id: u32, rate: f32) -> Result<()> {
+pub fn validate_text_synthesis_request(
+    text: &str,
+    style_id: u32,
+    rate: f32,
+    pitch: f32,
+) -> Result<()> {
     validate_basic_request(&TextSynthesisRequest {
         text,
         style_id,
         rate,
+        pitch,
     })
 }
 
@@ -109,9 +119,18 @@ async fn ensure_models_on_demand(
         return Ok(());
     }
 
+    ensure_startup_resources(request.quiet_setup_messages, output).await
+}
+
+/// Downloads missing VOICEVOX resources before the first daemon request of a session.
+///
+/// # Errors
+///
+/// Returns an error if resource setup fails.
+pub async fn ensure_startup_resources(quiet: bool, output: &dyn AppOutput) -> Result<()> {
     let missing = missing_startup_resources();
     if !missing.is_empty() {
-        if !request.quiet_setup_messages {
+        if !quiet {
             output.info(&format!(
                 "VOICEVOX resources not found ({}). Setting up VOICEVOX...",
                 missing.join(", ")
@@ -170,6 +189,11 @@ pub async fn synthesize_bytes_via_daemon_cancellable(
                         let reason = reason.unwrap_or_default();
                         lifecycle = lifecycle.cancel();
                         if matches!(lifecycle, SynthesisLifecycleState::Canceled) {
+                            if phase == SynthesisPhase::Synthesize
+                                && let Some(synthesizer) = synthesizer.take()
+                            {
+                                cancel_in_daemon(synthesizer).await;
+                            }
                             return Ok(SynthesisFlowOutcome::Canceled(reason));
                         }
                         Ok(SynthesisStep::Next(phase))
@@ -200,6 +224,15 @@ pub async fn synthesize_bytes_via_daemon_cancellable(
     }
 }
 
+/// How long a cancelled flow waits to deliver `Cancel` before giving up on the daemon.
+const DAEMON_CANCEL_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Best effort: the caller is already cancelled, so a daemon that cannot be told
+/// simply finishes the request and discards it.
+async fn cancel_in_daemon(synthesizer: DaemonSynthesizer) {
+    let _ = tokio::time::timeout(DAEMON_CANCEL_TIMEOUT, synthesizer.cancel()).await;
+}
+
 fn try_take_cancellation(cancel_rx: &mut oneshot::Receiver<String>) -> Option<String> {
     match cancel_rx.try_recv() {
         Ok(reason) => Some(reason),
@@ -221,7 +254,13 @@ async fn run_synthesis_phase(
 ) -> Result<SynthesisStep> {
     match phase {
         SynthesisPhase::Validate => {
-            validate_text_synthesis_request(request.text, request.style_id, request.rate)?;
+            validate_text_synthesis_request(
+                request.text,
+                request.style_id,
+                request.options.rate,
+                request.options.pitch,
+            )?;
+            request.options.validate()?;
             Ok(SynthesisStep::Next(SynthesisPhase::EnsureResources))
         }
         SynthesisPhase::EnsureResources => {
@@ -229,20 +268,27 @@ async fn run_synthesis_phase(
             Ok(SynthesisStep::Next(SynthesisPhase::Connect))
         }
         SynthesisPhase::Connect => {
-            let client = connect_daemon_client_auto_start(request.socket_path).await?;
+            let _span = latency::span(Stage::DaemonConnect);
+            let client = if request.auto_start_daemon {
+                connect_daemon_client_auto_start(request.socket_path).await?
+            } else {
+                DaemonClient::new_at(request.socket_path).await?
+            };
             *synthesizer = Some(DaemonSynthesizer::new_with_client(client));
             Ok(SynthesisStep::Next(SynthesisPhase::Synthesize))
         }
         SynthesisPhase::Synthesize => {
-            let mut synthesizer = synthesizer
-                .take()
+            // Borrowed rather than taken, so a cancelled flow still holds the
+            // connection and can send `Cancel` on it.
+            let synthesizer = synthesizer
+                .as_mut()
                 .expect("synthesizer must exist in synthesize phase");
-            let synth_req = TextSynthesisRequest {
-                text: request.text,
-                style_id: request.style_id,
-                rate: request.rate,
-            };
-            let wav_data = synthesizer.synthesize_bytes(&synth_req).await?;
+            let wav_data = synthesizer
+                .synthesize_with_options(request.text, request.style_id, request.options)
+                .await?;
+            for warning in synthesizer.take_warnings() {
+                output.error(&format!("warning: {warning}"));
+            }
             Ok(SynthesisStep::Done(wav_data))
         }
     }
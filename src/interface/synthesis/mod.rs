@@ -9,4 +9,4 @@ pub use flow::{
     synthesize_bytes_via_daemon, validate_text_synthesis_request,
 };
 pub use mode::{SynthesisMode, select_synthesis_mode, select_synthesis_mode_with_config};
-pub use streaming::StreamingSynthesizer;
+pub use streaming::{SegmentProgress, StreamingSynthesizer};
@@ -1,7 +1,9 @@
 use anyhow::{Context, Result};
+use std::path::Path;
 
 use crate::config::Config;
 use crate::infrastructure::daemon::client::DaemonClient;
+use crate::infrastructure::paths::get_socket_path;
 
 use super::daemon::DaemonSynthesizer;
 use super::streaming::StreamingSynthesizer;
@@ -11,8 +13,8 @@ pub enum SynthesisMode {
     Daemon(DaemonSynthesizer),
 }
 
-async fn connect_daemon_client_with_retry_context() -> Result<DaemonClient> {
-    DaemonClient::connect_with_retry()
+async fn connect_daemon_client_with_retry_context(socket_path: &Path) -> Result<DaemonClient> {
+    DaemonClient::connect_with_retry_at(socket_path)
         .await
         .context("Failed to connect to VOICEVOX daemon after multiple attempts")
 }
@@ -23,10 +25,14 @@ async fn connect_daemon_client_with_retry_context() -> Result<DaemonClient> {
 ///
 /// Returns an error if daemon connection fails or streaming synthesizer construction fails.
 pub async fn select_synthesis_mode(streaming: bool) -> Result<SynthesisMode> {
-    select_synthesis_mode_with_config(streaming, &Config::default()).await
+    select_synthesis_mode_with_config(streaming, &Config::default(), &get_socket_path()).await
 }
 
-/// Selects synthesis mode with injected configuration for streaming behavior.
+/// Selects synthesis mode with injected configuration for streaming behavior,
+/// talking to the daemon at `socket_path`.
+///
+/// Falls back to whole-utterance synthesis when the daemon's playback policy
+/// disallows streaming.
 ///
 /// # Errors
 ///
@@ -34,9 +40,11 @@ pub async fn select_synthesis_mode(streaming: bool) -> Result<SynthesisMode> {
 pub async fn select_synthesis_mode_with_config(
     streaming: bool,
     config: &Config,
+    socket_path: &Path,
 ) -> Result<SynthesisMode> {
-    let client = connect_daemon_client_with_retry_context().await?;
-    if streaming {
+    let mut client = connect_daemon_client_with_retry_context(socket_path).await?;
+    // The daemon's time-of-day policy can veto streaming playback (e.g. at night).
+    if streaming && client.streaming_allowed().await? {
         Ok(SynthesisMode::Streaming(
             StreamingSynthesizer::new_with_client_and_config(client, config)?,
         ))
@@ -1,14 +1,36 @@
 use anyhow::{Context, Result};
-use rodio::{Decoder, Player};
-use std::io::Cursor;
+use futures_util::StreamExt;
+use futures_util::stream::FuturesOrdered;
+use std::time::Duration;
+use voicevox_text::TextSegmenter;
 
 use crate::config::Config;
-use crate::domain::synthesis::{TextSegmenter, TextSplitter};
-use crate::infrastructure::daemon::client::DaemonClient;
+use crate::domain::synthesis::wav::wav_duration;
+use crate::infrastructure::daemon::client::{DaemonClient, is_duplicate_skipped};
+use crate::infrastructure::ipc::{DaemonWarning, SynthesisOptions};
+use crate::interface::sink::{PlaybackSink, wait_for_room};
+
+/// Segments synthesized ahead of the one being queued for playback, each over
+/// its own daemon connection.
+const SYNTHESIS_LOOKAHEAD: usize = 2;
+
+/// One segment of a streaming synthesis, reported as it reaches the sink.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentProgress<'a> {
+    /// Position among the segments that have text, from 0.
+    pub index: usize,
+    pub total: usize,
+    pub text: &'a str,
+    /// Length of the queued audio; `None` when the daemon skipped the segment as a
+    /// recent repeat.
+    pub duration: Option<Duration>,
+}
 
 pub struct StreamingSynthesizer {
     daemon_rpc: DaemonClient,
     text_segmenter: Box<dyn TextSegmenter + Send + Sync>,
+    /// Warnings collected from the lookahead connections.
+    lookahead_warnings: Vec<DaemonWarning>,
 }
 
 impl StreamingSynthesizer {
@@ -31,19 +53,11 @@ impl StreamingSynthesizer {
     /// Creates a streaming synthesizer with explicit configuration injection.
     #[allow(clippy::missing_errors_doc)]
     pub fn new_with_client_and_config(daemon_rpc: DaemonClient, config: &Config) -> Result<Self> {
-        let delimiters = config
-            .text_splitter
-            .delimiters
-            .iter()
-            .filter_map(|s| s.chars().next())
-            .collect::<Vec<_>>();
-        let text_segmenter = Box::new(TextSplitter::new(
-            delimiters,
-            config.text_splitter.max_length,
-        ));
+        let text_segmenter = Box::new(config.text_splitter.splitter());
         Ok(Self {
             daemon_rpc,
             text_segmenter,
+            lookahead_warnings: Vec::new(),
         })
     }
 
@@ -56,11 +70,14 @@ impl StreamingSynthesizer {
         Ok(Self {
             daemon_rpc,
             text_segmenter,
+            lookahead_warnings: Vec::new(),
         })
     }
 
     /// Synthesizes text in segments and returns synthesized WAV segments.
     ///
+    /// Segments the daemon skips as recent repeats are left out.
+    ///
     /// # Errors
     ///
     /// Returns an error if segment synthesis fails.
@@ -71,7 +88,10 @@ impl StreamingSynthesizer {
         rate: f32,
     ) -> Result<Vec<Vec<u8>>> {
         let segments = self.text_segmenter.split(text);
-        let options = crate::infrastructure::ipc::OwnedSynthesizeOptions { rate };
+        let options = SynthesisOptions {
+            rate,
+            ..Default::default()
+        };
         let mut wav_segments = Vec::new();
 
         for (i, segment) in segments
@@ -79,72 +99,405 @@ impl StreamingSynthesizer {
             .filter(|segment| !segment.trim().is_empty())
             .enumerate()
         {
-            let wav_data = self
-                .daemon_rpc
-                .synthesize(segment, style_id, options)
-                .await
-                .with_context(|| {
-                    format!("Failed to synthesize segment {i} ({} bytes)", segment.len())
-                })?;
+            let wav_data = match self.daemon_rpc.synthesize(segment, style_id, options).await {
+                Ok(wav_data) => wav_data,
+                Err(error) if is_duplicate_skipped(&error) => continue,
+                Err(error) => {
+                    return Err(error.context(format!(
+                        "Failed to synthesize segment {i} ({} bytes)",
+                        segment.len()
+                    )));
+                }
+            };
             wav_segments.push(wav_data);
         }
 
         Ok(wav_segments)
     }
 
+    /// Returns and clears warnings the daemon reported for earlier segments.
+    pub fn take_warnings(&mut self) -> Vec<DaemonWarning> {
+        let mut warnings = std::mem::take(&mut self.lookahead_warnings);
+        warnings.extend(self.daemon_rpc.take_warnings());
+        warnings
+    }
+
     /// Appends synthesized WAV segments to the provided sink.
     ///
     /// # Errors
     ///
     /// Returns an error if any audio segment cannot be decoded.
-    pub fn append_segments_to_sink(&self, wav_segments: &[Vec<u8>], sink: &Player) -> Result<()> {
+    pub fn append_segments_to_sink(
+        &self,
+        wav_segments: &[Vec<u8>],
+        sink: &dyn PlaybackSink,
+    ) -> Result<()> {
         for (i, wav_data) in wav_segments.iter().enumerate() {
-            let cursor = Cursor::new(wav_data.clone());
-            let source = Decoder::new(cursor)
+            sink.append_wav(wav_data.clone())
                 .with_context(|| format!("Failed to decode audio for segment {i}"))?;
-            sink.append(source);
-            if i == 0 {
-                sink.play();
-            }
         }
         Ok(())
     }
 
     /// Synthesizes text in segments and streams each to the sink as soon as it is ready.
     ///
-    /// Playback begins after the first segment is synthesized; subsequent segments are
-    /// appended while earlier ones are already playing.
+    /// Playback begins after the first segment is synthesized. While a segment is
+    /// queued and playing, the next two segments are already
+    /// being synthesized over extra daemon connections, and results are appended
+    /// in text order, so long sentences no longer stall playback. If the extra
+    /// connections cannot be opened, segments are synthesized one at a time.
+    /// When the sink is full, queuing waits until playback makes room. Returns how
+    /// many segments were queued; duplicates the daemon skipped are not counted.
     ///
     /// # Errors
     ///
     /// Returns an error if segment synthesis or audio decoding fails.
+    #[allow(clippy::future_not_send)]
     pub async fn synthesize_streaming(
         &mut self,
         text: &str,
         style_id: u32,
         rate: f32,
-        sink: &Player,
-    ) -> Result<()> {
+        sink: &dyn PlaybackSink,
+    ) -> Result<usize> {
+        let options = SynthesisOptions {
+            rate,
+            ..Default::default()
+        };
+        self.synthesize_streaming_with_progress(text, style_id, options, sink, &mut |_| {})
+            .await
+    }
+
+    /// Like [`Self::synthesize_streaming`] with every synthesis option, calling
+    /// `on_segment` in text order as each segment is queued or skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if segment synthesis or audio decoding fails.
+    #[allow(clippy::future_not_send)]
+    pub async fn synthesize_streaming_with_progress(
+        &mut self,
+        text: &str,
+        style_id: u32,
+        options: SynthesisOptions,
+        sink: &dyn PlaybackSink,
+        on_segment: &mut dyn FnMut(SegmentProgress<'_>),
+    ) -> Result<usize> {
         let segments = self.text_segmenter.split(text);
-        let options = crate::infrastructure::ipc::OwnedSynthesizeOptions { rate };
 
-        for (i, segment) in segments.iter().filter(|s| !s.trim().is_empty()).enumerate() {
-            let wav_data = self
-                .daemon_rpc
-                .synthesize(segment, style_id, options)
-                .await
-                .with_context(|| {
-                    format!("Failed to synthesize segment {i} ({} bytes)", segment.len())
-                })?;
+        let mut lookahead = Vec::with_capacity(SYNTHESIS_LOOKAHEAD);
+        if segments
+            .iter()
+            .filter(|s| !s.trim().is_empty())
+            .nth(1)
+            .is_some()
+        {
+            for _ in 0..SYNTHESIS_LOOKAHEAD {
+                match self.daemon_rpc.connect_another().await {
+                    Ok(client) => lookahead.push(client),
+                    Err(_) => break,
+                }
+            }
+        }
 
-            let cursor = Cursor::new(wav_data);
-            let source = Decoder::new(cursor)
-                .with_context(|| format!("Failed to decode audio for segment {i}"))?;
-            sink.append(source);
-            if i == 0 {
-                sink.play();
+        let result = pipeline_segments(
+            std::iter::once(&mut self.daemon_rpc)
+                .chain(lookahead.iter_mut())
+                .collect(),
+            &segments,
+            style_id,
+            options,
+            sink,
+            on_segment,
+        )
+        .await;
+        for client in &mut lookahead {
+            self.lookahead_warnings.extend(client.take_warnings());
+        }
+        result
+    }
+}
+
+/// Synthesizes `segments` over `clients`, one request in flight per client, and
+/// appends the audio to `sink` in segment order, reporting each to `on_segment`.
+///
+/// On failure the requests still in flight are awaited, so every connection is
+/// left between responses.
+#[allow(clippy::future_not_send)]
+async fn pipeline_segments(
+    mut idle: Vec<&mut DaemonClient>,
+    segments: &[String],
+    style_id: u32,
+    options: SynthesisOptions,
+    sink: &dyn PlaybackSink,
+    on_segment: &mut dyn FnMut(SegmentProgress<'_>),
+) -> Result<usize> {
+    let spoken: Vec<&str> = segments
+        .iter()
+        .map(String::as_str)
+        .filter(|s| !s.trim().is_empty())
+        .collect();
+    let mut pending = spoken.iter().copied().enumerate();
+    let mut in_flight = FuturesOrdered::new();
+    let mut queued = 0;
+
+    loop {
+        while let Some(client) = idle.pop() {
+            let Some((index, segment)) = pending.next() else {
+                idle.push(client);
+                break;
+            };
+            in_flight.push_back(synthesize_segment(
+                client, index, segment, style_id, options,
+            ));
+        }
+        let Some((client, index, segment_len, result)) = in_flight.next().await else {
+            return Ok(queued);
+        };
+        idle.push(client);
+        let mut progress = SegmentProgress {
+            index,
+            total: spoken.len(),
+            text: spoken[index],
+            duration: None,
+        };
+
+        let appended = match result {
+            Ok(wav_data) => {
+                progress.duration = Some(wav_duration(&wav_data).unwrap_or_default());
+                wait_for_room(sink).await;
+                sink.append_wav(wav_data)
+                    .with_context(|| format!("Failed to decode audio for segment {index}"))
+            }
+            Err(error) if is_duplicate_skipped(&error) => {
+                on_segment(progress);
+                continue;
             }
+            Err(error) => Err(error.context(format!(
+                "Failed to synthesize segment {index} ({segment_len} bytes)"
+            ))),
+        };
+        if let Err(error) = appended {
+            while in_flight.next().await.is_some() {}
+            return Err(error);
         }
-        Ok(())
+        on_segment(progress);
+        queued += 1;
+    }
+}
+
+/// Synthesizes one segment, handing the client back with the result.
+async fn synthesize_segment<'a>(
+    client: &'a mut DaemonClient,
+    index: usize,
+    segment: &str,
+    style_id: u32,
+    options: SynthesisOptions,
+) -> (&'a mut DaemonClient, usize, usize, Result<Vec<u8>>) {
+    let result = client.synthesize(segment, style_id, options).await;
+    (client, index, segment.len(), result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::ipc::{DaemonErrorCode, DaemonRequest, DaemonResponse};
+    use futures_util::SinkExt;
+    use std::cell::RefCell;
+    use std::path::Path;
+    use tokio::net::{UnixListener, UnixStream};
+    use tokio_util::codec::{Framed, LengthDelimitedCodec};
+
+    /// Splits on `|`, so each test spells out its segments.
+    struct BarSegmenter;
+
+    impl TextSegmenter for BarSegmenter {
+        fn split(&self, text: &str) -> Vec<String> {
+            text.split('|').map(str::to_owned).collect()
+        }
+    }
+
+    /// Records the audio appended, never filling up.
+    #[derive(Default)]
+    struct RecordingSink {
+        appended: RefCell<Vec<String>>,
+    }
+
+    impl PlaybackSink for RecordingSink {
+        fn append_wav(&self, wav_data: Vec<u8>) -> Result<()> {
+            self.appended
+                .borrow_mut()
+                .push(String::from_utf8(wav_data).expect("utf-8"));
+            Ok(())
+        }
+
+        fn queued(&self) -> usize {
+            0
+        }
+
+        fn capacity(&self) -> usize {
+            1
+        }
+
+        fn stop(&self) {}
+    }
+
+    /// Answers one connection like the daemon would, echoing each text back as its
+    /// audio: `slow` arrives late, `repeat` is skipped, and `fail` is an error.
+    async fn serve_scripted(stream: UnixStream) {
+        let mut framed = Framed::new(stream, LengthDelimitedCodec::new());
+        while let Some(Ok(frame)) = framed.next().await {
+            let Ok(DaemonRequest::Synthesize { text, .. }) = postcard::from_bytes(&frame) else {
+                return;
+            };
+            let response = match text.as_str() {
+                "repeat" => DaemonResponse::Skipped,
+                "fail" => DaemonResponse::Error {
+                    code: DaemonErrorCode::SynthesisFailed,
+                    message: "scripted failure".to_string(),
+                },
+                _ => {
+                    if text == "slow" {
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                    }
+                    DaemonResponse::SynthesizeResult {
+                        wav_data: text.into_bytes(),
+                        warnings: Vec::new(),
+                    }
+                }
+            };
+            let frame = postcard::to_allocvec(&response).expect("encode");
+            if framed.send(frame.into()).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    fn spawn_scripted_daemon(socket_path: &Path) {
+        let listener = UnixListener::bind(socket_path).expect("bind");
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve_scripted(stream));
+            }
+        });
+    }
+
+    async fn clients(socket_path: &Path, count: usize) -> Vec<DaemonClient> {
+        let mut clients = Vec::with_capacity(count);
+        for _ in 0..count {
+            clients.push(DaemonClient::new_at(socket_path).await.expect("connect"));
+        }
+        clients
+    }
+
+    async fn pipeline(
+        clients: &mut [DaemonClient],
+        text: &str,
+        sink: &RecordingSink,
+    ) -> (Result<usize>, Vec<(usize, bool)>) {
+        let mut reported = Vec::new();
+        let result = pipeline_segments(
+            clients.iter_mut().collect(),
+            &BarSegmenter.split(text),
+            1,
+            SynthesisOptions::default(),
+            sink,
+            &mut |segment| reported.push((segment.index, segment.duration.is_some())),
+        )
+        .await;
+        (result, reported)
+    }
+
+    #[tokio::test]
+    async fn segments_reach_the_sink_in_text_order_whatever_finishes_first() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let socket_path = dir.path().join("daemon.sock");
+        spawn_scripted_daemon(&socket_path);
+        let mut clients = clients(&socket_path, 3).await;
+        let sink = RecordingSink::default();
+
+        let (result, reported) = pipeline(&mut clients, "slow|a||b|c", &sink).await;
+
+        assert_eq!(result.expect("pipeline"), 4);
+        assert_eq!(*sink.appended.borrow(), ["slow", "a", "b", "c"]);
+        assert_eq!(
+            reported,
+            [(0, true), (1, true), (2, true), (3, true)],
+            "the empty segment is not counted"
+        );
+    }
+
+    #[tokio::test]
+    async fn skipped_repeats_are_reported_but_not_queued() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let socket_path = dir.path().join("daemon.sock");
+        spawn_scripted_daemon(&socket_path);
+        let mut clients = clients(&socket_path, 3).await;
+        let sink = RecordingSink::default();
+
+        let (result, reported) = pipeline(&mut clients, "a|repeat|b", &sink).await;
+
+        assert_eq!(result.expect("pipeline"), 2);
+        assert_eq!(*sink.appended.borrow(), ["a", "b"]);
+        assert_eq!(reported, [(0, true), (1, false), (2, true)]);
+    }
+
+    #[tokio::test]
+    async fn a_failed_segment_waits_for_the_requests_in_flight() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let socket_path = dir.path().join("daemon.sock");
+        spawn_scripted_daemon(&socket_path);
+        let mut clients = clients(&socket_path, 3).await;
+        let sink = RecordingSink::default();
+
+        let (result, reported) = pipeline(&mut clients, "fail|slow|a|b", &sink).await;
+
+        let error = result.expect_err("the first segment fails");
+        assert!(
+            format!("{error:#}").contains("scripted failure"),
+            "{error:#}"
+        );
+        assert!(sink.appended.borrow().is_empty());
+        assert!(reported.is_empty());
+        // Had a response been left unread, the next request would get it instead.
+        for client in &mut clients {
+            let wav = client
+                .synthesize("next", 1, SynthesisOptions::default())
+                .await
+                .expect("the connection is still usable");
+            assert_eq!(wav, b"next");
+        }
+    }
+
+    #[tokio::test]
+    async fn streaming_falls_back_to_one_connection() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let socket_path = dir.path().join("daemon.sock");
+        let listener = UnixListener::bind(&socket_path).expect("bind");
+        let (client, accepted) =
+            tokio::join!(DaemonClient::new_at(&socket_path), listener.accept());
+        // Later connections are refused, so there is no lookahead.
+        drop(listener);
+        tokio::spawn(serve_scripted(accepted.expect("accept").0));
+        let mut synthesizer = StreamingSynthesizer::new_with_client_and_segmenter(
+            client.expect("connect"),
+            Box::new(BarSegmenter),
+        )
+        .expect("synthesizer");
+        let sink = RecordingSink::default();
+
+        let queued = synthesizer
+            .synthesize_streaming_with_progress(
+                "slow|a|repeat|b",
+                1,
+                SynthesisOptions::default(),
+                &sink,
+                &mut |_| {},
+            )
+            .await
+            .expect("streaming");
+
+        assert_eq!(queued, 3);
+        assert_eq!(*sink.appended.borrow(), ["slow", "a", "b"]);
     }
 }
@@ -0,0 +1,183 @@
+use anyhow::{Context, Result, bail, ensure};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+
+use crate::domain::synthesis::wav::parse_wav_header;
+
+/// Audio sent in each UDP datagram; small enough to stay under common MTUs.
+const UDP_PACKET_DURATION: Duration = Duration::from_millis(20);
+
+/// Longest HTTP response head read back from a PUT sink.
+const MAX_RESPONSE_HEAD: usize = 8 * 1024;
+
+/// Network destination that receives a copy of the synthesized audio.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TeeTarget {
+    /// Raw PCM in real time, e.g. for `ffplay -f s16le -ar 24000 -ac 1 udp://...`.
+    Udp { address: String },
+    /// The whole WAV uploaded with one `PUT` once it is synthesized.
+    HttpPut { authority: String, path: String },
+}
+
+impl TeeTarget {
+    /// Parses `udp://HOST:PORT` or `http://HOST[:PORT]/PATH`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for other schemes or when the host or port is missing.
+    pub fn parse(value: &str) -> Result<Self> {
+        if let Some(address) = value.strip_prefix("udp://") {
+            ensure!(
+                address.rsplit_once(':').is_some_and(|(host, port)| {
+                    !host.is_empty() && port.parse::<u16>().is_ok()
+                }),
+                "UDP tee target needs HOST:PORT: {value}"
+            );
+            return Ok(Self::Udp {
+                address: address.to_string(),
+            });
+        }
+        if let Some(rest) = value.strip_prefix("http://") {
+            let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+            ensure!(
+                !authority.is_empty(),
+                "HTTP tee target needs a host: {value}"
+            );
+            let authority = if authority.contains(':') {
+                authority.to_string()
+            } else {
+                format!("{authority}:80")
+            };
+            return Ok(Self::HttpPut {
+                authority,
+                path: if path.is_empty() { "/" } else { path }.to_string(),
+            });
+        }
+        bail!("Unsupported tee target '{value}' (expected udp://HOST:PORT or http://HOST/PATH)")
+    }
+}
+
+/// Sends `wav` to `target`, pacing UDP packets at playback speed.
+///
+/// # Errors
+///
+/// Returns an error if the audio is not PCM WAV, the destination cannot be reached,
+/// or an HTTP sink answers with a non-success status.
+pub async fn send_to_tee(target: &TeeTarget, wav: &[u8]) -> Result<()> {
+    match target {
+        TeeTarget::Udp { address } => stream_udp(address, wav)
+            .await
+            .with_context(|| format!("Failed to stream audio to udp://{address}")),
+        TeeTarget::HttpPut { authority, path } => put_http(authority, path, wav)
+            .await
+            .with_context(|| format!("Failed to upload audio to http://{authority}{path}")),
+    }
+}
+
+async fn stream_udp(address: &str, wav: &[u8]) -> Result<()> {
+    let header = parse_wav_header(wav)?;
+    let frame_bytes = usize::from(header.channels) * usize::from(header.bits_per_sample / 8);
+    ensure!(frame_bytes > 0, "WAV declares an empty sample format");
+    let frames_per_packet =
+        (header.sample_rate as usize * UDP_PACKET_DURATION.as_millis() as usize / 1000).max(1);
+    let pcm = &wav[header.data_offset..header.data_offset + header.data_size];
+
+    let socket = UdpSocket::bind(if address.starts_with('[') {
+        "[::]:0"
+    } else {
+        "0.0.0.0:0"
+    })
+    .await?;
+    socket.connect(address).await?;
+    let packet_duration =
+        Duration::from_secs_f64(frames_per_packet as f64 / f64::from(header.sample_rate.max(1)));
+    let mut ticks = tokio::time::interval(packet_duration);
+    for packet in pcm.chunks(frames_per_packet * frame_bytes) {
+        ticks.tick().await;
+        socket.send(packet).await?;
+    }
+    Ok(())
+}
+
+async fn put_http(authority: &str, path: &str, wav: &[u8]) -> Result<()> {
+    let mut stream = TcpStream::connect(authority).await?;
+    let host = authority
+        .rsplit_once(':')
+        .map_or(authority, |(host, _)| host);
+    let head = format!(
+        "PUT {path} HTTP/1.1\r\nHost: {host}\r\nContent-Type: audio/wav\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        wav.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(wav).await?;
+    stream.flush().await?;
+
+    let mut response = Vec::new();
+    (&mut stream)
+        .take(MAX_RESPONSE_HEAD as u64)
+        .read_to_end(&mut response)
+        .await?;
+    let status_line = response
+        .split(|&byte| byte == b'\n')
+        .next()
+        .map(|line| String::from_utf8_lossy(line).trim().to_string())
+        .unwrap_or_default();
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse::<u16>().ok())
+        .with_context(|| format!("Malformed HTTP response: '{status_line}'"))?;
+    ensure!(
+        (200..300).contains(&status),
+        "Sink answered '{status_line}'"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::synthesis::wav::make_wav;
+
+    #[test]
+    fn parses_udp_and_http_targets() {
+        assert_eq!(
+            TeeTarget::parse("udp://127.0.0.1:5004").expect("udp"),
+            TeeTarget::Udp {
+                address: "127.0.0.1:5004".into()
+            }
+        );
+        assert_eq!(
+            TeeTarget::parse("http://radio.local/live.wav").expect("http"),
+            TeeTarget::HttpPut {
+                authority: "radio.local:80".into(),
+                path: "/live.wav".into()
+            }
+        );
+        assert!(TeeTarget::parse("udp://127.0.0.1").is_err());
+        assert!(TeeTarget::parse("https://radio.local/live").is_err());
+    }
+
+    #[tokio::test]
+    async fn udp_sink_receives_all_pcm_in_packets() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").await.expect("bind");
+        let target = TeeTarget::Udp {
+            address: receiver.local_addr().expect("addr").to_string(),
+        };
+        // 50 ms of 16-bit mono at 1 kHz: three packets of up to 20 frames.
+        let pcm = (0..100).map(|byte| byte as u8).collect::<Vec<_>>();
+        send_to_tee(&target, &make_wav(&pcm, 1, 1000, 16))
+            .await
+            .expect("stream");
+
+        let mut received = Vec::new();
+        let mut buffer = [0; 1024];
+        while received.len() < pcm.len() {
+            let length = receiver.recv(&mut buffer).await.expect("packet");
+            assert!(length <= 40);
+            received.extend_from_slice(&buffer[..length]);
+        }
+        assert_eq!(received, pcm);
+    }
+}
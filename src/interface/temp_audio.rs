@@ -0,0 +1,108 @@
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tempfile::{Builder, NamedTempFile};
+
+use crate::infrastructure::paths::get_temp_dir;
+
+/// Temporary files alive in this process, removed by [`remove_live_temp_files`].
+///
+/// Dropping a [`TempWavFile`] already deletes it, but destructors do not run
+/// when a signal terminates the process, so the CLI's signal handler sweeps this list.
+static LIVE_TEMP_FILES: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// A WAV written for an external program, deleted when dropped.
+///
+/// The file is created with `O_EXCL` under a random name and mode `0600`, so
+/// another user on the machine cannot pre-create, replace, or symlink it.
+pub struct TempWavFile {
+    file: NamedTempFile,
+}
+
+impl TempWavFile {
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        self.file.path()
+    }
+}
+
+impl Drop for TempWavFile {
+    fn drop(&mut self) {
+        let path = self.file.path();
+        if let Ok(mut live) = LIVE_TEMP_FILES.lock() {
+            live.retain(|live_path| live_path != path);
+        }
+    }
+}
+
+/// Writes `wav_data` to a fresh file in [`get_temp_dir`].
+///
+/// # Errors
+///
+/// Returns an error if the temp directory is missing or the file cannot be written.
+pub fn create_temp_wav_file(wav_data: &[u8]) -> Result<TempWavFile> {
+    create_temp_wav_file_in(&get_temp_dir(), wav_data)
+}
+
+fn create_temp_wav_file_in(dir: &Path, wav_data: &[u8]) -> Result<TempWavFile> {
+    let mut temp = Builder::new()
+        .prefix("voicevox_")
+        .suffix(".wav")
+        .tempfile_in(dir)
+        .with_context(|| {
+            format!(
+                "Failed to create temporary audio file in {} (set {} to change it)",
+                dir.display(),
+                crate::config::ENV_VOICEVOX_TMPDIR
+            )
+        })?;
+
+    temp.write_all(wav_data)
+        .context("Failed to write temporary audio file")?;
+    temp.flush()
+        .context("Failed to flush temporary audio file")?;
+
+    if let Ok(mut live) = LIVE_TEMP_FILES.lock() {
+        live.push(temp.path().to_owned());
+    }
+    Ok(TempWavFile { file: temp })
+}
+
+/// Deletes every temporary audio file this process still holds.
+pub fn remove_live_temp_files() {
+    let paths = LIVE_TEMP_FILES
+        .lock()
+        .map(|mut live| std::mem::take(&mut *live))
+        .unwrap_or_default();
+    for path in paths {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn temp_files_are_tracked_until_dropped_or_swept() {
+        let dir = tempfile::tempdir().expect("temp dir");
+
+        let dropped = create_temp_wav_file_in(dir.path(), b"RIFF").expect("temp wav");
+        let dropped_path = dropped.path().to_owned();
+        assert!(dropped_path.starts_with(dir.path()));
+        assert_eq!(std::fs::read(&dropped_path).expect("read"), b"RIFF");
+        drop(dropped);
+        assert!(!dropped_path.exists());
+
+        let swept = create_temp_wav_file_in(dir.path(), b"RIFF").expect("temp wav");
+        assert!(
+            LIVE_TEMP_FILES
+                .lock()
+                .expect("lock")
+                .contains(&swept.path().to_owned())
+        );
+        remove_live_temp_files();
+        assert!(!swept.path().exists());
+    }
+}